@@ -0,0 +1,118 @@
+//! Runs the hot execute paths of the optimized contracts in cosmwasm-vm and prints the gas
+//! they use as JSON, to compare against a previous run when the fee logic changes.
+//!
+//! Build the artifacts with `build-all-terra-contracts.sh` first, then run
+//! `cargo run -p luart-benchmarks [artifacts dir] > bench_output.txt`.
+//!
+//! The token has no batch transfer and the marketplace lives outside this repository, so
+//! only the token paths are covered here.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use cosmwasm_std::{Binary, ContractResult, Decimal, Response, to_binary, Uint128};
+use cosmwasm_vm::Instance;
+use cosmwasm_vm::testing::{
+    execute, instantiate, mock_env, mock_info, mock_instance_with_gas_limit, MockApi, MockQuerier, MockStorage,
+};
+use cw20::Cw20Coin;
+use luart_token::msg::{ExecuteMsg, InstantiateMsg, SwapFeeConfigResponse};
+use serde::Serialize;
+use terraswap::pair::Cw20HookMsg;
+
+const GAS_LIMIT: u64 = 10_000_000_000_000;
+const OWNER: &str = "owner";
+const SPENDER: &str = "spender";
+const RECIPIENT: &str = "recipient";
+const FEE_RECEIVER: &str = "fee_receiver";
+const DEX: &str = "dex_contract";
+
+type MockInstance = Instance<MockApi, MockStorage, MockQuerier>;
+
+#[derive(Serialize)]
+struct Benchmark {
+    contract: String,
+    name: String,
+    gas_used: u64,
+}
+
+fn main() {
+    let artifacts = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("artifacts"));
+    let token_wasm = fs::read(artifacts.join("luart_token.wasm")).expect("luart_token.wasm is not built");
+
+    let benchmarks = vec![
+        bench_token(&token_wasm, "transfer", &[], transfer_msg()),
+        bench_token(&token_wasm, "send_without_fee", &[], send_msg(Binary::default())),
+        bench_token(&token_wasm, "send_with_fee", &[], send_msg(swap_msg())),
+        bench_token(&token_wasm, "send_from_with_fee", &[allowance_msg()], send_from_msg(swap_msg())),
+    ];
+    println!("{}", serde_json::to_string_pretty(&benchmarks).unwrap());
+}
+
+/// Instantiates a fresh token, executes the setup messages of the owner and measures the
+/// gas of the benchmarked message alone. The messages sent from an allowance are executed
+/// by the spender.
+fn bench_token(wasm: &[u8], name: &str, setup: &[ExecuteMsg], msg: ExecuteMsg) -> Benchmark {
+    let mut instance = mock_instance_with_gas_limit(wasm, GAS_LIMIT);
+    let info = mock_info(OWNER, &[]);
+    let res: ContractResult<Response> = instantiate(&mut instance, mock_env(), info, token_instantiate_msg());
+    res.unwrap();
+    for setup_msg in setup {
+        execute_token(&mut instance, OWNER, setup_msg.clone());
+    }
+
+    let sender = match msg {
+        ExecuteMsg::SendFrom { .. } => SPENDER,
+        _ => OWNER,
+    };
+    let gas_before = instance.get_gas_left();
+    execute_token(&mut instance, sender, msg);
+    Benchmark {
+        contract: "luart_token".to_string(),
+        name: name.to_string(),
+        gas_used: gas_before - instance.get_gas_left(),
+    }
+}
+
+fn execute_token(instance: &mut MockInstance, sender: &str, msg: ExecuteMsg) {
+    let res: ContractResult<Response> = execute(instance, mock_env(), mock_info(sender, &[]), msg);
+    res.unwrap();
+}
+
+fn token_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Luart".to_string(),
+        symbol: "LUART".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: OWNER.to_string(), amount: Uint128::new(1_000_000_000) }],
+        mint: None,
+        swap_fee_config: Some(SwapFeeConfigResponse {
+            fee_admin: OWNER.to_string(),
+            enable_swap_fee: true,
+            swap_percent_fee: Decimal::percent(200),
+            fee_receiver: FEE_RECEIVER.to_string(),
+        }),
+        pauser: None,
+    }
+}
+
+fn swap_msg() -> Binary {
+    to_binary(&Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap()
+}
+
+fn transfer_msg() -> ExecuteMsg {
+    ExecuteMsg::Transfer { recipient: RECIPIENT.to_string(), amount: Uint128::new(1_000_000) }
+}
+
+fn send_msg(msg: Binary) -> ExecuteMsg {
+    ExecuteMsg::Send { contract: DEX.to_string(), amount: Uint128::new(1_000_000), msg }
+}
+
+fn allowance_msg() -> ExecuteMsg {
+    ExecuteMsg::IncreaseAllowance { spender: SPENDER.to_string(), amount: Uint128::new(1_000_000), expires: None }
+}
+
+fn send_from_msg(msg: Binary) -> ExecuteMsg {
+    ExecuteMsg::SendFrom { owner: OWNER.to_string(), contract: DEX.to_string(), amount: Uint128::new(1_000_000), msg }
+}
@@ -0,0 +1,225 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use cw_storage_plus::Bound;
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ContentResponse, ContentsResponse, ExecuteMsg, HasAccessResponse, InstantiateMsg, MigrateMsg,
+    QueryMsg,
+};
+use crate::state::{Config, CONFIG, ConfigOwner, CONTENTS, Requirement};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-access";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let staking = match msg.staking {
+        Some(staking) => Some(deps.api.addr_validate(&staking)?),
+        None => None,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        token: deps.api.addr_validate(&msg.token)?,
+        staking,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetContent { key, requirements } => set_content(deps, info, key, requirements),
+        ExecuteMsg::RemoveContent { key } => remove_content(deps, info, key),
+        ExecuteMsg::UpdateConfig { staking } => update_config(deps, info, staking),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn set_content(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+    requirements: Vec<Requirement>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    if requirements.is_empty() {
+        return Err(ContractError::NoRequirements {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let requirements = requirements
+        .into_iter()
+        .map(|requirement| match requirement {
+            Requirement::Staked { .. } if config.staking.is_none() => Err(ContractError::StakingNotConfigured {}),
+            Requirement::Nft { collection } => Ok(Requirement::Nft {
+                collection: deps.api.addr_validate(collection.as_str())?,
+            }),
+            requirement => Ok(requirement),
+        })
+        .collect::<Result<Vec<Requirement>, ContractError>>()?;
+    CONTENTS.save(deps.storage, &key, &requirements)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_content")
+        .add_attribute("key", key))
+}
+
+pub fn remove_content(deps: DepsMut, info: MessageInfo, key: String) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    CONTENTS.load(deps.storage, &key)?;
+    CONTENTS.remove(deps.storage, &key);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_content")
+        .add_attribute("key", key))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking: Option<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(staking) = staking {
+        config.staking = Some(deps.api.addr_validate(&staking)?);
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Content { key } => to_binary(&ContentResponse {
+            requirements: CONTENTS.load(deps.storage, &key)?,
+            key,
+        }),
+        QueryMsg::Contents { start_after, limit } => to_binary(&query_contents(deps, start_after, limit)?),
+        QueryMsg::HasAccess { address, key } => to_binary(&query_has_access(deps, address, key)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        token: config.token.to_string(),
+        staking: config.staking.map(|s| s.to_string()),
+    })
+}
+
+pub fn query_contents(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ContentsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let contents: StdResult<Vec<ContentResponse>> = CONTENTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, requirements) = item?;
+            Ok(ContentResponse { key: String::from_utf8(key)?, requirements })
+        })
+        .collect();
+
+    Ok(ContentsResponse { contents: contents? })
+}
+
+/// The requirements are checked in order, the contracts of the requirements after the first
+/// one met are not queried
+pub fn query_has_access(deps: Deps, address: String, key: String) -> StdResult<HasAccessResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let requirements = CONTENTS.load(deps.storage, &key)?;
+
+    for requirement in requirements.iter() {
+        if meets_requirement(deps, &config, requirement, &address)? {
+            return Ok(HasAccessResponse { has_access: true });
+        }
+    }
+    Ok(HasAccessResponse { has_access: false })
+}
+
+fn meets_requirement(deps: Deps, config: &Config, requirement: &Requirement, address: &Addr) -> StdResult<bool> {
+    match requirement {
+        Requirement::Balance { min_amount } => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                &config.token,
+                &Cw20QueryMsg::Balance { address: address.to_string() },
+            )?;
+            Ok(res.balance >= *min_amount)
+        }
+        Requirement::Staked { min_amount } => {
+            let staking = config
+                .staking
+                .as_ref()
+                .ok_or_else(|| StdError::generic_err("The staking contract is not configured"))?;
+            let res: StakerInfoResponse = deps.querier.query_wasm_smart(
+                staking,
+                &StakingQueryMsg::StakerInfo { staker: address.to_string() },
+            )?;
+            Ok(res.bond_amount >= *min_amount)
+        }
+        Requirement::Nft { collection } => {
+            let res: TokensResponse = deps.querier.query_wasm_smart(
+                collection,
+                &Cw721QueryMsg::Tokens { owner: address.to_string(), start_after: None, limit: Some(1) },
+            )?;
+            Ok(!res.tokens.is_empty())
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use cosmwasm_std::{OverflowError, StdError};
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("A content needs at least one requirement")]
+    NoRequirements {},
+
+    #[error("The staking contract is not configured")]
+    StakingNotConfigured {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
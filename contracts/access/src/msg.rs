@@ -0,0 +1,87 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Requirement;
+
+/// This structure describes the parameters used for creating an access contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub token: String,
+    pub staking: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Gates the content on the requirements, replacing the previous ones.
+    /// Only the owner can execute it.
+    SetContent {
+        key: String,
+        requirements: Vec<Requirement>,
+    },
+    /// Removes the content. Only the owner can execute it.
+    RemoveContent { key: String },
+    /// Only the owner can update the configuration
+    UpdateConfig { staking: Option<String> },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the requirements of the content.
+    /// Return type: ContentResponse.
+    Content { key: String },
+    /// Returns the contents ordered by key.
+    /// Return type: ContentsResponse.
+    Contents {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether the address meets any requirement of the content, reading its LUART
+    /// balance, its stake and its NFTs from their contracts.
+    /// Return type: HasAccessResponse.
+    HasAccess { address: String, key: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub token: String,
+    pub staking: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContentResponse {
+    pub key: String,
+    pub requirements: Vec<Requirement>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContentsResponse {
+    pub contents: Vec<ContentResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HasAccessResponse {
+    pub has_access: bool,
+}
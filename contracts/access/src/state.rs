@@ -0,0 +1,49 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who manages the contents, unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token
+    pub token: Addr,
+    /// The staking contract bonding the LUART, required by the staked requirements
+    pub staking: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Requirement {
+    /// The address holds at least `min_amount` LUART
+    Balance { min_amount: Uint128 },
+    /// The address has bonded at least `min_amount` LUART in the staking contract
+    Staked { min_amount: Uint128 },
+    /// The address owns at least one NFT of the collection, e.g. a badge
+    Nft { collection: Addr },
+}
+
+/// The requirements of each content key, an address meeting any of them has access
+pub const CONTENTS: Map<&str, Vec<Requirement>> = Map::new("contents");
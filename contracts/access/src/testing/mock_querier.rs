@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+
+pub const MOCK_TOKEN: &str = "mock_token";
+pub const MOCK_STAKING: &str = "mock_staking";
+pub const MOCK_BADGES: &str = "mock_badges";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the LUART balance, staking and badge queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    balances: HashMap<String, Uint128>,
+    stakes: HashMap<String, Uint128>,
+    badges: HashMap<String, Vec<String>>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match contract_addr.as_str() {
+                    MOCK_TOKEN => match from_binary(msg) {
+                        Ok(Cw20QueryMsg::Balance { address }) => {
+                            let balance = self.balances.get(&address).copied().unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse { balance })))
+                        }
+                        _ => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "Unsupported token query".to_string(),
+                            request: msg.clone(),
+                        }),
+                    },
+                    MOCK_STAKING => match from_binary(msg) {
+                        Ok(StakingQueryMsg::StakerInfo { staker }) => {
+                            let bond_amount = self.stakes.get(&staker).copied().unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&StakerInfoResponse {
+                                staker,
+                                bond_amount,
+                            })))
+                        }
+                        _ => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "Unsupported staking query".to_string(),
+                            request: msg.clone(),
+                        }),
+                    },
+                    MOCK_BADGES => match from_binary(msg) {
+                        Ok(Cw721QueryMsg::Tokens { owner, .. }) => {
+                            let tokens = self.badges.get(&owner).cloned().unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&TokensResponse { tokens })))
+                        }
+                        _ => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "Unsupported badges query".to_string(),
+                            request: msg.clone(),
+                        }),
+                    },
+                    _ => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            balances: HashMap::new(),
+            stakes: HashMap::new(),
+            badges: HashMap::new(),
+        }
+    }
+
+    // configure the LUART balance of an address
+    pub fn with_balance(&mut self, address: &str, balance: u128) {
+        self.balances.insert(address.to_string(), Uint128::new(balance));
+    }
+
+    // configure the bonded amount of an address
+    pub fn with_stake(&mut self, address: &str, amount: u128) {
+        self.stakes.insert(address.to_string(), Uint128::new(amount));
+    }
+
+    // configure the badges owned by an address
+    pub fn with_badges(&mut self, address: &str, token_ids: &[&str]) {
+        self.badges.insert(address.to_string(), token_ids.iter().map(|id| id.to_string()).collect());
+    }
+}
@@ -0,0 +1,107 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, from_binary, Response, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ContentResponse, ContentsResponse, ExecuteMsg, HasAccessResponse, InstantiateMsg, QueryMsg};
+use crate::state::Requirement;
+use crate::testing::mock_querier::{MOCK_BADGES, MOCK_STAKING, MOCK_TOKEN, mock_dependencies};
+
+const OWNER: &str = "mock_owner";
+const HOLDER: &str = "mock_holder";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+    staking: Option<&str>,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        token: MOCK_TOKEN.to_string(),
+        staking: staking.map(|s| s.to_string()),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn has_access(deps: Deps, address: &str, key: &str) -> bool {
+    let msg = QueryMsg::HasAccess { address: address.to_string(), key: key.to_string() };
+    from_binary::<HasAccessResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().has_access
+}
+
+#[test]
+fn test_set_content() {
+    let mut deps = mock_dependencies(&[]);
+    default_instantiate(deps.as_mut(), mock_env(), None);
+
+    let set_content = |requirements: Vec<Requirement>| ExecuteMsg::SetContent {
+        key: "gallery".to_string(),
+        requirements,
+    };
+    let balance = Requirement::Balance { min_amount: Uint128::new(1_000) };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(HOLDER, &[]), set_content(vec![balance.clone()]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), set_content(vec![])).unwrap_err();
+    assert_eq!(err, ContractError::NoRequirements {});
+    let staked = Requirement::Staked { min_amount: Uint128::new(1_000) };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), set_content(vec![staked.clone()]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::StakingNotConfigured {});
+
+    let update_config = ExecuteMsg::UpdateConfig { staking: Some(MOCK_STAKING.to_string()) };
+    execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), update_config).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), set_content(vec![balance.clone(), staked.clone()]))
+        .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Contents { start_after: None, limit: None }).unwrap();
+    assert_eq!(from_binary::<ContentsResponse>(&res).unwrap(), ContentsResponse {
+        contents: vec![ContentResponse { key: "gallery".to_string(), requirements: vec![balance, staked] }],
+    });
+
+    let remove = ExecuteMsg::RemoveContent { key: "gallery".to_string() };
+    execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), remove.clone()).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), remove).unwrap_err();
+    query(deps.as_ref(), mock_env(), QueryMsg::Content { key: "gallery".to_string() }).unwrap_err();
+}
+
+#[test]
+fn test_has_access() {
+    let mut deps = mock_dependencies(&[]);
+    default_instantiate(deps.as_mut(), mock_env(), Some(MOCK_STAKING));
+
+    let gated = |key: &str, requirements: Vec<Requirement>| ExecuteMsg::SetContent {
+        key: key.to_string(),
+        requirements,
+    };
+    let contents = vec![
+        gated("holders", vec![Requirement::Balance { min_amount: Uint128::new(1_000) }]),
+        gated("stakers", vec![Requirement::Staked { min_amount: Uint128::new(500) }]),
+        gated("members", vec![
+            Requirement::Nft { collection: Addr::unchecked(MOCK_BADGES) },
+            Requirement::Staked { min_amount: Uint128::new(10_000) },
+        ]),
+    ];
+    for content in contents {
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), content).unwrap();
+    }
+
+    assert!(!has_access(deps.as_ref(), HOLDER, "holders"));
+    assert!(!has_access(deps.as_ref(), HOLDER, "stakers"));
+    assert!(!has_access(deps.as_ref(), HOLDER, "members"));
+
+    deps.querier.with_balance(HOLDER, 1_000);
+    deps.querier.with_stake(HOLDER, 499);
+    assert!(has_access(deps.as_ref(), HOLDER, "holders"));
+    assert!(!has_access(deps.as_ref(), HOLDER, "stakers"));
+
+    // Any requirement met grants the access
+    deps.querier.with_stake(HOLDER, 10_000);
+    assert!(has_access(deps.as_ref(), HOLDER, "stakers"));
+    assert!(has_access(deps.as_ref(), HOLDER, "members"));
+    deps.querier.with_stake(HOLDER, 0);
+    deps.querier.with_badges(HOLDER, &["1"]);
+    assert!(has_access(deps.as_ref(), HOLDER, "members"));
+
+    // Unknown contents are not found rather than denied
+    let msg = QueryMsg::HasAccess { address: HOLDER.to_string(), key: "unknown".to_string() };
+    query(deps.as_ref(), mock_env(), msg).unwrap_err();
+}
@@ -0,0 +1,502 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Expiration};
+use luart_protocol::marketplace::{ListingCountResponse, QueryMsg as MarketplaceQueryMsg};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use sha2::Digest;
+
+use crate::error::ContractError;
+use crate::msg::{
+    BoostMultiplierResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse,
+    LatestStageResponse, MigrateMsg, QueryMsg, StageResponse, UnclaimedAmountResponse,
+};
+use crate::state::{
+    Boost, BoostCondition, CLAIMS, ClawbackDestination, Config, CONFIG, ConfigOwner, LATEST_STAGE,
+    Stage, STAGES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-airdrop";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let treasury = msg.treasury.map(|t| deps.api.addr_validate(&t)).transpose()?;
+
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        cw20_token_address: deps.api.addr_validate(&msg.cw20_token_address)?,
+        treasury,
+    })?;
+    LATEST_STAGE.save(deps.storage, &0u8)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig { treasury } => update_config(deps, info, treasury),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+        ExecuteMsg::RegisterMerkleRoot {
+            merkle_root,
+            expiration,
+            total_amount,
+        } => register_merkle_root(deps, info, merkle_root, expiration, total_amount),
+        ExecuteMsg::Claim {
+            stage,
+            amount,
+            proof,
+        } => claim(deps, env, info, stage, amount, proof),
+        ExecuteMsg::UpdateStageBoosts {
+            stage,
+            boosts,
+            boost_amount,
+        } => update_stage_boosts(deps, env, info, stage, boosts, boost_amount),
+        ExecuteMsg::Withdraw { stage, address } => withdraw(deps, env, info, stage, address),
+        ExecuteMsg::Clawback { stage, destination } => clawback(deps, env, info, stage, destination),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = Some(deps.api.addr_validate(&treasury)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn register_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    merkle_root: String,
+    expiration: Option<Expiration>,
+    total_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // check merkle root length
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    if total_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let stage = LATEST_STAGE.load(deps.storage)?
+        .checked_add(1)
+        .ok_or(ContractError::InvalidInput {})?;
+
+    STAGES.save(deps.storage, stage.into(), &Stage {
+        merkle_root: merkle_root.clone(),
+        expiration: expiration.unwrap_or(Expiration::Never {}),
+        total_amount,
+        claimed_amount: Uint128::zero(),
+        withdrawn_amount: Uint128::zero(),
+        boosts: vec![],
+        boost_amount: Uint128::zero(),
+        boost_claimed_amount: Uint128::zero(),
+        rolled_over_amount: Uint128::zero(),
+        rolled_in_amount: Uint128::zero(),
+    })?;
+    LATEST_STAGE.save(deps.storage, &stage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_merkle_root")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("merkle_root", merkle_root)
+        .add_attribute("total_amount", total_amount))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut stage_info = STAGES.load(deps.storage, stage.into())?;
+    if stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageExpired { stage });
+    }
+
+    // verify not claimed
+    if CLAIMS.may_load(deps.storage, (&info.sender, stage.into()))?.unwrap_or(false) {
+        return Err(ContractError::Claimed {});
+    }
+
+    verify_merkle_proof(&stage_info.merkle_root, info.sender.as_str(), amount, proof)?;
+
+    stage_info.claimed_amount = stage_info.claimed_amount.checked_add(amount)?;
+    if stage_info.claimed_amount > stage_info.total_amount {
+        return Err(ContractError::StageTotalExceeded {});
+    }
+
+    // The boost is paid from the stage boost reserve as long as it lasts
+    let multiplier = boost_multiplier(deps.as_ref(), &stage_info.boosts, &info.sender)?;
+    let boost_amount = (amount * multiplier)
+        .saturating_sub(amount)
+        .min(stage_info.remaining_boost_amount());
+    stage_info.boost_claimed_amount = stage_info.boost_claimed_amount.checked_add(boost_amount)?;
+
+    STAGES.save(deps.storage, stage.into(), &stage_info)?;
+    CLAIMS.save(deps.storage, (&info.sender, stage.into()), &true)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let transfer_amount = amount.checked_add(boost_amount)?;
+    let transfer_msg = token_transfer_msg(&config, info.sender.to_string(), transfer_amount)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "claim")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("boost_amount", boost_amount))
+}
+
+pub fn update_stage_boosts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    boosts: Vec<Boost>,
+    boost_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut stage_info = STAGES.load(deps.storage, stage.into())?;
+    if stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageExpired { stage });
+    }
+    if boost_amount < stage_info.boost_claimed_amount {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    stage_info.boosts = boosts
+        .into_iter()
+        .map(|boost| {
+            if boost.multiplier < Decimal::one() {
+                return Err(ContractError::InvalidBoostMultiplier {});
+            }
+            let condition = match boost.condition {
+                BoostCondition::Staked { contract, min_amount } => BoostCondition::Staked {
+                    contract: deps.api.addr_validate(contract.as_str())?,
+                    min_amount,
+                },
+                BoostCondition::ListedNft { contract } => BoostCondition::ListedNft {
+                    contract: deps.api.addr_validate(contract.as_str())?,
+                },
+            };
+            Ok(Boost { condition, multiplier: boost.multiplier })
+        })
+        .collect::<Result<Vec<Boost>, ContractError>>()?;
+    stage_info.boost_amount = boost_amount;
+    STAGES.save(deps.storage, stage.into(), &stage_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_stage_boosts")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("boosts", stage_info.boosts.len().to_string())
+        .add_attribute("boost_amount", boost_amount))
+}
+
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut stage_info = STAGES.load(deps.storage, stage.into())?;
+    if !stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageNotExpired { stage });
+    }
+
+    let amount = stage_info.unclaimed_amount();
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let recipient = deps.api.addr_validate(&address)?;
+    stage_info.withdrawn_amount = stage_info.withdrawn_amount.checked_add(amount)?;
+    STAGES.save(deps.storage, stage.into(), &stage_info)?;
+
+    Ok(Response::new()
+        .add_message(token_transfer_msg(&config, recipient.to_string(), amount)?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+pub fn clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    destination: ClawbackDestination,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut stage_info = STAGES.load(deps.storage, stage.into())?;
+    if !stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageNotExpired { stage });
+    }
+
+    let amount = stage_info.unclaimed_amount();
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "clawback")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("amount", amount);
+
+    match destination {
+        ClawbackDestination::Treasury {} => {
+            let treasury = config.treasury.clone().ok_or(ContractError::TreasuryNotSet {})?;
+            stage_info.withdrawn_amount = stage_info.withdrawn_amount.checked_add(amount)?;
+            response = response
+                .add_message(token_transfer_msg(&config, treasury.to_string(), amount)?)
+                .add_attribute("destination", "treasury")
+                .add_attribute("recipient", treasury);
+        }
+        ClawbackDestination::Stage { stage: target } => {
+            if !config.is_owner(info.sender.as_str()) {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            // The tokens stay in the contract and raise the claimable total of the later stage
+            let mut target_info = STAGES
+                .may_load(deps.storage, target.into())?
+                .ok_or(ContractError::InvalidRolloverStage {})?;
+            if target <= stage || target_info.expiration.is_expired(&env.block) {
+                return Err(ContractError::InvalidRolloverStage {});
+            }
+            target_info.total_amount = target_info.total_amount.checked_add(amount)?;
+            target_info.rolled_in_amount = target_info.rolled_in_amount.checked_add(amount)?;
+            STAGES.save(deps.storage, target.into(), &target_info)?;
+
+            stage_info.rolled_over_amount = stage_info.rolled_over_amount.checked_add(amount)?;
+            response = response
+                .add_attribute("destination", "stage")
+                .add_attribute("target_stage", target.to_string());
+        }
+    }
+    STAGES.save(deps.storage, stage.into(), &stage_info)?;
+
+    Ok(response)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Stage { stage } => to_binary(&query_stage(deps, stage)?),
+        QueryMsg::LatestStage {} => to_binary(&query_latest_stage(deps)?),
+        QueryMsg::IsClaimed { stage, address } => {
+            to_binary(&query_is_claimed(deps, stage, address)?)
+        }
+        QueryMsg::BoostMultiplier { stage, address } => {
+            to_binary(&query_boost_multiplier(deps, stage, address)?)
+        }
+        QueryMsg::UnclaimedAmount { stage } => {
+            to_binary(&query_unclaimed_amount(deps, env, stage)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        cw20_token_address: config.cw20_token_address.to_string(),
+        treasury: config.treasury.map(|t| t.to_string()),
+    })
+}
+
+pub fn query_stage(deps: Deps, stage: u8) -> StdResult<StageResponse> {
+    let stage_info = STAGES.load(deps.storage, stage.into())?;
+    Ok(StageResponse {
+        stage,
+        merkle_root: stage_info.merkle_root,
+        expiration: stage_info.expiration,
+        total_amount: stage_info.total_amount,
+        claimed_amount: stage_info.claimed_amount,
+        withdrawn_amount: stage_info.withdrawn_amount,
+        boosts: stage_info.boosts,
+        boost_amount: stage_info.boost_amount,
+        boost_claimed_amount: stage_info.boost_claimed_amount,
+        rolled_over_amount: stage_info.rolled_over_amount,
+        rolled_in_amount: stage_info.rolled_in_amount,
+    })
+}
+
+pub fn query_latest_stage(deps: Deps) -> StdResult<LatestStageResponse> {
+    let latest_stage = LATEST_STAGE.load(deps.storage)?;
+    Ok(LatestStageResponse { latest_stage })
+}
+
+pub fn query_is_claimed(deps: Deps, stage: u8, address: String) -> StdResult<IsClaimedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let is_claimed = CLAIMS.may_load(deps.storage, (&address, stage.into()))?.unwrap_or(false);
+    Ok(IsClaimedResponse { is_claimed })
+}
+
+pub fn query_boost_multiplier(
+    deps: Deps,
+    stage: u8,
+    address: String,
+) -> StdResult<BoostMultiplierResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let stage_info = STAGES.load(deps.storage, stage.into())?;
+    let multiplier = boost_multiplier(deps, &stage_info.boosts, &address)?;
+    Ok(BoostMultiplierResponse { multiplier })
+}
+
+pub fn query_unclaimed_amount(
+    deps: Deps,
+    env: Env,
+    stage: u8,
+) -> StdResult<UnclaimedAmountResponse> {
+    let stage_info = STAGES.load(deps.storage, stage.into())?;
+    Ok(UnclaimedAmountResponse {
+        stage,
+        unclaimed_amount: stage_info.unclaimed_amount(),
+        expired: stage_info.expiration.is_expired(&env.block),
+    })
+}
+
+/// Returns the boost multiplier of the address, the bonus of every boost whose condition
+/// the address meets adds up: two met boosts of 1.5 and 1.2 give 1.7
+fn boost_multiplier(deps: Deps, boosts: &[Boost], address: &Addr) -> StdResult<Decimal> {
+    let mut multiplier = Decimal::one();
+    for boost in boosts.iter() {
+        let met = match &boost.condition {
+            BoostCondition::Staked { contract, min_amount } => {
+                let res: StakerInfoResponse = deps.querier.query_wasm_smart(
+                    contract,
+                    &StakingQueryMsg::StakerInfo { staker: address.to_string() },
+                )?;
+                !res.bond_amount.is_zero() && res.bond_amount >= *min_amount
+            }
+            BoostCondition::ListedNft { contract } => {
+                let res: ListingCountResponse = deps.querier.query_wasm_smart(
+                    contract,
+                    &MarketplaceQueryMsg::ListingCount { seller: address.to_string() },
+                )?;
+                res.count > 0
+            }
+        };
+        if met {
+            multiplier = multiplier + (boost.multiplier - Decimal::one());
+        }
+    }
+    Ok(multiplier)
+}
+
+/// Checks that `sha256(address + amount)` is a leaf of the tree with the given root.
+/// Sibling hashes are sorted before hashing so the proof does not need to carry positions.
+fn verify_merkle_proof(
+    merkle_root: &str,
+    address: &str,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<(), ContractError> {
+    let leaf = format!("{}{}", address, amount);
+    let hash: [u8; 32] = sha2::Sha256::digest(leaf.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| ContractError::WrongLength {})?;
+
+    let hash = proof.into_iter().try_fold(hash, |hash, p| {
+        let mut proof_buf = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        sha2::Sha256::digest(&hashes.concat())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::WrongLength {})
+    })?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    if root_buf != hash {
+        return Err(ContractError::VerificationFailed {});
+    }
+
+    Ok(())
+}
+
+fn token_transfer_msg(config: &Config, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.cw20_token_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
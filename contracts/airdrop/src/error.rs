@@ -0,0 +1,61 @@
+use cosmwasm_std::{OverflowError, StdError};
+use hex::FromHexError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Invalid input")]
+    InvalidInput {},
+
+    #[error("Boost multipliers must be at least 1")]
+    InvalidBoostMultiplier {},
+
+    #[error("Already claimed")]
+    Claimed {},
+
+    #[error("Wrong length")]
+    WrongLength {},
+
+    #[error("Verification failed")]
+    VerificationFailed {},
+
+    #[error("Stage {stage} claim period has expired")]
+    StageExpired { stage: u8 },
+
+    #[error("Stage {stage} claim period has not expired yet")]
+    StageNotExpired { stage: u8 },
+
+    #[error("Claimed amount exceeds the stage total")]
+    StageTotalExceeded {},
+
+    #[error("The treasury is not set")]
+    TreasuryNotSet {},
+
+    #[error("Unclaimed tokens can only roll over into a later active stage")]
+    InvalidRolloverStage {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Expiration;
+
+use crate::state::{Boost, ClawbackDestination};
+
+/// This structure describes the parameters used for creating an airdrop contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token contract address
+    pub cw20_token_address: String,
+    /// The address receiving the unclaimed tokens clawed back from expired stages
+    pub treasury: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Updates the treasury. Only the owner can execute it.
+    UpdateConfig { treasury: Option<String> },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good, disabling the owner actions. Only the owner can execute it.
+    RenounceOwnership {},
+    /// Registers a new stage with its merkle root. Only the owner can execute it.
+    RegisterMerkleRoot {
+        /// Hex encoded sha256 merkle root
+        merkle_root: String,
+        /// Claim deadline of the stage, never expires if unset
+        expiration: Option<Expiration>,
+        /// Sum of all allocations in the merkle tree
+        total_amount: Uint128,
+    },
+    /// Claims the sender allocation of the given stage
+    Claim {
+        stage: u8,
+        amount: Uint128,
+        /// Hex encoded merkle proof
+        proof: Vec<String>,
+    },
+    /// Sets the activity boosts of a stage and the amount of tokens reserved for them.
+    /// Boosts apply to claims made afterwards, the merkle tree is left untouched.
+    /// Only the owner can execute it.
+    UpdateStageBoosts {
+        stage: u8,
+        boosts: Vec<Boost>,
+        boost_amount: Uint128,
+    },
+    /// Transfers the unclaimed tokens of an expired stage to the given address.
+    /// Only the owner can execute it.
+    Withdraw { stage: u8, address: String },
+    /// Moves the unclaimed tokens of an expired stage to the treasury or into a later stage.
+    /// Anyone can claw back to the treasury, only the owner can roll over to a stage.
+    Clawback {
+        stage: u8,
+        destination: ClawbackDestination,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the stage merkle root and claim accounting.
+    /// Return type: StageResponse.
+    Stage { stage: u8 },
+    /// Returns the latest registered stage.
+    /// Return type: LatestStageResponse.
+    LatestStage {},
+    /// Returns whether the address has claimed its stage allocation.
+    /// Return type: IsClaimedResponse.
+    IsClaimed { stage: u8, address: String },
+    /// Returns the boost multiplier the address would get when claiming the stage.
+    /// Return type: BoostMultiplierResponse.
+    BoostMultiplier { stage: u8, address: String },
+    /// Returns the stage tokens neither claimed nor moved out yet.
+    /// Return type: UnclaimedAmountResponse.
+    UnclaimedAmount { stage: u8 },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub cw20_token_address: String,
+    pub treasury: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageResponse {
+    pub stage: u8,
+    pub merkle_root: String,
+    pub expiration: Expiration,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub withdrawn_amount: Uint128,
+    pub boosts: Vec<Boost>,
+    pub boost_amount: Uint128,
+    pub boost_claimed_amount: Uint128,
+    pub rolled_over_amount: Uint128,
+    pub rolled_in_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LatestStageResponse {
+    pub latest_stage: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostMultiplierResponse {
+    pub multiplier: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnclaimedAmountResponse {
+    pub stage: u8,
+    pub unclaimed_amount: Uint128,
+    /// Whether the stage expired and its unclaimed tokens can be clawed back
+    pub expired: bool,
+}
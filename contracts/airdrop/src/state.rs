@@ -0,0 +1,120 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Item, Map, U8Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can register merkle roots and withdraw unclaimed tokens
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token contract address
+    pub cw20_token_address: Addr,
+    /// The address receiving the unclaimed tokens clawed back from expired stages
+    #[serde(default)]
+    pub treasury: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClawbackDestination {
+    /// Transfers the unclaimed tokens to the configured treasury
+    Treasury {},
+    /// Moves the unclaimed tokens into the allocation of a later stage
+    Stage { stage: u8 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostCondition {
+    /// The claimer has bonded at least `min_amount` in the staking contract
+    Staked { contract: Addr, min_amount: Uint128 },
+    /// The claimer has at least one NFT listed on the marketplace
+    ListedNft { contract: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Boost {
+    pub condition: BoostCondition,
+    /// The claimed amount is multiplied by it when the condition is met,
+    /// bonuses of several met boosts add up
+    pub multiplier: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stage {
+    /// Hex encoded merkle root of the stage allocations
+    pub merkle_root: String,
+    /// Claims are rejected and the remainder can be withdrawn after this expiration
+    pub expiration: Expiration,
+    /// The total amount of tokens allocated by the merkle tree
+    pub total_amount: Uint128,
+    /// The amount of tokens already claimed by users
+    pub claimed_amount: Uint128,
+    /// The amount of unclaimed tokens withdrawn by the owner after expiration
+    pub withdrawn_amount: Uint128,
+    /// Activity boosts checked at claim time
+    #[serde(default)]
+    pub boosts: Vec<Boost>,
+    /// The amount of tokens reserved for boosts on top of the merkle tree allocations
+    #[serde(default)]
+    pub boost_amount: Uint128,
+    /// The amount of boost tokens already claimed by users
+    #[serde(default)]
+    pub boost_claimed_amount: Uint128,
+    /// The amount of unclaimed tokens moved into a later stage after expiration
+    #[serde(default)]
+    pub rolled_over_amount: Uint128,
+    /// The amount of tokens moved into this stage from expired stages, part of the total amount
+    #[serde(default)]
+    pub rolled_in_amount: Uint128,
+}
+
+impl Stage {
+    pub fn unclaimed_amount(&self) -> Uint128 {
+        self.total_amount
+            .saturating_add(self.boost_amount)
+            .saturating_sub(self.claimed_amount)
+            .saturating_sub(self.boost_claimed_amount)
+            .saturating_sub(self.withdrawn_amount)
+            .saturating_sub(self.rolled_over_amount)
+    }
+
+    pub fn remaining_boost_amount(&self) -> Uint128 {
+        self.boost_amount.saturating_sub(self.boost_claimed_amount)
+    }
+}
+
+impl Config {
+    pub fn is_owner(&self, address: &str) -> bool {
+        match &self.owner {
+            Some(owner) => owner.as_str() == address,
+            None => false,
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+pub const LATEST_STAGE: Item<u8> = Item::new("latest_stage");
+pub const STAGES: Map<U8Key, Stage> = Map::new("stages");
+pub const CLAIMS: Map<(&Addr, U8Key), bool> = Map::new("claims");
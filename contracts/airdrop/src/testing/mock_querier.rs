@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use luart_protocol::marketplace::{ListingCountResponse, QueryMsg as MarketplaceQueryMsg};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+
+pub const MOCK_STAKING: &str = "mock_staking";
+pub const MOCK_MARKETPLACE: &str = "mock_marketplace";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the staking and marketplace activity queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    stakes: HashMap<String, Uint128>,
+    listings: HashMap<String, u64>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match contract_addr.as_str() {
+                    MOCK_STAKING => match from_binary(msg) {
+                        Ok(StakingQueryMsg::StakerInfo { staker }) => {
+                            let bond_amount = self.stakes.get(&staker).copied().unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&StakerInfoResponse {
+                                staker,
+                                bond_amount,
+                            })))
+                        }
+                        Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "Unsupported staking query".to_string(),
+                            request: msg.clone(),
+                        }),
+                        Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                            error: format!("Parsing staking query: {}", e),
+                            request: msg.clone(),
+                        }),
+                    },
+                    MOCK_MARKETPLACE => match from_binary(msg) {
+                        Ok(MarketplaceQueryMsg::ListingCount { seller }) => {
+                            let count = self.listings.get(&seller).copied().unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&ListingCountResponse { count })))
+                        }
+                        Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                            error: format!("Parsing marketplace query: {}", e),
+                            request: msg.clone(),
+                        }),
+                    },
+                    _ => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            stakes: HashMap::new(),
+            listings: HashMap::new(),
+        }
+    }
+
+    // configure the bonded amount of an address
+    pub fn with_stake(&mut self, address: &str, amount: u128) {
+        self.stakes.insert(address.to_string(), Uint128::new(amount));
+    }
+
+    // configure the number of NFTs listed by an address
+    pub fn with_listings(&mut self, address: &str, count: u64) {
+        self.listings.insert(address.to_string(), count);
+    }
+}
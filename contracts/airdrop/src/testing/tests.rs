@@ -0,0 +1,423 @@
+use cosmwasm_std::{
+    Addr, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Expiration};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    BoostMultiplierResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, QueryMsg,
+    StageResponse, UnclaimedAmountResponse,
+};
+use crate::state::{Boost, BoostCondition, ClawbackDestination};
+use crate::testing::mock_querier::{MOCK_MARKETPLACE, MOCK_STAKING, mock_dependencies};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const TREASURY: &str = "mock_treasury";
+
+// Tree of (addr0001, 1000), (addr0002, 2000), (addr0003, 3000)
+const MERKLE_ROOT: &str = "1d1dad5be17f102b36c9ea951aefd07d05483d564bd016db61a70c14525aef0e";
+
+fn proof_addr0001() -> Vec<String> {
+    vec![
+        "4c25f57a1f2175d3744c84a73bee53d07782f4d65e8bcd861311a68cb8fdaa79".to_string(),
+        "c0316a8ffd9fe8c428c6e5cc6aecfdeb1e5de83a59196e1aa901ec63d3c4b1a9".to_string(),
+    ]
+}
+
+fn proof_addr0003() -> Vec<String> {
+    vec!["ec6d9ecefe96129ee828831ec07df5eb56e53e4b76d083229e704e6b5398e182".to_string()]
+}
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        cw20_token_address: TOKEN.to_string(),
+        treasury: Some(TREASURY.to_string()),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn register_stage(deps: DepsMut, env: Env, expiration: Option<Expiration>) {
+    execute(deps, env, mock_info(OWNER, &[]),
+            ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: MERKLE_ROOT.to_string(),
+                expiration,
+                total_amount: Uint128::new(6000),
+            }).unwrap();
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_register_merkle_root() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Only the owner can register a stage
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::RegisterMerkleRoot {
+                          merkle_root: MERKLE_ROOT.to_string(),
+                          expiration: None,
+                          total_amount: Uint128::new(6000),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Invalid root is rejected
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::RegisterMerkleRoot {
+                          merkle_root: "1d1dad".to_string(),
+                          expiration: None,
+                          total_amount: Uint128::new(6000),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Hex(hex::FromHexError::InvalidStringLength));
+
+    register_stage(deps.as_mut(), env.clone(), None);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Stage { stage: 1 }).unwrap();
+    let stage: StageResponse = from_binary(&res).unwrap();
+    assert_eq!(stage, StageResponse {
+        stage: 1,
+        merkle_root: MERKLE_ROOT.to_string(),
+        expiration: Expiration::Never {},
+        total_amount: Uint128::new(6000),
+        claimed_amount: Uint128::zero(),
+        withdrawn_amount: Uint128::zero(),
+        boosts: vec![],
+        boost_amount: Uint128::zero(),
+        boost_claimed_amount: Uint128::zero(),
+        rolled_over_amount: Uint128::zero(),
+        rolled_in_amount: Uint128::zero(),
+    });
+}
+
+#[test]
+fn test_claim() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    register_stage(deps.as_mut(), env.clone(), None);
+
+    // Wrong amount fails the proof verification
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(1001),
+                          proof: proof_addr0001(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::VerificationFailed {});
+
+    // Someone else cannot use the proof
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0002", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(1000),
+                          proof: proof_addr0001(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::VerificationFailed {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(1000),
+                          proof: proof_addr0001(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0001", 1000)]);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0003", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(3000),
+                          proof: proof_addr0003(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0003", 3000)]);
+
+    // Cannot claim twice
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(1000),
+                          proof: proof_addr0001(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::IsClaimed {
+        stage: 1,
+        address: "addr0001".to_string(),
+    }).unwrap();
+    let claimed: IsClaimedResponse = from_binary(&res).unwrap();
+    assert!(claimed.is_claimed);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::IsClaimed {
+        stage: 1,
+        address: "addr0002".to_string(),
+    }).unwrap();
+    let claimed: IsClaimedResponse = from_binary(&res).unwrap();
+    assert!(!claimed.is_claimed);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Stage { stage: 1 }).unwrap();
+    let stage: StageResponse = from_binary(&res).unwrap();
+    assert_eq!(stage.claimed_amount, Uint128::new(4000));
+}
+
+#[test]
+fn test_withdraw_after_expiration() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let expiration = Expiration::AtHeight(env.block.height + 100);
+    register_stage(deps.as_mut(), env.clone(), Some(expiration));
+
+    execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+            ExecuteMsg::Claim {
+                stage: 1,
+                amount: Uint128::new(1000),
+                proof: proof_addr0001(),
+            }).unwrap();
+
+    // Cannot withdraw before the claim deadline
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Withdraw {
+                          stage: 1,
+                          address: TREASURY.to_string(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::StageNotExpired { stage: 1 });
+
+    env.block.height += 100;
+
+    // Claims are closed after the deadline
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0003", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(3000),
+                          proof: proof_addr0003(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::StageExpired { stage: 1 });
+
+    // Only the owner can withdraw
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      ExecuteMsg::Withdraw {
+                          stage: 1,
+                          address: TREASURY.to_string(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Withdraw {
+                          stage: 1,
+                          address: TREASURY.to_string(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(TREASURY, 5000)]);
+
+    // Nothing is left to withdraw
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::Withdraw {
+                          stage: 1,
+                          address: TREASURY.to_string(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+}
+
+#[test]
+fn test_clawback() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    register_stage(deps.as_mut(), env.clone(), Some(Expiration::AtHeight(env.block.height + 100)));
+    register_stage(deps.as_mut(), env.clone(), Some(Expiration::AtHeight(env.block.height + 200)));
+
+    execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+            ExecuteMsg::Claim {
+                stage: 1,
+                amount: Uint128::new(1000),
+                proof: proof_addr0001(),
+            }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Clawback { stage: 1, destination: ClawbackDestination::Treasury {} }).unwrap_err();
+    assert_eq!(err, ContractError::StageNotExpired { stage: 1 });
+
+    env.block.height += 100;
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::UnclaimedAmount { stage: 1 }).unwrap();
+    let unclaimed: UnclaimedAmountResponse = from_binary(&res).unwrap();
+    assert_eq!(unclaimed, UnclaimedAmountResponse {
+        stage: 1,
+        unclaimed_amount: Uint128::new(5000),
+        expired: true,
+    });
+
+    // Only the owner can roll over, and only into a later active stage
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Clawback {
+                          stage: 1,
+                          destination: ClawbackDestination::Stage { stage: 2 },
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Clawback {
+                          stage: 1,
+                          destination: ClawbackDestination::Stage { stage: 1 },
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRolloverStage {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Clawback { stage: 1, destination: ClawbackDestination::Stage { stage: 2 } }).unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Stage { stage: 2 }).unwrap();
+    let stage: StageResponse = from_binary(&res).unwrap();
+    assert_eq!(stage.total_amount, Uint128::new(11000));
+    assert_eq!(stage.rolled_in_amount, Uint128::new(5000));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Clawback { stage: 1, destination: ClawbackDestination::Treasury {} }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    // Anyone can send the unclaimed tokens of an expired stage to the treasury
+    env.block.height += 100;
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::Clawback { stage: 2, destination: ClawbackDestination::Treasury {} }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(TREASURY, 11000)]);
+}
+
+#[test]
+fn test_boosted_claim() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    register_stage(deps.as_mut(), env.clone(), None);
+
+    let boosts = vec![
+        Boost {
+            condition: BoostCondition::Staked {
+                contract: Addr::unchecked(MOCK_STAKING),
+                min_amount: Uint128::new(100),
+            },
+            multiplier: Decimal::from_ratio(3u128, 2u128),
+        },
+        Boost {
+            condition: BoostCondition::ListedNft { contract: Addr::unchecked(MOCK_MARKETPLACE) },
+            multiplier: Decimal::from_ratio(6u128, 5u128),
+        },
+    ];
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::UpdateStageBoosts {
+                          stage: 1,
+                          boosts: boosts.clone(),
+                          boost_amount: Uint128::new(1000),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::UpdateStageBoosts {
+                          stage: 1,
+                          boosts: vec![Boost {
+                              condition: BoostCondition::ListedNft { contract: Addr::unchecked(MOCK_MARKETPLACE) },
+                              multiplier: Decimal::percent(50),
+                          }],
+                          boost_amount: Uint128::new(1000),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBoostMultiplier {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::UpdateStageBoosts {
+                stage: 1,
+                boosts,
+                boost_amount: Uint128::new(1000),
+            }).unwrap();
+
+    deps.querier.with_stake("addr0001", 100);
+    deps.querier.with_listings("addr0001", 2);
+    deps.querier.with_stake("addr0002", 99);
+    deps.querier.with_stake("addr0003", 500);
+
+    // Bonuses of both met boosts add up
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::BoostMultiplier {
+        stage: 1,
+        address: "addr0001".to_string(),
+    }).unwrap();
+    let boost: BoostMultiplierResponse = from_binary(&res).unwrap();
+    assert_eq!(boost.multiplier, Decimal::from_ratio(17u128, 10u128));
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::BoostMultiplier {
+        stage: 1,
+        address: "addr0002".to_string(),
+    }).unwrap();
+    let boost: BoostMultiplierResponse = from_binary(&res).unwrap();
+    assert_eq!(boost.multiplier, Decimal::one());
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(1000),
+                          proof: proof_addr0001(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0001", 1700)]);
+
+    // The boost is capped by what is left of the boost reserve
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0003", &[]),
+                      ExecuteMsg::Claim {
+                          stage: 1,
+                          amount: Uint128::new(3000),
+                          proof: proof_addr0003(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0003", 3300)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Stage { stage: 1 }).unwrap();
+    let stage: StageResponse = from_binary(&res).unwrap();
+    assert_eq!(stage.claimed_amount, Uint128::new(4000));
+    assert_eq!(stage.boost_claimed_amount, Uint128::new(1000));
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]),
+                      ExecuteMsg::ProposeOwner { owner: "mock_dao".to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::ProposeOwner { owner: "mock_dao".to_string() }).unwrap();
+
+    // The owner keeps its powers until the proposal is accepted
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.owner, Some(OWNER.to_string()));
+    assert_eq!(config.pending_owner, Some("mock_dao".to_string()));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::AcceptOwnership {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]), ExecuteMsg::AcceptOwnership {}).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::UpdateConfig { treasury: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]), ExecuteMsg::RenounceOwnership {}).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.owner, None);
+    assert_eq!(config.pending_owner, None);
+    let err = execute(deps.as_mut(), env, mock_info("mock_dao", &[]),
+                      ExecuteMsg::UpdateConfig { treasury: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
@@ -0,0 +1,278 @@
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw721_base::{InstantiateMsg as BaseInstantiateMsg, MintMsg};
+use cw721_base::QueryMsg as BaseQueryMsg;
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use luart_utils::roles::{grant_role, has_role, revoke_role};
+
+use crate::error::ContractError;
+use crate::msg::{
+    BadgeResponse, BadgesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, IssuerResponse,
+    MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    Badge, BADGE_COUNT, BADGES, Config, CONFIG, ConfigOwner, Cw721BaseContract, ISSUER_ROLE,
+    OWNER_BADGES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-badges";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+    CONFIG.save(deps.storage, &Config { owner: Some(owner) })?;
+    BADGE_COUNT.save(deps.storage, &0u64)?;
+    for issuer in msg.issuers.iter() {
+        grant_role(deps.storage, ISSUER_ROLE, &deps.api.addr_validate(issuer)?)?;
+    }
+
+    // The badges contract issues the badges on behalf of the issuers
+    Cw721BaseContract::default().instantiate(deps, env.clone(), info, BaseInstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        minter: env.contract.address.to_string(),
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Issue {
+            recipient,
+            kind,
+            token_uri,
+        } => issue(deps, env, info, recipient, kind, token_uri),
+        ExecuteMsg::Burn { token_id } => burn(deps, info, token_id),
+        ExecuteMsg::GrantIssuer { address } => update_issuer(deps, info, address, true),
+        ExecuteMsg::RevokeIssuer { address } => update_issuer(deps, info, address, false),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+        ExecuteMsg::TransferNft { .. } | ExecuteMsg::SendNft { .. } => {
+            Err(ContractError::NonTransferable {})
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn issue(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    kind: String,
+    token_uri: Option<String>,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, ISSUER_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if kind.is_empty() {
+        return Err(ContractError::InvalidKind {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if OWNER_BADGES.has(deps.storage, (&recipient, &kind)) {
+        return Err(ContractError::AlreadyIssued { kind });
+    }
+
+    let token_id = (BADGE_COUNT.load(deps.storage)? + 1).to_string();
+    BADGE_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    BADGES.save(deps.storage, &token_id, &Badge {
+        kind: kind.clone(),
+        owner: recipient.clone(),
+        issuer: info.sender.clone(),
+        issued_at: env.block.time.seconds(),
+    })?;
+    OWNER_BADGES.save(deps.storage, (&recipient, &kind), &token_id)?;
+
+    let mint_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    Cw721BaseContract::default().mint(deps, env, mint_info, MintMsg {
+        token_id: token_id.clone(),
+        owner: recipient.to_string(),
+        token_uri,
+        extension: None,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "issue")
+        .add_attribute("issuer", info.sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("kind", kind)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, ISSUER_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let badge = BADGES.load(deps.storage, &token_id)?;
+    BADGES.remove(deps.storage, &token_id);
+    OWNER_BADGES.remove(deps.storage, (&badge.owner, &badge.kind));
+
+    // The cw721 burn is restricted to the holder, the issuer removes the token directly
+    let base = Cw721BaseContract::default();
+    base.tokens.remove(deps.storage, &token_id)?;
+    base.decrement_tokens(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("owner", badge.owner)
+        .add_attribute("kind", badge.kind)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn update_issuer(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if granted {
+        grant_role(deps.storage, ISSUER_ROLE, &address)?;
+    } else {
+        revoke_role(deps.storage, ISSUER_ROLE, &address);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if granted { "grant_issuer" } else { "revoke_issuer" })
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let base = Cw721BaseContract::default();
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::IsIssuer { address } => to_binary(&query_is_issuer(deps, address)?),
+        QueryMsg::Badge { token_id } => to_binary(&query_badge(deps, token_id)?),
+        QueryMsg::BadgesOf {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query_badges_of(deps, address, start_after, limit)?),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => base.query(deps, env, BaseQueryMsg::OwnerOf { token_id, include_expired }),
+        QueryMsg::NumTokens {} => base.query(deps, env, BaseQueryMsg::NumTokens {}),
+        QueryMsg::ContractInfo {} => base.query(deps, env, BaseQueryMsg::ContractInfo {}),
+        QueryMsg::NftInfo { token_id } => base.query(deps, env, BaseQueryMsg::NftInfo { token_id }),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => base.query(deps, env, BaseQueryMsg::AllNftInfo { token_id, include_expired }),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => base.query(deps, env, BaseQueryMsg::Tokens { owner, start_after, limit }),
+        QueryMsg::AllTokens { start_after, limit } => {
+            base.query(deps, env, BaseQueryMsg::AllTokens { start_after, limit })
+        }
+        QueryMsg::Minter {} => base.query(deps, env, BaseQueryMsg::Minter {}),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+    })
+}
+
+pub fn query_is_issuer(deps: Deps, address: String) -> StdResult<IssuerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(IssuerResponse { is_issuer: has_role(deps.storage, ISSUER_ROLE, &address) })
+}
+
+pub fn query_badge(deps: Deps, token_id: String) -> StdResult<BadgeResponse> {
+    let badge = BADGES.load(deps.storage, &token_id)?;
+    badge_response(deps, token_id, badge)
+}
+
+pub fn query_badges_of(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BadgesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let badges: StdResult<Vec<BadgeResponse>> = OWNER_BADGES
+        .prefix(&address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, token_id) = item?;
+            let badge = BADGES.load(deps.storage, &token_id)?;
+            badge_response(deps, token_id, badge)
+        })
+        .collect();
+
+    Ok(BadgesResponse { badges: badges? })
+}
+
+fn badge_response(deps: Deps, token_id: String, badge: Badge) -> StdResult<BadgeResponse> {
+    let token = Cw721BaseContract::default().tokens.load(deps.storage, &token_id)?;
+    Ok(BadgeResponse {
+        token_id,
+        kind: badge.kind,
+        owner: badge.owner.to_string(),
+        issuer: badge.issuer.to_string(),
+        issued_at: badge.issued_at,
+        token_uri: token.token_uri,
+    })
+}
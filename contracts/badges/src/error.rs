@@ -0,0 +1,33 @@
+use cosmwasm_std::StdError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Base(#[from] cw721_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Badges cannot be transferred")]
+    NonTransferable {},
+
+    #[error("The badge kind must not be empty")]
+    InvalidKind {},
+
+    #[error("The address already holds a {kind} badge")]
+    AlreadyIssued { kind: String },
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
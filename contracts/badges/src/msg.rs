@@ -0,0 +1,139 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Binary;
+
+/// This structure describes the parameters used for creating a badges contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The addresses granted the issuer role
+    pub issuers: Vec<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Issues a badge of the kind to the recipient. Only an issuer can execute it.
+    Issue {
+        recipient: String,
+        kind: String,
+        token_uri: Option<String>,
+    },
+    /// Burns the badge. Only an issuer can execute it.
+    Burn { token_id: String },
+    /// Grants the issuer role. Only the owner can execute it.
+    GrantIssuer { address: String },
+    /// Revokes the issuer role. Only the owner can execute it.
+    RevokeIssuer { address: String },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+    /// Always rejected, the badges are bound to their owner
+    TransferNft { recipient: String, token_id: String },
+    /// Always rejected, the badges are bound to their owner
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the badges contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns whether the address holds the issuer role.
+    /// Return type: IssuerResponse.
+    IsIssuer { address: String },
+    /// Returns the badge.
+    /// Return type: BadgeResponse.
+    Badge { token_id: String },
+    /// Returns the badges of the address by kind. Supports pagination.
+    /// Return type: BadgesResponse.
+    BadgesOf {
+        address: String,
+        /// The kind to start after
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the owner of the badge.
+    /// Return type: OwnerOfResponse.
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns the number of badges.
+    /// Return type: NumTokensResponse.
+    NumTokens {},
+    /// Returns the contract name and symbol.
+    /// Return type: ContractInfoResponse.
+    ContractInfo {},
+    /// Returns the token uri and extension.
+    /// Return type: NftInfoResponse.
+    NftInfo { token_id: String },
+    /// Returns the token info and its owner.
+    /// Return type: AllNftInfoResponse.
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns the badges of the owner.
+    /// Return type: TokensResponse.
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns all the badges.
+    /// Return type: TokensResponse.
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the minter of the badges, the badges contract itself.
+    /// Return type: MinterResponse.
+    Minter {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IssuerResponse {
+    pub is_issuer: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BadgeResponse {
+    pub token_id: String,
+    pub kind: String,
+    pub owner: String,
+    pub issuer: String,
+    pub issued_at: u64,
+    pub token_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BadgesResponse {
+    pub badges: Vec<BadgeResponse>,
+}
@@ -0,0 +1,55 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty, StdResult, Storage};
+use cw721_base::{Cw721Contract, Extension};
+use cw_storage_plus::{Item, Map};
+use luart_utils::ownable::Ownable;
+
+/// The cw721 contract keeping the badges, the badges contract itself is its minter
+pub type Cw721BaseContract<'a> = Cw721Contract<'a, Extension, Empty>;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who grants and revokes the issuer role
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+}
+
+/// Issues and burns the badges
+pub const ISSUER_ROLE: &str = "issuer";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Badge {
+    /// The loyalty tier or role of the badge, an address holds one badge of each kind
+    pub kind: String,
+    pub owner: Addr,
+    pub issuer: Addr,
+    /// Timestamp in seconds
+    pub issued_at: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+/// The number of issued badges, the token ids are sequential from 1
+pub const BADGE_COUNT: Item<u64> = Item::new("badge_count");
+pub const BADGES: Map<&str, Badge> = Map::new("badges");
+/// The token id of the badge of each kind held by an address
+pub const OWNER_BADGES: Map<(&Addr, &str), String> = Map::new("owner_badges");
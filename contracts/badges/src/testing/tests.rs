@@ -0,0 +1,115 @@
+use cosmwasm_std::{DepsMut, Env, from_binary, Response, to_binary};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw721::{NumTokensResponse, OwnerOfResponse};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{BadgesResponse, ExecuteMsg, InstantiateMsg, IssuerResponse, QueryMsg};
+
+const OWNER: &str = "mock_owner";
+const ISSUER: &str = "mock_issuer";
+const HOLDER: &str = "mock_holder";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        name: "Luart Badges".to_string(),
+        symbol: "BADGE".to_string(),
+        owner: None,
+        issuers: vec![ISSUER.to_string()],
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn issue_msg(kind: &str) -> ExecuteMsg {
+    ExecuteMsg::Issue {
+        recipient: HOLDER.to_string(),
+        kind: kind.to_string(),
+        token_uri: Some(format!("ipfs://badges/{}.json", kind)),
+    }
+}
+
+#[test]
+fn test_issue() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]), issue_msg("og")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]), issue_msg("og")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]), issue_msg("volume_gold")).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]), issue_msg("og")).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyIssued { kind: "og".to_string() });
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::BadgesOf {
+        address: HOLDER.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let badges: BadgesResponse = from_binary(&res).unwrap();
+    assert_eq!(badges.badges.iter().map(|b| b.kind.as_str()).collect::<Vec<_>>(), vec!["og", "volume_gold"]);
+    assert_eq!(badges.badges[0].issuer, ISSUER);
+    assert_eq!(badges.badges[1].token_uri, Some("ipfs://badges/volume_gold.json".to_string()));
+
+    let res = query(deps.as_ref(), env, QueryMsg::OwnerOf {
+        token_id: "1".to_string(),
+        include_expired: None,
+    }).unwrap();
+    let owner: OwnerOfResponse = from_binary(&res).unwrap();
+    assert_eq!(owner.owner, HOLDER);
+}
+
+#[test]
+fn test_non_transferable() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]), issue_msg("og")).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]), ExecuteMsg::TransferNft {
+        recipient: "buyer".to_string(),
+        token_id: "1".to_string(),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]), ExecuteMsg::SendNft {
+        contract: "marketplace".to_string(),
+        token_id: "1".to_string(),
+        msg: to_binary("sell").unwrap(),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+
+    // Only the issuer burns the badge, it can then be issued again
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]),
+                      ExecuteMsg::Burn { token_id: "1".to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]), ExecuteMsg::Burn { token_id: "1".to_string() })
+        .unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::NumTokens {}).unwrap();
+    let num_tokens: NumTokensResponse = from_binary(&res).unwrap();
+    assert_eq!(num_tokens.count, 0);
+    execute(deps.as_mut(), env, mock_info(ISSUER, &[]), issue_msg("og")).unwrap();
+}
+
+#[test]
+fn test_update_issuer() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ISSUER, &[]),
+                      ExecuteMsg::GrantIssuer { address: "other".to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::GrantIssuer { address: "other".to_string() }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::RevokeIssuer { address: ISSUER.to_string() }).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::IsIssuer { address: "other".to_string() }).unwrap();
+    let issuer: IssuerResponse = from_binary(&res).unwrap();
+    assert!(issuer.is_issuer);
+    let err = execute(deps.as_mut(), env, mock_info(ISSUER, &[]), issue_msg("og")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
@@ -0,0 +1,320 @@
+use cosmwasm_std::{
+    Addr, Binary, Decimal, Deps, DepsMut, Env, Fraction, from_binary, MessageInfo, Response,
+    StdResult, to_binary, Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use luart_types::asset::Asset;
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    BondInfoResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg, StateResponse,
+};
+use crate::state::{BondInfo, BONDS, Config, CONFIG, State, STATE, Terms, TERMS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-bond";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    validate_terms(&msg.terms)?;
+
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+        lp_token: deps.api.addr_validate(&msg.lp_token)?,
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+    })?;
+    TERMS.save(deps.storage, &msg.terms)?;
+    STATE.save(deps.storage, &State {
+        last_decay: env.block.time.seconds(),
+        ..State::default()
+    })?;
+
+    Ok(Response::default())
+}
+
+fn validate_terms(terms: &Terms) -> Result<(), ContractError> {
+    if terms.min_price.is_zero() {
+        return Err(ContractError::InvalidTerms { reason: "minimum price must be positive".to_string() });
+    }
+    if terms.vesting_term == 0 {
+        return Err(ContractError::InvalidTerms { reason: "vesting term must be positive".to_string() });
+    }
+    if terms.max_payout.is_zero() || terms.max_payout > terms.max_debt {
+        return Err(ContractError::InvalidTerms {
+            reason: "maximum payout must be positive and below the maximum debt".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Redeem {} => redeem(deps, env, info),
+        ExecuteMsg::UpdateTerms { terms } => update_terms(deps, env, info, terms),
+        ExecuteMsg::UpdateConfig { owner, treasury } => update_config(deps, info, owner, treasury),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Bond { max_price } => {
+            if config.lp_token != info.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            let bonder = deps.api.addr_validate(&cw20_msg.sender)?;
+            bond(deps, env, config, bonder, cw20_msg.amount, max_price)
+        }
+        Cw20HookMsg::Fund {} => {
+            if config.luart_token != info.sender || config.owner != cw20_msg.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let mut state = STATE.load(deps.storage)?;
+            state.available_amount = state.available_amount.checked_add(cw20_msg.amount)?;
+            STATE.save(deps.storage, &state)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "fund")
+                .add_attribute("amount", cw20_msg.amount))
+        }
+    }
+}
+
+pub fn bond(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    bonder: Addr,
+    amount: Uint128,
+    max_price: Decimal,
+) -> Result<Response, ContractError> {
+    let terms = TERMS.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    state.decay_debt(&terms, now);
+
+    let total_supply = query_total_supply(deps.as_ref(), &config.luart_token)?;
+    let price = terms.bond_price(state.total_debt, total_supply);
+    if price > max_price {
+        return Err(ContractError::SlippageLimitExceeded { price });
+    }
+
+    let payout = amount.multiply_ratio(price.denominator(), price.numerator());
+    if payout.is_zero() {
+        return Err(ContractError::BondTooSmall {});
+    }
+    if payout > terms.max_payout {
+        return Err(ContractError::BondTooLarge { max: terms.max_payout.to_string() });
+    }
+    if state.total_debt.checked_add(payout)? > terms.max_debt {
+        return Err(ContractError::MaxDebtReached {});
+    }
+    state.available_amount = state.available_amount
+        .checked_sub(payout)
+        .map_err(|_| ContractError::InsufficientLuart {})?;
+    state.total_debt = state.total_debt.checked_add(payout)?;
+    STATE.save(deps.storage, &state)?;
+
+    // A new bond adds to the remaining payout and restarts the vesting term
+    let mut bond_info = BONDS.may_load(deps.storage, &bonder)?.unwrap_or(BondInfo {
+        payout: Uint128::zero(),
+        vesting: 0,
+        last_time: now,
+    });
+    bond_info.payout = bond_info.payout.checked_add(payout)?;
+    bond_info.vesting = terms.vesting_term;
+    bond_info.last_time = now;
+    BONDS.save(deps.storage, &bonder, &bond_info)?;
+
+    // The bonded LP tokens are owned by the protocol from now on
+    Ok(Response::new()
+        .add_message(Asset::Cw20(config.lp_token).transfer_msg(&config.treasury, amount)?)
+        .add_attribute("action", "bond")
+        .add_attribute("bonder", bonder)
+        .add_attribute("amount", amount)
+        .add_attribute("price", price.to_string())
+        .add_attribute("payout", payout))
+}
+
+pub fn redeem(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut bond_info = BONDS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NothingToRedeem {})?;
+
+    let now = env.block.time.seconds();
+    let amount = bond_info.vested_payout(now);
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRedeem {});
+    }
+
+    bond_info.payout = bond_info.payout.checked_sub(amount)?;
+    if bond_info.payout.is_zero() {
+        BONDS.remove(deps.storage, &info.sender);
+    } else {
+        bond_info.vesting = bond_info.vesting.saturating_sub(now - bond_info.last_time);
+        bond_info.last_time = now;
+        BONDS.save(deps.storage, &info.sender, &bond_info)?;
+    }
+
+    Ok(Response::new()
+        .add_message(Asset::Cw20(config.luart_token).transfer_msg(&info.sender, amount)?)
+        .add_attribute("action", "redeem")
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("remaining_payout", bond_info.payout))
+}
+
+pub fn update_terms(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    terms: Terms,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_terms(&terms)?;
+
+    // Decay the debt with the previous vesting term before it changes
+    let mut state = STATE.load(deps.storage)?;
+    state.decay_debt(&TERMS.load(deps.storage)?, env.block.time.seconds());
+    STATE.save(deps.storage, &state)?;
+    TERMS.save(deps.storage, &terms)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_terms")
+        .add_attribute("control_variable", terms.control_variable.to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = deps.api.addr_validate(&treasury)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Terms {} => to_binary(&TERMS.load(deps.storage)?),
+        QueryMsg::State {} => to_binary(&query_state(deps, env)?),
+        QueryMsg::BondInfo { address } => to_binary(&query_bond_info(deps, env, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        luart_token: config.luart_token.to_string(),
+        lp_token: config.lp_token.to_string(),
+        treasury: config.treasury.to_string(),
+    })
+}
+
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let terms = TERMS.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    state.decay_debt(&terms, env.block.time.seconds());
+
+    let total_supply = query_total_supply(deps, &config.luart_token)?;
+    let debt_ratio = if total_supply.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(state.total_debt, total_supply)
+    };
+
+    Ok(StateResponse {
+        total_debt: state.total_debt,
+        debt_ratio,
+        bond_price: terms.bond_price(state.total_debt, total_supply),
+        available_amount: state.available_amount,
+    })
+}
+
+pub fn query_bond_info(deps: Deps, env: Env, address: String) -> StdResult<BondInfoResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let bond_info = BONDS.load(deps.storage, &address)?;
+    Ok(BondInfoResponse {
+        pending_payout: bond_info.vested_payout(env.block.time.seconds()),
+        payout: bond_info.payout,
+        vesting: bond_info.vesting,
+        last_time: bond_info.last_time,
+    })
+}
+
+fn query_total_supply(deps: Deps, token: &Addr) -> StdResult<Uint128> {
+    let res: TokenInfoResponse = deps.querier.query_wasm_smart(token, &Cw20QueryMsg::TokenInfo {})?;
+    Ok(res.total_supply)
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::{Decimal, OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid bond terms: {reason}")]
+    InvalidTerms { reason: String },
+
+    #[error("Bond price {price} is above the maximum price")]
+    SlippageLimitExceeded { price: Decimal },
+
+    #[error("Bond payout is too small")]
+    BondTooSmall {},
+
+    #[error("Bond payout is above the maximum of {max}")]
+    BondTooLarge { max: String },
+
+    #[error("The maximum debt is reached")]
+    MaxDebtReached {},
+
+    #[error("Not enough LUART available for bonds")]
+    InsufficientLuart {},
+
+    #[error("Nothing to redeem")]
+    NothingToRedeem {},
+}
@@ -0,0 +1,103 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::Terms;
+
+/// This structure describes the parameters used for creating a bond contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token paid out to bonders
+    pub luart_token: String,
+    /// The LUART-UST LP token accepted for bonds
+    pub lp_token: String,
+    /// The address holding the bonded LP tokens
+    pub treasury: String,
+    pub terms: Terms,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives LP tokens to bond or LUART to fund payouts, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Transfers the vested payout of the sender
+    Redeem {},
+    /// Updates the bond terms. Only the owner can execute it.
+    UpdateTerms { terms: Terms },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        treasury: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bonds the sent LP tokens for a LUART payout vesting over the vesting term.
+    /// Fails if the bond price is above `max_price`.
+    Bond { max_price: Decimal },
+    /// Deposits LUART paid out to bonders. Only the owner can send it.
+    Fund {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the bond terms.
+    /// Return type: Terms.
+    Terms {},
+    /// Returns the current debt and bond price.
+    /// Return type: StateResponse.
+    State {},
+    /// Returns the bond of the address.
+    /// Return type: BondInfoResponse.
+    BondInfo { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub luart_token: String,
+    pub lp_token: String,
+    pub treasury: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    /// The amount of LUART owed to bonders, decayed to the current block
+    pub total_debt: Uint128,
+    /// The debt over the LUART total supply
+    pub debt_ratio: Decimal,
+    /// The bond price in LP tokens per LUART
+    pub bond_price: Decimal,
+    /// The amount of deposited LUART not promised to bonders yet
+    pub available_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondInfoResponse {
+    /// The LUART left to be paid out
+    pub payout: Uint128,
+    /// Seconds left until the payout is fully vested, from `last_time`
+    pub vesting: u64,
+    pub last_time: u64,
+    /// The payout redeemable now
+    pub pending_payout: Uint128,
+}
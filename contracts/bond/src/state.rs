@@ -0,0 +1,95 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Fraction, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can update the bond terms and fund the contract
+    pub owner: Addr,
+    /// The LUART token paid out to bonders
+    pub luart_token: Addr,
+    /// The LUART-UST LP token accepted for bonds
+    pub lp_token: Addr,
+    /// The address holding the bonded LP tokens
+    pub treasury: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Terms {
+    /// Scales the bond price with the debt ratio
+    pub control_variable: Decimal,
+    /// The lowest bond price, in LP tokens per LUART
+    pub min_price: Decimal,
+    /// Seconds over which payouts vest linearly
+    pub vesting_term: u64,
+    /// The maximum LUART payout of a single bond
+    pub max_payout: Uint128,
+    /// The maximum amount of LUART owed to bonders at once
+    pub max_debt: Uint128,
+}
+
+impl Terms {
+    /// Returns the bond price in LP tokens per LUART, `control_variable * debt / supply`
+    /// floored at the minimum price
+    pub fn bond_price(&self, total_debt: Uint128, total_supply: Uint128) -> Decimal {
+        if total_supply.is_zero() {
+            return self.min_price;
+        }
+        let numerator = Uint128::from(self.control_variable.numerator())
+            .multiply_ratio(total_debt, total_supply);
+        let price = Decimal::from_ratio(numerator, self.control_variable.denominator());
+        if price < self.min_price { self.min_price } else { price }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct State {
+    /// The amount of LUART owed to bonders, decaying over the vesting term
+    pub total_debt: Uint128,
+    /// Timestamp in seconds of the last debt decay
+    pub last_decay: u64,
+    /// The amount of deposited LUART not promised to bonders yet
+    pub available_amount: Uint128,
+}
+
+impl State {
+    /// Decays the debt linearly, as if every payout vested over the vesting term
+    pub fn decay_debt(&mut self, terms: &Terms, time: u64) {
+        let elapsed = time.saturating_sub(self.last_decay);
+        let decay = if elapsed >= terms.vesting_term {
+            self.total_debt
+        } else {
+            self.total_debt.multiply_ratio(elapsed, terms.vesting_term)
+        };
+        self.total_debt = self.total_debt.saturating_sub(decay);
+        self.last_decay = time;
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondInfo {
+    /// The LUART left to be paid out
+    pub payout: Uint128,
+    /// Seconds left until the payout is fully vested
+    pub vesting: u64,
+    /// Timestamp in seconds of the last bond or redemption
+    pub last_time: u64,
+}
+
+impl BondInfo {
+    /// Returns the part of the payout vested at the given timestamp
+    pub fn vested_payout(&self, time: u64) -> Uint128 {
+        let elapsed = time.saturating_sub(self.last_time);
+        if elapsed >= self.vesting {
+            return self.payout;
+        }
+        self.payout.multiply_ratio(elapsed, self.vesting)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const TERMS: Item<Terms> = Item::new("terms");
+pub const STATE: Item<State> = Item::new("state");
+pub const BONDS: Map<&Addr, BondInfo> = Map::new("bonds");
@@ -0,0 +1,77 @@
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the LUART token info queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    total_supply: Uint128,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr: _, msg }) => {
+                match from_binary(msg) {
+                    Ok(Cw20QueryMsg::TokenInfo {}) => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                            name: "Luart Token".to_string(),
+                            symbol: "LUART".to_string(),
+                            decimals: 6,
+                            total_supply: self.total_supply,
+                        })))
+                    }
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported token query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            total_supply: Uint128::zero(),
+        }
+    }
+
+    // configure the LUART total supply
+    pub fn with_total_supply(&mut self, total_supply: u128) {
+        self.total_supply = Uint128::new(total_supply);
+    }
+}
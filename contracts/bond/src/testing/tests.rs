@@ -0,0 +1,170 @@
+use cosmwasm_std::{
+    CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use crate::state::Terms;
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const LUART: &str = "mock_luart";
+const LP_TOKEN: &str = "mock_lp_token";
+const TREASURY: &str = "mock_treasury";
+const USER: &str = "mock_user";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        luart_token: LUART.to_string(),
+        lp_token: LP_TOKEN.to_string(),
+        treasury: TREASURY.to_string(),
+        terms: Terms {
+            control_variable: Decimal::from_ratio(500u128, 1u128),
+            min_price: Decimal::percent(50),
+            vesting_term: 5 * DAY,
+            max_payout: Uint128::new(10_000),
+            max_debt: Uint128::new(20_000),
+        },
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn fund(deps: DepsMut, env: Env, amount: u128) -> Response {
+    execute(deps, env, mock_info(LUART, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: OWNER.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::Fund {}).unwrap(),
+            })).unwrap()
+}
+
+fn bond(deps: DepsMut, env: Env, amount: u128, max_price: Decimal) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(LP_TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: USER.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::Bond { max_price }).unwrap(),
+            }))
+}
+
+fn transfer_msg(token: &str, recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_bond_price() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_total_supply(1_000_000);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    fund(deps.as_mut(), env.clone(), 30_000);
+
+    // Only the LP token is accepted
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]),
+                      ExecuteMsg::Receive(Cw20ReceiveMsg {
+                          sender: USER.to_string(),
+                          amount: Uint128::new(1_000),
+                          msg: to_binary(&Cw20HookMsg::Bond { max_price: Decimal::one() }).unwrap(),
+                      })).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Without debt the price is the minimum price, the LP goes to the treasury
+    let res = bond(deps.as_mut(), env.clone(), 1_000, Decimal::percent(50)).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(LP_TOKEN, TREASURY, 1_000)]);
+
+    // The price follows the debt ratio
+    let err = bond(deps.as_mut(), env.clone(), 1_000, Decimal::percent(90)).unwrap_err();
+    assert_eq!(err, ContractError::SlippageLimitExceeded { price: Decimal::one() });
+
+    bond(deps.as_mut(), env.clone(), 1_000, Decimal::one()).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state, StateResponse {
+        total_debt: Uint128::new(3_000),
+        debt_ratio: Decimal::permille(3),
+        bond_price: Decimal::percent(150),
+        available_amount: Uint128::new(27_000),
+    });
+}
+
+#[test]
+fn test_bond_limits() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_total_supply(1_000_000);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = bond(deps.as_mut(), env.clone(), 1_000, Decimal::one()).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientLuart {});
+
+    fund(deps.as_mut(), env.clone(), 100_000);
+
+    let err = bond(deps.as_mut(), env.clone(), 6_000, Decimal::one()).unwrap_err();
+    assert_eq!(err, ContractError::BondTooLarge { max: "10000".to_string() });
+
+    bond(deps.as_mut(), env.clone(), 5_000, Decimal::one()).unwrap();
+    bond(deps.as_mut(), env.clone(), 50_000, Decimal::percent(500)).unwrap();
+
+    let err = bond(deps.as_mut(), env.clone(), 100, Decimal::percent(1_000)).unwrap_err();
+    assert_eq!(err, ContractError::MaxDebtReached {});
+
+    // The debt decays over the vesting term
+    env.block.time = env.block.time.plus_seconds(5 * DAY);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_debt, Uint128::zero());
+    assert_eq!(state.bond_price, Decimal::percent(50));
+
+    bond(deps.as_mut(), env, 100, Decimal::percent(50)).unwrap();
+}
+
+#[test]
+fn test_redeem() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_total_supply(1_000_000);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    fund(deps.as_mut(), env.clone(), 30_000);
+    bond(deps.as_mut(), env.clone(), 1_000, Decimal::percent(50)).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Redeem {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRedeem {});
+
+    // The payout vests linearly over the vesting term
+    env.block.time = env.block.time.plus_seconds(DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Redeem {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(LUART, USER, 400)]);
+
+    env.block.time = env.block.time.plus_seconds(2 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Redeem {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(LUART, USER, 800)]);
+
+    env.block.time = env.block.time.plus_seconds(10 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Redeem {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(LUART, USER, 800)]);
+
+    let err = execute(deps.as_mut(), env, mock_info(USER, &[]),
+                      ExecuteMsg::Redeem {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRedeem {});
+}
@@ -0,0 +1,295 @@
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Storage, to_binary,
+    Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use luart_utils::roles::{grant_role, has_role, revoke_role, ROLES};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, MintPeriodResponse, OperatorsResponse,
+    ProcessedResponse, QueryMsg,
+};
+use crate::state::{
+    BURN_COUNT, Config, CONFIG, ConfigOwner, MINT_PERIOD, MintPeriod, OPERATOR_ROLE, PROCESSED_TXS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-bridge";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.mint_period == 0 {
+        return Err(ContractError::InvalidMintPeriod {});
+    }
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        token: deps.api.addr_validate(&msg.token)?,
+        mint_limit: msg.mint_limit,
+        mint_period: msg.mint_period,
+    })?;
+    MINT_PERIOD.save(deps.storage, &MintPeriod { start: env.block.time.seconds(), minted: Uint128::zero() })?;
+    BURN_COUNT.save(deps.storage, &0u64)?;
+
+    for operator in msg.operators.iter() {
+        grant_role(deps.storage, OPERATOR_ROLE, &deps.api.addr_validate(operator)?)?;
+    }
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::BridgeMint {
+            recipient,
+            amount,
+            tx_hash,
+        } => bridge_mint(deps, env, info, recipient, amount, tx_hash),
+        ExecuteMsg::BridgeBurn {
+            amount,
+            dest_chain,
+            dest_addr,
+        } => bridge_burn(deps, info, amount, dest_chain, dest_addr),
+        ExecuteMsg::AddOperator { address } => update_operator(deps, info, address, true),
+        ExecuteMsg::RemoveOperator { address } => update_operator(deps, info, address, false),
+        ExecuteMsg::UpdateMintLimit {
+            mint_limit,
+            mint_period,
+        } => update_mint_limit(deps, info, mint_limit, mint_period),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn bridge_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    tx_hash: String,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, OPERATOR_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    // The hashes are case insensitive hex strings, they are stored in lowercase
+    let tx_hash = tx_hash.trim().to_lowercase();
+    if tx_hash.is_empty() {
+        return Err(ContractError::InvalidTxHash {});
+    }
+    if PROCESSED_TXS.has(deps.storage, &tx_hash) {
+        return Err(ContractError::AlreadyProcessed { tx_hash });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut period = current_mint_period(deps.storage, &config, env.block.time.seconds())?;
+    period.minted += amount;
+    if period.minted > config.mint_limit {
+        let remaining = config.mint_limit.saturating_sub(period.minted - amount);
+        return Err(ContractError::MintLimitExceeded { remaining: remaining.to_string() });
+    }
+    MINT_PERIOD.save(deps.storage, &period)?;
+    PROCESSED_TXS.save(deps.storage, &tx_hash, &Empty {})?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let mint = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: recipient.to_string(), amount })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(mint)
+        .add_attribute("action", "bridge_mint")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_attribute("tx_hash", tx_hash))
+}
+
+/// Returns the mint period including the current time, starting a new one once it has ended
+fn current_mint_period(storage: &dyn Storage, config: &Config, now: u64) -> StdResult<MintPeriod> {
+    let period = MINT_PERIOD.load(storage)?;
+    if now >= period.start + config.mint_period {
+        return Ok(MintPeriod { start: now, minted: Uint128::zero() });
+    }
+    Ok(period)
+}
+
+/// Burns the tokens of the sender, the operators release them on the destination chain
+/// from the emitted attributes
+pub fn bridge_burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+    dest_chain: String,
+    dest_addr: String,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if dest_chain.is_empty() || dest_addr.is_empty() {
+        return Err(ContractError::InvalidDestination {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let burn_id = BURN_COUNT.load(deps.storage)? + 1;
+    BURN_COUNT.save(deps.storage, &burn_id)?;
+
+    let burn = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::BurnFrom { owner: info.sender.to_string(), amount })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(burn)
+        .add_attribute("action", "bridge_burn")
+        .add_attribute("burn_id", burn_id.to_string())
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("dest_chain", dest_chain)
+        .add_attribute("dest_addr", dest_addr))
+}
+
+pub fn update_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    added: bool,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if added {
+        grant_role(deps.storage, OPERATOR_ROLE, &address)?;
+    } else {
+        revoke_role(deps.storage, OPERATOR_ROLE, &address);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if added { "add_operator" } else { "remove_operator" })
+        .add_attribute("address", address))
+}
+
+pub fn update_mint_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    mint_limit: Option<Uint128>,
+    mint_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.mint_limit = mint_limit.unwrap_or(config.mint_limit);
+    config.mint_period = mint_period.unwrap_or(config.mint_period);
+    if config.mint_period == 0 {
+        return Err(ContractError::InvalidMintPeriod {});
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_mint_limit")
+        .add_attribute("mint_limit", config.mint_limit)
+        .add_attribute("mint_period", config.mint_period.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::MintPeriod {} => to_binary(&query_mint_period(deps, env)?),
+        QueryMsg::Processed { tx_hash } => to_binary(&query_processed(deps, tx_hash)?),
+        QueryMsg::Operators { start_after, limit } => to_binary(&query_operators(deps, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        token: config.token.to_string(),
+        mint_limit: config.mint_limit,
+        mint_period: config.mint_period,
+    })
+}
+
+pub fn query_mint_period(deps: Deps, env: Env) -> StdResult<MintPeriodResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let period = current_mint_period(deps.storage, &config, env.block.time.seconds())?;
+    Ok(MintPeriodResponse {
+        start: period.start,
+        minted: period.minted,
+        remaining: config.mint_limit.saturating_sub(period.minted),
+    })
+}
+
+pub fn query_processed(deps: Deps, tx_hash: String) -> StdResult<ProcessedResponse> {
+    let tx_hash = tx_hash.trim().to_lowercase();
+    Ok(ProcessedResponse { processed: PROCESSED_TXS.has(deps.storage, &tx_hash) })
+}
+
+pub fn query_operators(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let operators: StdResult<Vec<String>> = ROLES
+        .prefix(OPERATOR_ROLE)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| String::from_utf8(key).map_err(Into::into))
+        .collect();
+
+    Ok(OperatorsResponse { operators: operators? })
+}
@@ -0,0 +1,39 @@
+use cosmwasm_std::StdError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("The mint period must be positive")]
+    InvalidMintPeriod {},
+
+    #[error("Mint limit exceeded, {remaining} left in the period")]
+    MintLimitExceeded { remaining: String },
+
+    #[error("Transaction {tx_hash} was already processed")]
+    AlreadyProcessed { tx_hash: String },
+
+    #[error("The transaction hash must not be empty")]
+    InvalidTxHash {},
+
+    #[error("The destination chain and address must not be empty")]
+    InvalidDestination {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+
+/// This structure describes the parameters used for creating a bridge contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token address, the contract has to be registered as its minter
+    pub token: String,
+    pub operators: Vec<String>,
+    /// The maximum amount minted during a period
+    pub mint_limit: Uint128,
+    /// The period length in seconds
+    pub mint_period: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Mints the tokens locked or burned on the source chain in the transaction `tx_hash`,
+    /// each transaction is minted for once. Only an operator can execute it.
+    BridgeMint {
+        recipient: String,
+        amount: Uint128,
+        tx_hash: String,
+    },
+    /// Burns tokens of the sender to release them on the destination chain. The sender has
+    /// to give the contract an allowance for the amount beforehand.
+    BridgeBurn {
+        amount: Uint128,
+        dest_chain: String,
+        dest_addr: String,
+    },
+    /// Only the owner can add an operator
+    AddOperator { address: String },
+    /// Only the owner can remove an operator
+    RemoveOperator { address: String },
+    /// Updates the mint limit. Only the owner can execute it.
+    UpdateMintLimit {
+        mint_limit: Option<Uint128>,
+        mint_period: Option<u64>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the amount minted during the current period.
+    /// Return type: MintPeriodResponse.
+    MintPeriod {},
+    /// Returns whether the source chain transaction was already minted for.
+    /// Return type: ProcessedResponse.
+    Processed { tx_hash: String },
+    /// Returns the operators.
+    /// Return type: OperatorsResponse.
+    Operators {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub token: String,
+    pub mint_limit: Uint128,
+    pub mint_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintPeriodResponse {
+    /// Timestamp in seconds
+    pub start: u64,
+    pub minted: Uint128,
+    /// The amount which can still be minted until the end of the period
+    pub remaining: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProcessedResponse {
+    pub processed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorsResponse {
+    pub operators: Vec<String>,
+}
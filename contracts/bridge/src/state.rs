@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who manages the operators and the mint limit
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token, the contract has to be its minter
+    pub token: Addr,
+    /// The maximum amount minted during a period
+    pub mint_limit: Uint128,
+    /// The period length in seconds
+    pub mint_period: u64,
+}
+
+/// The tokens minted during the current period
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct MintPeriod {
+    /// Timestamp in seconds
+    pub start: u64,
+    pub minted: Uint128,
+}
+
+/// Mints the tokens bridged from the other chains
+pub const OPERATOR_ROLE: &str = "operator";
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const MINT_PERIOD: Item<MintPeriod> = Item::new("mint_period");
+/// The source chain transactions already minted for, keyed by their lowercase hash
+pub const PROCESSED_TXS: Map<&str, Empty> = Map::new("processed_txs");
+pub const BURN_COUNT: Item<u64> = Item::new("burn_count");
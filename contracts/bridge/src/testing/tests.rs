@@ -0,0 +1,132 @@
+use cosmwasm_std::{Deps, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MintPeriodResponse, OperatorsResponse, ProcessedResponse, QueryMsg,
+};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const OPERATOR: &str = "mock_operator";
+const RECIPIENT: &str = "mock_recipient";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        token: TOKEN.to_string(),
+        operators: vec![OPERATOR.to_string()],
+        mint_limit: Uint128::new(1_000),
+        mint_period: DAY,
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn mint_msg(amount: u128, tx_hash: &str) -> ExecuteMsg {
+    ExecuteMsg::BridgeMint {
+        recipient: RECIPIENT.to_string(),
+        amount: Uint128::new(amount),
+        tx_hash: tx_hash.to_string(),
+    }
+}
+
+fn query_mint_period(deps: Deps, env: Env) -> MintPeriodResponse {
+    from_binary(&query(deps, env, QueryMsg::MintPeriod {}).unwrap()).unwrap()
+}
+
+#[test]
+fn test_bridge_mint() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]), mint_msg(100, "0xAB")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(600, "0xAB")).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: RECIPIENT.to_string(), amount: Uint128::new(600) })
+            .unwrap(),
+        funds: vec![],
+    })]);
+
+    // A transaction is minted for once, whatever the case of its hash
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(600, "0xab")).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyProcessed { tx_hash: "0xab".to_string() });
+    let res: ProcessedResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Processed {
+        tx_hash: "0xAB".to_string(),
+    }).unwrap()).unwrap();
+    assert!(res.processed);
+
+    // At most 1000 tokens are minted a day
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(500, "0xcd")).unwrap_err();
+    assert_eq!(err, ContractError::MintLimitExceeded { remaining: "400".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(400, "0xcd")).unwrap();
+    assert_eq!(query_mint_period(deps.as_ref(), env.clone()).remaining, Uint128::zero());
+
+    env.block.time = env.block.time.plus_seconds(DAY);
+    assert_eq!(query_mint_period(deps.as_ref(), env.clone()).remaining, Uint128::new(1_000));
+    execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(500, "0xef")).unwrap();
+    let period = query_mint_period(deps.as_ref(), env);
+    assert_eq!(period.minted, Uint128::new(500));
+    assert_eq!(period.remaining, Uint128::new(500));
+}
+
+#[test]
+fn test_bridge_burn() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let burn = |amount: u128, dest_addr: &str| ExecuteMsg::BridgeBurn {
+        amount: Uint128::new(amount),
+        dest_chain: "ethereum".to_string(),
+        dest_addr: dest_addr.to_string(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]), burn(100, "")).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDestination {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]), burn(0, "0x1234")).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]), burn(100, "0x1234")).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::BurnFrom { owner: RECIPIENT.to_string(), amount: Uint128::new(100) })
+            .unwrap(),
+        funds: vec![],
+    })]);
+    let res = execute(deps.as_mut(), env, mock_info(RECIPIENT, &[]), burn(100, "0x1234")).unwrap();
+    assert_eq!(res.attributes[1].value, "2");
+}
+
+#[test]
+fn test_operators() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let add = ExecuteMsg::AddOperator { address: "mock_operator2".to_string() };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), add.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), add).unwrap();
+
+    let remove = ExecuteMsg::RemoveOperator { address: OPERATOR.to_string() };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), remove).unwrap();
+    let res: OperatorsResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Operators {
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    assert_eq!(res.operators, vec!["mock_operator2".to_string()]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OPERATOR, &[]), mint_msg(100, "0xab")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env, mock_info("mock_operator2", &[]), mint_msg(100, "0xab")).unwrap();
+}
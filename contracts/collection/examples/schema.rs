@@ -0,0 +1,32 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use luart_utils::batch::BatchResponse;
+
+use luart_collection::msg::{
+    CheckRoyaltiesResponse, ConfigResponse, ExecuteMsg, HasTraitResponse, InstantiateMsg,
+    MintCountResponse, MintEligibilityResponse, MintInfoResponse, MintPhasesResponse, QueryMsg,
+    RevealInfoResponse, RoyaltiesInfoResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(MintInfoResponse), &out_dir);
+    export_schema(&schema_for!(MintPhasesResponse), &out_dir);
+    export_schema(&schema_for!(MintEligibilityResponse), &out_dir);
+    export_schema(&schema_for!(MintCountResponse), &out_dir);
+    export_schema(&schema_for!(RevealInfoResponse), &out_dir);
+    export_schema(&schema_for!(RoyaltiesInfoResponse), &out_dir);
+    export_schema(&schema_for!(CheckRoyaltiesResponse), &out_dir);
+    export_schema(&schema_for!(HasTraitResponse), &out_dir);
+    export_schema(&schema_for!(BatchResponse), &out_dir);
+}
@@ -0,0 +1,957 @@
+use std::collections::HashSet;
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw721::{Cw721Execute, Cw721Query, TokensResponse};
+use cw721_base::{ExecuteMsg as BaseExecuteMsg, InstantiateMsg as BaseInstantiateMsg, MintMsg};
+use cw721_base::QueryMsg as BaseQueryMsg;
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CheckRoyaltiesResponse, ConfigResponse, ExecuteMsg, HasTraitResponse, ImportedToken,
+    InstantiateMsg, MigrateMsg, MintCountResponse, MintEligibilityResponse, MintInfoResponse,
+    MintPhasesResponse, QueryMsg, RandomnessExecuteMsg, RevealInfoResponse, RoyaltiesInfoResponse,
+    RoyaltyMsg, TransferMsg,
+};
+use crate::state::{
+    Allowlist, ALLOWLISTS, Config, CONFIG, Cw721BaseContract, DELAYED_REVEAL, Extension, Metadata,
+    MINT_CONFIG, MINT_COUNT, MINT_PHASES, MintConfig, MintPhase, PENDING_REVEAL, PHASE_MINTS,
+    PUBLIC_PHASE, REVEAL_OFFSET, Royalty, ROYALTY, TOKEN_ROYALTIES, TRAIT_TOKENS, WALLET_MINTS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-collection";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// the most tokens minted or transferred by a batch, to stay within the block gas limit
+const MAX_BATCH_SIZE: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let creator = match msg.creator {
+        Some(creator) => deps.api.addr_validate(&creator)?,
+        None => info.sender.clone(),
+    };
+
+    if msg.protocol_fee > Decimal::one() {
+        return Err(ContractError::InvalidConfig { reason: "protocol fee must not exceed 1".to_string() });
+    }
+    validate_mint_config(&msg.mint_config, 0)?;
+    if let Some(delayed_reveal) = msg.delayed_reveal {
+        let mut hash_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(&delayed_reveal.provenance_hash, &mut hash_buf)?;
+        DELAYED_REVEAL.save(deps.storage, &delayed_reveal)?;
+    }
+    if let Some(royalty) = msg.royalty {
+        let royalty = validate_royalty(deps.as_ref(), royalty)?;
+        ROYALTY.save(deps.storage, &royalty)?;
+    }
+
+    let randomness_contract = msg
+        .randomness_contract
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    CONFIG.save(deps.storage, &Config {
+        creator,
+        base_token_uri: msg.base_token_uri,
+        payment_denom: msg.payment_denom,
+        protocol_fee: msg.protocol_fee,
+        protocol_fee_receiver: deps.api.addr_validate(&msg.protocol_fee_receiver)?,
+        randomness_contract,
+    })?;
+    MINT_CONFIG.save(deps.storage, &msg.mint_config)?;
+    MINT_COUNT.save(deps.storage, &0u64)?;
+    MINT_PHASES.save(deps.storage, &vec![])?;
+
+    // The collection mints the tokens on behalf of the buyers
+    Cw721BaseContract::default().instantiate(deps, env.clone(), info, BaseInstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        minter: env.contract.address.to_string(),
+    })?;
+
+    Ok(Response::default())
+}
+
+fn validate_mint_config(mint_config: &MintConfig, minted: u64) -> Result<(), ContractError> {
+    if mint_config.max_supply == 0 || mint_config.max_supply < minted {
+        return Err(ContractError::InvalidConfig {
+            reason: "max supply must be positive and cover the minted tokens".to_string(),
+        });
+    }
+    if mint_config.per_wallet_limit == Some(0) {
+        return Err(ContractError::InvalidConfig { reason: "per wallet limit must be positive".to_string() });
+    }
+
+    Ok(())
+}
+
+fn validate_royalty(deps: Deps, royalty: RoyaltyMsg) -> Result<Royalty, ContractError> {
+    if royalty.share > Decimal::one() {
+        return Err(ContractError::InvalidConfig { reason: "royalty share must not exceed 1".to_string() });
+    }
+
+    Ok(Royalty {
+        receiver: deps.api.addr_validate(&royalty.receiver)?,
+        share: royalty.share,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Mint { proof } => mint(deps, env, info, proof, 1),
+        ExecuteMsg::MintBatch { count, proof } => mint(deps, env, info, proof, count),
+        ExecuteMsg::UpdateMintConfig {
+            price,
+            max_supply,
+            per_wallet_limit,
+            start_time,
+        } => update_mint_config(deps, info, price, max_supply, per_wallet_limit, start_time),
+        ExecuteMsg::SetMintPhases { phases } => set_mint_phases(deps, info, phases),
+        ExecuteMsg::AddToAllowlist { phase, addresses } => {
+            update_allowlist(deps, info, phase, addresses, true)
+        }
+        ExecuteMsg::RemoveFromAllowlist { phase, addresses } => {
+            update_allowlist(deps, info, phase, addresses, false)
+        }
+        ExecuteMsg::Reveal { base_uri, offset } => reveal(deps, env, info, base_uri, offset),
+        ExecuteMsg::ReceiveRandomness { round_id, randomness } => {
+            receive_randomness(deps, info, round_id, randomness)
+        }
+        ExecuteMsg::UpdateRoyalty { royalty } => update_royalty(deps, info, None, royalty),
+        ExecuteMsg::SetTokenRoyalty { token_id, royalty } => {
+            update_royalty(deps, info, Some(token_id), royalty)
+        }
+        ExecuteMsg::SetMetadata { token_id, metadata } => set_metadata(deps, info, token_id, metadata),
+        ExecuteMsg::ImportTokens { batch } => import_tokens(deps, env, info, batch),
+        ExecuteMsg::TransferNft { recipient, token_id } => {
+            execute_base(deps, env, info, BaseExecuteMsg::TransferNft { recipient, token_id })
+        }
+        ExecuteMsg::TransferBatch { transfers } => transfer_batch(deps, env, info, transfers),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute_base(deps, env, info, BaseExecuteMsg::SendNft { contract, token_id, msg }),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute_base(deps, env, info, BaseExecuteMsg::Approve { spender, token_id, expires }),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute_base(deps, env, info, BaseExecuteMsg::Revoke { spender, token_id })
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute_base(deps, env, info, BaseExecuteMsg::ApproveAll { operator, expires })
+        }
+        ExecuteMsg::RevokeAll { operator } => {
+            execute_base(deps, env, info, BaseExecuteMsg::RevokeAll { operator })
+        }
+    }
+}
+
+fn execute_base(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: BaseExecuteMsg<Extension>,
+) -> Result<Response, ContractError> {
+    Ok(Cw721BaseContract::default().execute(deps, env, info, msg)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// Mints the next tokens to the sender in the current phase, the price of each token must be sent along
+pub fn mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proof: Option<Vec<String>>,
+    count: u32,
+) -> Result<Response, ContractError> {
+    if count == 0 || count > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize { max: MAX_BATCH_SIZE });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let phase = check_eligibility(deps.as_ref(), &env, &info.sender, proof, count)?;
+    let price = phase.price.checked_mul(Uint128::from(count))?;
+    must_pay_exactly(&info, &config.payment_denom, price)?;
+
+    let minted = MINT_COUNT.load(deps.storage)?;
+    MINT_COUNT.save(deps.storage, &(minted + u64::from(count)))?;
+    WALLET_MINTS.update(deps.storage, &info.sender, |mints| -> StdResult<_> {
+        Ok(mints.unwrap_or_default() + count)
+    })?;
+    if phase.name != PUBLIC_PHASE {
+        PHASE_MINTS.update(deps.storage, (&phase.name, &info.sender), |mints| -> StdResult<_> {
+            Ok(mints.unwrap_or_default() + count)
+        })?;
+    }
+
+    let base = Cw721BaseContract::default();
+    let mut response = Response::new()
+        .add_messages(payment_msgs(&config, price)?)
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender.clone())
+        .add_attribute("phase", phase.name);
+    for id in minted + 1..=minted + u64::from(count) {
+        let token_uri = token_uri(deps.storage, &config, id)?;
+        let mint_info = MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: vec![],
+        };
+        base.mint(deps.branch(), env.clone(), mint_info, MintMsg {
+            token_id: id.to_string(),
+            owner: info.sender.to_string(),
+            token_uri: Some(token_uri),
+            extension: None,
+        })?;
+        response = response.add_attribute("token_id", id.to_string());
+    }
+
+    Ok(response.add_attribute("price", phase.price))
+}
+
+/// Transfers several tokens of the sender at once, each transfer is authorized as a single one
+pub fn transfer_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfers: Vec<TransferMsg>,
+) -> Result<Response, ContractError> {
+    if transfers.is_empty() || transfers.len() > MAX_BATCH_SIZE as usize {
+        return Err(ContractError::InvalidBatchSize { max: MAX_BATCH_SIZE });
+    }
+
+    let base = Cw721BaseContract::default();
+    let count = transfers.len();
+    for transfer in transfers {
+        base.transfer_nft(deps.branch(), env.clone(), info.clone(), transfer.recipient, transfer.token_id)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_batch")
+        .add_attribute("sender", info.sender)
+        .add_attribute("count", count.to_string()))
+}
+
+/// Returns the placeholder uri until the reveal, then the uri of the metadata shifted by the offset
+fn token_uri(storage: &dyn Storage, config: &Config, token_id: u64) -> StdResult<String> {
+    let metadata_id = match (DELAYED_REVEAL.may_load(storage)?, REVEAL_OFFSET.may_load(storage)?) {
+        (Some(delayed_reveal), None) => return Ok(delayed_reveal.placeholder_uri),
+        (_, Some(offset)) => {
+            let max_supply = MINT_CONFIG.load(storage)?.max_supply;
+            (token_id - 1 + offset) % max_supply + 1
+        }
+        (None, None) => token_id,
+    };
+    Ok(format!("{}/{}", config.base_token_uri.trim_end_matches('/'), metadata_id))
+}
+
+/// Returns the phase open at the given time, the last one started
+fn current_phase(storage: &dyn Storage, now: u64) -> StdResult<Option<MintPhase>> {
+    Ok(all_phases(storage)?
+        .into_iter()
+        .rev()
+        .find(|phase| phase.start_time <= now))
+}
+
+/// Returns the allowlist phases and the public phase by start time
+fn all_phases(storage: &dyn Storage) -> StdResult<Vec<MintPhase>> {
+    let mut phases = MINT_PHASES.load(storage)?;
+    phases.push(MINT_CONFIG.load(storage)?.public_phase());
+    // The sort is stable, the public phase comes last among phases starting together
+    phases.sort_by_key(|phase| phase.start_time);
+    Ok(phases)
+}
+
+/// Returns the current phase if the address can mint the number of tokens in it
+fn check_eligibility(
+    deps: Deps,
+    env: &Env,
+    address: &Addr,
+    proof: Option<Vec<String>>,
+    count: u32,
+) -> Result<MintPhase, ContractError> {
+    let phase = current_phase(deps.storage, env.block.time.seconds())?
+        .ok_or(ContractError::MintNotStarted {})?;
+
+    let mint_config = MINT_CONFIG.load(deps.storage)?;
+    let minted = MINT_COUNT.load(deps.storage)?;
+    if minted >= mint_config.max_supply {
+        return Err(ContractError::SoldOut {});
+    }
+    if minted + u64::from(count) > mint_config.max_supply {
+        return Err(ContractError::NotEnoughSupply { remaining: mint_config.max_supply - minted });
+    }
+
+    let allowlisted = match &phase.allowlist {
+        Allowlist::Public {} => true,
+        Allowlist::MerkleRoot { root } => verify_merkle_proof(root, address, proof.unwrap_or_default())?,
+        Allowlist::Stored {} => ALLOWLISTS.has(deps.storage, (&phase.name, address)),
+    };
+    if !allowlisted {
+        return Err(ContractError::NotAllowlisted { phase: phase.name });
+    }
+
+    if let Some(limit) = phase.per_wallet_limit {
+        if phase_mints(deps.storage, &phase, address)? + count > limit {
+            return Err(ContractError::WalletLimitReached { limit });
+        }
+    }
+
+    Ok(phase)
+}
+
+/// The public phase limits the mints of all phases
+fn phase_mints(storage: &dyn Storage, phase: &MintPhase, address: &Addr) -> StdResult<u32> {
+    let count = if phase.name == PUBLIC_PHASE {
+        WALLET_MINTS.may_load(storage, address)?
+    } else {
+        PHASE_MINTS.may_load(storage, (&phase.name, address))?
+    };
+    Ok(count.unwrap_or_default())
+}
+
+fn verify_merkle_proof(
+    merkle_root: &str,
+    address: &Addr,
+    proof: Vec<String>,
+) -> Result<bool, ContractError> {
+    let hash: [u8; 32] = Sha256::digest(address.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| ContractError::InvalidConfig { reason: "wrong hash length".to_string() })?;
+
+    let hash = proof.into_iter().try_fold(hash, |hash, p| {
+        let mut proof_buf = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        Sha256::digest(&hashes.concat())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::InvalidConfig { reason: "wrong hash length".to_string() })
+    })?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    Ok(root_buf == hash)
+}
+
+/// Splits the mint price between the protocol fee receiver and the creator
+fn payment_msgs(config: &Config, price: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    let protocol_amount = price * config.protocol_fee;
+    let creator_amount = price.checked_sub(protocol_amount)?;
+
+    let mut messages = vec![];
+    for (recipient, amount) in [
+        (&config.protocol_fee_receiver, protocol_amount),
+        (&config.creator, creator_amount),
+    ].iter() {
+        if !amount.is_zero() {
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: config.payment_denom.clone(),
+                    amount: *amount,
+                }],
+            }));
+        }
+    }
+
+    Ok(messages)
+}
+
+pub fn update_mint_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    price: Option<Uint128>,
+    max_supply: Option<u64>,
+    per_wallet_limit: Option<u32>,
+    start_time: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut mint_config = MINT_CONFIG.load(deps.storage)?;
+    mint_config.price = price.unwrap_or(mint_config.price);
+    if let Some(max_supply) = max_supply {
+        // The revealed metadata are shifted modulo the max supply
+        if max_supply != mint_config.max_supply && REVEAL_OFFSET.may_load(deps.storage)?.is_some() {
+            return Err(ContractError::AlreadyRevealed {});
+        }
+        mint_config.max_supply = max_supply;
+    }
+    if per_wallet_limit.is_some() {
+        mint_config.per_wallet_limit = per_wallet_limit;
+    }
+    mint_config.start_time = start_time.unwrap_or(mint_config.start_time);
+    validate_mint_config(&mint_config, MINT_COUNT.load(deps.storage)?)?;
+    MINT_CONFIG.save(deps.storage, &mint_config)?;
+
+    Ok(Response::new().add_attribute("action", "update_mint_config"))
+}
+
+pub fn set_mint_phases(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut phases: Vec<MintPhase>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut names = HashSet::new();
+    for phase in phases.iter() {
+        if phase.name.is_empty() || phase.name == PUBLIC_PHASE || !names.insert(phase.name.as_str()) {
+            return Err(ContractError::InvalidConfig {
+                reason: format!("invalid or duplicated phase name: {}", phase.name),
+            });
+        }
+        if phase.per_wallet_limit == Some(0) {
+            return Err(ContractError::InvalidConfig { reason: "per wallet limit must be positive".to_string() });
+        }
+        if let Allowlist::MerkleRoot { root } = &phase.allowlist {
+            let mut root_buf: [u8; 32] = [0; 32];
+            hex::decode_to_slice(root, &mut root_buf)?;
+        }
+    }
+    phases.sort_by_key(|phase| phase.start_time);
+    MINT_PHASES.save(deps.storage, &phases)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_phases")
+        .add_attribute("phases", phases.len().to_string()))
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    base_uri: String,
+    offset: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let delayed_reveal = DELAYED_REVEAL.may_load(deps.storage)?.ok_or_else(|| ContractError::InvalidConfig {
+        reason: "the collection has no delayed reveal".to_string(),
+    })?;
+    if REVEAL_OFFSET.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    if hex::encode(Sha256::digest(base_uri.as_bytes())) != delayed_reveal.provenance_hash.to_lowercase() {
+        return Err(ContractError::ProvenanceMismatch {});
+    }
+
+    let offset = match (offset, &config.randomness_contract) {
+        (Some(offset), _) => offset,
+        // The reveal completes once the randomness contract sends the random value
+        (None, Some(randomness_contract)) => {
+            PENDING_REVEAL.save(deps.storage, &base_uri)?;
+            return Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: randomness_contract.to_string(),
+                    msg: to_binary(&RandomnessExecuteMsg::Request {})?,
+                    funds: vec![],
+                })
+                .add_attribute("action", "request_reveal")
+                .add_attribute("base_uri", base_uri));
+        }
+        (None, None) => random_offset(&env, &delayed_reveal.provenance_hash),
+    };
+
+    save_reveal(deps.storage, config, base_uri, offset)
+}
+
+pub fn receive_randomness(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: u64,
+    randomness: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.randomness_contract.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if REVEAL_OFFSET.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    let base_uri = PENDING_REVEAL.may_load(deps.storage)?.ok_or_else(|| ContractError::InvalidConfig {
+        reason: "no pending reveal".to_string(),
+    })?;
+
+    let mut seed = [0u8; 32];
+    hex::decode_to_slice(&randomness, &mut seed)?;
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&seed[..8]);
+
+    let res = save_reveal(deps.storage, config, base_uri, u64::from_be_bytes(bytes))?;
+    Ok(res.add_attribute("round_id", round_id.to_string()))
+}
+
+fn save_reveal(
+    storage: &mut dyn Storage,
+    mut config: Config,
+    base_uri: String,
+    offset: u64,
+) -> Result<Response, ContractError> {
+    let offset = offset % MINT_CONFIG.load(storage)?.max_supply;
+    config.base_token_uri = base_uri;
+    CONFIG.save(storage, &config)?;
+    REVEAL_OFFSET.save(storage, &offset)?;
+    PENDING_REVEAL.remove(storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal")
+        .add_attribute("base_uri", config.base_token_uri)
+        .add_attribute("offset", offset.to_string()))
+}
+
+/// Derives the offset from the block, unknown before the reveal transaction is included
+fn random_offset(env: &Env, provenance_hash: &str) -> u64 {
+    let seed = Sha256::new()
+        .chain(provenance_hash.as_bytes())
+        .chain(env.block.height.to_be_bytes())
+        .chain(env.block.time.nanos().to_be_bytes())
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&seed[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Replaces the metadata of the token, the traits of the previous metadata are removed from the index
+pub fn set_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    metadata: Metadata,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_metadata(&metadata)?;
+
+    let base = Cw721BaseContract::default();
+    let mut token = base.tokens.load(deps.storage, &token_id)?;
+    index_traits(deps.storage, &token_id, token.extension.take(), &metadata)?;
+    let traits = metadata.attributes.len();
+    token.extension = Some(metadata);
+    base.tokens.save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_metadata")
+        .add_attribute("token_id", token_id)
+        .add_attribute("traits", traits.to_string()))
+}
+
+fn validate_metadata(metadata: &Metadata) -> Result<(), ContractError> {
+    for (i, attribute) in metadata.attributes.iter().enumerate() {
+        if attribute.trait_type.is_empty() || attribute.value.is_empty() {
+            return Err(ContractError::InvalidMetadata { reason: "empty trait type or value".to_string() });
+        }
+        if metadata.attributes[..i].iter().any(|a| a.trait_type == attribute.trait_type) {
+            return Err(ContractError::InvalidMetadata {
+                reason: format!("duplicated trait type: {}", attribute.trait_type),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the token from the trait indexes of its previous metadata to those of the new one
+fn index_traits(
+    storage: &mut dyn Storage,
+    token_id: &str,
+    previous: Option<Metadata>,
+    metadata: &Metadata,
+) -> StdResult<()> {
+    for attribute in previous.map(|previous| previous.attributes).unwrap_or_default() {
+        TRAIT_TOKENS.remove(storage, (&attribute.trait_type, &attribute.value, token_id));
+    }
+    for attribute in metadata.attributes.iter() {
+        TRAIT_TOKENS.save(storage, (&attribute.trait_type, &attribute.value, token_id), &true)?;
+    }
+
+    Ok(())
+}
+
+/// Re-creates the tokens exported from a legacy cw721 contract before the mint starts, the mint
+/// continues after the highest numeric token id imported
+pub fn import_tokens(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch: Vec<ImportedToken>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if batch.is_empty() || batch.len() > MAX_BATCH_SIZE as usize {
+        return Err(ContractError::InvalidBatchSize { max: MAX_BATCH_SIZE });
+    }
+    // The placeholder uri would replace the uri of the imported tokens
+    if DELAYED_REVEAL.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::InvalidConfig {
+            reason: "cannot import tokens in a collection with a delayed reveal".to_string(),
+        });
+    }
+    if current_phase(deps.storage, env.block.time.seconds())?.is_some() {
+        return Err(ContractError::InvalidConfig { reason: "cannot import tokens once the mint started".to_string() });
+    }
+
+    let max_supply = MINT_CONFIG.load(deps.storage)?.max_supply;
+    let mut minted = MINT_COUNT.load(deps.storage)?;
+    let base = Cw721BaseContract::default();
+    let count = batch.len();
+    for token in batch {
+        // The numeric token ids would collide with the ids of the minted tokens
+        if let Ok(id) = token.token_id.parse::<u64>() {
+            if id > max_supply {
+                return Err(ContractError::InvalidConfig {
+                    reason: format!("token id {} exceeds the max supply", token.token_id),
+                });
+            }
+            minted = minted.max(id);
+        }
+        if let Some(metadata) = &token.metadata {
+            validate_metadata(metadata)?;
+            index_traits(deps.storage, &token.token_id, None, metadata)?;
+        }
+
+        let mint_info = MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: vec![],
+        };
+        base.mint(deps.branch(), env.clone(), mint_info, MintMsg {
+            token_id: token.token_id,
+            owner: token.owner,
+            token_uri: token.token_uri,
+            extension: token.metadata,
+        })?;
+    }
+    MINT_COUNT.save(deps.storage, &minted)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "import_tokens")
+        .add_attribute("count", count.to_string()))
+}
+
+/// Updates the collection royalty, or the royalty of the token if given
+pub fn update_royalty(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: Option<String>,
+    royalty: Option<RoyaltyMsg>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let royalty = royalty.map(|royalty| validate_royalty(deps.as_ref(), royalty)).transpose()?;
+    let share = royalty.as_ref().map(|royalty| royalty.share).unwrap_or_else(Decimal::zero);
+    match (&token_id, royalty) {
+        (None, Some(royalty)) => ROYALTY.save(deps.storage, &royalty)?,
+        (None, None) => ROYALTY.remove(deps.storage),
+        (Some(token_id), Some(royalty)) => TOKEN_ROYALTIES.save(deps.storage, token_id, &royalty)?,
+        (Some(token_id), None) => TOKEN_ROYALTIES.remove(deps.storage, token_id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_royalty")
+        .add_attribute("token_id", token_id.unwrap_or_default())
+        .add_attribute("share", share.to_string()))
+}
+
+pub fn update_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    phase: String,
+    addresses: Vec<String>,
+    allowlisted: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let stored = MINT_PHASES
+        .load(deps.storage)?
+        .iter()
+        .any(|p| p.name == phase && p.allowlist == Allowlist::Stored {});
+    if !stored {
+        return Err(ContractError::InvalidConfig {
+            reason: format!("phase {} does not use a stored allowlist", phase),
+        });
+    }
+
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if allowlisted {
+            ALLOWLISTS.save(deps.storage, (&phase, &address), &true)?;
+        } else {
+            ALLOWLISTS.remove(deps.storage, (&phase, &address));
+        }
+    }
+
+    let action = if allowlisted { "add_to_allowlist" } else { "remove_from_allowlist" };
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("phase", phase)
+        .add_attribute("addresses", addresses.len().to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let base = Cw721BaseContract::default();
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::MintInfo {} => to_binary(&query_mint_info(deps)?),
+        QueryMsg::MintPhases {} => to_binary(&query_mint_phases(deps, env)?),
+        QueryMsg::MintEligibility { address, proof } => {
+            to_binary(&query_mint_eligibility(deps, env, address, proof)?)
+        }
+        QueryMsg::RevealInfo {} => to_binary(&query_reveal_info(deps)?),
+        QueryMsg::RoyaltyInfo { token_id, sale_price } => {
+            to_binary(&query_royalty_info(deps, token_id, sale_price)?)
+        }
+        QueryMsg::CheckRoyalties {} => to_binary(&CheckRoyaltiesResponse { royalty_payments: true }),
+        QueryMsg::MintCount { address } => to_binary(&query_mint_count(deps, address)?),
+        QueryMsg::TokensByTrait {
+            trait_type,
+            value,
+            start_after,
+            limit,
+        } => to_binary(&query_tokens_by_trait(deps, trait_type, value, start_after, limit)?),
+        QueryMsg::HasTrait {
+            token_id,
+            trait_type,
+            value,
+        } => to_binary(&HasTraitResponse {
+            has_trait: TRAIT_TOKENS.has(deps.storage, (&trait_type, &value, &token_id)),
+        }),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => base.query(deps, env, BaseQueryMsg::OwnerOf { token_id, include_expired }),
+        QueryMsg::ApprovedForAll {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => base.query(deps, env, BaseQueryMsg::ApprovedForAll { owner, include_expired, start_after, limit }),
+        QueryMsg::NumTokens {} => base.query(deps, env, BaseQueryMsg::NumTokens {}),
+        QueryMsg::ContractInfo {} => base.query(deps, env, BaseQueryMsg::ContractInfo {}),
+        QueryMsg::NftInfo { token_id } => {
+            let mut nft_info = base.nft_info(deps, token_id.clone())?;
+            nft_info.token_uri = revealed_token_uri(deps, &token_id)?.or(nft_info.token_uri);
+            to_binary(&nft_info)
+        }
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => {
+            let mut all_nft_info = base.all_nft_info(deps, env, token_id.clone(), include_expired.unwrap_or(false))?;
+            all_nft_info.info.token_uri = revealed_token_uri(deps, &token_id)?.or(all_nft_info.info.token_uri);
+            to_binary(&all_nft_info)
+        }
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => base.query(deps, env, BaseQueryMsg::Tokens { owner, start_after, limit }),
+        QueryMsg::AllTokens { start_after, limit } => {
+            base.query(deps, env, BaseQueryMsg::AllTokens { start_after, limit })
+        }
+        QueryMsg::Minter {} => base.query(deps, env, BaseQueryMsg::Minter {}),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        creator: config.creator.to_string(),
+        base_token_uri: config.base_token_uri,
+        payment_denom: config.payment_denom,
+        protocol_fee: config.protocol_fee,
+        protocol_fee_receiver: config.protocol_fee_receiver.to_string(),
+        randomness_contract: config.randomness_contract.map(|addr| addr.to_string()),
+    })
+}
+
+pub fn query_mint_info(deps: Deps) -> StdResult<MintInfoResponse> {
+    let mint_config = MINT_CONFIG.load(deps.storage)?;
+    Ok(MintInfoResponse {
+        price: mint_config.price,
+        max_supply: mint_config.max_supply,
+        per_wallet_limit: mint_config.per_wallet_limit,
+        start_time: mint_config.start_time,
+        minted: MINT_COUNT.load(deps.storage)?,
+    })
+}
+
+pub fn query_mint_phases(deps: Deps, env: Env) -> StdResult<MintPhasesResponse> {
+    Ok(MintPhasesResponse {
+        phases: all_phases(deps.storage)?,
+        current_phase: current_phase(deps.storage, env.block.time.seconds())?.map(|phase| phase.name),
+    })
+}
+
+pub fn query_mint_eligibility(
+    deps: Deps,
+    env: Env,
+    address: String,
+    proof: Option<Vec<String>>,
+) -> StdResult<MintEligibilityResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let phase = current_phase(deps.storage, env.block.time.seconds())?;
+
+    let (eligible, remaining, reason) = match check_eligibility(deps, &env, &address, proof, 1) {
+        Ok(phase) => {
+            let mints = phase_mints(deps.storage, &phase, &address)?;
+            (true, phase.per_wallet_limit.map(|limit| limit - mints), None)
+        }
+        Err(err) => (false, None, Some(err.to_string())),
+    };
+
+    Ok(MintEligibilityResponse {
+        phase: phase.as_ref().map(|phase| phase.name.clone()),
+        price: phase.map(|phase| phase.price),
+        eligible,
+        remaining,
+        reason,
+    })
+}
+
+/// The tokens minted before the reveal keep the placeholder uri in the cw721 storage
+fn revealed_token_uri(deps: Deps, token_id: &str) -> StdResult<Option<String>> {
+    if REVEAL_OFFSET.may_load(deps.storage)?.is_none() {
+        return Ok(None);
+    }
+    let token_id = token_id
+        .parse::<u64>()
+        .map_err(|_| StdError::generic_err("invalid token id"))?;
+    Ok(Some(token_uri(deps.storage, &CONFIG.load(deps.storage)?, token_id)?))
+}
+
+pub fn query_reveal_info(deps: Deps) -> StdResult<RevealInfoResponse> {
+    let delayed_reveal = DELAYED_REVEAL.may_load(deps.storage)?;
+    let offset = REVEAL_OFFSET.may_load(deps.storage)?;
+    Ok(RevealInfoResponse {
+        placeholder_uri: delayed_reveal.as_ref().map(|reveal| reveal.placeholder_uri.clone()),
+        provenance_hash: delayed_reveal.map(|reveal| reveal.provenance_hash),
+        pending: PENDING_REVEAL.may_load(deps.storage)?.is_some(),
+        revealed: offset.is_some(),
+        offset,
+    })
+}
+
+pub fn query_royalty_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse> {
+    let royalty = match TOKEN_ROYALTIES.may_load(deps.storage, &token_id)? {
+        Some(royalty) => Some(royalty),
+        None => ROYALTY.may_load(deps.storage)?,
+    };
+
+    Ok(match royalty {
+        Some(royalty) => RoyaltiesInfoResponse {
+            address: royalty.receiver.to_string(),
+            royalty_amount: sale_price * royalty.share,
+        },
+        None => RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        },
+    })
+}
+
+pub fn query_mint_count(deps: Deps, address: String) -> StdResult<MintCountResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let count = WALLET_MINTS.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(MintCountResponse { count })
+}
+
+pub fn query_tokens_by_trait(
+    deps: Deps,
+    trait_type: String,
+    value: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let tokens: StdResult<Vec<String>> = TRAIT_TOKENS
+        .prefix((&trait_type, &value))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            String::from_utf8(k).map_err(|_| StdError::generic_err("Corrupted token key"))
+        })
+        .collect();
+
+    Ok(TokensResponse { tokens: tokens? })
+}
+
+fn must_pay_exactly(info: &MessageInfo, denom: &str, price: Uint128) -> Result<(), ContractError> {
+    let paid = match info.funds.as_slice() {
+        [] => price.is_zero(),
+        [coin] => coin.denom == denom && coin.amount == price,
+        _ => false,
+    };
+    if !paid {
+        return Err(ContractError::InvalidPayment { price: format!("{}{}", price, denom) });
+    }
+
+    Ok(())
+}
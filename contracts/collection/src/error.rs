@@ -0,0 +1,54 @@
+use cosmwasm_std::{OverflowError, StdError};
+use hex::FromHexError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("{0}")]
+    Base(#[from] cw721_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid collection configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Minting has not started yet")]
+    MintNotStarted {},
+
+    #[error("The collection is sold out")]
+    SoldOut {},
+
+    #[error("Only {remaining} tokens are left to mint")]
+    NotEnoughSupply { remaining: u64 },
+
+    #[error("A batch must contain between 1 and {max} tokens")]
+    InvalidBatchSize { max: u32 },
+
+    #[error("Cannot mint more than {limit} tokens per wallet")]
+    WalletLimitReached { limit: u32 },
+
+    #[error("Not in the allowlist of the {phase} phase")]
+    NotAllowlisted { phase: String },
+
+    #[error("Invalid metadata: {reason}")]
+    InvalidMetadata { reason: String },
+
+    #[error("The metadata is already revealed")]
+    AlreadyRevealed {},
+
+    #[error("The base token uri does not match the provenance hash")]
+    ProvenanceMismatch {},
+
+    #[error("Must pay exactly {price}")]
+    InvalidPayment { price: String },
+}
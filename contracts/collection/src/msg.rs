@@ -0,0 +1,317 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, Decimal, Uint128};
+use cw721::Expiration;
+
+use crate::state::{DelayedReveal, Metadata, MintConfig, MintPhase};
+
+/// This structure describes the parameters used for creating a collection contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    /// The creator address, defaults to the sender
+    pub creator: Option<String>,
+    /// The token uri is the base token uri followed by the token id
+    pub base_token_uri: String,
+    /// The native denom the mint price is paid in
+    pub payment_denom: String,
+    pub mint_config: MintConfig,
+    /// Mints the tokens with a placeholder uri, the base token uri is set at reveal
+    pub delayed_reveal: Option<DelayedReveal>,
+    /// The royalty of all the tokens, none if unset
+    pub royalty: Option<RoyaltyMsg>,
+    /// The randomness contract drawing the reveal offset, the collection must be one of its consumers
+    pub randomness_contract: Option<String>,
+    /// The share of the mint proceeds sent to the protocol fee receiver
+    pub protocol_fee: Decimal,
+    pub protocol_fee_receiver: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyMsg {
+    pub receiver: String,
+    /// The share of the sale price
+    pub share: Decimal,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Mints the next token to the sender in the current phase, its price must be sent along
+    Mint {
+        /// Hex encoded merkle proof of the sender, for merkle allowlist phases
+        proof: Option<Vec<String>>,
+    },
+    /// Mints the next tokens to the sender in the current phase, the price of each token must be sent along
+    MintBatch {
+        count: u32,
+        /// Hex encoded merkle proof of the sender, for merkle allowlist phases
+        proof: Option<Vec<String>>,
+    },
+    /// Updates the public mint. Only the creator can execute it.
+    UpdateMintConfig {
+        price: Option<Uint128>,
+        max_supply: Option<u64>,
+        per_wallet_limit: Option<u32>,
+        start_time: Option<u64>,
+    },
+    /// Replaces the allowlist phases. Only the creator can execute it.
+    SetMintPhases { phases: Vec<MintPhase> },
+    /// Adds addresses to the stored allowlist of a phase. Only the creator can execute it.
+    AddToAllowlist {
+        phase: String,
+        addresses: Vec<String>,
+    },
+    /// Removes addresses from the stored allowlist of a phase. Only the creator can execute it.
+    RemoveFromAllowlist {
+        phase: String,
+        addresses: Vec<String>,
+    },
+    /// Sets the base token uri committed by the provenance hash and shifts the metadata of the tokens
+    /// by the offset. Only the creator can execute it.
+    Reveal {
+        base_uri: String,
+        /// Drawn by the randomness contract when unset, derived from the block without one
+        offset: Option<u64>,
+    },
+    /// Completes the pending reveal with the random offset. Only the randomness contract can execute it.
+    ReceiveRandomness { round_id: u64, randomness: String },
+    /// Sets or removes the collection royalty. Only the creator can execute it.
+    UpdateRoyalty { royalty: Option<RoyaltyMsg> },
+    /// Sets or removes the royalty of a token, overriding the collection royalty.
+    /// Only the creator can execute it.
+    SetTokenRoyalty {
+        token_id: String,
+        royalty: Option<RoyaltyMsg>,
+    },
+    /// Sets the on-chain metadata of a token and indexes its traits. Only the creator can execute it.
+    SetMetadata {
+        token_id: String,
+        metadata: Metadata,
+    },
+    /// Re-creates tokens exported from a legacy cw721 contract, keeping their ids. Only the creator can
+    /// execute it, before the mint starts.
+    ImportTokens { batch: Vec<ImportedToken> },
+    /// Transfers a token to another account without triggering actions
+    TransferNft { recipient: String, token_id: String },
+    /// Transfers several tokens without triggering actions
+    TransferBatch { transfers: Vec<TransferMsg> },
+    /// Transfers a token to a contract and triggers an action on the receiving contract
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Allows the spender to transfer the token
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Removes the approval of the spender on the token
+    Revoke { spender: String, token_id: String },
+    /// Allows the operator to transfer any token of the sender
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    /// Removes the operator approval
+    RevokeAll { operator: String },
+}
+
+/// A token exported from a legacy cw721 contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ImportedToken {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferMsg {
+    pub recipient: String,
+    pub token_id: String,
+}
+
+/// The messages executed on the randomness contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessExecuteMsg {
+    Request {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the collection configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the public mint parameters and the minted supply.
+    /// Return type: MintInfoResponse.
+    MintInfo {},
+    /// Returns the mint phases and the current one.
+    /// Return type: MintPhasesResponse.
+    MintPhases {},
+    /// Returns whether the address can mint in the current phase, and at which price.
+    /// Return type: MintEligibilityResponse.
+    MintEligibility {
+        address: String,
+        /// Hex encoded merkle proof of the address, for merkle allowlist phases
+        proof: Option<Vec<String>>,
+    },
+    /// Returns the delayed reveal and the offset once revealed.
+    /// Return type: RevealInfoResponse.
+    RevealInfo {},
+    /// Returns the royalty receiver and amount owed on a sale of the token at the sale price.
+    /// Return type: RoyaltiesInfoResponse.
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    /// Returns whether the collection implements royalties.
+    /// Return type: CheckRoyaltiesResponse.
+    CheckRoyalties {},
+    /// Returns the number of tokens minted by the address.
+    /// Return type: MintCountResponse.
+    MintCount { address: String },
+    /// Returns the tokens having the trait value.
+    /// Return type: TokensResponse.
+    TokensByTrait {
+        trait_type: String,
+        value: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether the token has the trait value.
+    /// Return type: HasTraitResponse.
+    HasTrait {
+        token_id: String,
+        trait_type: String,
+        value: String,
+    },
+    /// Returns the owner of the token.
+    /// Return type: OwnerOfResponse.
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns the operators of the owner.
+    /// Return type: ApprovedForAllResponse.
+    ApprovedForAll {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the number of tokens.
+    /// Return type: NumTokensResponse.
+    NumTokens {},
+    /// Returns the collection name and symbol.
+    /// Return type: ContractInfoResponse.
+    ContractInfo {},
+    /// Returns the token uri and on-chain metadata, the placeholder uri until the reveal.
+    /// Return type: NftInfoResponse.
+    NftInfo { token_id: String },
+    /// Returns the token info and its owner.
+    /// Return type: AllNftInfoResponse.
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns the tokens of the owner.
+    /// Return type: TokensResponse.
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns all the tokens.
+    /// Return type: TokensResponse.
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the minter of the tokens, the collection itself.
+    /// Return type: MinterResponse.
+    Minter {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub creator: String,
+    pub base_token_uri: String,
+    pub payment_denom: String,
+    pub protocol_fee: Decimal,
+    pub protocol_fee_receiver: String,
+    pub randomness_contract: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintInfoResponse {
+    pub price: Uint128,
+    pub max_supply: u64,
+    pub per_wallet_limit: Option<u32>,
+    pub start_time: u64,
+    pub minted: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintCountResponse {
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HasTraitResponse {
+    pub has_trait: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintPhasesResponse {
+    /// The allowlist phases and the public phase, by start time
+    pub phases: Vec<MintPhase>,
+    pub current_phase: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintEligibilityResponse {
+    pub phase: Option<String>,
+    pub price: Option<Uint128>,
+    pub eligible: bool,
+    /// The number of tokens the address can still mint in the phase, unlimited if unset
+    pub remaining: Option<u32>,
+    /// Why the address cannot mint
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RevealInfoResponse {
+    pub placeholder_uri: Option<String>,
+    pub provenance_hash: Option<String>,
+    /// Waiting for the random offset of the randomness contract
+    pub pending: bool,
+    pub revealed: bool,
+    pub offset: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltiesInfoResponse {
+    /// Empty if the token has no royalty
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
@@ -0,0 +1,131 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw721_base::Cw721Contract;
+use cw_storage_plus::{Item, Map};
+
+/// The on-chain metadata of the tokens, set by the creator
+pub type Extension = Option<Metadata>;
+
+/// The cw721 contract keeping the tokens, the collection itself is its minter
+pub type Cw721BaseContract<'a> = Cw721Contract<'a, Extension, Empty>;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Manages the collection and receives the mint proceeds
+    pub creator: Addr,
+    /// The token uri is the base token uri followed by the token id
+    pub base_token_uri: String,
+    /// The native denom the mint price is paid in
+    pub payment_denom: String,
+    /// The share of the mint proceeds sent to the protocol fee receiver
+    pub protocol_fee: Decimal,
+    pub protocol_fee_receiver: Addr,
+    /// The randomness contract drawing the reveal offset
+    pub randomness_contract: Option<Addr>,
+}
+
+/// The public mint, it counts the mints of all phases against the wallet limit
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintConfig {
+    /// The price of a token in the payment denom
+    pub price: Uint128,
+    pub max_supply: u64,
+    /// The maximum number of tokens minted by a wallet, unlimited if unset
+    pub per_wallet_limit: Option<u32>,
+    /// Minting opens at this timestamp in seconds
+    pub start_time: u64,
+}
+
+pub const PUBLIC_PHASE: &str = "public";
+
+impl MintConfig {
+    pub fn public_phase(&self) -> MintPhase {
+        MintPhase {
+            name: PUBLIC_PHASE.to_string(),
+            start_time: self.start_time,
+            price: self.price,
+            per_wallet_limit: self.per_wallet_limit,
+            allowlist: Allowlist::Public {},
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Allowlist {
+    /// Anyone can mint
+    Public {},
+    /// The addresses are the leaves of the tree, the minters send their proof
+    MerkleRoot {
+        /// Hex encoded sha256 merkle root
+        root: String,
+    },
+    /// The addresses added by the creator
+    Stored {},
+}
+
+/// A mint phase lasts from its start time until the next phase starts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintPhase {
+    pub name: String,
+    pub start_time: u64,
+    /// The price of a token in the payment denom
+    pub price: Uint128,
+    /// The maximum number of tokens minted by a wallet during the phase, unlimited if unset
+    pub per_wallet_limit: Option<u32>,
+    pub allowlist: Allowlist,
+}
+
+/// The tokens show the placeholder uri until the creator reveals the metadata
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelayedReveal {
+    pub placeholder_uri: String,
+    /// Hex encoded sha256 hash of the revealed base token uri, committed before the mint
+    pub provenance_hash: String,
+}
+
+/// The royalty owed to the receiver on secondary sales
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Royalty {
+    pub receiver: Addr,
+    /// The share of the sale price
+    pub share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// At most one trait of each type
+    pub attributes: Vec<Trait>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const MINT_CONFIG: Item<MintConfig> = Item::new("mint_config");
+/// The last minted token id, the mint continues after the highest imported numeric token id
+pub const MINT_COUNT: Item<u64> = Item::new("mint_count");
+pub const WALLET_MINTS: Map<&Addr, u32> = Map::new("wallet_mints");
+/// The allowlist phases, the public mint is added as the phase named "public"
+pub const MINT_PHASES: Item<Vec<MintPhase>> = Item::new("mint_phases");
+pub const PHASE_MINTS: Map<(&str, &Addr), u32> = Map::new("phase_mints");
+pub const ALLOWLISTS: Map<(&str, &Addr), bool> = Map::new("allowlists");
+pub const DELAYED_REVEAL: Item<DelayedReveal> = Item::new("delayed_reveal");
+/// The base token uri of a reveal waiting for the random offset of the randomness contract
+pub const PENDING_REVEAL: Item<String> = Item::new("pending_reveal");
+/// Fixed at reveal time, the token n shows the metadata (n - 1 + offset) % max_supply + 1
+pub const REVEAL_OFFSET: Item<u64> = Item::new("reveal_offset");
+/// The collection royalty, the tokens without their own royalty use it
+pub const ROYALTY: Item<Royalty> = Item::new("royalty");
+pub const TOKEN_ROYALTIES: Map<&str, Royalty> = Map::new("token_royalties");
+/// The tokens having each (trait type, value), indexed from the on-chain metadata
+pub const TRAIT_TOKENS: Map<(&str, &str, &str), bool> = Map::new("trait_tokens");
@@ -0,0 +1,594 @@
+use cosmwasm_std::{
+    attr, BankMsg, coins, Decimal, Deps, DepsMut, Env, from_binary, Response, SubMsg, to_binary, WasmMsg,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_dependencies, mock_env, mock_info};
+use cw721::{NftInfoResponse, OwnerOfResponse, TokensResponse};
+use cw721_base::MinterResponse;
+use sha2::{Digest, Sha256};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, HasTraitResponse, ImportedToken, InstantiateMsg, MintCountResponse,
+    MintEligibilityResponse, MintInfoResponse, MintPhasesResponse, QueryMsg, RandomnessExecuteMsg,
+    RevealInfoResponse, RoyaltiesInfoResponse, RoyaltyMsg, TransferMsg,
+};
+use crate::state::{Allowlist, DelayedReveal, Extension, Metadata, MintConfig, MintPhase, Trait};
+
+const CREATOR: &str = "mock_creator";
+const FEE_RECEIVER: &str = "mock_fee_receiver";
+const RANDOMNESS: &str = "mock_randomness";
+const DENOM: &str = "uusd";
+const PRICE: u128 = 10_000_000;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    instantiate_with_reveal(deps, env, None, None)
+}
+
+fn instantiate_with_reveal(
+    deps: DepsMut,
+    env: Env,
+    delayed_reveal: Option<DelayedReveal>,
+    randomness_contract: Option<String>,
+) -> Response {
+    let msg = InstantiateMsg {
+        name: "Luart Genesis".to_string(),
+        symbol: "GENESIS".to_string(),
+        creator: Some(CREATOR.to_string()),
+        base_token_uri: "ipfs://genesis/".to_string(),
+        payment_denom: DENOM.to_string(),
+        mint_config: MintConfig {
+            price: PRICE.into(),
+            max_supply: 3,
+            per_wallet_limit: Some(2),
+            start_time: env.block.time.seconds() + 100,
+        },
+        protocol_fee: Decimal::percent(5),
+        protocol_fee_receiver: FEE_RECEIVER.to_string(),
+        delayed_reveal,
+        royalty: Some(RoyaltyMsg {
+            receiver: CREATOR.to_string(),
+            share: Decimal::percent(5),
+        }),
+        randomness_contract,
+    };
+    instantiate(deps, env, mock_info("deployer", &[]), msg).unwrap()
+}
+
+#[test]
+fn test_mint() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send { to_address: FEE_RECEIVER.to_string(), amount: coins(500_000, DENOM) }),
+        SubMsg::new(BankMsg::Send { to_address: CREATOR.to_string(), amount: coins(9_500_000, DENOM) }),
+    ]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+        token_id: "1".to_string(),
+        include_expired: None,
+    }).unwrap();
+    let owner: OwnerOfResponse = from_binary(&res).unwrap();
+    assert_eq!(owner.owner, "buyer");
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::NftInfo { token_id: "1".to_string() }).unwrap();
+    let info: NftInfoResponse<Extension> = from_binary(&res).unwrap();
+    assert_eq!(info.token_uri, Some("ipfs://genesis/1".to_string()));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Minter {}).unwrap();
+    let minter: MinterResponse = from_binary(&res).unwrap();
+    assert_eq!(minter.minter, MOCK_CONTRACT_ADDR);
+
+    // The collection is sold out after the max supply
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("other", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("late", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::MintInfo {}).unwrap();
+    let mint_info: MintInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(mint_info.minted, 3);
+}
+
+#[test]
+fn test_batches() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]),
+                      ExecuteMsg::MintBatch { count: 0, proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBatchSize { max: 30 });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(3 * PRICE, DENOM)),
+                      ExecuteMsg::MintBatch { count: 3, proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::WalletLimitReached { limit: 2 });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::MintBatch { count: 2, proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { price: "20000000uusd".to_string() });
+    let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(2 * PRICE, DENOM)),
+                      ExecuteMsg::MintBatch { count: 2, proof: None }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send { to_address: FEE_RECEIVER.to_string(), amount: coins(1_000_000, DENOM) }),
+        SubMsg::new(BankMsg::Send { to_address: CREATOR.to_string(), amount: coins(19_000_000, DENOM) }),
+    ]);
+    assert_eq!(res.attributes[3..5], [attr("token_id", "1"), attr("token_id", "2")]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other", &coins(2 * PRICE, DENOM)),
+                      ExecuteMsg::MintBatch { count: 2, proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughSupply { remaining: 1 });
+
+    let transfers = vec![
+        TransferMsg { recipient: "friend".to_string(), token_id: "1".to_string() },
+        TransferMsg { recipient: "friend".to_string(), token_id: "2".to_string() },
+    ];
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other", &[]),
+                      ExecuteMsg::TransferBatch { transfers: transfers.clone() }).unwrap_err();
+    assert_eq!(err, ContractError::Base(cw721_base::ContractError::Unauthorized {}));
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::TransferBatch { transfers }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Tokens {
+        owner: "friend".to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let tokens: TokensResponse = from_binary(&res).unwrap();
+    assert_eq!(tokens.tokens, vec!["1", "2"]);
+}
+
+#[test]
+fn test_mint_conditions() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::MintNotStarted {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE + 1, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { price: "10000000uusd".to_string() });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, "uluna")),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { price: "10000000uusd".to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::WalletLimitReached { limit: 2 });
+
+    let res = query(deps.as_ref(), env, QueryMsg::MintCount { address: "buyer".to_string() }).unwrap();
+    let mint_count: MintCountResponse = from_binary(&res).unwrap();
+    assert_eq!(mint_count.count, 2);
+}
+
+#[test]
+fn test_update_mint_config() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let update_msg = ExecuteMsg::UpdateMintConfig {
+        price: Some(0u128.into()),
+        max_supply: Some(10),
+        per_wallet_limit: None,
+        start_time: Some(env.block.time.seconds()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), update_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), update_msg).unwrap();
+
+    // A free mint sends no payment
+    let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]),
+                      ExecuteMsg::Mint { proof: None }).unwrap();
+    assert!(res.messages.is_empty());
+
+    // The holder transfers through the collection
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::TransferNft {
+        recipient: "friend".to_string(),
+        token_id: "1".to_string(),
+    }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::OwnerOf {
+        token_id: "1".to_string(),
+        include_expired: None,
+    }).unwrap();
+    let owner: OwnerOfResponse = from_binary(&res).unwrap();
+    assert_eq!(owner.owner, "friend");
+}
+
+fn leaf_hash(address: &str) -> Vec<u8> {
+    Sha256::digest(address.as_bytes()).to_vec()
+}
+
+fn mint_phases(env: &Env, merkle_root: String) -> Vec<MintPhase> {
+    vec![
+        MintPhase {
+            name: "og".to_string(),
+            start_time: env.block.time.seconds(),
+            price: 0u128.into(),
+            per_wallet_limit: Some(1),
+            allowlist: Allowlist::MerkleRoot { root: merkle_root },
+        },
+        MintPhase {
+            name: "allowlist".to_string(),
+            start_time: env.block.time.seconds() + 50,
+            price: (PRICE / 2).into(),
+            per_wallet_limit: None,
+            allowlist: Allowlist::Stored {},
+        },
+    ]
+}
+
+fn query_eligibility(deps: Deps, env: &Env, address: &str, proof: Option<Vec<String>>)
+                     -> MintEligibilityResponse {
+    let res = query(deps, env.clone(), QueryMsg::MintEligibility { address: address.to_string(), proof }).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn test_mint_phases() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // The merkle tree of the og phase holds two addresses
+    let mut leaves = [leaf_hash("og_holder"), leaf_hash("og_friend")];
+    leaves.sort_unstable();
+    let merkle_root = hex::encode(Sha256::digest(&leaves.concat()));
+    let og_proof = Some(vec![hex::encode(leaf_hash("og_friend"))]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::SetMintPhases { phases: mint_phases(&env, merkle_root.clone()) }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let mut invalid_phases = mint_phases(&env, merkle_root.clone());
+    invalid_phases[1].name = "public".to_string();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::SetMintPhases { phases: invalid_phases }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig { reason: "invalid or duplicated phase name: public".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+            ExecuteMsg::SetMintPhases { phases: mint_phases(&env, merkle_root) }).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::MintPhases {}).unwrap();
+    let phases: MintPhasesResponse = from_binary(&res).unwrap();
+    assert_eq!(phases.phases.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), vec!["og", "allowlist", "public"]);
+    assert_eq!(phases.current_phase, Some("og".to_string()));
+
+    // Og phase, a free mint for the merkle tree addresses
+    let eligibility = query_eligibility(deps.as_ref(), &env, "og_holder", og_proof.clone());
+    assert_eq!(eligibility, MintEligibilityResponse {
+        phase: Some("og".to_string()),
+        price: Some(0u128.into()),
+        eligible: true,
+        remaining: Some(1),
+        reason: None,
+    });
+    let eligibility = query_eligibility(deps.as_ref(), &env, "stranger", og_proof.clone());
+    assert!(!eligibility.eligible);
+    assert_eq!(eligibility.reason, Some("Not in the allowlist of the og phase".to_string()));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("og_holder", &[]),
+                      ExecuteMsg::Mint { proof: og_proof.clone() }).unwrap();
+    assert!(res.messages.is_empty());
+    let err = execute(deps.as_mut(), env.clone(), mock_info("og_holder", &[]),
+                      ExecuteMsg::Mint { proof: og_proof.clone() }).unwrap_err();
+    assert_eq!(err, ContractError::WalletLimitReached { limit: 1 });
+
+    // Allowlist phase, the creator stores the addresses
+    env.block.time = env.block.time.plus_seconds(50);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::AddToAllowlist {
+        phase: "og".to_string(),
+        addresses: vec!["buyer".to_string()],
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig { reason: "phase og does not use a stored allowlist".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::AddToAllowlist {
+        phase: "allowlist".to_string(),
+        addresses: vec!["og_holder".to_string(), "buyer".to_string()],
+    }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("og_friend", &[]),
+                      ExecuteMsg::Mint { proof: og_proof }).unwrap_err();
+    assert_eq!(err, ContractError::NotAllowlisted { phase: "allowlist".to_string() });
+    let res = execute(deps.as_mut(), env.clone(), mock_info("og_holder", &coins(PRICE / 2, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap();
+    assert_eq!(res.attributes[2], attr("phase", "allowlist"));
+
+    // Public phase, the og and allowlist mints count against the wallet limit
+    env.block.time = env.block.time.plus_seconds(50);
+    let eligibility = query_eligibility(deps.as_ref(), &env, "og_holder", None);
+    assert_eq!(eligibility.phase, Some("public".to_string()));
+    assert_eq!(eligibility.reason, Some("Cannot mint more than 2 tokens per wallet".to_string()));
+    let eligibility = query_eligibility(deps.as_ref(), &env, "stranger", None);
+    assert_eq!(eligibility.remaining, Some(2));
+    execute(deps.as_mut(), env, mock_info("stranger", &coins(PRICE, DENOM)), ExecuteMsg::Mint { proof: None })
+        .unwrap();
+}
+
+fn query_token_uri(deps: Deps, env: &Env, token_id: &str) -> Option<String> {
+    let res = query(deps, env.clone(), QueryMsg::NftInfo { token_id: token_id.to_string() }).unwrap();
+    let info: NftInfoResponse<Extension> = from_binary(&res).unwrap();
+    info.token_uri
+}
+
+#[test]
+fn test_delayed_reveal() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    let base_uri = "ipfs://revealed";
+    instantiate_with_reveal(deps.as_mut(), env.clone(), Some(DelayedReveal {
+        placeholder_uri: "ipfs://placeholder.json".to_string(),
+        provenance_hash: hex::encode(Sha256::digest(base_uri.as_bytes())),
+    }), None);
+    env.block.time = env.block.time.plus_seconds(100);
+
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    assert_eq!(query_token_uri(deps.as_ref(), &env, "1"), Some("ipfs://placeholder.json".to_string()));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Reveal {
+        base_uri: base_uri.to_string(),
+        offset: Some(2),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Reveal {
+        base_uri: "ipfs://other".to_string(),
+        offset: Some(2),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::ProvenanceMismatch {});
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Reveal {
+        base_uri: base_uri.to_string(),
+        offset: Some(2),
+    }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Reveal {
+        base_uri: base_uri.to_string(),
+        offset: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRevealed {});
+
+    // The metadata are shifted by the offset modulo the max supply
+    assert_eq!(query_token_uri(deps.as_ref(), &env, "1"), Some("ipfs://revealed/3".to_string()));
+    assert_eq!(query_token_uri(deps.as_ref(), &env, "2"), Some("ipfs://revealed/1".to_string()));
+    execute(deps.as_mut(), env.clone(), mock_info("other", &coins(PRICE, DENOM)),
+            ExecuteMsg::Mint { proof: None }).unwrap();
+    assert_eq!(query_token_uri(deps.as_ref(), &env, "3"), Some("ipfs://revealed/2".to_string()));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::UpdateMintConfig {
+        price: None,
+        max_supply: Some(10),
+        per_wallet_limit: None,
+        start_time: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRevealed {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::RevealInfo {}).unwrap();
+    let reveal_info: RevealInfoResponse = from_binary(&res).unwrap();
+    assert!(reveal_info.revealed);
+    assert_eq!(reveal_info.offset, Some(2));
+}
+
+#[test]
+fn test_randomness_reveal() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let base_uri = "ipfs://revealed";
+    instantiate_with_reveal(deps.as_mut(), env.clone(), Some(DelayedReveal {
+        placeholder_uri: "ipfs://placeholder.json".to_string(),
+        provenance_hash: hex::encode(Sha256::digest(base_uri.as_bytes())),
+    }), Some(RANDOMNESS.to_string()));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Reveal {
+        base_uri: base_uri.to_string(),
+        offset: None,
+    }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: RANDOMNESS.to_string(),
+        msg: to_binary(&RandomnessExecuteMsg::Request {}).unwrap(),
+        funds: vec![],
+    })]);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::RevealInfo {}).unwrap();
+    let reveal_info: RevealInfoResponse = from_binary(&res).unwrap();
+    assert!(reveal_info.pending);
+    assert!(!reveal_info.revealed);
+
+    // The first 8 bytes of the random value give the offset, modulo the max supply
+    let mut randomness = [0u8; 32];
+    randomness[7] = 4;
+    let receive_msg = ExecuteMsg::ReceiveRandomness {
+        round_id: 1,
+        randomness: hex::encode(randomness),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), receive_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RANDOMNESS, &[]), receive_msg.clone()).unwrap();
+    assert!(res.attributes.contains(&attr("offset", "1")));
+    let err = execute(deps.as_mut(), env.clone(), mock_info(RANDOMNESS, &[]), receive_msg).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRevealed {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::RevealInfo {}).unwrap();
+    let reveal_info: RevealInfoResponse = from_binary(&res).unwrap();
+    assert!(!reveal_info.pending);
+    assert_eq!(reveal_info.offset, Some(1));
+}
+
+fn metadata(traits: &[(&str, &str)]) -> Metadata {
+    Metadata {
+        name: Some("Genesis".to_string()),
+        attributes: traits.iter().map(|(trait_type, value)| Trait {
+            display_type: None,
+            trait_type: trait_type.to_string(),
+            value: value.to_string(),
+        }).collect(),
+        ..Metadata::default()
+    }
+}
+
+fn query_tokens_by_trait(deps: Deps, env: &Env, trait_type: &str, value: &str) -> Vec<String> {
+    let res = query(deps, env.clone(), QueryMsg::TokensByTrait {
+        trait_type: trait_type.to_string(),
+        value: value.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let tokens: TokensResponse = from_binary(&res).unwrap();
+    tokens.tokens
+}
+
+#[test]
+fn test_trait_metadata() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    env.block.time = env.block.time.plus_seconds(100);
+    for buyer in ["buyer", "buyer", "other"] {
+        execute(deps.as_mut(), env.clone(), mock_info(buyer, &coins(PRICE, DENOM)),
+                ExecuteMsg::Mint { proof: None }).unwrap();
+    }
+
+    let set_metadata = |token_id: &str, traits: &[(&str, &str)]| ExecuteMsg::SetMetadata {
+        token_id: token_id.to_string(),
+        metadata: metadata(traits),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), set_metadata("1", &[("hat", "red")]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      set_metadata("1", &[("hat", "red"), ("hat", "blue")])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidMetadata { reason: "duplicated trait type: hat".to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+            set_metadata("1", &[("hat", "red"), ("eyes", "green")])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), set_metadata("2", &[("hat", "blue")])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), set_metadata("3", &[("hat", "red")])).unwrap();
+    assert_eq!(query_tokens_by_trait(deps.as_ref(), &env, "hat", "red"), vec!["1", "3"]);
+
+    // Replacing the metadata moves the token between the trait indexes
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), set_metadata("1", &[("hat", "blue")])).unwrap();
+    assert_eq!(query_tokens_by_trait(deps.as_ref(), &env, "hat", "red"), vec!["3"]);
+    assert_eq!(query_tokens_by_trait(deps.as_ref(), &env, "hat", "blue"), vec!["1", "2"]);
+    assert!(query_tokens_by_trait(deps.as_ref(), &env, "eyes", "green").is_empty());
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::HasTrait {
+        token_id: "2".to_string(),
+        trait_type: "hat".to_string(),
+        value: "blue".to_string(),
+    }).unwrap();
+    let has_trait: HasTraitResponse = from_binary(&res).unwrap();
+    assert!(has_trait.has_trait);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::NftInfo { token_id: "1".to_string() }).unwrap();
+    let info: NftInfoResponse<Extension> = from_binary(&res).unwrap();
+    assert_eq!(info.extension, Some(metadata(&[("hat", "blue")])));
+}
+
+#[test]
+fn test_import_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let batch = vec![
+        ImportedToken {
+            token_id: "2".to_string(),
+            owner: "holder".to_string(),
+            token_uri: Some("ipfs://legacy/2".to_string()),
+            metadata: Some(metadata(&[("hat", "red")])),
+        },
+        ImportedToken {
+            token_id: "legacy-a".to_string(),
+            owner: "other_holder".to_string(),
+            token_uri: None,
+            metadata: Some(metadata(&[("hat", "red")])),
+        },
+    ];
+    let err = execute(deps.as_mut(), env.clone(), mock_info("holder", &[]),
+                      ExecuteMsg::ImportTokens { batch: batch.clone() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::ImportTokens { batch }).unwrap();
+    assert_eq!(query_tokens_by_trait(deps.as_ref(), &env, "hat", "red"), vec!["2", "legacy-a"]);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+        token_id: "legacy-a".to_string(),
+        include_expired: None,
+    }).unwrap();
+    let owner: OwnerOfResponse = from_binary(&res).unwrap();
+    assert_eq!(owner.owner, "other_holder");
+
+    // The mint continues after the imported token ids, until the max supply
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::ImportTokens {
+        batch: vec![ImportedToken {
+            token_id: "1".to_string(),
+            owner: "holder".to_string(),
+            token_uri: None,
+            metadata: None,
+        }],
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig { reason: "cannot import tokens once the mint started".to_string() });
+    let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap();
+    assert_eq!(res.attributes[3], attr("token_id", "3"));
+    let err = execute(deps.as_mut(), env, mock_info("buyer", &coins(PRICE, DENOM)),
+                      ExecuteMsg::Mint { proof: None }).unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+}
+
+fn query_royalty(deps: Deps, env: &Env, token_id: &str) -> RoyaltiesInfoResponse {
+    let res = query(deps, env.clone(), QueryMsg::RoyaltyInfo {
+        token_id: token_id.to_string(),
+        sale_price: 1_000_000u128.into(),
+    }).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn test_royalties() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    assert_eq!(query_royalty(deps.as_ref(), &env, "1"), RoyaltiesInfoResponse {
+        address: CREATOR.to_string(),
+        royalty_amount: 50_000u128.into(),
+    });
+
+    let token_royalty = ExecuteMsg::SetTokenRoyalty {
+        token_id: "1".to_string(),
+        royalty: Some(RoyaltyMsg {
+            receiver: "artist".to_string(),
+            share: Decimal::percent(10),
+        }),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), token_royalty.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), token_royalty).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::UpdateRoyalty {
+        royalty: Some(RoyaltyMsg {
+            receiver: CREATOR.to_string(),
+            share: Decimal::percent(101),
+        }),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig { reason: "royalty share must not exceed 1".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::UpdateRoyalty { royalty: None })
+        .unwrap();
+
+    // The token royalty overrides the collection one
+    assert_eq!(query_royalty(deps.as_ref(), &env, "1"), RoyaltiesInfoResponse {
+        address: "artist".to_string(),
+        royalty_amount: 100_000u128.into(),
+    });
+    assert_eq!(query_royalty(deps.as_ref(), &env, "2"), RoyaltiesInfoResponse {
+        address: String::new(),
+        royalty_amount: 0u128.into(),
+    });
+}
@@ -0,0 +1,243 @@
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, to_binary, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use luart_protocol::governance::ExecuteMsg as GovernanceExecuteMsg;
+use luart_protocol::pausable::{
+    ExecuteMsg as PausableExecuteMsg, PausedResponse, QueryMsg as PausableQueryMsg,
+};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ApprovalsResponse, ConfigResponse, ContractPauseStatus, CouncilAction, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, PauseStatusResponse, QueryMsg,
+};
+use crate::state::{APPROVALS, Config, CONFIG};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-council";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        governance: deps.api.addr_validate(&msg.governance)?,
+        members: validate_addresses(deps.as_ref(), &msg.members)?,
+        threshold: msg.threshold,
+        pausable_contracts: validate_addresses(deps.as_ref(), &msg.pausable_contracts)?,
+    };
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+fn validate_addresses(deps: Deps, addresses: &[String]) -> StdResult<Vec<Addr>> {
+    let mut validated: Vec<Addr> = vec![];
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if !validated.contains(&address) {
+            validated.push(address);
+        }
+    }
+    Ok(validated)
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.threshold == 0 || config.threshold > config.members.len() as u64 {
+        return Err(ContractError::InvalidConfig {
+            reason: "threshold must be positive and reachable by the members".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Veto { proposal_id } => approve(deps, info, CouncilAction::Veto { proposal_id }),
+        ExecuteMsg::Pause {} => pause(deps, info),
+        ExecuteMsg::Unpause {} => approve(deps, info, CouncilAction::Unpause {}),
+        ExecuteMsg::UpdateConfig {
+            members,
+            threshold,
+            pausable_contracts,
+        } => update_config(deps, info, members, threshold, pausable_contracts),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn approve(
+    deps: DepsMut,
+    info: MessageInfo,
+    action: CouncilAction,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.members.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = action.key();
+    let mut approvals = APPROVALS.may_load(deps.storage, &key)?.unwrap_or_default();
+    if approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    approvals.push(info.sender.clone());
+
+    let response = Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("council_action", key.clone())
+        .add_attribute("member", info.sender);
+
+    if (approvals.len() as u64) < config.threshold {
+        APPROVALS.save(deps.storage, &key, &approvals)?;
+        return Ok(response);
+    }
+
+    APPROVALS.remove(deps.storage, &key);
+    let messages = match action {
+        CouncilAction::Veto { proposal_id } => vec![execute_msg(
+            &config.governance,
+            &GovernanceExecuteMsg::VetoProposal { proposal_id },
+        )?],
+        CouncilAction::Unpause {} => pausable_msgs(&config, &PausableExecuteMsg::Unpause {})?,
+    };
+
+    Ok(response.add_messages(messages))
+}
+
+pub fn pause(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // A single member can stop the protocol, resuming it takes the threshold
+    let config = CONFIG.load(deps.storage)?;
+    if !config.members.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::new()
+        .add_messages(pausable_msgs(&config, &PausableExecuteMsg::Pause {})?)
+        .add_attribute("action", "pause")
+        .add_attribute("member", info.sender))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    members: Option<Vec<String>>,
+    threshold: Option<u64>,
+    pausable_contracts: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.governance != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(members) = members {
+        config.members = validate_addresses(deps.as_ref(), &members)?;
+
+        // Approvals of the previous members are discarded
+        let keys: Vec<Vec<u8>> = APPROVALS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect();
+        for key in keys {
+            let key = String::from_utf8(key).map_err(|_| StdError::generic_err("Corrupted approval key"))?;
+            APPROVALS.remove(deps.storage, &key);
+        }
+    }
+
+    if let Some(threshold) = threshold {
+        config.threshold = threshold;
+    }
+
+    if let Some(pausable_contracts) = pausable_contracts {
+        config.pausable_contracts = validate_addresses(deps.as_ref(), &pausable_contracts)?;
+    }
+
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Approvals { action } => to_binary(&query_approvals(deps, action)?),
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        governance: config.governance.to_string(),
+        members: config.members.iter().map(|m| m.to_string()).collect(),
+        threshold: config.threshold,
+        pausable_contracts: config.pausable_contracts.iter().map(|c| c.to_string()).collect(),
+    })
+}
+
+pub fn query_approvals(deps: Deps, action: CouncilAction) -> StdResult<ApprovalsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let approvals = APPROVALS.may_load(deps.storage, &action.key())?.unwrap_or_default();
+    Ok(ApprovalsResponse {
+        approvals: approvals.iter().map(|a| a.to_string()).collect(),
+        threshold: config.threshold,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let contracts: StdResult<Vec<ContractPauseStatus>> = config.pausable_contracts.iter()
+        .map(|contract| {
+            let res: PausedResponse = deps.querier.query_wasm_smart(contract, &PausableQueryMsg::Paused {})?;
+            Ok(ContractPauseStatus { contract: contract.to_string(), paused: res.paused })
+        })
+        .collect();
+    Ok(PauseStatusResponse { contracts: contracts? })
+}
+
+fn pausable_msgs(config: &Config, msg: &PausableExecuteMsg) -> StdResult<Vec<CosmosMsg>> {
+    config.pausable_contracts.iter()
+        .map(|contract| execute_msg(contract, msg))
+        .collect()
+}
+
+fn execute_msg<T: serde::Serialize>(contract: &Addr, msg: &T) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a council contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub governance: String,
+    pub members: Vec<String>,
+    pub threshold: u64,
+    pub pausable_contracts: Vec<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Approves the veto of a passed governance proposal, sent once the threshold is reached.
+    /// Only a member can execute it.
+    Veto { proposal_id: u64 },
+    /// Pauses all the pausable contracts at once. Any single member can execute it.
+    Pause {},
+    /// Approves unpausing all the pausable contracts, sent once the threshold is reached.
+    /// Only a member can execute it.
+    Unpause {},
+    /// Updates the council. Only the governance contract can execute it.
+    UpdateConfig {
+        members: Option<Vec<String>>,
+        threshold: Option<u64>,
+        pausable_contracts: Option<Vec<String>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CouncilAction {
+    Veto { proposal_id: u64 },
+    Unpause {},
+}
+
+impl CouncilAction {
+    /// Returns the storage key of the action approvals
+    pub fn key(&self) -> String {
+        match self {
+            CouncilAction::Veto { proposal_id } => format!("veto_{}", proposal_id),
+            CouncilAction::Unpause {} => "unpause".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the council configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the members approving a pending action.
+    /// Return type: ApprovalsResponse.
+    Approvals { action: CouncilAction },
+    /// Returns whether each pausable contract is paused.
+    /// Return type: PauseStatusResponse.
+    PauseStatus {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub governance: String,
+    pub members: Vec<String>,
+    pub threshold: u64,
+    pub pausable_contracts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<String>,
+    pub threshold: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractPauseStatus {
+    pub contract: String,
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    pub contracts: Vec<ContractPauseStatus>,
+}
@@ -0,0 +1,20 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The governance contract, it vetoes proposals for the council and manages it
+    pub governance: Addr,
+    pub members: Vec<Addr>,
+    /// The number of member approvals required to veto or unpause
+    pub threshold: u64,
+    /// The contracts implementing the pause interface
+    pub pausable_contracts: Vec<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The members approving each pending action
+pub const APPROVALS: Map<&str, Vec<Addr>> = Map::new("approvals");
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use luart_protocol::pausable::{PausedResponse, QueryMsg as PausableQueryMsg};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the paused queries of the pausable contracts.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    paused: HashMap<String, bool>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
+                Ok(PausableQueryMsg::Paused {}) => {
+                    let paused = self.paused.get(contract_addr).copied().unwrap_or_default();
+                    SystemResult::Ok(ContractResult::from(to_binary(&PausedResponse { paused })))
+                }
+                Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing pausable query: {}", e),
+                    request: msg.clone(),
+                }),
+            },
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            paused: HashMap::new(),
+        }
+    }
+
+    // configure whether a pausable contract is paused
+    pub fn with_paused(&mut self, contract: &str, paused: bool) {
+        self.paused.insert(contract.to_string(), paused);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mock_querier;
+pub mod tests;
@@ -0,0 +1,137 @@
+use cosmwasm_std::{CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, to_binary, WasmMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use luart_protocol::governance::ExecuteMsg as GovernanceExecuteMsg;
+use luart_protocol::pausable::ExecuteMsg as PausableExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ApprovalsResponse, ContractPauseStatus, CouncilAction, ExecuteMsg, InstantiateMsg, PauseStatusResponse,
+    QueryMsg,
+};
+use crate::testing::mock_querier::mock_dependencies;
+
+const GOVERNANCE: &str = "mock_governance";
+const TOKEN: &str = "mock_token";
+const MARKETPLACE: &str = "mock_marketplace";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const CAROL: &str = "carol";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        governance: GOVERNANCE.to_string(),
+        members: vec![ALICE.to_string(), BOB.to_string(), CAROL.to_string()],
+        threshold: 2,
+        pausable_contracts: vec![TOKEN.to_string(), MARKETPLACE.to_string()],
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+fn wasm_msg<T: serde::Serialize>(contract: &str, msg: &T) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_veto() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Veto { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Veto { proposal_id: 1 }).unwrap();
+    assert!(res.messages.is_empty());
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::Veto { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyApproved {});
+
+    let res = query(deps.as_ref(), env.clone(),
+                    QueryMsg::Approvals { action: CouncilAction::Veto { proposal_id: 1 } }).unwrap();
+    let approvals: ApprovalsResponse = from_binary(&res).unwrap();
+    assert_eq!(approvals, ApprovalsResponse { approvals: vec![ALICE.to_string()], threshold: 2 });
+
+    // The second approval reaches the threshold and vetoes the proposal
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Veto { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![wasm_msg(GOVERNANCE, &GovernanceExecuteMsg::VetoProposal { proposal_id: 1 })]);
+
+    let res = query(deps.as_ref(), env,
+                    QueryMsg::Approvals { action: CouncilAction::Veto { proposal_id: 1 } }).unwrap();
+    let approvals: ApprovalsResponse = from_binary(&res).unwrap();
+    assert!(approvals.approvals.is_empty());
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // A single member pauses every contract
+    let res = execute(deps.as_mut(), env.clone(), mock_info(CAROL, &[]), ExecuteMsg::Pause {}).unwrap();
+    assert_eq!(res.messages, vec![
+        wasm_msg(TOKEN, &PausableExecuteMsg::Pause {}),
+        wasm_msg(MARKETPLACE, &PausableExecuteMsg::Pause {}),
+    ]);
+
+    deps.querier.with_paused(TOKEN, true);
+    deps.querier.with_paused(MARKETPLACE, true);
+    let res: PauseStatusResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::PauseStatus {}).unwrap())
+        .unwrap();
+    assert_eq!(res.contracts, vec![
+        ContractPauseStatus { contract: TOKEN.to_string(), paused: true },
+        ContractPauseStatus { contract: MARKETPLACE.to_string(), paused: true },
+    ]);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(CAROL, &[]), ExecuteMsg::Unpause {}).unwrap();
+    assert!(res.messages.is_empty());
+    let res = execute(deps.as_mut(), env, mock_info(ALICE, &[]), ExecuteMsg::Unpause {}).unwrap();
+    assert_eq!(res.messages, vec![
+        wasm_msg(TOKEN, &PausableExecuteMsg::Unpause {}),
+        wasm_msg(MARKETPLACE, &PausableExecuteMsg::Unpause {}),
+    ]);
+}
+
+#[test]
+fn test_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Veto { proposal_id: 1 }).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        members: Some(vec![BOB.to_string(), CAROL.to_string()]),
+        threshold: None,
+        pausable_contracts: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), ExecuteMsg::UpdateConfig {
+        members: None,
+        threshold: Some(4),
+        pausable_contracts: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig {
+        reason: "threshold must be positive and reachable by the members".to_string(),
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), msg).unwrap();
+
+    // The approval of the removed member is discarded
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::Veto { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env, mock_info(BOB, &[]), ExecuteMsg::Veto { proposal_id: 1 }).unwrap();
+    assert!(res.messages.is_empty());
+}
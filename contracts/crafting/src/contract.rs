@@ -0,0 +1,358 @@
+use std::collections::HashSet;
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order, Response,
+    StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionExecuteMsg, ConfigResponse, CraftInfoResponse, CraftInput, Cw20HookMsg, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg, RecipeResponse, RecipesResponse,
+};
+use crate::state::{
+    Config, CONFIG, ConfigOwner, CRAFTED, LAST_CRAFTS, Recipe, RECIPE_COUNT, RECIPES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-crafting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+    })?;
+    RECIPE_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+fn validate_recipe(deps: Deps, recipe: &Recipe) -> Result<(), ContractError> {
+    if recipe.name.is_empty() {
+        return Err(ContractError::InvalidRecipe { reason: "name must not be empty".to_string() });
+    }
+    if recipe.inputs.is_empty() {
+        return Err(ContractError::InvalidRecipe { reason: "inputs must not be empty".to_string() });
+    }
+    let mut collections = HashSet::new();
+    for input in recipe.inputs.iter() {
+        deps.api.addr_validate(&input.collection)?;
+        if input.count == 0 || !collections.insert(input.collection.as_str()) {
+            return Err(ContractError::InvalidRecipe {
+                reason: format!("invalid or duplicated input collection: {}", input.collection),
+            });
+        }
+    }
+    deps.api.addr_validate(&recipe.output_collection)?;
+    if recipe.max_supply == Some(0) {
+        return Err(ContractError::InvalidRecipe { reason: "max supply must be positive".to_string() });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Craft { recipe_id, inputs } => {
+            craft(deps, env, info.sender, recipe_id, inputs, Uint128::zero())
+        }
+        ExecuteMsg::AddRecipe { recipe } => add_recipe(deps, info, recipe),
+        ExecuteMsg::UpdateRecipe {
+            recipe_id,
+            luart_cost,
+            cooldown,
+            max_supply,
+            enabled,
+        } => update_recipe(deps, info, recipe_id, luart_cost, cooldown, max_supply, enabled),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Craft { recipe_id, inputs } => {
+            let crafter = deps.api.addr_validate(&cw20_msg.sender)?;
+            craft(deps, env, crafter, recipe_id, inputs, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn craft(
+    deps: DepsMut,
+    env: Env,
+    crafter: Addr,
+    recipe_id: u64,
+    inputs: Vec<CraftInput>,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let recipe = RECIPES.load(deps.storage, U64Key::new(recipe_id))?;
+    if !recipe.enabled {
+        return Err(ContractError::RecipeDisabled {});
+    }
+    if paid != recipe.luart_cost {
+        return Err(ContractError::InvalidPayment { amount: recipe.luart_cost.to_string() });
+    }
+
+    let crafted = CRAFTED.may_load(deps.storage, U64Key::new(recipe_id))?.unwrap_or_default();
+    if let Some(max_supply) = recipe.max_supply {
+        if crafted >= max_supply {
+            return Err(ContractError::SoldOut {});
+        }
+    }
+
+    let now = env.block.time.seconds();
+    if let Some(last_craft_time) = LAST_CRAFTS.may_load(deps.storage, (U64Key::new(recipe_id), &crafter))? {
+        let next_craft_time = last_craft_time + recipe.cooldown;
+        if now < next_craft_time {
+            return Err(ContractError::CooldownActive { next_craft_time });
+        }
+    }
+
+    check_inputs(deps.as_ref(), &recipe, &crafter, &inputs)?;
+
+    CRAFTED.save(deps.storage, U64Key::new(recipe_id), &(crafted + 1))?;
+    LAST_CRAFTS.save(deps.storage, (U64Key::new(recipe_id), &crafter), &now)?;
+
+    // The crafting contract is an operator of the input tokens and the minter of the output collection
+    let mut messages: Vec<CosmosMsg> = inputs
+        .into_iter()
+        .map(|input| collection_msg(input.collection, CollectionExecuteMsg::Burn { token_id: input.token_id }))
+        .collect::<StdResult<_>>()?;
+    let token_id = format!("{}-{}", recipe_id, crafted + 1);
+    messages.push(collection_msg(recipe.output_collection, CollectionExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: crafter.to_string(),
+        token_uri: recipe.output_token_uri,
+        extension: None,
+    })?);
+    if !paid.is_zero() {
+        let config = CONFIG.load(deps.storage)?;
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.luart_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount: paid })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "craft")
+        .add_attribute("crafter", crafter)
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("burnt_luart", paid))
+}
+
+/// Checks the inputs are the exact recipe inputs, all held by the crafter
+fn check_inputs(
+    deps: Deps,
+    recipe: &Recipe,
+    crafter: &Addr,
+    inputs: &[CraftInput],
+) -> Result<(), ContractError> {
+    let unique: HashSet<(&str, &str)> = inputs
+        .iter()
+        .map(|input| (input.collection.as_str(), input.token_id.as_str()))
+        .collect();
+    let expected: u32 = recipe.inputs.iter().map(|input| input.count).sum();
+    if unique.len() != inputs.len() || inputs.len() != expected as usize {
+        return Err(ContractError::InvalidInputs {});
+    }
+    for recipe_input in recipe.inputs.iter() {
+        let count = inputs.iter().filter(|input| input.collection == recipe_input.collection).count();
+        if count != recipe_input.count as usize {
+            return Err(ContractError::InvalidInputs {});
+        }
+    }
+
+    for input in inputs.iter() {
+        let owner: OwnerOfResponse = deps.querier.query_wasm_smart(&input.collection, &Cw721QueryMsg::OwnerOf {
+            token_id: input.token_id.clone(),
+            include_expired: None,
+        })?;
+        if owner.owner != crafter.as_str() {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    Ok(())
+}
+
+fn collection_msg(collection: String, msg: CollectionExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: collection,
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    }))
+}
+
+pub fn add_recipe(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipe: Recipe,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+    validate_recipe(deps.as_ref(), &recipe)?;
+
+    let recipe_id = RECIPE_COUNT.load(deps.storage)? + 1;
+    RECIPE_COUNT.save(deps.storage, &recipe_id)?;
+    RECIPES.save(deps.storage, U64Key::new(recipe_id), &recipe)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_recipe")
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("name", recipe.name))
+}
+
+pub fn update_recipe(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipe_id: u64,
+    luart_cost: Option<Uint128>,
+    cooldown: Option<u64>,
+    max_supply: Option<u64>,
+    enabled: Option<bool>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut recipe = RECIPES.load(deps.storage, U64Key::new(recipe_id))?;
+    recipe.luart_cost = luart_cost.unwrap_or(recipe.luart_cost);
+    recipe.cooldown = cooldown.unwrap_or(recipe.cooldown);
+    if max_supply.is_some() {
+        recipe.max_supply = max_supply;
+    }
+    recipe.enabled = enabled.unwrap_or(recipe.enabled);
+    validate_recipe(deps.as_ref(), &recipe)?;
+    RECIPES.save(deps.storage, U64Key::new(recipe_id), &recipe)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_recipe")
+        .add_attribute("recipe_id", recipe_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Recipe { recipe_id } => to_binary(&query_recipe(deps, recipe_id)?),
+        QueryMsg::Recipes { start_after, limit } => to_binary(&query_recipes(deps, start_after, limit)?),
+        QueryMsg::CraftInfo { recipe_id, address } => {
+            to_binary(&query_craft_info(deps, recipe_id, address)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        luart_token: config.luart_token.to_string(),
+    })
+}
+
+pub fn query_recipe(deps: Deps, recipe_id: u64) -> StdResult<RecipeResponse> {
+    let recipe = RECIPES.load(deps.storage, U64Key::new(recipe_id))?;
+    recipe_response(deps, recipe_id, recipe)
+}
+
+pub fn query_recipes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RecipesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let recipes: StdResult<Vec<RecipeResponse>> = RECIPES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, recipe) = item?;
+            recipe_response(deps, parse_recipe_id(&k)?, recipe)
+        })
+        .collect();
+
+    Ok(RecipesResponse { recipes: recipes? })
+}
+
+fn recipe_response(deps: Deps, recipe_id: u64, recipe: Recipe) -> StdResult<RecipeResponse> {
+    Ok(RecipeResponse {
+        recipe_id,
+        recipe,
+        crafted: CRAFTED.may_load(deps.storage, U64Key::new(recipe_id))?.unwrap_or_default(),
+    })
+}
+
+pub fn query_craft_info(deps: Deps, recipe_id: u64, address: String) -> StdResult<CraftInfoResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let recipe = RECIPES.load(deps.storage, U64Key::new(recipe_id))?;
+    let last_craft_time = LAST_CRAFTS.may_load(deps.storage, (U64Key::new(recipe_id), &address))?;
+    Ok(CraftInfoResponse {
+        last_craft_time,
+        next_craft_time: last_craft_time.map(|time| time + recipe.cooldown),
+    })
+}
+
+fn parse_recipe_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted recipe key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
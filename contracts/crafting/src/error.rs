@@ -0,0 +1,42 @@
+use cosmwasm_std::{OverflowError, StdError};
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid recipe: {reason}")]
+    InvalidRecipe { reason: String },
+
+    #[error("The recipe is disabled")]
+    RecipeDisabled {},
+
+    #[error("The recipe supply is exhausted")]
+    SoldOut {},
+
+    #[error("The recipe can be crafted again at {next_craft_time}")]
+    CooldownActive { next_craft_time: u64 },
+
+    #[error("The inputs do not match the recipe")]
+    InvalidInputs {},
+
+    #[error("Must pay exactly {amount} LUART")]
+    InvalidPayment { amount: String },
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
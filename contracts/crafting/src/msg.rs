@@ -0,0 +1,128 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Empty, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::Recipe;
+
+/// This structure describes the parameters used for creating a crafting contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token burnt by the crafts
+    pub luart_token: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// An input NFT of a craft
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CraftInput {
+    pub collection: String,
+    pub token_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the LUART cost of a craft, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Crafts a recipe without LUART cost, burning the input NFTs of the sender
+    Craft {
+        recipe_id: u64,
+        inputs: Vec<CraftInput>,
+    },
+    /// Adds a recipe. Only the owner can execute it.
+    AddRecipe { recipe: Recipe },
+    /// Updates a recipe. Only the owner can execute it.
+    UpdateRecipe {
+        recipe_id: u64,
+        luart_cost: Option<Uint128>,
+        cooldown: Option<u64>,
+        max_supply: Option<u64>,
+        enabled: Option<bool>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Crafts a recipe with the sent LUART, burning them along the input NFTs of the sender
+    Craft {
+        recipe_id: u64,
+        inputs: Vec<CraftInput>,
+    },
+}
+
+/// The messages executed on the collections, a subset of the cw721-base execute messages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionExecuteMsg {
+    Burn { token_id: String },
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Option<Empty>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the recipe and its number of crafts.
+    /// Return type: RecipeResponse.
+    Recipe { recipe_id: u64 },
+    /// Returns the recipes by id.
+    /// Return type: RecipesResponse.
+    Recipes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns when the address can craft the recipe again.
+    /// Return type: CraftInfoResponse.
+    CraftInfo { recipe_id: u64, address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub luart_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecipeResponse {
+    pub recipe_id: u64,
+    pub recipe: Recipe,
+    pub crafted: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecipesResponse {
+    pub recipes: Vec<RecipeResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CraftInfoResponse {
+    pub last_craft_time: Option<u64>,
+    /// The end of the cooldown, none before the first craft
+    pub next_craft_time: Option<u64>,
+}
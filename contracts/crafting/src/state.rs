@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can add and update the recipes
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token burnt by the crafts
+    pub luart_token: Addr,
+}
+
+/// The input NFTs of a recipe from one collection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecipeInput {
+    pub collection: String,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Recipe {
+    pub name: String,
+    /// The NFTs burnt by each craft, the crafter approves the crafting contract beforehand
+    pub inputs: Vec<RecipeInput>,
+    /// The amount of LUART burnt by each craft
+    pub luart_cost: Uint128,
+    /// The collection minting the crafted tokens, the crafting contract must be its minter
+    pub output_collection: String,
+    pub output_token_uri: Option<String>,
+    /// Seconds a crafter waits between two crafts of the recipe
+    pub cooldown: u64,
+    /// The maximum number of crafts, unlimited if unset
+    pub max_supply: Option<u64>,
+    pub enabled: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const RECIPE_COUNT: Item<u64> = Item::new("recipe_count");
+pub const RECIPES: Map<U64Key, Recipe> = Map::new("recipes");
+/// The number of crafts of each recipe, the crafted token ids are `{recipe_id}-{craft number}`
+pub const CRAFTED: Map<U64Key, u64> = Map::new("crafted");
+/// Timestamp in seconds of the last craft of a recipe by an address
+pub const LAST_CRAFTS: Map<(U64Key, &Addr), u64> = Map::new("last_crafts");
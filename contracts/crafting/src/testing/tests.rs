@@ -0,0 +1,163 @@
+use cosmwasm_std::{
+    CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionExecuteMsg, CraftInfoResponse, CraftInput, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    QueryMsg, RecipeResponse,
+};
+use crate::state::{Recipe, RecipeInput};
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const LUART: &str = "mock_luart";
+const HEROES: &str = "mock_heroes";
+const ITEMS: &str = "mock_items";
+const LEGENDS: &str = "mock_legends";
+const CRAFTER: &str = "mock_crafter";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        luart_token: LUART.to_string(),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn default_recipe() -> Recipe {
+    Recipe {
+        name: "Legend".to_string(),
+        inputs: vec![
+            RecipeInput { collection: HEROES.to_string(), count: 2 },
+            RecipeInput { collection: ITEMS.to_string(), count: 1 },
+        ],
+        luart_cost: Uint128::new(1_000),
+        output_collection: LEGENDS.to_string(),
+        output_token_uri: Some("ipfs://legend.json".to_string()),
+        cooldown: DAY,
+        max_supply: Some(2),
+        enabled: true,
+    }
+}
+
+fn input(collection: &str, token_id: &str) -> CraftInput {
+    CraftInput { collection: collection.to_string(), token_id: token_id.to_string() }
+}
+
+fn craft(deps: DepsMut, env: Env, amount: u128, inputs: Vec<CraftInput>) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(LUART, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: CRAFTER.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::Craft { recipe_id: 1, inputs }).unwrap(),
+            }))
+}
+
+fn wasm_msg<T: serde::Serialize>(contract: &str, msg: &T) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_add_recipe() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CRAFTER, &[]),
+                      ExecuteMsg::AddRecipe { recipe: default_recipe() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let mut recipe = default_recipe();
+    recipe.inputs[1].collection = HEROES.to_string();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::AddRecipe { recipe }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRecipe {
+        reason: "invalid or duplicated input collection: mock_heroes".to_string(),
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::AddRecipe { recipe: default_recipe() }).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateRecipe {
+        recipe_id: 1,
+        luart_cost: None,
+        cooldown: None,
+        max_supply: None,
+        enabled: Some(false),
+    }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Recipe { recipe_id: 1 }).unwrap();
+    let recipe: RecipeResponse = from_binary(&res).unwrap();
+    assert!(!recipe.recipe.enabled);
+    assert_eq!(recipe.crafted, 0);
+}
+
+#[test]
+fn test_craft() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::AddRecipe { recipe: default_recipe() }).unwrap();
+    for token_id in ["1", "2", "3", "4", "5", "6"].iter() {
+        deps.querier.with_owner(HEROES, token_id, CRAFTER);
+    }
+    deps.querier.with_owner(HEROES, "7", "someone_else");
+    deps.querier.with_owner(ITEMS, "1", CRAFTER);
+    deps.querier.with_owner(ITEMS, "2", CRAFTER);
+    deps.querier.with_owner(ITEMS, "3", CRAFTER);
+
+    let inputs = vec![input(HEROES, "1"), input(HEROES, "2"), input(ITEMS, "1")];
+    let err = craft(deps.as_mut(), env.clone(), 999, inputs.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { amount: "1000".to_string() });
+    let err = craft(deps.as_mut(), env.clone(), 1_000,
+                    vec![input(HEROES, "1"), input(HEROES, "1"), input(ITEMS, "1")]).unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+    let err = craft(deps.as_mut(), env.clone(), 1_000,
+                    vec![input(HEROES, "1"), input(HEROES, "7"), input(ITEMS, "1")]).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = craft(deps.as_mut(), env.clone(), 1_000, inputs).unwrap();
+    assert_eq!(res.messages, vec![
+        wasm_msg(HEROES, &CollectionExecuteMsg::Burn { token_id: "1".to_string() }),
+        wasm_msg(HEROES, &CollectionExecuteMsg::Burn { token_id: "2".to_string() }),
+        wasm_msg(ITEMS, &CollectionExecuteMsg::Burn { token_id: "1".to_string() }),
+        wasm_msg(LEGENDS, &CollectionExecuteMsg::Mint {
+            token_id: "1-1".to_string(),
+            owner: CRAFTER.to_string(),
+            token_uri: Some("ipfs://legend.json".to_string()),
+            extension: None,
+        }),
+        wasm_msg(LUART, &Cw20ExecuteMsg::Burn { amount: Uint128::new(1_000) }),
+    ]);
+
+    // The crafter waits for the cooldown
+    let inputs = vec![input(HEROES, "3"), input(HEROES, "4"), input(ITEMS, "2")];
+    let err = craft(deps.as_mut(), env.clone(), 1_000, inputs.clone()).unwrap_err();
+    assert_eq!(err, ContractError::CooldownActive { next_craft_time: env.block.time.seconds() + DAY });
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::CraftInfo {
+        recipe_id: 1,
+        address: CRAFTER.to_string(),
+    }).unwrap();
+    let craft_info: CraftInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(craft_info.next_craft_time, Some(env.block.time.seconds() + DAY));
+
+    env.block.time = env.block.time.plus_seconds(DAY);
+    craft(deps.as_mut(), env.clone(), 1_000, inputs).unwrap();
+
+    // The recipe supply is capped
+    env.block.time = env.block.time.plus_seconds(DAY);
+    let err = craft(deps.as_mut(), env, 1_000,
+                    vec![input(HEROES, "5"), input(HEROES, "6"), input(ITEMS, "3")]).unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+}
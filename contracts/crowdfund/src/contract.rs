@@ -0,0 +1,354 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, to_binary, Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::U64Key;
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    BackerResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    StateResponse,
+};
+use crate::state::{Config, CONFIG, MilestoneVote, PLEDGES, State, STATE, VOTERS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-crowdfund";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let creator = match msg.creator {
+        Some(creator) => deps.api.addr_validate(&creator)?,
+        None => info.sender,
+    };
+
+    let config = Config {
+        creator,
+        denom: msg.denom,
+        goal: msg.goal,
+        start_time: msg.start_time,
+        end_time: msg.end_time,
+        milestones: msg.milestones,
+        voting_period: msg.voting_period,
+        quorum: msg.quorum,
+    };
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &State::default())?;
+
+    Ok(Response::default())
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.goal.is_zero() {
+        return Err(ContractError::InvalidConfig { reason: "goal must be positive".to_string() });
+    }
+    if config.end_time <= config.start_time {
+        return Err(ContractError::InvalidConfig { reason: "campaign must end after it starts".to_string() });
+    }
+    let total_ratio = config.milestones
+        .iter()
+        .fold(Decimal::zero(), |total, m| total + m.release_ratio);
+    if config.milestones.is_empty() || total_ratio != Decimal::one() {
+        return Err(ContractError::InvalidConfig {
+            reason: "milestone release ratios must add up to one".to_string(),
+        });
+    }
+    if config.voting_period == 0 {
+        return Err(ContractError::InvalidConfig { reason: "voting period must be positive".to_string() });
+    }
+    if config.quorum > Decimal::one() {
+        return Err(ContractError::InvalidConfig { reason: "quorum must not exceed one".to_string() });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Pledge {} => pledge(deps, env, info),
+        ExecuteMsg::RequestRelease {} => request_release(deps, env, info),
+        ExecuteMsg::Vote { approve } => vote(deps, env, info, approve),
+        ExecuteMsg::TallyVote {} => tally_vote(deps, env),
+        ExecuteMsg::Refund {} => refund(deps, env, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn pledge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    if now < config.start_time || now >= config.end_time {
+        return Err(ContractError::CampaignNotActive {});
+    }
+    let amount = must_pay(&info, &config.denom)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.total_pledged = state.total_pledged.checked_add(amount)?;
+    STATE.save(deps.storage, &state)?;
+    PLEDGES.update(deps.storage, &info.sender, |pledged| -> StdResult<_> {
+        Ok(pledged.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pledge")
+        .add_attribute("backer", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn request_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let now = env.block.time.seconds();
+    if now < config.end_time {
+        return Err(ContractError::CampaignNotEnded {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    if state.total_pledged < config.goal {
+        return Err(ContractError::GoalNotReached {});
+    }
+    if state.failed {
+        return Err(ContractError::CampaignFailed {});
+    }
+    if state.vote.is_some() {
+        return Err(ContractError::VoteInProgress {});
+    }
+    if state.current_milestone as usize >= config.milestones.len() {
+        return Err(ContractError::AllMilestonesReleased {});
+    }
+
+    state.vote = Some(MilestoneVote {
+        end_time: now + config.voting_period,
+        ..MilestoneVote::default()
+    });
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_release")
+        .add_attribute("milestone", state.current_milestone.to_string()))
+}
+
+pub fn vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    approve: bool,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    let mut vote = state.vote.ok_or(ContractError::NoVoteInProgress {})?;
+    if env.block.time.seconds() >= vote.end_time {
+        return Err(ContractError::VoteEnded {});
+    }
+
+    let pledged = PLEDGES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+    let key = (U64Key::new(state.current_milestone), &info.sender);
+    if VOTERS.has(deps.storage, key.clone()) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+    VOTERS.save(deps.storage, key, &approve)?;
+
+    if approve {
+        vote.yes = vote.yes.checked_add(pledged)?;
+    } else {
+        vote.no = vote.no.checked_add(pledged)?;
+    }
+    state.vote = Some(vote);
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("backer", info.sender)
+        .add_attribute("approve", approve.to_string())
+        .add_attribute("weight", pledged))
+}
+
+pub fn tally_vote(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let vote = state.vote.take().ok_or(ContractError::NoVoteInProgress {})?;
+    if env.block.time.seconds() < vote.end_time {
+        return Err(ContractError::VoteNotEnded {});
+    }
+
+    let milestone = state.current_milestone;
+    let mut response = Response::new()
+        .add_attribute("action", "tally_vote")
+        .add_attribute("milestone", milestone.to_string());
+
+    // Without quorum the vote is discarded and the creator can request it again
+    if vote.yes + vote.no < state.total_pledged * config.quorum {
+        STATE.save(deps.storage, &state)?;
+        return Ok(response.add_attribute("result", "no_quorum"));
+    }
+
+    if vote.yes > vote.no {
+        // The last milestone releases the rounding leftovers as well
+        let amount = if milestone as usize == config.milestones.len() - 1 {
+            state.total_pledged.checked_sub(state.released)?
+        } else {
+            state.total_pledged * config.milestones[milestone as usize].release_ratio
+        };
+        state.released = state.released.checked_add(amount)?;
+        state.current_milestone += 1;
+        response = response
+            .add_message(bank_send_msg(&config.creator, &config.denom, amount))
+            .add_attribute("result", "approved")
+            .add_attribute("amount", amount);
+    } else {
+        state.failed = true;
+        response = response.add_attribute("result", "rejected");
+    }
+    STATE.save(deps.storage, &state)?;
+
+    Ok(response)
+}
+
+pub fn refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if env.block.time.seconds() < config.end_time {
+        return Err(ContractError::CampaignNotEnded {});
+    }
+
+    let state = STATE.load(deps.storage)?;
+    let pledged = PLEDGES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NothingToRefund {})?;
+    let amount = refundable_amount(&config, &state, pledged);
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRefund {});
+    }
+    PLEDGES.remove(deps.storage, &info.sender);
+
+    Ok(Response::new()
+        .add_message(bank_send_msg(&info.sender, &config.denom, amount))
+        .add_attribute("action", "refund")
+        .add_attribute("backer", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Returns the refundable part of the pledge once the campaign ended
+fn refundable_amount(config: &Config, state: &State, pledged: Uint128) -> Uint128 {
+    if state.total_pledged < config.goal {
+        pledged
+    } else if state.failed {
+        state.refund_amount(pledged)
+    } else {
+        Uint128::zero()
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::Backer { address } => to_binary(&query_backer(deps, env, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        creator: config.creator.to_string(),
+        denom: config.denom,
+        goal: config.goal,
+        start_time: config.start_time,
+        end_time: config.end_time,
+        milestones: config.milestones,
+        voting_period: config.voting_period,
+        quorum: config.quorum,
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        total_pledged: state.total_pledged,
+        released: state.released,
+        current_milestone: state.current_milestone,
+        vote: state.vote,
+        failed: state.failed,
+    })
+}
+
+pub fn query_backer(deps: Deps, env: Env, address: String) -> StdResult<BackerResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let pledged = PLEDGES.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    let refundable = if env.block.time.seconds() < config.end_time {
+        Uint128::zero()
+    } else {
+        refundable_amount(&config, &state, pledged)
+    };
+
+    Ok(BackerResponse {
+        pledged,
+        voted: VOTERS.has(deps.storage, (U64Key::new(state.current_milestone), &address)),
+        refundable,
+    })
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+fn bank_send_msg(recipient: &Addr, denom: &str, amount: Uint128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin {
+            denom: denom.to_string(),
+            amount,
+        }],
+    })
+}
@@ -0,0 +1,53 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid campaign configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("The campaign is not accepting pledges")]
+    CampaignNotActive {},
+
+    #[error("The campaign has not ended yet")]
+    CampaignNotEnded {},
+
+    #[error("The campaign did not reach its goal")]
+    GoalNotReached {},
+
+    #[error("The campaign failed")]
+    CampaignFailed {},
+
+    #[error("All milestones are released")]
+    AllMilestonesReleased {},
+
+    #[error("A milestone vote is in progress")]
+    VoteInProgress {},
+
+    #[error("No milestone vote is in progress")]
+    NoVoteInProgress {},
+
+    #[error("The milestone vote has ended")]
+    VoteEnded {},
+
+    #[error("The milestone vote has not ended yet")]
+    VoteNotEnded {},
+
+    #[error("Already voted on this milestone")]
+    AlreadyVoted {},
+
+    #[error("Nothing to refund")]
+    NothingToRefund {},
+}
@@ -0,0 +1,95 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+
+use crate::state::{Milestone, MilestoneVote};
+
+/// This structure describes the parameters used for creating a crowdfund contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The address receiving the funds of the approved milestones, defaults to the sender
+    pub creator: Option<String>,
+    /// The native denom pledges are paid in
+    pub denom: String,
+    /// The campaign is refunded if less is pledged
+    pub goal: Uint128,
+    /// Campaign start timestamp in seconds
+    pub start_time: u64,
+    /// Campaign end timestamp in seconds
+    pub end_time: u64,
+    /// The milestones, their release ratios must add up to one
+    pub milestones: Vec<Milestone>,
+    /// Seconds backers have to vote on a milestone
+    pub voting_period: u64,
+    /// The share of the pledged funds which must vote for a milestone vote to count
+    pub quorum: Decimal,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Pledges the sent funds to the campaign
+    Pledge {},
+    /// Opens the backer vote on the next milestone. Only the creator can execute it.
+    RequestRelease {},
+    /// Votes on the current milestone with the sender pledge
+    Vote { approve: bool },
+    /// Closes the milestone vote. The milestone funds are released if the majority approved,
+    /// the campaign fails if it rejected and the vote is discarded without quorum.
+    TallyVote {},
+    /// Returns the sender pledge if the goal was not reached,
+    /// or its share of the unreleased funds if the campaign failed
+    Refund {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the campaign configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the campaign state.
+    /// Return type: StateResponse.
+    State {},
+    /// Returns the backer pledge.
+    /// Return type: BackerResponse.
+    Backer { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub creator: String,
+    pub denom: String,
+    pub goal: Uint128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub milestones: Vec<Milestone>,
+    pub voting_period: u64,
+    pub quorum: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub total_pledged: Uint128,
+    pub released: Uint128,
+    pub current_milestone: u64,
+    pub vote: Option<MilestoneVote>,
+    pub failed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BackerResponse {
+    pub pledged: Uint128,
+    /// Whether the backer voted on the current milestone
+    pub voted: bool,
+    pub refundable: Uint128,
+}
@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Milestone {
+    pub description: String,
+    /// The share of the pledged funds released to the creator once the milestone is approved
+    pub release_ratio: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address receiving the funds of the approved milestones
+    pub creator: Addr,
+    /// The native denom pledges are paid in
+    pub denom: String,
+    /// The campaign is refunded if less is pledged
+    pub goal: Uint128,
+    /// Campaign start timestamp in seconds
+    pub start_time: u64,
+    /// Campaign end timestamp in seconds
+    pub end_time: u64,
+    pub milestones: Vec<Milestone>,
+    /// Seconds backers have to vote on a milestone
+    pub voting_period: u64,
+    /// The share of the pledged funds which must vote for a milestone vote to count
+    pub quorum: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct MilestoneVote {
+    pub end_time: u64,
+    /// The pledged amount voting for the release
+    pub yes: Uint128,
+    /// The pledged amount voting against the release
+    pub no: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct State {
+    pub total_pledged: Uint128,
+    /// The amount released to the creator
+    pub released: Uint128,
+    /// The index of the next milestone to release
+    pub current_milestone: u64,
+    pub vote: Option<MilestoneVote>,
+    /// Set once a milestone is rejected, the remaining funds are refundable from then on
+    pub failed: bool,
+}
+
+impl State {
+    /// Returns the part of the pledge refunded when the campaign failed
+    pub fn refund_amount(&self, pledged: Uint128) -> Uint128 {
+        pledged.multiply_ratio(self.total_pledged - self.released, self.total_pledged)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+pub const PLEDGES: Map<&Addr, Uint128> = Map::new("pledges");
+/// The backers who voted on each milestone
+pub const VOTERS: Map<(U64Key, &Addr), bool> = Map::new("voters");
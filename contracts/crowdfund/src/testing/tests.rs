@@ -0,0 +1,155 @@
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, Uint128,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{BackerResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use crate::state::Milestone;
+
+const CREATOR: &str = "mock_creator";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const DENOM: &str = "uusd";
+
+const DURATION: u64 = 1_000;
+const VOTING_PERIOD: u64 = 100;
+
+/// Instantiates a campaign with a 1000 uusd goal released in 40% and 60% milestones
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        creator: None,
+        denom: DENOM.to_string(),
+        goal: Uint128::new(1_000),
+        start_time: env.block.time.seconds(),
+        end_time: env.block.time.seconds() + DURATION,
+        milestones: vec![
+            Milestone { description: "Prototype".to_string(), release_ratio: Decimal::percent(40) },
+            Milestone { description: "Launch".to_string(), release_ratio: Decimal::percent(60) },
+        ],
+        voting_period: VOTING_PERIOD,
+        quorum: Decimal::percent(50),
+    };
+    instantiate(deps, env, mock_info(CREATOR, &[]), msg).unwrap()
+}
+
+fn pledge(deps: DepsMut, env: Env, backer: &str, amount: u128) {
+    execute(deps, env, mock_info(backer, &[Coin::new(amount, DENOM)]), ExecuteMsg::Pledge {}).unwrap();
+}
+
+/// Opens a milestone vote, casts the votes and tallies it
+fn run_vote(deps: DepsMut, env: &mut Env, votes: &[(&str, bool)]) -> Response {
+    let mut deps = deps;
+    execute(deps.branch(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::RequestRelease {}).unwrap();
+    for (backer, approve) in votes {
+        execute(deps.branch(), env.clone(), mock_info(backer, &[]), ExecuteMsg::Vote { approve: *approve }).unwrap();
+    }
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    execute(deps, env.clone(), mock_info("anyone", &[]), ExecuteMsg::TallyVote {}).unwrap()
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin::new(amount, DENOM)],
+    }))
+}
+
+#[test]
+fn test_refund_below_goal() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    pledge(deps.as_mut(), env.clone(), ALICE, 600);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotEnded {});
+
+    env.block.time = env.block.time.plus_seconds(DURATION);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Pledge {}).unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotActive {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::RequestRelease {}).unwrap_err();
+    assert_eq!(err, ContractError::GoalNotReached {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Refund {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 600)]);
+    let err = execute(deps.as_mut(), env, mock_info(ALICE, &[]), ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+}
+
+#[test]
+fn test_milestone_release() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    pledge(deps.as_mut(), env.clone(), ALICE, 600);
+    pledge(deps.as_mut(), env.clone(), BOB, 400);
+    env.block.time = env.block.time.plus_seconds(DURATION);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::RequestRelease {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = run_vote(deps.as_mut(), &mut env, &[(ALICE, true), (BOB, false)]);
+    assert_eq!(res.messages, vec![bank_send_msg(CREATOR, 400)]);
+
+    // Bob alone does not reach the quorum, the vote is discarded
+    let res = run_vote(deps.as_mut(), &mut env, &[(BOB, false)]);
+    assert!(res.messages.is_empty());
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!((state.current_milestone, state.vote, state.failed), (1, None, false));
+
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::RequestRelease {}).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Vote { approve: true }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::Vote { approve: true }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::TallyVote {}).unwrap_err();
+    assert_eq!(err, ContractError::VoteNotEnded {});
+
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::TallyVote {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(CREATOR, 600)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::RequestRelease {}).unwrap_err();
+    assert_eq!(err, ContractError::AllMilestonesReleased {});
+    let err = execute(deps.as_mut(), env, mock_info(ALICE, &[]), ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+}
+
+#[test]
+fn test_rejected_milestone_refund() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    pledge(deps.as_mut(), env.clone(), ALICE, 600);
+    pledge(deps.as_mut(), env.clone(), BOB, 400);
+    env.block.time = env.block.time.plus_seconds(DURATION);
+
+    run_vote(deps.as_mut(), &mut env, &[(ALICE, true)]);
+    let res = run_vote(deps.as_mut(), &mut env, &[(ALICE, false), (BOB, true)]);
+    assert!(res.messages.is_empty());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::RequestRelease {}).unwrap_err();
+    assert_eq!(err, ContractError::CampaignFailed {});
+
+    // The backers share the 60% left after the first milestone
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Backer { address: BOB.to_string() }).unwrap();
+    let backer: BackerResponse = from_binary(&res).unwrap();
+    assert_eq!(backer.refundable, Uint128::new(240));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Refund {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 360)]);
+    let res = execute(deps.as_mut(), env, mock_info(BOB, &[]), ExecuteMsg::Refund {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(BOB, 240)]);
+}
@@ -0,0 +1,286 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, SubMsg, to_binary, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use luart_protocol::distributor::InstantiateMsg as DistributorInstantiateMsg;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, DistributorResponse, DistributorsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    Config, CONFIG, ConfigOwner, CREATOR_DISTRIBUTORS, DistributorInfo, DISTRIBUTORS, PENDING_DISTRIBUTOR,
+    PendingDistributor,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-distributor-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const INSTANTIATE_DISTRIBUTOR_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let fee_receiver = match msg.fee_receiver {
+        Some(fee_receiver) => deps.api.addr_validate(&fee_receiver)?,
+        None => owner.clone(),
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        distributor_code_id: msg.distributor_code_id,
+        creation_fee: msg.creation_fee.filter(|fee| !fee.amount.is_zero()),
+        fee_receiver,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateDistributor { distributor, admin, label } => {
+            create_distributor(deps, info, distributor, admin, label)
+        }
+        ExecuteMsg::UpdateConfig {
+            distributor_code_id,
+            creation_fee,
+            fee_receiver,
+        } => update_config(deps, info, distributor_code_id, creation_fee, fee_receiver),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// Collects the creation fee and instantiates the distributor,
+/// it is recorded once its address is read from the reply
+pub fn create_distributor(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut distributor: Box<DistributorInstantiateMsg>,
+    admin: Option<String>,
+    label: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let admin = admin.map(|admin| deps.api.addr_validate(&admin)).transpose()?;
+
+    let mut messages = vec![];
+    if let Some(fee) = config.creation_fee {
+        if info.funds != vec![fee.clone()] {
+            return Err(ContractError::InvalidCreationFee {
+                denom: fee.denom,
+                amount: fee.amount.u128(),
+            });
+        }
+        messages.push(BankMsg::Send {
+            to_address: config.fee_receiver.to_string(),
+            amount: vec![fee],
+        });
+    }
+
+    // the creator owns the distributor unless set otherwise, not the factory
+    distributor.owner = Some(distributor.owner.unwrap_or_else(|| info.sender.to_string()));
+
+    PENDING_DISTRIBUTOR.save(deps.storage, &PendingDistributor {
+        creator: info.sender.clone(),
+        code_id: config.distributor_code_id,
+        asset: distributor.asset.clone(),
+    })?;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: admin.map(|admin| admin.to_string()),
+        code_id: config.distributor_code_id,
+        msg: to_binary(&distributor)?,
+        funds: vec![],
+        label,
+    };
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessage(SubMsg::reply_on_success(instantiate, INSTANTIATE_DISTRIBUTOR_ID))
+        .add_attribute("action", "create_distributor")
+        .add_attribute("creator", info.sender)
+        .add_attribute("asset", distributor.asset.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_DISTRIBUTOR_ID {
+        return Err(StdError::generic_err(format!("Unknown reply id: {}", msg.id)).into());
+    }
+
+    let res = msg.result.into_result().map_err(StdError::generic_err)?;
+    let address = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate_contract" || event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "contract_address" || attr.key == "_contract_address")
+        .ok_or(ContractError::MissingDistributorAddress {})?
+        .value
+        .clone();
+    let address = deps.api.addr_validate(&address)?;
+
+    let pending = PENDING_DISTRIBUTOR.load(deps.storage)?;
+    PENDING_DISTRIBUTOR.remove(deps.storage);
+    DISTRIBUTORS.save(deps.storage, &address, &DistributorInfo {
+        creator: pending.creator.clone(),
+        code_id: pending.code_id,
+        asset: pending.asset,
+        created_at: env.block.time.seconds(),
+    })?;
+    CREATOR_DISTRIBUTORS.save(deps.storage, (&pending.creator, &address), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("distributor", address)
+        .add_attribute("creator", pending.creator))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    distributor_code_id: Option<u64>,
+    creation_fee: Option<Coin>,
+    fee_receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.distributor_code_id = distributor_code_id.unwrap_or(config.distributor_code_id);
+    if let Some(creation_fee) = creation_fee {
+        config.creation_fee = Some(creation_fee).filter(|fee| !fee.amount.is_zero());
+    }
+    if let Some(fee_receiver) = fee_receiver {
+        config.fee_receiver = deps.api.addr_validate(&fee_receiver)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("distributor_code_id", config.distributor_code_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Distributor { address } => to_binary(&query_distributor(deps, address)?),
+        QueryMsg::Distributors { start_after, limit } => {
+            to_binary(&query_distributors(deps, start_after, limit)?)
+        }
+        QueryMsg::DistributorsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&query_distributors_by_creator(deps, creator, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        distributor_code_id: config.distributor_code_id,
+        creation_fee: config.creation_fee,
+        fee_receiver: config.fee_receiver.to_string(),
+    })
+}
+
+pub fn query_distributor(deps: Deps, address: String) -> StdResult<DistributorResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let distributor = DISTRIBUTORS.load(deps.storage, &address)?;
+    Ok(distributor_response(address, distributor))
+}
+
+pub fn query_distributors(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DistributorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let distributors: StdResult<Vec<DistributorResponse>> = DISTRIBUTORS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, distributor) = item?;
+            let address = Addr::unchecked(String::from_utf8(address)?);
+            Ok(distributor_response(address, distributor))
+        })
+        .collect();
+
+    Ok(DistributorsResponse { distributors: distributors? })
+}
+
+pub fn query_distributors_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DistributorsResponse> {
+    let creator = deps.api.addr_validate(&creator)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let distributors: StdResult<Vec<DistributorResponse>> = CREATOR_DISTRIBUTORS
+        .prefix(&creator)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|address| {
+            let address = Addr::unchecked(String::from_utf8(address)?);
+            let distributor = DISTRIBUTORS.load(deps.storage, &address)?;
+            Ok(distributor_response(address, distributor))
+        })
+        .collect();
+
+    Ok(DistributorsResponse { distributors: distributors? })
+}
+
+fn distributor_response(address: Addr, distributor: DistributorInfo) -> DistributorResponse {
+    DistributorResponse {
+        address: address.to_string(),
+        creator: distributor.creator.to_string(),
+        code_id: distributor.code_id,
+        asset: distributor.asset,
+        created_at: distributor.created_at,
+    }
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::StdError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must send the creation fee of {amount}{denom}")]
+    InvalidCreationFee { denom: String, amount: u128 },
+
+    #[error("The instantiated distributor address is missing")]
+    MissingDistributorAddress {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
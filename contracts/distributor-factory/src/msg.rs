@@ -0,0 +1,100 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Coin;
+use luart_protocol::distributor::InstantiateMsg as DistributorInstantiateMsg;
+use luart_types::asset::Asset;
+
+/// This structure describes the parameters used for creating a distributor factory contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The code id of the merkle distributor contract
+    pub distributor_code_id: u64,
+    /// The fee paid to create a distributor, free when unset
+    pub creation_fee: Option<Coin>,
+    /// The address receiving the creation fees, defaults to the owner
+    pub fee_receiver: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiates a merkle distributor owned by the sender unless set otherwise.
+    /// The creation fee must be sent, the sender is recorded as the creator of the distributor.
+    CreateDistributor {
+        distributor: Box<DistributorInstantiateMsg>,
+        /// The address allowed to migrate the distributor, none by default
+        admin: Option<String>,
+        label: String,
+    },
+    /// Updates the code id and the fee of the distributors created from now on.
+    /// A zero creation fee makes the creation free. Only the owner can execute it.
+    UpdateConfig {
+        distributor_code_id: Option<u64>,
+        creation_fee: Option<Coin>,
+        fee_receiver: Option<String>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a distributor created by the factory.
+    /// Return type: DistributorResponse.
+    Distributor { address: String },
+    /// Returns the distributors created by the factory.
+    /// Return type: DistributorsResponse.
+    Distributors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the distributors created by the given address.
+    /// Return type: DistributorsResponse.
+    DistributorsByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub distributor_code_id: u64,
+    pub creation_fee: Option<Coin>,
+    pub fee_receiver: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributorResponse {
+    pub address: String,
+    pub creator: String,
+    pub code_id: u64,
+    pub asset: Asset,
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributorsResponse {
+    pub distributors: Vec<DistributorResponse>,
+}
@@ -0,0 +1,61 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Coin, Empty, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use luart_types::asset::Asset;
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who updates the code id and the creation fee
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The code id of the distributor contract instantiated
+    pub distributor_code_id: u64,
+    /// The fee paid by the creator of a distributor, free when unset
+    pub creation_fee: Option<Coin>,
+    /// The address receiving the creation fees
+    pub fee_receiver: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributorInfo {
+    pub creator: Addr,
+    pub code_id: u64,
+    pub asset: Asset,
+    /// Timestamp in seconds
+    pub created_at: u64,
+}
+
+/// The distributor being instantiated, saved until its address is read from the reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDistributor {
+    pub creator: Addr,
+    pub code_id: u64,
+    pub asset: Asset,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const PENDING_DISTRIBUTOR: Item<PendingDistributor> = Item::new("pending_distributor");
+pub const DISTRIBUTORS: Map<&Addr, DistributorInfo> = Map::new("distributors");
+/// The distributors of each creator
+pub const CREATOR_DISTRIBUTORS: Map<(&Addr, &Addr), Empty> = Map::new("creator_distributors");
@@ -0,0 +1,154 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, ContractResult, DepsMut, Env, Event, from_binary, Reply, Response, SubMsg,
+    SubMsgExecutionResponse, to_binary, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use luart_protocol::distributor::InstantiateMsg as DistributorInstantiateMsg;
+use luart_types::asset::Asset;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, DistributorResponse, DistributorsResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+const OWNER: &str = "mock_owner";
+const PARTNER: &str = "mock_partner";
+const FEE_RECEIVER: &str = "mock_treasury";
+const DISTRIBUTOR_CODE_ID: u64 = 9;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        distributor_code_id: DISTRIBUTOR_CODE_ID,
+        creation_fee: Some(Coin::new(100_000_000, "uusd")),
+        fee_receiver: Some(FEE_RECEIVER.to_string()),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn create_msg(asset: Asset) -> ExecuteMsg {
+    ExecuteMsg::CreateDistributor {
+        distributor: Box::new(DistributorInstantiateMsg { owner: None, asset }),
+        admin: None,
+        label: "Partner airdrop".to_string(),
+    }
+}
+
+/// The reply of the distributor instantiation, with the event emitted by Terra
+fn instantiate_reply(address: &str) -> Reply {
+    Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![Event::new("instantiate_contract").add_attribute("contract_address", address)],
+            data: None,
+        }),
+    }
+}
+
+#[test]
+fn test_create_distributor() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // The creation fee must be paid
+    let msg = create_msg(Asset::Native("uluna".to_string()));
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCreationFee { denom: "uusd".to_string(), amount: 100_000_000 });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[Coin::new(50_000_000, "uusd")]),
+                      msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCreationFee { denom: "uusd".to_string(), amount: 100_000_000 });
+
+    // The fee is forwarded and the partner owns the distributor
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[Coin::new(100_000_000, "uusd")]),
+                      msg).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send {
+            to_address: FEE_RECEIVER.to_string(),
+            amount: vec![Coin::new(100_000_000, "uusd")],
+        }),
+        SubMsg::reply_on_success(WasmMsg::Instantiate {
+            admin: None,
+            code_id: DISTRIBUTOR_CODE_ID,
+            msg: to_binary(&DistributorInstantiateMsg {
+                owner: Some(PARTNER.to_string()),
+                asset: Asset::Native("uluna".to_string()),
+            }).unwrap(),
+            funds: vec![],
+            label: "Partner airdrop".to_string(),
+        }, 1),
+    ]);
+    reply(deps.as_mut(), env.clone(), instantiate_reply("distributor1")).unwrap();
+
+    let msg = create_msg(Asset::Cw20(Addr::unchecked("mock_partner_token")));
+    execute(deps.as_mut(), env.clone(), mock_info("mock_other", &[Coin::new(100_000_000, "uusd")]), msg).unwrap();
+    let err = reply(deps.as_mut(), env.clone(), Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::MissingDistributorAddress {});
+    reply(deps.as_mut(), env.clone(), instantiate_reply("distributor2")).unwrap();
+
+    let res: DistributorResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Distributor {
+        address: "distributor1".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res, DistributorResponse {
+        address: "distributor1".to_string(),
+        creator: PARTNER.to_string(),
+        code_id: DISTRIBUTOR_CODE_ID,
+        asset: Asset::Native("uluna".to_string()),
+        created_at: env.block.time.seconds(),
+    });
+
+    let res: DistributorsResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::DistributorsByCreator {
+        creator: "mock_other".to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    let addresses: Vec<_> = res.distributors.iter().map(|d| d.address.as_str()).collect();
+    assert_eq!(addresses, vec!["distributor2"]);
+
+    let res: DistributorsResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Distributors {
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    let addresses: Vec<_> = res.distributors.iter().map(|d| d.address.as_str()).collect();
+    assert_eq!(addresses, vec!["distributor1", "distributor2"]);
+}
+
+#[test]
+fn test_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        distributor_code_id: Some(10),
+        creation_fee: Some(Coin::new(0, "uusd")),
+        fee_receiver: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let res: ConfigResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res, ConfigResponse {
+        owner: Some(OWNER.to_string()),
+        pending_owner: None,
+        distributor_code_id: 10,
+        creation_fee: None,
+        fee_receiver: FEE_RECEIVER.to_string(),
+    });
+
+    // The creation is free from now on, with the new code id
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]),
+                      create_msg(Asset::Native("uluna".to_string()))).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    reply(deps.as_mut(), env.clone(), instantiate_reply("distributor1")).unwrap();
+    let res: DistributorResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Distributor {
+        address: "distributor1".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.code_id, 10);
+}
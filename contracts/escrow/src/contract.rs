@@ -0,0 +1,388 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order,
+    Response, StdError, StdResult, to_binary, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, EscrowResponse, EscrowSideResponse, EscrowsResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{Escrow, ESCROW_COUNT, EscrowAsset, ESCROWS, EscrowSide};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-escrow";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    ESCROW_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => receive_cw721(deps, env, info, msg),
+        ExecuteMsg::Create {
+            counterparty,
+            creator_assets,
+            counterparty_assets,
+            expires_at,
+        } => create(deps, env, info, counterparty, creator_assets, counterparty_assets, expires_at),
+        ExecuteMsg::Fund { escrow_id } => {
+            if info.funds.is_empty() {
+                return Err(ContractError::InvalidDeposit {});
+            }
+            let assets = info.funds
+                .into_iter()
+                .map(|coin| EscrowAsset::Native { denom: coin.denom, amount: coin.amount })
+                .collect();
+            fund(deps, env, info.sender, escrow_id, assets)
+        }
+        ExecuteMsg::Approve { escrow_id } => approve(deps, env, info, escrow_id),
+        ExecuteMsg::Execute { escrow_id } => execute_swap(deps, env, info, escrow_id),
+        ExecuteMsg::Refund { escrow_id } => refund(deps, env, info, escrow_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Fund { escrow_id } => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            let asset = EscrowAsset::Cw20 {
+                contract_addr: info.sender.to_string(),
+                amount: cw20_msg.amount,
+            };
+            fund(deps, env, sender, escrow_id, vec![asset])
+        }
+    }
+}
+
+pub fn receive_cw721(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw721_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw721_msg.msg)? {
+        Cw721HookMsg::Fund { escrow_id } => {
+            let sender = deps.api.addr_validate(&cw721_msg.sender)?;
+            let asset = EscrowAsset::Cw721 {
+                contract_addr: info.sender.to_string(),
+                token_id: cw721_msg.token_id,
+            };
+            fund(deps, env, sender, escrow_id, vec![asset])
+        }
+    }
+}
+
+pub fn create(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    counterparty: String,
+    creator_assets: Vec<EscrowAsset>,
+    counterparty_assets: Vec<EscrowAsset>,
+    expires_at: u64,
+) -> Result<Response, ContractError> {
+    if expires_at <= env.block.time.seconds() {
+        return Err(ContractError::EscrowExpired {});
+    }
+    let counterparty = deps.api.addr_validate(&counterparty)?;
+    if counterparty == info.sender {
+        return Err(ContractError::InvalidBasket { reason: "cannot swap with yourself".to_string() });
+    }
+    if creator_assets.is_empty() && counterparty_assets.is_empty() {
+        return Err(ContractError::InvalidBasket { reason: "both baskets are empty".to_string() });
+    }
+    validate_basket(deps.as_ref(), &creator_assets)?;
+    validate_basket(deps.as_ref(), &counterparty_assets)?;
+
+    let escrow_id = ESCROW_COUNT.load(deps.storage)? + 1;
+    ESCROW_COUNT.save(deps.storage, &escrow_id)?;
+    ESCROWS.save(deps.storage, U64Key::new(escrow_id), &Escrow {
+        creator: EscrowSide::new(info.sender.clone(), creator_assets),
+        counterparty: EscrowSide::new(counterparty.clone(), counterparty_assets),
+        expires_at,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create")
+        .add_attribute("escrow_id", escrow_id.to_string())
+        .add_attribute("creator", info.sender)
+        .add_attribute("counterparty", counterparty))
+}
+
+fn validate_basket(deps: Deps, assets: &[EscrowAsset]) -> Result<(), ContractError> {
+    for (i, asset) in assets.iter().enumerate() {
+        match asset {
+            EscrowAsset::Native { amount, .. } if amount.is_zero() => {
+                return Err(ContractError::InvalidBasket { reason: "zero amount".to_string() });
+            }
+            EscrowAsset::Cw20 { contract_addr, amount } => {
+                if amount.is_zero() {
+                    return Err(ContractError::InvalidBasket { reason: "zero amount".to_string() });
+                }
+                deps.api.addr_validate(contract_addr)?;
+            }
+            EscrowAsset::Cw721 { contract_addr, .. } => {
+                deps.api.addr_validate(contract_addr)?;
+            }
+            _ => {}
+        }
+        if assets[..i].iter().any(|a| a.same_asset(asset)) {
+            return Err(ContractError::InvalidBasket { reason: "duplicated asset".to_string() });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn fund(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    escrow_id: u64,
+    assets: Vec<EscrowAsset>,
+) -> Result<Response, ContractError> {
+    let mut escrow = ESCROWS.load(deps.storage, U64Key::new(escrow_id))?;
+    if escrow.expires_at <= env.block.time.seconds() {
+        return Err(ContractError::EscrowExpired {});
+    }
+
+    let side = escrow.side_mut(&sender).ok_or(ContractError::Unauthorized {})?;
+    for asset in assets {
+        if !side.deposit(asset) {
+            return Err(ContractError::InvalidDeposit {});
+        }
+    }
+    let funded = side.is_funded();
+    ESCROWS.save(deps.storage, U64Key::new(escrow_id), &escrow)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("escrow_id", escrow_id.to_string())
+        .add_attribute("sender", sender)
+        .add_attribute("funded", funded.to_string()))
+}
+
+pub fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: u64,
+) -> Result<Response, ContractError> {
+    let mut escrow = ESCROWS.load(deps.storage, U64Key::new(escrow_id))?;
+    if escrow.expires_at <= env.block.time.seconds() {
+        return Err(ContractError::EscrowExpired {});
+    }
+    if !escrow.creator.is_funded() || !escrow.counterparty.is_funded() {
+        return Err(ContractError::NotFunded {});
+    }
+
+    escrow.side_mut(&info.sender).ok_or(ContractError::Unauthorized {})?.approved = true;
+    ESCROWS.save(deps.storage, U64Key::new(escrow_id), &escrow)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("escrow_id", escrow_id.to_string())
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: u64,
+) -> Result<Response, ContractError> {
+    let escrow = ESCROWS.load(deps.storage, U64Key::new(escrow_id))?;
+    if !escrow.is_party(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if escrow.expires_at <= env.block.time.seconds() {
+        return Err(ContractError::EscrowExpired {});
+    }
+    if !escrow.creator.approved || !escrow.counterparty.approved {
+        return Err(ContractError::NotApproved {});
+    }
+    ESCROWS.remove(deps.storage, U64Key::new(escrow_id));
+
+    let mut messages = transfer_msgs(&escrow.creator.deposited, &escrow.counterparty.address)?;
+    messages.extend(transfer_msgs(&escrow.counterparty.deposited, &escrow.creator.address)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "execute")
+        .add_attribute("escrow_id", escrow_id.to_string()))
+}
+
+pub fn refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: u64,
+) -> Result<Response, ContractError> {
+    let escrow = ESCROWS.load(deps.storage, U64Key::new(escrow_id))?;
+    if escrow.expires_at > env.block.time.seconds() {
+        if !escrow.is_party(&info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        // Both approvals commit the parties to the swap until the expiration
+        if escrow.creator.approved && escrow.counterparty.approved {
+            return Err(ContractError::EscrowLocked {});
+        }
+    }
+    ESCROWS.remove(deps.storage, U64Key::new(escrow_id));
+
+    let mut messages = transfer_msgs(&escrow.creator.deposited, &escrow.creator.address)?;
+    messages.extend(transfer_msgs(&escrow.counterparty.deposited, &escrow.counterparty.address)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "refund")
+        .add_attribute("escrow_id", escrow_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Escrow { escrow_id } => to_binary(&query_escrow(deps, escrow_id)?),
+        QueryMsg::Escrows { start_after, limit } => {
+            to_binary(&query_escrows(deps, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_escrow(deps: Deps, escrow_id: u64) -> StdResult<EscrowResponse> {
+    let escrow = ESCROWS.load(deps.storage, U64Key::new(escrow_id))?;
+    Ok(escrow_response(escrow_id, escrow))
+}
+
+pub fn query_escrows(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<EscrowsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let escrows: StdResult<Vec<EscrowResponse>> = ESCROWS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, escrow) = item?;
+            Ok(escrow_response(parse_escrow_id(&k)?, escrow))
+        })
+        .collect();
+
+    Ok(EscrowsResponse { escrows: escrows? })
+}
+
+fn escrow_response(escrow_id: u64, escrow: Escrow) -> EscrowResponse {
+    EscrowResponse {
+        escrow_id,
+        creator: side_response(escrow.creator),
+        counterparty: side_response(escrow.counterparty),
+        expires_at: escrow.expires_at,
+    }
+}
+
+fn side_response(side: EscrowSide) -> EscrowSideResponse {
+    EscrowSideResponse {
+        funded: side.is_funded(),
+        address: side.address.to_string(),
+        assets: side.assets,
+        deposited: side.deposited,
+        approved: side.approved,
+    }
+}
+
+fn parse_escrow_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted escrow key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Returns the messages transferring the assets to the recipient, the native coins
+/// are sent together
+fn transfer_msgs(assets: &[EscrowAsset], recipient: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    let mut coins = vec![];
+    let mut messages = vec![];
+    for asset in assets {
+        match asset {
+            EscrowAsset::Native { denom, amount } => coins.push(Coin {
+                denom: denom.clone(),
+                amount: *amount,
+            }),
+            EscrowAsset::Cw20 { contract_addr, amount } => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: *amount,
+                })?,
+                funds: vec![],
+            })),
+            EscrowAsset::Cw721 { contract_addr, token_id } => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: recipient.to_string(),
+                    token_id: token_id.clone(),
+                })?,
+                funds: vec![],
+            })),
+        }
+    }
+    if !coins.is_empty() {
+        messages.insert(0, CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins,
+        }));
+    }
+
+    Ok(messages)
+}
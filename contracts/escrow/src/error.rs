@@ -0,0 +1,32 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid escrow basket: {reason}")]
+    InvalidBasket { reason: String },
+
+    #[error("The escrow expired")]
+    EscrowExpired {},
+
+    #[error("The deposit is not part of the sender basket or exceeds it")]
+    InvalidDeposit {},
+
+    #[error("Both baskets must be fully funded")]
+    NotFunded {},
+
+    #[error("Both parties must approve the swap")]
+    NotApproved {},
+
+    #[error("The escrow was approved by both parties and has not expired")]
+    EscrowLocked {},
+}
@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+
+use crate::state::EscrowAsset;
+
+/// This structure describes the parameters used for creating an escrow contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Creates a swap of the creator basket against the counterparty basket
+    Create {
+        counterparty: String,
+        creator_assets: Vec<EscrowAsset>,
+        counterparty_assets: Vec<EscrowAsset>,
+        expires_at: u64,
+    },
+    /// Deposits the sent coins into the sender basket
+    Fund { escrow_id: u64 },
+    /// Approves the swap once both baskets are funded. Only the parties can execute it.
+    Approve { escrow_id: u64 },
+    /// Swaps the baskets once both parties approved. Only the parties can execute it.
+    Execute { escrow_id: u64 },
+    /// Returns the deposits to their owners. The parties can execute it until both approved,
+    /// anyone can execute it after the expiration.
+    Refund { escrow_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits the sent tokens into the sender basket
+    Fund { escrow_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    /// Deposits the sent NFT into the sender basket
+    Fund { escrow_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns an escrow and its funding state.
+    /// Return type: EscrowResponse.
+    Escrow { escrow_id: u64 },
+    /// Returns escrows by id.
+    /// Return type: EscrowsResponse.
+    Escrows {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowSideResponse {
+    pub address: String,
+    pub assets: Vec<EscrowAsset>,
+    pub deposited: Vec<EscrowAsset>,
+    pub funded: bool,
+    pub approved: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowResponse {
+    pub escrow_id: u64,
+    pub creator: EscrowSideResponse,
+    pub counterparty: EscrowSideResponse,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowsResponse {
+    pub escrows: Vec<EscrowResponse>,
+}
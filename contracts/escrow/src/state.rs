@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowAsset {
+    Native { denom: String, amount: Uint128 },
+    Cw20 { contract_addr: String, amount: Uint128 },
+    Cw721 { contract_addr: String, token_id: String },
+}
+
+impl EscrowAsset {
+    /// Returns whether both assets are the same coin, token or NFT, whatever their amounts
+    pub fn same_asset(&self, other: &EscrowAsset) -> bool {
+        match (self, other) {
+            (EscrowAsset::Native { denom: a, .. }, EscrowAsset::Native { denom: b, .. }) => a == b,
+            (
+                EscrowAsset::Cw20 { contract_addr: a, .. },
+                EscrowAsset::Cw20 { contract_addr: b, .. },
+            ) => a == b,
+            (
+                EscrowAsset::Cw721 { contract_addr: a, token_id: x },
+                EscrowAsset::Cw721 { contract_addr: b, token_id: y },
+            ) => a == b && x == y,
+            _ => false,
+        }
+    }
+
+    pub fn amount(&self) -> Uint128 {
+        match self {
+            EscrowAsset::Native { amount, .. } | EscrowAsset::Cw20 { amount, .. } => *amount,
+            EscrowAsset::Cw721 { .. } => Uint128::new(1),
+        }
+    }
+
+    fn add_amount(&mut self, added: Uint128) {
+        match self {
+            EscrowAsset::Native { amount, .. } | EscrowAsset::Cw20 { amount, .. } => *amount += added,
+            EscrowAsset::Cw721 { .. } => {}
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowSide {
+    pub address: Addr,
+    /// The basket this party gives in the swap
+    pub assets: Vec<EscrowAsset>,
+    /// The part of the basket deposited so far
+    pub deposited: Vec<EscrowAsset>,
+    pub approved: bool,
+}
+
+impl EscrowSide {
+    pub fn new(address: Addr, assets: Vec<EscrowAsset>) -> Self {
+        EscrowSide {
+            address,
+            assets,
+            deposited: vec![],
+            approved: false,
+        }
+    }
+
+    /// Adds the asset to the deposits, returns false if it is not part of the basket or
+    /// exceeds the basket amount
+    pub fn deposit(&mut self, asset: EscrowAsset) -> bool {
+        let required = match self.assets.iter().find(|a| a.same_asset(&asset)) {
+            Some(required) => required.amount(),
+            None => return false,
+        };
+
+        match self.deposited.iter_mut().find(|a| a.same_asset(&asset)) {
+            Some(deposited) => {
+                if matches!(asset, EscrowAsset::Cw721 { .. })
+                    || deposited.amount() + asset.amount() > required {
+                    return false;
+                }
+                deposited.add_amount(asset.amount());
+            }
+            None => {
+                if asset.amount() > required {
+                    return false;
+                }
+                self.deposited.push(asset);
+            }
+        }
+
+        true
+    }
+
+    pub fn is_funded(&self) -> bool {
+        self.assets.iter().all(|required| {
+            self.deposited
+                .iter()
+                .any(|d| d.same_asset(required) && d.amount() == required.amount())
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Escrow {
+    pub creator: EscrowSide,
+    pub counterparty: EscrowSide,
+    /// Funding, approving and executing are not possible after this time
+    pub expires_at: u64,
+}
+
+impl Escrow {
+    /// Returns the side of the address if it is one of the parties
+    pub fn side_mut(&mut self, address: &Addr) -> Option<&mut EscrowSide> {
+        if &self.creator.address == address {
+            Some(&mut self.creator)
+        } else if &self.counterparty.address == address {
+            Some(&mut self.counterparty)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_party(&self, address: &Addr) -> bool {
+        &self.creator.address == address || &self.counterparty.address == address
+    }
+}
+
+pub const ESCROW_COUNT: Item<u64> = Item::new("escrow_count");
+pub const ESCROWS: Map<U64Key, Escrow> = Map::new("escrows");
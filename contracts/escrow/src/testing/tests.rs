@@ -0,0 +1,217 @@
+use cosmwasm_std::{
+    BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, to_binary,
+    Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, EscrowResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::EscrowAsset;
+
+const CREATOR: &str = "mock_creator";
+const COUNTERPARTY: &str = "mock_counterparty";
+const TOKEN: &str = "mock_token";
+const NFT: &str = "mock_nft";
+const DENOM: &str = "uusd";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    instantiate(deps, env, mock_info(CREATOR, &[]), InstantiateMsg {}).unwrap()
+}
+
+/// Creates a swap of an NFT and 100 uusd against 5000 tokens
+fn create_msg(env: &Env) -> ExecuteMsg {
+    ExecuteMsg::Create {
+        counterparty: COUNTERPARTY.to_string(),
+        creator_assets: vec![
+            EscrowAsset::Cw721 { contract_addr: NFT.to_string(), token_id: "1".to_string() },
+            EscrowAsset::Native { denom: DENOM.to_string(), amount: Uint128::new(100) },
+        ],
+        counterparty_assets: vec![
+            EscrowAsset::Cw20 { contract_addr: TOKEN.to_string(), amount: Uint128::new(5_000) },
+        ],
+        expires_at: env.block.time.seconds() + 3_600,
+    }
+}
+
+fn fund_nft_msg(sender: &str, token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: sender.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_binary(&Cw721HookMsg::Fund { escrow_id: 1 }).unwrap(),
+    })
+}
+
+fn fund_token_msg(sender: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Fund { escrow_id: 1 }).unwrap(),
+    })
+}
+
+fn wasm_msg(contract_addr: &str, msg: Binary) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg,
+        funds: vec![],
+    }))
+}
+
+fn token_transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    wasm_msg(TOKEN, to_binary(&Cw20ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount: Uint128::new(amount),
+    }).unwrap())
+}
+
+fn nft_transfer_msg(recipient: &str) -> SubMsg {
+    wasm_msg(NFT, to_binary(&Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: "1".to_string(),
+    }).unwrap())
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin::new(amount, DENOM)],
+    }))
+}
+
+#[test]
+fn test_swap() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), create_msg(&env)).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(NFT, &[]), fund_nft_msg(CREATOR, "2")).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDeposit {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(NFT, &[]), fund_nft_msg("anyone", "1")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(NFT, &[]), fund_nft_msg(CREATOR, "1")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[Coin::new(100, DENOM)]),
+            ExecuteMsg::Fund { escrow_id: 1 }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::Approve { escrow_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NotFunded {});
+
+    // Tokens can be deposited in several parts but not above the basket amount
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fund_token_msg(COUNTERPARTY, 3_000)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fund_token_msg(COUNTERPARTY, 3_000)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDeposit {});
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fund_token_msg(COUNTERPARTY, 2_000)).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Escrow { escrow_id: 1 }).unwrap();
+    let escrow: EscrowResponse = from_binary(&res).unwrap();
+    assert!(escrow.creator.funded && escrow.counterparty.funded);
+
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Approve { escrow_id: 1 }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::Execute { escrow_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NotApproved {});
+    execute(deps.as_mut(), env.clone(), mock_info(COUNTERPARTY, &[]), ExecuteMsg::Approve { escrow_id: 1 }).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(COUNTERPARTY, &[]),
+                      ExecuteMsg::Execute { escrow_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![
+        bank_send_msg(COUNTERPARTY, 100),
+        nft_transfer_msg(COUNTERPARTY),
+        token_transfer_msg(CREATOR, 5_000),
+    ]);
+    query(deps.as_ref(), env, QueryMsg::Escrow { escrow_id: 1 }).unwrap_err();
+}
+
+#[test]
+fn test_refund() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), create_msg(&env)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(NFT, &[]), fund_nft_msg(CREATOR, "1")).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info(COUNTERPARTY, &[]),
+                      ExecuteMsg::Refund { escrow_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![nft_transfer_msg(CREATOR)]);
+
+    // Approved escrows can only be refunded after the expiration
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), create_msg(&env)).unwrap();
+    let fund_nft = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: CREATOR.to_string(),
+        token_id: "1".to_string(),
+        msg: to_binary(&Cw721HookMsg::Fund { escrow_id: 2 }).unwrap(),
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(NFT, &[]), fund_nft).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[Coin::new(100, DENOM)]),
+            ExecuteMsg::Fund { escrow_id: 2 }).unwrap();
+    let fund_token = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: COUNTERPARTY.to_string(),
+        amount: Uint128::new(5_000),
+        msg: to_binary(&Cw20HookMsg::Fund { escrow_id: 2 }).unwrap(),
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fund_token).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Approve { escrow_id: 2 }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(COUNTERPARTY, &[]), ExecuteMsg::Approve { escrow_id: 2 }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::Refund { escrow_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::EscrowLocked {});
+
+    env.block.time = env.block.time.plus_seconds(3_600);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]),
+                      ExecuteMsg::Execute { escrow_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::EscrowExpired {});
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::Refund { escrow_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![
+        bank_send_msg(CREATOR, 100),
+        nft_transfer_msg(CREATOR),
+        token_transfer_msg(COUNTERPARTY, 5_000),
+    ]);
+}
+
+#[test]
+fn test_create_validation() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::Create {
+        counterparty: COUNTERPARTY.to_string(),
+        creator_assets: vec![
+            EscrowAsset::Native { denom: DENOM.to_string(), amount: Uint128::new(100) },
+            EscrowAsset::Native { denom: DENOM.to_string(), amount: Uint128::new(200) },
+        ],
+        counterparty_assets: vec![],
+        expires_at: env.block.time.seconds() + 3_600,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBasket { reason: "duplicated asset".to_string() });
+
+    let msg = ExecuteMsg::Create {
+        counterparty: CREATOR.to_string(),
+        creator_assets: vec![],
+        counterparty_assets: vec![],
+        expires_at: env.block.time.seconds() + 3_600,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBasket { reason: "cannot swap with yourself".to_string() });
+
+    let msg = ExecuteMsg::Create {
+        counterparty: COUNTERPARTY.to_string(),
+        creator_assets: vec![],
+        counterparty_assets: vec![],
+        expires_at: env.block.time.seconds(),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::EscrowExpired {});
+}
@@ -0,0 +1,443 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_protocol::registry::{AddressResponse, QueryMsg as RegistryQueryMsg, STAKING};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse, TotalStakedResponse};
+use luart_protocol::token::{QueryMsg as TokenQueryMsg, TotalSupplyResponse};
+use luart_utils::batch::batch_query;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ClaimableResponse, ConfigResponse, EpochResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg,
+};
+use crate::state::{
+    add_asset, asset_key, Config, CONFIG, EPOCH_FEES, EPOCH_HEIGHTS, EPOCH_LENGTH, Insurance, INSURANCE,
+    NEXT_CLAIM_EPOCH, TRACKED_BALANCES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-fee-distributor";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The maximum number of epochs paid by a single claim
+const MAX_CLAIM_EPOCHS: usize = 52;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    let registry = match msg.registry {
+        Some(registry) => Some(deps.api.addr_validate(&registry)?),
+        None => None,
+    };
+    let token = match msg.token {
+        Some(token) => Some(deps.api.addr_validate(&token)?),
+        None => None,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        staking: deps.api.addr_validate(&msg.staking)?,
+        assets: msg.assets,
+        start_time: msg.start_time.unwrap_or_else(|| env.block.time.seconds()),
+        registry,
+        token,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Checkpoint {} => checkpoint(deps, env),
+        ExecuteMsg::Claim {} => claim(deps, env, info),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            staking,
+            assets,
+            registry,
+            token,
+        } => update_config(deps, info, owner, staking, assets, registry, token),
+        ExecuteMsg::UpdateInsurance { fund, share } => update_insurance(deps, info, fund, share),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// Credits the fee balances received since the last checkpoint to the running epoch.
+/// The fees are sent to the contract by plain transfers, so anyone can checkpoint them.
+pub fn checkpoint(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let insurance = INSURANCE.may_load(deps.storage)?;
+    let epoch = config.epoch_at(env.block.time.seconds());
+
+    // The checkpoint creating the epoch fixes its snapshot height, before any claim can run
+    let mut fees = match EPOCH_FEES.may_load(deps.storage, U64Key::new(epoch))? {
+        Some(fees) => fees,
+        None => {
+            EPOCH_HEIGHTS.save(deps.storage, U64Key::new(epoch), &env.block.height)?;
+            vec![]
+        }
+    };
+    let mut response = Response::new()
+        .add_attribute("action", "checkpoint")
+        .add_attribute("epoch", epoch.to_string());
+
+    for info in config.assets.iter() {
+        let key = asset_key(info);
+        let mut balance = query_asset_balance(deps.as_ref(), &env.contract.address, info)?;
+        let tracked = TRACKED_BALANCES.may_load(deps.storage, key)?.unwrap_or_default();
+        let mut received = balance.saturating_sub(tracked);
+        if received.is_zero() {
+            continue;
+        }
+
+        // The insurance share leaves the contract before the rest is credited to the stakers
+        if let Some(insurance) = &insurance {
+            let amount = received * insurance.share;
+            if !amount.is_zero() {
+                let asset = Asset { info: info.clone(), amount };
+                response = response
+                    .add_message(asset_transfer_msg(&asset, &insurance.fund)?)
+                    .add_attribute(format!("insurance_{}", key), amount);
+                received = received.checked_sub(amount)?;
+                balance = balance.checked_sub(amount)?;
+            }
+        }
+
+        add_asset(&mut fees, info, received);
+        TRACKED_BALANCES.save(deps.storage, key, &balance)?;
+        response = response.add_attribute(key, received);
+    }
+
+    EPOCH_FEES.save(deps.storage, U64Key::new(epoch), &fees)?;
+
+    Ok(response)
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let (rewards, next_epoch) = compute_claimable(deps.as_ref(), &config, &info.sender, &env)?;
+    NEXT_CLAIM_EPOCH.save(deps.storage, &info.sender, &next_epoch)?;
+
+    let mut messages = vec![];
+    for asset in rewards.iter().filter(|a| !a.amount.is_zero()) {
+        let key = asset_key(&asset.info);
+        TRACKED_BALANCES.update(deps.storage, key, |tracked| -> StdResult<_> {
+            Ok(tracked.unwrap_or_default().checked_sub(asset.amount)?)
+        })?;
+        messages.push(asset_transfer_msg(asset, &info.sender)?);
+    }
+    if messages.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim")
+        .add_attribute("address", info.sender)
+        .add_attribute("next_epoch", next_epoch.to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    staking: Option<String>,
+    assets: Option<Vec<AssetInfo>>,
+    registry: Option<String>,
+    token: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(staking) = staking {
+        config.staking = deps.api.addr_validate(&staking)?;
+    }
+
+    if let Some(assets) = assets {
+        config.assets = assets;
+    }
+
+    if let Some(registry) = registry {
+        config.registry = Some(deps.api.addr_validate(&registry)?);
+    }
+
+    if let Some(token) = token {
+        config.token = Some(deps.api.addr_validate(&token)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_insurance(
+    deps: DepsMut,
+    info: MessageInfo,
+    fund: Option<String>,
+    share: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if share > Decimal::one() {
+        return Err(ContractError::InvalidShare {});
+    }
+
+    match fund {
+        Some(fund) => INSURANCE.save(deps.storage, &Insurance {
+            fund: deps.api.addr_validate(&fund)?,
+            share,
+        })?,
+        None => INSURANCE.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_insurance")
+        .add_attribute("share", share.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::Epoch { epoch } => to_binary(&query_epoch(deps, epoch)?),
+        QueryMsg::Claimable { address } => to_binary(&query_claimable(deps, env, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let insurance = INSURANCE.may_load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        staking: config.staking.to_string(),
+        registry: config.registry.as_ref().map(|r| r.to_string()),
+        token: config.token.as_ref().map(|t| t.to_string()),
+        current_epoch: config.epoch_at(env.block.time.seconds()),
+        assets: config.assets,
+        start_time: config.start_time,
+        epoch_length: EPOCH_LENGTH,
+        insurance_fund: insurance.as_ref().map(|i| i.fund.to_string()),
+        insurance_share: insurance.map(|i| i.share).unwrap_or_default(),
+    })
+}
+
+pub fn query_epoch(deps: Deps, epoch: u64) -> StdResult<EpochResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(EpochResponse {
+        epoch,
+        start_time: config.epoch_start(epoch),
+        end_time: config.epoch_end(epoch),
+        fees: EPOCH_FEES.may_load(deps.storage, U64Key::new(epoch))?.unwrap_or_default(),
+        snapshot_height: EPOCH_HEIGHTS.may_load(deps.storage, U64Key::new(epoch))?,
+    })
+}
+
+pub fn query_claimable(deps: Deps, env: Env, address: String) -> StdResult<ClaimableResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let (rewards, next_epoch) = compute_claimable(deps, &config, &address, &env)?;
+    Ok(ClaimableResponse { rewards, next_epoch })
+}
+
+/// What shares the fees of the epochs
+enum ShareSource {
+    /// The LUART holder snapshots at the epoch snapshot height
+    Holders(Addr),
+    /// The stakes at the end of the epoch
+    Stakers(Addr),
+}
+
+/// Returns the staker share of the fees of the ended epochs, up to [`MAX_CLAIM_EPOCHS`] epochs,
+/// and the first epoch left to claim. Each epoch is shared by the stakes at its end, or by the
+/// token holders at its snapshot height when the token is set.
+fn compute_claimable(
+    deps: Deps,
+    config: &Config,
+    staker: &Addr,
+    env: &Env,
+) -> StdResult<(Vec<Asset>, u64)> {
+    let current_epoch = config.epoch_at(env.block.time.seconds());
+    let first_epoch = NEXT_CLAIM_EPOCH.may_load(deps.storage, staker)?.unwrap_or_default();
+
+    let epochs: StdResult<Vec<(u64, Vec<Asset>)>> = EPOCH_FEES
+        .range(
+            deps.storage,
+            Some(Bound::inclusive_int(first_epoch)),
+            Some(Bound::exclusive_int(current_epoch)),
+            Order::Ascending,
+        )
+        .take(MAX_CLAIM_EPOCHS)
+        .map(|item| {
+            let (k, fees) = item?;
+            Ok((parse_epoch(&k)?, fees))
+        })
+        .collect();
+    let epochs = epochs?;
+
+    // Claims stop at the last paid epoch when more than the maximum epochs are pending
+    let next_epoch = match epochs.last() {
+        Some((epoch, _)) if epochs.len() == MAX_CLAIM_EPOCHS => epoch + 1,
+        _ => current_epoch.max(first_epoch),
+    };
+
+    let source = match &config.token {
+        Some(token) => ShareSource::Holders(token.clone()),
+        None => ShareSource::Stakers(staking_contract(deps, config)?),
+    };
+    let mut rewards = vec![];
+    for (epoch, fees) in epochs {
+        let (amount, total) = match &source {
+            ShareSource::Holders(token) => {
+                let height = EPOCH_HEIGHTS.may_load(deps.storage, U64Key::new(epoch))?.ok_or_else(|| {
+                    StdError::generic_err(format!("Missing the snapshot height of epoch {}", epoch))
+                })?;
+                holder_weights(deps, token, staker, height)?
+            }
+            ShareSource::Stakers(staking) => staker_weights(deps, staking, staker, config.epoch_end(epoch))?,
+        };
+        if amount.is_zero() {
+            continue;
+        }
+
+        for fee in fees.iter() {
+            let share = fee.amount.multiply_ratio(amount, total);
+            add_asset(&mut rewards, &fee.info, share);
+        }
+    }
+
+    Ok((rewards, next_epoch))
+}
+
+/// Returns the stake of the staker and the total stake at the given time
+fn staker_weights(deps: Deps, staking: &Addr, staker: &Addr, time: u64) -> StdResult<(Uint128, Uint128)> {
+    let staker_info: StakerInfoResponse = deps.querier.query_wasm_smart(
+        staking,
+        &StakingQueryMsg::StakerInfoAt { staker: staker.to_string(), time },
+    )?;
+    if staker_info.bond_amount.is_zero() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+    let total: TotalStakedResponse = deps.querier.query_wasm_smart(
+        staking,
+        &StakingQueryMsg::TotalStakedAt { time },
+    )?;
+    Ok((staker_info.bond_amount, total.total_staked))
+}
+
+/// Returns the balance of the holder and the total supply at the start of the given height
+fn holder_weights(deps: Deps, token: &Addr, holder: &Addr, height: u64) -> StdResult<(Uint128, Uint128)> {
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &TokenQueryMsg::BalanceAt { address: holder.to_string(), height },
+    )?;
+    if balance.balance.is_zero() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+    let total: TotalSupplyResponse = deps.querier.query_wasm_smart(
+        token,
+        &TokenQueryMsg::TotalSupplyAt { height },
+    )?;
+    Ok((balance.balance, total.total_supply))
+}
+
+/// Returns the staking contract registered in the registry, or the configured one without registry
+fn staking_contract(deps: Deps, config: &Config) -> StdResult<Addr> {
+    match &config.registry {
+        Some(registry) => {
+            let res: AddressResponse = deps.querier.query_wasm_smart(registry, &RegistryQueryMsg::Address {
+                name: STAKING.to_string(),
+            })?;
+            Ok(res.address)
+        }
+        None => Ok(config.staking.clone()),
+    }
+}
+
+fn query_asset_balance(deps: Deps, address: &Addr, info: &AssetInfo) -> StdResult<Uint128> {
+    Ok(match info {
+        AssetInfo::NativeToken { denom } => deps.querier.query_balance(address, denom)?.amount,
+        AssetInfo::Token { contract_addr } => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance { address: address.to_string() },
+            )?;
+            res.balance
+        }
+    })
+}
+
+fn parse_epoch(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted epoch key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn asset_transfer_msg(asset: &Asset, recipient: &Addr) -> StdResult<CosmosMsg> {
+    Ok(match &asset.info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
@@ -0,0 +1,95 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Decimal;
+use terraswap::asset::{Asset, AssetInfo};
+
+/// This structure describes the parameters used for creating a fee distributor contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART staking contract
+    pub staking: String,
+    /// The fee assets distributed to the stakers
+    pub assets: Vec<AssetInfo>,
+    /// The start time of the first epoch, defaults to the current time
+    pub start_time: Option<u64>,
+    /// The registry the staking contract is resolved through, replacing `staking`
+    pub registry: Option<String>,
+    /// The LUART token whose holder snapshots share the epochs instead of the stakes
+    pub token: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Credits the fees received since the last checkpoint to the running epoch
+    Checkpoint {},
+    /// Claims the sender share of the fees of the ended epochs
+    Claim {},
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        staking: Option<String>,
+        assets: Option<Vec<AssetInfo>>,
+        registry: Option<String>,
+        token: Option<String>,
+    },
+    /// Sets the insurance fund receiving the share of the fees at each checkpoint, no fund
+    /// stops it. Only the owner can execute it.
+    UpdateInsurance { fund: Option<String>, share: Decimal },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the fees collected during an epoch.
+    /// Return type: EpochResponse.
+    Epoch { epoch: u64 },
+    /// Returns the fees the address can claim.
+    /// Return type: ClaimableResponse.
+    Claimable { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub staking: String,
+    pub registry: Option<String>,
+    pub token: Option<String>,
+    pub assets: Vec<AssetInfo>,
+    pub start_time: u64,
+    pub epoch_length: u64,
+    pub current_epoch: u64,
+    pub insurance_fund: Option<String>,
+    pub insurance_share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochResponse {
+    pub epoch: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub fees: Vec<Asset>,
+    /// The height the holder snapshots are read at, unset until a checkpoint creates the epoch
+    pub snapshot_height: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableResponse {
+    pub rewards: Vec<Asset>,
+    /// The first epoch left to claim after claiming the rewards
+    pub next_epoch: u64,
+}
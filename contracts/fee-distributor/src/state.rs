@@ -0,0 +1,78 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use terraswap::asset::{Asset, AssetInfo};
+
+/// The length of a distribution epoch, one week
+pub const EPOCH_LENGTH: u64 = 7 * 86_400;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    /// The staking contract reporting the stakes at the end of each epoch
+    pub staking: Addr,
+    /// The fee assets distributed to the stakers
+    pub assets: Vec<AssetInfo>,
+    /// The start time of the first epoch
+    pub start_time: u64,
+    /// The registry the staking contract is resolved through, replacing `staking`
+    #[serde(default)]
+    pub registry: Option<Addr>,
+    /// The LUART token whose holder snapshots share the epochs, replacing the stakes
+    #[serde(default)]
+    pub token: Option<Addr>,
+}
+
+impl Config {
+    /// Returns the epoch running at the given time
+    pub fn epoch_at(&self, time: u64) -> u64 {
+        time.saturating_sub(self.start_time) / EPOCH_LENGTH
+    }
+
+    pub fn epoch_start(&self, epoch: u64) -> u64 {
+        self.start_time + epoch * EPOCH_LENGTH
+    }
+
+    /// Returns the end time of the epoch, the stakes are read at this time
+    pub fn epoch_end(&self, epoch: u64) -> u64 {
+        self.epoch_start(epoch + 1)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Insurance {
+    /// The insurance fund receiving its share of the fees
+    pub fund: Addr,
+    /// The share of the received fees sent to the fund before crediting the epoch
+    pub share: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const INSURANCE: Item<Insurance> = Item::new("insurance");
+/// The fee balance of each asset already credited to an epoch and not claimed yet
+pub const TRACKED_BALANCES: Map<&str, Uint128> = Map::new("tracked_balances");
+/// The fees collected during each epoch
+pub const EPOCH_FEES: Map<U64Key, Vec<Asset>> = Map::new("epoch_fees");
+/// The height of the checkpoint creating each epoch, the holder snapshots sharing the epoch
+/// are read at the start of this height
+pub const EPOCH_HEIGHTS: Map<U64Key, u64> = Map::new("epoch_heights");
+/// The first epoch each staker has not claimed yet
+pub const NEXT_CLAIM_EPOCH: Map<&Addr, u64> = Map::new("next_claim_epoch");
+
+/// Returns the storage key of an asset, its denom or token address
+pub fn asset_key(info: &AssetInfo) -> &str {
+    match info {
+        AssetInfo::NativeToken { denom } => denom,
+        AssetInfo::Token { contract_addr } => contract_addr,
+    }
+}
+
+/// Adds the amount to the matching asset of the list
+pub fn add_asset(assets: &mut Vec<Asset>, info: &AssetInfo, amount: Uint128) {
+    match assets.iter_mut().find(|a| &a.info == info) {
+        Some(asset) => asset.amount += amount,
+        None => assets.push(Asset { info: info.clone(), amount }),
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Addr, Binary, Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use luart_protocol::registry::{AddressResponse, QueryMsg as RegistryQueryMsg, STAKING};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse, TotalStakedResponse};
+use luart_protocol::token::{QueryMsg as TokenQueryMsg, TotalSupplyResponse};
+
+pub const MOCK_STAKING: &str = "mock_staking";
+/// The registry the staking contract is registered in
+pub const MOCK_REGISTRY: &str = "mock_registry";
+/// The LUART token answering the holder snapshot queries
+pub const MOCK_LUART: &str = "mock_luart";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the staking and cw20 balance queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_balances: HashMap<String, Uint128>,
+    stakes: HashMap<(String, u64), Uint128>,
+    holder_balances: HashMap<(String, u64), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if contract_addr == MOCK_STAKING {
+                    return self.handle_staking_query(msg);
+                }
+                if contract_addr == MOCK_REGISTRY {
+                    return self.handle_registry_query(msg);
+                }
+                if contract_addr == MOCK_LUART {
+                    return self.handle_luart_query(msg);
+                }
+                match from_binary(msg) {
+                    Ok(Cw20QueryMsg::Balance { address: _ }) => {
+                        let balance = self.token_balances.get(contract_addr).copied().unwrap_or_default();
+                        SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse { balance })))
+                    }
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported token query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn handle_staking_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(StakingQueryMsg::StakerInfoAt { staker, time }) => {
+                let bond_amount = self.stakes.get(&(staker.clone(), time)).copied().unwrap_or_default();
+                SystemResult::Ok(ContractResult::from(to_binary(&StakerInfoResponse { staker, bond_amount })))
+            }
+            Ok(StakingQueryMsg::TotalStakedAt { time }) => {
+                let total_staked = self.stakes
+                    .iter()
+                    .filter(|((_, t), _)| *t == time)
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                SystemResult::Ok(ContractResult::from(to_binary(&TotalStakedResponse { total_staked })))
+            }
+            Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                error: "Unsupported staking query".to_string(),
+                request: msg.clone(),
+            }),
+            Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                error: format!("Parsing staking query: {}", e),
+                request: msg.clone(),
+            }),
+        }
+    }
+
+    fn handle_luart_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(TokenQueryMsg::BalanceAt { address, height }) => {
+                let balance = self.holder_balances.get(&(address, height)).copied().unwrap_or_default();
+                SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse { balance })))
+            }
+            Ok(TokenQueryMsg::TotalSupplyAt { height }) => {
+                let total_supply = self.holder_balances
+                    .iter()
+                    .filter(|((_, h), _)| *h == height)
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                SystemResult::Ok(ContractResult::from(to_binary(&TotalSupplyResponse { total_supply })))
+            }
+            Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                error: "Unsupported token query".to_string(),
+                request: msg.clone(),
+            }),
+            Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                error: format!("Parsing token query: {}", e),
+                request: msg.clone(),
+            }),
+        }
+    }
+
+    fn handle_registry_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(RegistryQueryMsg::Address { name }) if name == STAKING => {
+                let address = Addr::unchecked(MOCK_STAKING);
+                SystemResult::Ok(ContractResult::from(to_binary(&AddressResponse { name, address })))
+            }
+            _ => SystemResult::Err(SystemError::InvalidRequest {
+                error: "Unsupported registry query".to_string(),
+                request: msg.clone(),
+            }),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            stakes: HashMap::new(),
+            holder_balances: HashMap::new(),
+        }
+    }
+
+    // configure the contract balance of a cw20 token
+    pub fn with_token_balance(&mut self, token: &str, balance: u128) {
+        self.token_balances.insert(token.to_string(), Uint128::new(balance));
+    }
+
+    // configure the stake of an address at the given time
+    pub fn with_stake(&mut self, staker: &str, time: u64, amount: u128) {
+        self.stakes.insert((staker.to_string(), time), Uint128::new(amount));
+    }
+
+    // configure the LUART balance of a holder at the start of the given height
+    pub fn with_holder_balance(&mut self, holder: &str, height: u64, amount: u128) {
+        self.holder_balances.insert((holder.to_string(), height), Uint128::new(amount));
+    }
+
+    // configure the native contract balance
+    pub fn with_balance(&mut self, balance: &[Coin]) {
+        self.base.update_balance(MOCK_CONTRACT_ADDR, balance.to_vec());
+    }
+}
@@ -0,0 +1,255 @@
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::Cw20ExecuteMsg;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ClaimableResponse, ConfigResponse, EpochResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::EPOCH_LENGTH;
+use crate::testing::mock_querier::{mock_dependencies, MOCK_LUART, MOCK_REGISTRY, MOCK_STAKING};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const CAROL: &str = "carol";
+const DENOM: &str = "uusd";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        staking: MOCK_STAKING.to_string(),
+        assets: vec![
+            AssetInfo::NativeToken { denom: DENOM.to_string() },
+            AssetInfo::Token { contract_addr: TOKEN.to_string() },
+        ],
+        start_time: None,
+        registry: None,
+        token: None,
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn native(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn token(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_claim_epoch_share() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    deps.querier.with_token_balance(TOKEN, 200);
+    let mut env = mock_env();
+    let epoch_end = env.block.time.seconds() + EPOCH_LENGTH;
+    default_instantiate(deps.as_mut(), env.clone());
+    deps.querier.with_stake(ALICE, epoch_end, 300);
+    deps.querier.with_stake(BOB, epoch_end, 100);
+
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { epoch: 0 }).unwrap();
+    let epoch: EpochResponse = from_binary(&res).unwrap();
+    assert_eq!(epoch, EpochResponse {
+        epoch: 0,
+        start_time: env.block.time.seconds(),
+        end_time: epoch_end,
+        fees: vec![native(1_000), token(200)],
+        snapshot_height: Some(env.block.height),
+    });
+
+    // The fees of the running epoch cannot be claimed yet
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Claimable { address: ALICE.to_string() }).unwrap();
+    let claimable: ClaimableResponse = from_binary(&res).unwrap();
+    assert_eq!(claimable, ClaimableResponse { rewards: vec![native(750), token(150)], next_epoch: 1 });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 750), transfer_msg(ALICE, 150)]);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(BOB, 250), transfer_msg(BOB, 50)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+    let err = execute(deps.as_mut(), env, mock_info("anyone", &[]), ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+}
+
+#[test]
+fn test_checkpoint_tracks_claimed_fees() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    let mut env = mock_env();
+    let start_time = env.block.time.seconds();
+    default_instantiate(deps.as_mut(), env.clone());
+    deps.querier.with_stake(ALICE, start_time + EPOCH_LENGTH, 100);
+    deps.querier.with_stake(ALICE, start_time + 2 * EPOCH_LENGTH, 100);
+    deps.querier.with_stake(BOB, start_time + 2 * EPOCH_LENGTH, 300);
+
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap();
+
+    // Only the fees received after the claim are credited to the second epoch
+    deps.querier.with_balance(&[Coin::new(400, DENOM)]);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { epoch: 1 }).unwrap();
+    let epoch: EpochResponse = from_binary(&res).unwrap();
+    assert_eq!(epoch.fees, vec![native(400)]);
+
+    // Bob joined in the second epoch and only shares its fees
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(BOB, 300)]);
+    let res = execute(deps.as_mut(), env, mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 100)]);
+}
+
+#[test]
+fn test_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking: Some("new_staking".to_string()),
+        assets: None,
+        registry: None,
+        token: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), msg).unwrap();
+}
+
+#[test]
+fn test_staking_from_registry() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    let mut env = mock_env();
+    let epoch_end = env.block.time.seconds() + EPOCH_LENGTH;
+    default_instantiate(deps.as_mut(), env.clone());
+    deps.querier.with_stake(ALICE, epoch_end, 300);
+    deps.querier.with_stake(BOB, epoch_end, 100);
+
+    // The stakes are read from the staking contract registered in the registry
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking: Some("old_staking".to_string()),
+        assets: None,
+        registry: Some(MOCK_REGISTRY.to_string()),
+        token: None,
+    }).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let res = execute(deps.as_mut(), env, mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 750)]);
+}
+
+#[test]
+fn test_holder_snapshot_share() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking: None,
+        assets: None,
+        registry: None,
+        token: Some(MOCK_LUART.to_string()),
+    }).unwrap();
+
+    // The checkpoint creating the epoch fixes the snapshot height
+    let snapshot_height = env.block.height;
+    deps.querier.with_holder_balance(ALICE, snapshot_height, 300);
+    deps.querier.with_holder_balance(BOB, snapshot_height, 100);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { epoch: 0 }).unwrap();
+    let epoch: EpochResponse = from_binary(&res).unwrap();
+    assert_eq!(epoch.snapshot_height, Some(snapshot_height));
+
+    // Alice hands her tokens to Carol after the epoch end, Carol shares nothing of the epoch
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    env.block.height += 100;
+    deps.querier.with_holder_balance(ALICE, env.block.height, 0);
+    deps.querier.with_holder_balance(BOB, env.block.height, 100);
+    deps.querier.with_holder_balance(CAROL, env.block.height, 300);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 750)]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CAROL, &[]), ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+    let res = execute(deps.as_mut(), env, mock_info(BOB, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(BOB, 250)]);
+}
+
+#[test]
+fn test_insurance_share() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    deps.querier.with_token_balance(TOKEN, 200);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateInsurance {
+        fund: Some("insurance".to_string()),
+        share: Decimal::percent(101),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidShare {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateInsurance {
+        fund: Some("insurance".to_string()),
+        share: Decimal::percent(10),
+    }).unwrap();
+
+    // A tenth of the fees goes to the insurance fund, the rest to the stakers
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg("insurance", 100), transfer_msg("insurance", 20)]);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { epoch: 0 }).unwrap();
+    let epoch: EpochResponse = from_binary(&res).unwrap();
+    assert_eq!(epoch.fees, vec![native(900), token(180)]);
+
+    // Only the fees received afterwards are shared again
+    deps.querier.with_balance(&[Coin::new(1_400, DENOM)]);
+    deps.querier.with_token_balance(TOKEN, 180);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Checkpoint {}).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg("insurance", 50)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.insurance_fund, Some("insurance".to_string()));
+    assert_eq!(config.insurance_share, Decimal::percent(10));
+}
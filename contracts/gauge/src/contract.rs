@@ -0,0 +1,283 @@
+use cosmwasm_std::{
+    Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_binary,
+    Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::U64Key;
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, GaugeVote, GaugeWeight, GaugeWeightsResponse, InstantiateMsg,
+    MigrateMsg, QueryMsg, UserVoteResponse, UserVotesResponse,
+};
+use crate::state::{Config, CONFIG, EPOCH_LENGTH, GAUGE_VOTES, USER_VOTES, UserVote};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-gauge";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    let mut gauges: Vec<Addr> = vec![];
+    for gauge in msg.gauges.iter() {
+        let gauge = deps.api.addr_validate(gauge)?;
+        if gauges.contains(&gauge) {
+            return Err(ContractError::GaugeExists { gauge: gauge.to_string() });
+        }
+        gauges.push(gauge);
+    }
+
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        staking: deps.api.addr_validate(&msg.staking)?,
+        gauges,
+        start_time: msg.start_time.unwrap_or_else(|| env.block.time.seconds()),
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Vote { votes } => vote(deps, env, info, votes),
+        ExecuteMsg::AddGauge { gauge } => add_gauge(deps, info, gauge),
+        ExecuteMsg::RemoveGauge { gauge } => remove_gauge(deps, info, gauge),
+        ExecuteMsg::UpdateConfig { owner, staking } => update_config(deps, info, owner, staking),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<GaugeVote>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch = config.epoch_at(env.block.time.seconds());
+
+    // The voting power is snapshotted at the epoch start
+    let staker_info: StakerInfoResponse = deps.querier.query_wasm_smart(
+        &config.staking,
+        &StakingQueryMsg::StakerInfoAt { staker: info.sender.to_string(), time: config.epoch_start(epoch) },
+    )?;
+    if staker_info.bond_amount.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    let mut total_ratio = Decimal::zero();
+    let mut user_votes: Vec<UserVote> = vec![];
+    for vote in votes.iter() {
+        let gauge = deps.api.addr_validate(&vote.gauge)?;
+        if !config.gauges.contains(&gauge) {
+            return Err(ContractError::UnknownGauge { gauge: vote.gauge.clone() });
+        }
+        if vote.ratio.is_zero() || user_votes.iter().any(|v| v.gauge == gauge) {
+            return Err(ContractError::InvalidVotes {
+                reason: "each gauge must be voted once with a positive ratio".to_string(),
+            });
+        }
+        total_ratio = total_ratio + vote.ratio;
+        user_votes.push(UserVote { gauge, weight: staker_info.bond_amount * vote.ratio });
+    }
+    if total_ratio > Decimal::one() {
+        return Err(ContractError::InvalidVotes { reason: "the ratios must not exceed one in total".to_string() });
+    }
+
+    // Votes cast again during the same epoch replace the previous ones
+    let previous_votes = USER_VOTES
+        .may_load(deps.storage, (U64Key::new(epoch), &info.sender))?
+        .unwrap_or_default();
+    for previous in previous_votes.iter() {
+        GAUGE_VOTES.update(deps.storage, (U64Key::new(epoch), &previous.gauge), |votes| -> StdResult<_> {
+            Ok(votes.unwrap_or_default().checked_sub(previous.weight)?)
+        })?;
+    }
+    for user_vote in user_votes.iter() {
+        GAUGE_VOTES.update(deps.storage, (U64Key::new(epoch), &user_vote.gauge), |votes| -> StdResult<_> {
+            Ok(votes.unwrap_or_default().checked_add(user_vote.weight)?)
+        })?;
+    }
+    USER_VOTES.save(deps.storage, (U64Key::new(epoch), &info.sender), &user_votes)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("voter", info.sender)
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("voting_power", staker_info.bond_amount))
+}
+
+pub fn add_gauge(
+    deps: DepsMut,
+    info: MessageInfo,
+    gauge: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let gauge = deps.api.addr_validate(&gauge)?;
+    if config.gauges.contains(&gauge) {
+        return Err(ContractError::GaugeExists { gauge: gauge.to_string() });
+    }
+    config.gauges.push(gauge.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_gauge")
+        .add_attribute("gauge", gauge))
+}
+
+pub fn remove_gauge(
+    deps: DepsMut,
+    info: MessageInfo,
+    gauge: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The votes already cast for a removed gauge are left out of the split
+    let gauge = deps.api.addr_validate(&gauge)?;
+    if !config.gauges.contains(&gauge) {
+        return Err(ContractError::UnknownGauge { gauge: gauge.to_string() });
+    }
+    config.gauges.retain(|g| g != &gauge);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_gauge")
+        .add_attribute("gauge", gauge))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    staking: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(staking) = staking {
+        config.staking = deps.api.addr_validate(&staking)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::GaugeWeights { epoch } => to_binary(&query_gauge_weights(deps, env, epoch)?),
+        QueryMsg::UserVotes { address, epoch } => to_binary(&query_user_votes(deps, env, address, epoch)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        staking: config.staking.to_string(),
+        gauges: config.gauges.iter().map(|g| g.to_string()).collect(),
+        start_time: config.start_time,
+        epoch_length: EPOCH_LENGTH,
+        current_epoch: config.epoch_at(env.block.time.seconds()),
+    })
+}
+
+pub fn query_gauge_weights(deps: Deps, env: Env, epoch: Option<u64>) -> StdResult<GaugeWeightsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch = epoch.unwrap_or_else(|| config.epoch_at(env.block.time.seconds()));
+
+    // The split of an epoch is voted during the previous one, nothing is voted for the first
+    let mut votes: Vec<Uint128> = vec![];
+    for gauge in config.gauges.iter() {
+        let gauge_votes = match epoch.checked_sub(1) {
+            Some(voting_epoch) => GAUGE_VOTES
+                .may_load(deps.storage, (U64Key::new(voting_epoch), gauge))?
+                .unwrap_or_default(),
+            None => Uint128::zero(),
+        };
+        votes.push(gauge_votes);
+    }
+    let total_votes: Uint128 = votes.iter().copied().sum();
+
+    let weights = config.gauges.iter().zip(votes)
+        .map(|(gauge, votes)| GaugeWeight {
+            gauge: gauge.to_string(),
+            votes,
+            share: if total_votes.is_zero() {
+                Decimal::zero()
+            } else {
+                Decimal::from_ratio(votes, total_votes)
+            },
+        })
+        .collect();
+
+    Ok(GaugeWeightsResponse { epoch, weights })
+}
+
+pub fn query_user_votes(
+    deps: Deps,
+    env: Env,
+    address: String,
+    epoch: Option<u64>,
+) -> StdResult<UserVotesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let epoch = epoch.unwrap_or_else(|| config.epoch_at(env.block.time.seconds()));
+
+    let votes = USER_VOTES
+        .may_load(deps.storage, (U64Key::new(epoch), &address))?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|v| UserVoteResponse { gauge: v.gauge.to_string(), weight: v.weight })
+        .collect();
+
+    Ok(UserVotesResponse { epoch, votes })
+}
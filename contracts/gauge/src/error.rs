@@ -0,0 +1,26 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Unknown gauge {gauge}")]
+    UnknownGauge { gauge: String },
+
+    #[error("The gauge {gauge} already exists")]
+    GaugeExists { gauge: String },
+
+    #[error("Invalid votes: {reason}")]
+    InvalidVotes { reason: String },
+
+    #[error("No voting power at the epoch start")]
+    NoVotingPower {},
+}
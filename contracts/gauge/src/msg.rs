@@ -0,0 +1,100 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+
+/// This structure describes the parameters used for creating a gauge controller contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART staking contract reporting the voting power
+    pub staking: String,
+    /// The staking pools sharing the emissions
+    pub gauges: Vec<String>,
+    /// The start time of the first epoch, defaults to the current time
+    pub start_time: Option<u64>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Splits the sender voting power between gauges for the next epoch,
+    /// replacing the votes already cast during the running epoch
+    Vote { votes: Vec<GaugeVote> },
+    /// Adds a staking pool to the gauges. Only the owner can execute it.
+    AddGauge { gauge: String },
+    /// Removes a staking pool from the gauges. Only the owner can execute it.
+    RemoveGauge { gauge: String },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        staking: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeVote {
+    pub gauge: String,
+    /// The share of the voting power given to the gauge
+    pub ratio: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the emission split of an epoch, voted during the previous one.
+    /// Staking pools read it at epoch boundaries, the running epoch by default.
+    /// Return type: GaugeWeightsResponse.
+    GaugeWeights { epoch: Option<u64> },
+    /// Returns the votes cast by a user during an epoch, the running epoch by default.
+    /// Return type: UserVotesResponse.
+    UserVotes { address: String, epoch: Option<u64> },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub staking: String,
+    pub gauges: Vec<String>,
+    pub start_time: u64,
+    pub epoch_length: u64,
+    pub current_epoch: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeWeightsResponse {
+    pub epoch: u64,
+    pub weights: Vec<GaugeWeight>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeWeight {
+    pub gauge: String,
+    pub votes: Uint128,
+    /// The share of the emissions allocated to the gauge
+    pub share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserVotesResponse {
+    pub epoch: u64,
+    pub votes: Vec<UserVoteResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserVoteResponse {
+    pub gauge: String,
+    pub weight: Uint128,
+}
@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+/// The length of a voting epoch, one week
+pub const EPOCH_LENGTH: u64 = 7 * 86_400;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    /// The staking contract reporting the voting power at the start of each epoch
+    pub staking: Addr,
+    /// The staking pools sharing the emissions
+    pub gauges: Vec<Addr>,
+    /// The start time of the first epoch
+    pub start_time: u64,
+}
+
+impl Config {
+    /// Returns the epoch running at the given time
+    pub fn epoch_at(&self, time: u64) -> u64 {
+        time.saturating_sub(self.start_time) / EPOCH_LENGTH
+    }
+
+    /// Returns the start time of the epoch, the voting power is read at this time
+    pub fn epoch_start(&self, epoch: u64) -> u64 {
+        self.start_time + epoch * EPOCH_LENGTH
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserVote {
+    pub gauge: Addr,
+    pub weight: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The votes cast for each gauge during an epoch, they split the emissions of the next epoch
+pub const GAUGE_VOTES: Map<(U64Key, &Addr), Uint128> = Map::new("gauge_votes");
+/// The votes cast by each user during an epoch
+pub const USER_VOTES: Map<(U64Key, &Addr), Vec<UserVote>> = Map::new("user_votes");
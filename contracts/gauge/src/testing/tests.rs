@@ -0,0 +1,147 @@
+use cosmwasm_std::{Decimal, Deps, DepsMut, Env, from_binary, Response, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, GaugeVote, GaugeWeight, GaugeWeightsResponse, InstantiateMsg, QueryMsg,
+    UserVoteResponse, UserVotesResponse,
+};
+use crate::state::EPOCH_LENGTH;
+use crate::testing::mock_querier::{mock_dependencies, MOCK_STAKING};
+
+const OWNER: &str = "mock_owner";
+const SINGLE_POOL: &str = "single_pool";
+const LP_POOL: &str = "lp_pool";
+const NFT_POOL: &str = "nft_pool";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: Some(OWNER.to_string()),
+        staking: MOCK_STAKING.to_string(),
+        gauges: vec![SINGLE_POOL.to_string(), LP_POOL.to_string(), NFT_POOL.to_string()],
+        start_time: None,
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+fn vote_msg(votes: &[(&str, u64)]) -> ExecuteMsg {
+    ExecuteMsg::Vote {
+        votes: votes.iter()
+            .map(|(gauge, percent)| GaugeVote { gauge: gauge.to_string(), ratio: Decimal::percent(*percent) })
+            .collect(),
+    }
+}
+
+fn query_weights(deps: Deps, env: Env) -> Vec<GaugeWeight> {
+    let res = query(deps, env, QueryMsg::GaugeWeights { epoch: None }).unwrap();
+    let weights: GaugeWeightsResponse = from_binary(&res).unwrap();
+    weights.weights
+}
+
+#[test]
+fn test_vote() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), vote_msg(&[(LP_POOL, 100)])).unwrap_err();
+    assert_eq!(err, ContractError::NoVotingPower {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      vote_msg(&[("other_pool", 100)])).unwrap_err();
+    assert_eq!(err, ContractError::UnknownGauge { gauge: "other_pool".to_string() });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      vote_msg(&[(LP_POOL, 50), (LP_POOL, 50)])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidVotes {
+        reason: "each gauge must be voted once with a positive ratio".to_string(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      vote_msg(&[(LP_POOL, 60), (NFT_POOL, 50)])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidVotes { reason: "the ratios must not exceed one in total".to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), vote_msg(&[(LP_POOL, 60), (NFT_POOL, 40)])).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::UserVotes { address: ALICE.to_string(), epoch: None }).unwrap();
+    let votes: UserVotesResponse = from_binary(&res).unwrap();
+    assert_eq!(votes, UserVotesResponse {
+        epoch: 0,
+        votes: vec![
+            UserVoteResponse { gauge: LP_POOL.to_string(), weight: Uint128::new(360) },
+            UserVoteResponse { gauge: NFT_POOL.to_string(), weight: Uint128::new(240) },
+        ],
+    });
+}
+
+#[test]
+fn test_gauge_weights() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    deps.querier.with_stake(BOB, 400);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), vote_msg(&[(SINGLE_POOL, 100)])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), vote_msg(&[(LP_POOL, 50), (NFT_POOL, 50)])).unwrap();
+    // Voting again replaces the previous votes
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), vote_msg(&[(LP_POOL, 100)])).unwrap();
+
+    // Nothing was voted for the first epoch
+    let weights = query_weights(deps.as_ref(), env.clone());
+    assert!(weights.iter().all(|w| w.share.is_zero()));
+
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let weights = query_weights(deps.as_ref(), env.clone());
+    assert_eq!(weights, vec![
+        GaugeWeight { gauge: SINGLE_POOL.to_string(), votes: Uint128::new(600), share: Decimal::percent(60) },
+        GaugeWeight { gauge: LP_POOL.to_string(), votes: Uint128::new(400), share: Decimal::percent(40) },
+        GaugeWeight { gauge: NFT_POOL.to_string(), votes: Uint128::zero(), share: Decimal::zero() },
+    ]);
+
+    // The votes do not carry over to the following epoch
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let weights = query_weights(deps.as_ref(), env);
+    assert!(weights.iter().all(|w| w.votes.is_zero()));
+}
+
+#[test]
+fn test_add_and_remove_gauge() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    deps.querier.with_stake(BOB, 400);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::AddGauge { gauge: "new_pool".to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::AddGauge { gauge: LP_POOL.to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::GaugeExists { gauge: LP_POOL.to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::AddGauge { gauge: "new_pool".to_string() }).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), vote_msg(&[("new_pool", 100)])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), vote_msg(&[(NFT_POOL, 100)])).unwrap();
+
+    // The votes for a removed gauge are left out of the split
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::RemoveGauge { gauge: "new_pool".to_string() }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      vote_msg(&[("new_pool", 100)])).unwrap_err();
+    assert_eq!(err, ContractError::UnknownGauge { gauge: "new_pool".to_string() });
+
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let weights = query_weights(deps.as_ref(), env);
+    assert_eq!(weights.len(), 3);
+    assert_eq!(weights[2], GaugeWeight {
+        gauge: NFT_POOL.to_string(),
+        votes: Uint128::new(400),
+        share: Decimal::one(),
+    });
+}
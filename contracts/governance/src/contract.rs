@@ -0,0 +1,610 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Order,
+    Response, StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse, TotalStakedResponse};
+use luart_protocol::token::ExecuteMsg as TokenExecuteMsg;
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, DepositClaim, DepositClaimsResponse, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, ProposalResponse, ProposalsResponse, QueryMsg, VoteResponse,
+};
+use crate::state::{
+    Config, CONFIG, Proposal, PROPOSAL_COUNT, ProposalStatus, PROPOSALS, SwapFeeConfigUpdate,
+    VoteInfo, VoteOption, VOTES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-governance";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const MIN_TITLE_LENGTH: usize = 4;
+const MAX_TITLE_LENGTH: usize = 64;
+const MIN_DESC_LENGTH: usize = 4;
+const MAX_DESC_LENGTH: usize = 1024;
+const MAX_LINK_LENGTH: usize = 128;
+const MAX_SWAP_PERCENT_FEE: u64 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+        staking: deps.api.addr_validate(&msg.staking)?,
+        quorum: msg.quorum,
+        threshold: msg.threshold,
+        voting_period: msg.voting_period,
+        timelock_period: msg.timelock_period,
+        expiration_period: msg.expiration_period,
+        proposal_deposit: msg.proposal_deposit,
+        guardian: match msg.guardian {
+            Some(guardian) => Some(deps.api.addr_validate(&guardian)?),
+            None => None,
+        },
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+        spam_slash_ratio: msg.spam_slash_ratio,
+        veto_slash_ratio: msg.veto_slash_ratio,
+    };
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    PROPOSAL_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.quorum > Decimal::one() || config.threshold > Decimal::one() {
+        return Err(ContractError::InvalidConfig {
+            reason: "quorum and threshold must not exceed one".to_string(),
+        });
+    }
+    if config.spam_slash_ratio > Decimal::one() || config.veto_slash_ratio > Decimal::one() {
+        return Err(ContractError::InvalidConfig {
+            reason: "slash ratios must not exceed one".to_string(),
+        });
+    }
+    if config.voting_period == 0 || config.expiration_period == 0 {
+        return Err(ContractError::InvalidConfig {
+            reason: "voting and expiration periods must be positive".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CastVote { proposal_id, vote } => cast_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::EndProposal { proposal_id } => end_proposal(deps, env, proposal_id),
+        ExecuteMsg::ClaimDeposit { proposal_id } => claim_deposit(deps, env, proposal_id),
+        ExecuteMsg::ExecuteProposal { proposal_id } => execute_proposal(deps, env, proposal_id),
+        ExecuteMsg::VetoProposal { proposal_id } => veto_proposal(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateGuardian { guardian } => {
+            if info.sender != env.contract.address {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let mut config = CONFIG.load(deps.storage)?;
+            config.guardian = match guardian {
+                Some(guardian) => Some(deps.api.addr_validate(&guardian)?),
+                None => None,
+            };
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new().add_attribute("action", "update_guardian"))
+        }
+        ExecuteMsg::UpdateConfig {
+            staking,
+            quorum,
+            threshold,
+            voting_period,
+            timelock_period,
+            expiration_period,
+            proposal_deposit,
+            treasury,
+            spam_slash_ratio,
+            veto_slash_ratio,
+        } => {
+            if info.sender != env.contract.address {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let mut config = CONFIG.load(deps.storage)?;
+            if let Some(staking) = staking {
+                config.staking = deps.api.addr_validate(&staking)?;
+            }
+            config.quorum = quorum.unwrap_or(config.quorum);
+            config.threshold = threshold.unwrap_or(config.threshold);
+            config.voting_period = voting_period.unwrap_or(config.voting_period);
+            config.timelock_period = timelock_period.unwrap_or(config.timelock_period);
+            config.expiration_period = expiration_period.unwrap_or(config.expiration_period);
+            config.proposal_deposit = proposal_deposit.unwrap_or(config.proposal_deposit);
+            if let Some(treasury) = treasury {
+                config.treasury = deps.api.addr_validate(&treasury)?;
+            }
+            config.spam_slash_ratio = spam_slash_ratio.unwrap_or(config.spam_slash_ratio);
+            config.veto_slash_ratio = veto_slash_ratio.unwrap_or(config.veto_slash_ratio);
+            validate_config(&config)?;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new().add_attribute("action", "update_config"))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (title, description, link, messages, fee_config) = match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::SubmitProposal {
+            title,
+            description,
+            link,
+            messages,
+        } => (title, description, link, messages, None),
+        Cw20HookMsg::SubmitFeeProposal {
+            title,
+            description,
+            link,
+            fee_config,
+        } => {
+            validate_fee_config(deps.as_ref(), &fee_config)?;
+            (title, description, link, vec![], Some(fee_config))
+        }
+    };
+
+    let proposal = Proposal {
+        proposer: deps.api.addr_validate(&cw20_msg.sender)?,
+        title,
+        description,
+        link,
+        messages,
+        fee_config,
+        status: ProposalStatus::Active,
+        start_time: env.block.time.seconds(),
+        end_time: env.block.time.seconds() + config.voting_period,
+        yes_votes: Uint128::zero(),
+        no_votes: Uint128::zero(),
+        abstain_votes: Uint128::zero(),
+        deposit: cw20_msg.amount,
+        quorum_reached: false,
+        deposit_claimed: false,
+    };
+    submit_proposal(deps, config, proposal)
+}
+
+pub fn submit_proposal(
+    deps: DepsMut,
+    config: Config,
+    proposal: Proposal,
+) -> Result<Response, ContractError> {
+    if proposal.deposit < config.proposal_deposit {
+        return Err(ContractError::InsufficientDeposit { required: config.proposal_deposit });
+    }
+    validate_proposal(&proposal)?;
+
+    let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposer", proposal.proposer)
+        .add_attribute("end_time", proposal.end_time.to_string()))
+}
+
+fn validate_proposal(proposal: &Proposal) -> Result<(), ContractError> {
+    if proposal.title.len() < MIN_TITLE_LENGTH || proposal.title.len() > MAX_TITLE_LENGTH {
+        return Err(ContractError::InvalidProposal { reason: "title length is out of bounds".to_string() });
+    }
+    if proposal.description.len() < MIN_DESC_LENGTH || proposal.description.len() > MAX_DESC_LENGTH {
+        return Err(ContractError::InvalidProposal {
+            reason: "description length is out of bounds".to_string(),
+        });
+    }
+    if let Some(link) = &proposal.link {
+        if link.len() > MAX_LINK_LENGTH {
+            return Err(ContractError::InvalidProposal { reason: "link is too long".to_string() });
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_fee_config(deps: Deps, fee_config: &SwapFeeConfigUpdate) -> Result<(), ContractError> {
+    if *fee_config == SwapFeeConfigUpdate::default() {
+        return Err(ContractError::InvalidProposal { reason: "the fee proposal changes nothing".to_string() });
+    }
+    if let Some(swap_percent_fee) = fee_config.swap_percent_fee {
+        if swap_percent_fee > Decimal::percent(MAX_SWAP_PERCENT_FEE) {
+            return Err(ContractError::InvalidProposal {
+                reason: format!("the swap fee must not exceed {}%", MAX_SWAP_PERCENT_FEE),
+            });
+        }
+    }
+    if let Some(fee_admin) = &fee_config.fee_admin {
+        deps.api.addr_validate(fee_admin)?;
+    }
+    if let Some(fee_receiver) = &fee_config.fee_receiver {
+        deps.api.addr_validate(fee_receiver)?;
+    }
+
+    Ok(())
+}
+
+pub fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: VoteOption,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+    if env.block.time.seconds() >= proposal.end_time {
+        return Err(ContractError::VotingPeriodEnded {});
+    }
+    if VOTES.has(deps.storage, (U64Key::new(proposal_id), &info.sender)) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+
+    // The voting power is snapshotted at the proposal start
+    let staker_info: StakerInfoResponse = deps.querier.query_wasm_smart(
+        &config.staking,
+        &StakingQueryMsg::StakerInfoAt { staker: info.sender.to_string(), time: proposal.start_time },
+    )?;
+    let weight = staker_info.bond_amount;
+    if weight.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    match vote {
+        VoteOption::Yes => proposal.yes_votes = proposal.yes_votes.checked_add(weight)?,
+        VoteOption::No => proposal.no_votes = proposal.no_votes.checked_add(weight)?,
+        VoteOption::Abstain => proposal.abstain_votes = proposal.abstain_votes.checked_add(weight)?,
+    }
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+    VOTES.save(deps.storage, (U64Key::new(proposal_id), &info.sender), &VoteInfo { option: vote, weight })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cast_vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("weight", weight))
+}
+
+pub fn end_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+    if env.block.time.seconds() < proposal.end_time {
+        return Err(ContractError::VotingPeriodNotEnded {});
+    }
+
+    let total: TotalStakedResponse = deps.querier.query_wasm_smart(
+        &config.staking,
+        &StakingQueryMsg::TotalStakedAt { time: proposal.start_time },
+    )?;
+    let quorum_reached = !total.total_staked.is_zero()
+        && proposal.total_votes() >= total.total_staked * config.quorum;
+    let threshold_reached = !proposal.yes_votes.is_zero()
+        && proposal.yes_votes > (proposal.yes_votes + proposal.no_votes) * config.threshold;
+
+    proposal.quorum_reached = quorum_reached;
+    proposal.status = if quorum_reached && threshold_reached {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Rejected
+    };
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "end_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("passed", (proposal.status == ProposalStatus::Passed).to_string()))
+}
+
+pub fn claim_deposit(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.deposit_claimed {
+        return Err(ContractError::DepositAlreadyClaimed {});
+    }
+    if !proposal.is_deposit_claimable(&config, env.block.time.seconds()) {
+        return Err(ContractError::DepositNotClaimable {});
+    }
+
+    proposal.deposit_claimed = true;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    let slashed_amount = proposal.deposit * proposal.slash_ratio(&config);
+    let refund_amount = proposal.deposit.checked_sub(slashed_amount)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !refund_amount.is_zero() {
+        messages.push(token_transfer_msg(&config.luart_token, &proposal.proposer, refund_amount)?);
+    }
+    if !slashed_amount.is_zero() {
+        messages.push(token_transfer_msg(&config.luart_token, &config.treasury, slashed_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim_deposit")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("refund_amount", refund_amount)
+        .add_attribute("slashed_amount", slashed_amount))
+}
+
+pub fn execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(ContractError::ProposalNotPassed {});
+    }
+
+    let now = env.block.time.seconds();
+    let executable_at = proposal.end_time + config.timelock_period;
+    if now < executable_at {
+        return Err(ContractError::TimelockNotExpired {});
+    }
+    if now >= executable_at + config.expiration_period {
+        return Err(ContractError::ProposalExpired {});
+    }
+
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    let mut messages: Vec<CosmosMsg> = proposal.messages.into_iter().map(CosmosMsg::Wasm).collect();
+    if let Some(fee_config) = proposal.fee_config {
+        // The governance contract must be the fee admin of the LUART token
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.luart_token.to_string(),
+            msg: to_binary(&TokenExecuteMsg::UpdateSwapFeeConfig {
+                fee_admin: fee_config.fee_admin,
+                enable_swap_fee: fee_config.enable_swap_fee,
+                swap_percent_fee: fee_config.swap_percent_fee,
+                fee_receiver: fee_config.fee_receiver,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn veto_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.guardian != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(ContractError::ProposalNotPassed {});
+    }
+    if env.block.time.seconds() >= proposal.end_time + config.timelock_period {
+        return Err(ContractError::VetoWindowEnded {});
+    }
+
+    proposal.status = ProposalStatus::Vetoed;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "veto_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
+        QueryMsg::DepositClaims {
+            proposer,
+            start_after,
+            limit,
+        } => to_binary(&query_deposit_claims(deps, env, proposer, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        luart_token: config.luart_token.to_string(),
+        staking: config.staking.to_string(),
+        quorum: config.quorum,
+        threshold: config.threshold,
+        voting_period: config.voting_period,
+        timelock_period: config.timelock_period,
+        expiration_period: config.expiration_period,
+        proposal_deposit: config.proposal_deposit,
+        guardian: config.guardian.map(|g| g.to_string()),
+        treasury: config.treasury.to_string(),
+        spam_slash_ratio: config.spam_slash_ratio,
+        veto_slash_ratio: config.veto_slash_ratio,
+    })
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    Ok(proposal_response(proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let proposals: StdResult<Vec<ProposalResponse>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, proposal) = item?;
+            Ok(proposal_response(parse_proposal_id(&k)?, proposal))
+        })
+        .collect();
+
+    Ok(ProposalsResponse { proposals: proposals? })
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let vote = VOTES.may_load(deps.storage, (U64Key::new(proposal_id), &voter))?;
+    Ok(VoteResponse {
+        vote: vote.as_ref().map(|v| v.option),
+        weight: vote.map(|v| v.weight).unwrap_or_default(),
+    })
+}
+
+pub fn query_deposit_claims(
+    deps: Deps,
+    env: Env,
+    proposer: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DepositClaimsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposer = deps.api.addr_validate(&proposer)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let now = env.block.time.seconds();
+
+    let claims: StdResult<Vec<DepositClaim>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, proposal)) => proposal.proposer == proposer,
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (k, proposal) = item?;
+            let slashed_amount = proposal.deposit * proposal.slash_ratio(&config);
+            Ok(DepositClaim {
+                proposal_id: parse_proposal_id(&k)?,
+                status: proposal.status,
+                deposit: proposal.deposit,
+                refund_amount: proposal.deposit.checked_sub(slashed_amount)?,
+                slashed_amount,
+                claimable: !proposal.deposit_claimed && proposal.is_deposit_claimable(&config, now),
+                claimed: proposal.deposit_claimed,
+            })
+        })
+        .collect();
+
+    Ok(DepositClaimsResponse { claims: claims? })
+}
+
+fn proposal_response(proposal_id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        proposal_id,
+        proposer: proposal.proposer.to_string(),
+        title: proposal.title,
+        description: proposal.description,
+        link: proposal.link,
+        messages: proposal.messages,
+        fee_config: proposal.fee_config,
+        status: proposal.status,
+        start_time: proposal.start_time,
+        end_time: proposal.end_time,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+        deposit: proposal.deposit,
+    }
+}
+
+fn parse_proposal_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted proposal key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn token_transfer_msg(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount })?,
+        funds: vec![],
+    }))
+}
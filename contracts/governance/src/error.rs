@@ -0,0 +1,56 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid governance configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Invalid proposal: {reason}")]
+    InvalidProposal { reason: String },
+
+    #[error("Must deposit at least {required} tokens")]
+    InsufficientDeposit { required: Uint128 },
+
+    #[error("The proposal is not in voting")]
+    ProposalNotActive {},
+
+    #[error("The voting period has ended")]
+    VotingPeriodEnded {},
+
+    #[error("The voting period has not ended yet")]
+    VotingPeriodNotEnded {},
+
+    #[error("Already voted")]
+    AlreadyVoted {},
+
+    #[error("No voting power at the proposal snapshot")]
+    NoVotingPower {},
+
+    #[error("The proposal has not passed")]
+    ProposalNotPassed {},
+
+    #[error("The proposal is still timelocked")]
+    TimelockNotExpired {},
+
+    #[error("The proposal execution window has expired")]
+    ProposalExpired {},
+
+    #[error("The proposal can only be vetoed before its timelock has expired")]
+    VetoWindowEnded {},
+
+    #[error("The proposal outcome is not final yet")]
+    DepositNotClaimable {},
+
+    #[error("The deposit was already claimed")]
+    DepositAlreadyClaimed {},
+}
@@ -0,0 +1,185 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128, WasmMsg};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::{ProposalStatus, SwapFeeConfigUpdate, VoteOption};
+
+/// This structure describes the parameters used for creating a governance contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The LUART token contract, proposal deposits are paid in it
+    pub luart_token: String,
+    /// The staking contract reporting the voting power
+    pub staking: String,
+    pub quorum: Decimal,
+    pub threshold: Decimal,
+    pub voting_period: u64,
+    pub timelock_period: u64,
+    pub expiration_period: u64,
+    pub proposal_deposit: Uint128,
+    /// The council allowed to veto passed proposals during their timelock
+    pub guardian: Option<String>,
+    /// The receiver of the slashed deposits
+    pub treasury: String,
+    pub spam_slash_ratio: Decimal,
+    pub veto_slash_ratio: Decimal,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the proposal deposits, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Votes on an active proposal with the sender stake at the proposal start
+    CastVote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
+    /// Tallies the proposal after its voting period
+    EndProposal { proposal_id: u64 },
+    /// Refunds the deposit to the proposer once the proposal outcome is final,
+    /// slashing a share to the treasury for spam and vetoed proposals
+    ClaimDeposit { proposal_id: u64 },
+    /// Executes the messages of a passed proposal once its timelock has expired
+    ExecuteProposal { proposal_id: u64 },
+    /// Vetoes a passed proposal before its timelock has expired.
+    /// Only the guardian can execute it.
+    VetoProposal { proposal_id: u64 },
+    /// Sets or removes the guardian. Only the contract itself can execute it,
+    /// through a passed proposal.
+    UpdateGuardian { guardian: Option<String> },
+    /// Updates the governance parameters. Only the contract itself can execute it,
+    /// through a passed proposal.
+    UpdateConfig {
+        staking: Option<String>,
+        quorum: Option<Decimal>,
+        threshold: Option<Decimal>,
+        voting_period: Option<u64>,
+        timelock_period: Option<u64>,
+        expiration_period: Option<u64>,
+        proposal_deposit: Option<Uint128>,
+        treasury: Option<String>,
+        spam_slash_ratio: Option<Decimal>,
+        veto_slash_ratio: Option<Decimal>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Submits a proposal with the sent tokens as deposit
+    SubmitProposal {
+        title: String,
+        description: String,
+        link: Option<String>,
+        /// The messages executed once the proposal passed, none for a text proposal
+        messages: Vec<WasmMsg>,
+    },
+    /// Submits a proposal changing the LUART token swap fee configuration,
+    /// with the sent tokens as deposit
+    SubmitFeeProposal {
+        title: String,
+        description: String,
+        link: Option<String>,
+        fee_config: SwapFeeConfigUpdate,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the governance parameters.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a proposal.
+    /// Return type: ProposalResponse.
+    Proposal { proposal_id: u64 },
+    /// Returns proposals by id.
+    /// Return type: ProposalsResponse.
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the vote of the voter on a proposal.
+    /// Return type: VoteResponse.
+    Vote { proposal_id: u64, voter: String },
+    /// Returns the deposits of a proposer by proposal id.
+    /// Return type: DepositClaimsResponse.
+    DepositClaims {
+        proposer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub luart_token: String,
+    pub staking: String,
+    pub quorum: Decimal,
+    pub threshold: Decimal,
+    pub voting_period: u64,
+    pub timelock_period: u64,
+    pub expiration_period: u64,
+    pub proposal_deposit: Uint128,
+    pub guardian: Option<String>,
+    pub treasury: String,
+    pub spam_slash_ratio: Decimal,
+    pub veto_slash_ratio: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    pub messages: Vec<WasmMsg>,
+    pub fee_config: Option<SwapFeeConfigUpdate>,
+    pub status: ProposalStatus,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+    pub deposit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositClaim {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    pub deposit: Uint128,
+    /// The amount refunded to the proposer once claimable
+    pub refund_amount: Uint128,
+    pub slashed_amount: Uint128,
+    pub claimable: bool,
+    pub claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositClaimsResponse {
+    pub claims: Vec<DepositClaim>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteResponse {
+    pub vote: Option<VoteOption>,
+    pub weight: Uint128,
+}
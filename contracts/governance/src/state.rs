@@ -0,0 +1,123 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128, WasmMsg};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The LUART token contract, proposal deposits are paid in it
+    pub luart_token: Addr,
+    /// The staking contract reporting the voting power at the proposal snapshot
+    pub staking: Addr,
+    /// The share of the staked tokens which must vote for a proposal to be valid
+    pub quorum: Decimal,
+    /// The share of the yes votes among the yes and no votes for a proposal to pass
+    pub threshold: Decimal,
+    /// Seconds a proposal is open for voting
+    pub voting_period: u64,
+    /// Seconds after the voting period before a passed proposal can be executed
+    pub timelock_period: u64,
+    /// Seconds after the timelock a passed proposal can be executed
+    pub expiration_period: u64,
+    /// The minimum deposit to submit a proposal
+    pub proposal_deposit: Uint128,
+    /// The council allowed to veto passed proposals during their timelock
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    /// The receiver of the slashed deposits
+    pub treasury: Addr,
+    /// The share of the deposit slashed when a proposal misses the quorum
+    pub spam_slash_ratio: Decimal,
+    /// The share of the deposit slashed when a proposal is vetoed
+    pub veto_slash_ratio: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Active,
+    Passed,
+    Rejected,
+    Executed,
+    Vetoed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    /// The messages executed once the proposal passed, text proposals have none
+    pub messages: Vec<WasmMsg>,
+    /// The token swap fee change applied once the proposal passed
+    #[serde(default)]
+    pub fee_config: Option<SwapFeeConfigUpdate>,
+    pub status: ProposalStatus,
+    /// Voting power is read from the staked amounts at this time
+    pub start_time: u64,
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+    pub deposit: Uint128,
+    /// Set once the proposal ended, a proposal missing the quorum is considered spam
+    #[serde(default)]
+    pub quorum_reached: bool,
+    #[serde(default)]
+    pub deposit_claimed: bool,
+}
+
+impl Proposal {
+    pub fn total_votes(&self) -> Uint128 {
+        self.yes_votes + self.no_votes + self.abstain_votes
+    }
+
+    /// Returns the share of the deposit slashed to the treasury
+    pub fn slash_ratio(&self, config: &Config) -> Decimal {
+        match self.status {
+            ProposalStatus::Vetoed => config.veto_slash_ratio,
+            ProposalStatus::Rejected if !self.quorum_reached => config.spam_slash_ratio,
+            _ => Decimal::zero(),
+        }
+    }
+
+    /// Returns whether the outcome is final and the deposit can be claimed,
+    /// a passed proposal can still be vetoed during its timelock
+    pub fn is_deposit_claimable(&self, config: &Config, now: u64) -> bool {
+        match self.status {
+            ProposalStatus::Active => false,
+            ProposalStatus::Passed => now >= self.end_time + config.timelock_period,
+            _ => true,
+        }
+    }
+}
+
+/// A typed change of the LUART token swap fee configuration, readable by the voters
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct SwapFeeConfigUpdate {
+    pub fee_admin: Option<String>,
+    pub enable_swap_fee: Option<bool>,
+    pub swap_percent_fee: Option<Decimal>,
+    pub fee_receiver: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteInfo {
+    pub option: VoteOption,
+    pub weight: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+pub const VOTES: Map<(U64Key, &Addr), VoteInfo> = Map::new("votes");
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Binary, Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse, TotalStakedResponse};
+
+pub const MOCK_STAKING: &str = "mock_staking";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the staking queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    stakes: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+            if contract_addr == MOCK_STAKING => self.handle_staking_query(msg),
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn handle_staking_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(StakingQueryMsg::StakerInfoAt { staker, time: _ }) => {
+                let bond_amount = self.stakes.get(&staker).copied().unwrap_or_default();
+                SystemResult::Ok(ContractResult::from(to_binary(&StakerInfoResponse { staker, bond_amount })))
+            }
+            Ok(StakingQueryMsg::TotalStakedAt { time: _ }) => {
+                let total_staked = self.stakes.values().copied().sum();
+                SystemResult::Ok(ContractResult::from(to_binary(&TotalStakedResponse { total_staked })))
+            }
+            Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                error: "Unsupported staking query".to_string(),
+                request: msg.clone(),
+            }),
+            Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                error: format!("Parsing staking query: {}", e),
+                request: msg.clone(),
+            }),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            stakes: HashMap::new(),
+        }
+    }
+
+    // configure the stake of an address, the same at any time
+    pub fn with_stake(&mut self, staker: &str, amount: u128) {
+        self.stakes.insert(staker.to_string(), Uint128::new(amount));
+    }
+}
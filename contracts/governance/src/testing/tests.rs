@@ -0,0 +1,390 @@
+use cosmwasm_std::{
+    CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use luart_protocol::token::ExecuteMsg as TokenExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, DepositClaim, DepositClaimsResponse, ExecuteMsg, InstantiateMsg,
+    ProposalResponse, QueryMsg,
+};
+use crate::state::{ProposalStatus, SwapFeeConfigUpdate, VoteOption};
+use crate::testing::mock_querier::{mock_dependencies, MOCK_STAKING};
+
+const TOKEN: &str = "mock_token";
+const PROPOSER: &str = "mock_proposer";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const GUARDIAN: &str = "mock_guardian";
+const TREASURY: &str = "mock_treasury";
+
+const VOTING_PERIOD: u64 = 1_000;
+const TIMELOCK_PERIOD: u64 = 100;
+const EXPIRATION_PERIOD: u64 = 500;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        luart_token: TOKEN.to_string(),
+        staking: MOCK_STAKING.to_string(),
+        quorum: Decimal::percent(30),
+        threshold: Decimal::percent(50),
+        voting_period: VOTING_PERIOD,
+        timelock_period: TIMELOCK_PERIOD,
+        expiration_period: EXPIRATION_PERIOD,
+        proposal_deposit: Uint128::new(1_000),
+        guardian: Some(GUARDIAN.to_string()),
+        treasury: TREASURY.to_string(),
+        spam_slash_ratio: Decimal::percent(20),
+        veto_slash_ratio: Decimal::percent(50),
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+fn submit_msg(title: &str, deposit: u128, messages: Vec<WasmMsg>) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: PROPOSER.to_string(),
+        amount: Uint128::new(deposit),
+        msg: to_binary(&Cw20HookMsg::SubmitProposal {
+            title: title.to_string(),
+            description: "Proposal description".to_string(),
+            link: None,
+            messages,
+        }).unwrap(),
+    })
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+fn update_quorum_msg() -> WasmMsg {
+    WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        msg: to_binary(&ExecuteMsg::UpdateConfig {
+            staking: None,
+            quorum: Some(Decimal::percent(40)),
+            threshold: None,
+            voting_period: None,
+            timelock_period: None,
+            expiration_period: None,
+            proposal_deposit: None,
+            treasury: None,
+            spam_slash_ratio: None,
+            veto_slash_ratio: None,
+        }).unwrap(),
+        funds: vec![],
+    }
+}
+
+#[test]
+fn test_submit_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]),
+                      submit_msg("Text proposal", 1_000, vec![])).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      submit_msg("Text proposal", 999, vec![])).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientDeposit { required: Uint128::new(1_000) });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      submit_msg("Tx", 1_000, vec![])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal { reason: "title length is out of bounds".to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+            submit_msg("Text proposal", 1_000, vec![])).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Active);
+    assert_eq!(proposal.proposer, PROPOSER.to_string());
+    assert_eq!(proposal.start_time, env.block.time.seconds());
+    assert_eq!(proposal.end_time, env.block.time.seconds() + VOTING_PERIOD);
+    assert_eq!(proposal.deposit, Uint128::new(1_000));
+}
+
+#[test]
+fn test_vote_and_execute() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    deps.querier.with_stake(BOB, 300);
+    deps.querier.with_stake("carol", 100);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+            submit_msg("Raise quorum", 1_000, vec![update_quorum_msg()])).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::Yes }).unwrap_err();
+    assert_eq!(err, ContractError::NoVotingPower {});
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+            ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::Yes }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::No }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]),
+            ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::No }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::EndProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::VotingPeriodNotEnded {});
+
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]),
+                      ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::No }).unwrap_err();
+    assert_eq!(err, ContractError::VotingPeriodEnded {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+            ExecuteMsg::EndProposal { proposal_id: 1 }).unwrap();
+
+    // The deposit is held until the proposal can not be vetoed anymore
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PROPOSER, &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::DepositNotClaimable {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::TimelockNotExpired {});
+
+    env.block.time = env.block.time.plus_seconds(TIMELOCK_PERIOD);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(update_quorum_msg()))]);
+
+    // A passed proposal deposit is fully refunded
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PROPOSER, &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(PROPOSER, 1_000)]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PROPOSER, &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::DepositAlreadyClaimed {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+
+    // The executed message updates the config as the contract itself
+    let msg = ExecuteMsg::UpdateConfig {
+        staking: None,
+        quorum: Some(Decimal::percent(40)),
+        threshold: None,
+        voting_period: None,
+        timelock_period: None,
+        expiration_period: None,
+        proposal_deposit: None,
+        treasury: None,
+        spam_slash_ratio: None,
+        veto_slash_ratio: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.quorum, Decimal::percent(40));
+}
+
+#[test]
+fn test_rejected_proposals() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    deps.querier.with_stake(BOB, 300);
+    deps.querier.with_stake("carol", 100);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Carol alone does not reach the quorum
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+            submit_msg("Without quorum", 1_000, vec![])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("carol", &[]),
+            ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::Yes }).unwrap();
+
+    // Alice rejects with the majority
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+            submit_msg("Rejected", 1_000, vec![])).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+            ExecuteMsg::CastVote { proposal_id: 2, vote: VoteOption::No }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]),
+            ExecuteMsg::CastVote { proposal_id: 2, vote: VoteOption::Yes }).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    for proposal_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                ExecuteMsg::EndProposal { proposal_id }).unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id }).unwrap();
+        let proposal: ProposalResponse = from_binary(&res).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+    }
+
+    env.block.time = env.block.time.plus_seconds(TIMELOCK_PERIOD);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+
+    // The spam deposit is partly slashed, the rejected one with quorum is refunded
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::DepositClaims {
+        proposer: PROPOSER.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let claims: DepositClaimsResponse = from_binary(&res).unwrap();
+    assert_eq!(claims.claims[0], DepositClaim {
+        proposal_id: 1,
+        status: ProposalStatus::Rejected,
+        deposit: Uint128::new(1_000),
+        refund_amount: Uint128::new(800),
+        slashed_amount: Uint128::new(200),
+        claimable: true,
+        claimed: false,
+    });
+    assert_eq!(claims.claims[1].refund_amount, Uint128::new(1_000));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(PROPOSER, 800), transfer_msg(TREASURY, 200)]);
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(PROPOSER, 1_000)]);
+}
+
+fn fee_proposal_msg(fee_config: SwapFeeConfigUpdate) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: PROPOSER.to_string(),
+        amount: Uint128::new(1_000),
+        msg: to_binary(&Cw20HookMsg::SubmitFeeProposal {
+            title: "Swap fee change".to_string(),
+            description: "Raise the swap fee to 2%".to_string(),
+            link: None,
+            fee_config,
+        }).unwrap(),
+    })
+}
+
+#[test]
+fn test_fee_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      fee_proposal_msg(SwapFeeConfigUpdate::default())).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal { reason: "the fee proposal changes nothing".to_string() });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      fee_proposal_msg(SwapFeeConfigUpdate {
+                          swap_percent_fee: Some(Decimal::percent(11)),
+                          ..SwapFeeConfigUpdate::default()
+                      })).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal { reason: "the swap fee must not exceed 10%".to_string() });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      fee_proposal_msg(SwapFeeConfigUpdate {
+                          fee_receiver: Some("".to_string()),
+                          ..SwapFeeConfigUpdate::default()
+                      })).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    let fee_config = SwapFeeConfigUpdate {
+        enable_swap_fee: Some(true),
+        swap_percent_fee: Some(Decimal::percent(2)),
+        ..SwapFeeConfigUpdate::default()
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fee_proposal_msg(fee_config.clone())).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.fee_config, Some(fee_config));
+    assert!(proposal.messages.is_empty());
+
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+            ExecuteMsg::CastVote { proposal_id: 1, vote: VoteOption::Yes }).unwrap();
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+            ExecuteMsg::EndProposal { proposal_id: 1 }).unwrap();
+    env.block.time = env.block.time.plus_seconds(TIMELOCK_PERIOD);
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&TokenExecuteMsg::UpdateSwapFeeConfig {
+            fee_admin: None,
+            enable_swap_fee: Some(true),
+            swap_percent_fee: Some(Decimal::percent(2)),
+            fee_receiver: None,
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+}
+
+#[test]
+fn test_veto_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_stake(ALICE, 600);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    for _ in 0..2 {
+        execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                submit_msg("Raise quorum", 1_000, vec![update_quorum_msg()])).unwrap();
+    }
+    for proposal_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                ExecuteMsg::CastVote { proposal_id, vote: VoteOption::Yes }).unwrap();
+    }
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GUARDIAN, &[]),
+                      ExecuteMsg::VetoProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    for proposal_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                ExecuteMsg::EndProposal { proposal_id }).unwrap();
+    }
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::VetoProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(GUARDIAN, &[]),
+            ExecuteMsg::VetoProposal { proposal_id: 1 }).unwrap();
+
+    // The guardian can not veto once the proposal is executable
+    env.block.time = env.block.time.plus_seconds(TIMELOCK_PERIOD);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GUARDIAN, &[]),
+                      ExecuteMsg::VetoProposal { proposal_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::VetoWindowEnded {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Vetoed);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ClaimDeposit { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(PROPOSER, 500), transfer_msg(TREASURY, 500)]);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+            ExecuteMsg::ExecuteProposal { proposal_id: 2 }).unwrap();
+
+    // The guardian is removed through governance only
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GUARDIAN, &[]),
+                      ExecuteMsg::UpdateGuardian { guardian: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]),
+            ExecuteMsg::UpdateGuardian { guardian: None }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.guardian, None);
+}
@@ -0,0 +1,286 @@
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Env, from_binary, IbcMsg, IbcTimeout, MessageInfo, Order, Response,
+    StdResult, to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::ibc::Ics20Packet;
+use crate::msg::{
+    ChannelResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, ListChannelsResponse,
+    MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    CHANNEL_CONFIG, CHANNEL_INFO, CHANNEL_STATE, ChannelInfo, Config, CONFIG,
+    increase_channel_balance, RateLimit,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-ics20";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.default_timeout == 0 {
+        return Err(ContractError::InvalidTimeout {});
+    }
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        token: deps.api.addr_validate(&msg.token)?,
+        default_timeout: msg.default_timeout,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::AllowChannel {
+            channel,
+            rate_limit,
+        } => allow_channel(deps, info, channel, rate_limit),
+        ExecuteMsg::DisallowChannel { channel } => disallow_channel(deps, info, channel),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            default_timeout,
+        } => update_config(deps, info, owner, default_timeout),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(ContractError::InvalidToken {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Transfer {
+            channel,
+            remote_address,
+            timeout,
+        } => transfer(deps, env, config, cw20_msg, channel, remote_address, timeout),
+    }
+}
+
+/// Escrows the received LUART and sends it over the channel as an ICS20 packet
+pub fn transfer(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    cw20_msg: Cw20ReceiveMsg,
+    channel: String,
+    remote_address: String,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    if cw20_msg.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if !CHANNEL_INFO.has(deps.storage, &channel) {
+        return Err(ContractError::ChannelNotFound { id: channel });
+    }
+
+    let channel_config = CHANNEL_CONFIG.may_load(deps.storage, &channel)?.unwrap_or_default();
+    if !channel_config.allowed {
+        return Err(ContractError::ChannelNotAllowed { id: channel });
+    }
+
+    let timeout = timeout.unwrap_or(config.default_timeout);
+    if timeout == 0 {
+        return Err(ContractError::InvalidTimeout {});
+    }
+
+    let now = env.block.time.seconds();
+    increase_channel_balance(deps.storage, &channel, cw20_msg.amount, channel_config.rate_limit.as_ref(), now)?;
+
+    let packet = Ics20Packet::new(cw20_msg.amount, token_denom(&config), &cw20_msg.sender, &remote_address);
+    let msg = IbcMsg::SendPacket {
+        channel_id: channel.clone(),
+        data: to_binary(&packet)?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout)),
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "transfer")
+        .add_attribute("channel", channel)
+        .add_attribute("sender", cw20_msg.sender)
+        .add_attribute("receiver", remote_address)
+        .add_attribute("amount", cw20_msg.amount))
+}
+
+/// The ICS20 denom of the LUART sent by the contract
+pub fn token_denom(config: &Config) -> String {
+    format!("cw20:{}", config.token)
+}
+
+pub fn allow_channel(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel: String,
+    rate_limit: Option<RateLimit>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+
+    if !CHANNEL_INFO.has(deps.storage, &channel) {
+        return Err(ContractError::ChannelNotFound { id: channel });
+    }
+
+    if let Some(rate_limit) = &rate_limit {
+        if rate_limit.period == 0 {
+            return Err(ContractError::InvalidRateLimit {});
+        }
+    }
+
+    CHANNEL_CONFIG.update(deps.storage, &channel, |channel_config| -> StdResult<_> {
+        let mut channel_config = channel_config.unwrap_or_default();
+        channel_config.allowed = true;
+        channel_config.rate_limit = rate_limit;
+        Ok(channel_config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "allow_channel")
+        .add_attribute("channel", channel))
+}
+
+pub fn disallow_channel(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+
+    if !CHANNEL_INFO.has(deps.storage, &channel) {
+        return Err(ContractError::ChannelNotFound { id: channel });
+    }
+
+    CHANNEL_CONFIG.update(deps.storage, &channel, |channel_config| -> StdResult<_> {
+        let mut channel_config = channel_config.unwrap_or_default();
+        channel_config.allowed = false;
+        Ok(channel_config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "disallow_channel")
+        .add_attribute("channel", channel))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    default_timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.as_ref(), &info)?;
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(default_timeout) = default_timeout {
+        if default_timeout == 0 {
+            return Err(ContractError::InvalidTimeout {});
+        }
+        config.default_timeout = default_timeout;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_owner(deps: Deps, info: &MessageInfo) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(config)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Channel { id } => to_binary(&query_channel(deps, id)?),
+        QueryMsg::ListChannels { start_after, limit } => to_binary(&query_list_channels(deps, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        token: config.token.to_string(),
+        default_timeout: config.default_timeout,
+    })
+}
+
+pub fn query_channel(deps: Deps, id: String) -> StdResult<ChannelResponse> {
+    Ok(ChannelResponse {
+        info: CHANNEL_INFO.load(deps.storage, &id)?,
+        config: CHANNEL_CONFIG.may_load(deps.storage, &id)?.unwrap_or_default(),
+        state: CHANNEL_STATE.may_load(deps.storage, &id)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_list_channels(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListChannelsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let channels: StdResult<Vec<ChannelInfo>> = CHANNEL_INFO
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, channel)| channel))
+        .collect();
+
+    Ok(ListChannelsResponse { channels: channels? })
+}
@@ -0,0 +1,50 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("The timeout must be positive")]
+    InvalidTimeout {},
+
+    #[error("The rate limit period must be positive")]
+    InvalidRateLimit {},
+
+    #[error("Only LUART can be sent over the channels")]
+    InvalidToken {},
+
+    #[error("Unknown channel {id}")]
+    ChannelNotFound { id: String },
+
+    #[error("Channel {id} is not allowed")]
+    ChannelNotAllowed { id: String },
+
+    #[error("Rate limit of channel {id} exceeded")]
+    RateLimitExceeded { id: String },
+
+    #[error("Insufficient funds to redeem the tokens sent over channel {id}")]
+    InsufficientFunds { id: String },
+
+    #[error("Only the LUART sent over the channel can be redeemed, got {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Only supports unordered channels")]
+    OnlyUnorderedChannel {},
+
+    #[error("Only supports channel with ics20 version {expected}, got {version}")]
+    InvalidIbcVersion { expected: String, version: String },
+
+    #[error("The channels cannot be closed")]
+    CannotClose {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
+}
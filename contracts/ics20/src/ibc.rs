@@ -0,0 +1,243 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    Binary, ContractResult, DepsMut, Env, from_binary, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcEndpoint, IbcOrder, IbcPacket,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Reply,
+    Response, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::token_denom;
+use crate::error::ContractError;
+use crate::state::{
+    CHANNEL_CONFIG, CHANNEL_INFO, ChannelInfo, CONFIG, reduce_channel_balance, REPLY_ARGS, ReplyArgs,
+    undo_increase_channel_balance, undo_reduce_channel_balance,
+};
+
+pub const ICS20_VERSION: &str = "ics20-1";
+pub const ICS20_ORDERING: IbcOrder = IbcOrder::Unordered;
+
+const RECEIVE_ID: u64 = 1;
+
+/// The fungible token packet data of ICS20
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ics20Packet {
+    pub amount: Uint128,
+    /// The token denom, prefixed by the port and channel of each hop except the origin one
+    pub denom: String,
+    pub receiver: String,
+    pub sender: String,
+}
+
+impl Ics20Packet {
+    pub fn new(amount: Uint128, denom: impl Into<String>, sender: &str, receiver: &str) -> Self {
+        Ics20Packet {
+            amount,
+            denom: denom.into(),
+            sender: sender.to_string(),
+            receiver: receiver.to_string(),
+        }
+    }
+}
+
+/// The ICS20 acknowledgement, in the format of the Cosmos SDK transfer module
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ics20Ack {
+    Result(Binary),
+    Error(String),
+}
+
+fn ack_success() -> Binary {
+    to_binary(&Ics20Ack::Result(b"1".into())).unwrap()
+}
+
+fn ack_fail(err: String) -> Binary {
+    to_binary(&Ics20Ack::Error(err)).unwrap()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    enforce_order_and_version(msg.channel(), msg.counterparty_version())
+}
+
+/// Records the opened channel, the owner has to allow it before sending tokens over it
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    enforce_order_and_version(msg.channel(), msg.counterparty_version())?;
+
+    let channel: IbcChannel = msg.into();
+    let info = ChannelInfo {
+        id: channel.endpoint.channel_id,
+        counterparty_endpoint: channel.counterparty_endpoint,
+        connection_id: channel.connection_id,
+    };
+    CHANNEL_INFO.save(deps.storage, &info.id, &info)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "channel_connect")
+        .add_attribute("channel", info.id))
+}
+
+fn enforce_order_and_version(channel: &IbcChannel, counterparty_version: Option<&str>) -> Result<(), ContractError> {
+    if channel.order != ICS20_ORDERING {
+        return Err(ContractError::OnlyUnorderedChannel {});
+    }
+
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        if version != ICS20_VERSION {
+            return Err(ContractError::InvalidIbcVersion {
+                expected: ICS20_VERSION.to_string(),
+                version: version.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Closing a channel would lock the tokens escrowed for it
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Err(ContractError::CannotClose {})
+}
+
+/// Redeems the LUART sent back over the channel. The failures are returned in the
+/// acknowledgement so that the sending chain refunds the tokens.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    match do_ibc_packet_receive(deps, env, &msg.packet) {
+        Ok(res) => Ok(res),
+        Err(err) => Ok(IbcReceiveResponse::new()
+            .set_ack(ack_fail(err.to_string()))
+            .add_attribute("action", "receive")
+            .add_attribute("success", "false")
+            .add_attribute("error", err.to_string())),
+    }
+}
+
+fn do_ibc_packet_receive(deps: DepsMut, env: Env, packet: &IbcPacket) -> Result<IbcReceiveResponse, ContractError> {
+    let msg: Ics20Packet = from_binary(&packet.data)?;
+    if msg.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    if msg.denom != voucher_denom(&packet.src, &token_denom(&config)) {
+        return Err(ContractError::InvalidDenom { denom: msg.denom });
+    }
+
+    let channel = packet.dest.channel_id.clone();
+    let rate_limit = CHANNEL_CONFIG.may_load(deps.storage, &channel)?.and_then(|config| config.rate_limit);
+    reduce_channel_balance(deps.storage, &channel, msg.amount, rate_limit.as_ref(), env.block.time.seconds())?;
+
+    let receiver = deps.api.addr_validate(&msg.receiver)?;
+    REPLY_ARGS.save(deps.storage, &ReplyArgs { channel: channel.clone(), amount: msg.amount })?;
+    let transfer = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: receiver.to_string(), amount: msg.amount })?,
+        funds: vec![],
+    };
+
+    Ok(IbcReceiveResponse::new()
+        .set_ack(ack_success())
+        .add_submessage(SubMsg::reply_on_error(transfer, RECEIVE_ID))
+        .add_attribute("action", "receive")
+        .add_attribute("success", "true")
+        .add_attribute("channel", channel)
+        .add_attribute("sender", msg.sender)
+        .add_attribute("receiver", receiver)
+        .add_attribute("amount", msg.amount))
+}
+
+/// The denom of the tokens coming back over a channel, prefixed by the remote endpoint
+fn voucher_denom(remote: &IbcEndpoint, denom: &str) -> String {
+    format!("{}/{}/{}", remote.port_id, remote.channel_id, denom)
+}
+
+/// Turns a failed payout of redeemed tokens into an error acknowledgement
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    if reply.id != RECEIVE_ID {
+        return Err(ContractError::UnknownReplyId { id: reply.id });
+    }
+
+    match reply.result {
+        ContractResult::Ok(_) => Ok(Response::new()),
+        ContractResult::Err(err) => {
+            let args = REPLY_ARGS.load(deps.storage)?;
+            undo_reduce_channel_balance(deps.storage, &args.channel, args.amount)?;
+            Ok(Response::new().set_data(ack_fail(err)))
+        }
+    }
+}
+
+/// Refunds the sender when the remote chain failed to process the transfer
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let ack: Ics20Ack = from_binary(&msg.acknowledgement.data)?;
+    match ack {
+        Ics20Ack::Result(_) => {
+            let packet: Ics20Packet = from_binary(&msg.original_packet.data)?;
+            Ok(IbcBasicResponse::new()
+                .add_attribute("action", "acknowledge")
+                .add_attribute("success", "true")
+                .add_attribute("sender", packet.sender)
+                .add_attribute("receiver", packet.receiver)
+                .add_attribute("amount", packet.amount))
+        }
+        Ics20Ack::Error(err) => refund_packet(deps, &msg.original_packet, "acknowledge")
+            .map(|res| res.add_attribute("error", err)),
+    }
+}
+
+/// Refunds the sender of the timed out transfer
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    refund_packet(deps, &msg.packet, "timeout")
+}
+
+fn refund_packet(deps: DepsMut, packet: &IbcPacket, action: &str) -> Result<IbcBasicResponse, ContractError> {
+    let msg: Ics20Packet = from_binary(&packet.data)?;
+    let config = CONFIG.load(deps.storage)?;
+    undo_increase_channel_balance(deps.storage, &packet.src.channel_id, msg.amount)?;
+
+    let refund = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: msg.sender.clone(), amount: msg.amount })?,
+        funds: vec![],
+    };
+
+    Ok(IbcBasicResponse::new()
+        .add_message(refund)
+        .add_attribute("action", action)
+        .add_attribute("success", "false")
+        .add_attribute("sender", msg.sender)
+        .add_attribute("amount", msg.amount))
+}
@@ -0,0 +1,8 @@
+pub mod contract;
+pub mod error;
+pub mod ibc;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::{ChannelConfig, ChannelInfo, ChannelState, RateLimit};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token address
+    pub token: String,
+    /// The timeout in seconds of the transfers not setting one
+    pub default_timeout: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Sends the received LUART over a channel, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Allows the transfers out over an opened channel and sets its rate limit.
+    /// Only the owner can execute it.
+    AllowChannel {
+        channel: String,
+        rate_limit: Option<RateLimit>,
+    },
+    /// Stops the transfers out over a channel, keeping its rate limit for the redemptions.
+    /// Only the owner can execute it.
+    DisallowChannel { channel: String },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        default_timeout: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Sends the received tokens over an allowed channel to the remote address
+    Transfer {
+        channel: String,
+        remote_address: String,
+        /// The timeout in seconds, defaults to the configured one
+        timeout: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns an opened channel with its settings and balances.
+    /// Return type: ChannelResponse.
+    Channel { id: String },
+    /// Returns the opened channels.
+    /// Return type: ListChannelsResponse.
+    ListChannels {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub token: String,
+    pub default_timeout: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChannelResponse {
+    pub info: ChannelInfo,
+    pub config: ChannelConfig,
+    pub state: ChannelState,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListChannelsResponse {
+    pub channels: Vec<ChannelInfo>,
+}
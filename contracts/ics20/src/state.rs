@@ -0,0 +1,148 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, IbcEndpoint, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use crate::error::ContractError;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    /// The LUART token sent over the channels
+    pub token: Addr,
+    /// The timeout in seconds of the transfers not setting one
+    pub default_timeout: u64,
+}
+
+/// A channel opened with the contract, whether it is allowed or not
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChannelInfo {
+    /// The channel identifier on this chain
+    pub id: String,
+    /// The remote port and channel
+    pub counterparty_endpoint: IbcEndpoint,
+    /// The connection the channel was opened over
+    pub connection_id: String,
+}
+
+/// The maximum amount moving over a channel in each direction during a period
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimit {
+    pub max_amount: Uint128,
+    /// The period length in seconds
+    pub period: u64,
+}
+
+/// The owner settings of a channel. The transfers out are only accepted over the allowed
+/// channels, the tokens sent over a disallowed channel can still be redeemed back.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ChannelConfig {
+    pub allowed: bool,
+    /// No limit when unset
+    pub rate_limit: Option<RateLimit>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ChannelState {
+    /// The tokens escrowed for the transfers over the channel, which can be redeemed back
+    pub outstanding: Uint128,
+    pub total_sent: Uint128,
+    pub total_received: Uint128,
+    /// The start time of the current rate limit period
+    pub period_start: u64,
+    pub sent_in_period: Uint128,
+    pub received_in_period: Uint128,
+}
+
+/// The transfer being redeemed back, to restore the channel state if the payout fails
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReplyArgs {
+    pub channel: String,
+    pub amount: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CHANNEL_INFO: Map<&str, ChannelInfo> = Map::new("channel_info");
+pub const CHANNEL_CONFIG: Map<&str, ChannelConfig> = Map::new("channel_config");
+pub const CHANNEL_STATE: Map<&str, ChannelState> = Map::new("channel_state");
+pub const REPLY_ARGS: Item<ReplyArgs> = Item::new("reply_args");
+
+/// Starts a new rate limit period once the current one has ended
+fn roll_period(state: &mut ChannelState, rate_limit: &RateLimit, now: u64) {
+    if now >= state.period_start + rate_limit.period {
+        state.period_start = now;
+        state.sent_in_period = Uint128::zero();
+        state.received_in_period = Uint128::zero();
+    }
+}
+
+/// Escrows the tokens sent out over the channel, within the rate limit of the channel
+pub fn increase_channel_balance(
+    storage: &mut dyn Storage,
+    channel: &str,
+    amount: Uint128,
+    rate_limit: Option<&RateLimit>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let mut state = CHANNEL_STATE.may_load(storage, channel)?.unwrap_or_default();
+    if let Some(rate_limit) = rate_limit {
+        roll_period(&mut state, rate_limit, now);
+        state.sent_in_period += amount;
+        if state.sent_in_period > rate_limit.max_amount {
+            return Err(ContractError::RateLimitExceeded { id: channel.to_string() });
+        }
+    }
+
+    state.outstanding += amount;
+    state.total_sent += amount;
+    CHANNEL_STATE.save(storage, channel, &state)?;
+    Ok(())
+}
+
+/// Releases the tokens redeemed back over the channel, within the rate limit of the channel
+pub fn reduce_channel_balance(
+    storage: &mut dyn Storage,
+    channel: &str,
+    amount: Uint128,
+    rate_limit: Option<&RateLimit>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let mut state = CHANNEL_STATE.may_load(storage, channel)?.unwrap_or_default();
+    if let Some(rate_limit) = rate_limit {
+        roll_period(&mut state, rate_limit, now);
+        state.received_in_period += amount;
+        if state.received_in_period > rate_limit.max_amount {
+            return Err(ContractError::RateLimitExceeded { id: channel.to_string() });
+        }
+    }
+
+    state.outstanding = state.outstanding.checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientFunds { id: channel.to_string() })?;
+    state.total_received += amount;
+    CHANNEL_STATE.save(storage, channel, &state)?;
+    Ok(())
+}
+
+/// Restores the escrow of the tokens sent out and refunded, leaving the rate limit period as is
+pub fn undo_increase_channel_balance(storage: &mut dyn Storage, channel: &str, amount: Uint128) -> StdResult<()> {
+    CHANNEL_STATE.update(storage, channel, |state| -> StdResult<_> {
+        let mut state = state.unwrap_or_default();
+        state.outstanding = state.outstanding.checked_sub(amount)?;
+        state.total_sent = state.total_sent.checked_sub(amount)?;
+        Ok(state)
+    })?;
+    Ok(())
+}
+
+/// Restores the escrow of the tokens redeemed back when their payout failed
+pub fn undo_reduce_channel_balance(storage: &mut dyn Storage, channel: &str, amount: Uint128) -> StdResult<()> {
+    CHANNEL_STATE.update(storage, channel, |state| -> StdResult<_> {
+        let mut state = state.unwrap_or_default();
+        state.outstanding += amount;
+        state.total_received = state.total_received.checked_sub(amount)?;
+        state.received_in_period = state.received_in_period.saturating_sub(amount);
+        Ok(state)
+    })?;
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use cosmwasm_std::{
+    ContractResult, Deps, DepsMut, Env, from_binary, IbcAcknowledgement, IbcMsg, IbcOrder, IbcTimeout,
+    Reply, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_open_init,
+    mock_ibc_channel_open_try, mock_ibc_packet_ack, mock_ibc_packet_recv, mock_ibc_packet_timeout,
+    mock_info,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::ibc::{
+    ibc_channel_connect, ibc_channel_open, ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout,
+    ICS20_VERSION, Ics20Ack, Ics20Packet, reply,
+};
+use crate::msg::{ChannelResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, ListChannelsResponse, QueryMsg};
+use crate::state::RateLimit;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const SENDER: &str = "mock_sender";
+const RECEIVER: &str = "mock_receiver";
+const REMOTE_ADDRESS: &str = "cosmos1remote";
+const CHANNEL: &str = "channel-5";
+const DEFAULT_TIMEOUT: u64 = 3_600;
+
+fn default_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+) {
+    let msg = InstantiateMsg {
+        owner: None,
+        token: TOKEN.to_string(),
+        default_timeout: DEFAULT_TIMEOUT,
+    };
+    instantiate(deps.branch(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let connect = mock_ibc_channel_connect_ack(CHANNEL, IbcOrder::Unordered, ICS20_VERSION);
+    ibc_channel_connect(deps, env, connect).unwrap();
+}
+
+fn transfer_msg(amount: u128, timeout: Option<u64>) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: SENDER.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Transfer {
+            channel: CHANNEL.to_string(),
+            remote_address: REMOTE_ADDRESS.to_string(),
+            timeout,
+        }).unwrap(),
+    })
+}
+
+fn allow_msg(rate_limit: Option<RateLimit>) -> ExecuteMsg {
+    ExecuteMsg::AllowChannel { channel: CHANNEL.to_string(), rate_limit }
+}
+
+fn token_transfer(recipient: &str, amount: u128) -> WasmMsg {
+    WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }
+}
+
+/// The LUART coming back from the remote chain, prefixed with the remote endpoint
+fn redeem_packet(amount: u128) -> Ics20Packet {
+    Ics20Packet::new(
+        Uint128::new(amount),
+        format!("their-port/channel-1234/cw20:{}", TOKEN),
+        REMOTE_ADDRESS,
+        RECEIVER,
+    )
+}
+
+fn query_channel(deps: Deps, env: Env) -> ChannelResponse {
+    from_binary(&query(deps, env, QueryMsg::Channel { id: CHANNEL.to_string() }).unwrap()).unwrap()
+}
+
+#[test]
+fn test_channel_handshake() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+
+    let open = mock_ibc_channel_open_init(CHANNEL, IbcOrder::Ordered, ICS20_VERSION);
+    let err = ibc_channel_open(deps.as_mut(), env.clone(), open).unwrap_err();
+    assert_eq!(err, ContractError::OnlyUnorderedChannel {});
+
+    let open = mock_ibc_channel_open_try(CHANNEL, IbcOrder::Unordered, "ics20-2");
+    let err = ibc_channel_open(deps.as_mut(), env.clone(), open).unwrap_err();
+    assert_eq!(err, ContractError::InvalidIbcVersion {
+        expected: ICS20_VERSION.to_string(),
+        version: "ics20-2".to_string(),
+    });
+
+    let open = mock_ibc_channel_open_try(CHANNEL, IbcOrder::Unordered, ICS20_VERSION);
+    ibc_channel_open(deps.as_mut(), env.clone(), open).unwrap();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let res: ListChannelsResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::ListChannels {
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    assert_eq!(res.channels.len(), 1);
+    assert_eq!(res.channels[0].id, CHANNEL);
+    assert_eq!(res.channels[0].connection_id, "connection-2");
+
+    // The opened channels are not allowed until the owner allows them
+    let channel = query_channel(deps.as_ref(), env.clone());
+    assert!(!channel.config.allowed);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(100, None)).unwrap_err();
+    assert_eq!(err, ContractError::ChannelNotAllowed { id: CHANNEL.to_string() });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), allow_msg(None)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]), ExecuteMsg::AllowChannel {
+        channel: "channel-9".to_string(),
+        rate_limit: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::ChannelNotFound { id: "channel-9".to_string() });
+}
+
+#[test]
+fn test_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let rate_limit = RateLimit { max_amount: Uint128::new(1_000), period: 86_400 };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), allow_msg(Some(rate_limit))).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]), transfer_msg(100, None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidToken {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(600, None)).unwrap();
+    let packet = Ics20Packet::new(Uint128::new(600), format!("cw20:{}", TOKEN), SENDER, REMOTE_ADDRESS);
+    assert_eq!(res.messages, vec![SubMsg::new(IbcMsg::SendPacket {
+        channel_id: CHANNEL.to_string(),
+        data: to_binary(&packet).unwrap(),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(DEFAULT_TIMEOUT)),
+    })]);
+
+    // The transfers are limited to 1000 tokens a day
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(500, Some(60))).unwrap_err();
+    assert_eq!(err, ContractError::RateLimitExceeded { id: CHANNEL.to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(400, Some(60))).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(86_400);
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(500, Some(60))).unwrap();
+    let channel = query_channel(deps.as_ref(), env.clone());
+    assert_eq!(channel.state.outstanding, Uint128::new(1_500));
+    assert_eq!(channel.state.sent_in_period, Uint128::new(500));
+
+    let disallow = ExecuteMsg::DisallowChannel { channel: CHANNEL.to_string() };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), disallow).unwrap();
+    let err = execute(deps.as_mut(), env, mock_info(TOKEN, &[]), transfer_msg(100, None)).unwrap_err();
+    assert_eq!(err, ContractError::ChannelNotAllowed { id: CHANNEL.to_string() });
+}
+
+#[test]
+fn test_redeem() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let rate_limit = RateLimit { max_amount: Uint128::new(1_000), period: 86_400 };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), allow_msg(Some(rate_limit))).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(1_000, None)).unwrap();
+
+    // Only the tokens escrowed for the channel can be redeemed
+    let foreign = Ics20Packet::new(Uint128::new(100), "their-port/channel-1234/uatom", REMOTE_ADDRESS, RECEIVER);
+    let res = ibc_packet_receive(deps.as_mut(), env.clone(), mock_ibc_packet_recv(CHANNEL, &foreign).unwrap())
+        .unwrap();
+    assert!(res.messages.is_empty());
+    let ack: Ics20Ack = from_binary(&res.acknowledgement).unwrap();
+    assert_eq!(ack, Ics20Ack::Error(ContractError::InvalidDenom {
+        denom: "their-port/channel-1234/uatom".to_string(),
+    }.to_string()));
+
+    let recv = mock_ibc_packet_recv(CHANNEL, &redeem_packet(1_500)).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), env.clone(), recv).unwrap();
+    let ack: Ics20Ack = from_binary(&res.acknowledgement).unwrap();
+    assert_eq!(ack, Ics20Ack::Error(ContractError::RateLimitExceeded { id: CHANNEL.to_string() }.to_string()));
+
+    // The redemptions keep the rate limit of the channel once it is disallowed
+    let disallow = ExecuteMsg::DisallowChannel { channel: CHANNEL.to_string() };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), disallow).unwrap();
+    let recv = mock_ibc_packet_recv(CHANNEL, &redeem_packet(700)).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), env.clone(), recv).unwrap();
+    let ack: Ics20Ack = from_binary(&res.acknowledgement).unwrap();
+    assert_eq!(ack, Ics20Ack::Result(b"1".into()));
+    assert_eq!(res.messages, vec![SubMsg::reply_on_error(token_transfer(RECEIVER, 700), 1)]);
+    assert_eq!(query_channel(deps.as_ref(), env.clone()).state.outstanding, Uint128::new(300));
+
+    // A failed payout is acknowledged as an error and restores the escrow
+    let res = reply(deps.as_mut(), env.clone(), Reply { id: 1, result: ContractResult::Err("failed".to_string()) })
+        .unwrap();
+    let ack: Ics20Ack = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(ack, Ics20Ack::Error("failed".to_string()));
+    let channel = query_channel(deps.as_ref(), env);
+    assert_eq!(channel.state.outstanding, Uint128::new(1_000));
+    assert_eq!(channel.state.total_received, Uint128::zero());
+}
+
+#[test]
+fn test_refund() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), allow_msg(None)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), transfer_msg(1_000, None)).unwrap();
+    let packet = Ics20Packet::new(Uint128::new(400), format!("cw20:{}", TOKEN), SENDER, REMOTE_ADDRESS);
+
+    let success = IbcAcknowledgement::encode_json(&Ics20Ack::Result(b"1".into())).unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env.clone(), mock_ibc_packet_ack(CHANNEL, &packet, success).unwrap())
+        .unwrap();
+    assert!(res.messages.is_empty());
+
+    let error = IbcAcknowledgement::encode_json(&Ics20Ack::Error("invalid receiver".to_string())).unwrap();
+    let res = ibc_packet_ack(deps.as_mut(), env.clone(), mock_ibc_packet_ack(CHANNEL, &packet, error).unwrap())
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(token_transfer(SENDER, 400))]);
+
+    let res = ibc_packet_timeout(deps.as_mut(), env.clone(), mock_ibc_packet_timeout(CHANNEL, &packet).unwrap())
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(token_transfer(SENDER, 400))]);
+    let channel = query_channel(deps.as_ref(), env);
+    assert_eq!(channel.state.outstanding, Uint128::new(200));
+    assert_eq!(channel.state.total_sent, Uint128::new(200));
+}
@@ -0,0 +1,331 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+    to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AssetBudgetResponse, BudgetResponse, ClaimResponse, ClaimsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, QueryMsg,
+};
+use crate::state::{asset_key, Claim, CLAIM_COUNT, CLAIMS, ClaimStatus, Config, CONFIG, PERIOD_PAYOUTS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-insurance";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.period == 0 {
+        return Err(ContractError::InvalidPeriod {});
+    }
+
+    CONFIG.save(deps.storage, &Config {
+        governance: deps.api.addr_validate(&msg.governance)?,
+        period: msg.period,
+        budgets: msg.budgets,
+    })?;
+    CLAIM_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::FileClaim {
+            incident,
+            asset,
+            description,
+        } => file_claim(deps, env, info, incident, asset, description),
+        ExecuteMsg::ApproveClaim { claim_id, amount } => approve_claim(deps, env, info, claim_id, amount),
+        ExecuteMsg::RejectClaim { claim_id } => reject_claim(deps, info, claim_id),
+        ExecuteMsg::UpdateConfig {
+            governance,
+            period,
+            budgets,
+        } => update_config(deps, info, governance, period, budgets),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn file_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    incident: String,
+    asset: Asset,
+    description: String,
+) -> Result<Response, ContractError> {
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let claim_id = CLAIM_COUNT.load(deps.storage)? + 1;
+    CLAIM_COUNT.save(deps.storage, &claim_id)?;
+    CLAIMS.save(deps.storage, U64Key::new(claim_id), &Claim {
+        claimant: info.sender.clone(),
+        incident: incident.clone(),
+        asset,
+        description,
+        status: ClaimStatus::Pending,
+        filed_at: env.block.time.seconds(),
+        paid_amount: Uint128::zero(),
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "file_claim")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attribute("claimant", info.sender)
+        .add_attribute("incident", incident))
+}
+
+pub fn approve_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.governance != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut claim = CLAIMS.load(deps.storage, U64Key::new(claim_id))?;
+    if claim.status != ClaimStatus::Pending {
+        return Err(ContractError::ClaimNotPending {});
+    }
+    let amount = amount.unwrap_or(claim.asset.amount);
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if amount > claim.asset.amount {
+        return Err(ContractError::ExceedsClaim { claimed: claim.asset.amount });
+    }
+
+    // The payouts of every claim of the period share its budget
+    let key = asset_key(&claim.asset.info).to_string();
+    let budget = config.budget(&claim.asset.info)
+        .ok_or_else(|| ContractError::NoBudget { asset: key.clone() })?;
+    let period = U64Key::new(config.period_at(env.block.time.seconds()));
+    let paid = PERIOD_PAYOUTS.may_load(deps.storage, (period.clone(), &key))?.unwrap_or_default();
+    let remaining = budget.saturating_sub(paid);
+    if amount > remaining {
+        return Err(ContractError::BudgetExceeded { remaining });
+    }
+    PERIOD_PAYOUTS.save(deps.storage, (period, &key), &paid.checked_add(amount)?)?;
+
+    claim.status = ClaimStatus::Paid;
+    claim.paid_amount = amount;
+    CLAIMS.save(deps.storage, U64Key::new(claim_id), &claim)?;
+
+    let payout = Asset { info: claim.asset.info, amount };
+
+    Ok(Response::new()
+        .add_message(asset_transfer_msg(&payout, &claim.claimant)?)
+        .add_attribute("action", "approve_claim")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attribute("claimant", claim.claimant)
+        .add_attribute("asset", key)
+        .add_attribute("amount", amount))
+}
+
+pub fn reject_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    claim_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.governance != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut claim = CLAIMS.load(deps.storage, U64Key::new(claim_id))?;
+    if claim.status != ClaimStatus::Pending {
+        return Err(ContractError::ClaimNotPending {});
+    }
+    claim.status = ClaimStatus::Rejected;
+    CLAIMS.save(deps.storage, U64Key::new(claim_id), &claim)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reject_claim")
+        .add_attribute("claim_id", claim_id.to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    governance: Option<String>,
+    period: Option<u64>,
+    budgets: Option<Vec<Asset>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.governance != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(governance) = governance {
+        config.governance = deps.api.addr_validate(&governance)?;
+    }
+
+    if let Some(period) = period {
+        if period == 0 {
+            return Err(ContractError::InvalidPeriod {});
+        }
+        config.period = period;
+    }
+
+    if let Some(budgets) = budgets {
+        config.budgets = budgets;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Claim { claim_id } => to_binary(&query_claim(deps, claim_id)?),
+        QueryMsg::Claims { start_after, limit } => to_binary(&query_claims(deps, start_after, limit)?),
+        QueryMsg::Budget {} => to_binary(&query_budget(deps, env)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        governance: config.governance.to_string(),
+        period: config.period,
+        budgets: config.budgets,
+    })
+}
+
+pub fn query_claim(deps: Deps, claim_id: u64) -> StdResult<ClaimResponse> {
+    let claim = CLAIMS.load(deps.storage, U64Key::new(claim_id))?;
+    Ok(claim_response(claim_id, claim))
+}
+
+pub fn query_claims(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ClaimsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let claims: StdResult<Vec<ClaimResponse>> = CLAIMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, claim) = item?;
+            Ok(claim_response(parse_claim_id(&k)?, claim))
+        })
+        .collect();
+
+    Ok(ClaimsResponse { claims: claims? })
+}
+
+pub fn query_budget(deps: Deps, env: Env) -> StdResult<BudgetResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let period = config.period_at(env.block.time.seconds());
+
+    let budgets: StdResult<Vec<AssetBudgetResponse>> = config.budgets
+        .into_iter()
+        .map(|budget| {
+            let paid = PERIOD_PAYOUTS
+                .may_load(deps.storage, (U64Key::new(period), asset_key(&budget.info)))?
+                .unwrap_or_default();
+            Ok(AssetBudgetResponse {
+                remaining: budget.amount.saturating_sub(paid),
+                info: budget.info,
+                budget: budget.amount,
+                paid,
+            })
+        })
+        .collect();
+
+    Ok(BudgetResponse {
+        period_end: (period + 1) * config.period,
+        budgets: budgets?,
+    })
+}
+
+fn claim_response(claim_id: u64, claim: Claim) -> ClaimResponse {
+    ClaimResponse {
+        claim_id,
+        claimant: claim.claimant.to_string(),
+        incident: claim.incident,
+        asset: claim.asset,
+        description: claim.description,
+        status: claim.status,
+        filed_at: claim.filed_at,
+        paid_amount: claim.paid_amount,
+    }
+}
+
+fn parse_claim_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted claim key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn asset_transfer_msg(asset: &Asset, recipient: &Addr) -> StdResult<CosmosMsg> {
+    Ok(match &asset.info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
@@ -0,0 +1,32 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Invalid zero budget period")]
+    InvalidPeriod {},
+
+    #[error("The claim is not pending")]
+    ClaimNotPending {},
+
+    #[error("The payout exceeds the {claimed} claimed")]
+    ExceedsClaim { claimed: Uint128 },
+
+    #[error("The fund has no payout budget for {asset}")]
+    NoBudget { asset: String },
+
+    #[error("Payout budget exceeded, {remaining} left for the period")]
+    BudgetExceeded { remaining: Uint128 },
+}
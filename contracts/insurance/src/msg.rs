@@ -0,0 +1,111 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::state::ClaimStatus;
+
+/// This structure describes the parameters used for creating an insurance contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The governance contract approving the payouts
+    pub governance: String,
+    /// The length of a budget period in seconds
+    pub period: u64,
+    /// The most paid out of each asset per period
+    pub budgets: Vec<Asset>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Files a compensation claim for a loss suffered in an incident
+    FileClaim {
+        incident: String,
+        asset: Asset,
+        description: String,
+    },
+    /// Pays a pending claim, the claimed amount by default, within the budget of the period.
+    /// Only governance can execute it.
+    ApproveClaim {
+        claim_id: u64,
+        amount: Option<Uint128>,
+    },
+    /// Rejects a pending claim. Only governance can execute it.
+    RejectClaim { claim_id: u64 },
+    /// Updates the contract configuration. Only governance can execute it.
+    UpdateConfig {
+        governance: Option<String>,
+        period: Option<u64>,
+        budgets: Option<Vec<Asset>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a compensation claim.
+    /// Return type: ClaimResponse.
+    Claim { claim_id: u64 },
+    /// Returns compensation claims by id.
+    /// Return type: ClaimsResponse.
+    Claims {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns what is left of the budgets of the running period.
+    /// Return type: BudgetResponse.
+    Budget {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub governance: String,
+    pub period: u64,
+    pub budgets: Vec<Asset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimResponse {
+    pub claim_id: u64,
+    pub claimant: String,
+    pub incident: String,
+    pub asset: Asset,
+    pub description: String,
+    pub status: ClaimStatus,
+    pub filed_at: u64,
+    pub paid_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimsResponse {
+    pub claims: Vec<ClaimResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetBudgetResponse {
+    pub info: AssetInfo,
+    pub budget: Uint128,
+    /// The amount paid out during the period
+    pub paid: Uint128,
+    pub remaining: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BudgetResponse {
+    /// The end time of the running period, when the budgets reset
+    pub period_end: u64,
+    pub budgets: Vec<AssetBudgetResponse>,
+}
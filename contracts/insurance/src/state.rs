@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use terraswap::asset::{Asset, AssetInfo};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The governance contract approving the payouts
+    pub governance: Addr,
+    /// The length of a budget period in seconds
+    pub period: u64,
+    /// The most paid out of each asset per period
+    pub budgets: Vec<Asset>,
+}
+
+impl Config {
+    /// Returns the budget period running at the given time
+    pub fn period_at(&self, time: u64) -> u64 {
+        time / self.period
+    }
+
+    pub fn budget(&self, info: &AssetInfo) -> Option<Uint128> {
+        self.budgets.iter().find(|b| &b.info == info).map(|b| b.amount)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Claim {
+    pub claimant: Addr,
+    /// The incident the claimant was affected by
+    pub incident: String,
+    /// The loss the claimant asks compensation for
+    pub asset: Asset,
+    pub description: String,
+    pub status: ClaimStatus,
+    pub filed_at: u64,
+    /// The compensation paid, at most the claimed amount
+    pub paid_amount: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CLAIM_COUNT: Item<u64> = Item::new("claim_count");
+pub const CLAIMS: Map<U64Key, Claim> = Map::new("claims");
+/// The amount paid out during a budget period, keyed by period and asset
+pub const PERIOD_PAYOUTS: Map<(U64Key, &str), Uint128> = Map::new("period_payouts");
+
+/// Returns the storage key of an asset, its denom or token address
+pub fn asset_key(info: &AssetInfo) -> &str {
+    match info {
+        AssetInfo::NativeToken { denom } => denom,
+        AssetInfo::Token { contract_addr } => contract_addr,
+    }
+}
@@ -0,0 +1,126 @@
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, Uint128};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{BudgetResponse, ClaimResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::ClaimStatus;
+
+const GOVERNANCE: &str = "mock_governance";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const DENOM: &str = "uusd";
+
+const PERIOD: u64 = 30 * 86_400;
+
+fn native(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        amount: Uint128::new(amount),
+    }
+}
+
+/// The fund pays out up to 1000 uusd per period
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        governance: GOVERNANCE.to_string(),
+        period: PERIOD,
+        budgets: vec![native(1_000)],
+    };
+    instantiate(deps, env, mock_info(GOVERNANCE, &[]), msg).unwrap()
+}
+
+fn file_claim(deps: DepsMut, env: Env, claimant: &str, asset: Asset) {
+    execute(deps, env, mock_info(claimant, &[]),
+            ExecuteMsg::FileClaim {
+                incident: "oracle-2022-05".to_string(),
+                asset,
+                description: "Liquidated at a stale price".to_string(),
+            }).unwrap();
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+#[test]
+fn test_approve_and_reject_claims() {
+    let mut deps = mock_dependencies(&[Coin::new(10_000, DENOM)]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    file_claim(deps.as_mut(), env.clone(), ALICE, native(800));
+    file_claim(deps.as_mut(), env.clone(), BOB, native(300));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 1, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 1, amount: Some(Uint128::new(900)) }).unwrap_err();
+    assert_eq!(err, ContractError::ExceedsClaim { claimed: Uint128::new(800) });
+
+    // Governance compensates part of the loss
+    let res = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 1, amount: Some(Uint128::new(600)) }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(ALICE, 600)]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Claim { claim_id: 1 }).unwrap();
+    let claim: ClaimResponse = from_binary(&res).unwrap();
+    assert_eq!(claim.status, ClaimStatus::Paid);
+    assert_eq!(claim.paid_amount, Uint128::new(600));
+    assert_eq!(claim.incident, "oracle-2022-05".to_string());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 1, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::ClaimNotPending {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+            ExecuteMsg::RejectClaim { claim_id: 2 }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Claim { claim_id: 2 }).unwrap();
+    let claim: ClaimResponse = from_binary(&res).unwrap();
+    assert_eq!(claim.status, ClaimStatus::Rejected);
+    assert_eq!(claim.paid_amount, Uint128::zero());
+}
+
+#[test]
+fn test_period_budget() {
+    let mut deps = mock_dependencies(&[Coin::new(10_000, DENOM)]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    file_claim(deps.as_mut(), env.clone(), ALICE, native(700));
+    file_claim(deps.as_mut(), env.clone(), BOB, native(500));
+    file_claim(deps.as_mut(), env.clone(), BOB, Asset {
+        info: AssetInfo::Token { contract_addr: "mock_token".to_string() },
+        amount: Uint128::new(100),
+    });
+
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+            ExecuteMsg::ApproveClaim { claim_id: 1, amount: None }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 2, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::BudgetExceeded { remaining: Uint128::new(300) });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 3, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoBudget { asset: "mock_token".to_string() });
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Budget {}).unwrap();
+    let budget: BudgetResponse = from_binary(&res).unwrap();
+    assert_eq!(budget.budgets[0].paid, Uint128::new(700));
+    assert_eq!(budget.budgets[0].remaining, Uint128::new(300));
+
+    // The budget resets with the next period
+    env.block.time = env.block.time.plus_seconds(PERIOD);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]),
+                      ExecuteMsg::ApproveClaim { claim_id: 2, amount: None }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(BOB, 500)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Budget {}).unwrap();
+    let budget: BudgetResponse = from_binary(&res).unwrap();
+    assert_eq!(budget.budgets[0].remaining, Uint128::new(500));
+}
@@ -0,0 +1,29 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use luart_protocol::pausable::PausedResponse;
+use luart_utils::batch::BatchResponse;
+
+use luart_launchpad::msg::{
+    AllocationResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SaleResponse,
+    UserInfoResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(SaleResponse), &out_dir);
+    export_schema(&schema_for!(UserInfoResponse), &out_dir);
+    export_schema(&schema_for!(AllocationResponse), &out_dir);
+    export_schema(&schema_for!(PausedResponse), &out_dir);
+    export_schema(&schema_for!(BatchResponse), &out_dir);
+}
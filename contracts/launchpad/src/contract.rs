@@ -0,0 +1,708 @@
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Fraction, from_binary, MessageInfo, QueryRequest,
+    Response, StdError, StdResult, to_binary, to_vec, Uint128, Uint256, WasmQuery,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U64Key;
+use luart_protocol::pausable::PausedResponse;
+use luart_protocol::registry::{AddressResponse, QueryMsg as RegistryQueryMsg, STAKING};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, TierResponse};
+use luart_types::asset::Asset;
+use luart_utils::batch::batch_query;
+use luart_utils::pausable::{assert_not_paused, is_paused, PAUSE_CONFIG, set_paused, update_pauser};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AllocationResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, KycAttestation, KycPayload, MigrateMsg,
+    PhaseConfig, PhaseResponse, QueryMsg, SaleResponse, UserInfoResponse,
+};
+use crate::state::{
+    Config, CONFIG, Phase, PhaseKind, Sale, SALE_COUNT, SALES, USER_SALES, UserSale, WHITELIST,
+};
+
+/// The length of a compressed secp256k1 public key
+const COMPRESSED_PUBKEY_LENGTH: usize = 33;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-launchpad";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    let registry = match msg.registry {
+        Some(registry) => Some(deps.api.addr_validate(&registry)?),
+        None => None,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        staking_contract: deps.api.addr_validate(&msg.staking_contract)?,
+        payment_denom: msg.payment_denom,
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+        registry,
+        kyc_provider: None,
+    })?;
+    SALE_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(_)
+        | ExecuteMsg::Contribute { .. }
+        | ExecuteMsg::Claim { .. }
+        | ExecuteMsg::WithdrawFunds { .. } => assert_not_paused(deps.storage)?,
+        _ => {}
+    }
+
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Contribute { sale_id, attestation } => contribute(deps, env, info, sale_id, attestation),
+        ExecuteMsg::Claim { sale_id } => claim(deps, env, info, sale_id),
+        ExecuteMsg::WithdrawFunds { sale_id } => withdraw_funds(deps, env, info, sale_id),
+        ExecuteMsg::AddToWhitelist { sale_id, addresses } => {
+            update_whitelist(deps, info, sale_id, addresses, true)
+        }
+        ExecuteMsg::RemoveFromWhitelist { sale_id, addresses } => {
+            update_whitelist(deps, info, sale_id, addresses, false)
+        }
+        ExecuteMsg::UpdateConfig {
+            owner,
+            staking_contract,
+            treasury,
+            registry,
+            kyc_provider,
+        } => update_config(deps, info, owner, staking_contract, treasury, registry, kyc_provider),
+        ExecuteMsg::UpdatePauser { pauser } => execute_update_pauser(deps, info, pauser),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateSale {
+            rate,
+            phases,
+            whitelist_allocation,
+            fcfs_allocation,
+            tier_allocations,
+            kyc_tier,
+            deposit_weights,
+        } => {
+            if config.owner != cw20_msg.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            let sale = Sale {
+                offering_token: info.sender,
+                offering_amount: cw20_msg.amount,
+                rate,
+                phases: phases.into_iter().map(|p: PhaseConfig| Phase {
+                    kind: p.kind,
+                    start: p.start,
+                    end: p.end,
+                    cap: p.cap,
+                    raised: Uint128::zero(),
+                }).collect(),
+                whitelist_allocation,
+                fcfs_allocation,
+                tier_allocations,
+                raised: Uint128::zero(),
+                withdrawn: false,
+                kyc_tier,
+                deposit_weights,
+                tier_deposits: vec![],
+                tier_depositors: vec![],
+            };
+            create_sale(deps, env, sale)
+        }
+    }
+}
+
+pub fn create_sale(
+    deps: DepsMut,
+    env: Env,
+    sale: Sale,
+) -> Result<Response, ContractError> {
+    validate_sale(&env, &sale)?;
+
+    let sale_id = SALE_COUNT.load(deps.storage)? + 1;
+    SALE_COUNT.save(deps.storage, &sale_id)?;
+    SALES.save(deps.storage, U64Key::new(sale_id), &sale)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_sale")
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("offering_token", sale.offering_token)
+        .add_attribute("offering_amount", sale.offering_amount))
+}
+
+fn validate_sale(env: &Env, sale: &Sale) -> Result<(), ContractError> {
+    if sale.offering_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if sale.rate.is_zero() {
+        return Err(ContractError::InvalidSale { reason: "rate must be positive".to_string() });
+    }
+    if sale.phases.is_empty() {
+        return Err(ContractError::InvalidSale { reason: "at least one phase is required".to_string() });
+    }
+
+    let mut last_end = env.block.time.seconds();
+    let mut total_cap = Uint128::zero();
+    for phase in sale.phases.iter() {
+        if phase.start < last_end || phase.end <= phase.start {
+            return Err(ContractError::InvalidSale {
+                reason: "phases must be in the future, ordered and not overlapping".to_string(),
+            });
+        }
+        last_end = phase.end;
+        total_cap = total_cap.checked_add(phase.cap)?;
+    }
+
+    if sale.phases.iter().filter(|p| p.kind == PhaseKind::Deposit).count() > 1 {
+        return Err(ContractError::InvalidSale { reason: "at most one deposit phase is allowed".to_string() });
+    }
+    if sale.deposit_weights.contains(&0) {
+        return Err(ContractError::InvalidSale { reason: "deposit weights must be positive".to_string() });
+    }
+
+    // Every phase could be filled up to its cap, the deposited tokens must cover it. The
+    // product is taken on 256 bits, the admin supplied caps and rate can overflow 128 bits.
+    let sold_amount = total_cap
+        .full_mul(sale.rate.numerator())
+        .checked_div(Uint256::from(sale.rate.denominator()))
+        .map_err(StdError::from)?;
+    if sold_amount > Uint256::from(sale.offering_amount) {
+        return Err(ContractError::InvalidSale {
+            reason: "phase caps exceed the offering amount".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn contribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sale_id: u64,
+    attestation: Option<KycAttestation>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.payment_denom)?;
+
+    let mut sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    if let Some(kyc_tier) = sale.kyc_tier {
+        let attestation = attestation.ok_or(ContractError::KycRequired {})?;
+        verify_kyc_attestation(deps.as_ref(), &env, &config, &info.sender, kyc_tier, &attestation)?;
+    }
+    let phase_index = sale.active_phase(env.block.time.seconds())
+        .ok_or(ContractError::NoActivePhase {})?;
+
+    let mut user_sale = USER_SALES
+        .may_load(deps.storage, (U64Key::new(sale_id), &info.sender))?
+        .unwrap_or_default();
+    user_sale.contributions.resize(sale.phases.len(), Uint128::zero());
+
+    if sale.phases[phase_index].kind == PhaseKind::Deposit {
+        return deposit(deps, &config, sale_id, sale, user_sale, phase_index, &info.sender, amount);
+    }
+
+    let allocation = phase_allocation(deps.as_ref(), &config, sale_id, &sale, phase_index, &info.sender)?;
+    let remaining_allocation = allocation.saturating_sub(user_sale.contributions[phase_index]);
+    let phase = &mut sale.phases[phase_index];
+    let remaining_cap = phase.cap.saturating_sub(phase.raised);
+
+    // Oversubscribed part is refunded right away
+    let accepted = amount.min(remaining_allocation).min(remaining_cap);
+    if accepted.is_zero() {
+        return Err(ContractError::NoAllocation {});
+    }
+    let refund = amount.checked_sub(accepted)?;
+
+    phase.raised = phase.raised.checked_add(accepted)?;
+    sale.raised = sale.raised.checked_add(accepted)?;
+    user_sale.contributions[phase_index] = user_sale.contributions[phase_index].checked_add(accepted)?;
+
+    SALES.save(deps.storage, U64Key::new(sale_id), &sale)?;
+    USER_SALES.save(deps.storage, (U64Key::new(sale_id), &info.sender), &user_sale)?;
+
+    let mut response = Response::new();
+    if !refund.is_zero() {
+        response = response.add_message(Asset::Native(config.payment_denom).transfer_msg(&info.sender, refund)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "contribute")
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("phase", phase_index.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", accepted)
+        .add_attribute("refund", refund))
+}
+
+/// Records the whole amount as a deposit, the allocation being settled on claim
+#[allow(clippy::too_many_arguments)]
+fn deposit(
+    deps: DepsMut,
+    config: &Config,
+    sale_id: u64,
+    mut sale: Sale,
+    mut user_sale: UserSale,
+    phase_index: usize,
+    address: &Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // The weight is set by the first deposit so the totals per weight stay consistent
+    if user_sale.contributions[phase_index].is_zero() {
+        user_sale.weight_index = match sale.deposit_weights.len() {
+            0 | 1 => 0,
+            _ => sale.weight_index(query_tier(deps.as_ref(), &staking_contract(deps.as_ref(), config)?, address)?),
+        };
+        sale.tier_depositors.resize(sale.weights().len(), 0);
+        sale.tier_depositors[user_sale.weight_index] += 1;
+    }
+    sale.tier_deposits.resize(sale.weights().len(), Uint128::zero());
+    sale.tier_deposits[user_sale.weight_index] = sale.tier_deposits[user_sale.weight_index].checked_add(amount)?;
+    sale.phases[phase_index].raised = sale.phases[phase_index].raised.checked_add(amount)?;
+    user_sale.contributions[phase_index] = user_sale.contributions[phase_index].checked_add(amount)?;
+
+    SALES.save(deps.storage, U64Key::new(sale_id), &sale)?;
+    USER_SALES.save(deps.storage, (U64Key::new(sale_id), address), &user_sale)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("phase", phase_index.to_string())
+        .add_attribute("address", address)
+        .add_attribute("amount", amount))
+}
+
+/// Checks the attestation of the address is signed by the KYC provider, unexpired and of a
+/// high enough tier
+fn verify_kyc_attestation(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    address: &Addr,
+    kyc_tier: u8,
+    attestation: &KycAttestation,
+) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidKycAttestation { reason: reason.to_string() };
+    let kyc_provider = config.kyc_provider.as_ref().ok_or(ContractError::KycRequired {})?;
+    if attestation.expiry <= env.block.time.seconds() {
+        return Err(invalid("expired"));
+    }
+    if attestation.tier < kyc_tier {
+        return Err(invalid("tier too low"));
+    }
+
+    let payload = to_vec(&KycPayload {
+        address: address.to_string(),
+        tier: attestation.tier,
+        expiry: attestation.expiry,
+    })?;
+    let verified = deps.api
+        .secp256k1_verify(&Sha256::digest(&payload), &attestation.signature, kyc_provider)
+        .unwrap_or(false);
+    if !verified {
+        return Err(invalid("wrong signature"));
+    }
+
+    Ok(())
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sale_id: u64,
+) -> Result<Response, ContractError> {
+    let sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    if env.block.time.seconds() < sale.end() {
+        return Err(ContractError::SaleNotEnded {});
+    }
+
+    let mut user_sale = USER_SALES
+        .may_load(deps.storage, (U64Key::new(sale_id), &info.sender))?
+        .ok_or(ContractError::NothingToClaim {})?;
+    if user_sale.claimed {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let accepted = sale.accepted_contribution(&user_sale);
+    let amount = accepted * sale.rate;
+    let refund = user_sale.total_contribution().checked_sub(accepted)?;
+    if amount.is_zero() && refund.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    user_sale.claimed = true;
+    USER_SALES.save(deps.storage, (U64Key::new(sale_id), &info.sender), &user_sale)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !amount.is_zero() {
+        messages.push(Asset::Cw20(sale.offering_token).transfer_msg(&info.sender, amount)?);
+    }
+    if !refund.is_zero() {
+        let config = CONFIG.load(deps.storage)?;
+        messages.push(Asset::Native(config.payment_denom).transfer_msg(&info.sender, refund)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim")
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("refund", refund))
+}
+
+pub fn withdraw_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sale_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    if env.block.time.seconds() < sale.end() {
+        return Err(ContractError::SaleNotEnded {});
+    }
+    if sale.withdrawn {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+    sale.withdrawn = true;
+    SALES.save(deps.storage, U64Key::new(sale_id), &sale)?;
+
+    let (deposits_raised, deposits_sold) = sale.allocated_deposits();
+    let raised = sale.raised.checked_add(deposits_raised)?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !raised.is_zero() {
+        messages.push(Asset::Native(config.payment_denom).transfer_msg(&config.treasury, raised)?);
+    }
+
+    let unsold_amount = sale.offering_amount.checked_sub(sale.raised.checked_add(deposits_sold)? * sale.rate)?;
+    if !unsold_amount.is_zero() {
+        messages.push(Asset::Cw20(sale.offering_token).transfer_msg(&config.owner, unsold_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "withdraw_funds")
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("raised", raised)
+        .add_attribute("unsold_amount", unsold_amount))
+}
+
+pub fn update_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    sale_id: u64,
+    addresses: Vec<String>,
+    whitelisted: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // make sure the sale exists
+    SALES.load(deps.storage, U64Key::new(sale_id))?;
+
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if whitelisted {
+            WHITELIST.save(deps.storage, (U64Key::new(sale_id), &address), &true)?;
+        } else {
+            WHITELIST.remove(deps.storage, (U64Key::new(sale_id), &address));
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if whitelisted { "add_to_whitelist" } else { "remove_from_whitelist" })
+        .add_attribute("sale_id", sale_id.to_string())
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    staking_contract: Option<String>,
+    treasury: Option<String>,
+    registry: Option<String>,
+    kyc_provider: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(staking_contract) = staking_contract {
+        config.staking_contract = deps.api.addr_validate(&staking_contract)?;
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = deps.api.addr_validate(&treasury)?;
+    }
+
+    if let Some(registry) = registry {
+        config.registry = Some(deps.api.addr_validate(&registry)?);
+    }
+
+    if let Some(kyc_provider) = kyc_provider {
+        if kyc_provider.len() != COMPRESSED_PUBKEY_LENGTH {
+            return Err(StdError::generic_err("The KYC provider key must be a compressed secp256k1 public key").into());
+        }
+        config.kyc_provider = Some(kyc_provider);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_update_pauser(
+    deps: DepsMut,
+    info: MessageInfo,
+    pauser: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pauser = match pauser {
+        Some(pauser) => Some(deps.api.addr_validate(&pauser)?),
+        None => None,
+    };
+    update_pauser(deps.storage, pauser)?;
+
+    Ok(Response::new().add_attribute("action", "update_pauser"))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    set_paused(deps.storage, &info.sender, paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Sale { sale_id } => to_binary(&query_sale(deps, env, sale_id)?),
+        QueryMsg::UserInfo { sale_id, address } => {
+            to_binary(&query_user_info(deps, env, sale_id, address)?)
+        }
+        QueryMsg::Allocation { sale_id, address } => to_binary(&query_allocation(deps, sale_id, address)?),
+        QueryMsg::Paused {} => to_binary(&PausedResponse { paused: is_paused(deps.storage)? }),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        staking_contract: config.staking_contract.to_string(),
+        payment_denom: config.payment_denom,
+        treasury: config.treasury.to_string(),
+        registry: config.registry.map(|r| r.to_string()),
+        pauser: PAUSE_CONFIG.may_load(deps.storage)?.and_then(|c| c.pauser).map(|p| p.to_string()),
+        kyc_provider: config.kyc_provider,
+    })
+}
+
+pub fn query_sale(deps: Deps, env: Env, sale_id: u64) -> StdResult<SaleResponse> {
+    let sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    let active_phase = sale.active_phase(env.block.time.seconds()).map(|i| i as u32);
+    Ok(SaleResponse {
+        sale_id,
+        offering_token: sale.offering_token.to_string(),
+        offering_amount: sale.offering_amount,
+        rate: sale.rate,
+        phases: sale.phases.into_iter().map(|p| PhaseResponse {
+            kind: p.kind,
+            start: p.start,
+            end: p.end,
+            cap: p.cap,
+            raised: p.raised,
+        }).collect(),
+        whitelist_allocation: sale.whitelist_allocation,
+        fcfs_allocation: sale.fcfs_allocation,
+        tier_allocations: sale.tier_allocations,
+        raised: sale.raised,
+        active_phase,
+        kyc_tier: sale.kyc_tier,
+        deposit_weights: sale.deposit_weights,
+    })
+}
+
+pub fn query_user_info(
+    deps: Deps,
+    env: Env,
+    sale_id: u64,
+    address: String,
+) -> StdResult<UserInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    let mut user_sale = USER_SALES
+        .may_load(deps.storage, (U64Key::new(sale_id), &address))?
+        .unwrap_or_default();
+    user_sale.contributions.resize(sale.phases.len(), Uint128::zero());
+
+    let remaining_allocation = match sale.active_phase(env.block.time.seconds()) {
+        Some(phase_index) if sale.phases[phase_index].kind == PhaseKind::Deposit => Uint128::MAX,
+        Some(phase_index) => {
+            let phase = &sale.phases[phase_index];
+            phase_allocation(deps, &config, sale_id, &sale, phase_index, &address)?
+                .saturating_sub(user_sale.contributions[phase_index])
+                .min(phase.cap.saturating_sub(phase.raised))
+        }
+        None => Uint128::zero(),
+    };
+
+    Ok(UserInfoResponse {
+        purchased_amount: sale.accepted_contribution(&user_sale) * sale.rate,
+        contributions: user_sale.contributions,
+        remaining_allocation,
+        claimed: user_sale.claimed,
+    })
+}
+
+pub fn query_allocation(deps: Deps, sale_id: u64, address: String) -> StdResult<AllocationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let sale = SALES.load(deps.storage, U64Key::new(sale_id))?;
+    let user_sale = USER_SALES
+        .may_load(deps.storage, (U64Key::new(sale_id), &address))?
+        .unwrap_or_default();
+
+    let deposit = sale.deposit_phase()
+        .and_then(|phase_index| user_sale.contributions.get(phase_index).copied())
+        .unwrap_or_default();
+    let allocation = deposit * sale.fill_ratios()[user_sale.weight_index];
+    Ok(AllocationResponse {
+        deposit,
+        allocation,
+        refund: deposit.checked_sub(allocation)?,
+        claimed: user_sale.claimed,
+    })
+}
+
+/// Returns the maximum amount the address can contribute in the given phase
+fn phase_allocation(
+    deps: Deps,
+    config: &Config,
+    sale_id: u64,
+    sale: &Sale,
+    phase_index: usize,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    Ok(match sale.phases[phase_index].kind {
+        PhaseKind::Whitelist => {
+            let whitelisted = WHITELIST
+                .may_load(deps.storage, (U64Key::new(sale_id), address))?
+                .unwrap_or(false);
+            if whitelisted { sale.whitelist_allocation } else { Uint128::zero() }
+        }
+        PhaseKind::Guaranteed => {
+            let tier = query_tier(deps, &staking_contract(deps, config)?, address)?;
+            match tier {
+                0 => Uint128::zero(),
+                tier => sale.tier_allocations
+                    .get(tier as usize - 1)
+                    .or_else(|| sale.tier_allocations.last())
+                    .copied()
+                    .unwrap_or_default(),
+            }
+        }
+        PhaseKind::Fcfs => sale.fcfs_allocation,
+        PhaseKind::Deposit => Uint128::MAX,
+    })
+}
+
+/// Returns the staking contract registered in the registry, or the configured one without registry
+fn staking_contract(deps: Deps, config: &Config) -> StdResult<Addr> {
+    match &config.registry {
+        Some(registry) => {
+            let res: AddressResponse = deps.querier.query_wasm_smart(registry, &RegistryQueryMsg::Address {
+                name: STAKING.to_string(),
+            })?;
+            Ok(res.address)
+        }
+        None => Ok(config.staking_contract.clone()),
+    }
+}
+
+fn query_tier(deps: Deps, staking_contract: &Addr, address: &Addr) -> StdResult<u8> {
+    let res: TierResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: staking_contract.to_string(),
+        msg: to_binary(&StakingQueryMsg::Tier {
+            address: address.to_string(),
+        })?,
+    }))?;
+    Ok(res.tier)
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
@@ -0,0 +1,58 @@
+use cosmwasm_std::{OverflowError, StdError};
+use luart_utils::pausable::PauseError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Invalid sale configuration: {reason}")]
+    InvalidSale { reason: String },
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("No active phase")]
+    NoActivePhase {},
+
+    #[error("No allocation left in the current phase")]
+    NoAllocation {},
+
+    #[error("The sale has not ended yet")]
+    SaleNotEnded {},
+
+    #[error("Already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("The sale requires a KYC attestation")]
+    KycRequired {},
+
+    #[error("Invalid KYC attestation: {reason}")]
+    InvalidKycAttestation { reason: String },
+
+    #[error("The launchpad is paused")]
+    Paused {},
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Unauthorized {} => ContractError::Unauthorized {},
+            PauseError::Paused {} => ContractError::Paused {},
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::PhaseKind;
+
+/// This structure describes the parameters used for creating a launchpad contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The staking contract queried for participant tiers
+    pub staking_contract: String,
+    /// The native denom contributions are paid in
+    pub payment_denom: String,
+    /// The address receiving the raised funds
+    pub treasury: String,
+    /// The registry the staking contract is resolved through, replacing `staking_contract`
+    pub registry: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the offering tokens of a new sale, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Contributes the sent payment funds to the active phase of the sale.
+    /// Funds above the sender allocation or the phase cap are refunded.
+    /// The sales requiring KYC need an attestation of the sender.
+    Contribute {
+        sale_id: u64,
+        attestation: Option<KycAttestation>,
+    },
+    /// Transfers the purchased tokens and refunds the unallocated deposit to the sender once
+    /// the sale has ended
+    Claim { sale_id: u64 },
+    /// Transfers the raised funds to the treasury and unsold tokens back to the owner
+    /// once the sale has ended. Only the owner can execute it.
+    WithdrawFunds { sale_id: u64 },
+    /// Adds addresses to the sale whitelist. Only the owner can execute it.
+    AddToWhitelist { sale_id: u64, addresses: Vec<String> },
+    /// Removes addresses from the sale whitelist. Only the owner can execute it.
+    RemoveFromWhitelist { sale_id: u64, addresses: Vec<String> },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        staking_contract: Option<String>,
+        treasury: Option<String>,
+        registry: Option<String>,
+        kyc_provider: Option<Binary>,
+    },
+    /// Sets or removes the pauser. Only the owner can execute it.
+    UpdatePauser { pauser: Option<String> },
+    /// Stops the sales, contributions, claims and withdrawals. Only the pauser can execute it.
+    Pause {},
+    /// Resumes the paused launchpad. Only the pauser can execute it.
+    Unpause {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates a sale offering the sent tokens. Only the owner can send it.
+    CreateSale {
+        /// The amount of offering tokens received per payment token
+        rate: Decimal,
+        /// Sale phases ordered by time, they must not overlap
+        phases: Vec<PhaseConfig>,
+        whitelist_allocation: Uint128,
+        fcfs_allocation: Uint128,
+        /// Guaranteed phase allocations indexed by tier starting at tier 1
+        tier_allocations: Vec<Uint128>,
+        /// The minimum tier attested by the KYC provider to contribute, none for no KYC
+        kyc_tier: Option<u8>,
+        /// Deposit phase allocation weights indexed by tier starting at tier 0, the higher tiers
+        /// use the last weight. Every depositor weighs the same if empty.
+        #[serde(default)]
+        deposit_weights: Vec<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KycAttestation {
+    pub tier: u8,
+    /// The timestamp in seconds after which the attestation is not valid anymore
+    pub expiry: u64,
+    /// The signature of the sha256 hash of the JSON encoded [`KycPayload`] by the KYC provider
+    pub signature: Binary,
+}
+
+/// The message signed by the KYC provider for a verified address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KycPayload {
+    pub address: String,
+    pub tier: u8,
+    pub expiry: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PhaseConfig {
+    pub kind: PhaseKind,
+    pub start: u64,
+    pub end: u64,
+    /// The maximum amount of payment tokens raised in this phase
+    pub cap: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the sale configuration and progress.
+    /// Return type: SaleResponse.
+    Sale { sale_id: u64 },
+    /// Returns the user contribution and remaining allocation in the active phase.
+    /// Return type: UserInfoResponse.
+    UserInfo { sale_id: u64, address: String },
+    /// Returns the user deposit in the deposit phase and its allocation, projected from the
+    /// current deposits until the phase ends.
+    /// Return type: AllocationResponse.
+    Allocation { sale_id: u64, address: String },
+    /// Returns whether the launchpad is paused.
+    /// Return type: PausedResponse.
+    Paused {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub staking_contract: String,
+    pub payment_denom: String,
+    pub treasury: String,
+    pub registry: Option<String>,
+    pub pauser: Option<String>,
+    pub kyc_provider: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PhaseResponse {
+    pub kind: PhaseKind,
+    pub start: u64,
+    pub end: u64,
+    pub cap: Uint128,
+    pub raised: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleResponse {
+    pub sale_id: u64,
+    pub offering_token: String,
+    pub offering_amount: Uint128,
+    pub rate: Decimal,
+    pub phases: Vec<PhaseResponse>,
+    pub whitelist_allocation: Uint128,
+    pub fcfs_allocation: Uint128,
+    pub tier_allocations: Vec<Uint128>,
+    pub raised: Uint128,
+    pub active_phase: Option<u32>,
+    pub kyc_tier: Option<u8>,
+    pub deposit_weights: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserInfoResponse {
+    /// The user contributions per phase
+    pub contributions: Vec<Uint128>,
+    /// The amount the user can still contribute in the active phase, unlimited in a deposit phase
+    pub remaining_allocation: Uint128,
+    /// The amount of offering tokens purchased
+    pub purchased_amount: Uint128,
+    pub claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllocationResponse {
+    /// The payment tokens deposited
+    pub deposit: Uint128,
+    /// The part of the deposit paying for offering tokens
+    pub allocation: Uint128,
+    /// The part of the deposit refunded on claim
+    pub refund: Uint128,
+    pub claimed: bool,
+}
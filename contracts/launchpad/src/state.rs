@@ -0,0 +1,193 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can create sales and manage whitelists
+    pub owner: Addr,
+    /// The staking contract queried for participant tiers
+    pub staking_contract: Addr,
+    /// The native denom contributions are paid in
+    pub payment_denom: String,
+    /// The address receiving the raised funds
+    pub treasury: Addr,
+    /// The registry the staking contract is resolved through, replacing `staking_contract`
+    #[serde(default)]
+    pub registry: Option<Addr>,
+    /// The compressed secp256k1 public key of the KYC provider signing the attestations
+    #[serde(default)]
+    pub kyc_provider: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseKind {
+    /// Only whitelisted addresses can contribute up to the whitelist allocation
+    Whitelist,
+    /// Stakers can contribute up to the allocation of their staking tier
+    Guaranteed,
+    /// Anyone can contribute up to the FCFS allocation while the phase cap lasts
+    Fcfs,
+    /// Anyone can deposit without limit, the phase cap is allocated by tier weight at the end of
+    /// the phase and the excess deposits are refunded
+    Deposit,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Phase {
+    pub kind: PhaseKind,
+    /// Phase start timestamp in seconds
+    pub start: u64,
+    /// Phase end timestamp in seconds
+    pub end: u64,
+    /// The maximum amount of payment tokens raised in this phase
+    pub cap: Uint128,
+    /// The amount of payment tokens raised in this phase, the total deposits in a deposit phase
+    pub raised: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Sale {
+    /// The cw20 token being sold
+    pub offering_token: Addr,
+    /// The amount of tokens deposited for the sale
+    pub offering_amount: Uint128,
+    /// The amount of offering tokens received per payment token
+    pub rate: Decimal,
+    /// Sale phases ordered by time
+    pub phases: Vec<Phase>,
+    /// Per-user allocation in payment tokens of the whitelist phase
+    pub whitelist_allocation: Uint128,
+    /// Per-user allocation in payment tokens of the FCFS phase
+    pub fcfs_allocation: Uint128,
+    /// Per-user allocation in payment tokens of the guaranteed phase, indexed by tier starting at tier 1
+    pub tier_allocations: Vec<Uint128>,
+    /// The total amount of payment tokens raised, the deposit phase excluded
+    pub raised: Uint128,
+    /// Whether the raised funds and unsold tokens were withdrawn
+    pub withdrawn: bool,
+    /// The minimum tier attested by the KYC provider to contribute, none if the sale needs no KYC
+    #[serde(default)]
+    pub kyc_tier: Option<u8>,
+    /// The allocation weights of the deposit phase indexed by tier starting at tier 0, the higher
+    /// tiers use the last weight. Every depositor weighs the same if empty.
+    #[serde(default)]
+    pub deposit_weights: Vec<u64>,
+    /// The total deposits per deposit weight
+    #[serde(default)]
+    pub tier_deposits: Vec<Uint128>,
+    /// The number of depositors per deposit weight
+    #[serde(default)]
+    pub tier_depositors: Vec<u64>,
+}
+
+impl Sale {
+    pub fn end(&self) -> u64 {
+        self.phases.last().map(|p| p.end).unwrap_or_default()
+    }
+
+    pub fn active_phase(&self, time: u64) -> Option<usize> {
+        self.phases.iter().position(|p| p.start <= time && time < p.end)
+    }
+
+    /// The index of the deposit phase, a sale has at most one
+    pub fn deposit_phase(&self) -> Option<usize> {
+        self.phases.iter().position(|p| p.kind == PhaseKind::Deposit)
+    }
+
+    /// The deposit weights, a single weight if unset
+    pub fn weights(&self) -> Vec<u64> {
+        if self.deposit_weights.is_empty() { vec![1] } else { self.deposit_weights.clone() }
+    }
+
+    /// The index of the deposit weight of the tier
+    pub fn weight_index(&self, tier: u8) -> usize {
+        (tier as usize).min(self.weights().len() - 1)
+    }
+
+    /// The share of the deposits of each weight allocated. The cap is shared in proportion to
+    /// the weighted deposits, the deposits of the heaviest weights being filled first when their
+    /// share exceeds them.
+    pub fn fill_ratios(&self) -> Vec<Decimal> {
+        let weights = self.weights();
+        let mut ratios = vec![Decimal::one(); weights.len()];
+        let phase = match self.deposit_phase() {
+            Some(phase_index) => &self.phases[phase_index],
+            None => return ratios,
+        };
+        if phase.raised <= phase.cap {
+            return ratios;
+        }
+
+        let deposits = |index: usize| self.tier_deposits.get(index).copied().unwrap_or_default();
+        let mut remaining_cap = phase.cap;
+        let mut remaining_weighted = (0..weights.len())
+            .fold(Uint128::zero(), |acc, index| acc + deposits(index) * Uint128::from(weights[index]));
+        let mut order: Vec<usize> = (0..weights.len()).collect();
+        order.sort_by(|a, b| weights[*b].cmp(&weights[*a]));
+        let mut filled = true;
+        for index in order {
+            let weighted = deposits(index) * Uint128::from(weights[index]);
+            filled = filled && remaining_cap * Uint128::from(weights[index]) >= remaining_weighted;
+            if filled {
+                remaining_cap = remaining_cap.saturating_sub(deposits(index));
+                remaining_weighted = remaining_weighted.saturating_sub(weighted);
+            } else if !remaining_weighted.is_zero() {
+                ratios[index] = Decimal::from_ratio(remaining_cap * Uint128::from(weights[index]), remaining_weighted);
+            }
+        }
+
+        ratios
+    }
+
+    /// The deposits allocated as (funds raised, payment sold) amounts. The allocations are
+    /// rounded down, so the funds raised leave one unit per depositor of a partially filled
+    /// weight in the contract to cover the rounding of their refunds.
+    pub fn allocated_deposits(&self) -> (Uint128, Uint128) {
+        let mut raised = Uint128::zero();
+        let mut sold = Uint128::zero();
+        for (index, ratio) in self.fill_ratios().into_iter().enumerate() {
+            let allocated = self.tier_deposits.get(index).copied().unwrap_or_default() * ratio;
+            let depositors = self.tier_depositors.get(index).copied().unwrap_or_default();
+            sold += allocated;
+            raised += if ratio == Decimal::one() { allocated } else { allocated.saturating_sub(depositors.into()) };
+        }
+        (raised, sold)
+    }
+
+    /// The part of the contributions of the user paid for tokens, the deposit counting for its
+    /// allocation
+    pub fn accepted_contribution(&self, user_sale: &UserSale) -> Uint128 {
+        let deposit = self.deposit_phase()
+            .and_then(|phase_index| user_sale.contributions.get(phase_index).copied())
+            .unwrap_or_default();
+        let allocation = deposit * self.fill_ratios()[user_sale.weight_index];
+        user_sale.total_contribution() - deposit + allocation
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct UserSale {
+    /// Contributions of the user per sale phase
+    pub contributions: Vec<Uint128>,
+    /// Whether the user claimed the purchased tokens
+    pub claimed: bool,
+    /// The index of the deposit weight of the user, set by its first deposit
+    #[serde(default)]
+    pub weight_index: usize,
+}
+
+impl UserSale {
+    pub fn total_contribution(&self) -> Uint128 {
+        self.contributions.iter().fold(Uint128::zero(), |acc, c| acc + *c)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const SALE_COUNT: Item<u64> = Item::new("sale_count");
+pub const SALES: Map<U64Key, Sale> = Map::new("sales");
+pub const WHITELIST: Map<(U64Key, &Addr), bool> = Map::new("whitelist");
+pub const USER_SALES: Map<(U64Key, &Addr), UserSale> = Map::new("user_sales");
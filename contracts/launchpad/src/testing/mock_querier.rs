@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Addr, Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, StdError, SystemError, SystemResult, to_binary, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use luart_protocol::registry::{AddressResponse, QueryMsg as RegistryQueryMsg};
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, TierResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the staking tier and registry queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    tiers: HashMap<String, u8>,
+    registered: HashMap<String, String>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr: _, msg }) => {
+                if let Ok(RegistryQueryMsg::Address { name }) = from_binary(msg) {
+                    let res = match self.registered.get(&name) {
+                        Some(address) => to_binary(&AddressResponse { name, address: Addr::unchecked(address) }),
+                        None => Err(StdError::generic_err(format!("No address registered as {}", name))),
+                    };
+                    return SystemResult::Ok(ContractResult::from(res));
+                }
+                match from_binary(msg) {
+                    Ok(StakingQueryMsg::Tier { address }) => {
+                        let tier = self.tiers.get(&address).copied().unwrap_or_default();
+                        SystemResult::Ok(ContractResult::from(to_binary(&TierResponse { tier })))
+                    }
+                    Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported staking query".to_string(),
+                        request: msg.clone(),
+                    }),
+                    Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                        error: format!("Parsing staking query: {}", e),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            tiers: HashMap::new(),
+            registered: HashMap::new(),
+        }
+    }
+
+    // configure the staking tier of an address
+    pub fn with_tier(&mut self, address: &str, tier: u8) {
+        self.tiers.insert(address.to_string(), tier);
+    }
+
+    // configure an address of the registry
+    pub fn with_registered(&mut self, name: &str, address: &str) {
+        self.registered.insert(name.to_string(), address.to_string());
+    }
+}
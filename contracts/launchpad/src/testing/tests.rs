@@ -0,0 +1,426 @@
+use cosmwasm_std::{
+    BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, Response, StdError, SubMsg, to_binary,
+    to_vec, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use k256::ecdsa::{Signature, SigningKey};
+use k256::ecdsa::signature::Signer;
+use luart_protocol::pausable::PausedResponse;
+use luart_protocol::registry::STAKING as STAKING_NAME;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    AllocationResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, KycAttestation, KycPayload, PhaseConfig, QueryMsg,
+    UserInfoResponse,
+};
+use crate::state::PhaseKind;
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const STAKING: &str = "mock_staking";
+const TREASURY: &str = "mock_treasury";
+const USER: &str = "mock_user";
+const DENOM: &str = "uusd";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        staking_contract: STAKING.to_string(),
+        payment_denom: DENOM.to_string(),
+        treasury: TREASURY.to_string(),
+        registry: None,
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+/// Creates a sale with whitelist, guaranteed and FCFS phases lasting 100 seconds each,
+/// starting 100 seconds after the current block
+fn create_sale(deps: DepsMut, env: Env) -> Response {
+    let now = env.block.time.seconds();
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: OWNER.to_string(),
+                amount: Uint128::new(50_000),
+                msg: to_binary(&Cw20HookMsg::CreateSale {
+                    rate: Decimal::from_ratio(10u128, 1u128),
+                    phases: vec![
+                        PhaseConfig { kind: PhaseKind::Whitelist, start: now + 100, end: now + 200, cap: Uint128::new(1_000) },
+                        PhaseConfig { kind: PhaseKind::Guaranteed, start: now + 200, end: now + 300, cap: Uint128::new(2_000) },
+                        PhaseConfig { kind: PhaseKind::Fcfs, start: now + 300, end: now + 400, cap: Uint128::new(1_000) },
+                    ],
+                    whitelist_allocation: Uint128::new(500),
+                    fcfs_allocation: Uint128::new(2_000),
+                    tier_allocations: vec![Uint128::new(300), Uint128::new(800)],
+                    kyc_tier: None,
+                    deposit_weights: vec![],
+                }).unwrap(),
+            })).unwrap()
+}
+
+fn refund_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_create_sale_validation() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let now = env.block.time.seconds();
+
+    // The sold amount of caps overflowing 128 bits is rejected as well
+    for cap in [Uint128::new(1_000), Uint128::MAX] {
+        let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                          ExecuteMsg::Receive(Cw20ReceiveMsg {
+                              sender: OWNER.to_string(),
+                              amount: Uint128::new(1_000),
+                              msg: to_binary(&Cw20HookMsg::CreateSale {
+                                  rate: Decimal::from_ratio(10u128, 1u128),
+                                  phases: vec![
+                                      PhaseConfig { kind: PhaseKind::Fcfs, start: now + 100, end: now + 200, cap },
+                                  ],
+                                  whitelist_allocation: Uint128::zero(),
+                                  fcfs_allocation: Uint128::new(100),
+                                  tier_allocations: vec![],
+                                  kyc_tier: None,
+                                  deposit_weights: vec![],
+                              }).unwrap(),
+                          })).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSale { reason: "phase caps exceed the offering amount".to_string() });
+    }
+
+    let err = execute(deps.as_mut(), env, mock_info(TOKEN, &[]),
+                      ExecuteMsg::Receive(Cw20ReceiveMsg {
+                          sender: USER.to_string(),
+                          amount: Uint128::new(1_000),
+                          msg: to_binary(&Cw20HookMsg::CreateSale {
+                              rate: Decimal::one(),
+                              phases: vec![],
+                              whitelist_allocation: Uint128::zero(),
+                              fcfs_allocation: Uint128::zero(),
+                              tier_allocations: vec![],
+                              kyc_tier: None,
+                              deposit_weights: vec![],
+                          }).unwrap(),
+                      })).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_contribute_phases() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_sale(deps.as_mut(), env.clone());
+    deps.querier.with_tier(USER, 2);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoActivePhase {});
+
+    // Whitelist phase
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoAllocation {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::AddToWhitelist { sale_id: 1, addresses: vec![USER.to_string()] }).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(600, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 100)]);
+
+    // Guaranteed phase uses the staking tier allocation
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("no_tier", &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoAllocation {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(100, "uluna")]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: DENOM.to_string() });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 200)]);
+
+    // FCFS phase is capped by the phase cap
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::UserInfo {
+        sale_id: 1,
+        address: USER.to_string(),
+    }).unwrap();
+    let user_info: UserInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(user_info, UserInfoResponse {
+        contributions: vec![Uint128::new(500), Uint128::new(800), Uint128::zero()],
+        remaining_allocation: Uint128::new(1_000),
+        purchased_amount: Uint128::new(13_000),
+        claimed: false,
+    });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_500, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 500)]);
+
+    let err = execute(deps.as_mut(), env, mock_info("another", &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoAllocation {});
+}
+
+#[test]
+fn test_staking_from_registry() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_sale(deps.as_mut(), env.clone());
+    deps.querier.with_tier(USER, 1);
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking_contract: None,
+        treasury: None,
+        registry: Some("mock_registry".to_string()),
+        kyc_provider: None,
+    }).unwrap();
+
+    // The tiers are read from the staking contract registered in the registry
+    env.block.time = env.block.time.plus_seconds(200);
+    let contribute = ExecuteMsg::Contribute { sale_id: 1, attestation: None };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(100, DENOM)]), contribute.clone())
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+
+    deps.querier.with_registered(STAKING_NAME, "mock_staking_v2");
+    let res = execute(deps.as_mut(), env, mock_info(USER, &[Coin::new(500, DENOM)]), contribute).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 200)]);
+}
+
+#[test]
+fn test_claim_and_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_sale(deps.as_mut(), env.clone());
+
+    env.block.time = env.block.time.plus_seconds(300);
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(400, DENOM)]),
+            ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { sale_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::SaleNotEnded {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { sale_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(USER, 4_000)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { sale_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyClaimed {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::WithdrawFunds { sale_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::WithdrawFunds { sale_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![
+        refund_msg(TREASURY, 400),
+        transfer_msg(OWNER, 46_000),
+    ]);
+}
+
+#[test]
+fn test_deposit_phase() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    deps.querier.with_tier(USER, 1);
+    let now = env.block.time.seconds();
+    let create_sale = |phases: Vec<PhaseConfig>| ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: OWNER.to_string(),
+        amount: Uint128::new(12_000),
+        msg: to_binary(&Cw20HookMsg::CreateSale {
+            rate: Decimal::from_ratio(10u128, 1u128),
+            phases,
+            whitelist_allocation: Uint128::zero(),
+            fcfs_allocation: Uint128::zero(),
+            tier_allocations: vec![],
+            kyc_tier: None,
+            deposit_weights: vec![1, 3],
+        }).unwrap(),
+    });
+    let deposit_phase = |start: u64, end: u64| PhaseConfig {
+        kind: PhaseKind::Deposit,
+        start,
+        end,
+        cap: Uint128::new(500),
+    };
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      create_sale(vec![deposit_phase(now + 100, now + 200), deposit_phase(now + 200, now + 300)]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSale { reason: "at most one deposit phase is allowed".to_string() });
+    let phase = PhaseConfig { cap: Uint128::new(1_000), ..deposit_phase(now + 100, now + 200) };
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), create_sale(vec![phase])).unwrap();
+
+    // Deposits are unlimited and kept whole
+    env.block.time = env.block.time.plus_seconds(100);
+    let contribute = ExecuteMsg::Contribute { sale_id: 1, attestation: None };
+    let deposits = [(USER, 300), ("mock_other_user", 600), ("mock_other_user", 400), ("mock_third_user", 400)];
+    for (address, amount) in deposits {
+        let res = execute(deps.as_mut(), env.clone(), mock_info(address, &[Coin::new(amount, DENOM)]),
+                          contribute.clone()).unwrap();
+        assert!(res.messages.is_empty());
+    }
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::UserInfo { sale_id: 1, address: USER.to_string() }).unwrap();
+    let user_info: UserInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(user_info.remaining_allocation, Uint128::MAX);
+
+    // The tier 1 deposits weigh three times more and are filled first, the rest of the cap is
+    // shared by the tier 0 deposits
+    let allocation = |deps: Deps, address: &str| -> AllocationResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::Allocation { sale_id: 1, address: address.to_string() })
+            .unwrap()).unwrap()
+    };
+    assert_eq!(allocation(deps.as_ref(), USER), AllocationResponse {
+        deposit: Uint128::new(300),
+        allocation: Uint128::new(300),
+        refund: Uint128::zero(),
+        claimed: false,
+    });
+    assert_eq!(allocation(deps.as_ref(), "mock_other_user"), AllocationResponse {
+        deposit: Uint128::new(1_000),
+        allocation: Uint128::new(500),
+        refund: Uint128::new(500),
+        claimed: false,
+    });
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), ExecuteMsg::Claim { sale_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(USER, 3_000)]);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("mock_other_user", &[]), ExecuteMsg::Claim { sale_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("mock_other_user", 5_000), refund_msg("mock_other_user", 500)]);
+    assert!(allocation(deps.as_ref(), "mock_other_user").claimed);
+
+    // One unit per partially filled depositor stays in the contract to cover the rounding
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]), ExecuteMsg::WithdrawFunds { sale_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(TREASURY, 998), transfer_msg(OWNER, 2_000)]);
+}
+
+#[test]
+fn test_pause() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let update_pauser = ExecuteMsg::UpdatePauser { pauser: Some("mock_council".to_string()) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), update_pauser.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_pauser).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("mock_council", &[]), ExecuteMsg::Pause {}).unwrap();
+
+    let res: PausedResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Paused {}).unwrap()).unwrap();
+    assert_eq!(res, PausedResponse { paused: true });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute { sale_id: 1, attestation: None }).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("mock_council", &[]), ExecuteMsg::Unpause {}).unwrap();
+    let res: PausedResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Paused {}).unwrap()).unwrap();
+    assert_eq!(res, PausedResponse { paused: false });
+}
+
+// The attestation as signed by the KYC provider off-chain
+fn kyc_attestation(key: &SigningKey, address: &str, tier: u8, expiry: u64) -> KycAttestation {
+    let payload = to_vec(&KycPayload { address: address.to_string(), tier, expiry }).unwrap();
+    let signature: Signature = key.sign(&payload);
+    KycAttestation { tier, expiry, signature: Binary::from(signature.as_ref()) }
+}
+
+#[test]
+fn test_kyc_attestation() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let provider = SigningKey::from_bytes(&[1; 32]).unwrap();
+    let impostor = SigningKey::from_bytes(&[2; 32]).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking_contract: None,
+        treasury: None,
+        registry: None,
+        kyc_provider: Some(Binary::from(provider.verifying_key().to_bytes().as_slice())),
+    }).unwrap();
+
+    let now = env.block.time.seconds();
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: OWNER.to_string(),
+        amount: Uint128::new(10_000),
+        msg: to_binary(&Cw20HookMsg::CreateSale {
+            rate: Decimal::one(),
+            phases: vec![
+                PhaseConfig { kind: PhaseKind::Fcfs, start: now + 100, end: now + 200, cap: Uint128::new(1_000) },
+            ],
+            whitelist_allocation: Uint128::zero(),
+            fcfs_allocation: Uint128::new(1_000),
+            tier_allocations: vec![],
+            kyc_tier: Some(2),
+            deposit_weights: vec![],
+        }).unwrap(),
+    })).unwrap();
+    env.block.time = env.block.time.plus_seconds(100);
+    let expiry = env.block.time.seconds() + 1_000;
+
+    let contribute = |attestation: Option<KycAttestation>| ExecuteMsg::Contribute { sale_id: 1, attestation };
+    let funds = [Coin::new(100, DENOM)];
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &funds), contribute(None)).unwrap_err();
+    assert_eq!(err, ContractError::KycRequired {});
+
+    // The attestation must be signed by the provider for the sender, unexpired and of the sale tier
+    let invalid = |reason: &str| ContractError::InvalidKycAttestation { reason: reason.to_string() };
+    let attestation = kyc_attestation(&impostor, USER, 2, expiry);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &funds), contribute(Some(attestation)))
+        .unwrap_err();
+    assert_eq!(err, invalid("wrong signature"));
+    let attestation = kyc_attestation(&provider, "mock_other_user", 2, expiry);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &funds), contribute(Some(attestation)))
+        .unwrap_err();
+    assert_eq!(err, invalid("wrong signature"));
+    let attestation = kyc_attestation(&provider, USER, 1, expiry);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &funds), contribute(Some(attestation)))
+        .unwrap_err();
+    assert_eq!(err, invalid("tier too low"));
+    let attestation = kyc_attestation(&provider, USER, 2, env.block.time.seconds());
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &funds), contribute(Some(attestation)))
+        .unwrap_err();
+    assert_eq!(err, invalid("expired"));
+
+    let attestation = kyc_attestation(&provider, USER, 3, expiry);
+    execute(deps.as_mut(), env, mock_info(USER, &funds), contribute(Some(attestation))).unwrap();
+}
@@ -0,0 +1,480 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, coins, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo,
+    Order, Response, StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, LoanResponse,
+    LoansResponse, MigrateMsg, OfferResponse, OffersResponse, QueryMsg,
+};
+use crate::state::{
+    Config, CONFIG, Loan, LOAN_COUNT, LOAN_OFFERS, LoanCurrency, LOANS, LoanStatus,
+    MAX_INTEREST_RATE_PERCENT, MAX_TERM, Offer, OFFER_COUNT, OFFERS, OfferStatus,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-lending";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(deps.storage, &Config {
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+        payment_denom: msg.payment_denom,
+    })?;
+    LOAN_COUNT.save(deps.storage, &0u64)?;
+    OFFER_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => receive_cw721(deps, info, msg),
+        ExecuteMsg::MakeOffer { loan_id, interest_rate, term } => {
+            let principal = native_payment(deps.as_ref(), &info)?;
+            make_offer(deps, info.sender, loan_id, LoanCurrency::Native, principal, interest_rate, term)
+        }
+        ExecuteMsg::CancelOffer { offer_id } => cancel_offer(deps, info, offer_id),
+        ExecuteMsg::AcceptOffer { offer_id } => accept_offer(deps, env, info, offer_id),
+        ExecuteMsg::Repay { loan_id } => {
+            let paid = native_payment(deps.as_ref(), &info)?;
+            repay(deps, env, loan_id, LoanCurrency::Native, paid)
+        }
+        ExecuteMsg::WithdrawCollateral { loan_id } => withdraw_collateral(deps, info, loan_id),
+        ExecuteMsg::ClaimCollateral { loan_id } => claim_collateral(deps, env, info, loan_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// Returns the amount of payment denom sent, zero if other coins are sent
+fn native_payment(deps: Deps, info: &MessageInfo) -> StdResult<Uint128> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(match info.funds.as_slice() {
+        [coin] if coin.denom == config.payment_denom => coin.amount,
+        _ => Uint128::zero(),
+    })
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::MakeOffer { loan_id, interest_rate, term } => {
+            make_offer(deps, sender, loan_id, LoanCurrency::Luart, cw20_msg.amount, interest_rate, term)
+        }
+        Cw20HookMsg::Repay { loan_id } => repay(deps, env, loan_id, LoanCurrency::Luart, cw20_msg.amount),
+    }
+}
+
+pub fn receive_cw721(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw721_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw721_msg.msg)? {
+        Cw721HookMsg::ListCollateral {} => {
+            let borrower = deps.api.addr_validate(&cw721_msg.sender)?;
+            let loan_id = LOAN_COUNT.load(deps.storage)? + 1;
+            LOAN_COUNT.save(deps.storage, &loan_id)?;
+            LOANS.save(deps.storage, U64Key::new(loan_id), &Loan {
+                borrower: borrower.clone(),
+                nft_contract: info.sender.clone(),
+                token_id: cw721_msg.token_id.clone(),
+                status: LoanStatus::Listed,
+                offer_id: None,
+                start_time: None,
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("action", "list_collateral")
+                .add_attribute("loan_id", loan_id.to_string())
+                .add_attribute("borrower", borrower)
+                .add_attribute("nft_contract", info.sender)
+                .add_attribute("token_id", cw721_msg.token_id))
+        }
+    }
+}
+
+pub fn make_offer(
+    deps: DepsMut,
+    lender: Addr,
+    loan_id: u64,
+    currency: LoanCurrency,
+    principal: Uint128,
+    interest_rate: Decimal,
+    term: u64,
+) -> Result<Response, ContractError> {
+    let loan = LOANS.load(deps.storage, U64Key::new(loan_id))?;
+    if loan.status != LoanStatus::Listed {
+        return Err(ContractError::InvalidLoanStatus { status: "listed".to_string() });
+    }
+    if loan.borrower == lender {
+        return Err(ContractError::InvalidOffer { reason: "cannot lend to yourself".to_string() });
+    }
+    if principal.is_zero() {
+        return Err(ContractError::InvalidOffer { reason: "principal must be positive".to_string() });
+    }
+    if term == 0 {
+        return Err(ContractError::InvalidOffer { reason: "term must be positive".to_string() });
+    }
+    if term > MAX_TERM {
+        return Err(ContractError::InvalidOffer { reason: format!("term cannot exceed {} seconds", MAX_TERM) });
+    }
+    if interest_rate > Decimal::percent(MAX_INTEREST_RATE_PERCENT) {
+        return Err(ContractError::InvalidOffer {
+            reason: format!("interest rate cannot exceed {} percent", MAX_INTEREST_RATE_PERCENT),
+        });
+    }
+
+    let offer = Offer {
+        loan_id,
+        lender: lender.clone(),
+        currency,
+        principal,
+        interest_rate,
+        term,
+        status: OfferStatus::Open,
+    };
+    // The offer has to be repayable
+    offer.repayment()?;
+
+    let offer_id = OFFER_COUNT.load(deps.storage)? + 1;
+    OFFER_COUNT.save(deps.storage, &offer_id)?;
+    OFFERS.save(deps.storage, U64Key::new(offer_id), &offer)?;
+    LOAN_OFFERS.save(deps.storage, (U64Key::new(loan_id), U64Key::new(offer_id)), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "make_offer")
+        .add_attribute("loan_id", loan_id.to_string())
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("lender", lender)
+        .add_attribute("principal", principal))
+}
+
+pub fn cancel_offer(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let mut offer = OFFERS.load(deps.storage, U64Key::new(offer_id))?;
+    if offer.lender != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if offer.status != OfferStatus::Open {
+        return Err(ContractError::OfferClosed {});
+    }
+    offer.status = OfferStatus::Cancelled;
+    OFFERS.save(deps.storage, U64Key::new(offer_id), &offer)?;
+    LOAN_OFFERS.remove(deps.storage, (U64Key::new(offer.loan_id), U64Key::new(offer_id)));
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &offer.currency, &offer.lender, offer.principal)?)
+        .add_attribute("action", "cancel_offer")
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+pub fn accept_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let mut offer = OFFERS.load(deps.storage, U64Key::new(offer_id))?;
+    if offer.status != OfferStatus::Open {
+        return Err(ContractError::OfferClosed {});
+    }
+    let mut loan = LOANS.load(deps.storage, U64Key::new(offer.loan_id))?;
+    if loan.borrower != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if loan.status != LoanStatus::Listed {
+        return Err(ContractError::InvalidLoanStatus { status: "listed".to_string() });
+    }
+
+    // The other offers stay open until their lenders cancel them
+    offer.status = OfferStatus::Accepted;
+    OFFERS.save(deps.storage, U64Key::new(offer_id), &offer)?;
+    LOAN_OFFERS.remove(deps.storage, (U64Key::new(offer.loan_id), U64Key::new(offer_id)));
+    loan.status = LoanStatus::Active;
+    loan.offer_id = Some(offer_id);
+    loan.start_time = Some(env.block.time.seconds());
+    let due_time = offer.due_time(env.block.time.seconds())?;
+    LOANS.save(deps.storage, U64Key::new(offer.loan_id), &loan)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &offer.currency, &loan.borrower, offer.principal)?)
+        .add_attribute("action", "accept_offer")
+        .add_attribute("loan_id", offer.loan_id.to_string())
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("due_time", due_time.to_string()))
+}
+
+pub fn repay(
+    deps: DepsMut,
+    env: Env,
+    loan_id: u64,
+    currency: LoanCurrency,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let mut loan = LOANS.load(deps.storage, U64Key::new(loan_id))?;
+    let (offer, due_time) = active_offer(deps.as_ref(), &loan)?;
+    if env.block.time.seconds() >= due_time {
+        return Err(ContractError::LoanExpired {});
+    }
+    let repayment = offer.repayment()?;
+    if currency != offer.currency || paid != repayment {
+        let config = CONFIG.load(deps.storage)?;
+        let amount = match offer.currency {
+            LoanCurrency::Native => format!("{}{}", repayment, config.payment_denom),
+            LoanCurrency::Luart => format!("{} LUART", repayment),
+        };
+        return Err(ContractError::InvalidPayment { amount });
+    }
+
+    loan.status = LoanStatus::Repaid;
+    LOANS.save(deps.storage, U64Key::new(loan_id), &loan)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &offer.currency, &offer.lender, repayment)?)
+        .add_message(nft_transfer_msg(&loan, &loan.borrower)?)
+        .add_attribute("action", "repay")
+        .add_attribute("loan_id", loan_id.to_string())
+        .add_attribute("repayment", repayment))
+}
+
+pub fn withdraw_collateral(
+    deps: DepsMut,
+    info: MessageInfo,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut loan = LOANS.load(deps.storage, U64Key::new(loan_id))?;
+    if loan.borrower != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if loan.status != LoanStatus::Listed {
+        return Err(ContractError::InvalidLoanStatus { status: "listed".to_string() });
+    }
+    loan.status = LoanStatus::Withdrawn;
+    LOANS.save(deps.storage, U64Key::new(loan_id), &loan)?;
+
+    Ok(Response::new()
+        .add_message(nft_transfer_msg(&loan, &loan.borrower)?)
+        .add_attribute("action", "withdraw_collateral")
+        .add_attribute("loan_id", loan_id.to_string()))
+}
+
+pub fn claim_collateral(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut loan = LOANS.load(deps.storage, U64Key::new(loan_id))?;
+    let (offer, due_time) = active_offer(deps.as_ref(), &loan)?;
+    if offer.lender != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < due_time {
+        return Err(ContractError::LoanNotExpired {});
+    }
+    loan.status = LoanStatus::Defaulted;
+    LOANS.save(deps.storage, U64Key::new(loan_id), &loan)?;
+
+    Ok(Response::new()
+        .add_message(nft_transfer_msg(&loan, &offer.lender)?)
+        .add_attribute("action", "claim_collateral")
+        .add_attribute("loan_id", loan_id.to_string()))
+}
+
+/// Returns the accepted offer of an active loan and the end of its term
+fn active_offer(deps: Deps, loan: &Loan) -> Result<(Offer, u64), ContractError> {
+    match (&loan.status, loan.offer_id, loan.start_time) {
+        (LoanStatus::Active, Some(offer_id), Some(start_time)) => {
+            let offer = OFFERS.load(deps.storage, U64Key::new(offer_id))?;
+            let due_time = offer.due_time(start_time)?;
+            Ok((offer, due_time))
+        }
+        _ => Err(ContractError::InvalidLoanStatus { status: "active".to_string() }),
+    }
+}
+
+fn nft_transfer_msg(loan: &Loan, recipient: &Addr) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: loan.nft_contract.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: loan.token_id.clone(),
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn payment_msg(
+    config: &Config,
+    currency: &LoanCurrency,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(match currency {
+        LoanCurrency::Native => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), &config.payment_denom),
+        }),
+        LoanCurrency::Luart => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.luart_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Loan { loan_id } => to_binary(&query_loan(deps, loan_id)?),
+        QueryMsg::Loans { start_after, limit } => to_binary(&query_loans(deps, start_after, limit)?),
+        QueryMsg::Offer { offer_id } => to_binary(&query_offer(deps, offer_id)?),
+        QueryMsg::Offers { loan_id, start_after, limit } => {
+            to_binary(&query_offers(deps, loan_id, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        luart_token: config.luart_token.to_string(),
+        payment_denom: config.payment_denom,
+    })
+}
+
+pub fn query_loan(deps: Deps, loan_id: u64) -> StdResult<LoanResponse> {
+    let loan = LOANS.load(deps.storage, U64Key::new(loan_id))?;
+    loan_response(deps, loan_id, loan)
+}
+
+pub fn query_loans(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LoansResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let loans: StdResult<Vec<LoanResponse>> = LOANS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, loan) = item?;
+            loan_response(deps, parse_id(&k)?, loan)
+        })
+        .collect();
+
+    Ok(LoansResponse { loans: loans? })
+}
+
+fn loan_response(deps: Deps, loan_id: u64, loan: Loan) -> StdResult<LoanResponse> {
+    let offer = match loan.offer_id {
+        Some(offer_id) => Some(OFFERS.load(deps.storage, U64Key::new(offer_id))?),
+        None => None,
+    };
+    Ok(LoanResponse {
+        loan_id,
+        due_time: match (&offer, loan.start_time) {
+            (Some(offer), Some(start_time)) => Some(offer.due_time(start_time)?),
+            _ => None,
+        },
+        repayment: offer.as_ref().map(|offer| offer.repayment()).transpose()?,
+        offer,
+        loan,
+    })
+}
+
+pub fn query_offer(deps: Deps, offer_id: u64) -> StdResult<OfferResponse> {
+    let offer = OFFERS.load(deps.storage, U64Key::new(offer_id))?;
+    Ok(OfferResponse { offer_id, offer })
+}
+
+pub fn query_offers(
+    deps: Deps,
+    loan_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let offers: StdResult<Vec<OfferResponse>> = LOAN_OFFERS
+        .prefix(U64Key::new(loan_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            query_offer(deps, parse_id(&k)?)
+        })
+        .collect();
+
+    Ok(OffersResponse { offers: offers? })
+}
+
+fn parse_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted id key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
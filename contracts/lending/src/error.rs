@@ -0,0 +1,32 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid offer: {reason}")]
+    InvalidOffer { reason: String },
+
+    #[error("Must pay exactly {amount}")]
+    InvalidPayment { amount: String },
+
+    #[error("The loan is not {status}")]
+    InvalidLoanStatus { status: String },
+
+    #[error("The offer is not open")]
+    OfferClosed {},
+
+    #[error("The term of the loan is over")]
+    LoanExpired {},
+
+    #[error("The term of the loan is not over")]
+    LoanNotExpired {},
+}
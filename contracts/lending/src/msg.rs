@@ -0,0 +1,130 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+
+use crate::state::{Loan, Offer};
+
+/// This structure describes the parameters used for creating a lending contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub luart_token: String,
+    /// The native denom of the loans not lent in LUART
+    pub payment_denom: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Makes an offer or repays a loan in LUART, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Lists the sent NFT as the collateral of a loan, see [`Cw721HookMsg`]
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Offers to lend the sent payment denom against the collateral, for a term of at most five years
+    MakeOffer {
+        loan_id: u64,
+        interest_rate: Decimal,
+        term: u64,
+    },
+    /// Returns the escrowed principal of an offer not accepted. Only the lender can execute it.
+    CancelOffer { offer_id: u64 },
+    /// Starts the loan and sends the principal of the offer. Only the borrower can execute it.
+    AcceptOffer { offer_id: u64 },
+    /// Repays the loan in the payment denom before the end of the term and returns the collateral
+    Repay { loan_id: u64 },
+    /// Returns the collateral of a loan without accepted offer. Only the borrower can execute it.
+    WithdrawCollateral { loan_id: u64 },
+    /// Takes the collateral of a loan not repaid at the end of the term.
+    /// Only the lender can execute it.
+    ClaimCollateral { loan_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Offers to lend the sent LUART against the collateral, for a term of at most five years
+    MakeOffer {
+        loan_id: u64,
+        interest_rate: Decimal,
+        term: u64,
+    },
+    /// Repays the loan with the sent LUART
+    Repay { loan_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    /// Escrows the sent NFT and opens its offer book
+    ListCollateral {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the loan and its accepted offer.
+    /// Return type: LoanResponse.
+    Loan { loan_id: u64 },
+    /// Returns the loans by id.
+    /// Return type: LoansResponse.
+    Loans {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the offer.
+    /// Return type: OfferResponse.
+    Offer { offer_id: u64 },
+    /// Returns the open offers of the loan by id.
+    /// Return type: OffersResponse.
+    Offers {
+        loan_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub luart_token: String,
+    pub payment_denom: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LoanResponse {
+    pub loan_id: u64,
+    pub loan: Loan,
+    pub offer: Option<Offer>,
+    /// The end of the term of an accepted offer
+    pub due_time: Option<u64>,
+    /// The principal and interest repaid to the lender
+    pub repayment: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LoansResponse {
+    pub loans: Vec<LoanResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferResponse {
+    pub offer_id: u64,
+    pub offer: Offer,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<OfferResponse>,
+}
@@ -0,0 +1,101 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use std::convert::TryFrom;
+
+use cosmwasm_std::{
+    Addr, Decimal, Fraction, OverflowError, OverflowOperation, StdError, StdResult, Uint128, Uint256,
+};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub luart_token: Addr,
+    /// The native denom of the loans not lent in LUART
+    pub payment_denom: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoanCurrency {
+    /// The payment denom of the configuration
+    Native,
+    Luart,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoanStatus {
+    /// The collateral waits for an accepted offer
+    Listed,
+    Active,
+    Repaid,
+    /// The collateral went to the lender
+    Defaulted,
+    /// The borrower took back the collateral without borrowing
+    Withdrawn,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Loan {
+    pub borrower: Addr,
+    /// The escrowed NFT securing the loan
+    pub nft_contract: Addr,
+    pub token_id: String,
+    pub status: LoanStatus,
+    /// The accepted offer
+    pub offer_id: Option<u64>,
+    pub start_time: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferStatus {
+    Open,
+    Accepted,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    pub loan_id: u64,
+    pub lender: Addr,
+    pub currency: LoanCurrency,
+    /// The escrowed amount lent to the borrower
+    pub principal: Uint128,
+    /// The interest of the whole term, as a share of the principal
+    pub interest_rate: Decimal,
+    /// Seconds the borrower has to repay after accepting the offer
+    pub term: u64,
+    pub status: OfferStatus,
+}
+
+impl Offer {
+    pub fn repayment(&self) -> StdResult<Uint128> {
+        let interest = self.principal
+            .full_mul(self.interest_rate.numerator())
+            .checked_div(Uint256::from(self.interest_rate.denominator()))?;
+        let interest = Uint128::try_from(interest).map_err(|err| StdError::generic_err(err.to_string()))?;
+        Ok(self.principal.checked_add(interest)?)
+    }
+
+    /// The end of the term of the offer accepted at the start time
+    pub fn due_time(&self, start_time: u64) -> StdResult<u64> {
+        start_time.checked_add(self.term).ok_or_else(|| {
+            StdError::from(OverflowError::new(OverflowOperation::Add, start_time, self.term))
+        })
+    }
+}
+
+/// The longest term of an offer, five years
+pub const MAX_TERM: u64 = 5 * 365 * 86_400;
+/// The highest interest rate of an offer, in percent of the principal
+pub const MAX_INTEREST_RATE_PERCENT: u64 = 1_000;
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const LOAN_COUNT: Item<u64> = Item::new("loan_count");
+pub const LOANS: Map<U64Key, Loan> = Map::new("loans");
+pub const OFFER_COUNT: Item<u64> = Item::new("offer_count");
+pub const OFFERS: Map<U64Key, Offer> = Map::new("offers");
+/// The open offers of each loan
+pub const LOAN_OFFERS: Map<(U64Key, U64Key), bool> = Map::new("loan_offers");
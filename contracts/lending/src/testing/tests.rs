@@ -0,0 +1,189 @@
+use cosmwasm_std::{
+    BankMsg, coins, Decimal, DepsMut, Env, from_binary, Response, StdError, SubMsg, to_binary,
+    Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, LoanResponse, LoansResponse,
+    OffersResponse, QueryMsg,
+};
+use crate::state::{LoanStatus, MAX_INTEREST_RATE_PERCENT, MAX_TERM};
+
+const LUART: &str = "mock_luart";
+const COLLECTION: &str = "mock_collection";
+const BORROWER: &str = "borrower";
+const LENDER: &str = "lender";
+const OTHER_LENDER: &str = "other_lender";
+const DENOM: &str = "uusd";
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        luart_token: LUART.to_string(),
+        payment_denom: DENOM.to_string(),
+    };
+    instantiate(deps, env, mock_info("deployer", &[]), msg).unwrap()
+}
+
+fn list_collateral(deps: DepsMut, env: Env, token_id: &str) {
+    execute(deps, env, mock_info(COLLECTION, &[]), ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: BORROWER.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_binary(&Cw721HookMsg::ListCollateral {}).unwrap(),
+    })).unwrap();
+}
+
+fn luart_msg(sender: &str, amount: u128, msg: &Cw20HookMsg) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(msg).unwrap(),
+    })
+}
+
+fn nft_transfer(recipient: &str, token_id: &str) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: COLLECTION.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        }).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn luart_transfer(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: LUART.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn test_repaid_loan() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    list_collateral(deps.as_mut(), env.clone(), "1");
+
+    let offer_msg = ExecuteMsg::MakeOffer { loan_id: 1, interest_rate: Decimal::percent(10), term: 30 * DAY };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &coins(1_000, DENOM)), offer_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidOffer { reason: "cannot lend to yourself".to_string() });
+    for term in [MAX_TERM + 1, u64::MAX] {
+        let msg = ExecuteMsg::MakeOffer { loan_id: 1, interest_rate: Decimal::percent(10), term };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &coins(1_000, DENOM)), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidOffer { reason: "term cannot exceed 157680000 seconds".to_string() });
+    }
+    let msg = ExecuteMsg::MakeOffer {
+        loan_id: 1,
+        interest_rate: Decimal::percent(MAX_INTEREST_RATE_PERCENT + 1),
+        term: 30 * DAY,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &coins(1_000, DENOM)), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidOffer { reason: "interest rate cannot exceed 1000 percent".to_string() });
+
+    // The repayment of the offer has to fit the amounts
+    let msg = Cw20HookMsg::MakeOffer { loan_id: 1, interest_rate: Decimal::percent(100), term: 30 * DAY };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), luart_msg(LENDER, u128::MAX / 2 + 1, &msg))
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    execute(deps.as_mut(), env.clone(), mock_info(LENDER, &coins(1_000, DENOM)), offer_msg).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), luart_msg(OTHER_LENDER, 5_000, &Cw20HookMsg::MakeOffer {
+        loan_id: 1,
+        interest_rate: Decimal::percent(5),
+        term: 7 * DAY,
+    })).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Offers { loan_id: 1, start_after: None, limit: None })
+        .unwrap();
+    let offers: OffersResponse = from_binary(&res).unwrap();
+    assert_eq!(offers.offers.len(), 2);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &[]), ExecuteMsg::AcceptOffer { offer_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &[]), ExecuteMsg::AcceptOffer { offer_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send { to_address: BORROWER.to_string(), amount: coins(1_000, DENOM) }),
+    ]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &[]), ExecuteMsg::AcceptOffer { offer_id: 2 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLoanStatus { status: "listed".to_string() });
+
+    // The lender of the other offer takes back the principal
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OTHER_LENDER, &[]), ExecuteMsg::CancelOffer { offer_id: 2 })
+        .unwrap();
+    assert_eq!(res.messages, vec![luart_transfer(OTHER_LENDER, 5_000)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &coins(1_000, DENOM)),
+                      ExecuteMsg::Repay { loan_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { amount: "1100uusd".to_string() });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &coins(1_100, DENOM)),
+                      ExecuteMsg::Repay { loan_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send { to_address: LENDER.to_string(), amount: coins(1_100, DENOM) }),
+        nft_transfer(BORROWER, "1"),
+    ]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Loan { loan_id: 1 }).unwrap();
+    let loan: LoanResponse = from_binary(&res).unwrap();
+    assert_eq!(loan.loan.status, LoanStatus::Repaid);
+    assert_eq!(loan.due_time, Some(env.block.time.seconds() + 30 * DAY));
+    assert_eq!(loan.repayment, Some(Uint128::new(1_100)));
+}
+
+#[test]
+fn test_defaulted_loan() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    list_collateral(deps.as_mut(), env.clone(), "1");
+    list_collateral(deps.as_mut(), env.clone(), "2");
+
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), luart_msg(LENDER, 5_000, &Cw20HookMsg::MakeOffer {
+        loan_id: 1,
+        interest_rate: Decimal::percent(20),
+        term: 7 * DAY,
+    })).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &[]), ExecuteMsg::AcceptOffer { offer_id: 1 }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &[]), ExecuteMsg::ClaimCollateral { loan_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::LoanNotExpired {});
+    env.block.time = env.block.time.plus_seconds(7 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]),
+                      luart_msg(BORROWER, 6_000, &Cw20HookMsg::Repay { loan_id: 1 })).unwrap_err();
+    assert_eq!(err, ContractError::LoanExpired {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &[]), ExecuteMsg::ClaimCollateral { loan_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &[]), ExecuteMsg::ClaimCollateral { loan_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![nft_transfer(LENDER, "1")]);
+
+    // The collateral without accepted offer goes back to the borrower
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LENDER, &[]), ExecuteMsg::WithdrawCollateral { loan_id: 2 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BORROWER, &[]), ExecuteMsg::WithdrawCollateral { loan_id: 2 })
+        .unwrap();
+    assert_eq!(res.messages, vec![nft_transfer(BORROWER, "2")]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Loans { start_after: None, limit: None }).unwrap();
+    let loans: LoansResponse = from_binary(&res).unwrap();
+    let statuses: Vec<LoanStatus> = loans.loans.into_iter().map(|loan| loan.loan.status).collect();
+    assert_eq!(statuses, vec![LoanStatus::Defaulted, LoanStatus::Withdrawn]);
+}
@@ -0,0 +1,433 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Order,
+    Response, StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use luart_utils::batch::batch_query;
+use terraswap::asset::{Asset, AssetInfo, PairInfo};
+use terraswap::pair::{ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    PositionResponse, PositionsResponse, QueryMsg, StateResponse,
+};
+use crate::state::{Config, CONFIG, Position, POSITIONS, State, STATE, vested_amount};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-lockdrop";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let pair = match msg.pair {
+        Some(pair) => Some(deps.api.addr_validate(&pair)?),
+        None => None,
+    };
+
+    if msg.deposit_end <= msg.deposit_start {
+        return Err(ContractError::InvalidConfig {
+            reason: "deposit window must end after it starts".to_string(),
+        });
+    }
+    if msg.lock_options.is_empty()
+        || msg.lock_options.iter().any(|o| o.duration == 0 || o.multiplier.is_zero()) {
+        return Err(ContractError::InvalidConfig {
+            reason: "lock options must have a positive duration and multiplier".to_string(),
+        });
+    }
+
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+        pair,
+        deposit_denom: msg.deposit_denom,
+        deposit_start: msg.deposit_start,
+        deposit_end: msg.deposit_end,
+        lock_options: msg.lock_options,
+    })?;
+    STATE.save(deps.storage, &State::default())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::Lock { duration } => lock(deps, env, info, duration),
+        ExecuteMsg::ProvideLiquidity { slippage_tolerance } => {
+            provide_liquidity(deps, env, info, slippage_tolerance)
+        }
+        ExecuteMsg::Claim { duration } => claim(deps, env, info, duration),
+        ExecuteMsg::UpdateConfig { owner, pair } => update_config(deps, info, owner, pair),
+        ExecuteMsg::Callback(msg) => {
+            if info.sender != env.contract.address {
+                return Err(ContractError::Unauthorized {});
+            }
+            match msg {
+                CallbackMsg::RecordLiquidity {} => record_liquidity(deps, env),
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender || config.owner != cw20_msg.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    if state.liquidity_provided_at.is_some() {
+        return Err(ContractError::LiquidityProvided {});
+    }
+
+    let action = match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::DepositListingTokens {} => {
+            state.listing_amount = state.listing_amount.checked_add(cw20_msg.amount)?;
+            "deposit_listing_tokens"
+        }
+        Cw20HookMsg::IncreaseIncentives {} => {
+            state.incentives_amount = state.incentives_amount.checked_add(cw20_msg.amount)?;
+            "increase_incentives"
+        }
+    };
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("amount", cw20_msg.amount))
+}
+
+pub fn lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.deposit_denom)?;
+
+    let now = env.block.time.seconds();
+    if now < config.deposit_start || now >= config.deposit_end {
+        return Err(ContractError::DepositWindowClosed {});
+    }
+
+    let multiplier = config.lock_option(duration)
+        .ok_or(ContractError::InvalidLockDuration { duration })?
+        .multiplier;
+    let weight = amount * multiplier;
+
+    let mut position = POSITIONS
+        .may_load(deps.storage, (&info.sender, U64Key::new(duration)))?
+        .unwrap_or_default();
+    position.amount = position.amount.checked_add(amount)?;
+    position.weight = position.weight.checked_add(weight)?;
+    POSITIONS.save(deps.storage, (&info.sender, U64Key::new(duration)), &position)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.total_deposited = state.total_deposited.checked_add(amount)?;
+    state.total_weight = state.total_weight.checked_add(weight)?;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("address", info.sender)
+        .add_attribute("duration", duration.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("weight", weight))
+}
+
+pub fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    slippage_tolerance: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < config.deposit_end {
+        return Err(ContractError::DepositWindowNotClosed {});
+    }
+    let pair = config.pair.ok_or(ContractError::PairNotSet {})?;
+
+    let mut state = STATE.load(deps.storage)?;
+    if state.liquidity_provided_at.is_some() {
+        return Err(ContractError::LiquidityProvided {});
+    }
+    if state.total_deposited.is_zero() || state.listing_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let pair_info: PairInfo = deps.querier.query_wasm_smart(&pair, &PairQueryMsg::Pair {})?;
+    state.lp_token = Some(deps.api.addr_validate(&pair_info.liquidity_token)?);
+    STATE.save(deps.storage, &state)?;
+
+    let messages = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.luart_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: pair.to_string(),
+                amount: state.listing_amount,
+                expires: None,
+            })?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::Token { contract_addr: config.luart_token.to_string() },
+                        amount: state.listing_amount,
+                    },
+                    Asset {
+                        info: AssetInfo::NativeToken { denom: config.deposit_denom.clone() },
+                        amount: state.total_deposited,
+                    },
+                ],
+                slippage_tolerance,
+                receiver: None,
+            })?,
+            funds: vec![Coin {
+                denom: config.deposit_denom,
+                amount: state.total_deposited,
+            }],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::RecordLiquidity {}))?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "provide_liquidity")
+        .add_attribute("listing_amount", state.listing_amount)
+        .add_attribute("deposit_amount", state.total_deposited))
+}
+
+pub fn record_liquidity(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    let lp_token = state.lp_token.clone().ok_or(ContractError::LiquidityNotProvided {})?;
+
+    let res: BalanceResponse = deps.querier.query_wasm_smart(&lp_token, &Cw20QueryMsg::Balance {
+        address: env.contract.address.to_string(),
+    })?;
+    state.lp_amount = res.balance;
+    state.liquidity_provided_at = Some(env.block.time.seconds());
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "record_liquidity")
+        .add_attribute("lp_amount", state.lp_amount))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let lp_token = match (&state.lp_token, state.liquidity_provided_at) {
+        (Some(lp_token), Some(_)) => lp_token.clone(),
+        _ => return Err(ContractError::LiquidityNotProvided {}),
+    };
+
+    let mut position = POSITIONS
+        .may_load(deps.storage, (&info.sender, U64Key::new(duration)))?
+        .ok_or(ContractError::NothingToClaim {})?;
+
+    let now = env.block.time.seconds();
+    let lp_vested = vested_amount(position.lp_share(&state), &state, duration, now);
+    let incentives_vested = vested_amount(position.incentives_share(&state), &state, duration, now);
+    let lp_amount = lp_vested.checked_sub(position.lp_claimed)?;
+    let incentives_amount = incentives_vested.checked_sub(position.incentives_claimed)?;
+    if lp_amount.is_zero() && incentives_amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    position.lp_claimed = lp_vested;
+    position.incentives_claimed = incentives_vested;
+    POSITIONS.save(deps.storage, (&info.sender, U64Key::new(duration)), &position)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !lp_amount.is_zero() {
+        messages.push(token_transfer_msg(&lp_token, info.sender.to_string(), lp_amount)?);
+    }
+    if !incentives_amount.is_zero() {
+        messages.push(token_transfer_msg(&config.luart_token, info.sender.to_string(), incentives_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim")
+        .add_attribute("address", info.sender)
+        .add_attribute("duration", duration.to_string())
+        .add_attribute("lp_amount", lp_amount)
+        .add_attribute("incentives_amount", incentives_amount))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    pair: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(pair) = pair {
+        if STATE.load(deps.storage)?.liquidity_provided_at.is_some() {
+            return Err(ContractError::LiquidityProvided {});
+        }
+        config.pair = Some(deps.api.addr_validate(&pair)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::Positions { address } => to_binary(&query_positions(deps, env, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        luart_token: config.luart_token.to_string(),
+        pair: config.pair.map(|p| p.to_string()),
+        deposit_denom: config.deposit_denom,
+        deposit_start: config.deposit_start,
+        deposit_end: config.deposit_end,
+        lock_options: config.lock_options,
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        total_deposited: state.total_deposited,
+        total_weight: state.total_weight,
+        listing_amount: state.listing_amount,
+        incentives_amount: state.incentives_amount,
+        lp_token: state.lp_token.map(|t| t.to_string()),
+        lp_amount: state.lp_amount,
+        liquidity_provided_at: state.liquidity_provided_at,
+    })
+}
+
+pub fn query_positions(deps: Deps, env: Env, address: String) -> StdResult<PositionsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let state = STATE.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let positions: StdResult<Vec<PositionResponse>> = POSITIONS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (k, position) = item?;
+            let duration = parse_duration(&k)?;
+            Ok(position_response(duration, position, &state, now))
+        })
+        .collect();
+
+    Ok(PositionsResponse { positions: positions? })
+}
+
+fn position_response(duration: u64, position: Position, state: &State, time: u64) -> PositionResponse {
+    let lp_share = position.lp_share(state);
+    let incentives_share = position.incentives_share(state);
+    PositionResponse {
+        duration,
+        amount: position.amount,
+        weight: position.weight,
+        lp_share,
+        lp_claimed: position.lp_claimed,
+        lp_claimable: vested_amount(lp_share, state, duration, time)
+            .saturating_sub(position.lp_claimed),
+        incentives_share,
+        incentives_claimed: position.incentives_claimed,
+        incentives_claimable: vested_amount(incentives_share, state, duration, time)
+            .saturating_sub(position.incentives_claimed),
+    }
+}
+
+fn parse_duration(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted position key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+fn token_transfer_msg(token: &Addr, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,44 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid lockdrop configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("No lock option with a duration of {duration} seconds")]
+    InvalidLockDuration { duration: u64 },
+
+    #[error("The deposit window is closed")]
+    DepositWindowClosed {},
+
+    #[error("The deposit window is not closed yet")]
+    DepositWindowNotClosed {},
+
+    #[error("The LUART-UST pair is not set")]
+    PairNotSet {},
+
+    #[error("Liquidity was already provided")]
+    LiquidityProvided {},
+
+    #[error("Liquidity was not provided yet")]
+    LiquidityNotProvided {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+}
@@ -0,0 +1,127 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::LockOption;
+
+/// This structure describes the parameters used for creating a lockdrop contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token address
+    pub luart_token: String,
+    /// The LUART-UST terraswap pair, can be set later on
+    pub pair: Option<String>,
+    /// The native denom users lock
+    pub deposit_denom: String,
+    /// Deposit window start timestamp in seconds
+    pub deposit_start: u64,
+    /// Deposit window end timestamp in seconds
+    pub deposit_end: u64,
+    /// The available lock durations
+    pub lock_options: Vec<LockOption>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives LUART from the owner, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Locks the sent funds for the given duration during the deposit window
+    Lock { duration: u64 },
+    /// Provides the locked funds and the listing LUART to the pair once the deposit window
+    /// has ended. Only the owner can execute it.
+    ProvideLiquidity { slippage_tolerance: Option<Decimal> },
+    /// Transfers the vested LP tokens and LUART incentives of the sender position
+    Claim { duration: u64 },
+    /// Updates the lockdrop configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        pair: Option<String>,
+    },
+    /// Internal messages, only the contract itself can execute them
+    Callback(CallbackMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Records the LP tokens received from the pair
+    RecordLiquidity {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits the LUART paired with the locked funds at listing
+    DepositListingTokens {},
+    /// Deposits LUART distributed to participants by lock weight
+    IncreaseIncentives {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the lockdrop configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the lockdrop totals.
+    /// Return type: StateResponse.
+    State {},
+    /// Returns the positions of the address.
+    /// Return type: PositionsResponse.
+    Positions { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub luart_token: String,
+    pub pair: Option<String>,
+    pub deposit_denom: String,
+    pub deposit_start: u64,
+    pub deposit_end: u64,
+    pub lock_options: Vec<LockOption>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub total_deposited: Uint128,
+    pub total_weight: Uint128,
+    pub listing_amount: Uint128,
+    pub incentives_amount: Uint128,
+    pub lp_token: Option<String>,
+    pub lp_amount: Uint128,
+    pub liquidity_provided_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    pub duration: u64,
+    pub amount: Uint128,
+    pub weight: Uint128,
+    /// The LP tokens backing the position deposits
+    pub lp_share: Uint128,
+    pub lp_claimed: Uint128,
+    pub lp_claimable: Uint128,
+    /// The LUART incentives owed to the position
+    pub incentives_share: Uint128,
+    pub incentives_claimed: Uint128,
+    pub incentives_claimable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionsResponse {
+    pub positions: Vec<PositionResponse>,
+}
@@ -0,0 +1,104 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockOption {
+    /// The lock duration in seconds
+    pub duration: u64,
+    /// The lock weight received per deposited token
+    pub multiplier: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who funds the lockdrop and provides the liquidity
+    pub owner: Addr,
+    /// The LUART token address
+    pub luart_token: Addr,
+    /// The LUART-UST terraswap pair, set once the pair is created
+    pub pair: Option<Addr>,
+    /// The native denom users lock
+    pub deposit_denom: String,
+    /// Deposit window start timestamp in seconds
+    pub deposit_start: u64,
+    /// Deposit window end timestamp in seconds
+    pub deposit_end: u64,
+    /// The available lock durations
+    pub lock_options: Vec<LockOption>,
+}
+
+impl Config {
+    pub fn lock_option(&self, duration: u64) -> Option<&LockOption> {
+        self.lock_options.iter().find(|o| o.duration == duration)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct State {
+    /// The total amount of locked deposits
+    pub total_deposited: Uint128,
+    /// The sum of every position weight
+    pub total_weight: Uint128,
+    /// The amount of LUART paired with the deposits at listing
+    pub listing_amount: Uint128,
+    /// The amount of LUART distributed to participants by lock weight
+    pub incentives_amount: Uint128,
+    /// The LP token of the pair
+    pub lp_token: Option<Addr>,
+    /// The amount of LP tokens received when providing liquidity
+    pub lp_amount: Uint128,
+    /// Timestamp in seconds the liquidity was provided at, vesting starts from it
+    pub liquidity_provided_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Position {
+    /// The amount of locked deposits
+    pub amount: Uint128,
+    /// The lock weight, deposits times the lock option multiplier
+    pub weight: Uint128,
+    /// The amount of LP tokens already claimed
+    pub lp_claimed: Uint128,
+    /// The amount of LUART incentives already claimed
+    pub incentives_claimed: Uint128,
+}
+
+impl Position {
+    /// Returns the LP tokens backing the position deposits
+    pub fn lp_share(&self, state: &State) -> Uint128 {
+        if state.total_deposited.is_zero() {
+            return Uint128::zero();
+        }
+        state.lp_amount.multiply_ratio(self.amount, state.total_deposited)
+    }
+
+    /// Returns the incentives owed to the position, pro-rata to its weight
+    pub fn incentives_share(&self, state: &State) -> Uint128 {
+        if state.total_weight.is_zero() {
+            return Uint128::zero();
+        }
+        state.incentives_amount.multiply_ratio(self.weight, state.total_weight)
+    }
+}
+
+/// Returns the vested part of the given amount, vesting linearly over the lock duration
+/// from the liquidity provision
+pub fn vested_amount(amount: Uint128, state: &State, duration: u64, time: u64) -> Uint128 {
+    let start = match state.liquidity_provided_at {
+        Some(start) => start,
+        None => return Uint128::zero(),
+    };
+    let elapsed = time.saturating_sub(start);
+    if elapsed >= duration {
+        return amount;
+    }
+    amount.multiply_ratio(elapsed, duration)
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+/// Positions of a user keyed by the lock duration
+pub const POSITIONS: Map<(&Addr, U64Key), Position> = Map::new("positions");
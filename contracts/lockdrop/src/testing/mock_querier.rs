@@ -0,0 +1,83 @@
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_slice, OwnedDeps, Querier, QuerierResult, QueryRequest,
+    SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::BalanceResponse;
+use terraswap::asset::{AssetInfo, PairInfo};
+
+pub const MOCK_PAIR: &str = "mock_pair";
+pub const MOCK_LP_TOKEN: &str = "mock_lp_token";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the pair info and LP balance queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    lp_balance: Uint128,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg: _ }) => {
+                match contract_addr.as_str() {
+                    MOCK_PAIR => SystemResult::Ok(ContractResult::from(to_binary(&PairInfo {
+                        asset_infos: [
+                            AssetInfo::Token { contract_addr: "mock_token".to_string() },
+                            AssetInfo::NativeToken { denom: "uusd".to_string() },
+                        ],
+                        contract_addr: MOCK_PAIR.to_string(),
+                        liquidity_token: MOCK_LP_TOKEN.to_string(),
+                    }))),
+                    MOCK_LP_TOKEN => SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                        balance: self.lp_balance,
+                    }))),
+                    _ => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            lp_balance: Uint128::zero(),
+        }
+    }
+
+    // configure the LP token balance of the contract
+    pub fn with_lp_balance(&mut self, balance: u128) {
+        self.lp_balance = Uint128::new(balance);
+    }
+}
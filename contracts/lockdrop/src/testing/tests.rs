@@ -0,0 +1,226 @@
+use cosmwasm_std::{
+    Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::ExecuteMsg as PairExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    CallbackMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PositionResponse, PositionsResponse,
+    QueryMsg,
+};
+use crate::state::LockOption;
+use crate::testing::mock_querier::{MOCK_LP_TOKEN, MOCK_PAIR, mock_dependencies};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const USER: &str = "mock_user";
+const DENOM: &str = "uusd";
+
+const DAY: u64 = 86_400;
+
+/// Instantiates a lockdrop with a 100 seconds deposit window starting at the current block
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let now = env.block.time.seconds();
+    let msg = InstantiateMsg {
+        owner: None,
+        luart_token: TOKEN.to_string(),
+        pair: Some(MOCK_PAIR.to_string()),
+        deposit_denom: DENOM.to_string(),
+        deposit_start: now,
+        deposit_end: now + 100,
+        lock_options: vec![
+            LockOption { duration: 30 * DAY, multiplier: Decimal::one() },
+            LockOption { duration: 90 * DAY, multiplier: Decimal::from_ratio(3u128, 1u128) },
+        ],
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn deposit_luart(deps: DepsMut, env: Env, amount: u128, msg: Cw20HookMsg) -> Response {
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: OWNER.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&msg).unwrap(),
+            })).unwrap()
+}
+
+fn transfer_msg(token: &str, recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_lock() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Lock { duration: 60 * DAY }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLockDuration { duration: 60 * DAY });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, "uluna")]),
+                      ExecuteMsg::Lock { duration: 30 * DAY }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: DENOM.to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Lock { duration: 30 * DAY }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(500, DENOM)]),
+            ExecuteMsg::Lock { duration: 90 * DAY }).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Positions { address: USER.to_string() }).unwrap();
+    let positions: PositionsResponse = from_binary(&res).unwrap();
+    assert_eq!(positions.positions.iter().map(|p| (p.duration, p.amount, p.weight)).collect::<Vec<_>>(), vec![
+        (30 * DAY, Uint128::new(1_000), Uint128::new(1_000)),
+        (90 * DAY, Uint128::new(500), Uint128::new(1_500)),
+    ]);
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env, mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Lock { duration: 30 * DAY }).unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowClosed {});
+}
+
+#[test]
+fn test_provide_liquidity() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    deposit_luart(deps.as_mut(), env.clone(), 5_000, Cw20HookMsg::DepositListingTokens {});
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Lock { duration: 30 * DAY }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideLiquidity { slippage_tolerance: None }).unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowNotClosed {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::ProvideLiquidity { slippage_tolerance: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideLiquidity { slippage_tolerance: None }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: MOCK_PAIR.to_string(),
+                amount: Uint128::new(5_000),
+                expires: None,
+            }).unwrap(),
+            funds: vec![],
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_PAIR.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+                        amount: Uint128::new(5_000),
+                    },
+                    Asset {
+                        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+                        amount: Uint128::new(1_000),
+                    },
+                ],
+                slippage_tolerance: None,
+                receiver: None,
+            }).unwrap(),
+            funds: vec![Coin::new(1_000, DENOM)],
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::RecordLiquidity {})).unwrap(),
+            funds: vec![],
+        })),
+    ]);
+
+    // Only the contract can record the received LP tokens
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Callback(CallbackMsg::RecordLiquidity {})).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    deps.querier.with_lp_balance(2_000);
+    execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]),
+            ExecuteMsg::Callback(CallbackMsg::RecordLiquidity {})).unwrap();
+
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideLiquidity { slippage_tolerance: None }).unwrap_err();
+    assert_eq!(err, ContractError::LiquidityProvided {});
+}
+
+#[test]
+fn test_claim() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    deposit_luart(deps.as_mut(), env.clone(), 5_000, Cw20HookMsg::DepositListingTokens {});
+    deposit_luart(deps.as_mut(), env.clone(), 4_000, Cw20HookMsg::IncreaseIncentives {});
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Lock { duration: 30 * DAY }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("another", &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Lock { duration: 90 * DAY }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { duration: 30 * DAY }).unwrap_err();
+    assert_eq!(err, ContractError::LiquidityNotProvided {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::ProvideLiquidity { slippage_tolerance: None }).unwrap();
+    deps.querier.with_lp_balance(2_000);
+    execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]),
+            ExecuteMsg::Callback(CallbackMsg::RecordLiquidity {})).unwrap();
+
+    // LP tokens are shared by deposits, incentives by lock weight,
+    // both vest over the lock duration
+    env.block.time = env.block.time.plus_seconds(15 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { duration: 30 * DAY }).unwrap();
+    assert_eq!(res.messages, vec![
+        transfer_msg(MOCK_LP_TOKEN, USER, 500),
+        transfer_msg(TOKEN, USER, 500),
+    ]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim { duration: 30 * DAY }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    env.block.time = env.block.time.plus_seconds(30 * DAY);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Positions { address: "another".to_string() }).unwrap();
+    let positions: PositionsResponse = from_binary(&res).unwrap();
+    assert_eq!(positions.positions, vec![PositionResponse {
+        duration: 90 * DAY,
+        amount: Uint128::new(1_000),
+        weight: Uint128::new(3_000),
+        lp_share: Uint128::new(1_000),
+        lp_claimed: Uint128::zero(),
+        lp_claimable: Uint128::new(500),
+        incentives_share: Uint128::new(3_000),
+        incentives_claimed: Uint128::zero(),
+        incentives_claimable: Uint128::new(1_500),
+    }]);
+
+    let res = execute(deps.as_mut(), env, mock_info(USER, &[]),
+                      ExecuteMsg::Claim { duration: 30 * DAY }).unwrap();
+    assert_eq!(res.messages, vec![
+        transfer_msg(MOCK_LP_TOKEN, USER, 500),
+        transfer_msg(TOKEN, USER, 500),
+    ]);
+}
@@ -0,0 +1,300 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Response, StdResult, to_binary, Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ReceiveMsg, Expiration};
+use cw_storage_plus::U64Key;
+use luart_types::asset::Asset;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use sha2::Digest;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestRoundResponse, MigrateMsg,
+    QueryMsg, RoundResponse,
+};
+use crate::state::{CLAIMS, Config, CONFIG, ConfigOwner, LATEST_ROUND, Round, ROUNDS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-merkle-distributor";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let asset = match msg.asset {
+        Asset::Cw20(token) => Asset::Cw20(deps.api.addr_validate(token.as_str())?),
+        asset => asset,
+    };
+
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        asset,
+    })?;
+    LATEST_ROUND.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::RegisterRound { merkle_root, expiration } => {
+            let config = CONFIG.load(deps.storage)?;
+            let amount = match &config.asset {
+                Asset::Native(denom) => must_pay(&info, denom)?,
+                Asset::Cw20(_) => return Err(ContractError::Unauthorized {}),
+            };
+            register_round(deps, &config, info.sender, merkle_root, expiration, amount)
+        }
+        ExecuteMsg::Claim { round, amount, proof } => claim(deps, env, info, round, amount, proof),
+        ExecuteMsg::Withdraw { round, recipient } => withdraw(deps, env, info, round, recipient),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // only the distributed token can fund the rounds
+    if config.asset != Asset::Cw20(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::RegisterRound { merkle_root, expiration } => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            register_round(deps, &config, sender, merkle_root, expiration, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn register_round(
+    deps: DepsMut,
+    config: &Config,
+    sender: Addr,
+    merkle_root: String,
+    expiration: Option<Expiration>,
+    total_amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !config.is_owner(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // check merkle root length
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    let round = LATEST_ROUND.load(deps.storage)? + 1;
+    ROUNDS.save(deps.storage, U64Key::new(round), &Round {
+        merkle_root: merkle_root.clone(),
+        expiration: expiration.unwrap_or(Expiration::Never {}),
+        total_amount,
+        claimed_amount: Uint128::zero(),
+        withdrawn_amount: Uint128::zero(),
+    })?;
+    LATEST_ROUND.save(deps.storage, &round)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_round")
+        .add_attribute("round", round.to_string())
+        .add_attribute("merkle_root", merkle_root)
+        .add_attribute("total_amount", total_amount))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round: u64,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut round_info = ROUNDS.load(deps.storage, U64Key::new(round))?;
+    if round_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::RoundExpired { round });
+    }
+
+    if CLAIMS.may_load(deps.storage, (&info.sender, U64Key::new(round)))?.unwrap_or(false) {
+        return Err(ContractError::Claimed {});
+    }
+
+    verify_merkle_proof(&round_info.merkle_root, info.sender.as_str(), amount, proof)?;
+
+    round_info.claimed_amount = round_info.claimed_amount.checked_add(amount)?;
+    if round_info.claimed_amount > round_info.total_amount {
+        return Err(ContractError::RoundTotalExceeded {});
+    }
+    ROUNDS.save(deps.storage, U64Key::new(round), &round_info)?;
+    CLAIMS.save(deps.storage, (&info.sender, U64Key::new(round)), &true)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(payout_msg(deps.as_ref(), &config.asset, &info.sender, amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("round", round.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut round_info = ROUNDS.load(deps.storage, U64Key::new(round))?;
+    if !round_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::RoundNotExpired { round });
+    }
+
+    let amount = round_info.unclaimed_amount();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    round_info.withdrawn_amount = round_info.withdrawn_amount.checked_add(amount)?;
+    ROUNDS.save(deps.storage, U64Key::new(round), &round_info)?;
+
+    Ok(Response::new()
+        .add_message(payout_msg(deps.as_ref(), &config.asset, &recipient, amount)?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("round", round.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+/// Checks that `sha256(address + amount)` is a leaf of the tree with the given root.
+/// Sibling hashes are sorted before hashing so the proof does not need to carry positions,
+/// the trees are built like the ones of the airdrop.
+fn verify_merkle_proof(
+    merkle_root: &str,
+    address: &str,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<(), ContractError> {
+    let leaf = format!("{}{}", address, amount);
+    let hash: [u8; 32] = sha2::Sha256::digest(leaf.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| ContractError::WrongLength {})?;
+
+    let hash = proof.into_iter().try_fold(hash, |hash, p| {
+        let mut proof_buf = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        sha2::Sha256::digest(&hashes.concat())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::WrongLength {})
+    })?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    if root_buf != hash {
+        return Err(ContractError::VerificationFailed {});
+    }
+
+    Ok(())
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+/// The recipients of a native coin pay the tax out of the amount, the contract holds no more
+/// than the round totals
+fn payout_msg(deps: Deps, asset: &Asset, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    asset.transfer_msg(recipient, asset.deduct_tax(&deps.querier, amount)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Round { round } => to_binary(&query_round(deps, round)?),
+        QueryMsg::LatestRound {} => to_binary(&LatestRoundResponse {
+            latest_round: LATEST_ROUND.load(deps.storage)?,
+        }),
+        QueryMsg::IsClaimed { round, address } => to_binary(&query_is_claimed(deps, round, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        asset: config.asset,
+    })
+}
+
+pub fn query_round(deps: Deps, round: u64) -> StdResult<RoundResponse> {
+    let round_info = ROUNDS.load(deps.storage, U64Key::new(round))?;
+    Ok(RoundResponse {
+        round,
+        merkle_root: round_info.merkle_root,
+        expiration: round_info.expiration,
+        total_amount: round_info.total_amount,
+        claimed_amount: round_info.claimed_amount,
+        withdrawn_amount: round_info.withdrawn_amount,
+    })
+}
+
+pub fn query_is_claimed(deps: Deps, round: u64, address: String) -> StdResult<IsClaimedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(IsClaimedResponse {
+        is_claimed: CLAIMS.may_load(deps.storage, (&address, U64Key::new(round)))?.unwrap_or(false),
+    })
+}
@@ -0,0 +1,52 @@
+use cosmwasm_std::{OverflowError, StdError};
+use hex::FromHexError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("Already claimed")]
+    Claimed {},
+
+    #[error("Wrong length")]
+    WrongLength {},
+
+    #[error("Verification failed")]
+    VerificationFailed {},
+
+    #[error("Round {round} claim period has expired")]
+    RoundExpired { round: u64 },
+
+    #[error("Round {round} claim period has not expired yet")]
+    RoundNotExpired { round: u64 },
+
+    #[error("Claimed amount exceeds the round total")]
+    RoundTotalExceeded {},
+
+    #[error("Nothing left to withdraw")]
+    NothingToWithdraw {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
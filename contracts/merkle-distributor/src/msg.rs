@@ -0,0 +1,102 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Expiration};
+use luart_types::asset::Asset;
+
+pub use luart_protocol::distributor::InstantiateMsg;
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Registers a round funded with the received tokens, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Registers a round funded with the sent coins, their sum of the allocations.
+    /// Only the owner can execute it, when the distributed asset is a native coin.
+    RegisterRound {
+        /// Hex encoded sha256 merkle root
+        merkle_root: String,
+        /// Claim deadline of the round, never expires if unset
+        expiration: Option<Expiration>,
+    },
+    /// Claims the sender allocation of the given round
+    Claim {
+        round: u64,
+        amount: Uint128,
+        /// Hex encoded merkle proof
+        proof: Vec<String>,
+    },
+    /// Transfers the unclaimed amount of an expired round to the recipient.
+    /// Only the owner can execute it.
+    Withdraw { round: u64, recipient: String },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good, disabling the owner actions. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Registers a round funded with the received tokens, the sum of its allocations.
+    /// Only the owner can send it, when the distributed asset is the token.
+    RegisterRound {
+        merkle_root: String,
+        expiration: Option<Expiration>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the round merkle root and claim accounting.
+    /// Return type: RoundResponse.
+    Round { round: u64 },
+    /// Returns the latest registered round.
+    /// Return type: LatestRoundResponse.
+    LatestRound {},
+    /// Returns whether the address has claimed its round allocation.
+    /// Return type: IsClaimedResponse.
+    IsClaimed { round: u64, address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub asset: Asset,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundResponse {
+    pub round: u64,
+    pub merkle_root: String,
+    pub expiration: Expiration,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub withdrawn_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LatestRoundResponse {
+    pub latest_round: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_types::asset::Asset;
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who registers the rounds and withdraws the unclaimed amounts
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The native coin or cw20 token distributed
+    pub asset: Asset,
+}
+
+impl Config {
+    pub fn is_owner(&self, address: &Addr) -> bool {
+        self.owner.as_ref() == Some(address)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Round {
+    /// Hex encoded merkle root of the round allocations
+    pub merkle_root: String,
+    /// Claims are rejected and the remainder can be withdrawn after this expiration
+    pub expiration: Expiration,
+    /// The amount funded when registering the round, the sum of its allocations
+    pub total_amount: Uint128,
+    /// The amount already claimed
+    pub claimed_amount: Uint128,
+    /// The unclaimed amount withdrawn by the owner after expiration
+    pub withdrawn_amount: Uint128,
+}
+
+impl Round {
+    pub fn unclaimed_amount(&self) -> Uint128 {
+        self.total_amount
+            .saturating_sub(self.claimed_amount)
+            .saturating_sub(self.withdrawn_amount)
+    }
+}
+
+pub const LATEST_ROUND: Item<u64> = Item::new("latest_round");
+pub const ROUNDS: Map<U64Key, Round> = Map::new("rounds");
+pub const CLAIMS: Map<(&Addr, U64Key), bool> = Map::new("claims");
@@ -0,0 +1,178 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, CosmosMsg, from_binary, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
+use luart_types::asset::Asset;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestRoundResponse, QueryMsg, RoundResponse,
+};
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const TREASURY: &str = "mock_treasury";
+
+// Tree of (addr0001, 1000), (addr0002, 2000), (addr0003, 3000)
+const MERKLE_ROOT: &str = "1d1dad5be17f102b36c9ea951aefd07d05483d564bd016db61a70c14525aef0e";
+
+fn proof_addr0001() -> Vec<String> {
+    vec![
+        "4c25f57a1f2175d3744c84a73bee53d07782f4d65e8bcd861311a68cb8fdaa79".to_string(),
+        "c0316a8ffd9fe8c428c6e5cc6aecfdeb1e5de83a59196e1aa901ec63d3c4b1a9".to_string(),
+    ]
+}
+
+fn proof_addr0003() -> Vec<String> {
+    vec!["ec6d9ecefe96129ee828831ec07df5eb56e53e4b76d083229e704e6b5398e182".to_string()]
+}
+
+fn claim_msg(round: u64, amount: u128, proof: Vec<String>) -> ExecuteMsg {
+    ExecuteMsg::Claim {
+        round,
+        amount: Uint128::new(amount),
+        proof,
+    }
+}
+
+#[test]
+fn test_native_rounds() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), InstantiateMsg {
+        owner: None,
+        asset: Asset::Native("uusd".to_string()),
+    }).unwrap();
+
+    let register = ExecuteMsg::RegisterRound {
+        merkle_root: MERKLE_ROOT.to_string(),
+        expiration: None,
+    };
+
+    // Only the owner can register a round
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[Coin::new(6000, "uusd")]),
+                      register.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The round is funded with the distributed coin
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[Coin::new(6000, "uluna")]),
+                      register.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: "uusd".to_string() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[Coin::new(6000, "uusd")]), register.clone()).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[Coin::new(6000, "uusd")]), register).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::LatestRound {}).unwrap();
+    let latest: LatestRoundResponse = from_binary(&res).unwrap();
+    assert_eq!(latest.latest_round, 2);
+
+    // Wrong amount fails the proof verification
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      claim_msg(1, 1001, proof_addr0001())).unwrap_err();
+    assert_eq!(err, ContractError::VerificationFailed {});
+
+    // The recipient pays the tax out of the allocation
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      claim_msg(1, 1000, proof_addr0001())).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "addr0001".to_string(),
+        amount: vec![Coin::new(995, "uusd")],
+    }))]);
+
+    // Cannot claim twice from the same round, the next round is claimed separately
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      claim_msg(1, 1000, proof_addr0001())).unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+    execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]), claim_msg(2, 1000, proof_addr0001())).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::IsClaimed {
+        round: 1,
+        address: "addr0003".to_string(),
+    }).unwrap();
+    let claimed: IsClaimedResponse = from_binary(&res).unwrap();
+    assert!(!claimed.is_claimed);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Round { round: 1 }).unwrap();
+    let round: RoundResponse = from_binary(&res).unwrap();
+    assert_eq!(round, RoundResponse {
+        round: 1,
+        merkle_root: MERKLE_ROOT.to_string(),
+        expiration: Expiration::Never {},
+        total_amount: Uint128::new(6000),
+        claimed_amount: Uint128::new(1000),
+        withdrawn_amount: Uint128::zero(),
+    });
+}
+
+#[test]
+fn test_cw20_round_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), InstantiateMsg {
+        owner: None,
+        asset: Asset::Cw20(Addr::unchecked(TOKEN)),
+    }).unwrap();
+
+    let hook = |sender: &str| ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(6000),
+        msg: to_binary(&Cw20HookMsg::RegisterRound {
+            merkle_root: MERKLE_ROOT.to_string(),
+            expiration: Some(Expiration::AtHeight(env.block.height + 100)),
+        }).unwrap(),
+    });
+
+    // Only the owner can fund a round, with the distributed token
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), hook("anyone")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]), hook(OWNER)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), hook(OWNER)).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0003", &[]),
+                      claim_msg(1, 3000, proof_addr0003())).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: "addr0003".to_string(),
+            amount: Uint128::new(3000),
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+
+    let withdraw = ExecuteMsg::Withdraw {
+        round: 1,
+        recipient: TREASURY.to_string(),
+    };
+
+    // Cannot withdraw before the claim deadline
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), withdraw.clone()).unwrap_err();
+    assert_eq!(err, ContractError::RoundNotExpired { round: 1 });
+
+    env.block.height += 100;
+
+    // Claims are closed after the deadline
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]),
+                      claim_msg(1, 1000, proof_addr0001())).unwrap_err();
+    assert_eq!(err, ContractError::RoundExpired { round: 1 });
+
+    // Only the owner can withdraw
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]), withdraw.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), withdraw.clone()).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: TREASURY.to_string(),
+            amount: Uint128::new(3000),
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]), withdraw).unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+}
@@ -0,0 +1,256 @@
+use cosmwasm_std::{
+    Addr, Binary, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Response, StdError,
+    StdResult, to_binary, Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use luart_types::asset::Asset as LuartAsset;
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SimulationResponse,
+};
+use crate::state::{Config, CONFIG};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-mock-pair";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let commission_rate = msg.commission_rate.unwrap_or_else(Decimal::zero);
+    validate_price(msg.price, commission_rate)?;
+    for asset_info in msg.asset_infos.iter() {
+        if let AssetInfo::Token { contract_addr } = asset_info {
+            deps.api.addr_validate(contract_addr)?;
+        }
+    }
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        asset_infos: msg.asset_infos,
+        price: msg.price,
+        commission_rate,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::Swap {
+            offer_asset,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            let denom = match &offer_asset.info {
+                AssetInfo::NativeToken { denom } => denom,
+                AssetInfo::Token { .. } => return Err(ContractError::Unauthorized {}),
+            };
+            let sent = info.funds.iter()
+                .find(|coin| &coin.denom == denom)
+                .map(|coin| coin.amount)
+                .unwrap_or_default();
+            if sent != offer_asset.amount {
+                return Err(ContractError::InvalidFunds {});
+            }
+            swap(deps, info.sender, offer_asset, belief_price, max_spread, to)
+        }
+        ExecuteMsg::UpdatePrice {
+            price,
+            commission_rate,
+        } => update_price(deps, info, price, commission_rate),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Swap {
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            let offer_asset = Asset {
+                info: AssetInfo::Token { contract_addr: info.sender.to_string() },
+                amount: cw20_msg.amount,
+            };
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            swap(deps, sender, offer_asset, belief_price, max_spread, to)
+        }
+    }
+}
+
+/// Pays the other asset of the pair at the configured price, less the commission
+pub fn swap(
+    deps: DepsMut,
+    sender: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let (ask_info, simulation) = compute_swap(&config, &offer_asset)?;
+    if simulation.return_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    assert_max_spread(belief_price, max_spread, offer_asset.amount, simulation.return_amount)?;
+
+    let receiver = match to {
+        Some(to) => deps.api.addr_validate(&to)?,
+        None => sender.clone(),
+    };
+    let offer = luart_asset(&offer_asset.info);
+    let ask = luart_asset(&ask_info);
+
+    Ok(Response::new()
+        .add_message(ask.transfer_msg(&receiver, simulation.return_amount)?)
+        .add_attribute("action", "swap")
+        .add_attribute("sender", sender)
+        .add_attribute("receiver", receiver)
+        .add_attribute("offer_asset", offer.to_string())
+        .add_attribute("ask_asset", ask.to_string())
+        .add_attribute("offer_amount", offer_asset.amount)
+        .add_attribute("return_amount", simulation.return_amount)
+        .add_attribute("spread_amount", simulation.spread_amount)
+        .add_attribute("commission_amount", simulation.commission_amount))
+}
+
+pub fn update_price(
+    deps: DepsMut,
+    info: MessageInfo,
+    price: Option<Decimal>,
+    commission_rate: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(price) = price {
+        config.price = price;
+    }
+
+    if let Some(commission_rate) = commission_rate {
+        config.commission_rate = commission_rate;
+    }
+    validate_price(config.price, config.commission_rate)?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_price")
+        .add_attribute("price", config.price.to_string()))
+}
+
+fn validate_price(price: Decimal, commission_rate: Decimal) -> Result<(), ContractError> {
+    if price.is_zero() || commission_rate > Decimal::one() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    Ok(())
+}
+
+/// Returns the asset received for the offer asset and the swap amounts
+fn compute_swap(config: &Config, offer_asset: &Asset) -> Result<(AssetInfo, SimulationResponse), ContractError> {
+    let (ask_info, gross_amount) = if offer_asset.info == config.asset_infos[0] {
+        (config.asset_infos[1].clone(), offer_asset.amount * config.price)
+    } else if offer_asset.info == config.asset_infos[1] {
+        let amount = offer_asset.amount.multiply_ratio(DECIMAL_FRACTION, DECIMAL_FRACTION * config.price);
+        (config.asset_infos[0].clone(), amount)
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    };
+
+    let commission_amount = gross_amount * config.commission_rate;
+    Ok((ask_info, SimulationResponse {
+        return_amount: gross_amount - commission_amount,
+        spread_amount: Uint128::zero(),
+        commission_amount,
+    }))
+}
+
+/// Fails when the return amount is below the amount expected at the belief price,
+/// the offer amount divided by it, by more than the max spread
+fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+) -> Result<(), ContractError> {
+    if let (Some(belief_price), Some(max_spread)) = (belief_price, max_spread) {
+        let expected_return = offer_amount.multiply_ratio(DECIMAL_FRACTION, DECIMAL_FRACTION * belief_price);
+        let spread_amount = expected_return.saturating_sub(return_amount);
+        if return_amount < expected_return && Decimal::from_ratio(spread_amount, expected_return) > max_spread {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    }
+    Ok(())
+}
+
+fn luart_asset(info: &AssetInfo) -> LuartAsset {
+    match info {
+        AssetInfo::NativeToken { denom } => LuartAsset::Native(denom.clone()),
+        AssetInfo::Token { contract_addr } => LuartAsset::Cw20(Addr::unchecked(contract_addr)),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Simulation { offer_asset } => to_binary(&query_simulation(deps, offer_asset)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        asset_infos: config.asset_infos,
+        price: config.price,
+        commission_rate: config.commission_rate,
+    })
+}
+
+pub fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (_, simulation) = compute_swap(&config, &offer_asset)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(simulation)
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("The price must be positive and the commission rate at most 1")]
+    InvalidPrice {},
+
+    #[error("The asset is not traded by the pair")]
+    AssetMismatch {},
+
+    #[error("Native token balance mismatch between the argument and the transferred")]
+    InvalidFunds {},
+
+    #[error("Operation exceeds max spread limit")]
+    MaxSpreadAssertion {},
+}
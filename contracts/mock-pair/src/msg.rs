@@ -0,0 +1,82 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use terraswap::asset::{Asset, AssetInfo};
+
+/// This structure describes the parameters used for creating a mock pair contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub asset_infos: [AssetInfo; 2],
+    /// The amount of the second asset swapped for one unit of the first asset
+    pub price: Decimal,
+    /// The share of the return amount kept by the pair, defaults to zero
+    pub commission_rate: Option<Decimal>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// The swap messages follow the terraswap pair interface. The pair pays the swaps out of
+/// its own balance, it has to be funded with both assets beforehand.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Swaps the sent cw20 tokens, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Swaps the native coins sent along the message
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+    /// Updates the swap price and commission. Only the owner can execute it.
+    UpdatePrice {
+        price: Option<Decimal>,
+        commission_rate: Option<Decimal>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swaps the received tokens for the other asset, sent to the sender or to `to`
+    Swap {
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the result of swapping the offer asset.
+    /// Return type: SimulationResponse.
+    Simulation { offer_asset: Asset },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub asset_infos: [AssetInfo; 2],
+    pub price: Decimal,
+    pub commission_rate: Decimal,
+}
+
+/// The terraswap simulation response, the spread is always zero at a fixed price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
@@ -0,0 +1,18 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::Item;
+use terraswap::asset::AssetInfo;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub asset_infos: [AssetInfo; 2],
+    /// The amount of the second asset swapped for one unit of the first asset
+    pub price: Decimal,
+    /// The share of the return amount kept by the pair
+    pub commission_rate: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
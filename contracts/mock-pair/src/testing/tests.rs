@@ -0,0 +1,127 @@
+use cosmwasm_std::{
+    BankMsg, Coin, coins, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SimulationResponse};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const TRADER: &str = "mock_trader";
+const DENOM: &str = "uusd";
+
+/// One token is worth 2 uusd, 1% of the return amount is kept as commission
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        asset_infos: [
+            AssetInfo::Token { contract_addr: TOKEN.to_string() },
+            AssetInfo::NativeToken { denom: DENOM.to_string() },
+        ],
+        price: Decimal::from_ratio(2u128, 1u128),
+        commission_rate: Some(Decimal::percent(1)),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn cw20_swap_msg(amount: u128, belief_price: Option<Decimal>, max_spread: Option<Decimal>) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TRADER.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Swap { belief_price, max_spread, to: None }).unwrap(),
+    })
+}
+
+#[test]
+fn test_swap_token() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]), cw20_swap_msg(1_000, None, None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), cw20_swap_msg(1_000, None, None)).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+        to_address: TRADER.to_string(),
+        amount: coins(1_980, DENOM),
+    })]);
+
+    // The return is 1% below the belief price because of the commission
+    let belief_price = Some(Decimal::percent(50));
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]),
+                      cw20_swap_msg(1_000, belief_price, Some(Decimal::permille(5)))).unwrap_err();
+    assert_eq!(err, ContractError::MaxSpreadAssertion {});
+    execute(deps.as_mut(), env, mock_info(TOKEN, &[]), cw20_swap_msg(1_000, belief_price, Some(Decimal::percent(1))))
+        .unwrap();
+}
+
+#[test]
+fn test_swap_native() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let swap_msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+            amount: Uint128::new(1_000),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: Some("mock_receiver".to_string()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TRADER, &coins(999, DENOM)), swap_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TRADER, &coins(1_000, DENOM)), swap_msg.clone())
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: "mock_receiver".to_string(),
+            amount: Uint128::new(495),
+        }).unwrap(),
+        funds: vec![],
+    })]);
+
+    // Only the owner moves the price
+    let update_msg = ExecuteMsg::UpdatePrice { price: Some(Decimal::from_ratio(4u128, 1u128)), commission_rate: None };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TRADER, &[]), update_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdatePrice {
+        price: Some(Decimal::zero()),
+        commission_rate: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPrice {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_msg).unwrap();
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        amount: Uint128::new(1_000),
+    };
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Simulation { offer_asset }).unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(simulation.return_amount, Uint128::new(248));
+    assert_eq!(simulation.commission_amount, Uint128::new(2));
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken { denom: "uluna".to_string() },
+        amount: Uint128::new(1_000),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(TRADER, &[Coin::new(1_000, "uluna")]), ExecuteMsg::Swap {
+        offer_asset,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+}
@@ -0,0 +1,307 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, Member, MembersResponse, MigrateMsg,
+    ProposalResponse, ProposalsResponse, QueryMsg, VoteResponse,
+};
+use crate::state::{
+    BALLOTS, Config, CONFIG, MEMBERS, Proposal, PROPOSAL_COUNT, ProposalStatus, PROPOSALS, Vote,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-multisig";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut total_weight = 0u64;
+    for member in msg.members.iter() {
+        let address = deps.api.addr_validate(&member.address)?;
+        if member.weight == 0 || MEMBERS.has(deps.storage, &address) {
+            return Err(ContractError::InvalidConfig {
+                reason: "members must be unique with a positive weight".to_string(),
+            });
+        }
+        MEMBERS.save(deps.storage, &address, &member.weight)?;
+        total_weight += member.weight;
+    }
+
+    if msg.threshold == 0 || msg.threshold > total_weight {
+        return Err(ContractError::InvalidConfig {
+            reason: "threshold must be positive and reachable by the members".to_string(),
+        });
+    }
+    if msg.max_voting_period == 0 {
+        return Err(ContractError::InvalidConfig { reason: "voting period must be positive".to_string() });
+    }
+
+    CONFIG.save(deps.storage, &Config {
+        threshold: msg.threshold,
+        total_weight,
+        max_voting_period: msg.max_voting_period,
+    })?;
+    PROPOSAL_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            messages,
+            voting_period,
+        } => propose(deps, env, info, title, description, messages, voting_period),
+        ExecuteMsg::Vote { proposal_id, vote } => cast_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Execute { proposal_id } => execute_proposal(deps, proposal_id),
+        ExecuteMsg::Close { proposal_id } => close(deps, env, proposal_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    messages: Vec<CosmosMsg>,
+    voting_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let weight = MEMBERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let voting_period = voting_period.unwrap_or(config.max_voting_period);
+    if voting_period == 0 || voting_period > config.max_voting_period {
+        return Err(ContractError::InvalidVotingPeriod { max: config.max_voting_period });
+    }
+
+    // The proposer votes yes, a single member above the threshold passes it right away
+    let mut proposal = Proposal {
+        proposer: info.sender.clone(),
+        title,
+        description,
+        messages,
+        status: ProposalStatus::Open,
+        expires_at: env.block.time.seconds() + voting_period,
+        yes_weight: weight,
+        no_weight: 0,
+    };
+    proposal.update_status(&config);
+
+    let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+    BALLOTS.save(deps.storage, (U64Key::new(proposal_id), &info.sender), &Vote::Yes)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposer", info.sender))
+}
+
+pub fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let weight = MEMBERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Open {
+        return Err(ContractError::ProposalNotOpen {});
+    }
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::ProposalExpired {});
+    }
+    if BALLOTS.has(deps.storage, (U64Key::new(proposal_id), &info.sender)) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+
+    match vote {
+        Vote::Yes => proposal.yes_weight += weight,
+        Vote::No => proposal.no_weight += weight,
+    }
+    proposal.update_status(&config);
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+    BALLOTS.save(deps.storage, (U64Key::new(proposal_id), &info.sender), &vote)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender))
+}
+
+pub fn execute_proposal(
+    deps: DepsMut,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // A passed proposal stays executable after its voting period
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(ContractError::ProposalNotPassed {});
+    }
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_messages(proposal.messages)
+        .add_attribute("action", "execute")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn close(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Open {
+        return Err(ContractError::ProposalNotOpen {});
+    }
+    if env.block.time.seconds() < proposal.expires_at {
+        return Err(ContractError::ProposalNotExpired {});
+    }
+    proposal.status = ProposalStatus::Rejected;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
+        QueryMsg::Members {} => to_binary(&query_members(deps)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        threshold: config.threshold,
+        total_weight: config.total_weight,
+        max_voting_period: config.max_voting_period,
+    })
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    Ok(proposal_response(proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let proposals: StdResult<Vec<ProposalResponse>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, proposal) = item?;
+            Ok(proposal_response(parse_proposal_id(&k)?, proposal))
+        })
+        .collect();
+
+    Ok(ProposalsResponse { proposals: proposals? })
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let vote = BALLOTS.may_load(deps.storage, (U64Key::new(proposal_id), &voter))?;
+    Ok(VoteResponse { vote })
+}
+
+pub fn query_members(deps: Deps) -> StdResult<MembersResponse> {
+    // The membership is fixed and small, no pagination needed
+    let members: StdResult<Vec<Member>> = MEMBERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (k, weight) = item?;
+            let address = String::from_utf8(k)
+                .map_err(|_| StdError::generic_err("Corrupted member key"))?;
+            Ok(Member { address, weight })
+        })
+        .collect();
+
+    Ok(MembersResponse { members: members? })
+}
+
+fn proposal_response(proposal_id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        proposal_id,
+        proposer: proposal.proposer.to_string(),
+        title: proposal.title,
+        description: proposal.description,
+        messages: proposal.messages,
+        status: proposal.status,
+        expires_at: proposal.expires_at,
+        yes_weight: proposal.yes_weight,
+        no_weight: proposal.no_weight,
+    }
+}
+
+fn parse_proposal_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted proposal key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid multisig configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("The voting period must be between 1 and {max} seconds")]
+    InvalidVotingPeriod { max: u64 },
+
+    #[error("The proposal is not open for voting")]
+    ProposalNotOpen {},
+
+    #[error("The proposal voting period has expired")]
+    ProposalExpired {},
+
+    #[error("The proposal voting period has not expired yet")]
+    ProposalNotExpired {},
+
+    #[error("The proposal has not passed")]
+    ProposalNotPassed {},
+
+    #[error("Already voted")]
+    AlreadyVoted {},
+}
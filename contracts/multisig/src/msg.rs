@@ -0,0 +1,107 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::CosmosMsg;
+
+use crate::state::{ProposalStatus, Vote};
+
+/// This structure describes the parameters used for creating a multisig contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The fixed members, they can not be changed after instantiation
+    pub members: Vec<Member>,
+    pub threshold: u64,
+    pub max_voting_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Member {
+    pub address: String,
+    pub weight: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Proposes messages and votes yes with the proposer weight.
+    /// Only a member can execute it.
+    Propose {
+        title: String,
+        description: String,
+        messages: Vec<CosmosMsg>,
+        /// Seconds the proposal is open for voting, the maximum voting period by default
+        voting_period: Option<u64>,
+    },
+    /// Votes on an open proposal. Only a member can execute it.
+    Vote { proposal_id: u64, vote: Vote },
+    /// Executes the messages of a passed proposal
+    Execute { proposal_id: u64 },
+    /// Rejects a proposal which has not passed before its expiration
+    Close { proposal_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the threshold and voting parameters.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a proposal.
+    /// Return type: ProposalResponse.
+    Proposal { proposal_id: u64 },
+    /// Returns proposals by id.
+    /// Return type: ProposalsResponse.
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the vote of a member on a proposal.
+    /// Return type: VoteResponse.
+    Vote { proposal_id: u64, voter: String },
+    /// Returns the members and their weights.
+    /// Return type: MembersResponse.
+    Members {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub threshold: u64,
+    pub total_weight: u64,
+    pub max_voting_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub title: String,
+    pub description: String,
+    pub messages: Vec<CosmosMsg>,
+    pub status: ProposalStatus,
+    pub expires_at: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteResponse {
+    pub vote: Option<Vote>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MembersResponse {
+    pub members: Vec<Member>,
+}
@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The weight of yes votes required for a proposal to pass
+    pub threshold: u64,
+    /// The sum of the member weights
+    pub total_weight: u64,
+    /// Maximum seconds a proposal is open for voting
+    pub max_voting_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    Passed,
+    Rejected,
+    Executed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Yes,
+    No,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub messages: Vec<CosmosMsg>,
+    pub status: ProposalStatus,
+    pub expires_at: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+}
+
+impl Proposal {
+    /// Passes once the threshold is reached, rejects once it can not be reached anymore
+    pub fn update_status(&mut self, config: &Config) {
+        if self.status != ProposalStatus::Open {
+            return;
+        }
+        if self.yes_weight >= config.threshold {
+            self.status = ProposalStatus::Passed;
+        } else if config.total_weight - self.no_weight < config.threshold {
+            self.status = ProposalStatus::Rejected;
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const MEMBERS: Map<&Addr, u64> = Map::new("members");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+pub const BALLOTS: Map<(U64Key, &Addr), Vote> = Map::new("ballots");
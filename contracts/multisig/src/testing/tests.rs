@@ -0,0 +1,162 @@
+use cosmwasm_std::{BankMsg, coins, CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, Member, MembersResponse, ProposalResponse, QueryMsg, VoteResponse,
+};
+use crate::state::{ProposalStatus, Vote};
+
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const CAROL: &str = "carol";
+
+const MAX_VOTING_PERIOD: u64 = 7 * 86400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        members: vec![
+            Member { address: ALICE.to_string(), weight: 1 },
+            Member { address: BOB.to_string(), weight: 1 },
+            Member { address: CAROL.to_string(), weight: 1 },
+        ],
+        threshold: 2,
+        max_voting_period: MAX_VOTING_PERIOD,
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+fn send_msg() -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: "recipient".to_string(),
+        amount: coins(1_000, "uusd"),
+    })
+}
+
+fn propose_msg(voting_period: Option<u64>) -> ExecuteMsg {
+    ExecuteMsg::Propose {
+        title: "Pay the recipient".to_string(),
+        description: "Pay the recipient 1000 uusd".to_string(),
+        messages: vec![send_msg()],
+        voting_period,
+    }
+}
+
+#[test]
+fn test_instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+
+    let msg = InstantiateMsg {
+        members: vec![
+            Member { address: ALICE.to_string(), weight: 1 },
+            Member { address: ALICE.to_string(), weight: 2 },
+        ],
+        threshold: 2,
+        max_voting_period: MAX_VOTING_PERIOD,
+    };
+    let err = instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig {
+        reason: "members must be unique with a positive weight".to_string(),
+    });
+
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        members: vec![Member { address: ALICE.to_string(), weight: 1 }],
+        threshold: 2,
+        max_voting_period: MAX_VOTING_PERIOD,
+    };
+    let err = instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidConfig {
+        reason: "threshold must be positive and reachable by the members".to_string(),
+    });
+
+    let mut deps = mock_dependencies(&[]);
+    default_instantiate(deps.as_mut(), env.clone());
+    let res = query(deps.as_ref(), env, QueryMsg::Members {}).unwrap();
+    let members: MembersResponse = from_binary(&res).unwrap();
+    assert_eq!(members.members.len(), 3);
+    assert_eq!(members.members[0], Member { address: ALICE.to_string(), weight: 1 });
+}
+
+#[test]
+fn test_vote_and_execute() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), propose_msg(None)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      propose_msg(Some(MAX_VOTING_PERIOD + 1))).unwrap_err();
+    assert_eq!(err, ContractError::InvalidVotingPeriod { max: MAX_VOTING_PERIOD });
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), propose_msg(None)).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]),
+                      ExecuteMsg::Vote { proposal_id: 1, vote: Vote::Yes }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+
+    // The second yes vote reaches the threshold
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]),
+            ExecuteMsg::Vote { proposal_id: 1, vote: Vote::Yes }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(CAROL, &[]),
+                      ExecuteMsg::Vote { proposal_id: 1, vote: Vote::No }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotOpen {});
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Vote { proposal_id: 1, voter: BOB.to_string() }).unwrap();
+    let vote: VoteResponse = from_binary(&res).unwrap();
+    assert_eq!(vote.vote, Some(Vote::Yes));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(send_msg())]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.yes_weight, 2);
+    assert_eq!(proposal.expires_at, env.block.time.seconds() + MAX_VOTING_PERIOD);
+
+    let err = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotPassed {});
+}
+
+#[test]
+fn test_reject_and_close() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), propose_msg(None)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), propose_msg(Some(86400))).unwrap();
+
+    // Two no votes make the threshold unreachable
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]),
+            ExecuteMsg::Vote { proposal_id: 1, vote: Vote::No }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(CAROL, &[]),
+            ExecuteMsg::Vote { proposal_id: 1, vote: Vote::No }).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Rejected);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Close { proposal_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotExpired {});
+
+    env.block.time = env.block.time.plus_seconds(86400);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]),
+                      ExecuteMsg::Vote { proposal_id: 2, vote: Vote::Yes }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalExpired {});
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Close { proposal_id: 2 }).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::Proposal { proposal_id: 2 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Rejected);
+}
@@ -0,0 +1,318 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Order as StorageOrder, Response,
+    StdError, StdResult, to_binary, Uint128,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::{Bound, U64Key};
+use luart_types::asset::Asset;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderResponse, OrdersResponse, QueryMsg,
+};
+use crate::state::{Config, CONFIG, ConfigOwner, Order, ORDER_COUNT, ORDERS, Side};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-otc";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.fee_rate > Decimal::one() {
+        return Err(ContractError::InvalidFeeRate {});
+    }
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        token: deps.api.addr_validate(&msg.token)?,
+        denom: msg.denom,
+        fee_rate: msg.fee_rate,
+        fee_distributor: deps.api.addr_validate(&msg.fee_distributor)?,
+    })?;
+    ORDER_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::PostBuyOrder { ask_amount } => {
+            let config = CONFIG.load(deps.storage)?;
+            let amount = must_pay(&info, &config.denom)?;
+            post_order(deps, info.sender, Side::Buy, amount, ask_amount)
+        }
+        ExecuteMsg::FillSellOrder { order_id } => {
+            let config = CONFIG.load(deps.storage)?;
+            let amount = must_pay(&info, &config.denom)?;
+            fill_order(deps, config, order_id, Side::Sell, info.sender, amount)
+        }
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+        ExecuteMsg::UpdateConfig { fee_rate, fee_distributor } => {
+            update_config(deps, info, fee_rate, fee_distributor)
+        }
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(ContractError::Unauthorized {});
+    }
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::PostSellOrder { ask_amount } => post_order(deps, sender, Side::Sell, cw20_msg.amount, ask_amount),
+        Cw20HookMsg::FillBuyOrder { order_id } => {
+            fill_order(deps, config, order_id, Side::Buy, sender, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn post_order(
+    deps: DepsMut,
+    maker: Addr,
+    side: Side,
+    offer_amount: Uint128,
+    ask_amount: Uint128,
+) -> Result<Response, ContractError> {
+    if ask_amount.is_zero() {
+        return Err(StdError::generic_err("The order must ask for a non-zero amount").into());
+    }
+
+    let order_id = ORDER_COUNT.load(deps.storage)? + 1;
+    ORDER_COUNT.save(deps.storage, &order_id)?;
+    ORDERS.save(deps.storage, U64Key::new(order_id), &Order {
+        maker: maker.clone(),
+        side,
+        offer_amount,
+        ask_amount,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "post_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("maker", maker)
+        .add_attribute("offer_amount", offer_amount)
+        .add_attribute("ask_amount", ask_amount))
+}
+
+/// Fills the order with the amount the taker paid in the asset the order asks for. The taker
+/// receives the escrow pro-rata, the rounding favours the maker.
+pub fn fill_order(
+    deps: DepsMut,
+    config: Config,
+    order_id: u64,
+    side: Side,
+    taker: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+    if order.side != side {
+        return Err(StdError::generic_err("The order is on the other side").into());
+    }
+    if amount > order.ask_amount {
+        return Err(ContractError::FillExceedsOrder { amount, ask_amount: order.ask_amount });
+    }
+
+    let received = order.offer_amount.multiply_ratio(amount, order.ask_amount);
+    if received.is_zero() {
+        return Err(ContractError::FillTooSmall {});
+    }
+    order.offer_amount = order.offer_amount.checked_sub(received)?;
+    order.ask_amount = order.ask_amount.checked_sub(amount)?;
+    if order.offer_amount.is_zero() || order.ask_amount.is_zero() {
+        ORDERS.remove(deps.storage, U64Key::new(order_id));
+    } else {
+        ORDERS.save(deps.storage, U64Key::new(order_id), &order)?;
+    }
+
+    let (offer_asset, ask_asset) = order_assets(&config, side);
+    let fee_amount = received * config.fee_rate;
+    let mut messages = vec![
+        payout_msg(deps.as_ref(), &ask_asset, &order.maker, amount)?,
+        payout_msg(deps.as_ref(), &offer_asset, &taker, received.checked_sub(fee_amount)?)?,
+    ];
+    if !fee_amount.is_zero() {
+        messages.push(payout_msg(deps.as_ref(), &offer_asset, &config.fee_distributor, fee_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "fill_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("taker", taker)
+        .add_attribute("paid", amount)
+        .add_attribute("received", received)
+        .add_attribute("fee_amount", fee_amount))
+}
+
+pub fn cancel_order(deps: DepsMut, info: MessageInfo, order_id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+    if info.sender != order.maker {
+        return Err(ContractError::Unauthorized {});
+    }
+    ORDERS.remove(deps.storage, U64Key::new(order_id));
+
+    let (offer_asset, _) = order_assets(&config, order.side);
+    Ok(Response::new()
+        .add_message(payout_msg(deps.as_ref(), &offer_asset, &order.maker, order.offer_amount)?)
+        .add_attribute("action", "cancel_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("refund", order.offer_amount))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_rate: Option<Decimal>,
+    fee_distributor: Option<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(fee_rate) = fee_rate {
+        if fee_rate > Decimal::one() {
+            return Err(ContractError::InvalidFeeRate {});
+        }
+        config.fee_rate = fee_rate;
+    }
+    if let Some(fee_distributor) = fee_distributor {
+        config.fee_distributor = deps.api.addr_validate(&fee_distributor)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+/// The escrowed asset and the asked asset of the orders of the side
+fn order_assets(config: &Config, side: Side) -> (Asset, Asset) {
+    let token = Asset::Cw20(config.token.clone());
+    let native = Asset::Native(config.denom.clone());
+    match side {
+        Side::Sell => (token, native),
+        Side::Buy => (native, token),
+    }
+}
+
+/// The recipients of the native coin pay the tax out of the amount, the contract holds no more
+/// than the escrows
+fn payout_msg(deps: Deps, asset: &Asset, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    asset.transfer_msg(recipient, asset.deduct_tax(&deps.querier, amount)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Order { order_id } => {
+            to_binary(&order_response(order_id, ORDERS.load(deps.storage, U64Key::new(order_id))?))
+        }
+        QueryMsg::Orders { side, start_after, limit } => to_binary(&query_orders(deps, side, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        token: config.token.to_string(),
+        denom: config.denom,
+        fee_rate: config.fee_rate,
+        fee_distributor: config.fee_distributor.to_string(),
+    })
+}
+
+pub fn query_orders(
+    deps: Deps,
+    side: Option<Side>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let orders: StdResult<Vec<OrderResponse>> = ORDERS
+        .range(deps.storage, start, None, StorageOrder::Ascending)
+        .filter(|item| match (item, side) {
+            (Ok((_, order)), Some(side)) => order.side == side,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (k, order) = item?;
+            Ok(order_response(parse_order_id(&k)?, order))
+        })
+        .collect();
+
+    Ok(OrdersResponse { orders: orders? })
+}
+
+fn parse_order_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted order key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn order_response(order_id: u64, order: Order) -> OrderResponse {
+    let price = match order.side {
+        Side::Sell => Decimal::from_ratio(order.ask_amount, order.offer_amount),
+        Side::Buy => Decimal::from_ratio(order.offer_amount, order.ask_amount),
+    };
+    OrderResponse { order_id, order, price }
+}
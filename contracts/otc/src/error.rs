@@ -0,0 +1,36 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("The fee rate cannot exceed 1")]
+    InvalidFeeRate {},
+
+    #[error("The order asks for {ask_amount}, it cannot be filled with {amount}")]
+    FillExceedsOrder { amount: Uint128, ask_amount: Uint128 },
+
+    #[error("The fill is too small to receive any of the order")]
+    FillTooSmall {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
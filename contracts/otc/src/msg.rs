@@ -0,0 +1,102 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::{Order, Side};
+
+/// This structure describes the parameters used for creating an OTC contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub token: String,
+    pub denom: String,
+    pub fee_rate: Decimal,
+    pub fee_distributor: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives LUART to sell or to fill a buy order, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Escrows the sent coins in an order buying `ask_amount` LUART
+    PostBuyOrder { ask_amount: Uint128 },
+    /// Fills the sell order with the sent coins, up to the amount the order asks for.
+    /// The taker receives the LUART at the price of the maker, minus the fee.
+    FillSellOrder { order_id: u64 },
+    /// Returns the escrow not filled yet to the maker. Only the maker can execute it.
+    CancelOrder { order_id: u64 },
+    /// Only the owner can update the configuration
+    UpdateConfig {
+        fee_rate: Option<Decimal>,
+        fee_distributor: Option<String>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Escrows the sent LUART in an order selling it for `ask_amount` of the native coin
+    PostSellOrder { ask_amount: Uint128 },
+    /// Fills the buy order with the sent LUART, up to the amount the order asks for.
+    /// The taker receives the native coin at the price of the maker, minus the fee and the tax.
+    FillBuyOrder { order_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the order.
+    /// Return type: OrderResponse.
+    Order { order_id: u64 },
+    /// Returns the open orders of the side, or of both sides, from the oldest.
+    /// Return type: OrdersResponse.
+    Orders {
+        side: Option<Side>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub token: String,
+    pub denom: String,
+    pub fee_rate: Decimal,
+    pub fee_distributor: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    pub order_id: u64,
+    pub order: Order,
+    /// The native coin paid for one LUART
+    pub price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrdersResponse {
+    pub orders: Vec<OrderResponse>,
+}
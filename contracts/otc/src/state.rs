@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who sets the fee, unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token
+    pub token: Addr,
+    /// The native coin LUART is traded against, uusd
+    pub denom: String,
+    /// The share of the filled amount the takers pay
+    pub fee_rate: Decimal,
+    /// The fee distributor receiving the fees, it shares the ones it is configured for
+    pub fee_distributor: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    /// The maker sells LUART for the native coin
+    Sell,
+    /// The maker buys LUART with the native coin
+    Buy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Order {
+    pub maker: Addr,
+    pub side: Side,
+    /// The escrowed amount not filled yet, LUART when selling and the native coin when buying
+    pub offer_amount: Uint128,
+    /// The amount of the other asset the rest of the order is filled for, the two amounts
+    /// keep the price of the maker
+    pub ask_amount: Uint128,
+}
+
+pub const ORDER_COUNT: Item<u64> = Item::new("order_count");
+pub const ORDERS: Map<U64Key, Order> = Map::new("orders");
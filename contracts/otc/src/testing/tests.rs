@@ -0,0 +1,141 @@
+use cosmwasm_std::{
+    Addr, BankMsg, coins, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, StdError, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse, QueryMsg};
+use crate::state::{Order, Side};
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const FEE_DISTRIBUTOR: &str = "mock_fee_distributor";
+const MAKER: &str = "mock_maker";
+const TAKER: &str = "mock_taker";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        token: TOKEN.to_string(),
+        denom: "uusd".to_string(),
+        fee_rate: Decimal::percent(1),
+        fee_distributor: FEE_DISTRIBUTOR.to_string(),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn receive(sender: &str, amount: u128, msg: Cw20HookMsg) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&msg).unwrap(),
+    })
+}
+
+fn token_transfer(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn bank_send(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: coins(amount, "uusd"),
+    }))
+}
+
+#[test]
+fn test_sell_order() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let post = Cw20HookMsg::PostSellOrder { ask_amount: Uint128::new(502_500) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other", &[]), receive(MAKER, 1_000_000, post.clone()))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), receive(MAKER, 1_000_000, post)).unwrap();
+
+    // A partial fill at the price of the maker, the taker pays the fee in LUART
+    let fill = ExecuteMsg::FillSellOrder { order_id: 1 };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TAKER, &coins(201_000, "uusd")), fill.clone()).unwrap();
+    assert_eq!(res.messages, vec![
+        bank_send(MAKER, 200_000),
+        token_transfer(TAKER, 396_000),
+        token_transfer(FEE_DISTRIBUTOR, 4_000),
+    ]);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Order { order_id: 1 }).unwrap();
+    assert_eq!(from_binary::<OrderResponse>(&res).unwrap(), OrderResponse {
+        order_id: 1,
+        order: Order {
+            maker: Addr::unchecked(MAKER),
+            side: Side::Sell,
+            offer_amount: Uint128::new(600_000),
+            ask_amount: Uint128::new(301_500),
+        },
+        price: Decimal::from_ratio(502_500u128, 1_000_000u128),
+    });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TAKER, &coins(301_501, "uusd")), fill.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::FillExceedsOrder {
+        amount: Uint128::new(301_501),
+        ask_amount: Uint128::new(301_500),
+    });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TAKER, &coins(301_500, "uusd")), fill).unwrap();
+    assert_eq!(res.messages, vec![
+        bank_send(MAKER, 300_000),
+        token_transfer(TAKER, 594_000),
+        token_transfer(FEE_DISTRIBUTOR, 6_000),
+    ]);
+    query(deps.as_ref(), env, QueryMsg::Order { order_id: 1 }).unwrap_err();
+}
+
+#[test]
+fn test_buy_order() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let post = ExecuteMsg::PostBuyOrder { ask_amount: Uint128::new(200_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(MAKER, &coins(100_500, "uusd")), post.clone()).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(MAKER, &coins(100_500, "uusd")), post).unwrap();
+    let msg = QueryMsg::Orders { side: Some(Side::Sell), start_after: None, limit: None };
+    let res: OrdersResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.orders.is_empty());
+
+    // Only the LUART fills the buy orders
+    let fill = ExecuteMsg::FillSellOrder { order_id: 1 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TAKER, &coins(1_000, "uusd")), fill).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The order is on the other side")));
+    let fill = receive(TAKER, 200_000, Cw20HookMsg::FillBuyOrder { order_id: 1 });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), fill).unwrap();
+    assert_eq!(res.messages, vec![
+        token_transfer(MAKER, 200_000),
+        bank_send(TAKER, 99_000),
+        bank_send(FEE_DISTRIBUTOR, 1_000),
+    ]);
+
+    // The maker cancels the other order and is refunded the escrow
+    let cancel = ExecuteMsg::CancelOrder { order_id: 2 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TAKER, &[]), cancel.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(MAKER, &[]), cancel).unwrap();
+    assert_eq!(res.messages, vec![bank_send(MAKER, 100_000)]);
+    let msg = QueryMsg::Orders { side: None, start_after: None, limit: None };
+    let res: OrdersResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert!(res.orders.is_empty());
+}
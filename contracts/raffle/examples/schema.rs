@@ -0,0 +1,30 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use luart_utils::batch::BatchResponse;
+use luart_utils::keeper::KeeperJobsResponse;
+
+use luart_raffle::msg::{
+    ConfigResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, RaffleResponse,
+    RafflesResponse, TicketsResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(Cw721HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(RaffleResponse), &out_dir);
+    export_schema(&schema_for!(RafflesResponse), &out_dir);
+    export_schema(&schema_for!(TicketsResponse), &out_dir);
+    export_schema(&schema_for!(KeeperJobsResponse), &out_dir);
+    export_schema(&schema_for!(BatchResponse), &out_dir);
+}
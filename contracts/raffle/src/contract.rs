@@ -0,0 +1,600 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, coins, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo,
+    Order, Reply, Response, StdError, StdResult, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use luart_utils::keeper::{
+    bounty_msg, claim_bounty, fund_budget, KeeperJob, query_jobs, set_job, withdraw_budget,
+};
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    RaffleResponse, RafflesResponse, RandomnessExecuteMsg, RandomnessQueryMsg, RoundResponse,
+    RoundStatus, TicketsResponse,
+};
+use crate::state::{
+    Config, CONFIG, ConfigOwner, DRAW_JOB, PURCHASES, Raffle, RAFFLE_COUNT, RaffleStatus, RAFFLES,
+    ROUND_RAFFLES, TicketCurrency, WALLET_TICKETS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-raffle";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+        payment_denom: msg.payment_denom,
+        randomness_contract: deps.api.addr_validate(&msg.randomness_contract)?,
+    })?;
+    RAFFLE_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => receive_cw721(deps, env, info, msg),
+        ExecuteMsg::BuyTickets { raffle_id, count } => {
+            let config = CONFIG.load(deps.storage)?;
+            let paid = match info.funds.as_slice() {
+                [coin] if coin.denom == config.payment_denom => coin.amount,
+                _ => Uint128::zero(),
+            };
+            buy_tickets(deps, env, info.sender, raffle_id, count, TicketCurrency::Native, paid)
+        }
+        ExecuteMsg::Cancel { raffle_id } => cancel(deps, info, raffle_id),
+        ExecuteMsg::Draw { raffle_id } => draw(deps, env, info, raffle_id),
+        ExecuteMsg::ReceiveRandomness { round_id, randomness } => {
+            receive_randomness(deps, info, round_id, randomness)
+        }
+        ExecuteMsg::ClaimRefund { raffle_id } => claim_refund(deps, info, raffle_id),
+        ExecuteMsg::SetKeeperJob { name, job } => set_keeper_job(deps, info, name, job),
+        ExecuteMsg::WithdrawKeeperBudget { amount } => withdraw_keeper_budget(deps, info, amount),
+        ExecuteMsg::UpdateConfig { randomness_contract } => {
+            update_config(deps, info, randomness_contract)
+        }
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::BuyTickets { raffle_id, count } => {
+            let buyer = deps.api.addr_validate(&cw20_msg.sender)?;
+            buy_tickets(deps, env, buyer, raffle_id, count, TicketCurrency::Luart, cw20_msg.amount)
+        }
+        Cw20HookMsg::FundKeeperBudget {} => {
+            let budget = fund_budget(deps.storage, cw20_msg.amount)?;
+            Ok(Response::new()
+                .add_attribute("action", "fund_keeper_budget")
+                .add_attribute("amount", cw20_msg.amount)
+                .add_attribute("budget", budget))
+        }
+    }
+}
+
+pub fn receive_cw721(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw721_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw721_msg.msg)? {
+        Cw721HookMsg::CreateRaffle {
+            currency,
+            ticket_price,
+            max_tickets,
+            per_wallet_limit,
+            end_time,
+            refund_share,
+        } => {
+            if ticket_price.is_zero() {
+                return Err(ContractError::InvalidRaffle { reason: "ticket price must be positive".to_string() });
+            }
+            if max_tickets == Some(0) || per_wallet_limit == Some(0) {
+                return Err(ContractError::InvalidRaffle { reason: "ticket limits must be positive".to_string() });
+            }
+            if end_time <= env.block.time.seconds() {
+                return Err(ContractError::InvalidRaffle { reason: "end time must be in the future".to_string() });
+            }
+            if refund_share > Decimal::one() {
+                return Err(ContractError::InvalidRaffle { reason: "refund share must not exceed 1".to_string() });
+            }
+
+            let raffle_id = RAFFLE_COUNT.load(deps.storage)? + 1;
+            RAFFLE_COUNT.save(deps.storage, &raffle_id)?;
+            let creator = deps.api.addr_validate(&cw721_msg.sender)?;
+            RAFFLES.save(deps.storage, U64Key::new(raffle_id), &Raffle {
+                creator: creator.clone(),
+                nft_contract: info.sender.clone(),
+                token_id: cw721_msg.token_id.clone(),
+                currency,
+                ticket_price,
+                max_tickets,
+                per_wallet_limit,
+                end_time,
+                refund_share,
+                tickets_sold: 0,
+                status: RaffleStatus::Open,
+                round_id: None,
+                winning_ticket: None,
+                winner: None,
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("action", "create_raffle")
+                .add_attribute("raffle_id", raffle_id.to_string())
+                .add_attribute("creator", creator)
+                .add_attribute("nft_contract", info.sender)
+                .add_attribute("token_id", cw721_msg.token_id))
+        }
+    }
+}
+
+pub fn buy_tickets(
+    deps: DepsMut,
+    env: Env,
+    buyer: Addr,
+    raffle_id: u64,
+    count: u32,
+    currency: TicketCurrency,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let mut raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    if raffle.status != RaffleStatus::Open || raffle.end_time <= env.block.time.seconds() {
+        return Err(ContractError::RaffleClosed {});
+    }
+    if count == 0 {
+        return Err(ContractError::InvalidRaffle { reason: "ticket count must be positive".to_string() });
+    }
+    let cost = raffle.ticket_price.checked_mul(Uint128::from(count))?;
+    if currency != raffle.currency || paid != cost {
+        let config = CONFIG.load(deps.storage)?;
+        let price = match raffle.currency {
+            TicketCurrency::Native => format!("{}{}", cost, config.payment_denom),
+            TicketCurrency::Luart => format!("{} LUART", cost),
+        };
+        return Err(ContractError::InvalidPayment { price });
+    }
+    if let Some(max_tickets) = raffle.max_tickets {
+        let remaining = max_tickets - raffle.tickets_sold;
+        if count > remaining {
+            return Err(ContractError::NotEnoughTickets { remaining });
+        }
+    }
+    let mut wallet_tickets = WALLET_TICKETS
+        .may_load(deps.storage, (U64Key::new(raffle_id), &buyer))?
+        .unwrap_or_default();
+    wallet_tickets.tickets += count;
+    if let Some(limit) = raffle.per_wallet_limit {
+        if wallet_tickets.tickets > limit {
+            return Err(ContractError::WalletLimitReached { limit });
+        }
+    }
+
+    raffle.tickets_sold += count;
+    RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+    WALLET_TICKETS.save(deps.storage, (U64Key::new(raffle_id), &buyer), &wallet_tickets)?;
+    PURCHASES.save(
+        deps.storage,
+        (U64Key::new(raffle_id), U64Key::new(raffle.tickets_sold as u64)),
+        &buyer,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "buy_tickets")
+        .add_attribute("raffle_id", raffle_id.to_string())
+        .add_attribute("buyer", buyer)
+        .add_attribute("count", count.to_string())
+        .add_attribute("tickets_sold", raffle.tickets_sold.to_string()))
+}
+
+pub fn cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    raffle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    if raffle.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if raffle.status != RaffleStatus::Open || raffle.tickets_sold > 0 {
+        return Err(ContractError::RaffleClosed {});
+    }
+    raffle.status = RaffleStatus::Cancelled;
+    RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+
+    Ok(Response::new()
+        .add_message(nft_transfer_msg(&raffle, &raffle.creator)?)
+        .add_attribute("action", "cancel")
+        .add_attribute("raffle_id", raffle_id.to_string()))
+}
+
+pub fn draw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    raffle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    let config = CONFIG.load(deps.storage)?;
+    // Only the first draw of a raffle is paid, not the new draws after failed rounds
+    let first_draw = raffle.status == RaffleStatus::Open;
+    match raffle.status {
+        RaffleStatus::Open => {
+            let sold_out = raffle.max_tickets == Some(raffle.tickets_sold);
+            if raffle.end_time > env.block.time.seconds() && !sold_out {
+                return Err(ContractError::RaffleNotEnded {});
+            }
+        }
+        RaffleStatus::Drawing => {
+            // A failed round never sends its random value, the draw is requested again
+            let round_id = raffle.round_id.ok_or(ContractError::DrawPending {})?;
+            let round: RoundResponse = deps.querier.query_wasm_smart(
+                &config.randomness_contract,
+                &RandomnessQueryMsg::Round { round_id },
+            )?;
+            if round.status != RoundStatus::Failed {
+                return Err(ContractError::DrawPending {});
+            }
+            ROUND_RAFFLES.remove(deps.storage, U64Key::new(round_id));
+        }
+        _ => return Err(ContractError::RaffleClosed {}),
+    }
+
+    let bounty = if first_draw {
+        claim_bounty(deps.storage, DRAW_JOB, env.block.time.seconds())?
+    } else {
+        Uint128::zero()
+    };
+    let bounty_msgs = bounty_msg(&config.luart_token, &info.sender, bounty)?;
+
+    // Nobody to draw, the NFT goes back to the creator
+    if raffle.tickets_sold == 0 {
+        raffle.status = RaffleStatus::Cancelled;
+        RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+        return Ok(Response::new()
+            .add_message(nft_transfer_msg(&raffle, &raffle.creator)?)
+            .add_messages(bounty_msgs)
+            .add_attribute("action", "cancel")
+            .add_attribute("raffle_id", raffle_id.to_string())
+            .add_attribute("keeper_bounty", bounty));
+    }
+
+    raffle.status = RaffleStatus::Drawing;
+    raffle.round_id = None;
+    RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+
+    // The round id is read from the reply of the request
+    Ok(Response::new()
+        .add_messages(bounty_msgs)
+        .add_submessage(SubMsg::reply_on_success(WasmMsg::Execute {
+            contract_addr: config.randomness_contract.to_string(),
+            msg: to_binary(&RandomnessExecuteMsg::Request {})?,
+            funds: vec![],
+        }, raffle_id))
+        .add_attribute("action", "draw")
+        .add_attribute("raffle_id", raffle_id.to_string())
+        .add_attribute("keeper_bounty", bounty))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let raffle_id = msg.id;
+    let res = msg.result.into_result().map_err(StdError::generic_err)?;
+    let round_id: u64 = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "round_id")
+        .ok_or_else(|| StdError::generic_err("The randomness round id is missing"))?
+        .value
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid randomness round id"))?;
+
+    let mut raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    raffle.round_id = Some(round_id);
+    RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+    ROUND_RAFFLES.save(deps.storage, U64Key::new(round_id), &raffle_id)?;
+
+    Ok(Response::new()
+        .add_attribute("raffle_id", raffle_id.to_string())
+        .add_attribute("round_id", round_id.to_string()))
+}
+
+pub fn receive_randomness(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: u64,
+    randomness: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.randomness_contract != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let raffle_id = ROUND_RAFFLES.load(deps.storage, U64Key::new(round_id))?;
+    ROUND_RAFFLES.remove(deps.storage, U64Key::new(round_id));
+    let mut raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    if raffle.status != RaffleStatus::Drawing {
+        return Err(ContractError::RaffleClosed {});
+    }
+
+    let mut seed = [0u8; 32];
+    hex::decode_to_slice(&randomness, &mut seed)?;
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&seed[..8]);
+    let winning_ticket = (u64::from_be_bytes(bytes) % raffle.tickets_sold as u64) as u32;
+
+    // The first purchase ending after the winning ticket holds it
+    let (_, winner) = PURCHASES
+        .prefix(U64Key::new(raffle_id))
+        .range(deps.storage, Some(Bound::exclusive_int(winning_ticket as u64)), None, Order::Ascending)
+        .next()
+        .ok_or_else(|| StdError::generic_err("The winning ticket has no buyer"))??;
+    let winner_tickets = WALLET_TICKETS.load(deps.storage, (U64Key::new(raffle_id), &winner))?.tickets;
+
+    raffle.status = RaffleStatus::Completed;
+    raffle.winning_ticket = Some(winning_ticket);
+    raffle.winner = Some(winner.clone());
+    RAFFLES.save(deps.storage, U64Key::new(raffle_id), &raffle)?;
+
+    // The creator receives the proceeds but the refunds of the other participants
+    let proceeds = raffle.ticket_price * Uint128::from(raffle.tickets_sold);
+    let refunds = raffle.ticket_price * Uint128::from(raffle.tickets_sold - winner_tickets) * raffle.refund_share;
+    let creator_amount = proceeds.checked_sub(refunds)?;
+
+    let mut messages = vec![nft_transfer_msg(&raffle, &winner)?];
+    if !creator_amount.is_zero() {
+        messages.push(payment_msg(&config, &raffle.currency, &raffle.creator, creator_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "complete")
+        .add_attribute("raffle_id", raffle_id.to_string())
+        .add_attribute("winning_ticket", winning_ticket.to_string())
+        .add_attribute("winner", winner))
+}
+
+pub fn claim_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    raffle_id: u64,
+) -> Result<Response, ContractError> {
+    let raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    let mut wallet_tickets = WALLET_TICKETS
+        .may_load(deps.storage, (U64Key::new(raffle_id), &info.sender))?
+        .unwrap_or_default();
+    let amount = refund_amount(&raffle, &info.sender, wallet_tickets.tickets);
+    if amount.is_zero() || wallet_tickets.refunded {
+        return Err(ContractError::NothingToRefund {});
+    }
+    wallet_tickets.refunded = true;
+    WALLET_TICKETS.save(deps.storage, (U64Key::new(raffle_id), &info.sender), &wallet_tickets)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &raffle.currency, &info.sender, amount)?)
+        .add_attribute("action", "claim_refund")
+        .add_attribute("raffle_id", raffle_id.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Returns the refund of the tickets, zero until the raffle completed and for the winner
+fn refund_amount(raffle: &Raffle, address: &Addr, tickets: u32) -> Uint128 {
+    if raffle.status != RaffleStatus::Completed || raffle.winner.as_ref() == Some(address) {
+        return Uint128::zero();
+    }
+    raffle.ticket_price * Uint128::from(tickets) * raffle.refund_share
+}
+
+pub fn set_keeper_job(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    job: Option<KeeperJob>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+    if name != DRAW_JOB {
+        return Err(ContractError::UnknownKeeperJob { name });
+    }
+    set_job(deps.storage, &name, job)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_keeper_job")
+        .add_attribute("name", name))
+}
+
+pub fn withdraw_keeper_budget(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+    let budget = withdraw_budget(deps.storage, amount)?;
+
+    Ok(Response::new()
+        .add_messages(bounty_msg(&config.luart_token, &info.sender, amount)?)
+        .add_attribute("action", "withdraw_keeper_budget")
+        .add_attribute("amount", amount)
+        .add_attribute("budget", budget))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    randomness_contract: Option<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(randomness_contract) = randomness_contract {
+        config.randomness_contract = deps.api.addr_validate(&randomness_contract)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn nft_transfer_msg(raffle: &Raffle, recipient: &Addr) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: raffle.nft_contract.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: raffle.token_id.clone(),
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn payment_msg(
+    config: &Config,
+    currency: &TicketCurrency,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(match currency {
+        TicketCurrency::Native => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), &config.payment_denom),
+        }),
+        TicketCurrency::Luart => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.luart_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Raffle { raffle_id } => to_binary(&query_raffle(deps, raffle_id)?),
+        QueryMsg::Raffles { start_after, limit } => to_binary(&query_raffles(deps, start_after, limit)?),
+        QueryMsg::Tickets { raffle_id, address } => {
+            to_binary(&query_tickets(deps, raffle_id, address)?)
+        }
+        QueryMsg::KeeperJobs {} => to_binary(&query_jobs(deps.storage)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        luart_token: config.luart_token.to_string(),
+        payment_denom: config.payment_denom,
+        randomness_contract: config.randomness_contract.to_string(),
+    })
+}
+
+pub fn query_raffle(deps: Deps, raffle_id: u64) -> StdResult<RaffleResponse> {
+    let raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    Ok(RaffleResponse { raffle_id, raffle })
+}
+
+pub fn query_raffles(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RafflesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let raffles: StdResult<Vec<RaffleResponse>> = RAFFLES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, raffle) = item?;
+            Ok(RaffleResponse { raffle_id: parse_raffle_id(&k)?, raffle })
+        })
+        .collect();
+
+    Ok(RafflesResponse { raffles: raffles? })
+}
+
+pub fn query_tickets(deps: Deps, raffle_id: u64, address: String) -> StdResult<TicketsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let raffle = RAFFLES.load(deps.storage, U64Key::new(raffle_id))?;
+    let wallet_tickets = WALLET_TICKETS
+        .may_load(deps.storage, (U64Key::new(raffle_id), &address))?
+        .unwrap_or_default();
+    Ok(TicketsResponse {
+        tickets: wallet_tickets.tickets,
+        refund: refund_amount(&raffle, &address, wallet_tickets.tickets),
+        refunded: wallet_tickets.refunded,
+    })
+}
+
+fn parse_raffle_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted raffle key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
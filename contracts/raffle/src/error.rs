@@ -0,0 +1,55 @@
+use cosmwasm_std::{OverflowError, StdError};
+use hex::FromHexError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid raffle: {reason}")]
+    InvalidRaffle { reason: String },
+
+    #[error("The raffle is not open")]
+    RaffleClosed {},
+
+    #[error("The raffle has not ended yet")]
+    RaffleNotEnded {},
+
+    #[error("Only {remaining} tickets are left")]
+    NotEnoughTickets { remaining: u32 },
+
+    #[error("Cannot buy more than {limit} tickets per wallet")]
+    WalletLimitReached { limit: u32 },
+
+    #[error("Must pay exactly {price}")]
+    InvalidPayment { price: String },
+
+    #[error("The randomness round of the draw has not failed")]
+    DrawPending {},
+
+    #[error("Nothing to refund")]
+    NothingToRefund {},
+
+    #[error("Unknown keeper job: {name}")]
+    UnknownKeeperJob { name: String },
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use luart_utils::keeper::KeeperJob;
+
+use crate::state::{Raffle, TicketCurrency};
+
+/// This structure describes the parameters used for creating a raffle contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub luart_token: String,
+    /// The native denom of the tickets not paid in LUART
+    pub payment_denom: String,
+    /// The randomness contract drawing the winners, the raffle contract must be one of its consumers
+    pub randomness_contract: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Buys tickets paid in LUART, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Creates a raffle of the sent NFT, see [`Cw721HookMsg`]
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Buys tickets paid in the payment denom
+    BuyTickets { raffle_id: u64, count: u32 },
+    /// Cancels a raffle without tickets and returns the NFT. Only the creator can execute it.
+    Cancel { raffle_id: u64 },
+    /// Requests the random value drawing the winner. Anyone can execute it once the raffle ended
+    /// or sold out for the bounty of the draw job, or again without bounty if the randomness
+    /// round failed.
+    Draw { raffle_id: u64 },
+    /// Draws the winner with the random value. Only the randomness contract can execute it.
+    ReceiveRandomness { round_id: u64, randomness: String },
+    /// Refunds the share of the tickets cost of a participant who did not win
+    ClaimRefund { raffle_id: u64 },
+    /// Registers, replaces or removes with an unset job the bounty of a keeper job.
+    /// Only the owner can execute it.
+    SetKeeperJob { name: String, job: Option<KeeperJob> },
+    /// Sends LUART of the keeper budget to the owner. Only the owner can execute it.
+    WithdrawKeeperBudget { amount: Uint128 },
+    /// Updates the configuration. Only the owner can execute it.
+    UpdateConfig { randomness_contract: Option<String> },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Buys tickets with the sent LUART
+    BuyTickets { raffle_id: u64, count: u32 },
+    /// Adds the sent LUART to the budget paying the keeper bounties
+    FundKeeperBudget {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    /// Escrows the sent NFT as the prize of a new raffle
+    CreateRaffle {
+        currency: TicketCurrency,
+        ticket_price: Uint128,
+        max_tickets: Option<u32>,
+        per_wallet_limit: Option<u32>,
+        end_time: u64,
+        refund_share: Decimal,
+    },
+}
+
+/// The messages executed on the randomness contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessExecuteMsg {
+    Request {},
+}
+
+/// The queries of the randomness contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessQueryMsg {
+    Round { round_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundStatus {
+    Open,
+    Fulfilled,
+    Failed,
+}
+
+/// The part of the randomness round response used by the raffles
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundResponse {
+    pub round_id: u64,
+    pub status: RoundStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the raffle.
+    /// Return type: RaffleResponse.
+    Raffle { raffle_id: u64 },
+    /// Returns the raffles by id.
+    /// Return type: RafflesResponse.
+    Raffles {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the tickets of the address and its refund.
+    /// Return type: TicketsResponse.
+    Tickets { raffle_id: u64, address: String },
+    /// Returns the keeper jobs and the budget paying their bounties.
+    /// Return type: KeeperJobsResponse.
+    KeeperJobs {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub luart_token: String,
+    pub payment_denom: String,
+    pub randomness_contract: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RaffleResponse {
+    pub raffle_id: u64,
+    pub raffle: Raffle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RafflesResponse {
+    pub raffles: Vec<RaffleResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TicketsResponse {
+    pub tickets: u32,
+    /// The amount refunded to the address once the raffle completed, zero for the winner
+    pub refund: Uint128,
+    pub refunded: bool,
+}
@@ -0,0 +1,97 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can update the configuration
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    pub luart_token: Addr,
+    /// The native denom of the tickets not paid in LUART
+    pub payment_denom: String,
+    /// The randomness contract drawing the winners, the raffle contract must be one of its consumers
+    pub randomness_contract: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketCurrency {
+    /// The payment denom of the configuration
+    Native,
+    Luart,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RaffleStatus {
+    Open,
+    /// Waiting for the random value of the randomness contract
+    Drawing,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Raffle {
+    pub creator: Addr,
+    /// The escrowed NFT sent to the winner
+    pub nft_contract: Addr,
+    pub token_id: String,
+    pub currency: TicketCurrency,
+    pub ticket_price: Uint128,
+    /// The maximum number of tickets, unlimited if unset
+    pub max_tickets: Option<u32>,
+    /// The maximum number of tickets of a wallet, unlimited if unset
+    pub per_wallet_limit: Option<u32>,
+    /// Tickets are sold until this time in seconds
+    pub end_time: u64,
+    /// The share of their tickets cost refunded to the participants who did not win
+    pub refund_share: Decimal,
+    pub tickets_sold: u32,
+    pub status: RaffleStatus,
+    /// The randomness round of the last draw
+    pub round_id: Option<u64>,
+    pub winning_ticket: Option<u32>,
+    pub winner: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct WalletTickets {
+    pub tickets: u32,
+    pub refunded: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+/// The keeper job drawing the ended raffles
+pub const DRAW_JOB: &str = "draw";
+
+pub const RAFFLE_COUNT: Item<u64> = Item::new("raffle_count");
+pub const RAFFLES: Map<U64Key, Raffle> = Map::new("raffles");
+/// The ticket purchases of a raffle keyed by the number of tickets sold after them,
+/// the buyer holds the tickets from the previous key up to this one
+pub const PURCHASES: Map<(U64Key, U64Key), Addr> = Map::new("purchases");
+pub const WALLET_TICKETS: Map<(U64Key, &Addr), WalletTickets> = Map::new("wallet_tickets");
+/// The raffle waiting for the random value of each randomness round
+pub const ROUND_RAFFLES: Map<U64Key, u64> = Map::new("round_raffles");
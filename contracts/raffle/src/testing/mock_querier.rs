@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+
+use crate::msg::{RandomnessQueryMsg, RoundResponse, RoundStatus};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the randomness round queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    /// The status of each randomness round
+    rounds: HashMap<u64, RoundStatus>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => {
+                match from_binary(msg) {
+                    Ok(RandomnessQueryMsg::Round { round_id }) => match self.rounds.get(&round_id) {
+                        Some(status) => SystemResult::Ok(ContractResult::from(to_binary(&RoundResponse {
+                            round_id,
+                            status: status.clone(),
+                        }))),
+                        None => SystemResult::Ok(ContractResult::Err("Round not found".to_string())),
+                    },
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported randomness query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            rounds: HashMap::new(),
+        }
+    }
+
+    // configure the status of a randomness round
+    pub fn with_round_status(&mut self, round_id: u64, status: RoundStatus) {
+        self.rounds.insert(round_id, status);
+    }
+}
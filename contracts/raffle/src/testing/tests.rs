@@ -0,0 +1,304 @@
+use cosmwasm_std::{
+    Addr, BankMsg, coins, ContractResult, Decimal, Deps, DepsMut, Env, Event, from_binary, Reply,
+    Response, SubMsg, SubMsgExecutionResponse, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use luart_utils::keeper::{KeeperJob, KeeperJobsResponse};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, RaffleResponse,
+    RandomnessExecuteMsg, RoundStatus, TicketsResponse,
+};
+use crate::state::{RaffleStatus, TicketCurrency};
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const LUART: &str = "mock_luart";
+const RANDOMNESS: &str = "mock_randomness";
+const COLLECTION: &str = "mock_collection";
+const CREATOR: &str = "mock_creator";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const DENOM: &str = "uusd";
+const PRICE: u128 = 100;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        luart_token: LUART.to_string(),
+        payment_denom: DENOM.to_string(),
+        randomness_contract: RANDOMNESS.to_string(),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn create_raffle(deps: DepsMut, env: Env, token_id: &str, currency: TicketCurrency, max_tickets: Option<u32>) {
+    let end_time = env.block.time.seconds() + 100;
+    execute(deps, env, mock_info(COLLECTION, &[]), ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: CREATOR.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_binary(&Cw721HookMsg::CreateRaffle {
+            currency,
+            ticket_price: Uint128::new(PRICE),
+            max_tickets,
+            per_wallet_limit: Some(3),
+            end_time,
+            refund_share: Decimal::percent(50),
+        }).unwrap(),
+    })).unwrap();
+}
+
+fn round_reply(raffle_id: u64, round_id: u64) -> Reply {
+    Reply {
+        id: raffle_id,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![Event::new("wasm").add_attribute("round_id", round_id.to_string())],
+            data: None,
+        }),
+    }
+}
+
+fn randomness(value: u64) -> String {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&value.to_be_bytes());
+    hex::encode(bytes)
+}
+
+fn query_raffle(deps: Deps, env: Env, raffle_id: u64) -> RaffleResponse {
+    from_binary(&query(deps, env, QueryMsg::Raffle { raffle_id }).unwrap()).unwrap()
+}
+
+#[test]
+fn test_raffle() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_raffle(deps.as_mut(), env.clone(), "1", TicketCurrency::Native, None);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &coins(PRICE, DENOM)),
+                      ExecuteMsg::BuyTickets { raffle_id: 1, count: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { price: "200uusd".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &coins(2 * PRICE, DENOM)),
+            ExecuteMsg::BuyTickets { raffle_id: 1, count: 2 }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &coins(3 * PRICE, DENOM)),
+            ExecuteMsg::BuyTickets { raffle_id: 1, count: 3 }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &coins(PRICE, DENOM)),
+                      ExecuteMsg::BuyTickets { raffle_id: 1, count: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::WalletLimitReached { limit: 3 });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::RaffleNotEnded {});
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &coins(PRICE, DENOM)),
+                      ExecuteMsg::BuyTickets { raffle_id: 1, count: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::RaffleClosed {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(WasmMsg::Execute {
+        contract_addr: RANDOMNESS.to_string(),
+        msg: to_binary(&RandomnessExecuteMsg::Request {}).unwrap(),
+        funds: vec![],
+    }, 1)]);
+    reply(deps.as_mut(), env.clone(), round_reply(1, 7)).unwrap();
+    assert_eq!(query_raffle(deps.as_ref(), env.clone(), 1).raffle.round_id, Some(7));
+
+    // The tickets 0 and 1 are alice's, the tickets 2 to 4 are bob's
+    let receive_msg = ExecuteMsg::ReceiveRandomness { round_id: 7, randomness: randomness(8) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), receive_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RANDOMNESS, &[]), receive_msg).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: COLLECTION.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: "1".to_string(),
+            }).unwrap(),
+            funds: vec![],
+        }),
+        // The proceeds less half the cost of alice's tickets
+        SubMsg::new(BankMsg::Send { to_address: CREATOR.to_string(), amount: coins(400, DENOM) }),
+    ]);
+    let raffle = query_raffle(deps.as_ref(), env.clone(), 1).raffle;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winning_ticket, Some(3));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::ClaimRefund { raffle_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::ClaimRefund { raffle_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(BankMsg::Send { to_address: ALICE.to_string(), amount: coins(100, DENOM) }),
+    ]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::ClaimRefund { raffle_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::NothingToRefund {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::Tickets { raffle_id: 1, address: ALICE.to_string() }).unwrap();
+    let tickets: TicketsResponse = from_binary(&res).unwrap();
+    assert_eq!(tickets, TicketsResponse { tickets: 2, refund: Uint128::new(100), refunded: true });
+}
+
+#[test]
+fn test_luart_raffle() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_raffle(deps.as_mut(), env.clone(), "1", TicketCurrency::Luart, Some(3));
+    create_raffle(deps.as_mut(), env.clone(), "2", TicketCurrency::Native, None);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &coins(PRICE, DENOM)),
+                      ExecuteMsg::BuyTickets { raffle_id: 1, count: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { price: "100 LUART".to_string() });
+    let buy_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: ALICE.to_string(),
+        amount: Uint128::new(3 * PRICE),
+        msg: to_binary(&Cw20HookMsg::BuyTickets { raffle_id: 1, count: 3 }).unwrap(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("fake_token", &[]), buy_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), buy_msg).unwrap();
+
+    // A sold out raffle is drawn before its end, again once the randomness round failed
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 }).unwrap();
+    reply(deps.as_mut(), env.clone(), round_reply(1, 1)).unwrap();
+    deps.querier.with_round_status(1, RoundStatus::Open);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::DrawPending {});
+    deps.querier.with_round_status(1, RoundStatus::Failed);
+    execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 }).unwrap();
+    reply(deps.as_mut(), env.clone(), round_reply(1, 2)).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RANDOMNESS, &[]), ExecuteMsg::ReceiveRandomness {
+        round_id: 2,
+        randomness: randomness(0),
+    }).unwrap();
+    assert_eq!(res.messages[1], SubMsg::new(WasmMsg::Execute {
+        contract_addr: LUART.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: CREATOR.to_string(),
+            amount: Uint128::new(3 * PRICE),
+        }).unwrap(),
+        funds: vec![],
+    }));
+    assert_eq!(query_raffle(deps.as_ref(), env.clone(), 1).raffle.winner, Some(Addr::unchecked(ALICE)));
+
+    // The creator takes back the NFT of a raffle without tickets
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), ExecuteMsg::Cancel { raffle_id: 2 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), ExecuteMsg::Cancel { raffle_id: 2 })
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: COLLECTION.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: CREATOR.to_string(),
+            token_id: "2".to_string(),
+        }).unwrap(),
+        funds: vec![],
+    })]);
+    assert_eq!(query_raffle(deps.as_ref(), env, 2).raffle.status, RaffleStatus::Cancelled);
+}
+
+#[test]
+fn test_draw_keeper_bounty() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_raffle(deps.as_mut(), env.clone(), "1", TicketCurrency::Native, None);
+    create_raffle(deps.as_mut(), env.clone(), "2", TicketCurrency::Native, None);
+
+    let job = KeeperJob { bounty: Uint128::new(10), cooldown: 60 };
+    let set_job_msg = |name: &str| ExecuteMsg::SetKeeperJob { name: name.to_string(), job: Some(job.clone()) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ALICE, &[]), set_job_msg("draw")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), set_job_msg("settle")).unwrap_err();
+    assert_eq!(err, ContractError::UnknownKeeperJob { name: "settle".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), set_job_msg("draw")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: OWNER.to_string(),
+        amount: Uint128::new(15),
+        msg: to_binary(&Cw20HookMsg::FundKeeperBudget {}).unwrap(),
+    })).unwrap();
+
+    // The first draw is paid, the second one falls in the cooldown of the job
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages[1], SubMsg::new(WasmMsg::Execute {
+        contract_addr: LUART.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: BOB.to_string(), amount: Uint128::new(10) })
+            .unwrap(),
+        funds: vec![],
+    }));
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 2 })
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(query_raffle(deps.as_ref(), env.clone(), 2).raffle.status, RaffleStatus::Cancelled);
+
+    let res: KeeperJobsResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::KeeperJobs {}).unwrap())
+        .unwrap();
+    assert_eq!(res.budget, Uint128::new(5));
+    assert_eq!(res.jobs[0].next_paid_run, env.block.time.seconds() + 60);
+
+    let withdraw_msg = ExecuteMsg::WithdrawKeeperBudget { amount: Uint128::new(5) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), withdraw_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]), withdraw_msg).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: LUART.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: OWNER.to_string(), amount: Uint128::new(5) })
+            .unwrap(),
+        funds: vec![],
+    })]);
+}
+
+#[test]
+fn test_redraw_keeper_bounty() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_raffle(deps.as_mut(), env.clone(), "1", TicketCurrency::Native, None);
+    execute(deps.as_mut(), env.clone(), mock_info(ALICE, &coins(PRICE, DENOM)),
+            ExecuteMsg::BuyTickets { raffle_id: 1, count: 1 }).unwrap();
+    let job = KeeperJob { bounty: Uint128::new(10), cooldown: 0 };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::SetKeeperJob {
+        name: "draw".to_string(),
+        job: Some(job),
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: OWNER.to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&Cw20HookMsg::FundKeeperBudget {}).unwrap(),
+    })).unwrap();
+
+    // The draw requested again after a failed round pays no bounty
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    reply(deps.as_mut(), env.clone(), round_reply(1, 1)).unwrap();
+    deps.querier.with_round_status(1, RoundStatus::Failed);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BOB, &[]), ExecuteMsg::Draw { raffle_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(WasmMsg::Execute {
+        contract_addr: RANDOMNESS.to_string(),
+        msg: to_binary(&RandomnessExecuteMsg::Request {}).unwrap(),
+        funds: vec![],
+    }, 1)]);
+
+    let res: KeeperJobsResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::KeeperJobs {}).unwrap())
+        .unwrap();
+    assert_eq!(res.budget, Uint128::new(90));
+}
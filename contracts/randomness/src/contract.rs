@@ -0,0 +1,361 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, to_binary,
+    WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::U64Key;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use luart_utils::roles::{grant_role, has_role, revoke_role};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RandomnessCallbackMsg,
+    RolesResponse, RoundResponse,
+};
+use crate::state::{
+    COMMITMENTS, Commitment, COMMITTER_ROLE, Config, CONFIG, ConfigOwner, CONSUMER_ROLE, ROLE_NAMES,
+    Round, ROUND_COUNT, ROUNDS, RoundStatus,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-randomness";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    let config = Config {
+        owner: Some(owner),
+        commit_period: msg.commit_period,
+        reveal_period: msg.reveal_period,
+        min_reveals: msg.min_reveals,
+    };
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+    ROUND_COUNT.save(deps.storage, &0u64)?;
+
+    for committer in msg.committers.iter() {
+        grant_role(deps.storage, COMMITTER_ROLE, &deps.api.addr_validate(committer)?)?;
+    }
+    for consumer in msg.consumers.iter() {
+        grant_role(deps.storage, CONSUMER_ROLE, &deps.api.addr_validate(consumer)?)?;
+    }
+
+    Ok(Response::default())
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.commit_period == 0 || config.reveal_period == 0 {
+        return Err(ContractError::InvalidConfig { reason: "periods must be positive".to_string() });
+    }
+    if config.min_reveals == 0 {
+        return Err(ContractError::InvalidConfig { reason: "minimum reveals must be positive".to_string() });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Request {} => request(deps, env, info),
+        ExecuteMsg::Commit { round_id, commitment } => commit(deps, env, info, round_id, commitment),
+        ExecuteMsg::Reveal { round_id, secret } => reveal(deps, env, info, round_id, secret),
+        ExecuteMsg::Finalize { round_id } => finalize(deps, env, round_id),
+        ExecuteMsg::UpdateConfig {
+            commit_period,
+            reveal_period,
+            min_reveals,
+        } => update_config(deps, info, commit_period, reveal_period, min_reveals),
+        ExecuteMsg::GrantRole { role, address } => update_role(deps, info, role, address, true),
+        ExecuteMsg::RevokeRole { role, address } => update_role(deps, info, role, address, false),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn request(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, CONSUMER_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let commit_deadline = env.block.time.seconds() + config.commit_period;
+    let round_id = ROUND_COUNT.load(deps.storage)? + 1;
+    ROUND_COUNT.save(deps.storage, &round_id)?;
+    ROUNDS.save(deps.storage, U64Key::new(round_id), &Round {
+        requester: info.sender.clone(),
+        commit_deadline,
+        reveal_deadline: commit_deadline + config.reveal_period,
+        commits: 0,
+        reveals: 0,
+        status: RoundStatus::Open,
+        randomness: None,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request")
+        .add_attribute("requester", info.sender)
+        .add_attribute("round_id", round_id.to_string()))
+}
+
+pub fn commit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, COMMITTER_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut round = ROUNDS.load(deps.storage, U64Key::new(round_id))?;
+    if round.status != RoundStatus::Open {
+        return Err(ContractError::RoundClosed {});
+    }
+    if env.block.time.seconds() >= round.commit_deadline {
+        return Err(ContractError::CommitPeriodOver {});
+    }
+    if COMMITMENTS.has(deps.storage, (U64Key::new(round_id), &info.sender)) {
+        return Err(ContractError::AlreadyCommitted {});
+    }
+
+    let mut hash_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&commitment, &mut hash_buf)?;
+    COMMITMENTS.save(deps.storage, (U64Key::new(round_id), &info.sender), &Commitment {
+        hash: hex::encode(hash_buf),
+        secret: None,
+    })?;
+    round.commits += 1;
+    ROUNDS.save(deps.storage, U64Key::new(round_id), &round)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit")
+        .add_attribute("committer", info.sender)
+        .add_attribute("round_id", round_id.to_string()))
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    secret: String,
+) -> Result<Response, ContractError> {
+    let mut round = ROUNDS.load(deps.storage, U64Key::new(round_id))?;
+    if round.status != RoundStatus::Open {
+        return Err(ContractError::RoundClosed {});
+    }
+    // The secrets are revealed once no commitment can depend on them
+    let now = env.block.time.seconds();
+    if now < round.commit_deadline || now >= round.reveal_deadline {
+        return Err(ContractError::NotRevealPeriod {});
+    }
+
+    let key = (U64Key::new(round_id), &info.sender);
+    let mut commitment = COMMITMENTS
+        .may_load(deps.storage, key.clone())?
+        .filter(|commitment| commitment.secret.is_none())
+        .ok_or(ContractError::NoCommitment {})?;
+    if hex::encode(Sha256::digest(secret.as_bytes())) != commitment.hash {
+        return Err(ContractError::InvalidSecret {});
+    }
+    commitment.secret = Some(secret);
+    COMMITMENTS.save(deps.storage, key, &commitment)?;
+    round.reveals += 1;
+    ROUNDS.save(deps.storage, U64Key::new(round_id), &round)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal")
+        .add_attribute("committer", info.sender)
+        .add_attribute("round_id", round_id.to_string()))
+}
+
+pub fn finalize(
+    deps: DepsMut,
+    env: Env,
+    round_id: u64,
+) -> Result<Response, ContractError> {
+    let mut round = ROUNDS.load(deps.storage, U64Key::new(round_id))?;
+    if round.status != RoundStatus::Open {
+        return Err(ContractError::RoundClosed {});
+    }
+    let now = env.block.time.seconds();
+    let all_revealed = now >= round.commit_deadline && round.reveals == round.commits;
+    if now < round.reveal_deadline && !all_revealed {
+        return Err(ContractError::RevealPeriodNotOver {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    if round.reveals < config.min_reveals {
+        round.status = RoundStatus::Failed;
+        ROUNDS.save(deps.storage, U64Key::new(round_id), &round)?;
+        return Ok(Response::new()
+            .add_attribute("action", "finalize")
+            .add_attribute("round_id", round_id.to_string())
+            .add_attribute("status", "failed"));
+    }
+
+    let randomness = derive_randomness(deps.as_ref(), round_id)?;
+    round.status = RoundStatus::Fulfilled;
+    round.randomness = Some(randomness.clone());
+    ROUNDS.save(deps.storage, U64Key::new(round_id), &round)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: round.requester.to_string(),
+            msg: to_binary(&RandomnessCallbackMsg::ReceiveRandomness {
+                round_id,
+                randomness: randomness.clone(),
+            })?,
+            funds: vec![],
+        })
+        .add_attribute("action", "finalize")
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("status", "fulfilled")
+        .add_attribute("randomness", randomness))
+}
+
+/// Hashes the round id and the revealed secrets by committer address. A single honest
+/// committer is enough for the value to be unpredictable before the reveals.
+fn derive_randomness(deps: Deps, round_id: u64) -> StdResult<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(round_id.to_be_bytes());
+    for item in COMMITMENTS
+        .prefix(U64Key::new(round_id))
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, commitment) = item?;
+        if let Some(secret) = commitment.secret {
+            hasher.update((secret.len() as u64).to_be_bytes());
+            hasher.update(secret.as_bytes());
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    commit_period: Option<u64>,
+    reveal_period: Option<u64>,
+    min_reveals: Option<u32>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.commit_period = commit_period.unwrap_or(config.commit_period);
+    config.reveal_period = reveal_period.unwrap_or(config.reveal_period);
+    config.min_reveals = min_reveals.unwrap_or(config.min_reveals);
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: String,
+    address: String,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    if !ROLE_NAMES.contains(&role.as_str()) {
+        return Err(StdError::generic_err(format!("Unknown role: {}", role)).into());
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    if granted {
+        grant_role(deps.storage, &role, &address)?;
+    } else {
+        revoke_role(deps.storage, &role, &address);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if granted { "grant_role" } else { "revoke_role" })
+        .add_attribute("role", role)
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Round { round_id } => to_binary(&query_round(deps, round_id)?),
+        QueryMsg::Roles { address } => to_binary(&query_roles(deps, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        commit_period: config.commit_period,
+        reveal_period: config.reveal_period,
+        min_reveals: config.min_reveals,
+    })
+}
+
+pub fn query_round(deps: Deps, round_id: u64) -> StdResult<RoundResponse> {
+    let round = ROUNDS.load(deps.storage, U64Key::new(round_id))?;
+    Ok(RoundResponse {
+        round_id,
+        requester: round.requester.to_string(),
+        commit_deadline: round.commit_deadline,
+        reveal_deadline: round.reveal_deadline,
+        commits: round.commits,
+        reveals: round.reveals,
+        status: round.status,
+        randomness: round.randomness,
+    })
+}
+
+pub fn query_roles(deps: Deps, address: String) -> StdResult<RolesResponse> {
+    let address: Addr = deps.api.addr_validate(&address)?;
+    let roles = ROLE_NAMES
+        .iter()
+        .filter(|role| has_role(deps.storage, role, &address))
+        .map(|role| role.to_string())
+        .collect();
+    Ok(RolesResponse { roles })
+}
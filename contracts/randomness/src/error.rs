@@ -0,0 +1,49 @@
+use cosmwasm_std::StdError;
+use hex::FromHexError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("The round is not open")]
+    RoundClosed {},
+
+    #[error("The commit period is over")]
+    CommitPeriodOver {},
+
+    #[error("Not in the reveal period")]
+    NotRevealPeriod {},
+
+    #[error("Already committed")]
+    AlreadyCommitted {},
+
+    #[error("No commitment to reveal")]
+    NoCommitment {},
+
+    #[error("The secret does not match the commitment")]
+    InvalidSecret {},
+
+    #[error("The round cannot be finalized before its reveal deadline")]
+    RevealPeriodNotOver {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
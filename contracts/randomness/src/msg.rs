@@ -0,0 +1,110 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::RoundStatus;
+
+/// This structure describes the parameters used for creating a randomness contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub committers: Vec<String>,
+    pub consumers: Vec<String>,
+    /// Seconds after a request during which the committers commit their secret hash
+    pub commit_period: u64,
+    /// Seconds after the commit period during which the committers reveal their secret
+    pub reveal_period: u64,
+    /// The minimum number of revealed secrets for a round to be fulfilled
+    pub min_reveals: u32,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Opens a round, its random value is sent to the sender in a [`RandomnessCallbackMsg`].
+    /// Only a consumer can execute it.
+    Request {},
+    /// Commits the hex encoded sha256 hash of a secret during the commit period.
+    /// Only a committer can execute it.
+    Commit { round_id: u64, commitment: String },
+    /// Reveals the committed secret during the reveal period
+    Reveal { round_id: u64, secret: String },
+    /// Derives the random value from the revealed secrets and sends it to the requester.
+    /// Anyone can execute it after the reveal deadline, or once every commitment is revealed.
+    Finalize { round_id: u64 },
+    /// Updates the round parameters. Only the owner can execute it.
+    UpdateConfig {
+        commit_period: Option<u64>,
+        reveal_period: Option<u64>,
+        min_reveals: Option<u32>,
+    },
+    /// Only the owner can grant the committer and consumer roles
+    GrantRole { role: String, address: String },
+    /// Only the owner can revoke a role
+    RevokeRole { role: String, address: String },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+/// The message sent to the requester of a fulfilled round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessCallbackMsg {
+    ReceiveRandomness {
+        round_id: u64,
+        /// Hex encoded 32 bytes random value
+        randomness: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the round.
+    /// Return type: RoundResponse.
+    Round { round_id: u64 },
+    /// Returns the roles granted to the given address.
+    /// Return type: RolesResponse.
+    Roles { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub commit_period: u64,
+    pub reveal_period: u64,
+    pub min_reveals: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundResponse {
+    pub round_id: u64,
+    pub requester: String,
+    pub commit_deadline: u64,
+    pub reveal_deadline: u64,
+    pub commits: u32,
+    pub reveals: u32,
+    pub status: RoundStatus,
+    pub randomness: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub roles: Vec<String>,
+}
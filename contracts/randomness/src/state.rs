@@ -0,0 +1,80 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who manages the roles and the round parameters
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// Seconds after a request during which the committers commit their secret hash
+    pub commit_period: u64,
+    /// Seconds after the commit period during which the committers reveal their secret
+    pub reveal_period: u64,
+    /// The minimum number of revealed secrets for a round to be fulfilled
+    pub min_reveals: u32,
+}
+
+/// Commits and reveals the secrets of the rounds
+pub const COMMITTER_ROLE: &str = "committer";
+/// Requests random values, the collection and raffle contracts
+pub const CONSUMER_ROLE: &str = "consumer";
+pub const ROLE_NAMES: [&str; 2] = [COMMITTER_ROLE, CONSUMER_ROLE];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundStatus {
+    Open,
+    /// The random value was sent to the requester
+    Fulfilled,
+    /// Too few secrets were revealed, the requester has to request a new round
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Round {
+    /// The consumer receiving the random value
+    pub requester: Addr,
+    /// Timestamp in seconds, the reveals start after it
+    pub commit_deadline: u64,
+    /// Timestamp in seconds
+    pub reveal_deadline: u64,
+    pub commits: u32,
+    pub reveals: u32,
+    pub status: RoundStatus,
+    /// Hex encoded sha256 hash of the revealed secrets
+    pub randomness: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Commitment {
+    /// Hex encoded sha256 hash of the secret
+    pub hash: String,
+    pub secret: Option<String>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const ROUND_COUNT: Item<u64> = Item::new("round_count");
+pub const ROUNDS: Map<U64Key, Round> = Map::new("rounds");
+pub const COMMITMENTS: Map<(U64Key, &Addr), Commitment> = Map::new("commitments");
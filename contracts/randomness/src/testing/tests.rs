@@ -0,0 +1,117 @@
+use cosmwasm_std::{DepsMut, Env, from_binary, Response, SubMsg, to_binary, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use sha2::{Digest, Sha256};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, RandomnessCallbackMsg, RoundResponse};
+use crate::state::RoundStatus;
+
+const OWNER: &str = "mock_owner";
+const CONSUMER: &str = "mock_raffle";
+const COMMITTERS: [&str; 3] = ["mock_committer1", "mock_committer2", "mock_committer3"];
+
+const HOUR: u64 = 3_600;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        committers: COMMITTERS.iter().map(|c| c.to_string()).collect(),
+        consumers: vec![CONSUMER.to_string()],
+        commit_period: HOUR,
+        reveal_period: HOUR,
+        min_reveals: 2,
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn commit(deps: DepsMut, env: Env, committer: &str, secret: &str) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(committer, &[]), ExecuteMsg::Commit {
+        round_id: 1,
+        commitment: hex::encode(Sha256::digest(secret.as_bytes())),
+    })
+}
+
+fn reveal(deps: DepsMut, env: Env, committer: &str, secret: &str) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(committer, &[]), ExecuteMsg::Reveal {
+        round_id: 1,
+        secret: secret.to_string(),
+    })
+}
+
+fn query_round(deps: cosmwasm_std::Deps, env: Env) -> RoundResponse {
+    from_binary(&query(deps, env, QueryMsg::Round { round_id: 1 }).unwrap()).unwrap()
+}
+
+#[test]
+fn test_commit_reveal() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Request {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(CONSUMER, &[]), ExecuteMsg::Request {}).unwrap();
+
+    let err = commit(deps.as_mut(), env.clone(), "anyone", "secret").unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    commit(deps.as_mut(), env.clone(), COMMITTERS[0], "first").unwrap();
+    commit(deps.as_mut(), env.clone(), COMMITTERS[1], "second").unwrap();
+    let err = commit(deps.as_mut(), env.clone(), COMMITTERS[1], "second").unwrap_err();
+    assert_eq!(err, ContractError::AlreadyCommitted {});
+
+    // The secrets are revealed after the commit period
+    let err = reveal(deps.as_mut(), env.clone(), COMMITTERS[0], "first").unwrap_err();
+    assert_eq!(err, ContractError::NotRevealPeriod {});
+    env.block.time = env.block.time.plus_seconds(HOUR);
+    let err = commit(deps.as_mut(), env.clone(), COMMITTERS[2], "third").unwrap_err();
+    assert_eq!(err, ContractError::CommitPeriodOver {});
+    let err = reveal(deps.as_mut(), env.clone(), COMMITTERS[0], "wrong").unwrap_err();
+    assert_eq!(err, ContractError::InvalidSecret {});
+    reveal(deps.as_mut(), env.clone(), COMMITTERS[0], "first").unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Finalize { round_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::RevealPeriodNotOver {});
+
+    // Anyone finalizes the round once every commitment is revealed
+    reveal(deps.as_mut(), env.clone(), COMMITTERS[1], "second").unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Finalize { round_id: 1 }).unwrap();
+    let round = query_round(deps.as_ref(), env);
+    assert_eq!(round.status, RoundStatus::Fulfilled);
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: CONSUMER.to_string(),
+        msg: to_binary(&RandomnessCallbackMsg::ReceiveRandomness {
+            round_id: 1,
+            randomness: round.randomness.unwrap(),
+        }).unwrap(),
+        funds: vec![],
+    })]);
+}
+
+#[test]
+fn test_failed_round() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(CONSUMER, &[]), ExecuteMsg::Request {}).unwrap();
+    commit(deps.as_mut(), env.clone(), COMMITTERS[0], "first").unwrap();
+    commit(deps.as_mut(), env.clone(), COMMITTERS[1], "second").unwrap();
+
+    // A single secret is revealed, below the minimum
+    env.block.time = env.block.time.plus_seconds(HOUR);
+    reveal(deps.as_mut(), env.clone(), COMMITTERS[0], "first").unwrap();
+    env.block.time = env.block.time.plus_seconds(HOUR);
+    let err = reveal(deps.as_mut(), env.clone(), COMMITTERS[1], "second").unwrap_err();
+    assert_eq!(err, ContractError::NotRevealPeriod {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Finalize { round_id: 1 }).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(query_round(deps.as_ref(), env.clone()).status, RoundStatus::Failed);
+    let err = execute(deps.as_mut(), env, mock_info("anyone", &[]),
+                      ExecuteMsg::Finalize { round_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::RoundClosed {});
+}
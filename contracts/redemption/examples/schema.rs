@@ -0,0 +1,29 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use luart_utils::batch::BatchResponse;
+
+use luart_redemption::msg::{
+    CampaignResponse, CampaignsResponse, ConfigResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg,
+    InstantiateMsg, QueryMsg, RedemptionResponse, RedemptionsResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(Cw721HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(CampaignResponse), &out_dir);
+    export_schema(&schema_for!(CampaignsResponse), &out_dir);
+    export_schema(&schema_for!(RedemptionResponse), &out_dir);
+    export_schema(&schema_for!(RedemptionsResponse), &out_dir);
+    export_schema(&schema_for!(BatchResponse), &out_dir);
+}
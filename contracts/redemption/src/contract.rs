@@ -0,0 +1,383 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order, Response,
+    StdError, StdResult, to_binary, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CampaignResponse, CampaignsResponse, CollectionExecuteMsg, ConfigResponse, Cw20HookMsg,
+    Cw721HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RedemptionResponse,
+    RedemptionsResponse,
+};
+use crate::state::{
+    Campaign, CAMPAIGN_COUNT, CAMPAIGNS, CODE_HASHES, Config, CONFIG, ConfigOwner, REDEEMED,
+    Redemption, REDEMPTION_COUNT, RedemptionCost, REDEMPTIONS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-redemption";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+    })?;
+    CAMPAIGN_COUNT.save(deps.storage, &0u64)?;
+    REDEMPTION_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+fn validate_campaign(deps: Deps, campaign: &Campaign) -> Result<(), ContractError> {
+    if campaign.name.is_empty() {
+        return Err(ContractError::InvalidCampaign { reason: "name must not be empty".to_string() });
+    }
+    if campaign.start_time >= campaign.end_time {
+        return Err(ContractError::InvalidCampaign { reason: "the campaign must end after its start".to_string() });
+    }
+    if campaign.max_supply == Some(0) {
+        return Err(ContractError::InvalidCampaign { reason: "max supply must be positive".to_string() });
+    }
+    match &campaign.cost {
+        RedemptionCost::Nft { collection } => {
+            deps.api.addr_validate(collection)?;
+        }
+        RedemptionCost::Luart { amount } => {
+            if amount.is_zero() {
+                return Err(ContractError::InvalidCampaign { reason: "LUART amount must be positive".to_string() });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => receive_cw721(deps, env, info, msg),
+        ExecuteMsg::CreateCampaign { campaign } => create_campaign(deps, info, campaign),
+        ExecuteMsg::UpdateCampaign {
+            campaign_id,
+            end_time,
+            max_supply,
+        } => update_campaign(deps, info, campaign_id, end_time, max_supply),
+        ExecuteMsg::Fulfill { redemption_id } => fulfill(deps, info, redemption_id),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.luart_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Redeem { campaign_id, code_hash } => {
+            let campaign = CAMPAIGNS.load(deps.storage, U64Key::new(campaign_id))?;
+            match campaign.cost {
+                RedemptionCost::Luart { amount } if amount == cw20_msg.amount => {}
+                RedemptionCost::Luart { amount } => {
+                    return Err(ContractError::InvalidPayment { amount: amount.to_string() });
+                }
+                RedemptionCost::Nft { .. } => return Err(ContractError::Unauthorized {}),
+            }
+
+            let redeemer = deps.api.addr_validate(&cw20_msg.sender)?;
+            let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.luart_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount: cw20_msg.amount })?,
+                funds: vec![],
+            });
+            redeem(deps, env, redeemer, campaign_id, campaign, code_hash, None, burn_msg)
+        }
+    }
+}
+
+pub fn receive_cw721(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw721_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw721_msg.msg)? {
+        Cw721HookMsg::Redeem { campaign_id, code_hash } => {
+            let campaign = CAMPAIGNS.load(deps.storage, U64Key::new(campaign_id))?;
+            match &campaign.cost {
+                RedemptionCost::Nft { collection } if *collection == info.sender => {}
+                _ => return Err(ContractError::Unauthorized {}),
+            }
+
+            let redeemer = deps.api.addr_validate(&cw721_msg.sender)?;
+            let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                msg: to_binary(&CollectionExecuteMsg::Burn { token_id: cw721_msg.token_id.clone() })?,
+                funds: vec![],
+            });
+            redeem(deps, env, redeemer, campaign_id, campaign, code_hash, Some(cw721_msg.token_id), burn_msg)
+        }
+    }
+}
+
+/// Records the redemption of the code hash and burns the paid NFT or LUART
+#[allow(clippy::too_many_arguments)]
+fn redeem(
+    deps: DepsMut,
+    env: Env,
+    redeemer: Addr,
+    campaign_id: u64,
+    campaign: Campaign,
+    code_hash: String,
+    token_id: Option<String>,
+    burn_msg: CosmosMsg,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    if now < campaign.start_time || now >= campaign.end_time {
+        return Err(ContractError::CampaignNotOpen {});
+    }
+    let redeemed = REDEEMED.may_load(deps.storage, U64Key::new(campaign_id))?.unwrap_or_default();
+    if let Some(max_supply) = campaign.max_supply {
+        if redeemed >= max_supply {
+            return Err(ContractError::SoldOut {});
+        }
+    }
+
+    let mut hash_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&code_hash, &mut hash_buf)?;
+    let code_hash = hex::encode(hash_buf);
+    if CODE_HASHES.has(deps.storage, &code_hash) {
+        return Err(ContractError::CodeAlreadyUsed {});
+    }
+
+    let redemption_id = REDEMPTION_COUNT.load(deps.storage)? + 1;
+    REDEMPTION_COUNT.save(deps.storage, &redemption_id)?;
+    REDEEMED.save(deps.storage, U64Key::new(campaign_id), &(redeemed + 1))?;
+    CODE_HASHES.save(deps.storage, &code_hash, &redemption_id)?;
+    REDEMPTIONS.save(deps.storage, U64Key::new(redemption_id), &Redemption {
+        campaign_id,
+        redeemer: redeemer.clone(),
+        code_hash: code_hash.clone(),
+        token_id: token_id.clone(),
+        time: now,
+        fulfilled: false,
+    })?;
+
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_attribute("action", "redeem")
+        .add_attribute("campaign_id", campaign_id.to_string())
+        .add_attribute("redemption_id", redemption_id.to_string())
+        .add_attribute("redeemer", redeemer)
+        .add_attribute("code_hash", code_hash)
+        .add_attribute("token_id", token_id.unwrap_or_default()))
+}
+
+pub fn create_campaign(
+    deps: DepsMut,
+    info: MessageInfo,
+    campaign: Campaign,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+    validate_campaign(deps.as_ref(), &campaign)?;
+
+    let campaign_id = CAMPAIGN_COUNT.load(deps.storage)? + 1;
+    CAMPAIGN_COUNT.save(deps.storage, &campaign_id)?;
+    CAMPAIGNS.save(deps.storage, U64Key::new(campaign_id), &campaign)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_campaign")
+        .add_attribute("campaign_id", campaign_id.to_string())
+        .add_attribute("name", campaign.name))
+}
+
+pub fn update_campaign(
+    deps: DepsMut,
+    info: MessageInfo,
+    campaign_id: u64,
+    end_time: Option<u64>,
+    max_supply: Option<u64>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut campaign = CAMPAIGNS.load(deps.storage, U64Key::new(campaign_id))?;
+    campaign.end_time = end_time.unwrap_or(campaign.end_time);
+    if max_supply.is_some() {
+        campaign.max_supply = max_supply;
+    }
+    validate_campaign(deps.as_ref(), &campaign)?;
+    CAMPAIGNS.save(deps.storage, U64Key::new(campaign_id), &campaign)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_campaign")
+        .add_attribute("campaign_id", campaign_id.to_string()))
+}
+
+pub fn fulfill(
+    deps: DepsMut,
+    info: MessageInfo,
+    redemption_id: u64,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut redemption = REDEMPTIONS.load(deps.storage, U64Key::new(redemption_id))?;
+    if redemption.fulfilled {
+        return Err(ContractError::AlreadyFulfilled {});
+    }
+    redemption.fulfilled = true;
+    REDEMPTIONS.save(deps.storage, U64Key::new(redemption_id), &redemption)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fulfill")
+        .add_attribute("redemption_id", redemption_id.to_string())
+        .add_attribute("code_hash", redemption.code_hash))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Campaign { campaign_id } => to_binary(&query_campaign(deps, campaign_id)?),
+        QueryMsg::Campaigns { start_after, limit } => {
+            to_binary(&query_campaigns(deps, start_after, limit)?)
+        }
+        QueryMsg::Redemption { redemption_id } => to_binary(&query_redemption(deps, redemption_id)?),
+        QueryMsg::RedemptionByCode { code_hash } => {
+            let redemption_id = CODE_HASHES.load(deps.storage, &code_hash.to_lowercase())?;
+            to_binary(&query_redemption(deps, redemption_id)?)
+        }
+        QueryMsg::Redemptions { start_after, limit } => {
+            to_binary(&query_redemptions(deps, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        luart_token: config.luart_token.to_string(),
+    })
+}
+
+pub fn query_campaign(deps: Deps, campaign_id: u64) -> StdResult<CampaignResponse> {
+    let campaign = CAMPAIGNS.load(deps.storage, U64Key::new(campaign_id))?;
+    campaign_response(deps, campaign_id, campaign)
+}
+
+pub fn query_campaigns(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<CampaignsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let campaigns: StdResult<Vec<CampaignResponse>> = CAMPAIGNS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, campaign) = item?;
+            campaign_response(deps, parse_id(&k)?, campaign)
+        })
+        .collect();
+
+    Ok(CampaignsResponse { campaigns: campaigns? })
+}
+
+fn campaign_response(deps: Deps, campaign_id: u64, campaign: Campaign) -> StdResult<CampaignResponse> {
+    Ok(CampaignResponse {
+        campaign_id,
+        campaign,
+        redeemed: REDEEMED.may_load(deps.storage, U64Key::new(campaign_id))?.unwrap_or_default(),
+    })
+}
+
+pub fn query_redemption(deps: Deps, redemption_id: u64) -> StdResult<RedemptionResponse> {
+    Ok(RedemptionResponse {
+        redemption_id,
+        redemption: REDEMPTIONS.load(deps.storage, U64Key::new(redemption_id))?,
+    })
+}
+
+pub fn query_redemptions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RedemptionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let redemptions: StdResult<Vec<RedemptionResponse>> = REDEMPTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, redemption) = item?;
+            Ok(RedemptionResponse {
+                redemption_id: parse_id(&k)?,
+                redemption,
+            })
+        })
+        .collect();
+
+    Ok(RedemptionsResponse { redemptions: redemptions? })
+}
+
+fn parse_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted id key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
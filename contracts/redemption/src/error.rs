@@ -0,0 +1,43 @@
+use cosmwasm_std::StdError;
+use hex::FromHexError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid campaign: {reason}")]
+    InvalidCampaign { reason: String },
+
+    #[error("The campaign is not open")]
+    CampaignNotOpen {},
+
+    #[error("The campaign supply is exhausted")]
+    SoldOut {},
+
+    #[error("The redemption code was already used")]
+    CodeAlreadyUsed {},
+
+    #[error("The redemption is already fulfilled")]
+    AlreadyFulfilled {},
+
+    #[error("Must pay exactly {amount} LUART")]
+    InvalidPayment { amount: String },
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
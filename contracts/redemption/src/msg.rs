@@ -0,0 +1,137 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+
+use crate::state::{Campaign, Redemption};
+
+/// This structure describes the parameters used for creating a redemption contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token burnt by the redemptions
+    pub luart_token: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the LUART of a redemption, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Receives the NFT of a redemption, see [`Cw721HookMsg`]
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Adds a campaign. Only the owner can execute it.
+    CreateCampaign { campaign: Campaign },
+    /// Updates the end or the supply of a campaign. Only the owner can execute it.
+    UpdateCampaign {
+        campaign_id: u64,
+        end_time: Option<u64>,
+        max_supply: Option<u64>,
+    },
+    /// Marks the reward of a redemption as delivered. Only the owner can execute it.
+    Fulfill { redemption_id: u64 },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Burns the sent LUART to redeem the reward of a campaign
+    Redeem {
+        campaign_id: u64,
+        /// Hex encoded sha256 hash of the redemption code
+        code_hash: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    /// Burns the sent token to redeem the reward of a campaign
+    Redeem {
+        campaign_id: u64,
+        /// Hex encoded sha256 hash of the redemption code
+        code_hash: String,
+    },
+}
+
+/// The messages executed on the collections, a subset of the cw721-base execute messages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionExecuteMsg {
+    Burn { token_id: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the campaign and its number of redemptions.
+    /// Return type: CampaignResponse.
+    Campaign { campaign_id: u64 },
+    /// Returns the campaigns by id.
+    /// Return type: CampaignsResponse.
+    Campaigns {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the redemption.
+    /// Return type: RedemptionResponse.
+    Redemption { redemption_id: u64 },
+    /// Returns the redemption of the code hash.
+    /// Return type: RedemptionResponse.
+    RedemptionByCode { code_hash: String },
+    /// Returns the redemptions by id.
+    /// Return type: RedemptionsResponse.
+    Redemptions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub luart_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignResponse {
+    pub campaign_id: u64,
+    pub campaign: Campaign,
+    pub redeemed: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignsResponse {
+    pub campaigns: Vec<CampaignResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionResponse {
+    pub redemption_id: u64,
+    pub redemption: Redemption,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionsResponse {
+    pub redemptions: Vec<RedemptionResponse>,
+}
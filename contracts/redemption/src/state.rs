@@ -0,0 +1,81 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who creates the campaigns and fulfills the redemptions
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token burnt by the redemptions
+    pub luart_token: Addr,
+}
+
+/// What a redeemer burns to claim the reward
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RedemptionCost {
+    /// Any token of the collection, sent to the redemption contract which must be able to burn it
+    Nft { collection: String },
+    /// The amount of LUART, sent to the redemption contract
+    Luart { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Campaign {
+    pub name: String,
+    /// The reward claimed off-chain, e.g. the description of a physical item
+    pub reward: String,
+    pub cost: RedemptionCost,
+    /// Timestamp in seconds of the first redemption
+    pub start_time: u64,
+    /// Timestamp in seconds after the last redemption
+    pub end_time: u64,
+    /// The maximum number of redemptions, unlimited if unset
+    pub max_supply: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Redemption {
+    pub campaign_id: u64,
+    pub redeemer: Addr,
+    /// Hex encoded sha256 hash of the redemption code kept off-chain by the redeemer
+    pub code_hash: String,
+    /// The burnt token, none for LUART redemptions
+    pub token_id: Option<String>,
+    /// Timestamp in seconds of the redemption
+    pub time: u64,
+    /// Set by the owner once the reward is delivered
+    pub fulfilled: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const CAMPAIGN_COUNT: Item<u64> = Item::new("campaign_count");
+pub const CAMPAIGNS: Map<U64Key, Campaign> = Map::new("campaigns");
+/// The number of redemptions of each campaign
+pub const REDEEMED: Map<U64Key, u64> = Map::new("redeemed");
+pub const REDEMPTION_COUNT: Item<u64> = Item::new("redemption_count");
+pub const REDEMPTIONS: Map<U64Key, Redemption> = Map::new("redemptions");
+/// The redemption of each code hash, a code is redeemed once
+pub const CODE_HASHES: Map<&str, u64> = Map::new("code_hashes");
@@ -0,0 +1,140 @@
+use cosmwasm_std::{DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::Cw721ReceiveMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    CampaignResponse, CollectionExecuteMsg, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg,
+    QueryMsg, RedemptionResponse,
+};
+use crate::state::{Campaign, RedemptionCost};
+
+const OWNER: &str = "owner";
+const LUART: &str = "mock_luart";
+const COLLECTION: &str = "mock_collection";
+const HOLDER: &str = "holder";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: Some(OWNER.to_string()),
+        luart_token: LUART.to_string(),
+    };
+    instantiate(deps, env, mock_info("deployer", &[]), msg).unwrap()
+}
+
+fn create_campaign(deps: DepsMut, env: &Env, cost: RedemptionCost, max_supply: Option<u64>) {
+    let campaign = Campaign {
+        name: "Genesis hoodie".to_string(),
+        reward: "A hoodie shipped to the redeemer".to_string(),
+        cost,
+        start_time: env.block.time.seconds() + 100,
+        end_time: env.block.time.seconds() + 1_000,
+        max_supply,
+    };
+    execute(deps, env.clone(), mock_info(OWNER, &[]), ExecuteMsg::CreateCampaign { campaign }).unwrap();
+}
+
+fn code_hash(byte: u8) -> String {
+    hex::encode([byte; 32])
+}
+
+fn nft_redeem_msg(token_id: &str, code_hash: String) -> ExecuteMsg {
+    ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: HOLDER.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_binary(&Cw721HookMsg::Redeem { campaign_id: 1, code_hash }).unwrap(),
+    })
+}
+
+#[test]
+fn test_redeem_nft() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_campaign(deps.as_mut(), &env, RedemptionCost::Nft { collection: COLLECTION.to_string() }, Some(2));
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(COLLECTION, &[]), nft_redeem_msg("1", code_hash(1)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotOpen {});
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_collection", &[]),
+                      nft_redeem_msg("1", code_hash(1))).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(COLLECTION, &[]), nft_redeem_msg("1", code_hash(1)))
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: COLLECTION.to_string(),
+        msg: to_binary(&CollectionExecuteMsg::Burn { token_id: "1".to_string() }).unwrap(),
+        funds: vec![],
+    })]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(COLLECTION, &[]), nft_redeem_msg("2", code_hash(1)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::CodeAlreadyUsed {});
+    execute(deps.as_mut(), env.clone(), mock_info(COLLECTION, &[]), nft_redeem_msg("2", code_hash(2))).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(COLLECTION, &[]), nft_redeem_msg("3", code_hash(3)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Campaign { campaign_id: 1 }).unwrap();
+    let campaign: CampaignResponse = from_binary(&res).unwrap();
+    assert_eq!(campaign.redeemed, 2);
+
+    // The owner marks the reward delivered once
+    let fulfill_msg = ExecuteMsg::Fulfill { redemption_id: 2 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]), fulfill_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), fulfill_msg.clone()).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), fulfill_msg).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyFulfilled {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::RedemptionByCode { code_hash: code_hash(2) }).unwrap();
+    let redemption: RedemptionResponse = from_binary(&res).unwrap();
+    assert_eq!(redemption.redemption_id, 2);
+    assert_eq!(redemption.redemption.token_id, Some("2".to_string()));
+    assert!(redemption.redemption.fulfilled);
+}
+
+#[test]
+fn test_redeem_luart() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_campaign(deps.as_mut(), &env, RedemptionCost::Luart { amount: Uint128::new(500) }, None);
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let redeem_msg = |amount: u128| ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: HOLDER.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Redeem { campaign_id: 1, code_hash: code_hash(1) }).unwrap(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), redeem_msg(400)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPayment { amount: "500".to_string() });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]), redeem_msg(500)).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: LUART.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn { amount: Uint128::new(500) }).unwrap(),
+        funds: vec![],
+    })]);
+
+    // The owner closes the campaign early
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateCampaign {
+        campaign_id: 1,
+        end_time: Some(env.block.time.seconds()),
+        max_supply: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCampaign { reason: "the campaign must end after its start".to_string() });
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateCampaign {
+        campaign_id: 1,
+        end_time: Some(env.block.time.seconds() + 1),
+        max_supply: None,
+    }).unwrap();
+    env.block.time = env.block.time.plus_seconds(1);
+    let err = execute(deps.as_mut(), env, mock_info(LUART, &[]), redeem_msg(500)).unwrap_err();
+    assert_eq!(err, ContractError::CampaignNotOpen {});
+}
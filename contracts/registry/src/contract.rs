@@ -0,0 +1,167 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, to_binary};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AddressEntry, AddressesResponse, AddressResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg,
+};
+use crate::state::{ADDRESSES, Config, CONFIG, ConfigOwner};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-registry";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_NAME_LENGTH: usize = 32;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config { owner: Some(owner) })?;
+    save_addresses(deps, msg.addresses)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetAddresses { addresses } => set_addresses(deps, info, addresses),
+        ExecuteMsg::RemoveAddress { name } => remove_address(deps, info, name),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn set_addresses(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<AddressEntry>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut res = Response::new().add_attribute("action", "set_addresses");
+    for entry in addresses.iter() {
+        res = res.add_attribute("name", &entry.name).add_attribute("address", &entry.address);
+    }
+    save_addresses(deps, addresses)?;
+
+    Ok(res)
+}
+
+/// Validates all the entries before saving any of them
+fn save_addresses(deps: DepsMut, addresses: Vec<AddressEntry>) -> Result<(), ContractError> {
+    let addresses = addresses
+        .into_iter()
+        .map(|entry| {
+            validate_name(&entry.name)?;
+            Ok((entry.name, deps.api.addr_validate(&entry.address)?))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    for (name, address) in addresses.iter() {
+        ADDRESSES.save(deps.storage, name, address)?;
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), ContractError> {
+    let valid_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_';
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH || !name.chars().all(valid_char) {
+        return Err(ContractError::InvalidName {});
+    }
+    Ok(())
+}
+
+pub fn remove_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    ADDRESSES.remove(deps.storage, &name);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_address")
+        .add_attribute("name", name))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Address { name } => to_binary(&query_address(deps, name)?),
+        QueryMsg::Addresses { start_after, limit } => to_binary(&query_addresses(deps, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+    })
+}
+
+pub fn query_address(deps: Deps, name: String) -> StdResult<AddressResponse> {
+    let address = ADDRESSES
+        .may_load(deps.storage, &name)?
+        .ok_or_else(|| StdError::generic_err(format!("No address registered as {}", name)))?;
+    Ok(AddressResponse { name, address })
+}
+
+pub fn query_addresses(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AddressesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let addresses: StdResult<Vec<AddressResponse>> = ADDRESSES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (name, address) = item?;
+            Ok(AddressResponse { name: String::from_utf8(name)?, address })
+        })
+        .collect();
+
+    Ok(AddressesResponse { addresses: addresses? })
+}
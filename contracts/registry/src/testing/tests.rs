@@ -0,0 +1,77 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, from_binary, Response, StdError, to_binary};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use luart_protocol::registry::{
+    AddressResponse as ProtocolAddressResponse, QueryMsg as ProtocolQueryMsg, STAKING, TOKEN, TREASURY,
+};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{AddressEntry, AddressesResponse, AddressResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+const OWNER: &str = "mock_owner";
+const GOVERNANCE: &str = "mock_governance";
+
+fn entry(name: &str, address: &str) -> AddressEntry {
+    AddressEntry { name: name.to_string(), address: address.to_string() }
+}
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: Some(GOVERNANCE.to_string()),
+        addresses: vec![entry(TOKEN, "mock_token")],
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn query_addresses(deps: Deps, start_after: Option<&str>, limit: Option<u32>) -> Vec<String> {
+    let msg = QueryMsg::Addresses { start_after: start_after.map(String::from), limit };
+    let res: AddressesResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+    res.addresses.into_iter().map(|entry| entry.name).collect()
+}
+
+#[test]
+fn test_set_addresses() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Only the governance updates the addresses
+    let set = ExecuteMsg::SetAddresses {
+        addresses: vec![entry(STAKING, "mock_staking"), entry(TREASURY, "mock_treasury")],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), set.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), set).unwrap();
+
+    // An invalid name saves none of the entries
+    let set = ExecuteMsg::SetAddresses {
+        addresses: vec![entry(TOKEN, "mock_new_token"), entry("Staking", "mock_staking")],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), set).unwrap_err();
+    assert_eq!(err, ContractError::InvalidName {});
+
+    // The contracts resolve their peers through the protocol interface
+    let msg: QueryMsg = from_binary(&to_binary(&ProtocolQueryMsg::Address { name: TOKEN.to_string() }).unwrap())
+        .unwrap();
+    let res: ProtocolAddressResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.address, Addr::unchecked("mock_token"));
+
+    // An upgraded contract is registered once for all its peers
+    let set = ExecuteMsg::SetAddresses { addresses: vec![entry(STAKING, "mock_staking_v2")] };
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), set).unwrap();
+    let res: AddressResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Address {
+        name: STAKING.to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.address, Addr::unchecked("mock_staking_v2"));
+
+    assert_eq!(query_addresses(deps.as_ref(), None, None), vec![STAKING, TOKEN, TREASURY]);
+    assert_eq!(query_addresses(deps.as_ref(), Some(STAKING), Some(1)), vec![TOKEN]);
+
+    let remove = ExecuteMsg::RemoveAddress { name: TREASURY.to_string() };
+    execute(deps.as_mut(), env.clone(), mock_info(GOVERNANCE, &[]), remove).unwrap();
+    let err = query(deps.as_ref(), env, QueryMsg::Address { name: TREASURY.to_string() }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("No address registered as treasury"));
+}
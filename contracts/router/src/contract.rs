@@ -0,0 +1,509 @@
+use cosmwasm_std::{
+    Addr, Binary, coins, CosmosMsg, Deps, DepsMut, Env, Fraction, from_binary, MessageInfo, Reply, Response,
+    StdError, StdResult, SubMsg, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use luart_protocol::staking::Cw20HookMsg as StakingHookMsg;
+use luart_protocol::token::{QueryMsg as TokenQueryMsg, SwapFeeConfig};
+use luart_types::asset::Asset as LuartAsset;
+use luart_utils::batch::batch_query;
+use luart_utils::nonreentrant::{self, guarded_submsg};
+use luart_utils::ownable::{Ownable, query_pending_owner};
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::{
+    Cw20HookMsg as PairHookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg, SimulationResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    BuySimulationResponse, CallbackMsg, ConfigResponse, Cw20HookMsg, Destination, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, QueryMsg, RouteSimulationResponse, SwapOperation,
+};
+use crate::state::{Config, CONFIG, ConfigOwner, PENDING_BUY, PendingBuy};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The reply of the swap of a purchase, forwarding the LUART returned
+const BUY_REPLY_ID: u64 = 1;
+/// The reply of the last message of a purchase or a route, exiting the reentrancy guard
+const GUARD_REPLY_ID: u64 = 2;
+const MAX_OPERATIONS: usize = 5;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        token: deps.api.addr_validate(&msg.token)?,
+        pair: deps.api.addr_validate(&msg.pair)?,
+        offer_denom: msg.offer_denom,
+        staking: deps.api.addr_validate(&msg.staking)?,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Buy { destination, min_received } => buy(deps, env, info, destination, min_received),
+        ExecuteMsg::ExecuteSwapOperations { operations, minimum_receive, to } => {
+            let offer_asset_info = operations
+                .first()
+                .map(|operation| operation.offer_asset_info().clone())
+                .ok_or_else(|| StdError::generic_err("The route has no operations"))?;
+            let denom = match &offer_asset_info {
+                AssetInfo::NativeToken { denom } => denom,
+                AssetInfo::Token { .. } => return Err(StdError::generic_err("Tokens are sent through Receive").into()),
+            };
+            let offer = Asset { amount: must_pay(&info, denom)?, info: offer_asset_info };
+            execute_swap_operations(deps, env, info.sender, offer, operations, minimum_receive, to)
+        }
+        ExecuteMsg::UpdateConfig { pair, staking } => update_config(deps, info, pair, staking),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+        ExecuteMsg::Callback(msg) => {
+            if info.sender != env.contract.address {
+                return Err(ContractError::Unauthorized {});
+            }
+            match msg {
+                CallbackMsg::ExecuteSwapOperation { operation, to } => execute_swap_operation(deps, env, operation, to),
+                CallbackMsg::AssertMinimumReceive { asset_info, prev_balance, minimum_receive, receiver } => {
+                    assert_minimum_receive(deps.as_ref(), asset_info, prev_balance, minimum_receive, receiver)
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::ExecuteSwapOperations { operations, minimum_receive, to } => {
+            let offer = Asset {
+                info: AssetInfo::Token { contract_addr: info.sender.to_string() },
+                amount: cw20_msg.amount,
+            };
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_swap_operations(deps, env, sender, offer, operations, minimum_receive, to)
+        }
+    }
+}
+
+pub fn buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    destination: Destination,
+    min_received: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.offer_denom)?;
+    validate_destination(deps.as_ref(), &destination)?;
+    // The pair and the destination cannot start another purchase or route until it is forwarded
+    nonreentrant::enter(deps.storage)?;
+
+    // The router pays the tax of the coins sent to the pair out of the purchase
+    let offer_amount = LuartAsset::Native(config.offer_denom.clone()).deduct_tax(&deps.querier, amount)?;
+    let balance_before = LuartAsset::Cw20(config.token.clone()).query_balance(&deps.querier, &env.contract.address)?;
+    PENDING_BUY.save(deps.storage, &PendingBuy {
+        buyer: info.sender.clone(),
+        destination,
+        min_received: min_received.unwrap_or_default(),
+        balance_before,
+    })?;
+
+    let swap = WasmMsg::Execute {
+        contract_addr: config.pair.to_string(),
+        msg: to_binary(&PairExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken { denom: config.offer_denom.clone() },
+                amount: offer_amount,
+            },
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        })?,
+        funds: coins(offer_amount.u128(), config.offer_denom),
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap, BUY_REPLY_ID))
+        .add_attribute("action", "buy")
+        .add_attribute("buyer", info.sender)
+        .add_attribute("offer_amount", offer_amount))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        BUY_REPLY_ID => forward_purchase(deps, env),
+        GUARD_REPLY_ID => {
+            nonreentrant::exit(deps.storage);
+            Ok(Response::default())
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id {}", id)).into()),
+    }
+}
+
+/// Forwards the LUART returned by the swap of the purchase to its destination
+fn forward_purchase(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_BUY.load(deps.storage)?;
+    PENDING_BUY.remove(deps.storage);
+
+    let balance = LuartAsset::Cw20(config.token.clone()).query_balance(&deps.querier, &env.contract.address)?;
+    let return_amount = balance.checked_sub(pending.balance_before)?;
+    let fee_amount = forward_fee(deps.as_ref(), &config, &pending.destination, return_amount)?;
+    let received = return_amount.checked_sub(fee_amount)?;
+    if received < pending.min_received {
+        return Err(ContractError::MinReceived { received, min_received: pending.min_received });
+    }
+
+    let forward = SubMsg::new(forward_msg(&config, &pending.buyer, pending.destination, return_amount)?);
+    Ok(Response::new()
+        .add_submessage(guarded_submsg(forward, GUARD_REPLY_ID))
+        .add_attribute("buyer", pending.buyer)
+        .add_attribute("return_amount", return_amount)
+        .add_attribute("fee_amount", fee_amount))
+}
+
+/// Swaps along the route in callbacks, each operation swapping what the previous one returned.
+/// The minimum is asserted on the balance of the receiver, so that the taxes and the LUART
+/// swap fees paid along the route are accounted for.
+pub fn execute_swap_operations(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer: Asset,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Option<Uint128>,
+    to: Option<String>,
+) -> Result<Response, ContractError> {
+    validate_route(&offer.info, &operations)?;
+    // The pairs of the route cannot start another route or purchase, which would swap the
+    // balances the router holds in between the operations
+    nonreentrant::enter(deps.storage)?;
+    let receiver = match to {
+        Some(to) => deps.api.addr_validate(&to)?,
+        None => sender,
+    };
+    let ask_asset_info = operations[operations.len() - 1].ask_asset_info().clone();
+
+    let last = operations.len() - 1;
+    let mut messages = operations
+        .into_iter()
+        .enumerate()
+        .map(|(i, operation)| Ok(SubMsg::new(callback_msg(&env, CallbackMsg::ExecuteSwapOperation {
+            operation,
+            to: if i == last { Some(receiver.to_string()) } else { None },
+        })?)))
+        .collect::<StdResult<Vec<_>>>()?;
+    if let Some(minimum_receive) = minimum_receive {
+        let prev_balance = luart_asset(&ask_asset_info).query_balance(&deps.querier, &receiver)?;
+        messages.push(SubMsg::new(callback_msg(&env, CallbackMsg::AssertMinimumReceive {
+            asset_info: ask_asset_info,
+            prev_balance,
+            minimum_receive,
+            receiver: receiver.to_string(),
+        })?));
+    }
+    // The last message replies once the whole route succeeded
+    if let Some(last) = messages.pop() {
+        messages.push(guarded_submsg(last, GUARD_REPLY_ID));
+    }
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attribute("action", "execute_swap_operations")
+        .add_attribute("offer_asset", luart_asset(&offer.info).to_string())
+        .add_attribute("offer_amount", offer.amount)
+        .add_attribute("receiver", receiver))
+}
+
+pub fn execute_swap_operation(
+    deps: DepsMut,
+    env: Env,
+    operation: SwapOperation,
+    to: Option<String>,
+) -> Result<Response, ContractError> {
+    let offer_asset = luart_asset(operation.offer_asset_info());
+    let balance = offer_asset.query_balance(&deps.querier, &env.contract.address)?;
+    // The router pays the tax of the coins sent to the pair, the token takes its swap fee out
+    // of the LUART sent and the pair swaps the rest
+    let amount = offer_asset.deduct_tax(&deps.querier, balance)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err(format!("The route returned no {}", offer_asset)).into());
+    }
+
+    let msg = match operation.offer_asset_info() {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: operation.pair().to_string(),
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset { info: operation.offer_asset_info().clone(), amount },
+                belief_price: None,
+                max_spread: None,
+                to,
+            })?,
+            funds: coins(amount.u128(), denom),
+        },
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: operation.pair().to_string(),
+                amount,
+                msg: to_binary(&PairHookMsg::Swap { belief_price: None, max_spread: None, to })?,
+            })?,
+            funds: vec![],
+        },
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "execute_swap_operation")
+        .add_attribute("pair", operation.pair())
+        .add_attribute("offer_amount", amount))
+}
+
+pub fn assert_minimum_receive(
+    deps: Deps,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    minimum_receive: Uint128,
+    receiver: String,
+) -> Result<Response, ContractError> {
+    let balance = luart_asset(&asset_info).query_balance(&deps.querier, &deps.api.addr_validate(&receiver)?)?;
+    let received = balance.checked_sub(prev_balance)?;
+    if received < minimum_receive {
+        return Err(ContractError::MinReceived { received, min_received: minimum_receive });
+    }
+
+    Ok(Response::default())
+}
+
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+        funds: vec![],
+    }))
+}
+
+fn luart_asset(asset_info: &AssetInfo) -> LuartAsset {
+    match asset_info {
+        AssetInfo::NativeToken { denom } => LuartAsset::Native(denom.clone()),
+        AssetInfo::Token { contract_addr } => LuartAsset::Cw20(Addr::unchecked(contract_addr)),
+    }
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair: Option<String>,
+    staking: Option<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(pair) = pair {
+        config.pair = deps.api.addr_validate(&pair)?;
+    }
+    if let Some(staking) = staking {
+        config.staking = deps.api.addr_validate(&staking)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+fn validate_destination(deps: Deps, destination: &Destination) -> StdResult<()> {
+    match destination {
+        Destination::Wallet { recipient: Some(recipient) } => deps.api.addr_validate(recipient).map(|_| ()),
+        Destination::Contract { contract, .. } => deps.api.addr_validate(contract).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+/// The swap fee the token takes out of the amount forwarded to the destination, only the
+/// sends with a swap hook pay it
+fn forward_fee(deps: Deps, config: &Config, destination: &Destination, amount: Uint128) -> StdResult<Uint128> {
+    match destination {
+        Destination::Contract { msg, .. } if matches!(from_binary(msg), Ok(PairHookMsg::Swap { .. })) => {
+            swap_fee(deps, config, amount)
+        }
+        _ => Ok(Uint128::zero()),
+    }
+}
+
+/// The swap fee the token takes out of a LUART amount sent to a pair. It ignores the fee
+/// discounts, so it never underestimates.
+fn swap_fee(deps: Deps, config: &Config, amount: Uint128) -> StdResult<Uint128> {
+    let fee_config: SwapFeeConfig = deps.querier.query_wasm_smart(&config.token, &TokenQueryMsg::SwapFeeConfig {})?;
+    if !fee_config.enable_swap_fee {
+        return Ok(Uint128::zero());
+    }
+    let percent_fee = fee_config.swap_percent_fee;
+    Ok(amount.multiply_ratio(percent_fee.numerator(), percent_fee.denominator() * 100))
+}
+
+fn forward_msg(config: &Config, buyer: &Addr, destination: Destination, amount: Uint128) -> StdResult<CosmosMsg> {
+    let token_msg = match destination {
+        Destination::Wallet { recipient } => Cw20ExecuteMsg::Transfer {
+            recipient: recipient.unwrap_or_else(|| buyer.to_string()),
+            amount,
+        },
+        Destination::Stake {} => Cw20ExecuteMsg::Send {
+            contract: config.staking.to_string(),
+            amount,
+            msg: to_binary(&StakingHookMsg::Bond { on_behalf_of: Some(buyer.to_string()) })?,
+        },
+        Destination::Contract { contract, msg } => Cw20ExecuteMsg::Send { contract, amount, msg },
+    };
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&token_msg)?,
+        funds: vec![],
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::SimulateBuy { offer_amount, destination } => {
+            to_binary(&query_simulate_buy(deps, offer_amount, destination)?)
+        }
+        QueryMsg::SimulateRoute { offer, operations } => to_binary(&query_simulate_route(deps, offer, operations)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        token: config.token.to_string(),
+        pair: config.pair.to_string(),
+        offer_denom: config.offer_denom,
+        staking: config.staking.to_string(),
+    })
+}
+
+pub fn query_simulate_buy(
+    deps: Deps,
+    offer_amount: Uint128,
+    destination: Destination,
+) -> StdResult<BuySimulationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let offer_amount = LuartAsset::Native(config.offer_denom.clone()).deduct_tax(&deps.querier, offer_amount)?;
+    let simulation: SimulationResponse = deps.querier.query_wasm_smart(&config.pair, &PairQueryMsg::Simulation {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken { denom: config.offer_denom.clone() },
+            amount: offer_amount,
+        },
+    })?;
+
+    let fee_amount = forward_fee(deps, &config, &destination, simulation.return_amount)?;
+    Ok(BuySimulationResponse {
+        return_amount: simulation.return_amount,
+        fee_amount,
+        received: simulation.return_amount.checked_sub(fee_amount)?,
+    })
+}
+
+/// Each operation offers the asset asked by the previous one, starting from the offer asset
+fn validate_route(offer_asset_info: &AssetInfo, operations: &[SwapOperation]) -> StdResult<()> {
+    if operations.is_empty() || operations.len() > MAX_OPERATIONS {
+        return Err(StdError::generic_err(format!("A route has 1 to {} operations", MAX_OPERATIONS)));
+    }
+
+    let mut asset_info = offer_asset_info;
+    for operation in operations {
+        if operation.offer_asset_info() != asset_info {
+            return Err(StdError::generic_err("The operations of the route do not follow each other"));
+        }
+        asset_info = operation.ask_asset_info();
+    }
+    Ok(())
+}
+
+pub fn query_simulate_route(
+    deps: Deps,
+    offer: Asset,
+    operations: Vec<SwapOperation>,
+) -> StdResult<RouteSimulationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_route(&offer.info, &operations)?;
+
+    let mut amount = offer.amount;
+    let mut fee_amount = Uint128::zero();
+    for operation in operations {
+        // The router pays the tax of the coins sent to the pair, the token takes its fee out
+        // of the LUART sent to the pair
+        let offer_amount = match operation.offer_asset_info() {
+            AssetInfo::NativeToken { .. } => {
+                luart_asset(operation.offer_asset_info()).deduct_tax(&deps.querier, amount)?
+            }
+            AssetInfo::Token { contract_addr } if contract_addr == config.token.as_str() => {
+                let fee = swap_fee(deps, &config, amount)?;
+                fee_amount += fee;
+                amount.checked_sub(fee)?
+            }
+            AssetInfo::Token { .. } => amount,
+        };
+        let simulation: SimulationResponse = deps.querier.query_wasm_smart(operation.pair(), &PairQueryMsg::Simulation {
+            offer_asset: Asset { info: operation.offer_asset_info().clone(), amount: offer_amount },
+        })?;
+
+        // The pair pays the tax of the coins it returns
+        amount = luart_asset(operation.ask_asset_info()).deduct_tax(&deps.querier, simulation.return_amount)?;
+    }
+
+    Ok(RouteSimulationResponse { return_amount: amount, fee_amount })
+}
@@ -0,0 +1,43 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use luart_utils::nonreentrant::ReentrancyError;
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("The destination would receive {received}, below the minimum of {min_received}")]
+    MinReceived { received: Uint128, min_received: Uint128 },
+
+    #[error("Reentrant call")]
+    Reentrant {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
+
+impl From<ReentrancyError> for ContractError {
+    fn from(err: ReentrancyError) -> Self {
+        match err {
+            ReentrancyError::Std(err) => ContractError::Std(err),
+            ReentrancyError::Reentrant {} => ContractError::Reentrant {},
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, Uint128};
+use cw20::Cw20ReceiveMsg;
+use terraswap::asset::{Asset, AssetInfo};
+
+/// This structure describes the parameters used for creating a router contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub token: String,
+    pub pair: String,
+    pub offer_denom: String,
+    pub staking: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// Where the purchased LUART is forwarded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+    /// Transfers the LUART to the recipient, the buyer if unset
+    Wallet { recipient: Option<String> },
+    /// Bonds the LUART in the staking contract on behalf of the buyer
+    Stake {},
+    /// Sends the LUART to the contract with the hook message, e.g. an offer on the marketplace.
+    /// The token takes its swap fee out of the sent amount when the message is a swap.
+    Contract { contract: String, msg: Binary },
+}
+
+/// A leg of a multi-hop route
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapOperation {
+    /// Swaps the offer asset for the ask asset on a terraswap pair
+    Terraswap {
+        pair: String,
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+    /// Swaps the offer asset for the ask asset on an astroport pair, which shares the asset,
+    /// swap and simulation messages of the terraswap pairs
+    Astroport {
+        pair: String,
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+impl SwapOperation {
+    pub fn pair(&self) -> &str {
+        match self {
+            SwapOperation::Terraswap { pair, .. } | SwapOperation::Astroport { pair, .. } => pair,
+        }
+    }
+
+    pub fn offer_asset_info(&self) -> &AssetInfo {
+        match self {
+            SwapOperation::Terraswap { offer_asset_info, .. }
+            | SwapOperation::Astroport { offer_asset_info, .. } => offer_asset_info,
+        }
+    }
+
+    pub fn ask_asset_info(&self) -> &AssetInfo {
+        match self {
+            SwapOperation::Terraswap { ask_asset_info, .. } | SwapOperation::Astroport { ask_asset_info, .. } => {
+                ask_asset_info
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Swaps the received tokens along a route, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Swaps the sent coins to LUART and forwards it to the destination. Fails if the
+    /// destination would receive less than `min_received`, once the token swap fee is paid.
+    Buy {
+        destination: Destination,
+        min_received: Option<Uint128>,
+    },
+    /// Swaps the sent coins along the operations, sending the last ask asset to `to`, the
+    /// sender if unset. Fails if the recipient receives less than `minimum_receive`.
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+    },
+    /// Only the owner can update the configuration
+    UpdateConfig {
+        pair: Option<String>,
+        staking: Option<String>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+    /// Internal messages, only the contract itself can execute them
+    Callback(CallbackMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Swaps the whole balance of the offer asset held by the router, which holds no funds
+    /// between transactions. The ask asset is sent to `to`, the router if unset.
+    ExecuteSwapOperation {
+        operation: SwapOperation,
+        to: Option<String>,
+    },
+    /// Fails if the receiver balance grew by less than the minimum since the route started
+    AssertMinimumReceive {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        minimum_receive: Uint128,
+        receiver: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swaps the received tokens along the operations, see [`ExecuteMsg::ExecuteSwapOperations`]
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns what a purchase of the amount would forward to the destination at the current price.
+    /// Return type: BuySimulationResponse.
+    SimulateBuy {
+        offer_amount: Uint128,
+        destination: Destination,
+    },
+    /// Returns what the route returns for the offer at the current prices, once the Terra tax
+    /// of the native legs and the LUART swap fee of the legs offering LUART are paid.
+    /// Return type: RouteSimulationResponse.
+    SimulateRoute {
+        offer: Asset,
+        operations: Vec<SwapOperation>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub token: String,
+    pub pair: String,
+    pub offer_denom: String,
+    pub staking: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuySimulationResponse {
+    /// The LUART returned by the pair
+    pub return_amount: Uint128,
+    /// The swap fee the token takes when forwarding to the destination
+    pub fee_amount: Uint128,
+    /// The LUART the destination receives
+    pub received: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteSimulationResponse {
+    /// The amount of the ask asset of the last operation received at the end of the route
+    pub return_amount: Uint128,
+    /// The LUART swap fees paid along the route
+    pub fee_amount: Uint128,
+}
@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::Item;
+use luart_utils::ownable::Ownable;
+
+use crate::msg::Destination;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who updates the pair and the staking contract
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token
+    pub token: Addr,
+    /// The LUART pair the purchases are swapped through
+    pub pair: Addr,
+    /// The denom the purchases are paid in, the other asset of the pair
+    pub offer_denom: String,
+    /// The staking contract the purchased LUART can be bonded in
+    pub staking: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+/// A purchase waiting for the reply of its swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBuy {
+    pub buyer: Addr,
+    pub destination: Destination,
+    pub min_received: Uint128,
+    /// The LUART balance of the router before the swap, the swap returns the difference
+    pub balance_before: Uint128,
+}
+
+pub const PENDING_BUY: Item<PendingBuy> = Item::new("pending_buy");
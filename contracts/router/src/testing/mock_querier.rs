@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Decimal, from_binary, from_slice, OwnedDeps, Querier, QuerierResult, QueryRequest,
+    SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use luart_protocol::token::{QueryMsg as TokenQueryMsg, SwapFeeConfig};
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper};
+use terraswap::asset::AssetInfo;
+use terraswap::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
+
+pub const MOCK_TOKEN: &str = "mock_token";
+pub const MOCK_PAIR: &str = "mock_pair";
+/// The pairs return ten tokens for one coin, and one coin for ten tokens
+pub const PAIR_PRICE: u128 = 10;
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the Terra tax, LUART token and pair queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_balances: HashMap<String, Uint128>,
+    swap_percent_fee: Decimal,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            // A 0.5% tax capped at 1 UST
+            QueryRequest::Custom(TerraQueryWrapper { query_data, .. }) => {
+                let res = match query_data {
+                    TerraQuery::TaxRate {} => to_binary(&TaxRateResponse { rate: Decimal::permille(5) }),
+                    TerraQuery::TaxCap { .. } => to_binary(&TaxCapResponse { cap: Uint128::new(1_000_000) }),
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::from(res))
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr: _, msg }) => {
+                if let Ok(PairQueryMsg::Simulation { offer_asset }) = from_binary(msg) {
+                    let return_amount = match offer_asset.info {
+                        AssetInfo::NativeToken { .. } => offer_asset.amount * Uint128::new(PAIR_PRICE),
+                        AssetInfo::Token { .. } => offer_asset.amount.multiply_ratio(1u128, PAIR_PRICE),
+                    };
+                    return SystemResult::Ok(ContractResult::from(to_binary(&SimulationResponse {
+                        return_amount,
+                        spread_amount: Uint128::zero(),
+                        commission_amount: Uint128::zero(),
+                    })));
+                }
+                if let Ok(TokenQueryMsg::SwapFeeConfig {}) = from_binary(msg) {
+                    return SystemResult::Ok(ContractResult::from(to_binary(&SwapFeeConfig {
+                        fee_admin: "mock_fee_admin".to_string(),
+                        enable_swap_fee: true,
+                        swap_percent_fee: self.swap_percent_fee,
+                        fee_receiver: "mock_fee_receiver".to_string(),
+                    })));
+                }
+                match from_binary(msg) {
+                    Ok(Cw20QueryMsg::Balance { address }) => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                            balance: self.token_balances.get(&address).copied().unwrap_or_default(),
+                        })))
+                    }
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported token query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            swap_percent_fee: Decimal::from_ratio(2u128, 1u128),
+        }
+    }
+
+    // configure the LUART balance of the address
+    pub fn with_token_balance(&mut self, address: &str, balance: u128) {
+        self.token_balances.insert(address.to_string(), Uint128::new(balance));
+    }
+}
@@ -0,0 +1,302 @@
+use cosmwasm_std::{
+    coins, ContractResult, CosmosMsg, DepsMut, Env, from_binary, Reply, Response, StdError, SubMsg,
+    SubMsgExecutionResponse, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use luart_protocol::staking::Cw20HookMsg as StakingHookMsg;
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::{Cw20HookMsg as PairHookMsg, ExecuteMsg as PairExecuteMsg};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{
+    BuySimulationResponse, CallbackMsg, Cw20HookMsg, Destination, ExecuteMsg, InstantiateMsg, QueryMsg,
+    RouteSimulationResponse, SwapOperation,
+};
+use crate::testing::mock_querier::{mock_dependencies, MOCK_PAIR, MOCK_TOKEN};
+
+const OWNER: &str = "mock_owner";
+const STAKING: &str = "mock_staking";
+const BUYER: &str = "buyer";
+const DENOM: &str = "uusd";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        token: MOCK_TOKEN.to_string(),
+        pair: MOCK_PAIR.to_string(),
+        offer_denom: DENOM.to_string(),
+        staking: STAKING.to_string(),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn swap_reply() -> Reply {
+    Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    }
+}
+
+fn guard_reply() -> Reply {
+    Reply {
+        id: 2,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    }
+}
+
+fn token_msg(msg: Cw20ExecuteMsg) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_TOKEN.to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn test_buy() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let buy = ExecuteMsg::Buy { destination: Destination::Stake {}, min_received: Some(Uint128::new(10_000_000)) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BUYER, &coins(1_005_000, "uluna")), buy.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: DENOM.to_string() });
+
+    // The router pays the tax of the coins sent to the pair, the LUART it already holds is left out
+    deps.querier.with_token_balance(MOCK_CONTRACT_ADDR, 7);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BUYER, &coins(1_005_000, DENOM)), buy.clone()).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(WasmMsg::Execute {
+        contract_addr: MOCK_PAIR.to_string(),
+        msg: to_binary(&PairExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+                amount: Uint128::new(1_000_000),
+            },
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        }).unwrap(),
+        funds: coins(1_000_000, DENOM),
+    }, 1)]);
+
+    // The pair cannot start another purchase before the LUART is forwarded
+    let err = execute(deps.as_mut(), env.clone(), mock_info(MOCK_PAIR, &coins(1_005_000, DENOM)), buy.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Reentrant {});
+
+    // The LUART returned is bonded on behalf of the buyer
+    deps.querier.with_token_balance(MOCK_CONTRACT_ADDR, 10_000_007);
+    let res = reply(deps.as_mut(), env.clone(), swap_reply()).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(token_msg(Cw20ExecuteMsg::Send {
+        contract: STAKING.to_string(),
+        amount: Uint128::new(10_000_000),
+        msg: to_binary(&StakingHookMsg::Bond { on_behalf_of: Some(BUYER.to_string()) }).unwrap(),
+    }), 2)]);
+    reply(deps.as_mut(), env.clone(), guard_reply()).unwrap();
+    execute(deps.as_mut(), env, mock_info(BUYER, &coins(1_005_000, DENOM)), buy).unwrap();
+}
+
+#[test]
+fn test_min_received() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Forwarding to a pair pays the 2% swap fee of the token
+    let swap_hook = to_binary(&PairHookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap();
+    let destination = Destination::Contract { contract: "other_pair".to_string(), msg: swap_hook };
+    let simulate = QueryMsg::SimulateBuy { offer_amount: Uint128::new(1_005_000), destination: destination.clone() };
+    let res: BuySimulationResponse = from_binary(&query(deps.as_ref(), env.clone(), simulate).unwrap()).unwrap();
+    assert_eq!(res, BuySimulationResponse {
+        return_amount: Uint128::new(10_000_000),
+        fee_amount: Uint128::new(200_000),
+        received: Uint128::new(9_800_000),
+    });
+
+    let buy = ExecuteMsg::Buy { destination, min_received: Some(Uint128::new(9_900_000)) };
+    execute(deps.as_mut(), env.clone(), mock_info(BUYER, &coins(1_005_000, DENOM)), buy).unwrap();
+    deps.querier.with_token_balance(MOCK_CONTRACT_ADDR, 10_000_000);
+    let err = reply(deps.as_mut(), env.clone(), swap_reply()).unwrap_err();
+    assert_eq!(err, ContractError::MinReceived {
+        received: Uint128::new(9_800_000),
+        min_received: Uint128::new(9_900_000),
+    });
+
+    // The transfers pay no fee, the failed purchase was reverted
+    let mut deps = mock_dependencies(&[]);
+    default_instantiate(deps.as_mut(), env.clone());
+    let buy = ExecuteMsg::Buy {
+        destination: Destination::Wallet { recipient: None },
+        min_received: Some(Uint128::new(9_900_000)),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(BUYER, &coins(1_005_000, DENOM)), buy).unwrap();
+    deps.querier.with_token_balance(MOCK_CONTRACT_ADDR, 10_000_000);
+    let res = reply(deps.as_mut(), env, swap_reply()).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(token_msg(Cw20ExecuteMsg::Transfer {
+        recipient: BUYER.to_string(),
+        amount: Uint128::new(10_000_000),
+    }), 2)]);
+}
+
+#[test]
+fn test_simulate_route() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let luart = AssetInfo::Token { contract_addr: MOCK_TOKEN.to_string() };
+    let uusd = AssetInfo::NativeToken { denom: DENOM.to_string() };
+    let other_token = AssetInfo::Token { contract_addr: "mock_other_token".to_string() };
+    let operations = vec![
+        SwapOperation::Terraswap {
+            pair: MOCK_PAIR.to_string(),
+            offer_asset_info: luart.clone(),
+            ask_asset_info: uusd.clone(),
+        },
+        SwapOperation::Terraswap {
+            pair: "mock_other_pair".to_string(),
+            offer_asset_info: uusd.clone(),
+            ask_asset_info: other_token,
+        },
+    ];
+
+    // The LUART leg pays the 2% swap fee, the uusd returned pays the tax when it reaches the
+    // router and again when sent to the next pair
+    let simulate = QueryMsg::SimulateRoute {
+        offer: Asset { info: luart, amount: Uint128::new(1_000_000) },
+        operations: operations.clone(),
+    };
+    let res: RouteSimulationResponse = from_binary(&query(deps.as_ref(), env.clone(), simulate).unwrap()).unwrap();
+    assert_eq!(res, RouteSimulationResponse {
+        return_amount: Uint128::new(970_260),
+        fee_amount: Uint128::new(20_000),
+    });
+
+    // The operations chain from the offer asset
+    let simulate = QueryMsg::SimulateRoute {
+        offer: Asset { info: uusd, amount: Uint128::new(1_000_000) },
+        operations,
+    };
+    let err = query(deps.as_ref(), env, simulate).unwrap_err();
+    assert_eq!(err, StdError::generic_err("The operations of the route do not follow each other"));
+}
+
+#[test]
+fn test_execute_swap_operations() {
+    let mut deps = mock_dependencies(&coins(98_000, DENOM));
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let luart = AssetInfo::Token { contract_addr: MOCK_TOKEN.to_string() };
+    let uusd = AssetInfo::NativeToken { denom: DENOM.to_string() };
+    let swap_luart = SwapOperation::Terraswap {
+        pair: MOCK_PAIR.to_string(),
+        offer_asset_info: luart,
+        ask_asset_info: uusd.clone(),
+    };
+    let swap_uusd = SwapOperation::Astroport {
+        pair: "mock_astroport_pair".to_string(),
+        offer_asset_info: uusd.clone(),
+        ask_asset_info: AssetInfo::NativeToken { denom: "uluna".to_string() },
+    };
+
+    // The LUART is sent to the router, the last operation pays the recipient
+    let route = Cw20HookMsg::ExecuteSwapOperations {
+        operations: vec![swap_luart.clone(), swap_uusd.clone()],
+        minimum_receive: Some(Uint128::new(970_000)),
+        to: Some("recipient".to_string()),
+    };
+    let receive = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: BUYER.to_string(),
+        amount: Uint128::new(1_000_000),
+        msg: to_binary(&route).unwrap(),
+    });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(MOCK_TOKEN, &[]), receive).unwrap();
+    let callback = |msg: CallbackMsg| SubMsg::new(WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(msg)).unwrap(),
+        funds: vec![],
+    });
+    assert_eq!(res.messages, vec![
+        callback(CallbackMsg::ExecuteSwapOperation { operation: swap_luart.clone(), to: None }),
+        callback(CallbackMsg::ExecuteSwapOperation {
+            operation: swap_uusd.clone(),
+            to: Some("recipient".to_string()),
+        }),
+        SubMsg::reply_on_success(callback(CallbackMsg::AssertMinimumReceive {
+            asset_info: AssetInfo::NativeToken { denom: "uluna".to_string() },
+            prev_balance: Uint128::zero(),
+            minimum_receive: Uint128::new(970_000),
+            receiver: "recipient".to_string(),
+        }).msg, 2),
+    ]);
+
+    let swap = ExecuteMsg::Callback(CallbackMsg::ExecuteSwapOperation { operation: swap_luart, to: None });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BUYER, &[]), swap.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The token takes its swap fee out of the LUART sent to the pair
+    deps.querier.with_token_balance(MOCK_CONTRACT_ADDR, 1_000_000);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), swap).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(token_msg(Cw20ExecuteMsg::Send {
+        contract: MOCK_PAIR.to_string(),
+        amount: Uint128::new(1_000_000),
+        msg: to_binary(&PairHookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap(),
+    }))]);
+
+    // The router pays the tax of the uusd sent to the next pair
+    let swap = ExecuteMsg::Callback(CallbackMsg::ExecuteSwapOperation {
+        operation: swap_uusd,
+        to: Some("recipient".to_string()),
+    });
+    let res = execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), swap).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: "mock_astroport_pair".to_string(),
+        msg: to_binary(&PairExecuteMsg::Swap {
+            offer_asset: Asset { info: uusd, amount: Uint128::new(97_512) },
+            belief_price: None,
+            max_spread: None,
+            to: Some("recipient".to_string()),
+        }).unwrap(),
+        funds: coins(97_512, DENOM),
+    })]);
+
+    let assert = ExecuteMsg::Callback(CallbackMsg::AssertMinimumReceive {
+        asset_info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        prev_balance: Uint128::new(90_000),
+        minimum_receive: Uint128::new(10_000),
+        receiver: MOCK_CONTRACT_ADDR.to_string(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), assert).unwrap_err();
+    assert_eq!(err, ContractError::MinReceived {
+        received: Uint128::new(8_000),
+        min_received: Uint128::new(10_000),
+    });
+
+    // The pairs of the route cannot start another route until the route replied
+    let route = ExecuteMsg::ExecuteSwapOperations {
+        operations: vec![SwapOperation::Terraswap {
+            pair: MOCK_PAIR.to_string(),
+            offer_asset_info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+            ask_asset_info: AssetInfo::Token { contract_addr: MOCK_TOKEN.to_string() },
+        }],
+        minimum_receive: None,
+        to: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(MOCK_PAIR, &coins(100, DENOM)), route.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Reentrant {});
+    reply(deps.as_mut(), env.clone(), guard_reply()).unwrap();
+
+    // The coins of a route starting from a native asset are sent along the message
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BUYER, &coins(100, "uluna")), route.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: DENOM.to_string() });
+    let res = execute(deps.as_mut(), env, mock_info(BUYER, &coins(100, DENOM)), route).unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
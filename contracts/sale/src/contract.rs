@@ -0,0 +1,440 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo,
+    Response, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StateResponse,
+    UserInfoResponse,
+};
+use crate::state::{Config, CONFIG, SaleMode, State, STATE, USERS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-sale";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    let config = Config {
+        owner,
+        mode: msg.mode,
+        offering_token: deps.api.addr_validate(&msg.offering_token)?,
+        payment_denom: msg.payment_denom,
+        rate: msg.rate,
+        soft_cap: msg.soft_cap,
+        hard_cap: msg.hard_cap,
+        min_contribution: msg.min_contribution,
+        max_per_wallet: msg.max_per_wallet,
+        start_time: msg.start_time,
+        end_time: msg.end_time,
+        vesting_cliff: msg.vesting_cliff,
+        vesting_duration: msg.vesting_duration,
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+    };
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &State::default())?;
+
+    Ok(Response::default())
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.rate.is_zero() {
+        return Err(ContractError::InvalidConfig { reason: "rate must be positive".to_string() });
+    }
+    if config.hard_cap.is_zero() || config.soft_cap > config.hard_cap {
+        return Err(ContractError::InvalidConfig {
+            reason: "hard cap must be positive and above the soft cap".to_string(),
+        });
+    }
+    if config.max_per_wallet.is_zero() || config.min_contribution > config.max_per_wallet {
+        return Err(ContractError::InvalidConfig {
+            reason: "wallet limit must be positive and above the minimum contribution".to_string(),
+        });
+    }
+    if config.end_time <= config.start_time {
+        return Err(ContractError::InvalidConfig { reason: "sale must end after it starts".to_string() });
+    }
+    if config.vesting_cliff > config.vesting_duration {
+        return Err(ContractError::InvalidConfig {
+            reason: "vesting cliff must not exceed the vesting duration".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Contribute {} => contribute(deps, env, info),
+        ExecuteMsg::Claim {} => claim(deps, env, info),
+        ExecuteMsg::Refund {} => refund(deps, env, info),
+        ExecuteMsg::WithdrawFunds {} => withdraw_funds(deps, env, info),
+        ExecuteMsg::UpdateConfig { owner, treasury } => update_config(deps, info, owner, treasury),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Fund {} => {
+            if config.offering_token != info.sender || config.owner != cw20_msg.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            if env.block.time.seconds() >= config.end_time {
+                return Err(ContractError::SaleNotActive {});
+            }
+
+            let mut state = STATE.load(deps.storage)?;
+            state.offering_amount = state.offering_amount.checked_add(cw20_msg.amount)?;
+            STATE.save(deps.storage, &state)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "fund")
+                .add_attribute("amount", cw20_msg.amount)
+                .add_attribute("offering_amount", state.offering_amount))
+        }
+    }
+}
+
+pub fn contribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.payment_denom)?;
+
+    let now = env.block.time.seconds();
+    if now < config.start_time || now >= config.end_time {
+        return Err(ContractError::SaleNotActive {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    // The deposited tokens must cover a sale filled up to the hard cap
+    if state.offering_amount < config.hard_cap * config.rate {
+        return Err(ContractError::NotFunded {});
+    }
+    if amount < config.min_contribution {
+        return Err(ContractError::BelowMinContribution { min: config.min_contribution.to_string() });
+    }
+
+    let mut user = USERS.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+
+    // The part above the wallet limit or the hard cap is refunded right away,
+    // the overflow mode refunds the part above the hard cap after the sale instead
+    let mut accepted = amount.min(config.max_per_wallet.saturating_sub(user.contributed));
+    if config.mode == SaleMode::FixedPrice {
+        accepted = accepted.min(config.hard_cap.saturating_sub(state.total_raised));
+    }
+    if accepted.is_zero() {
+        return Err(ContractError::CapReached {});
+    }
+    let refund = amount.checked_sub(accepted)?;
+
+    user.contributed = user.contributed.checked_add(accepted)?;
+    state.total_raised = state.total_raised.checked_add(accepted)?;
+    USERS.save(deps.storage, &info.sender, &user)?;
+    STATE.save(deps.storage, &state)?;
+
+    let mut response = Response::new();
+    if !refund.is_zero() {
+        response = response.add_message(bank_send_msg(&info.sender, &config.payment_denom, refund));
+    }
+
+    Ok(response
+        .add_attribute("action", "contribute")
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", accepted)
+        .add_attribute("refund", refund))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    if now < config.end_time {
+        return Err(ContractError::SaleNotEnded {});
+    }
+    if state.total_raised < config.soft_cap {
+        return Err(ContractError::SoftCapNotReached {});
+    }
+
+    let mut user = USERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NothingToClaim {})?;
+    let purchased = config.accepted_amount(user.contributed, state.total_raised) * config.rate;
+    let vested = config.vested_amount(purchased, now);
+    let amount = vested.checked_sub(user.claimed)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    user.claimed = vested;
+    USERS.save(deps.storage, &info.sender, &user)?;
+
+    Ok(Response::new()
+        .add_message(token_transfer_msg(&config.offering_token, info.sender.to_string(), amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    if env.block.time.seconds() < config.end_time {
+        return Err(ContractError::SaleNotEnded {});
+    }
+    let soft_cap_reached = state.total_raised >= config.soft_cap;
+    if soft_cap_reached && config.mode == SaleMode::FixedPrice {
+        return Err(ContractError::SoftCapReached {});
+    }
+
+    let mut user = USERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NothingToClaim {})?;
+    let amount = refundable_amount(&config, &state, &user.contributed);
+    if user.refunded || amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    user.refunded = true;
+    USERS.save(deps.storage, &info.sender, &user)?;
+
+    Ok(Response::new()
+        .add_message(bank_send_msg(&info.sender, &config.payment_denom, amount))
+        .add_attribute("action", "refund")
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn withdraw_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < config.end_time {
+        return Err(ContractError::SaleNotEnded {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    if state.withdrawn {
+        return Err(ContractError::AlreadyWithdrawn {});
+    }
+    state.withdrawn = true;
+    STATE.save(deps.storage, &state)?;
+
+    // A failed sale keeps the raised funds for refunds and returns every offering token
+    let soft_cap_reached = state.total_raised >= config.soft_cap;
+    let (raised, sold_amount) = if soft_cap_reached {
+        let raised = config.accepted_amount(state.total_raised, state.total_raised);
+        (raised, raised * config.rate)
+    } else {
+        (Uint128::zero(), Uint128::zero())
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !raised.is_zero() {
+        messages.push(bank_send_msg(&config.treasury, &config.payment_denom, raised));
+    }
+
+    let unsold_amount = state.offering_amount.checked_sub(sold_amount)?;
+    if !unsold_amount.is_zero() {
+        messages.push(token_transfer_msg(&config.offering_token, config.owner.to_string(), unsold_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "withdraw_funds")
+        .add_attribute("raised", raised)
+        .add_attribute("unsold_amount", unsold_amount))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = deps.api.addr_validate(&treasury)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, env, address)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        mode: config.mode,
+        offering_token: config.offering_token.to_string(),
+        payment_denom: config.payment_denom,
+        rate: config.rate,
+        soft_cap: config.soft_cap,
+        hard_cap: config.hard_cap,
+        min_contribution: config.min_contribution,
+        max_per_wallet: config.max_per_wallet,
+        start_time: config.start_time,
+        end_time: config.end_time,
+        vesting_cliff: config.vesting_cliff,
+        vesting_duration: config.vesting_duration,
+        treasury: config.treasury.to_string(),
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        offering_amount: state.offering_amount,
+        total_raised: state.total_raised,
+        soft_cap_reached: state.total_raised >= config.soft_cap,
+        withdrawn: state.withdrawn,
+    })
+}
+
+pub fn query_user_info(deps: Deps, env: Env, address: String) -> StdResult<UserInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let user = USERS.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    let now = env.block.time.seconds();
+    let ended = now >= config.end_time;
+    let soft_cap_reached = state.total_raised >= config.soft_cap;
+
+    let accepted = config.accepted_amount(user.contributed, state.total_raised);
+    let purchased = accepted * config.rate;
+    let vested = if ended && soft_cap_reached {
+        config.vested_amount(purchased, now)
+    } else {
+        Uint128::zero()
+    };
+    let refundable = if ended && !user.refunded {
+        refundable_amount(&config, &state, &user.contributed)
+    } else {
+        Uint128::zero()
+    };
+
+    Ok(UserInfoResponse {
+        contributed: user.contributed,
+        accepted,
+        purchased,
+        vested,
+        claimed: user.claimed,
+        claimable: vested.saturating_sub(user.claimed),
+        refundable,
+    })
+}
+
+/// Returns the whole contribution if the sale failed,
+/// otherwise the part raised above the hard cap in the overflow mode
+fn refundable_amount(config: &Config, state: &State, contributed: &Uint128) -> Uint128 {
+    if state.total_raised < config.soft_cap {
+        return *contributed;
+    }
+    contributed.saturating_sub(config.accepted_amount(*contributed, state.total_raised))
+}
+
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds { denom: denom.to_string() }),
+    }
+}
+
+fn bank_send_msg(recipient: &Addr, denom: &str, amount: Uint128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin {
+            denom: denom.to_string(),
+            amount,
+        }],
+    })
+}
+
+fn token_transfer_msg(token: &Addr, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
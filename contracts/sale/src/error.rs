@@ -0,0 +1,47 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid sale configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Must send only {denom} funds")]
+    InvalidFunds { denom: String },
+
+    #[error("The sale is not funded with enough offering tokens")]
+    NotFunded {},
+
+    #[error("The sale is not active")]
+    SaleNotActive {},
+
+    #[error("The sale has not ended yet")]
+    SaleNotEnded {},
+
+    #[error("Contribution is below the minimum of {min}")]
+    BelowMinContribution { min: String },
+
+    #[error("The wallet limit or the hard cap is reached")]
+    CapReached {},
+
+    #[error("The soft cap was not reached, contributions are refundable")]
+    SoftCapNotReached {},
+
+    #[error("The soft cap was reached, contributions are not refundable")]
+    SoftCapReached {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Already withdrawn")]
+    AlreadyWithdrawn {},
+}
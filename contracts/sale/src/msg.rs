@@ -0,0 +1,132 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::SaleMode;
+
+/// This structure describes the parameters used for creating a sale contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The sale mechanism
+    pub mode: SaleMode,
+    /// The cw20 token being sold
+    pub offering_token: String,
+    /// The native denom contributions are paid in
+    pub payment_denom: String,
+    /// The amount of offering tokens received per payment token
+    pub rate: Decimal,
+    pub soft_cap: Uint128,
+    /// The contribution cap, or the raise target in the overflow mode
+    pub hard_cap: Uint128,
+    pub min_contribution: Uint128,
+    pub max_per_wallet: Uint128,
+    /// Sale start timestamp in seconds
+    pub start_time: u64,
+    /// Sale end timestamp in seconds
+    pub end_time: u64,
+    /// Seconds after the sale end before purchased tokens start to vest
+    pub vesting_cliff: u64,
+    /// Seconds after the sale end when purchased tokens are fully vested
+    pub vesting_duration: u64,
+    /// The address receiving the raised funds
+    pub treasury: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the offering tokens, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Contributes the sent payment funds to the sale
+    Contribute {},
+    /// Transfers the vested purchased tokens to the sender
+    Claim {},
+    /// Returns the sender contribution if the sale ended below the soft cap,
+    /// or the part raised above the hard cap in the overflow mode
+    Refund {},
+    /// Transfers the raised funds to the treasury and unsold tokens back to the owner
+    /// once the sale has ended. Only the owner can execute it.
+    WithdrawFunds {},
+    /// Updates the sale configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        treasury: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits offering tokens for the sale. Only the owner can send it.
+    Fund {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the sale configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the sale progress.
+    /// Return type: StateResponse.
+    State {},
+    /// Returns the contribution and vesting state of the address.
+    /// Return type: UserInfoResponse.
+    UserInfo { address: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub mode: SaleMode,
+    pub offering_token: String,
+    pub payment_denom: String,
+    pub rate: Decimal,
+    pub soft_cap: Uint128,
+    pub hard_cap: Uint128,
+    pub min_contribution: Uint128,
+    pub max_per_wallet: Uint128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub vesting_cliff: u64,
+    pub vesting_duration: u64,
+    pub treasury: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub offering_amount: Uint128,
+    pub total_raised: Uint128,
+    pub soft_cap_reached: bool,
+    pub withdrawn: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserInfoResponse {
+    /// The amount of payment tokens contributed
+    pub contributed: Uint128,
+    /// The part of the contribution used to purchase offering tokens
+    pub accepted: Uint128,
+    /// The amount of offering tokens purchased
+    pub purchased: Uint128,
+    /// The amount of purchased tokens vested so far
+    pub vested: Uint128,
+    /// The amount of offering tokens already claimed
+    pub claimed: Uint128,
+    /// The amount of offering tokens claimable now
+    pub claimable: Uint128,
+    /// The amount of payment tokens refundable now
+    pub refundable: Uint128,
+}
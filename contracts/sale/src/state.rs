@@ -0,0 +1,98 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SaleMode {
+    /// Contributions are accepted at the fixed rate until the hard cap is reached
+    FixedPrice,
+    /// Contributions are not capped, the offering tokens are shared pro-rata to the contributions
+    /// and the part raised above the hard cap is refunded after the sale
+    Overflow,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who funds the sale and withdraws the raised funds
+    pub owner: Addr,
+    /// The sale mechanism
+    pub mode: SaleMode,
+    /// The cw20 token being sold
+    pub offering_token: Addr,
+    /// The native denom contributions are paid in
+    pub payment_denom: String,
+    /// The amount of offering tokens received per payment token
+    pub rate: Decimal,
+    /// The sale is cancelled and refunded if less is raised
+    pub soft_cap: Uint128,
+    /// Contributions are rejected above this amount in the fixed price mode,
+    /// or the raise target in the overflow mode
+    pub hard_cap: Uint128,
+    /// The minimum amount of a single contribution
+    pub min_contribution: Uint128,
+    /// The maximum amount contributed by a single wallet
+    pub max_per_wallet: Uint128,
+    /// Sale start timestamp in seconds
+    pub start_time: u64,
+    /// Sale end timestamp in seconds
+    pub end_time: u64,
+    /// Seconds after the sale end before purchased tokens start to vest
+    pub vesting_cliff: u64,
+    /// Seconds after the sale end when purchased tokens are fully vested
+    pub vesting_duration: u64,
+    /// The address receiving the raised funds
+    pub treasury: Addr,
+}
+
+impl Config {
+    /// Returns the part of the contribution used to purchase offering tokens,
+    /// the remainder being refundable in the overflow mode
+    pub fn accepted_amount(&self, contributed: Uint128, total_raised: Uint128) -> Uint128 {
+        match self.mode {
+            SaleMode::Overflow if total_raised > self.hard_cap => {
+                contributed.multiply_ratio(self.hard_cap, total_raised)
+            }
+            _ => contributed,
+        }
+    }
+
+    /// Returns the vested part of the given amount at the given timestamp.
+    /// Vesting is linear from the sale end.
+    pub fn vested_amount(&self, amount: Uint128, time: u64) -> Uint128 {
+        if time < self.end_time + self.vesting_cliff {
+            return Uint128::zero();
+        }
+        let elapsed = time - self.end_time;
+        if elapsed >= self.vesting_duration {
+            return amount;
+        }
+        amount.multiply_ratio(elapsed, self.vesting_duration)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct State {
+    /// The amount of offering tokens deposited by the owner
+    pub offering_amount: Uint128,
+    /// The total amount of payment tokens raised
+    pub total_raised: Uint128,
+    /// Whether the owner withdrew the raised funds and unsold tokens
+    pub withdrawn: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct UserInfo {
+    /// The amount of payment tokens contributed
+    pub contributed: Uint128,
+    /// The amount of offering tokens already claimed
+    pub claimed: Uint128,
+    /// Whether the contribution was refunded after a failed sale
+    pub refunded: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+pub const USERS: Map<&Addr, UserInfo> = Map::new("users");
@@ -0,0 +1,264 @@
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary,
+    Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, UserInfoResponse};
+use crate::state::SaleMode;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const TREASURY: &str = "mock_treasury";
+const USER: &str = "mock_user";
+const DENOM: &str = "uusd";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    instantiate_with_mode(deps, env, SaleMode::FixedPrice)
+}
+
+/// Instantiates a sale starting 100 seconds after the current block and lasting 100 seconds
+fn instantiate_with_mode(deps: DepsMut, env: Env, mode: SaleMode) -> Response {
+    let now = env.block.time.seconds();
+    let msg = InstantiateMsg {
+        owner: None,
+        mode,
+        offering_token: TOKEN.to_string(),
+        payment_denom: DENOM.to_string(),
+        rate: Decimal::from_ratio(10u128, 1u128),
+        soft_cap: Uint128::new(1_000),
+        hard_cap: Uint128::new(2_000),
+        min_contribution: Uint128::new(100),
+        max_per_wallet: Uint128::new(1_500),
+        start_time: now + 100,
+        end_time: now + 200,
+        vesting_cliff: 10 * DAY,
+        vesting_duration: 100 * DAY,
+        treasury: TREASURY.to_string(),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn fund(deps: DepsMut, env: Env) -> Response {
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: OWNER.to_string(),
+                amount: Uint128::new(20_000),
+                msg: to_binary(&Cw20HookMsg::Fund {}).unwrap(),
+            })).unwrap()
+}
+
+fn refund_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_contribute() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap_err();
+    assert_eq!(err, ContractError::SaleNotActive {});
+
+    // Contributions wait for the hard cap to be covered by offering tokens
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap_err();
+    assert_eq!(err, ContractError::NotFunded {});
+
+    fund(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(99, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap_err();
+    assert_eq!(err, ContractError::BelowMinContribution { min: "100".to_string() });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, "uluna")]),
+                      ExecuteMsg::Contribute {}).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds { denom: DENOM.to_string() });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap();
+    assert!(res.messages.is_empty());
+
+    // The part above the wallet limit is refunded
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 500)]);
+
+    // The part above the hard cap is refunded
+    let res = execute(deps.as_mut(), env.clone(), mock_info("another", &[Coin::new(1_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg("another", 500)]);
+
+    let err = execute(deps.as_mut(), env, mock_info("another", &[Coin::new(100, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap_err();
+    assert_eq!(err, ContractError::CapReached {});
+}
+
+#[test]
+fn test_vested_claim_and_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    fund(deps.as_mut(), env.clone());
+
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Contribute {}).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::SaleNotEnded {});
+
+    // Nothing is vested before the cliff
+    env.block.time = env.block.time.plus_seconds(100 + 9 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Vesting is linear from the sale end once the cliff has passed
+    env.block.time = env.block.time.plus_seconds(41 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(USER, 5_000)]);
+
+    env.block.time = env.block.time.plus_seconds(60 * DAY);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::UserInfo { address: USER.to_string() }).unwrap();
+    let user_info: UserInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(user_info, UserInfoResponse {
+        contributed: Uint128::new(1_000),
+        accepted: Uint128::new(1_000),
+        purchased: Uint128::new(10_000),
+        vested: Uint128::new(10_000),
+        claimed: Uint128::new(5_000),
+        claimable: Uint128::new(5_000),
+        refundable: Uint128::zero(),
+    });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::SoftCapReached {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::WithdrawFunds {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::WithdrawFunds {}).unwrap();
+    assert_eq!(res.messages, vec![
+        refund_msg(TREASURY, 1_000),
+        transfer_msg(OWNER, 10_000),
+    ]);
+
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::WithdrawFunds {}).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyWithdrawn {});
+}
+
+#[test]
+fn test_refund_below_soft_cap() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    fund(deps.as_mut(), env.clone());
+
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(500, DENOM)]),
+            ExecuteMsg::Contribute {}).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::SaleNotEnded {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::SoftCapNotReached {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 500)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Every offering token goes back to the owner
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::WithdrawFunds {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(OWNER, 20_000)]);
+}
+
+#[test]
+fn test_overflow_sale() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    instantiate_with_mode(deps.as_mut(), env.clone(), SaleMode::Overflow);
+    fund(deps.as_mut(), env.clone());
+
+    // Contributions above the hard cap are accepted, only the wallet limit applies
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[Coin::new(2_000, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 500)]);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("another", &[Coin::new(1_500, DENOM)]),
+                      ExecuteMsg::Contribute {}).unwrap();
+    assert!(res.messages.is_empty());
+    execute(deps.as_mut(), env.clone(), mock_info("third", &[Coin::new(1_000, DENOM)]),
+            ExecuteMsg::Contribute {}).unwrap();
+
+    // 4000 raised for a 2000 target, every contribution is half accepted
+    env.block.time = env.block.time.plus_seconds(100 + 100 * DAY);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::UserInfo { address: USER.to_string() }).unwrap();
+    let user_info: UserInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(user_info, UserInfoResponse {
+        contributed: Uint128::new(1_500),
+        accepted: Uint128::new(750),
+        purchased: Uint128::new(7_500),
+        vested: Uint128::new(7_500),
+        claimed: Uint128::zero(),
+        claimable: Uint128::new(7_500),
+        refundable: Uint128::new(750),
+    });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(USER, 750)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]),
+                      ExecuteMsg::Refund {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("third", &[]),
+                      ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("third", 5_000)]);
+
+    // Only the target is raised and every offering token is sold
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::WithdrawFunds {}).unwrap();
+    assert_eq!(res.messages, vec![refund_msg(TREASURY, 2_000)]);
+}
@@ -0,0 +1,320 @@
+use std::convert::TryInto;
+
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{
+    Api, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, to_binary,
+    to_vec,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use luart_protocol::token::{BalanceResponse, QueryMsg as TokenQueryMsg};
+use luart_utils::batch::batch_query;
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, ProposalResponse, ProposalsResponse,
+    QueryMsg, SignedVote, VotePayload, VoteResponse,
+};
+use crate::state::{
+    Config, CONFIG, Proposal, PROPOSAL_COUNT, PROPOSALS, VoteInfo, VoteOption, VOTES,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-snapshot-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const ADDRESS_PREFIX: &str = "terra";
+const COMPRESSED_PUBKEY_LENGTH: usize = 33;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        luart_token: deps.api.addr_validate(&msg.luart_token)?,
+    })?;
+    PROPOSAL_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateProposal {
+            title,
+            description,
+            snapshot_height,
+            end_time,
+        } => create_proposal(deps, env, info, title, description, snapshot_height, end_time),
+        ExecuteMsg::SubmitVotes { proposal_id, votes } => submit_votes(deps, env, proposal_id, votes),
+        ExecuteMsg::UpdateConfig { owner } => update_config(deps, info, owner),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn create_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    snapshot_height: u64,
+    end_time: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if title.is_empty() {
+        return Err(ContractError::InvalidProposal { reason: "title must not be empty".to_string() });
+    }
+    // The balances of a future height could still change
+    if snapshot_height > env.block.height {
+        return Err(ContractError::InvalidProposal {
+            reason: "snapshot height must not be in the future".to_string(),
+        });
+    }
+    if end_time <= env.block.time.seconds() {
+        return Err(ContractError::InvalidProposal { reason: "end time must be in the future".to_string() });
+    }
+
+    let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &Proposal {
+        title,
+        description,
+        snapshot_height,
+        end_time,
+        yes_votes: Default::default(),
+        no_votes: Default::default(),
+        abstain_votes: Default::default(),
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("snapshot_height", snapshot_height.to_string()))
+}
+
+pub fn submit_votes(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+    votes: Vec<SignedVote>,
+) -> Result<Response, ContractError> {
+    if votes.is_empty() {
+        return Err(ContractError::NoVotes {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if env.block.time.seconds() >= proposal.end_time {
+        return Err(ContractError::VotingPeriodEnded {});
+    }
+
+    // A bad vote must not fail the whole batch, it is skipped instead
+    let mut accepted = 0u64;
+    let mut rejected = 0u64;
+    for signed_vote in votes {
+        let voter = match vote_signer(deps.api, &env, proposal_id, &signed_vote)? {
+            Some(voter) => voter,
+            None => {
+                rejected += 1;
+                continue;
+            }
+        };
+        if VOTES.has(deps.storage, (U64Key::new(proposal_id), &voter)) {
+            rejected += 1;
+            continue;
+        }
+
+        let balance: BalanceResponse = deps.querier.query_wasm_smart(
+            &config.luart_token,
+            &TokenQueryMsg::BalanceAt { address: voter.clone(), height: proposal.snapshot_height },
+        )?;
+        let weight = balance.balance;
+        if weight.is_zero() {
+            rejected += 1;
+            continue;
+        }
+
+        match signed_vote.vote {
+            VoteOption::Yes => proposal.yes_votes = proposal.yes_votes.checked_add(weight)?,
+            VoteOption::No => proposal.no_votes = proposal.no_votes.checked_add(weight)?,
+            VoteOption::Abstain => proposal.abstain_votes = proposal.abstain_votes.checked_add(weight)?,
+        }
+        VOTES.save(deps.storage, (U64Key::new(proposal_id), &voter), &VoteInfo {
+            option: signed_vote.vote,
+            weight,
+        })?;
+        accepted += 1;
+    }
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_votes")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("accepted", accepted.to_string())
+        .add_attribute("rejected", rejected.to_string()))
+}
+
+/// Returns the address of the vote signer, none if the signature is invalid
+fn vote_signer(
+    api: &dyn Api,
+    env: &Env,
+    proposal_id: u64,
+    signed_vote: &SignedVote,
+) -> StdResult<Option<String>> {
+    if signed_vote.pubkey.len() != COMPRESSED_PUBKEY_LENGTH {
+        return Ok(None);
+    }
+
+    let payload = to_vec(&VotePayload {
+        contract: env.contract.address.to_string(),
+        proposal_id,
+        vote: signed_vote.vote,
+    })?;
+    let verified = api
+        .secp256k1_verify(&Sha256::digest(&payload), &signed_vote.signature, &signed_vote.pubkey)
+        .unwrap_or(false);
+    if !verified {
+        return Ok(None);
+    }
+
+    Ok(Some(pubkey_to_address(&signed_vote.pubkey)?))
+}
+
+/// Derives the terra account address of a compressed secp256k1 public key
+pub fn pubkey_to_address(pubkey: &[u8]) -> StdResult<String> {
+    let hash = Ripemd160::digest(&Sha256::digest(pubkey));
+    bech32::encode(ADDRESS_PREFIX, hash.to_base32(), Variant::Bech32)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        luart_token: config.luart_token.to_string(),
+    })
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    Ok(proposal_response(proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let proposals: StdResult<Vec<ProposalResponse>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, proposal) = item?;
+            Ok(proposal_response(parse_proposal_id(&k)?, proposal))
+        })
+        .collect();
+
+    Ok(ProposalsResponse { proposals: proposals? })
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+    let vote = VOTES.may_load(deps.storage, (U64Key::new(proposal_id), &voter))?;
+    Ok(VoteResponse {
+        vote: vote.as_ref().map(|v| v.option),
+        weight: vote.map(|v| v.weight).unwrap_or_default(),
+    })
+}
+
+fn proposal_response(proposal_id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        proposal_id,
+        title: proposal.title,
+        description: proposal.description,
+        snapshot_height: proposal.snapshot_height,
+        end_time: proposal.end_time,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+    }
+}
+
+fn parse_proposal_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted proposal key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
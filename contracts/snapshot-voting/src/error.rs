@@ -0,0 +1,23 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid proposal: {reason}")]
+    InvalidProposal { reason: String },
+
+    #[error("The voting period has ended")]
+    VotingPeriodEnded {},
+
+    #[error("No votes to submit")]
+    NoVotes {},
+}
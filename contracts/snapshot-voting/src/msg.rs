@@ -0,0 +1,110 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, Uint128};
+
+use crate::state::VoteOption;
+
+/// This structure describes the parameters used for creating a snapshot voting contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner registering the proposals, the sender if not set
+    pub owner: Option<String>,
+    /// The LUART token contract, it must answer the BalanceAt query
+    pub luart_token: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Registers a proposal voted off-chain with the token balances at the snapshot height.
+    /// Only the owner can execute it.
+    CreateProposal {
+        title: String,
+        description: String,
+        snapshot_height: u64,
+        end_time: u64,
+    },
+    /// Tallies a batch of votes signed off-chain, anyone can relay them before the proposal end.
+    /// Votes with an invalid signature, already counted or without balance are skipped.
+    SubmitVotes {
+        proposal_id: u64,
+        votes: Vec<SignedVote>,
+    },
+    /// Updates the owner. Only the owner can execute it.
+    UpdateConfig { owner: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignedVote {
+    /// The compressed secp256k1 public key of the voter account
+    pub pubkey: Binary,
+    pub vote: VoteOption,
+    /// The signature of the sha256 hash of the JSON encoded [`VotePayload`]
+    pub signature: Binary,
+}
+
+/// The message signed by the voters, bound to this contract and the proposal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotePayload {
+    pub contract: String,
+    pub proposal_id: u64,
+    pub vote: VoteOption,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a proposal with its tally.
+    /// Return type: ProposalResponse.
+    Proposal { proposal_id: u64 },
+    /// Returns proposals by id.
+    /// Return type: ProposalsResponse.
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the vote of the voter address on a proposal.
+    /// Return type: VoteResponse.
+    Vote { proposal_id: u64, voter: String },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub luart_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: u64,
+    pub title: String,
+    pub description: String,
+    pub snapshot_height: u64,
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteResponse {
+    pub vote: Option<VoteOption>,
+    pub weight: Uint128,
+}
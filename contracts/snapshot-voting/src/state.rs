@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The owner registers the proposals
+    pub owner: Addr,
+    /// The LUART token contract, the voting power is the balance at the proposal snapshot
+    pub luart_token: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub title: String,
+    pub description: String,
+    /// Voting power is read from the token balances at this height
+    pub snapshot_height: u64,
+    /// Signed votes are accepted until this time
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteInfo {
+    pub option: VoteOption,
+    pub weight: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+/// Votes by the terra address derived from the signer public key
+pub const VOTES: Map<(U64Key, &str), VoteInfo> = Map::new("votes");
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Binary, Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use luart_protocol::token::{BalanceResponse, QueryMsg as TokenQueryMsg};
+
+pub const MOCK_TOKEN: &str = "mock_token";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the token queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    balances: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+            if contract_addr == MOCK_TOKEN => self.handle_token_query(msg),
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn handle_token_query(&self, msg: &Binary) -> QuerierResult {
+        match from_binary(msg) {
+            Ok(TokenQueryMsg::BalanceAt { address, height: _ }) => {
+                let balance = self.balances.get(&address).copied().unwrap_or_default();
+                SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse { balance })))
+            }
+            Ok(_) => SystemResult::Err(SystemError::InvalidRequest {
+                error: "Unsupported token query".to_string(),
+                request: msg.clone(),
+            }),
+            Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                error: format!("Parsing token query: {}", e),
+                request: msg.clone(),
+            }),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            balances: HashMap::new(),
+        }
+    }
+
+    // configure the balance of an address, the same at any height
+    pub fn with_balance(&mut self, address: &str, amount: u128) {
+        self.balances.insert(address.to_string(), Uint128::new(amount));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod tests;
+mod mock_querier;
@@ -0,0 +1,173 @@
+use cosmwasm_std::{attr, Binary, DepsMut, Env, from_binary, Response, to_vec, Uint128};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_env, mock_info};
+use k256::ecdsa::{Signature, SigningKey};
+use k256::ecdsa::signature::Signer;
+
+use crate::contract::{execute, instantiate, pubkey_to_address, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, ProposalResponse, QueryMsg, SignedVote, VotePayload,
+    VoteResponse,
+};
+use crate::state::VoteOption;
+use crate::testing::mock_querier::{mock_dependencies, MOCK_TOKEN};
+
+const OWNER: &str = "mock_owner";
+
+const VOTING_PERIOD: u64 = 3 * 86400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: Some(OWNER.to_string()),
+        luart_token: MOCK_TOKEN.to_string(),
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+fn create_proposal_msg(env: &Env) -> ExecuteMsg {
+    ExecuteMsg::CreateProposal {
+        title: "Add a bLUNA pool".to_string(),
+        description: "Add a bLUNA/LUART pool to the gauges".to_string(),
+        snapshot_height: env.block.height - 10,
+        end_time: env.block.time.seconds() + VOTING_PERIOD,
+    }
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32]).unwrap()
+}
+
+fn voter_address(key: &SigningKey) -> String {
+    pubkey_to_address(key.verifying_key().to_bytes().as_slice()).unwrap()
+}
+
+// The vote as signed by a wallet off-chain
+fn signed_vote(key: &SigningKey, proposal_id: u64, vote: VoteOption) -> SignedVote {
+    let payload = to_vec(&VotePayload {
+        contract: MOCK_CONTRACT_ADDR.to_string(),
+        proposal_id,
+        vote,
+    }).unwrap();
+    let signature: Signature = key.sign(&payload);
+    SignedVote {
+        pubkey: Binary::from(key.verifying_key().to_bytes().as_slice()),
+        vote,
+        signature: Binary::from(signature.as_ref()),
+    }
+}
+
+#[test]
+fn test_create_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config, ConfigResponse { owner: OWNER.to_string(), luart_token: MOCK_TOKEN.to_string() });
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      create_proposal_msg(&env)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::CreateProposal {
+                          title: "Future snapshot".to_string(),
+                          description: "".to_string(),
+                          snapshot_height: env.block.height + 1,
+                          end_time: env.block.time.seconds() + VOTING_PERIOD,
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal {
+        reason: "snapshot height must not be in the future".to_string(),
+    });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), create_proposal_msg(&env)).unwrap();
+    assert_eq!(res.attributes, vec![
+        attr("action", "create_proposal"),
+        attr("proposal_id", "1"),
+        attr("snapshot_height", (env.block.height - 10).to_string()),
+    ]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.snapshot_height, env.block.height - 10);
+    assert_eq!(proposal.end_time, env.block.time.seconds() + VOTING_PERIOD);
+    assert_eq!(proposal.yes_votes, Uint128::zero());
+}
+
+#[test]
+fn test_submit_votes() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), create_proposal_msg(&env)).unwrap();
+
+    let alice = signing_key(1);
+    let bob = signing_key(2);
+    let carol = signing_key(3);
+    deps.querier.with_balance(&voter_address(&alice), 1_000);
+    deps.querier.with_balance(&voter_address(&bob), 400);
+
+    // A vote signed for another option does not verify
+    let mut forged_vote = signed_vote(&bob, 1, VoteOption::Yes);
+    forged_vote.vote = VoteOption::No;
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), ExecuteMsg::SubmitVotes {
+        proposal_id: 1,
+        votes: vec![
+            signed_vote(&alice, 1, VoteOption::Yes),
+            forged_vote,
+            signed_vote(&bob, 1, VoteOption::Yes),
+            // Carol had no balance at the snapshot
+            signed_vote(&carol, 1, VoteOption::No),
+            // Signed for another proposal
+            signed_vote(&alice, 2, VoteOption::No),
+        ],
+    }).unwrap();
+    assert_eq!(res.attributes, vec![
+        attr("action", "submit_votes"),
+        attr("proposal_id", "1"),
+        attr("accepted", "2"),
+        attr("rejected", "3"),
+    ]);
+
+    // Votes are counted once per voter
+    let res = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), ExecuteMsg::SubmitVotes {
+        proposal_id: 1,
+        votes: vec![signed_vote(&alice, 1, VoteOption::Yes)],
+    }).unwrap();
+    assert_eq!(res.attributes[2..], [attr("accepted", "0"), attr("rejected", "1")]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.yes_votes, Uint128::new(1_400));
+    assert_eq!(proposal.no_votes, Uint128::zero());
+
+    let res = query(deps.as_ref(), env, QueryMsg::Vote { proposal_id: 1, voter: voter_address(&bob) }).unwrap();
+    let vote: VoteResponse = from_binary(&res).unwrap();
+    assert_eq!(vote, VoteResponse { vote: Some(VoteOption::Yes), weight: Uint128::new(400) });
+}
+
+#[test]
+fn test_voting_period_ended() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), create_proposal_msg(&env)).unwrap();
+
+    let alice = signing_key(1);
+    deps.querier.with_balance(&voter_address(&alice), 1_000);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]),
+                      ExecuteMsg::SubmitVotes { proposal_id: 1, votes: vec![] }).unwrap_err();
+    assert_eq!(err, ContractError::NoVotes {});
+
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    let err = execute(deps.as_mut(), env, mock_info("relayer", &[]), ExecuteMsg::SubmitVotes {
+        proposal_id: 1,
+        votes: vec![signed_vote(&alice, 1, VoteOption::Yes)],
+    }).unwrap_err();
+    assert_eq!(err, ContractError::VotingPeriodEnded {});
+}
@@ -0,0 +1,216 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError, StdResult, Storage, to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use luart_protocol::stats::StatEvent;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, DailyStatsResponse, DayStatsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ReportersResponse,
+};
+use crate::state::{Config, CONFIG, ConfigOwner, DAILY_STATS, DAY, REPORTERS, Stats, TOTALS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-stats";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config { owner: Some(owner) })?;
+    TOTALS.save(deps.storage, &Stats::default())?;
+    for reporter in msg.reporters {
+        REPORTERS.save(deps.storage, &deps.api.addr_validate(&reporter)?, &Empty {})?;
+    }
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Record { events } => record(deps, env, info, events),
+        ExecuteMsg::UpdateReporters { add, remove } => update_reporters(deps, info, add, remove),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn record(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    events: Vec<StatEvent>,
+) -> Result<Response, ContractError> {
+    if !REPORTERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let day = env.block.time.seconds() / DAY;
+    let mut totals = TOTALS.load(deps.storage)?;
+    // The TVL carries over from the last day with activity
+    let mut daily = match DAILY_STATS.may_load(deps.storage, U64Key::new(day))? {
+        Some(daily) => daily,
+        None => Stats { tvl: totals.tvl, ..Stats::default() },
+    };
+
+    let mut res = Response::new()
+        .add_attribute("action", "record")
+        .add_attribute("reporter", info.sender.to_string());
+    for event in events.iter() {
+        match event {
+            StatEvent::Sale { amount } => {
+                totals.volume = totals.volume.checked_add(*amount)?;
+                totals.sales += 1;
+                daily.volume = daily.volume.checked_add(*amount)?;
+                daily.sales += 1;
+                res = res.add_attribute("sale", amount.to_string());
+            }
+            StatEvent::Fee { amount } => {
+                totals.fees = totals.fees.checked_add(*amount)?;
+                daily.fees = daily.fees.checked_add(*amount)?;
+                res = res.add_attribute("fee", amount.to_string());
+            }
+            StatEvent::Lock { amount } => {
+                totals.tvl = totals.tvl.checked_add(*amount)?;
+                res = res.add_attribute("lock", amount.to_string());
+            }
+            // The value locked before the reporter was added is not tracked, it never fails the reporter
+            StatEvent::Unlock { amount } => {
+                totals.tvl = totals.tvl.saturating_sub(*amount);
+                res = res.add_attribute("unlock", amount.to_string());
+            }
+        }
+    }
+    daily.tvl = totals.tvl;
+
+    TOTALS.save(deps.storage, &totals)?;
+    DAILY_STATS.save(deps.storage, U64Key::new(day), &daily)?;
+
+    Ok(res)
+}
+
+pub fn update_reporters(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut res = Response::new().add_attribute("action", "update_reporters");
+    for reporter in add {
+        REPORTERS.save(deps.storage, &deps.api.addr_validate(&reporter)?, &Empty {})?;
+        res = res.add_attribute("added", reporter);
+    }
+    for reporter in remove {
+        REPORTERS.remove(deps.storage, &deps.api.addr_validate(&reporter)?);
+        res = res.add_attribute("removed", reporter);
+    }
+
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Reporters { start_after, limit } => to_binary(&query_reporters(deps, start_after, limit)?),
+        QueryMsg::Totals {} => to_binary(&TOTALS.load(deps.storage)?),
+        QueryMsg::DailyStats { start_after, limit } => {
+            to_binary(&query_daily_stats(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+    })
+}
+
+pub fn query_reporters(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ReportersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let reporters: StdResult<Vec<String>> = REPORTERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|k| Ok(String::from_utf8(k)?))
+        .collect();
+
+    Ok(ReportersResponse { reporters: reporters? })
+}
+
+pub fn query_daily_stats(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DailyStatsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let days: StdResult<Vec<DayStatsResponse>> = DAILY_STATS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, stats) = item?;
+            let day = parse_day(&k)?;
+            Ok(DayStatsResponse { day, start_time: day * DAY, stats })
+        })
+        .collect();
+
+    Ok(DailyStatsResponse { days: days? })
+}
+
+fn parse_day(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted day key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
@@ -0,0 +1,89 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use luart_protocol::stats::StatEvent;
+
+use crate::state::Stats;
+
+/// This structure describes the parameters used for creating a stats contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The contracts allowed to record events from the start
+    pub reporters: Vec<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Records the events in the current day. Only the reporters can execute it.
+    Record { events: Vec<StatEvent> },
+    /// Adds and removes reporters. Only the owner can execute it.
+    UpdateReporters {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the reporters ordered by address.
+    /// Return type: ReportersResponse.
+    Reporters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the stats since the instantiation.
+    /// Return type: Stats.
+    Totals {},
+    /// Returns the stats of the days with activity from the oldest, days are numbered from the epoch.
+    /// The days without activity are left out, their TVL is the one of the previous day.
+    /// Return type: DailyStatsResponse.
+    DailyStats {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReportersResponse {
+    pub reporters: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DayStatsResponse {
+    pub day: u64,
+    /// The time the day starts at
+    pub start_time: u64,
+    pub stats: Stats,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DailyStatsResponse {
+    pub days: Vec<DayStatsResponse>,
+}
@@ -0,0 +1,52 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+/// The length of the buckets the metrics are aggregated in
+pub const DAY: u64 = 86_400;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who manages the reporters, the governance contract once deployed
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+/// The activity aggregated over a period, the amounts in uusd
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stats {
+    pub volume: Uint128,
+    pub sales: u64,
+    pub fees: Uint128,
+    /// The value locked at the end of the period
+    pub tvl: Uint128,
+}
+
+/// The contracts allowed to record events
+pub const REPORTERS: Map<&Addr, Empty> = Map::new("reporters");
+/// The stats of each day with activity, by day number since the epoch
+pub const DAILY_STATS: Map<U64Key, Stats> = Map::new("daily_stats");
+/// The stats since the instantiation
+pub const TOTALS: Item<Stats> = Item::new("totals");
@@ -0,0 +1,108 @@
+use cosmwasm_std::{Deps, DepsMut, Env, from_binary, Response, to_binary, Uint128};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use luart_protocol::stats::{ExecuteMsg as ProtocolExecuteMsg, StatEvent};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{DailyStatsResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReportersResponse};
+use crate::state::{DAY, Stats};
+
+const OWNER: &str = "mock_owner";
+const MARKETPLACE: &str = "mock_marketplace";
+const STAKING: &str = "mock_staking";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        reporters: vec![MARKETPLACE.to_string()],
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn record(events: Vec<StatEvent>) -> ExecuteMsg {
+    // The reporters send the protocol interface of the contract
+    from_binary(&to_binary(&ProtocolExecuteMsg::Record { events }).unwrap()).unwrap()
+}
+
+fn query_days(deps: Deps, start_after: Option<u64>) -> DailyStatsResponse {
+    let msg = QueryMsg::DailyStats { start_after, limit: None };
+    from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+}
+
+fn stats(volume: u128, sales: u64, fees: u128, tvl: u128) -> Stats {
+    Stats { volume: Uint128::new(volume), sales, fees: Uint128::new(fees), tvl: Uint128::new(tvl) }
+}
+
+#[test]
+fn test_reporters() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = record(vec![StatEvent::Lock { amount: Uint128::new(100) }]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Only the owner manages the reporters
+    let update = ExecuteMsg::UpdateReporters {
+        add: vec![STAKING.to_string()],
+        remove: vec![MARKETPLACE.to_string()],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), update.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), msg.clone()).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(MARKETPLACE, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let msg = QueryMsg::Reporters { start_after: None, limit: None };
+    let res: ReportersResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(res.reporters, vec![STAKING.to_string()]);
+}
+
+#[test]
+fn test_daily_stats() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let update = ExecuteMsg::UpdateReporters { add: vec![STAKING.to_string()], remove: vec![] };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update).unwrap();
+    let today = env.block.time.seconds() / DAY;
+
+    let sales = record(vec![
+        StatEvent::Sale { amount: Uint128::new(1_000) },
+        StatEvent::Sale { amount: Uint128::new(500) },
+        StatEvent::Fee { amount: Uint128::new(30) },
+    ]);
+    execute(deps.as_mut(), env.clone(), mock_info(MARKETPLACE, &[]), sales).unwrap();
+    let stakes = record(vec![StatEvent::Lock { amount: Uint128::new(10_000) }]);
+    execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), stakes).unwrap();
+
+    // The days without activity are skipped and the TVL carries over
+    env.block.time = env.block.time.plus_seconds(2 * DAY);
+    let unstakes = record(vec![StatEvent::Unlock { amount: Uint128::new(4_000) }]);
+    execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), unstakes).unwrap();
+    let fees = record(vec![StatEvent::Fee { amount: Uint128::new(5) }]);
+    execute(deps.as_mut(), env.clone(), mock_info(MARKETPLACE, &[]), fees).unwrap();
+
+    let res = query_days(deps.as_ref(), None);
+    let days: Vec<_> = res.days.iter().map(|d| (d.day, d.start_time, d.stats.clone())).collect();
+    assert_eq!(days, vec![
+        (today, today * DAY, stats(1_500, 2, 30, 10_000)),
+        (today + 2, (today + 2) * DAY, stats(0, 0, 5, 6_000)),
+    ]);
+    assert_eq!(query_days(deps.as_ref(), Some(today)).days.len(), 1);
+
+    let res: Stats = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Totals {}).unwrap()).unwrap();
+    assert_eq!(res, stats(1_500, 2, 35, 6_000));
+
+    // An unlock of value locked before the reporter was added never fails the reporter
+    let unstakes = record(vec![StatEvent::Unlock { amount: Uint128::new(7_000) }]);
+    execute(deps.as_mut(), env.clone(), mock_info(STAKING, &[]), unstakes).unwrap();
+    let res: Stats = from_binary(&query(deps.as_ref(), env, QueryMsg::Totals {}).unwrap()).unwrap();
+    assert_eq!(res.tvl, Uint128::zero());
+}
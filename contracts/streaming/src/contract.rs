@@ -0,0 +1,306 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order,
+    Response, StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use terraswap::asset::AssetInfo;
+
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StreamResponse,
+    StreamsResponse,
+};
+use crate::state::{Stream, STREAM_COUNT, STREAMS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-streaming";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    STREAM_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateStream {
+            recipient,
+            rate_per_second,
+            start_time,
+            end_time,
+        } => {
+            let coin = match info.funds.as_slice() {
+                [coin] if !coin.amount.is_zero() => coin.clone(),
+                _ => return Err(ContractError::InvalidFunds {}),
+            };
+            create_stream(
+                deps,
+                env,
+                info.sender,
+                AssetInfo::NativeToken { denom: coin.denom },
+                coin.amount,
+                recipient,
+                rate_per_second,
+                start_time,
+                end_time,
+            )
+        }
+        ExecuteMsg::Withdraw { stream_id, amount } => withdraw(deps, env, info, stream_id, amount),
+        ExecuteMsg::Cancel { stream_id } => cancel(deps, env, info, stream_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateStream {
+            recipient,
+            rate_per_second,
+            start_time,
+            end_time,
+        } => {
+            let payer = deps.api.addr_validate(&cw20_msg.sender)?;
+            create_stream(
+                deps,
+                env,
+                payer,
+                AssetInfo::Token { contract_addr: info.sender.to_string() },
+                cw20_msg.amount,
+                recipient,
+                rate_per_second,
+                start_time,
+                end_time,
+            )
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_stream(
+    deps: DepsMut,
+    env: Env,
+    payer: Addr,
+    info: AssetInfo,
+    deposit: Uint128,
+    recipient: String,
+    rate_per_second: Uint128,
+    start_time: u64,
+    end_time: u64,
+) -> Result<Response, ContractError> {
+    if start_time < env.block.time.seconds() || end_time <= start_time {
+        return Err(ContractError::InvalidStreamTime {});
+    }
+
+    // The deposit is streamed entirely at the given rate
+    let expected = rate_per_second.checked_mul(Uint128::from(end_time - start_time))?;
+    if rate_per_second.is_zero() || deposit != expected {
+        return Err(ContractError::InvalidDeposit { expected });
+    }
+
+    let stream_id = STREAM_COUNT.load(deps.storage)? + 1;
+    STREAM_COUNT.save(deps.storage, &stream_id)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    STREAMS.save(deps.storage, U64Key::new(stream_id), &Stream {
+        payer: payer.clone(),
+        recipient: recipient.clone(),
+        info,
+        deposit,
+        rate_per_second,
+        start_time,
+        end_time,
+        withdrawn: Uint128::zero(),
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_stream")
+        .add_attribute("stream_id", stream_id.to_string())
+        .add_attribute("payer", payer)
+        .add_attribute("recipient", recipient)
+        .add_attribute("deposit", deposit))
+}
+
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut stream = STREAMS.load(deps.storage, U64Key::new(stream_id))?;
+    if stream.recipient != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let withdrawable = stream.withdrawable_amount(env.block.time.seconds());
+    let amount = amount.unwrap_or(withdrawable);
+    if amount.is_zero() || amount > withdrawable {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    stream.withdrawn = stream.withdrawn.checked_add(amount)?;
+    if stream.withdrawn == stream.deposit {
+        STREAMS.remove(deps.storage, U64Key::new(stream_id));
+    } else {
+        STREAMS.save(deps.storage, U64Key::new(stream_id), &stream)?;
+    }
+
+    Ok(Response::new()
+        .add_message(asset_transfer_msg(&stream.info, &stream.recipient, amount)?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("stream_id", stream_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+pub fn cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+) -> Result<Response, ContractError> {
+    let stream = STREAMS.load(deps.storage, U64Key::new(stream_id))?;
+    if stream.payer != info.sender && stream.recipient != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The recipient keeps what was streamed until now, the payer gets the rest back
+    let streamed = stream.streamed_amount(env.block.time.seconds());
+    let recipient_amount = streamed.checked_sub(stream.withdrawn)?;
+    let payer_amount = stream.deposit.checked_sub(streamed)?;
+    STREAMS.remove(deps.storage, U64Key::new(stream_id));
+
+    let mut messages = vec![];
+    if !recipient_amount.is_zero() {
+        messages.push(asset_transfer_msg(&stream.info, &stream.recipient, recipient_amount)?);
+    }
+    if !payer_amount.is_zero() {
+        messages.push(asset_transfer_msg(&stream.info, &stream.payer, payer_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "cancel")
+        .add_attribute("stream_id", stream_id.to_string())
+        .add_attribute("recipient_amount", recipient_amount)
+        .add_attribute("payer_amount", payer_amount))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Stream { stream_id } => to_binary(&query_stream(deps, env, stream_id)?),
+        QueryMsg::Streams { start_after, limit } => {
+            to_binary(&query_streams(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_stream(deps: Deps, env: Env, stream_id: u64) -> StdResult<StreamResponse> {
+    let stream = STREAMS.load(deps.storage, U64Key::new(stream_id))?;
+    Ok(stream_response(stream_id, stream, env.block.time.seconds()))
+}
+
+pub fn query_streams(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let now = env.block.time.seconds();
+
+    let streams: StdResult<Vec<StreamResponse>> = STREAMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, stream) = item?;
+            Ok(stream_response(parse_stream_id(&k)?, stream, now))
+        })
+        .collect();
+
+    Ok(StreamsResponse { streams: streams? })
+}
+
+fn stream_response(stream_id: u64, stream: Stream, now: u64) -> StreamResponse {
+    StreamResponse {
+        stream_id,
+        withdrawable: stream.withdrawable_amount(now),
+        payer: stream.payer.to_string(),
+        recipient: stream.recipient.to_string(),
+        info: stream.info,
+        deposit: stream.deposit,
+        rate_per_second: stream.rate_per_second,
+        start_time: stream.start_time,
+        end_time: stream.end_time,
+        withdrawn: stream.withdrawn,
+    }
+}
+
+fn parse_stream_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted stream key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn asset_transfer_msg(info: &AssetInfo, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must send a single non-zero coin")]
+    InvalidFunds {},
+
+    #[error("The stream must start in the future and end after it starts")]
+    InvalidStreamTime {},
+
+    #[error("The stream deposit must be the rate times the duration: {expected}")]
+    InvalidDeposit { expected: Uint128 },
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+}
@@ -0,0 +1,85 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use terraswap::asset::AssetInfo;
+
+/// This structure describes the parameters used for creating a streaming contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    /// Creates a stream of the sent coin. The coin amount must be the rate times the duration.
+    CreateStream {
+        recipient: String,
+        rate_per_second: Uint128,
+        start_time: u64,
+        end_time: u64,
+    },
+    /// Withdraws the streamed amount, all of it when unset. Only the recipient can execute it.
+    Withdraw {
+        stream_id: u64,
+        amount: Option<Uint128>,
+    },
+    /// Cancels the stream, paying the streamed amount to the recipient and refunding the rest
+    /// to the payer. Only the payer and the recipient can execute it.
+    Cancel { stream_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates a stream of the sent tokens. The amount must be the rate times the duration.
+    CreateStream {
+        recipient: String,
+        rate_per_second: Uint128,
+        start_time: u64,
+        end_time: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns a stream and its withdrawable amount.
+    /// Return type: StreamResponse.
+    Stream { stream_id: u64 },
+    /// Returns streams by id.
+    /// Return type: StreamsResponse.
+    Streams {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamResponse {
+    pub stream_id: u64,
+    pub payer: String,
+    pub recipient: String,
+    pub info: AssetInfo,
+    pub deposit: Uint128,
+    pub rate_per_second: Uint128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub withdrawn: Uint128,
+    /// The streamed amount the recipient can withdraw now
+    pub withdrawable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamsResponse {
+    pub streams: Vec<StreamResponse>,
+}
@@ -0,0 +1,37 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use terraswap::asset::AssetInfo;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stream {
+    pub payer: Addr,
+    pub recipient: Addr,
+    /// The streamed asset, a native coin or a cw20 token
+    pub info: AssetInfo,
+    pub deposit: Uint128,
+    /// The amount streamed to the recipient every second
+    pub rate_per_second: Uint128,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// The amount already withdrawn by the recipient
+    pub withdrawn: Uint128,
+}
+
+impl Stream {
+    /// Returns the amount streamed to the recipient so far
+    pub fn streamed_amount(&self, now: u64) -> Uint128 {
+        let elapsed = now.min(self.end_time).saturating_sub(self.start_time);
+        self.rate_per_second * Uint128::from(elapsed)
+    }
+
+    /// Returns the streamed amount not withdrawn yet
+    pub fn withdrawable_amount(&self, now: u64) -> Uint128 {
+        self.streamed_amount(now) - self.withdrawn
+    }
+}
+
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+pub const STREAMS: Map<U64Key, Stream> = Map::new("streams");
@@ -0,0 +1,141 @@
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StreamResponse};
+
+const PAYER: &str = "mock_payer";
+const RECIPIENT: &str = "mock_recipient";
+const TOKEN: &str = "mock_token";
+const DENOM: &str = "uusd";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    instantiate(deps, env, mock_info(PAYER, &[]), InstantiateMsg {}).unwrap()
+}
+
+fn create_stream_msg(env: &Env, rate_per_second: u128, duration: u64) -> ExecuteMsg {
+    ExecuteMsg::CreateStream {
+        recipient: RECIPIENT.to_string(),
+        rate_per_second: Uint128::new(rate_per_second),
+        start_time: env.block.time.seconds() + 100,
+        end_time: env.block.time.seconds() + 100 + duration,
+    }
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+#[test]
+fn test_withdraw_streamed_amount() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PAYER, &[Coin::new(999, DENOM)]),
+                      create_stream_msg(&env, 10, 100)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDeposit { expected: Uint128::new(1_000) });
+    execute(deps.as_mut(), env.clone(), mock_info(PAYER, &[Coin::new(1_000, DENOM)]),
+            create_stream_msg(&env, 10, 100)).unwrap();
+
+    // Nothing is streamed before the start time
+    let err = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]),
+                      ExecuteMsg::Withdraw { stream_id: 1, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+
+    env.block.time = env.block.time.plus_seconds(130);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PAYER, &[]),
+                      ExecuteMsg::Withdraw { stream_id: 1, amount: None }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]),
+                      ExecuteMsg::Withdraw { stream_id: 1, amount: Some(Uint128::new(100)) }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(RECIPIENT, 100)]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Stream { stream_id: 1 }).unwrap();
+    let stream: StreamResponse = from_binary(&res).unwrap();
+    assert_eq!(stream.withdrawn, Uint128::new(100));
+    assert_eq!(stream.withdrawable, Uint128::new(200));
+
+    // The stream is removed once fully withdrawn
+    env.block.time = env.block.time.plus_seconds(1_000);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]),
+                      ExecuteMsg::Withdraw { stream_id: 1, amount: None }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(RECIPIENT, 900)]);
+    query(deps.as_ref(), env, QueryMsg::Stream { stream_id: 1 }).unwrap_err();
+}
+
+#[test]
+fn test_cancel_stream() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    execute(deps.as_mut(), env.clone(), mock_info(PAYER, &[Coin::new(1_000, DENOM)]),
+            create_stream_msg(&env, 10, 100)).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(120);
+    execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]),
+            ExecuteMsg::Withdraw { stream_id: 1, amount: None }).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(20);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Cancel { stream_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PAYER, &[]),
+                      ExecuteMsg::Cancel { stream_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(RECIPIENT, 200), bank_send_msg(PAYER, 600)]);
+    query(deps.as_ref(), env, QueryMsg::Stream { stream_id: 1 }).unwrap_err();
+}
+
+#[test]
+fn test_token_stream() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: PAYER.to_string(),
+        amount: Uint128::new(5_000),
+        msg: to_binary(&Cw20HookMsg::CreateStream {
+            recipient: RECIPIENT.to_string(),
+            rate_per_second: Uint128::new(50),
+            start_time: env.block.time.seconds(),
+            end_time: env.block.time.seconds() + 100,
+        }).unwrap(),
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(TOKEN, &[]), msg).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(10);
+    let res = execute(deps.as_mut(), env, mock_info(RECIPIENT, &[]),
+                      ExecuteMsg::Cancel { stream_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: RECIPIENT.to_string(),
+                amount: Uint128::new(500),
+            }).unwrap(),
+            funds: vec![],
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: PAYER.to_string(),
+                amount: Uint128::new(4_500),
+            }).unwrap(),
+            funds: vec![],
+        })),
+    ]);
+}
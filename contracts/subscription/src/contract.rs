@@ -0,0 +1,262 @@
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PlanResponse, QueryMsg, SubscriptionResponse,
+    SubscriptionsResponse,
+};
+use crate::state::{Config, CONFIG, Plan, PLAN_COUNT, PLANS, Subscription, SUBSCRIPTIONS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-subscription";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(deps.storage, &Config { token: deps.api.addr_validate(&msg.token)? })?;
+    PLAN_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::RegisterPlan { price, period, grace_period } => {
+            register_plan(deps, info, price, period, grace_period)
+        }
+        ExecuteMsg::ClosePlan { plan_id } => close_plan(deps, info, plan_id),
+        ExecuteMsg::Subscribe { plan_id } => subscribe(deps, env, info, plan_id),
+        ExecuteMsg::Collect { plan_id, subscriber } => collect(deps, env, plan_id, subscriber),
+        ExecuteMsg::Cancel { plan_id } => cancel(deps, info, plan_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn register_plan(
+    deps: DepsMut,
+    info: MessageInfo,
+    price: Uint128,
+    period: u64,
+    grace_period: u64,
+) -> Result<Response, ContractError> {
+    if price.is_zero() || period == 0 {
+        return Err(ContractError::InvalidPlan {});
+    }
+
+    let plan_id = PLAN_COUNT.load(deps.storage)? + 1;
+    PLAN_COUNT.save(deps.storage, &plan_id)?;
+    PLANS.save(deps.storage, U64Key::new(plan_id), &Plan {
+        merchant: info.sender.clone(),
+        price,
+        period,
+        grace_period,
+        closed: false,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_plan")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("merchant", info.sender))
+}
+
+pub fn close_plan(deps: DepsMut, info: MessageInfo, plan_id: u64) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, U64Key::new(plan_id))?;
+    if info.sender != plan.merchant {
+        return Err(ContractError::Unauthorized {});
+    }
+    plan.closed = true;
+    PLANS.save(deps.storage, U64Key::new(plan_id), &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close_plan")
+        .add_attribute("plan_id", plan_id.to_string()))
+}
+
+pub fn subscribe(deps: DepsMut, env: Env, info: MessageInfo, plan_id: u64) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, U64Key::new(plan_id))?;
+    if plan.closed {
+        return Err(ContractError::PlanClosed {});
+    }
+    // A lapsed or cancelled subscription is replaced once its paid period ended
+    if let Some(subscription) = SUBSCRIPTIONS.may_load(deps.storage, (U64Key::new(plan_id), &info.sender))? {
+        if env.block.time.seconds() < subscription.paid_until {
+            return Err(ContractError::AlreadySubscribed {});
+        }
+    }
+
+    let subscription = Subscription {
+        paid_until: env.block.time.seconds() + plan.period,
+        cancelled: false,
+    };
+    SUBSCRIPTIONS.save(deps.storage, (U64Key::new(plan_id), &info.sender), &subscription)?;
+
+    Ok(Response::new()
+        .add_message(pull_payment(deps.as_ref(), &plan, &info.sender)?)
+        .add_attribute("action", "subscribe")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("subscriber", info.sender)
+        .add_attribute("paid_until", subscription.paid_until.to_string()))
+}
+
+pub fn collect(deps: DepsMut, env: Env, plan_id: u64, subscriber: String) -> Result<Response, ContractError> {
+    let subscriber = deps.api.addr_validate(&subscriber)?;
+    let plan = PLANS.load(deps.storage, U64Key::new(plan_id))?;
+    let mut subscription = SUBSCRIPTIONS.load(deps.storage, (U64Key::new(plan_id), &subscriber))?;
+    let now = env.block.time.seconds();
+    if now < subscription.paid_until {
+        return Err(ContractError::NotDue { paid_until: subscription.paid_until });
+    }
+
+    let res = Response::new()
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("subscriber", subscriber.to_string());
+    if plan.closed || subscription.cancelled || now > subscription.paid_until + plan.grace_period {
+        SUBSCRIPTIONS.remove(deps.storage, (U64Key::new(plan_id), &subscriber));
+        return Ok(res.add_attribute("action", "end_subscription"));
+    }
+
+    // The next period starts when the paid one ended, a payment within the grace period
+    // does not shift the schedule
+    subscription.paid_until += plan.period;
+    SUBSCRIPTIONS.save(deps.storage, (U64Key::new(plan_id), &subscriber), &subscription)?;
+
+    Ok(res
+        .add_message(pull_payment(deps.as_ref(), &plan, &subscriber)?)
+        .add_attribute("action", "collect")
+        .add_attribute("amount", plan.price)
+        .add_attribute("paid_until", subscription.paid_until.to_string()))
+}
+
+pub fn cancel(deps: DepsMut, info: MessageInfo, plan_id: u64) -> Result<Response, ContractError> {
+    let mut subscription = SUBSCRIPTIONS.load(deps.storage, (U64Key::new(plan_id), &info.sender))?;
+    subscription.cancelled = true;
+    SUBSCRIPTIONS.save(deps.storage, (U64Key::new(plan_id), &info.sender), &subscription)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("subscriber", info.sender))
+}
+
+/// Transfers the price of a period from the subscriber to the merchant, out of the allowance
+/// of the subscriber to this contract
+fn pull_payment(deps: Deps, plan: &Plan, subscriber: &Addr) -> StdResult<CosmosMsg> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: subscriber.to_string(),
+            recipient: plan.merchant.to_string(),
+            amount: plan.price,
+        })?,
+        funds: vec![],
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&ConfigResponse { token: CONFIG.load(deps.storage)?.token.to_string() }),
+        QueryMsg::Plan { plan_id } => to_binary(&PlanResponse {
+            plan_id,
+            plan: PLANS.load(deps.storage, U64Key::new(plan_id))?,
+        }),
+        QueryMsg::Subscription { plan_id, subscriber } => {
+            to_binary(&query_subscription(deps, &env, plan_id, subscriber)?)
+        }
+        QueryMsg::Subscriptions { plan_id, start_after, limit } => {
+            to_binary(&query_subscriptions(deps, &env, plan_id, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_subscription(
+    deps: Deps,
+    env: &Env,
+    plan_id: u64,
+    subscriber: String,
+) -> StdResult<SubscriptionResponse> {
+    let subscriber = deps.api.addr_validate(&subscriber)?;
+    let plan = PLANS.load(deps.storage, U64Key::new(plan_id))?;
+    let subscription = SUBSCRIPTIONS.load(deps.storage, (U64Key::new(plan_id), &subscriber))?;
+    Ok(subscription_response(env, plan_id, &plan, subscriber.to_string(), subscription))
+}
+
+pub fn query_subscriptions(
+    deps: Deps,
+    env: &Env,
+    plan_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SubscriptionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let plan = PLANS.load(deps.storage, U64Key::new(plan_id))?;
+
+    let subscriptions: StdResult<Vec<SubscriptionResponse>> = SUBSCRIPTIONS
+        .prefix(U64Key::new(plan_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (subscriber, subscription) = item?;
+            Ok(subscription_response(env, plan_id, &plan, String::from_utf8(subscriber)?, subscription))
+        })
+        .collect();
+
+    Ok(SubscriptionsResponse { subscriptions: subscriptions? })
+}
+
+fn subscription_response(
+    env: &Env,
+    plan_id: u64,
+    plan: &Plan,
+    subscriber: String,
+    subscription: Subscription,
+) -> SubscriptionResponse {
+    let now = env.block.time.seconds();
+    let collectible = !plan.closed && !subscription.cancelled;
+    SubscriptionResponse {
+        plan_id,
+        subscriber,
+        paid_until: subscription.paid_until,
+        cancelled: subscription.cancelled,
+        active: now < subscription.paid_until,
+        due: collectible && now >= subscription.paid_until && now <= subscription.paid_until + plan.grace_period,
+    }
+}
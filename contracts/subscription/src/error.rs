@@ -0,0 +1,26 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The price and the period of a plan cannot be zero")]
+    InvalidPlan {},
+
+    #[error("The plan is closed")]
+    PlanClosed {},
+
+    #[error("Already subscribed to the plan")]
+    AlreadySubscribed {},
+
+    #[error("The subscription is paid until {paid_until}")]
+    NotDue { paid_until: u64 },
+}
@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+
+use crate::state::Plan;
+
+/// This structure describes the parameters used for creating a subscription contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub token: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Registers a plan paid to the sender
+    RegisterPlan {
+        price: Uint128,
+        period: u64,
+        grace_period: u64,
+    },
+    /// Stops the new subscriptions and the collections of the plan. Only the merchant can
+    /// execute it.
+    ClosePlan { plan_id: u64 },
+    /// Subscribes the sender to the plan, paying the first period. The sender authorizes the
+    /// next payments with a LUART allowance to this contract, the price of each period.
+    Subscribe { plan_id: u64 },
+    /// Collects the payment of the next period from the subscriber once the paid period ended.
+    /// Past the grace period, or once cancelled, the subscription is removed instead. Anyone can
+    /// execute it, the keepers collect the due subscriptions.
+    Collect { plan_id: u64, subscriber: String },
+    /// Stops the payments of the sender, the subscription runs until the end of the paid period
+    Cancel { plan_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the plan.
+    /// Return type: PlanResponse.
+    Plan { plan_id: u64 },
+    /// Returns the subscription of the subscriber to the plan.
+    /// Return type: SubscriptionResponse.
+    Subscription { plan_id: u64, subscriber: String },
+    /// Returns the subscriptions to the plan ordered by subscriber, for the keepers.
+    /// Return type: SubscriptionsResponse.
+    Subscriptions {
+        plan_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlanResponse {
+    pub plan_id: u64,
+    pub plan: Plan,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SubscriptionResponse {
+    pub plan_id: u64,
+    pub subscriber: String,
+    pub paid_until: u64,
+    pub cancelled: bool,
+    /// Whether the subscriber paid for the current time
+    pub active: bool,
+    /// Whether a keeper can collect the next payment now
+    pub due: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SubscriptionsResponse {
+    pub subscriptions: Vec<SubscriptionResponse>,
+}
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The LUART token the plans are paid in
+    pub token: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Plan {
+    /// The address registering the plan and receiving the payments
+    pub merchant: Addr,
+    /// The LUART paid every period
+    pub price: Uint128,
+    /// The seconds between the payments
+    pub period: u64,
+    /// The seconds after the end of a paid period during which the next payment is still
+    /// collected, after which the subscription lapses
+    pub grace_period: u64,
+    /// No subscription is taken nor collected once the merchant closed the plan
+    pub closed: bool,
+}
+
+pub const PLAN_COUNT: Item<u64> = Item::new("plan_count");
+pub const PLANS: Map<U64Key, Plan> = Map::new("plans");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Subscription {
+    /// The time the paid periods end at, the next payment is due from then
+    pub paid_until: u64,
+    /// A cancelled subscription runs until the end of the paid period and is not collected
+    pub cancelled: bool,
+}
+
+/// The subscriptions by plan and subscriber
+pub const SUBSCRIPTIONS: Map<(U64Key, &Addr), Subscription> = Map::new("subscriptions");
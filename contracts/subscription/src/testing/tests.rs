@@ -0,0 +1,113 @@
+use cosmwasm_std::{Deps, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SubscriptionResponse, SubscriptionsResponse};
+
+const TOKEN: &str = "mock_token";
+const MERCHANT: &str = "mock_merchant";
+const SUBSCRIBER: &str = "mock_subscriber";
+const PERIOD: u64 = 30 * 86_400;
+const GRACE_PERIOD: u64 = 3 * 86_400;
+
+fn default_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg { token: TOKEN.to_string() };
+    let res = instantiate(deps.branch(), env.clone(), mock_info(MERCHANT, &[]), msg).unwrap();
+    let register = ExecuteMsg::RegisterPlan { price: Uint128::new(100), period: PERIOD, grace_period: GRACE_PERIOD };
+    execute(deps, env, mock_info(MERCHANT, &[]), register).unwrap();
+    res
+}
+
+fn payment(amount: u128) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: SUBSCRIBER.to_string(),
+            recipient: MERCHANT.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn query_subscription(deps: Deps, env: Env) -> SubscriptionResponse {
+    let msg = QueryMsg::Subscription { plan_id: 1, subscriber: SUBSCRIBER.to_string() };
+    from_binary(&query(deps, env, msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_collect() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let start = env.block.time.seconds();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SUBSCRIBER, &[]), ExecuteMsg::Subscribe { plan_id: 1 })
+        .unwrap();
+    assert_eq!(res.messages, vec![payment(100)]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SUBSCRIBER, &[]), ExecuteMsg::Subscribe { plan_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadySubscribed {});
+
+    let collect = ExecuteMsg::Collect { plan_id: 1, subscriber: SUBSCRIBER.to_string() };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), collect.clone()).unwrap_err();
+    assert_eq!(err, ContractError::NotDue { paid_until: start + PERIOD });
+
+    // A payment within the grace period keeps the schedule
+    env.block.time = env.block.time.plus_seconds(PERIOD + 86_400);
+    assert_eq!(query_subscription(deps.as_ref(), env.clone()), SubscriptionResponse {
+        plan_id: 1,
+        subscriber: SUBSCRIBER.to_string(),
+        paid_until: start + PERIOD,
+        cancelled: false,
+        active: false,
+        due: true,
+    });
+    let res = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), collect.clone()).unwrap();
+    assert_eq!(res.messages, vec![payment(100)]);
+    assert_eq!(query_subscription(deps.as_ref(), env.clone()).paid_until, start + 2 * PERIOD);
+
+    // The subscription lapses after the grace period
+    env.block.time = env.block.time.plus_seconds(PERIOD + GRACE_PERIOD);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), collect).unwrap();
+    assert!(res.messages.is_empty());
+    let msg = QueryMsg::Subscriptions { plan_id: 1, start_after: None, limit: None };
+    let res: SubscriptionsResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.subscriptions.is_empty());
+
+    // The merchant closes the plan to new subscriptions
+    let close = ExecuteMsg::ClosePlan { plan_id: 1 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SUBSCRIBER, &[]), close.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(MERCHANT, &[]), close).unwrap();
+    let err = execute(deps.as_mut(), env, mock_info(SUBSCRIBER, &[]), ExecuteMsg::Subscribe { plan_id: 1 })
+        .unwrap_err();
+    assert_eq!(err, ContractError::PlanClosed {});
+}
+
+#[test]
+fn test_cancel() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    execute(deps.as_mut(), env.clone(), mock_info(SUBSCRIBER, &[]), ExecuteMsg::Subscribe { plan_id: 1 }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(SUBSCRIBER, &[]), ExecuteMsg::Cancel { plan_id: 1 }).unwrap();
+    let subscription = query_subscription(deps.as_ref(), env.clone());
+    assert!(subscription.cancelled && subscription.active);
+
+    // The cancelled subscription ends with the paid period instead of being collected
+    env.block.time = env.block.time.plus_seconds(PERIOD);
+    assert!(!query_subscription(deps.as_ref(), env.clone()).due);
+    let collect = ExecuteMsg::Collect { plan_id: 1, subscriber: SUBSCRIBER.to_string() };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), collect).unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = execute(deps.as_mut(), env, mock_info(SUBSCRIBER, &[]), ExecuteMsg::Subscribe { plan_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![payment(100)]);
+}
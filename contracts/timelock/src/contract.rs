@@ -0,0 +1,274 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+    to_binary,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, OperationResponse, OperationsResponse,
+    QueryMsg,
+};
+use crate::state::{Config, CONFIG, Operation, OPERATION_COUNT, OperationStatus, OPERATIONS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-timelock";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+
+    let config = Config {
+        admin,
+        min_delay: msg.min_delay,
+        grace_period: msg.grace_period,
+    };
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    OPERATION_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.grace_period == 0 {
+        return Err(ContractError::InvalidConfig { reason: "grace period must be positive".to_string() });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Queue {
+            messages,
+            description,
+            delay,
+        } => queue(deps, env, info, messages, description, delay),
+        ExecuteMsg::Execute { operation_id } => execute_operation(deps, env, operation_id),
+        ExecuteMsg::Cancel { operation_id } => cancel(deps, info, operation_id),
+        ExecuteMsg::UpdateConfig {
+            admin,
+            min_delay,
+            grace_period,
+        } => update_config(deps, env, info, admin, min_delay, grace_period),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn queue(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    messages: Vec<CosmosMsg>,
+    description: Option<String>,
+    delay: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if messages.is_empty() {
+        return Err(ContractError::NoMessages {});
+    }
+
+    let delay = delay.unwrap_or(config.min_delay);
+    if delay < config.min_delay {
+        return Err(ContractError::DelayTooShort { min_delay: config.min_delay });
+    }
+
+    let now = env.block.time.seconds();
+    let operation = Operation {
+        messages,
+        description,
+        status: OperationStatus::Queued,
+        queued_at: now,
+        eta: now + delay,
+    };
+
+    let operation_id = OPERATION_COUNT.load(deps.storage)? + 1;
+    OPERATION_COUNT.save(deps.storage, &operation_id)?;
+    OPERATIONS.save(deps.storage, U64Key::new(operation_id), &operation)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "queue")
+        .add_attribute("operation_id", operation_id.to_string())
+        .add_attribute("eta", operation.eta.to_string()))
+}
+
+pub fn execute_operation(
+    deps: DepsMut,
+    env: Env,
+    operation_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut operation = OPERATIONS.load(deps.storage, U64Key::new(operation_id))?;
+    if operation.status != OperationStatus::Queued {
+        return Err(ContractError::OperationNotQueued {});
+    }
+
+    let now = env.block.time.seconds();
+    if now < operation.eta {
+        return Err(ContractError::OperationNotReady {});
+    }
+    if now >= operation.eta + config.grace_period {
+        return Err(ContractError::OperationExpired {});
+    }
+
+    operation.status = OperationStatus::Executed;
+    OPERATIONS.save(deps.storage, U64Key::new(operation_id), &operation)?;
+
+    Ok(Response::new()
+        .add_messages(operation.messages)
+        .add_attribute("action", "execute")
+        .add_attribute("operation_id", operation_id.to_string()))
+}
+
+pub fn cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    operation_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut operation = OPERATIONS.load(deps.storage, U64Key::new(operation_id))?;
+    if operation.status != OperationStatus::Queued {
+        return Err(ContractError::OperationNotQueued {});
+    }
+    operation.status = OperationStatus::Cancelled;
+    OPERATIONS.save(deps.storage, U64Key::new(operation_id), &operation)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("operation_id", operation_id.to_string()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    admin: Option<String>,
+    min_delay: Option<u64>,
+    grace_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Parameter changes are subject to the delay as well
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    config.min_delay = min_delay.unwrap_or(config.min_delay);
+    config.grace_period = grace_period.unwrap_or(config.grace_period);
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Operation { operation_id } => to_binary(&query_operation(deps, operation_id)?),
+        QueryMsg::Operations { start_after, limit } => {
+            to_binary(&query_operations(deps, start_after, limit)?)
+        }
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin.to_string(),
+        min_delay: config.min_delay,
+        grace_period: config.grace_period,
+    })
+}
+
+pub fn query_operation(deps: Deps, operation_id: u64) -> StdResult<OperationResponse> {
+    let operation = OPERATIONS.load(deps.storage, U64Key::new(operation_id))?;
+    Ok(operation_response(operation_id, operation))
+}
+
+pub fn query_operations(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OperationsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let operations: StdResult<Vec<OperationResponse>> = OPERATIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, operation) = item?;
+            Ok(operation_response(parse_operation_id(&k)?, operation))
+        })
+        .collect();
+
+    Ok(OperationsResponse { operations: operations? })
+}
+
+fn operation_response(operation_id: u64, operation: Operation) -> OperationResponse {
+    OperationResponse {
+        operation_id,
+        messages: operation.messages,
+        description: operation.description,
+        status: operation.status,
+        queued_at: operation.queued_at,
+        eta: operation.eta,
+    }
+}
+
+fn parse_operation_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted operation key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
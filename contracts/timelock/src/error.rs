@@ -0,0 +1,32 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid timelock configuration: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("The delay must be at least {min_delay} seconds")]
+    DelayTooShort { min_delay: u64 },
+
+    #[error("The operation has no messages")]
+    NoMessages {},
+
+    #[error("The operation is not queued")]
+    OperationNotQueued {},
+
+    #[error("The operation delay has not elapsed yet")]
+    OperationNotReady {},
+
+    #[error("The operation execution window has expired")]
+    OperationExpired {},
+}
@@ -0,0 +1,85 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::CosmosMsg;
+
+use crate::state::OperationStatus;
+
+/// This structure describes the parameters used for creating a timelock contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The admin queueing operations, the instantiator by default
+    pub admin: Option<String>,
+    pub min_delay: u64,
+    pub grace_period: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Queues messages executable once the delay has elapsed, the minimum delay by default.
+    /// Only the admin can execute it.
+    Queue {
+        messages: Vec<CosmosMsg>,
+        description: Option<String>,
+        delay: Option<u64>,
+    },
+    /// Executes the messages of a queued operation once its eta is reached
+    Execute { operation_id: u64 },
+    /// Cancels a queued operation. Only the admin can execute it.
+    Cancel { operation_id: u64 },
+    /// Updates the timelock parameters. Only the contract itself can execute it,
+    /// through a queued operation.
+    UpdateConfig {
+        admin: Option<String>,
+        min_delay: Option<u64>,
+        grace_period: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the timelock parameters.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a queued, executed or cancelled operation.
+    /// Return type: OperationResponse.
+    Operation { operation_id: u64 },
+    /// Returns operations by id.
+    /// Return type: OperationsResponse.
+    Operations {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub min_delay: u64,
+    pub grace_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperationResponse {
+    pub operation_id: u64,
+    pub messages: Vec<CosmosMsg>,
+    pub description: Option<String>,
+    pub status: OperationStatus,
+    pub queued_at: u64,
+    pub eta: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperationsResponse {
+    pub operations: Vec<OperationResponse>,
+}
@@ -0,0 +1,37 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address allowed to queue and cancel operations
+    pub admin: Addr,
+    /// Minimum seconds between queueing an operation and its execution
+    pub min_delay: u64,
+    /// Seconds after its eta a queued operation can be executed
+    pub grace_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Operation {
+    pub messages: Vec<CosmosMsg>,
+    pub description: Option<String>,
+    pub status: OperationStatus,
+    pub queued_at: u64,
+    /// The operation can be executed from this time on
+    pub eta: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const OPERATION_COUNT: Item<u64> = Item::new("operation_count");
+pub const OPERATIONS: Map<U64Key, Operation> = Map::new("operations");
@@ -0,0 +1,146 @@
+use cosmwasm_std::{Binary, CosmosMsg, DepsMut, Env, from_binary, Response, SubMsg, to_binary, WasmMsg};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_dependencies, mock_env, mock_info};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, OperationResponse, QueryMsg};
+use crate::state::OperationStatus;
+
+const ADMIN: &str = "mock_admin";
+const TOKEN: &str = "mock_token";
+
+const MIN_DELAY: u64 = 2 * 86400;
+const GRACE_PERIOD: u64 = 7 * 86400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        admin: Some(ADMIN.to_string()),
+        min_delay: MIN_DELAY,
+        grace_period: GRACE_PERIOD,
+    };
+    instantiate(deps, env, mock_info("creator", &[]), msg).unwrap()
+}
+
+// The timelock set as the token fee admin changes the swap fee
+fn fee_update_msg() -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: Binary::from(br#"{"update_swap_fee_config":{"swap_percent_fee":"0.02"}}"#.as_ref()),
+        funds: vec![],
+    })
+}
+
+fn queue_msg(delay: Option<u64>) -> ExecuteMsg {
+    ExecuteMsg::Queue {
+        messages: vec![fee_update_msg()],
+        description: Some("Raise the swap fee to 2%".to_string()),
+        delay,
+    }
+}
+
+#[test]
+fn test_queue() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), queue_msg(None)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]),
+                      queue_msg(Some(MIN_DELAY - 1))).unwrap_err();
+    assert_eq!(err, ContractError::DelayTooShort { min_delay: MIN_DELAY });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]),
+                      ExecuteMsg::Queue { messages: vec![], description: None, delay: None }).unwrap_err();
+    assert_eq!(err, ContractError::NoMessages {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), queue_msg(Some(3 * 86400))).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Operation { operation_id: 1 }).unwrap();
+    let operation: OperationResponse = from_binary(&res).unwrap();
+    assert_eq!(operation.status, OperationStatus::Queued);
+    assert_eq!(operation.messages, vec![fee_update_msg()]);
+    assert_eq!(operation.queued_at, env.block.time.seconds());
+    assert_eq!(operation.eta, env.block.time.seconds() + 3 * 86400);
+}
+
+#[test]
+fn test_execute() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), queue_msg(None)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), queue_msg(None)).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(MIN_DELAY - 1);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::OperationNotReady {});
+
+    // Anyone can execute once the delay has elapsed
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(fee_update_msg())]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::OperationNotQueued {});
+
+    env.block.time = env.block.time.plus_seconds(GRACE_PERIOD);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::OperationExpired {});
+}
+
+#[test]
+fn test_cancel_and_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), queue_msg(None)).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Cancel { operation_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), ExecuteMsg::Cancel { operation_id: 1 }).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(MIN_DELAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::OperationNotQueued {});
+
+    // The admin itself can only be replaced through the delay
+    let msg = ExecuteMsg::UpdateConfig {
+        admin: Some("new_admin".to_string()),
+        min_delay: Some(3 * 86400),
+        grace_period: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let update_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), ExecuteMsg::Queue {
+        messages: vec![update_msg.clone()],
+        description: None,
+        delay: None,
+    }).unwrap();
+    env.block.time = env.block.time.plus_seconds(MIN_DELAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Execute { operation_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(update_msg)]);
+    execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config, ConfigResponse {
+        admin: "new_admin".to_string(),
+        min_delay: 3 * 86400,
+        grace_period: GRACE_PERIOD,
+    });
+}
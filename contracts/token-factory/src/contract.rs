@@ -0,0 +1,244 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, SubMsg, to_binary, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use luart_protocol::token::InstantiateMsg as TokenInstantiateMsg;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TokenResponse, TokensResponse,
+};
+use crate::state::{
+    Config, CONFIG, ConfigOwner, CREATOR_TOKENS, PENDING_TOKEN, PendingToken, TokenInfo, TOKENS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-token-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const INSTANTIATE_TOKEN_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        token_code_id: msg.token_code_id,
+    })?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateToken { token, admin } => create_token(deps, info, token, admin),
+        ExecuteMsg::UpdateConfig { token_code_id } => update_config(deps, info, token_code_id),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// Instantiates the token, it is recorded once its address is read from the reply
+pub fn create_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: Box<TokenInstantiateMsg>,
+    admin: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let admin = admin.map(|admin| deps.api.addr_validate(&admin)).transpose()?;
+
+    PENDING_TOKEN.save(deps.storage, &PendingToken {
+        creator: info.sender.clone(),
+        code_id: config.token_code_id,
+        symbol: token.symbol.clone(),
+        swap_fee: token.swap_fee_config.is_some(),
+    })?;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: admin.map(|admin| admin.to_string()),
+        code_id: config.token_code_id,
+        msg: to_binary(&token)?,
+        funds: vec![],
+        label: token.symbol.clone(),
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(instantiate, INSTANTIATE_TOKEN_ID))
+        .add_attribute("action", "create_token")
+        .add_attribute("creator", info.sender)
+        .add_attribute("symbol", token.symbol))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_TOKEN_ID {
+        return Err(StdError::generic_err(format!("Unknown reply id: {}", msg.id)).into());
+    }
+
+    let res = msg.result.into_result().map_err(StdError::generic_err)?;
+    let address = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate_contract" || event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "contract_address" || attr.key == "_contract_address")
+        .ok_or(ContractError::MissingTokenAddress {})?
+        .value
+        .clone();
+    let address = deps.api.addr_validate(&address)?;
+
+    let pending = PENDING_TOKEN.load(deps.storage)?;
+    PENDING_TOKEN.remove(deps.storage);
+    TOKENS.save(deps.storage, &address, &TokenInfo {
+        creator: pending.creator.clone(),
+        code_id: pending.code_id,
+        symbol: pending.symbol,
+        swap_fee: pending.swap_fee,
+        created_at: env.block.time.seconds(),
+    })?;
+    CREATOR_TOKENS.save(deps.storage, (&pending.creator, &address), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("token", address)
+        .add_attribute("creator", pending.creator))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.token_code_id = token_code_id.unwrap_or(config.token_code_id);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("token_code_id", config.token_code_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Token { address } => to_binary(&query_token(deps, address)?),
+        QueryMsg::Tokens { start_after, limit } => to_binary(&query_tokens(deps, start_after, limit)?),
+        QueryMsg::TokensByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&query_tokens_by_creator(deps, creator, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        token_code_id: config.token_code_id,
+    })
+}
+
+pub fn query_token(deps: Deps, address: String) -> StdResult<TokenResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let token = TOKENS.load(deps.storage, &address)?;
+    Ok(token_response(address, token))
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let tokens: StdResult<Vec<TokenResponse>> = TOKENS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, token) = item?;
+            let address = Addr::unchecked(String::from_utf8(address)?);
+            Ok(token_response(address, token))
+        })
+        .collect();
+
+    Ok(TokensResponse { tokens: tokens? })
+}
+
+pub fn query_tokens_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let creator = deps.api.addr_validate(&creator)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let tokens: StdResult<Vec<TokenResponse>> = CREATOR_TOKENS
+        .prefix(&creator)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|address| {
+            let address = Addr::unchecked(String::from_utf8(address)?);
+            let token = TOKENS.load(deps.storage, &address)?;
+            Ok(token_response(address, token))
+        })
+        .collect();
+
+    Ok(TokensResponse { tokens: tokens? })
+}
+
+fn token_response(address: Addr, token: TokenInfo) -> TokenResponse {
+    TokenResponse {
+        address: address.to_string(),
+        creator: token.creator.to_string(),
+        code_id: token.code_id,
+        symbol: token.symbol,
+        swap_fee: token.swap_fee,
+        created_at: token.created_at,
+    }
+}
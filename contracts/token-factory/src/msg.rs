@@ -0,0 +1,87 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use luart_protocol::token::InstantiateMsg as TokenInstantiateMsg;
+
+/// This structure describes the parameters used for creating a token factory contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The code id of the token contract
+    pub token_code_id: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiates a token contract, with a swap fee when its configuration is set.
+    /// The sender is recorded as the creator of the token.
+    CreateToken {
+        token: Box<TokenInstantiateMsg>,
+        /// The address allowed to migrate the token, none by default
+        admin: Option<String>,
+    },
+    /// Updates the code id of the tokens created from now on. Only the owner can execute it.
+    UpdateConfig { token_code_id: Option<u64> },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns a token created by the factory.
+    /// Return type: TokenResponse.
+    Token { address: String },
+    /// Returns the tokens created by the factory.
+    /// Return type: TokensResponse.
+    Tokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the tokens created by the given address.
+    /// Return type: TokensResponse.
+    TokensByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub token_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenResponse {
+    pub address: String,
+    pub creator: String,
+    pub code_id: u64,
+    pub symbol: String,
+    pub swap_fee: bool,
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokensResponse {
+    pub tokens: Vec<TokenResponse>,
+}
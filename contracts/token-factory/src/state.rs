@@ -0,0 +1,58 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who updates the token code id
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The code id of the token contract instantiated
+    pub token_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInfo {
+    pub creator: Addr,
+    pub code_id: u64,
+    pub symbol: String,
+    pub swap_fee: bool,
+    /// Timestamp in seconds
+    pub created_at: u64,
+}
+
+/// The token being instantiated, saved until its address is read from the reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingToken {
+    pub creator: Addr,
+    pub code_id: u64,
+    pub symbol: String,
+    pub swap_fee: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+pub const PENDING_TOKEN: Item<PendingToken> = Item::new("pending_token");
+pub const TOKENS: Map<&Addr, TokenInfo> = Map::new("tokens");
+/// The tokens of each creator
+pub const CREATOR_TOKENS: Map<(&Addr, &Addr), Empty> = Map::new("creator_tokens");
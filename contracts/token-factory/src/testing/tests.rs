@@ -0,0 +1,139 @@
+use cosmwasm_std::{
+    ContractResult, Decimal, DepsMut, Env, Event, from_binary, Reply, Response, SubMsg,
+    SubMsgExecutionResponse, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::Cw20Coin;
+use luart_protocol::token::{InstantiateMsg as TokenInstantiateMsg, SwapFeeConfig};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TokenResponse, TokensResponse};
+
+const OWNER: &str = "mock_owner";
+const PARTNER: &str = "mock_partner";
+const TOKEN_CODE_ID: u64 = 7;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        token_code_id: TOKEN_CODE_ID,
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn token_msg(symbol: &str, swap_fee: bool) -> TokenInstantiateMsg {
+    TokenInstantiateMsg {
+        name: format!("Wrapped {}", symbol),
+        symbol: symbol.to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: PARTNER.to_string(), amount: Uint128::new(1_000_000) }],
+        mint: None,
+        swap_fee_config: swap_fee.then(|| SwapFeeConfig {
+            fee_admin: PARTNER.to_string(),
+            enable_swap_fee: true,
+            swap_percent_fee: Decimal::percent(2),
+            fee_receiver: PARTNER.to_string(),
+        }),
+        pauser: None,
+    }
+}
+
+/// The reply of the token instantiation, with the event emitted by Terra
+fn instantiate_reply(address: &str) -> Reply {
+    Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![Event::new("instantiate_contract").add_attribute("contract_address", address)],
+            data: None,
+        }),
+    }
+}
+
+#[test]
+fn test_create_token() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::CreateToken {
+        token: Box::new(token_msg("WFEE", true)),
+        admin: Some(PARTNER.to_string()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(WasmMsg::Instantiate {
+        admin: Some(PARTNER.to_string()),
+        code_id: TOKEN_CODE_ID,
+        msg: to_binary(&token_msg("WFEE", true)).unwrap(),
+        funds: vec![],
+        label: "WFEE".to_string(),
+    }, 1)]);
+    reply(deps.as_mut(), env.clone(), instantiate_reply("token1")).unwrap();
+
+    let msg = ExecuteMsg::CreateToken { token: Box::new(token_msg("WNOFEE", false)), admin: None };
+    execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg).unwrap();
+    reply(deps.as_mut(), env.clone(), instantiate_reply("token2")).unwrap();
+    let msg = ExecuteMsg::CreateToken { token: Box::new(token_msg("WOTHER", false)), admin: None };
+    execute(deps.as_mut(), env.clone(), mock_info("mock_other", &[]), msg).unwrap();
+    let err = reply(deps.as_mut(), env.clone(), Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    }).unwrap_err();
+    assert_eq!(err, ContractError::MissingTokenAddress {});
+    reply(deps.as_mut(), env.clone(), instantiate_reply("token3")).unwrap();
+
+    let res: TokenResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Token {
+        address: "token1".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res, TokenResponse {
+        address: "token1".to_string(),
+        creator: PARTNER.to_string(),
+        code_id: TOKEN_CODE_ID,
+        symbol: "WFEE".to_string(),
+        swap_fee: true,
+        created_at: env.block.time.seconds(),
+    });
+
+    let res: TokensResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::TokensByCreator {
+        creator: PARTNER.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    let symbols: Vec<_> = res.tokens.iter().map(|token| token.symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["WFEE", "WNOFEE"]);
+
+    let res: TokensResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Tokens {
+        start_after: Some("token1".to_string()),
+        limit: None,
+    }).unwrap()).unwrap();
+    let addresses: Vec<_> = res.tokens.iter().map(|token| token.address.as_str()).collect();
+    assert_eq!(addresses, vec!["token2", "token3"]);
+}
+
+#[test]
+fn test_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let msg = ExecuteMsg::UpdateConfig { token_code_id: Some(8) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    // The tokens created from now on use the new code id
+    let msg = ExecuteMsg::CreateToken { token: Box::new(token_msg("WFEE", true)), admin: None };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(PARTNER, &[]), msg).unwrap();
+    reply(deps.as_mut(), env.clone(), instantiate_reply("token1")).unwrap();
+    match &res.messages[0].msg {
+        cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) => assert_eq!(*code_id, 8),
+        msg => panic!("unexpected message {:?}", msg),
+    }
+    let res: TokenResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Token {
+        address: "token1".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.code_id, 8);
+}
@@ -0,0 +1,320 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order, Response, StdError, StdResult,
+    to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockResponse, LocksResponse, MigrateMsg, QueryMsg,
+};
+use crate::state::{BENEFICIARY_LOCKS, Config, CONFIG, Lock, LOCK_COUNT, LOCKS, Unlock};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-token-lock";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(deps.storage, &Config {
+        token: deps.api.addr_validate(&msg.token)?,
+    })?;
+    LOCK_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ExtendLock { lock_id, unlock_times } => extend_lock(deps, info, lock_id, unlock_times),
+        ExecuteMsg::Claim { lock_id } => claim(deps, env, info, lock_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // only LUART can be locked
+    if config.token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateLock {
+            beneficiary,
+            description,
+            schedule,
+        } => {
+            let creator = deps.api.addr_validate(&cw20_msg.sender)?;
+            let beneficiary = deps.api.addr_validate(&beneficiary)?;
+            create_lock(deps, env, creator, beneficiary, description, schedule, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn create_lock(
+    deps: DepsMut,
+    env: Env,
+    creator: Addr,
+    beneficiary: Addr,
+    description: Option<String>,
+    schedule: Vec<Unlock>,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_schedule(&schedule, env.block.time.seconds())?;
+    if schedule.iter().any(|u| u.amount.is_zero()) {
+        return Err(invalid_schedule("tranches cannot be empty"));
+    }
+    let total_amount: Uint128 = schedule.iter().map(|u| u.amount).sum();
+    if total_amount != amount {
+        return Err(invalid_schedule("tranches must add up to the locked amount"));
+    }
+
+    let lock_id = LOCK_COUNT.load(deps.storage)? + 1;
+    LOCK_COUNT.save(deps.storage, &lock_id)?;
+
+    LOCKS.save(deps.storage, U64Key::new(lock_id), &Lock {
+        creator: creator.clone(),
+        beneficiary: beneficiary.clone(),
+        description,
+        schedule,
+        claimed_amount: Uint128::zero(),
+    })?;
+    BENEFICIARY_LOCKS.save(deps.storage, (&beneficiary, U64Key::new(lock_id)), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_lock")
+        .add_attribute("lock_id", lock_id.to_string())
+        .add_attribute("creator", creator)
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("amount", amount))
+}
+
+pub fn extend_lock(
+    deps: DepsMut,
+    info: MessageInfo,
+    lock_id: u64,
+    unlock_times: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut lock = LOCKS.load(deps.storage, U64Key::new(lock_id))?;
+    if lock.beneficiary != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if unlock_times.len() != lock.schedule.len() {
+        return Err(invalid_schedule("one unlock time is required per tranche"));
+    }
+
+    for (unlock, time) in lock.schedule.iter_mut().zip(unlock_times) {
+        if time < unlock.time {
+            return Err(ContractError::LockShortened { time: unlock.time });
+        }
+        unlock.time = time;
+    }
+    // Extended tranches already unlocked may unlock again in the past
+    assert_schedule(&lock.schedule, 0)?;
+    LOCKS.save(deps.storage, U64Key::new(lock_id), &lock)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "extend_lock")
+        .add_attribute("lock_id", lock_id.to_string())
+        .add_attribute("end", lock_end(&lock).to_string()))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut lock = LOCKS.load(deps.storage, U64Key::new(lock_id))?;
+    if lock.beneficiary != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = lock.claimable_amount(env.block.time.seconds());
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    lock.claimed_amount = lock.claimed_amount.checked_add(amount)?;
+    LOCKS.save(deps.storage, U64Key::new(lock_id), &lock)?;
+
+    Ok(Response::new()
+        .add_message(token_transfer_msg(&config, lock.beneficiary.to_string(), amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("lock_id", lock_id.to_string())
+        .add_attribute("beneficiary", lock.beneficiary)
+        .add_attribute("amount", amount))
+}
+
+/// Checks the tranches unlock after the given time by strictly ascending times
+fn assert_schedule(schedule: &[Unlock], after: u64) -> Result<(), ContractError> {
+    if schedule.is_empty() {
+        return Err(invalid_schedule("at least one tranche is required"));
+    }
+    let mut previous = after;
+    for unlock in schedule {
+        if unlock.time <= previous {
+            return Err(invalid_schedule("unlock times must be in the future and ascending"));
+        }
+        previous = unlock.time;
+    }
+    Ok(())
+}
+
+fn invalid_schedule(reason: &str) -> ContractError {
+    ContractError::InvalidSchedule { reason: reason.to_string() }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Lock { lock_id } => to_binary(&query_lock(deps, env, lock_id)?),
+        QueryMsg::Locks { start_after, limit } => to_binary(&query_locks(deps, env, start_after, limit)?),
+        QueryMsg::LocksOf {
+            beneficiary,
+            start_after,
+            limit,
+        } => to_binary(&query_locks_of(deps, env, beneficiary, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token: config.token.to_string(),
+    })
+}
+
+pub fn query_lock(deps: Deps, env: Env, lock_id: u64) -> StdResult<LockResponse> {
+    let lock = LOCKS.load(deps.storage, U64Key::new(lock_id))?;
+    Ok(lock_response(lock_id, lock, env.block.time.seconds()))
+}
+
+pub fn query_locks(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LocksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let time = env.block.time.seconds();
+
+    let locks: StdResult<Vec<LockResponse>> = LOCKS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, lock) = item?;
+            Ok(lock_response(parse_lock_id(&k)?, lock, time))
+        })
+        .collect();
+
+    Ok(LocksResponse { locks: locks? })
+}
+
+pub fn query_locks_of(
+    deps: Deps,
+    env: Env,
+    beneficiary: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LocksResponse> {
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let time = env.block.time.seconds();
+
+    let locks: StdResult<Vec<LockResponse>> = BENEFICIARY_LOCKS
+        .prefix(&beneficiary)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|k| {
+            let lock_id = parse_lock_id(&k)?;
+            let lock = LOCKS.load(deps.storage, U64Key::new(lock_id))?;
+            Ok(lock_response(lock_id, lock, time))
+        })
+        .collect();
+
+    Ok(LocksResponse { locks: locks? })
+}
+
+fn lock_response(lock_id: u64, lock: Lock, time: u64) -> LockResponse {
+    let total_amount = lock.total_amount();
+    LockResponse {
+        lock_id,
+        creator: lock.creator.to_string(),
+        beneficiary: lock.beneficiary.to_string(),
+        locked_amount: total_amount.saturating_sub(lock.unlocked_amount(time)),
+        claimable_amount: lock.claimable_amount(time),
+        next_unlock: lock.next_unlock(time),
+        end: lock_end(&lock),
+        total_amount,
+        claimed_amount: lock.claimed_amount,
+        description: lock.description,
+        schedule: lock.schedule,
+    }
+}
+
+fn lock_end(lock: &Lock) -> u64 {
+    lock.schedule.last().map(|u| u.time).unwrap_or_default()
+}
+
+fn parse_lock_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted lock key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn token_transfer_msg(config: &Config, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,23 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid unlock schedule: {reason}")]
+    InvalidSchedule { reason: String },
+
+    #[error("Locks can only be extended, the unlock at {time} cannot move earlier")]
+    LockShortened { time: u64 },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+}
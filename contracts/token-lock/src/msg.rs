@@ -0,0 +1,98 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::Unlock;
+
+/// This structure describes the parameters used for creating a token lock contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The LUART token contract address
+    pub token: String,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives tokens funding a new lock, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Moves the unlock times of the tranches later, one time per tranche. No unlock can move
+    /// earlier. Only the beneficiary can execute it.
+    ExtendLock { lock_id: u64, unlock_times: Vec<u64> },
+    /// Transfers the unlocked and not yet claimed tokens to the beneficiary
+    Claim { lock_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Locks the sent amount for the beneficiary. The tranches must unlock in the future by
+    /// ascending time and add up to the sent amount.
+    CreateLock {
+        beneficiary: String,
+        description: Option<String>,
+        schedule: Vec<Unlock>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the lock with its locked and claimable amounts at the current block time.
+    /// Return type: LockResponse.
+    Lock { lock_id: u64 },
+    /// Returns locks by id.
+    /// Return type: LocksResponse.
+    Locks {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns all locks of the beneficiary. Supports pagination.
+    /// Return type: LocksResponse.
+    LocksOf {
+        beneficiary: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockResponse {
+    pub lock_id: u64,
+    pub creator: String,
+    pub beneficiary: String,
+    pub description: Option<String>,
+    pub schedule: Vec<Unlock>,
+    pub total_amount: Uint128,
+    /// The amount not unlocked yet
+    pub locked_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub claimable_amount: Uint128,
+    /// The time of the next tranche to unlock, unset once everything is unlocked
+    pub next_unlock: Option<u64>,
+    /// The time the last tranche unlocks at
+    pub end: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LocksResponse {
+    pub locks: Vec<LockResponse>,
+}
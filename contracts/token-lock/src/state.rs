@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The LUART token
+    pub token: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unlock {
+    /// Timestamp in seconds the amount unlocks at
+    pub time: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Lock {
+    /// The address who funded the lock
+    pub creator: Addr,
+    /// The address claiming the unlocked tokens
+    pub beneficiary: Addr,
+    /// What the tokens are, e.g. the team allocation
+    pub description: Option<String>,
+    /// The tranches by ascending unlock time
+    pub schedule: Vec<Unlock>,
+    /// The amount of tokens already claimed by the beneficiary
+    pub claimed_amount: Uint128,
+}
+
+impl Lock {
+    pub fn total_amount(&self) -> Uint128 {
+        self.schedule.iter().map(|u| u.amount).sum()
+    }
+
+    /// Returns the amount of tokens unlocked at the given timestamp
+    pub fn unlocked_amount(&self, time: u64) -> Uint128 {
+        self.schedule.iter().filter(|u| u.time <= time).map(|u| u.amount).sum()
+    }
+
+    /// Returns the amount of tokens the beneficiary can claim at the given timestamp.
+    /// Tranches unlocked before an extension may have been claimed already.
+    pub fn claimable_amount(&self, time: u64) -> Uint128 {
+        self.unlocked_amount(time).saturating_sub(self.claimed_amount)
+    }
+
+    /// Returns the time of the next tranche to unlock after the given timestamp
+    pub fn next_unlock(&self, time: u64) -> Option<u64> {
+        self.schedule.iter().map(|u| u.time).find(|t| *t > time)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const LOCK_COUNT: Item<u64> = Item::new("lock_count");
+pub const LOCKS: Map<U64Key, Lock> = Map::new("locks");
+/// The locks of each beneficiary
+pub const BENEFICIARY_LOCKS: Map<(&Addr, U64Key), bool> = Map::new("beneficiary_locks");
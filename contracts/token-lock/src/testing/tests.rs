@@ -0,0 +1,186 @@
+use cosmwasm_std::{CosmosMsg, Deps, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockResponse, LocksResponse, QueryMsg};
+use crate::state::Unlock;
+
+const TEAM: &str = "mock_team";
+const TOKEN: &str = "mock_token";
+const BENEFICIARY: &str = "mock_beneficiary";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        token: TOKEN.to_string(),
+    };
+    instantiate(deps, env, mock_info(TEAM, &[]), msg).unwrap()
+}
+
+fn create_lock(deps: DepsMut, env: Env, amount: u128, schedule: Vec<Unlock>) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: TEAM.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::CreateLock {
+                    beneficiary: BENEFICIARY.to_string(),
+                    description: Some("Team allocation".to_string()),
+                    schedule,
+                }).unwrap(),
+            }))
+}
+
+/// Two tranches of 600 and 400 tokens unlocking in 100 and 200 days
+fn team_schedule(env: &Env) -> Vec<Unlock> {
+    let now = env.block.time.seconds();
+    vec![
+        Unlock { time: now + 100 * DAY, amount: Uint128::new(600) },
+        Unlock { time: now + 200 * DAY, amount: Uint128::new(400) },
+    ]
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+fn query_lock(deps: Deps, env: Env, lock_id: u64) -> LockResponse {
+    from_binary(&query(deps, env, QueryMsg::Lock { lock_id }).unwrap()).unwrap()
+}
+
+#[test]
+fn test_create_and_claim_lock() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Only LUART can be locked
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_token", &[]),
+                      ExecuteMsg::Receive(Cw20ReceiveMsg {
+                          sender: TEAM.to_string(),
+                          amount: Uint128::new(1_000),
+                          msg: to_binary(&Cw20HookMsg::CreateLock {
+                              beneficiary: BENEFICIARY.to_string(),
+                              description: None,
+                              schedule: team_schedule(&env),
+                          }).unwrap(),
+                      })).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = create_lock(deps.as_mut(), env.clone(), 900, team_schedule(&env)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule {
+        reason: "tranches must add up to the locked amount".to_string(),
+    });
+    let mut schedule = team_schedule(&env);
+    schedule.reverse();
+    let err = create_lock(deps.as_mut(), env.clone(), 1_000, schedule).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule {
+        reason: "unlock times must be in the future and ascending".to_string(),
+    });
+
+    create_lock(deps.as_mut(), env.clone(), 1_000, team_schedule(&env)).unwrap();
+    let start = env.block.time.seconds();
+
+    // Anyone can read the proof of the lock
+    let lock = query_lock(deps.as_ref(), env.clone(), 1);
+    assert_eq!(lock.creator, TEAM.to_string());
+    assert_eq!(lock.beneficiary, BENEFICIARY.to_string());
+    assert_eq!(lock.total_amount, Uint128::new(1_000));
+    assert_eq!(lock.locked_amount, Uint128::new(1_000));
+    assert_eq!(lock.next_unlock, Some(start + 100 * DAY));
+    assert_eq!(lock.end, start + 200 * DAY);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // The first tranche unlocks
+    env.block.time = env.block.time.plus_seconds(100 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TEAM, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 600)]);
+
+    let lock = query_lock(deps.as_ref(), env.clone(), 1);
+    assert_eq!(lock.locked_amount, Uint128::new(400));
+    assert_eq!(lock.claimed_amount, Uint128::new(600));
+    assert_eq!(lock.next_unlock, Some(start + 200 * DAY));
+
+    env.block.time = env.block.time.plus_seconds(100 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 400)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::LocksOf {
+        beneficiary: BENEFICIARY.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let locks: LocksResponse = from_binary(&res).unwrap();
+    assert_eq!(locks.locks.len(), 1);
+    assert_eq!(locks.locks[0].locked_amount, Uint128::zero());
+    assert_eq!(locks.locks[0].next_unlock, None);
+}
+
+#[test]
+fn test_extend_lock() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_lock(deps.as_mut(), env.clone(), 1_000, team_schedule(&env)).unwrap();
+    let start = env.block.time.seconds();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(TEAM, &[]),
+                      ExecuteMsg::ExtendLock {
+                          lock_id: 1,
+                          unlock_times: vec![start + 150 * DAY, start + 300 * DAY],
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // No unlock can move earlier
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::ExtendLock {
+                          lock_id: 1,
+                          unlock_times: vec![start + 150 * DAY, start + 150 * DAY],
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::LockShortened { time: start + 200 * DAY });
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::ExtendLock { lock_id: 1, unlock_times: vec![start + 300 * DAY] }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule {
+        reason: "one unlock time is required per tranche".to_string(),
+    });
+
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::ExtendLock {
+                lock_id: 1,
+                unlock_times: vec![start + 150 * DAY, start + 300 * DAY],
+            }).unwrap();
+    let lock = query_lock(deps.as_ref(), env.clone(), 1);
+    assert_eq!(lock.schedule[0].time, start + 150 * DAY);
+    assert_eq!(lock.end, start + 300 * DAY);
+
+    // The first tranche is still locked at its original time
+    env.block.time = env.block.time.plus_seconds(100 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    env.block.time = env.block.time.plus_seconds(50 * DAY);
+    let res = execute(deps.as_mut(), env, mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { lock_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 600)]);
+}
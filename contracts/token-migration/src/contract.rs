@@ -0,0 +1,323 @@
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Order, Response, StdResult,
+    to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, MigrationResponse, MigrationsResponse,
+    QueryMsg, StateResponse,
+};
+use crate::state::{
+    Config, CONFIG, ConfigOwner, LegacyDisposal, Migration, MIGRATIONS, NewSupply, State, STATE,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-token-migration";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let ratio = msg.ratio.unwrap_or_else(Decimal::one);
+    if ratio.is_zero() {
+        return Err(ContractError::InvalidRatio {});
+    }
+    if msg.deadline <= env.block.time.seconds() {
+        return Err(ContractError::InvalidDeadline {});
+    }
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        legacy_token: deps.api.addr_validate(&msg.legacy_token)?,
+        new_token: deps.api.addr_validate(&msg.new_token)?,
+        ratio,
+        legacy_disposal: msg.legacy_disposal,
+        new_supply: msg.new_supply,
+        deadline: msg.deadline,
+    })?;
+    STATE.save(deps.storage, &State::default())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateDeadline { deadline } => update_deadline(deps, env, info, deadline),
+        ExecuteMsg::WithdrawUnreleased { recipient } => withdraw_unreleased(deps, env, info, recipient),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Migrate { recipient } => {
+            if info.sender != config.legacy_token {
+                return Err(ContractError::Unauthorized {});
+            }
+            let holder = deps.api.addr_validate(&cw20_msg.sender)?;
+            let recipient = match recipient {
+                Some(recipient) => deps.api.addr_validate(&recipient)?,
+                None => holder.clone(),
+            };
+            migrate_tokens(deps, env, config, holder, recipient, cw20_msg.amount)
+        }
+        Cw20HookMsg::Fund {} => {
+            if info.sender != config.new_token || config.new_supply != (NewSupply::Release {}) {
+                return Err(ContractError::Unauthorized {});
+            }
+            let mut state = STATE.load(deps.storage)?;
+            state.funded = state.funded.checked_add(cw20_msg.amount)?;
+            STATE.save(deps.storage, &state)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "fund")
+                .add_attribute("sender", cw20_msg.sender)
+                .add_attribute("amount", cw20_msg.amount))
+        }
+    }
+}
+
+/// Disposes of the legacy tokens and issues the new tokens at the configured ratio
+pub fn migrate_tokens(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    holder: Addr,
+    recipient: Addr,
+    legacy_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    if now >= config.deadline {
+        return Err(ContractError::MigrationClosed {});
+    }
+
+    let new_amount = legacy_amount * config.ratio;
+    if new_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    if config.new_supply == (NewSupply::Release {}) && state.available() < new_amount {
+        return Err(ContractError::InsufficientFunds { available: state.available() });
+    }
+
+    let mut migration = MIGRATIONS.may_load(deps.storage, &holder)?.unwrap_or_default();
+    if migration.legacy_amount.is_zero() {
+        state.migrators += 1;
+    }
+    migration.legacy_amount = migration.legacy_amount.checked_add(legacy_amount)?;
+    migration.new_amount = migration.new_amount.checked_add(new_amount)?;
+    migration.last_migrated_at = now;
+    MIGRATIONS.save(deps.storage, &holder, &migration)?;
+
+    state.legacy_migrated = state.legacy_migrated.checked_add(legacy_amount)?;
+    state.new_issued = state.new_issued.checked_add(new_amount)?;
+    STATE.save(deps.storage, &state)?;
+
+    let mut messages = vec![];
+    if config.legacy_disposal == (LegacyDisposal::Burn {}) {
+        messages.push(token_msg(&config.legacy_token, &Cw20ExecuteMsg::Burn { amount: legacy_amount })?);
+    }
+    let issue_msg = match config.new_supply {
+        NewSupply::Mint {} => Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: new_amount,
+        },
+        NewSupply::Release {} => Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: new_amount,
+        },
+    };
+    messages.push(token_msg(&config.new_token, &issue_msg)?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "migrate")
+        .add_attribute("holder", holder)
+        .add_attribute("recipient", recipient)
+        .add_attribute("legacy_amount", legacy_amount)
+        .add_attribute("new_amount", new_amount))
+}
+
+pub fn update_deadline(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    deadline: u64,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+    if deadline <= env.block.time.seconds() {
+        return Err(ContractError::InvalidDeadline {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.deadline = deadline;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_deadline")
+        .add_attribute("deadline", deadline.to_string()))
+}
+
+pub fn withdraw_unreleased(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    ConfigOwner.assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    if env.block.time.seconds() < config.deadline {
+        return Err(ContractError::MigrationOpen {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    let amount = state.available();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    state.withdrawn = state.withdrawn.checked_add(amount)?;
+    STATE.save(deps.storage, &state)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let transfer = Cw20ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_message(token_msg(&config.new_token, &transfer)?)
+        .add_attribute("action", "withdraw_unreleased")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+fn token_msg(token: &Addr, msg: &Cw20ExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::Migration { address } => to_binary(&query_migration(deps, address)?),
+        QueryMsg::Migrations { start_after, limit } => to_binary(&query_migrations(deps, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        legacy_token: config.legacy_token.to_string(),
+        new_token: config.new_token.to_string(),
+        ratio: config.ratio,
+        legacy_disposal: config.legacy_disposal,
+        new_supply: config.new_supply,
+        deadline: config.deadline,
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        legacy_migrated: state.legacy_migrated,
+        new_issued: state.new_issued,
+        funded: state.funded,
+        withdrawn: state.withdrawn,
+        available: state.available(),
+        migrators: state.migrators,
+    })
+}
+
+pub fn query_migration(deps: Deps, address: String) -> StdResult<MigrationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let migration = MIGRATIONS.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(migration_response(address, migration))
+}
+
+pub fn query_migrations(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MigrationsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let migrations: StdResult<Vec<MigrationResponse>> = MIGRATIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, migration) = item?;
+            let address = Addr::unchecked(String::from_utf8(address)?);
+            Ok(migration_response(address, migration))
+        })
+        .collect();
+
+    Ok(MigrationsResponse { migrations: migrations? })
+}
+
+fn migration_response(address: Addr, migration: Migration) -> MigrationResponse {
+    MigrationResponse {
+        address: address.to_string(),
+        legacy_amount: migration.legacy_amount,
+        new_amount: migration.new_amount,
+        last_migrated_at: migration.last_migrated_at,
+    }
+}
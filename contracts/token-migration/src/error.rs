@@ -0,0 +1,45 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("The ratio must be positive")]
+    InvalidRatio {},
+
+    #[error("The deadline must be in the future")]
+    InvalidDeadline {},
+
+    #[error("The migration is closed")]
+    MigrationClosed {},
+
+    #[error("The migration is still open")]
+    MigrationOpen {},
+
+    #[error("Only {available} new tokens are left to release")]
+    InsufficientFunds { available: Uint128 },
+
+    #[error("Nothing left to withdraw")]
+    NothingToWithdraw {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
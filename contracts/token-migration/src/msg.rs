@@ -0,0 +1,114 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::{LegacyDisposal, NewSupply};
+
+/// This structure describes the parameters used for creating a token migration contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    pub legacy_token: String,
+    pub new_token: String,
+    /// The new tokens received for each legacy token, 1 if unset
+    pub ratio: Option<Decimal>,
+    pub legacy_disposal: LegacyDisposal,
+    pub new_supply: NewSupply,
+    /// Timestamp in seconds from which the migrations are rejected
+    pub deadline: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives the legacy tokens to migrate or the new tokens to release, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Moves the deadline, which cannot be set in the past. Only the owner can execute it.
+    UpdateDeadline { deadline: u64 },
+    /// Sends the funded tokens left after the deadline to the recipient.
+    /// Only the owner can execute it.
+    WithdrawUnreleased { recipient: String },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swaps the sent legacy tokens for new tokens, sent to the recipient or the holder if unset.
+    /// The migration is recorded for the holder.
+    Migrate { recipient: Option<String> },
+    /// Adds the sent new tokens to the tokens released by the migrations
+    Fund {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the migrated totals.
+    /// Return type: StateResponse.
+    State {},
+    /// Returns the amounts migrated by the holder, zero if none.
+    /// Return type: MigrationResponse.
+    Migration { address: String },
+    /// Returns the migrations by holder address.
+    /// Return type: MigrationsResponse.
+    Migrations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub legacy_token: String,
+    pub new_token: String,
+    pub ratio: Decimal,
+    pub legacy_disposal: LegacyDisposal,
+    pub new_supply: NewSupply,
+    pub deadline: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub legacy_migrated: Uint128,
+    pub new_issued: Uint128,
+    pub funded: Uint128,
+    pub withdrawn: Uint128,
+    /// The funded tokens left to release
+    pub available: Uint128,
+    pub migrators: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationResponse {
+    pub address: String,
+    pub legacy_amount: Uint128,
+    pub new_amount: Uint128,
+    pub last_migrated_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationsResponse {
+    pub migrations: Vec<MigrationResponse>,
+}
@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use luart_utils::ownable::Ownable;
+
+/// What happens to the legacy tokens received
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyDisposal {
+    /// Burnt right away, the legacy token must let holders burn their balance
+    Burn {},
+    /// Kept by the migration contract for good
+    Vault {},
+}
+
+/// Where the new tokens come from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NewSupply {
+    /// Minted on each migration, the migration contract must be a minter of the new token
+    Mint {},
+    /// Released from the new tokens funded to the migration contract
+    Release {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who extends the deadline and withdraws the unreleased tokens
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    pub legacy_token: Addr,
+    pub new_token: Addr,
+    /// The new tokens received for each legacy token
+    pub ratio: Decimal,
+    pub legacy_disposal: LegacyDisposal,
+    pub new_supply: NewSupply,
+    /// Timestamp in seconds from which the migrations are rejected
+    pub deadline: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct State {
+    /// The legacy tokens migrated
+    pub legacy_migrated: Uint128,
+    /// The new tokens minted or released for them
+    pub new_issued: Uint128,
+    /// The new tokens funded for release
+    pub funded: Uint128,
+    /// The funded tokens withdrawn by the owner after the deadline
+    pub withdrawn: Uint128,
+    /// The number of holders who migrated
+    pub migrators: u64,
+}
+
+impl State {
+    /// The funded tokens neither released nor withdrawn
+    pub fn available(&self) -> Uint128 {
+        self.funded.saturating_sub(self.new_issued).saturating_sub(self.withdrawn)
+    }
+}
+
+pub const STATE: Item<State> = Item::new("state");
+
+/// The migrated amounts of a holder
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Migration {
+    pub legacy_amount: Uint128,
+    pub new_amount: Uint128,
+    /// Timestamp in seconds of the last migration
+    pub last_migrated_at: u64,
+}
+
+pub const MIGRATIONS: Map<&Addr, Migration> = Map::new("migrations");
@@ -0,0 +1,173 @@
+use cosmwasm_std::{CosmosMsg, Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrationResponse, MigrationsResponse, QueryMsg, StateResponse,
+};
+use crate::state::{LegacyDisposal, NewSupply};
+
+const OWNER: &str = "owner";
+const LEGACY: &str = "mock_legacy_luart";
+const LUART: &str = "mock_luart";
+const HOLDER: &str = "holder";
+const TREASURY: &str = "mock_treasury";
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+    ratio: Option<Decimal>,
+    legacy_disposal: LegacyDisposal,
+    new_supply: NewSupply,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: Some(OWNER.to_string()),
+        legacy_token: LEGACY.to_string(),
+        new_token: LUART.to_string(),
+        ratio,
+        legacy_disposal,
+        new_supply,
+        deadline: env.block.time.seconds() + 1_000,
+    };
+    instantiate(deps, env, mock_info("deployer", &[]), msg).unwrap()
+}
+
+fn receive_msg(sender: &str, amount: u128, msg: Cw20HookMsg) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&msg).unwrap(),
+    })
+}
+
+fn token_msg(token: &str, msg: Cw20ExecuteMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_burn_and_mint() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone(), None, LegacyDisposal::Burn {}, NewSupply::Mint {});
+
+    // Only the legacy token can be migrated
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]),
+                      receive_msg(HOLDER, 1_000, Cw20HookMsg::Migrate { recipient: None })).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Minted tokens need no funding
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]),
+                      receive_msg(OWNER, 1_000, Cw20HookMsg::Fund {})).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1_000, Cw20HookMsg::Migrate { recipient: None })).unwrap();
+    assert_eq!(res.messages, vec![
+        token_msg(LEGACY, Cw20ExecuteMsg::Burn { amount: Uint128::new(1_000) }),
+        token_msg(LUART, Cw20ExecuteMsg::Mint { recipient: HOLDER.to_string(), amount: Uint128::new(1_000) }),
+    ]);
+
+    // The new tokens can go to another wallet, the migration is recorded for the holder
+    let res = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 500, Cw20HookMsg::Migrate { recipient: Some("cold_wallet".to_string()) }))
+        .unwrap();
+    assert_eq!(res.messages[1], token_msg(LUART, Cw20ExecuteMsg::Mint {
+        recipient: "cold_wallet".to_string(),
+        amount: Uint128::new(500),
+    }));
+    execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+            receive_msg("other", 200, Cw20HookMsg::Migrate { recipient: None })).unwrap();
+
+    let res: MigrationResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Migration {
+        address: HOLDER.to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res, MigrationResponse {
+        address: HOLDER.to_string(),
+        legacy_amount: Uint128::new(1_500),
+        new_amount: Uint128::new(1_500),
+        last_migrated_at: env.block.time.seconds(),
+    });
+
+    let res: MigrationsResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Migrations {
+        start_after: Some(HOLDER.to_string()),
+        limit: None,
+    }).unwrap()).unwrap();
+    let addresses: Vec<_> = res.migrations.iter().map(|m| m.address.as_str()).collect();
+    assert_eq!(addresses, vec!["other"]);
+
+    let res: StateResponse = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(res.legacy_migrated, Uint128::new(1_700));
+    assert_eq!(res.new_issued, Uint128::new(1_700));
+    assert_eq!(res.migrators, 2);
+
+    // The owner can extend the deadline, the migrations are closed after it
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]),
+                      ExecuteMsg::UpdateDeadline { deadline: env.block.time.seconds() + 2_000 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::UpdateDeadline { deadline: env.block.time.seconds() + 2_000 }).unwrap();
+    env.block.time = env.block.time.plus_seconds(2_000);
+    let err = execute(deps.as_mut(), env, mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1_000, Cw20HookMsg::Migrate { recipient: None })).unwrap_err();
+    assert_eq!(err, ContractError::MigrationClosed {});
+}
+
+#[test]
+fn test_vault_and_release() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone(), Some(Decimal::percent(50)), LegacyDisposal::Vault {},
+                        NewSupply::Release {});
+
+    // The migrations need funded tokens
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1_000, Cw20HookMsg::Migrate { recipient: None })).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds { available: Uint128::zero() });
+
+    execute(deps.as_mut(), env.clone(), mock_info(LUART, &[]),
+            receive_msg(OWNER, 1_000, Cw20HookMsg::Fund {})).unwrap();
+
+    // The legacy tokens stay in the contract and half as many new tokens are released
+    let res = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1_000, Cw20HookMsg::Migrate { recipient: None })).unwrap();
+    assert_eq!(res.messages, vec![
+        token_msg(LUART, Cw20ExecuteMsg::Transfer { recipient: HOLDER.to_string(), amount: Uint128::new(500) }),
+    ]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1, Cw20HookMsg::Migrate { recipient: None })).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(LEGACY, &[]),
+                      receive_msg(HOLDER, 1_200, Cw20HookMsg::Migrate { recipient: None })).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds { available: Uint128::new(500) });
+
+    // The owner withdraws the unreleased tokens after the deadline
+    let withdraw = ExecuteMsg::WithdrawUnreleased { recipient: TREASURY.to_string() };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), withdraw.clone()).unwrap_err();
+    assert_eq!(err, ContractError::MigrationOpen {});
+    env.block.time = env.block.time.plus_seconds(1_000);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(HOLDER, &[]), withdraw.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), withdraw.clone()).unwrap();
+    assert_eq!(res.messages, vec![
+        token_msg(LUART, Cw20ExecuteMsg::Transfer { recipient: TREASURY.to_string(), amount: Uint128::new(500) }),
+    ]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), withdraw).unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+
+    let res: StateResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(res, StateResponse {
+        legacy_migrated: Uint128::new(1_000),
+        new_issued: Uint128::new(500),
+        funded: Uint128::new(1_000),
+        withdrawn: Uint128::new(500),
+        available: Uint128::zero(),
+        migrators: 1,
+    });
+}
@@ -3,7 +3,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use luart_token::msg::InstantiateMsg;
+use luart_token::msg::{DenomMetadataResponse, ExportStateResponse, HoldersResponse, InstantiateMsg};
 use cw20::{
     AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, MinterResponse,
     TokenInfoResponse,
@@ -25,4 +25,7 @@ fn main() {
     export_schema(&schema_for!(MinterResponse), &out_dir);
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+    export_schema(&schema_for!(ExportStateResponse), &out_dir);
+    export_schema(&schema_for!(HoldersResponse), &out_dir);
+    export_schema(&schema_for!(DenomMetadataResponse), &out_dir);
 }
@@ -1,6 +1,6 @@
-use std::ops::{Div, Mul, Sub};
+use std::convert::TryFrom;
 
-use cosmwasm_std::{Addr, Binary, Decimal, Deps, DepsMut, Env, from_binary, MessageInfo, Response, StdError, StdResult, Storage, to_binary, Uint128};
+use cosmwasm_std::{Addr, BankMsg, Binary, coins, CosmosMsg, Decimal, Empty, Deps, DepsMut, Env, Fraction, from_binary, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, to_binary, Uint128, Uint256, WasmMsg};
 use cosmwasm_std::entry_point;
 use cw20_base::allowances::{
     execute_burn_from as cw20_execute_burn_from, execute_decrease_allowance as cw20_execute_decrease_allowance,
@@ -13,22 +13,55 @@ use cw20_base::contract::{
     query_minter, query_token_info,
 };
 use cw20_base::ContractError;
-use cw20_base::enumerable::{query_all_accounts, query_all_allowances};
-use cw20_base::state::{BALANCES, MinterData, TOKEN_INFO, TokenInfo};
+use cw20_base::enumerable::query_all_accounts;
+use cw20_base::state::{ALLOWANCES, BALANCES, MinterData, TOKEN_INFO, TokenInfo};
 use cw2::set_contract_version;
+use cw20::{AllAllowancesResponse, AllowanceInfo, AllowanceResponse, BalanceResponse};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::{Bound, PrimaryKey, U128Key, U64Key};
+use luart_protocol::pausable::PausedResponse;
+use luart_protocol::token::TotalSupplyResponse;
+use luart_utils::batch::batch_query;
+use luart_utils::nonreentrant::{self, guarded_submsg, ReentrancyError};
+use luart_utils::pausable::{self, assert_not_paused, is_paused, is_pauser, PAUSE_CONFIG, PauseConfig, PauseError};
+use luart_utils::roles::{grant_role, has_role, revoke_role, ROLES};
 use terraswap::pair::Cw20HookMsg;
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SwapFeeConfigResponse};
-use crate::state::{SWAP_FEE_CONFIG, SwapFeeConfig};
+use crate::msg::{
+    AdminRecoveryResponse, BlockReason, Cw721HookMsg, DenomMetadataResponse, DenomUnit, DestinationSendCap,
+    DividendClaimResponse, DividendResponse, ExecuteMsg, ExportStateResponse, FeeDiscountConfigResponse,
+    FeeDiscountResponse, HolderResponse, HoldersResponse, InstantiateMsg, IsBlockedResponse, LaunchAllowlistResponse,
+    LaunchModeResponse, MigrateMsg, PauseInfoResponse, ProtocolContractsResponse, QueryMsg, RebateEpochResponse,
+    RebateResponse, RolesResponse, ScheduledSwapResponse, SendAllowlistResponse, SendCapResponse,
+    SendStrictModeResponse, StateEntry, StateSection, SwapFeeConfigResponse, TransferRestrictionsResponse,
+};
+use crate::state::{
+    ADMIN_RECOVERY, AdminRecovery, BALANCE_INDEX, BALANCE_INDEX_CURSOR, BALANCE_SNAPSHOTS, DESCRIPTION, Dividend,
+    DIVIDEND_CLAIMS, DIVIDEND_COUNT, DIVIDENDS, FEE_ADMIN_ROLE, FEE_DISCOUNT_CONFIG, FEE_DISCOUNTS, FeeDiscount,
+    FeeDiscountConfig, index_balance, LAUNCH_ALLOWLIST, LAUNCH_UNLOCK_TIME, MAX_DESCRIPTION_LENGTH, MAX_RECOVERY_WINDOW,
+    MINTER_ADMIN_ROLE, PROTOCOL_CONTRACTS, REBATE_EPOCH_LENGTH, REBATE_EPOCHS, REBATE_SHARE, RebateEpoch, ROLE_NAMES,
+    SCHEDULED_SWAP_COUNT, SCHEDULED_SWAP_INTERVAL, SCHEDULED_SWAPS, ScheduledSwap, SEND_ALLOWLIST, SEND_CAP_BUCKET,
+    SEND_CAP_BUCKETS, SEND_CAP_WINDOW, SEND_CAPS, SEND_STRICT_MODE, SWAP_FEE_CONFIG, SwapFeeConfig,
+    TOTAL_SUPPLY_SNAPSHOTS, TRADER_FEES,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "luart-token";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_EXPORT_LIMIT: u32 = 100;
+const DEFAULT_EXPORT_LIMIT: u32 = 30;
+
+/// The reply of the guarded hooks, the scheduled swap sends and the badge returns, exiting the
+/// reentrancy guard
+const GUARD_REPLY_ID: u64 = 1;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
@@ -63,8 +96,16 @@ pub fn instantiate(
     };
 
     TOKEN_INFO.save(deps.storage, &data)?;
+    TOTAL_SUPPLY_SNAPSHOTS.save(deps.storage, &total_supply, env.block.height)?;
+
+    for account in msg.initial_balances.iter() {
+        let address = deps.api.addr_validate(&account.address)?;
+        BALANCE_SNAPSHOTS.save(deps.storage, &address, &account.amount, env.block.height)?;
+        index_balance(deps.storage, &address, Uint128::zero(), account.amount)?;
+    }
 
     if let Some(swap_fee_config) = msg.swap_fee_config {
+        validate_swap_percent_fee(swap_fee_config.swap_percent_fee)?;
         let data = SwapFeeConfig {
             fee_admin: deps.api.addr_validate(&swap_fee_config.fee_admin)?,
             enable_swap_fee: swap_fee_config.enable_swap_fee,
@@ -74,11 +115,57 @@ pub fn instantiate(
         SWAP_FEE_CONFIG.save(deps.storage, &data)?;
     }
 
+    let pauser = match msg.pauser {
+        Some(pauser) => Some(deps.api.addr_validate(&pauser)?),
+        None => None,
+    };
+    PAUSE_CONFIG.save(deps.storage, &PauseConfig { pauser, paused: false })?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    if moves_tokens(&msg) {
+        assert_not_paused(deps.storage).map_err(pause_error)?;
+        assert_launch_receivers(deps.as_ref(), &env, &msg)?;
+        assert_send_destination(deps.as_ref(), &msg)?;
+    }
+    // The pair notified of a scheduled swap and the collection returning a badge cannot call back
+    // into the escrows of the token. The sends are not guarded, routers legitimately send the
+    // tokens they are notified of.
+    if matches!(
+        msg,
+        ExecuteMsg::ScheduleSwap { .. }
+            | ExecuteMsg::ExecuteScheduledSwap { .. }
+            | ExecuteMsg::CancelScheduledSwap { .. }
+            | ExecuteMsg::ReceiveNft(..)
+            | ExecuteMsg::WithdrawFeeDiscountNft {}
+    ) {
+        nonreentrant::assert_not_entered(deps.storage).map_err(reentrancy_error)?;
+    }
+
+    // The balances changed by the message are snapshotted for the BalanceAt query
+    let holders = balance_holders(deps.as_ref(), &env, &info, &msg)?;
+    init_balance_snapshots(deps.storage, &holders)?;
+    let previous_balances = holders.iter()
+        .map(|holder| Ok(BALANCES.may_load(deps.storage, holder)?.unwrap_or_default()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let previous_supply = init_total_supply_snapshot(deps.storage)?;
+    let res = execute_msg(deps.branch(), env.clone(), info, msg)?;
+    update_balance_snapshots(deps.storage, &holders, env.block.height)?;
+    update_balance_index(deps.storage, &holders, &previous_balances)?;
+    update_total_supply_snapshot(deps.storage, previous_supply, env.block.height)?;
+
+    Ok(res)
+}
+
+fn execute_msg(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -122,7 +209,246 @@ pub fn execute(
             enable_swap_fee,
             swap_percent_fee,
             fee_receiver,
-        } => update_swap_fee_config(deps, info, fee_admin, enable_swap_fee, swap_percent_fee, fee_receiver)
+        } => update_swap_fee_config(deps, info, fee_admin, enable_swap_fee, swap_percent_fee, fee_receiver),
+        ExecuteMsg::UpdatePauser { pauser } => update_pauser(deps, info, pauser),
+        ExecuteMsg::Pause {} => set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => set_paused(deps, info, false),
+        ExecuteMsg::GrantRole { role, address } => update_role(deps, info, role, address, true),
+        ExecuteMsg::RevokeRole { role, address } => update_role(deps, info, role, address, false),
+        ExecuteMsg::UpdateMinter { minter } => update_minter(deps, info, minter),
+        ExecuteMsg::IndexBalances { limit } => index_balances(deps, limit),
+        ExecuteMsg::PruneExpiredAllowances { owner, limit } => prune_expired_allowances(deps, env, owner, limit),
+        ExecuteMsg::ScheduleSwap { pair, total_amount, intervals, belief_price, max_spread } => {
+            schedule_swap(deps, env, info, pair, total_amount, intervals, belief_price, max_spread)
+        }
+        ExecuteMsg::ExecuteScheduledSwap { swap_id } => execute_scheduled_swap(deps, env, swap_id),
+        ExecuteMsg::CancelScheduledSwap { swap_id } => cancel_scheduled_swap(deps, env, info, swap_id),
+        ExecuteMsg::DepositDividend { height, claim_deadline } => {
+            deposit_dividend(deps, env, info, height, claim_deadline)
+        }
+        ExecuteMsg::ClaimDividend { snapshot_id } => claim_dividend(deps, env, info, snapshot_id),
+        ExecuteMsg::SweepDividend { snapshot_id } => sweep_dividend(deps, env, snapshot_id),
+        ExecuteMsg::UpdateProtocolContracts { add, remove } => update_protocol_contracts(deps, info, add, remove),
+        ExecuteMsg::UpdateSendCap { contract, cap } => update_send_cap(deps, info, contract, cap),
+        ExecuteMsg::ResetSendCapWindow { contract } => reset_send_cap_window(deps, info, contract),
+        ExecuteMsg::UpdateLaunchMode { unlock_time } => update_launch_mode(deps, env, info, unlock_time),
+        ExecuteMsg::UpdateLaunchAllowlist { add, remove } => update_launch_allowlist(deps, info, add, remove),
+        ExecuteMsg::UpdateSendStrictMode { enabled } => update_send_strict_mode(deps, info, enabled),
+        ExecuteMsg::UpdateSendAllowlist { add, remove } => update_send_allowlist(deps, info, add, remove),
+        ExecuteMsg::UpdateAdminRecovery { recovery, window } => {
+            update_admin_recovery(deps, env, info, recovery, window)
+        }
+        ExecuteMsg::Heartbeat {} => heartbeat(deps, env, info),
+        ExecuteMsg::ClaimFeeAdmin {} => claim_fee_admin(deps, env, info),
+        ExecuteMsg::UpdateFeeDiscountConfig { collection, discount } => {
+            update_fee_discount_config(deps, info, collection, discount)
+        }
+        ExecuteMsg::ReceiveNft(msg) => receive_cw721(deps, info, msg),
+        ExecuteMsg::WithdrawFeeDiscountNft {} => withdraw_fee_discount_nft(deps, info),
+        ExecuteMsg::UpdateRebateShare { share } => update_rebate_share(deps, info, share),
+        ExecuteMsg::ClaimRebate { epoch } => claim_rebate(deps, env, info, epoch),
+        ExecuteMsg::UpdateDescription { description } => update_description(deps, info, description),
+        ExecuteMsg::ImportState { section, batch } => import_state(deps, env, info, section, batch),
+    }
+}
+
+/// The fee admin of the swap fee configuration and the fee_admin role holders
+fn is_fee_admin(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    let is_config_admin = SWAP_FEE_CONFIG
+        .may_load(storage)?
+        .map(|c| c.fee_admin == *address)
+        .unwrap_or(false);
+    Ok(is_config_admin || has_role(storage, FEE_ADMIN_ROLE, address))
+}
+
+fn pause_error(err: PauseError) -> ContractError {
+    match err {
+        PauseError::Std(err) => ContractError::Std(err),
+        PauseError::Unauthorized {} => ContractError::Unauthorized {},
+        PauseError::Paused {} => StdError::generic_err("Token transfers are paused").into(),
+    }
+}
+
+/// The unlock time while only the launch allowlist receives tokens, none once lifted
+fn launch_unlock_time(storage: &dyn Storage, env: &Env) -> StdResult<Option<u64>> {
+    Ok(LAUNCH_UNLOCK_TIME.may_load(storage)?.filter(|unlock_time| env.block.time.seconds() < *unlock_time))
+}
+
+/// Checks the receivers of the message are in the launch allowlist until the launch unlock time.
+/// The token contract itself receives the escrowed scheduled swaps.
+fn assert_launch_receivers(deps: Deps, env: &Env, msg: &ExecuteMsg) -> StdResult<()> {
+    let unlock_time = match launch_unlock_time(deps.storage, env)? {
+        Some(unlock_time) => unlock_time,
+        None => return Ok(()),
+    };
+
+    let receiver = match msg {
+        ExecuteMsg::Transfer { recipient, .. }
+        | ExecuteMsg::Mint { recipient, .. }
+        | ExecuteMsg::TransferFrom { recipient, .. } => Some(deps.api.addr_validate(recipient)?),
+        ExecuteMsg::Send { contract, .. } | ExecuteMsg::SendFrom { contract, .. } => {
+            Some(deps.api.addr_validate(contract)?)
+        }
+        ExecuteMsg::ExecuteScheduledSwap { swap_id } => {
+            SCHEDULED_SWAPS.may_load(deps.storage, U64Key::new(*swap_id))?.map(|swap| swap.pair)
+        }
+        _ => None,
+    };
+    match receiver {
+        Some(receiver) if receiver != env.contract.address && !LAUNCH_ALLOWLIST.has(deps.storage, &receiver) => {
+            Err(StdError::generic_err(format!("Only the launch allowlist receives tokens until {}", unlock_time)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks the destination of the sends is in the send allowlist while in strict mode. The
+/// scheduled swaps send to their pair when executed.
+fn assert_send_destination(deps: Deps, msg: &ExecuteMsg) -> StdResult<()> {
+    if !SEND_STRICT_MODE.may_load(deps.storage)?.unwrap_or(false) {
+        return Ok(());
+    }
+
+    let destination = match msg {
+        ExecuteMsg::Send { contract, .. } | ExecuteMsg::SendFrom { contract, .. } => {
+            Some(deps.api.addr_validate(contract)?)
+        }
+        ExecuteMsg::ExecuteScheduledSwap { swap_id } => {
+            SCHEDULED_SWAPS.may_load(deps.storage, U64Key::new(*swap_id))?.map(|swap| swap.pair)
+        }
+        _ => None,
+    };
+    match destination {
+        Some(destination) if !SEND_ALLOWLIST.has(deps.storage, &destination) => {
+            Err(StdError::generic_err(format!("Sends to {} are disabled in strict mode", destination)))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn reentrancy_error(err: ReentrancyError) -> ContractError {
+    match err {
+        ReentrancyError::Std(err) => ContractError::Std(err),
+        ReentrancyError::Reentrant {} => StdError::generic_err("Reentrant call").into(),
+    }
+}
+
+fn balance_holders(deps: Deps, env: &Env, info: &MessageInfo, msg: &ExecuteMsg) -> StdResult<Vec<Addr>> {
+    let mut holders = match msg {
+        ExecuteMsg::Transfer { recipient, .. } => vec![info.sender.clone(), deps.api.addr_validate(recipient)?],
+        ExecuteMsg::Burn { .. } => vec![info.sender.clone()],
+        ExecuteMsg::Send { contract, .. } => vec![info.sender.clone(), deps.api.addr_validate(contract)?],
+        ExecuteMsg::Mint { recipient, .. } => vec![deps.api.addr_validate(recipient)?],
+        ExecuteMsg::TransferFrom { owner, recipient, .. } => {
+            vec![deps.api.addr_validate(owner)?, deps.api.addr_validate(recipient)?]
+        }
+        ExecuteMsg::SendFrom { owner, contract, .. } => {
+            vec![deps.api.addr_validate(owner)?, deps.api.addr_validate(contract)?]
+        }
+        ExecuteMsg::BurnFrom { owner, .. } => vec![deps.api.addr_validate(owner)?],
+        // The scheduled swaps are escrowed by the token contract itself
+        ExecuteMsg::ScheduleSwap { .. } => vec![info.sender.clone(), env.contract.address.clone()],
+        ExecuteMsg::ExecuteScheduledSwap { swap_id } => {
+            let mut holders = vec![env.contract.address.clone()];
+            if let Some(swap) = SCHEDULED_SWAPS.may_load(deps.storage, U64Key::new(*swap_id))? {
+                holders.push(swap.pair);
+            }
+            holders
+        }
+        ExecuteMsg::CancelScheduledSwap { swap_id } => {
+            let mut holders = vec![env.contract.address.clone()];
+            if let Some(swap) = SCHEDULED_SWAPS.may_load(deps.storage, U64Key::new(*swap_id))? {
+                holders.push(swap.owner);
+            }
+            holders
+        }
+        ExecuteMsg::ClaimRebate { .. } => vec![env.contract.address.clone(), info.sender.clone()],
+        _ => vec![],
+    };
+
+    // Swaps pay a fee to the fee receiver, and the rebated share of the sends to the token contract
+    if matches!(msg, ExecuteMsg::Send { .. } | ExecuteMsg::SendFrom { .. } | ExecuteMsg::ExecuteScheduledSwap { .. }) {
+        if let Some(fee_config) = SWAP_FEE_CONFIG.may_load(deps.storage)? {
+            holders.push(fee_config.fee_receiver);
+        }
+    }
+    if matches!(msg, ExecuteMsg::Send { .. } | ExecuteMsg::SendFrom { .. }) {
+        holders.push(env.contract.address.clone());
+    }
+
+    Ok(holders)
+}
+
+/// Records the balance of the holders never snapshotted before, it is their balance
+/// at any height since the snapshots were introduced
+fn init_balance_snapshots(storage: &mut dyn Storage, holders: &[Addr]) -> StdResult<()> {
+    for holder in holders.iter() {
+        if BALANCE_SNAPSHOTS.may_load(storage, holder)?.is_none() {
+            let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+            BALANCE_SNAPSHOTS.save(storage, holder, &balance, 0)?;
+        }
+    }
+    Ok(())
+}
+
+fn update_balance_snapshots(storage: &mut dyn Storage, holders: &[Addr], height: u64) -> StdResult<()> {
+    for holder in holders.iter() {
+        let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+        BALANCE_SNAPSHOTS.save(storage, holder, &balance, height)?;
+    }
+    Ok(())
+}
+
+/// Records the total supply if it was never snapshotted before and returns it
+fn init_total_supply_snapshot(storage: &mut dyn Storage) -> StdResult<Uint128> {
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    if TOTAL_SUPPLY_SNAPSHOTS.may_load(storage)?.is_none() {
+        TOTAL_SUPPLY_SNAPSHOTS.save(storage, &total_supply, 0)?;
+    }
+    Ok(total_supply)
+}
+
+fn update_total_supply_snapshot(storage: &mut dyn Storage, previous_supply: Uint128, height: u64) -> StdResult<()> {
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    if total_supply != previous_supply {
+        TOTAL_SUPPLY_SNAPSHOTS.save(storage, &total_supply, height)?;
+    }
+    Ok(())
+}
+
+fn update_balance_index(storage: &mut dyn Storage, holders: &[Addr], previous_balances: &[Uint128]) -> StdResult<()> {
+    for (holder, previous) in holders.iter().zip(previous_balances.iter()) {
+        let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+        index_balance(storage, holder, *previous, balance)?;
+    }
+    Ok(())
+}
+
+fn moves_tokens(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Transfer { .. }
+            | ExecuteMsg::Burn { .. }
+            | ExecuteMsg::Send { .. }
+            | ExecuteMsg::Mint { .. }
+            | ExecuteMsg::TransferFrom { .. }
+            | ExecuteMsg::SendFrom { .. }
+            | ExecuteMsg::BurnFrom { .. }
+            | ExecuteMsg::ScheduleSwap { .. }
+            | ExecuteMsg::ExecuteScheduledSwap { .. }
+            | ExecuteMsg::CancelScheduledSwap { .. }
+            | ExecuteMsg::ClaimRebate { .. }
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        GUARD_REPLY_ID => {
+            nonreentrant::exit(deps.storage);
+            Ok(Response::default())
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id {}", id)).into()),
     }
 }
 
@@ -143,18 +469,25 @@ pub fn execute_send(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if is_internal_route(deps.storage, &info.sender, &contract_addr)? {
+        return cw20_execute_send(deps, env, info, contract, amount, msg);
+    }
+    record_capped_send(deps.storage, &contract_addr, amount, env.block.time.seconds())?;
+
     let fee_config = SWAP_FEE_CONFIG.may_load(deps.storage)?;
 
     if let Some(fee_config) = fee_config {
         // Calculate fee amount based on message type
-        let fee_amount = calculate_fee_amount(amount, &msg, &fee_config);
+        let fee_amount = calculate_fee_amount(amount, &msg, &fee_config)?;
+        let fee_amount = apply_fee_discount(deps.storage, &info.sender, fee_amount)?;
 
         // If the fee is non zero then transfer the fee amount to the fee recipient address and execute cw20 send for left amount
         if !fee_amount.is_zero() {
-            // Transfer fee to configured receiver address
-            transfer(deps.storage, &info.sender, &fee_config.fee_receiver, fee_amount)?;
+            // Transfer fee to configured receiver address, setting aside the rebated share
+            collect_swap_fee(deps.storage, &env, &info.sender, &info.sender, &fee_config.fee_receiver, fee_amount)?;
 
-            let send_amount = amount.sub(fee_amount);
+            let send_amount = amount.checked_sub(fee_amount).map_err(StdError::from)?;
             let res = cw20_execute_send(deps, env, info.clone(), contract.clone(), send_amount, msg)?;
 
             return Ok(Response::new()
@@ -179,19 +512,26 @@ pub fn execute_send_from(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if is_internal_route(deps.storage, &owner_addr, &contract_addr)? {
+        return cw20_execute_send_from(deps, env, info, owner, contract, amount, msg);
+    }
+    record_capped_send(deps.storage, &contract_addr, amount, env.block.time.seconds())?;
+
     let fee_config = SWAP_FEE_CONFIG.may_load(deps.storage)?;
 
     if let Some(fee_config) = fee_config {
         // Calculate fee amount based on message type
-        let fee_amount = calculate_fee_amount(amount, &msg, &fee_config);
+        let fee_amount = calculate_fee_amount(amount, &msg, &fee_config)?;
+        let fee_amount = apply_fee_discount(deps.storage, &owner_addr, fee_amount)?;
 
         // If the fee is non zero then transfer the fee amount to the fee recipient address and execute cw20 send for left amount
         if !fee_amount.is_zero() {
-            // Transfer fee to configured receiver address
-            let owner_addr = deps.api.addr_validate(&owner)?;
-            transfer(deps.storage, &owner_addr, &fee_config.fee_receiver, fee_amount)?;
+            // Transfer fee to configured receiver address, setting aside the rebated share
+            collect_swap_fee(deps.storage, &env, &owner_addr, &owner_addr, &fee_config.fee_receiver, fee_amount)?;
 
-            let send_amount = amount.sub(fee_amount);
+            let send_amount = amount.checked_sub(fee_amount).map_err(StdError::from)?;
             let res = cw20_execute_send_from(deps, env, info.clone(), owner.clone(), contract.clone(), send_amount, msg)?;
 
             return Ok(Response::new()
@@ -220,7 +560,7 @@ pub fn update_swap_fee_config(
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
 
-    if swap_fee_config.fee_admin != info.sender {
+    if !is_fee_admin(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -233,6 +573,7 @@ pub fn update_swap_fee_config(
     }
 
     if let Some(swap_percent_fee) = swap_percent_fee {
+        validate_swap_percent_fee(swap_percent_fee)?;
         swap_fee_config.swap_percent_fee = swap_percent_fee
     }
 
@@ -246,50 +587,1433 @@ pub fn update_swap_fee_config(
         .add_attribute("method", "update_swap_fee_config"))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
-        QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
-        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
-        QueryMsg::Allowance { owner, spender } => {
-            to_binary(&query_allowance(deps, owner, spender)?)
-        }
-        QueryMsg::AllAllowances {
-            owner,
-            start_after,
-            limit,
-        } => to_binary(&query_all_allowances(deps, owner, start_after, limit)?),
-        QueryMsg::AllAccounts { start_after, limit } => {
-            to_binary(&query_all_accounts(deps, start_after, limit)?)
+#[allow(clippy::too_many_arguments)]
+pub fn schedule_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+    total_amount: Uint128,
+    intervals: u32,
+    belief_price: Decimal,
+    max_spread: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    if total_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if intervals == 0 || total_amount < Uint128::from(intervals) {
+        return Err(StdError::generic_err("A scheduled swap needs between 1 and total_amount intervals").into());
+    }
+    if belief_price.is_zero() {
+        return Err(StdError::generic_err("A scheduled swap needs a belief price").into());
+    }
+
+    let swap = ScheduledSwap {
+        owner: info.sender,
+        pair: deps.api.addr_validate(&pair)?,
+        remaining: total_amount,
+        swaps_left: intervals,
+        next_swap_time: env.block.time.seconds(),
+        belief_price,
+        max_spread,
+    };
+    transfer(deps.storage, &swap.owner, &env.contract.address, total_amount)?;
+
+    let swap_id = SCHEDULED_SWAP_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    SCHEDULED_SWAP_COUNT.save(deps.storage, &swap_id)?;
+    SCHEDULED_SWAPS.save(deps.storage, U64Key::new(swap_id), &swap)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "schedule_swap")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("owner", swap.owner)
+        .add_attribute("pair", swap.pair)
+        .add_attribute("total_amount", total_amount))
+}
+
+/// Sends the next even part of the escrowed tokens to the pair, the last part takes the
+/// rounding remainder
+pub fn execute_scheduled_swap(deps: DepsMut, env: Env, swap_id: u64) -> Result<Response, ContractError> {
+    let mut swap = SCHEDULED_SWAPS.load(deps.storage, U64Key::new(swap_id))?;
+    let now = env.block.time.seconds();
+    if now < swap.next_swap_time {
+        return Err(StdError::generic_err("The next swap is not due yet").into());
+    }
+
+    let amount = swap.remaining / Uint128::from(swap.swaps_left);
+    record_capped_send(deps.storage, &swap.pair, amount, now)?;
+
+    // Anyone executes the swaps, the stored price keeps them from being sandwiched
+    let msg = to_binary(&Cw20HookMsg::Swap {
+        belief_price: Some(swap.belief_price),
+        max_spread: swap.max_spread,
+        to: Some(swap.owner.to_string()),
+    })?;
+    let fee_amount = match SWAP_FEE_CONFIG.may_load(deps.storage)? {
+        Some(fee_config) => {
+            let fee_amount = calculate_fee_amount(amount, &msg, &fee_config)?;
+            let fee_amount = apply_fee_discount(deps.storage, &swap.owner, fee_amount)?;
+            if !fee_amount.is_zero() {
+                let payer = env.contract.address.clone();
+                collect_swap_fee(deps.storage, &env, &payer, &swap.owner, &fee_config.fee_receiver, fee_amount)?;
+            }
+            fee_amount
         }
-        QueryMsg::SwapFeeConfig {} => {
-            to_binary(&query_swap_fee_config(deps)?)
+        None => Uint128::zero(),
+    };
+
+    swap.remaining = swap.remaining.checked_sub(amount).map_err(StdError::from)?;
+    swap.swaps_left -= 1;
+    swap.next_swap_time = now + SCHEDULED_SWAP_INTERVAL;
+    if swap.swaps_left == 0 {
+        SCHEDULED_SWAPS.remove(deps.storage, U64Key::new(swap_id));
+    } else {
+        SCHEDULED_SWAPS.save(deps.storage, U64Key::new(swap_id), &swap)?;
+    }
+
+    let escrow = MessageInfo { sender: env.contract.address.clone(), funds: vec![] };
+    let send_amount = amount.checked_sub(fee_amount).map_err(StdError::from)?;
+    nonreentrant::enter(deps.storage).map_err(reentrancy_error)?;
+    let res = cw20_execute_send(deps, env, escrow, swap.pair.to_string(), send_amount, msg)?;
+    let messages = res.messages.into_iter().map(|msg| guarded_submsg(msg, GUARD_REPLY_ID));
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_scheduled_swap")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("fee_amount", fee_amount)
+        .add_attribute("swaps_left", swap.swaps_left.to_string())
+        .add_submessages(messages))
+}
+
+pub fn cancel_scheduled_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let swap = SCHEDULED_SWAPS.load(deps.storage, U64Key::new(swap_id))?;
+    if swap.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    transfer(deps.storage, &env.contract.address, &swap.owner, swap.remaining)?;
+    SCHEDULED_SWAPS.remove(deps.storage, U64Key::new(swap_id));
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel_scheduled_swap")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("refund_amount", swap.remaining))
+}
+
+pub fn deposit_dividend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    height: u64,
+    claim_deadline: u64,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let coin = match info.funds.as_slice() {
+        [coin] if !coin.amount.is_zero() => coin,
+        _ => return Err(StdError::generic_err("A dividend is deposited in exactly one native coin").into()),
+    };
+    if height > env.block.height {
+        return Err(StdError::generic_err("The snapshot height cannot be in the future").into());
+    }
+    if claim_deadline <= env.block.time.seconds() {
+        return Err(StdError::generic_err("The claim deadline must be in the future").into());
+    }
+    // The tokens escrowed by the token itself never claim, they are left out of the supply
+    let escrowed = balance_at(deps.storage, &env.contract.address, height)?;
+    let total_supply = total_supply_at(deps.storage, height)?.checked_sub(escrowed).map_err(StdError::from)?;
+    if total_supply.is_zero() {
+        return Err(StdError::generic_err("No supply at the snapshot height").into());
+    }
+
+    let snapshot_id = DIVIDEND_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    DIVIDEND_COUNT.save(deps.storage, &snapshot_id)?;
+    DIVIDENDS.save(deps.storage, U64Key::new(snapshot_id), &Dividend {
+        denom: coin.denom.clone(),
+        amount: coin.amount,
+        height,
+        total_supply,
+        claimed: Uint128::zero(),
+        claim_deadline,
+        swept: false,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "deposit_dividend")
+        .add_attribute("snapshot_id", snapshot_id.to_string())
+        .add_attribute("height", height.to_string())
+        .add_attribute("amount", coin.to_string()))
+}
+
+pub fn claim_dividend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    snapshot_id: u64,
+) -> Result<Response, ContractError> {
+    let mut dividend = DIVIDENDS.load(deps.storage, U64Key::new(snapshot_id))?;
+    if env.block.time.seconds() > dividend.claim_deadline {
+        return Err(StdError::generic_err("The claim deadline has passed").into());
+    }
+    if DIVIDEND_CLAIMS.has(deps.storage, (U64Key::new(snapshot_id), &info.sender)) {
+        return Err(StdError::generic_err("Dividend already claimed").into());
+    }
+
+    let amount = dividend_share(deps.storage, &env, &dividend, &info.sender)?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    dividend.claimed = dividend.claimed.checked_add(amount).map_err(StdError::from)?;
+    DIVIDENDS.save(deps.storage, U64Key::new(snapshot_id), &dividend)?;
+    DIVIDEND_CLAIMS.save(deps.storage, (U64Key::new(snapshot_id), &info.sender), &Empty {})?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), &dividend.denom),
+        })
+        .add_attribute("method", "claim_dividend")
+        .add_attribute("snapshot_id", snapshot_id.to_string())
+        .add_attribute("holder", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn sweep_dividend(deps: DepsMut, env: Env, snapshot_id: u64) -> Result<Response, ContractError> {
+    let mut dividend = DIVIDENDS.load(deps.storage, U64Key::new(snapshot_id))?;
+    if env.block.time.seconds() <= dividend.claim_deadline {
+        return Err(StdError::generic_err("The claim deadline has not passed yet").into());
+    }
+    if dividend.swept {
+        return Err(StdError::generic_err("Dividend already swept").into());
+    }
+    let fee_receiver = SWAP_FEE_CONFIG
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::not_found("SwapFeeConfig"))?
+        .fee_receiver;
+
+    dividend.swept = true;
+    DIVIDENDS.save(deps.storage, U64Key::new(snapshot_id), &dividend)?;
+
+    let unclaimed = dividend.amount.checked_sub(dividend.claimed).map_err(StdError::from)?;
+    let mut res = Response::new()
+        .add_attribute("method", "sweep_dividend")
+        .add_attribute("snapshot_id", snapshot_id.to_string())
+        .add_attribute("amount", unclaimed);
+    if !unclaimed.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: fee_receiver.to_string(),
+            amount: coins(unclaimed.u128(), &dividend.denom),
+        });
+    }
+
+    Ok(res)
+}
+
+fn dividend_share(storage: &dyn Storage, env: &Env, dividend: &Dividend, holder: &Addr) -> StdResult<Uint128> {
+    if *holder == env.contract.address {
+        return Ok(Uint128::zero());
+    }
+    let balance = balance_at(storage, holder, dividend.height)?;
+    Ok(dividend.amount.multiply_ratio(balance, dividend.total_supply))
+}
+
+pub fn update_protocol_contracts(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for contract in add {
+        PROTOCOL_CONTRACTS.save(deps.storage, &deps.api.addr_validate(&contract)?, &Empty {})?;
+    }
+    for contract in remove {
+        PROTOCOL_CONTRACTS.remove(deps.storage, &deps.api.addr_validate(&contract)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_protocol_contracts"))
+}
+
+/// Sends from a protocol contract to another one only move funds within the protocol
+fn is_internal_route(storage: &dyn Storage, sender: &Addr, contract: &Addr) -> StdResult<bool> {
+    Ok(PROTOCOL_CONTRACTS.has(storage, sender) && PROTOCOL_CONTRACTS.has(storage, contract))
+}
+
+pub fn update_send_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract = deps.api.addr_validate(&contract)?;
+    match cap {
+        Some(cap) => SEND_CAPS.save(deps.storage, &contract, &cap)?,
+        None => {
+            SEND_CAPS.remove(deps.storage, &contract);
+            SEND_CAP_BUCKETS.remove(deps.storage, &contract);
         }
     }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_send_cap")
+        .add_attribute("contract", contract))
 }
 
-pub fn query_swap_fee_config(deps: Deps) -> StdResult<SwapFeeConfigResponse> {
-    let swap_fee_config = SWAP_FEE_CONFIG.may_load(deps.storage)?;
-    match swap_fee_config {
-        Some(swap_fee_config) => {
-            Ok(SwapFeeConfigResponse {
-                fee_admin: swap_fee_config.fee_admin.to_string(),
-                enable_swap_fee: swap_fee_config.enable_swap_fee,
-                swap_percent_fee: swap_fee_config.swap_percent_fee,
-                fee_receiver: swap_fee_config.fee_receiver.to_string(),
-            })
+pub fn reset_send_cap_window(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract = deps.api.addr_validate(&contract)?;
+    SEND_CAP_BUCKETS.remove(deps.storage, &contract);
+
+    Ok(Response::new()
+        .add_attribute("method", "reset_send_cap_window")
+        .add_attribute("contract", contract))
+}
+
+pub fn update_launch_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unlock_time: u64,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+    match LAUNCH_UNLOCK_TIME.may_load(deps.storage)? {
+        Some(current) if current <= now => {
+            return Err(StdError::generic_err("The launch mode has already lifted").into());
         }
-        None => Ok(Default::default())
+        Some(current) if unlock_time > current => {
+            return Err(StdError::generic_err("The launch unlock time can only be brought forward").into());
+        }
+        None if unlock_time <= now => {
+            return Err(StdError::generic_err("The launch unlock time must be in the future").into());
+        }
+        _ => {}
     }
+    LAUNCH_UNLOCK_TIME.save(deps.storage, &unlock_time)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_launch_mode")
+        .add_attribute("unlock_time", unlock_time.to_string()))
 }
 
-fn calculate_fee_amount(amount: Uint128, msg: &Binary, swap_fee_config: &SwapFeeConfig) -> Uint128 {
-    if swap_fee_config.enable_swap_fee && is_swap_message(msg.clone()) {
-        amount.mul(swap_fee_config.swap_percent_fee).div(Uint128::new(100))
-    } else {
-        Uint128::zero()
+pub fn update_launch_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
     }
+
+    for address in add {
+        LAUNCH_ALLOWLIST.save(deps.storage, &deps.api.addr_validate(&address)?, &Empty {})?;
+    }
+    for address in remove {
+        LAUNCH_ALLOWLIST.remove(deps.storage, &deps.api.addr_validate(&address)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_launch_allowlist"))
+}
+
+pub fn update_send_strict_mode(deps: DepsMut, info: MessageInfo, enabled: bool) -> Result<Response, ContractError> {
+    // The pausers respond to incidents, but only the fee admin lifts the restriction
+    let authorized = is_fee_admin(deps.storage, &info.sender)?
+        || (enabled && is_pauser(deps.storage, &info.sender)?);
+    if !authorized {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    SEND_STRICT_MODE.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_send_strict_mode")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn update_send_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for contract in add {
+        SEND_ALLOWLIST.save(deps.storage, &deps.api.addr_validate(&contract)?, &Empty {})?;
+    }
+    for contract in remove {
+        SEND_ALLOWLIST.remove(deps.storage, &deps.api.addr_validate(&contract)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_send_allowlist"))
+}
+
+pub fn update_admin_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recovery: Option<String>,
+    window: u64,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match recovery {
+        Some(recovery) => {
+            if window == 0 {
+                return Err(StdError::generic_err("The heartbeat window cannot be zero").into());
+            }
+            if window > MAX_RECOVERY_WINDOW {
+                return Err(StdError::generic_err(
+                    format!("The heartbeat window cannot exceed {} seconds", MAX_RECOVERY_WINDOW)).into());
+            }
+            ADMIN_RECOVERY.save(deps.storage, &AdminRecovery {
+                recovery: deps.api.addr_validate(&recovery)?,
+                window,
+                last_heartbeat: env.block.time.seconds(),
+            })?;
+        }
+        None => ADMIN_RECOVERY.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_admin_recovery"))
+}
+
+pub fn heartbeat(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut admin_recovery = ADMIN_RECOVERY
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::not_found("AdminRecovery"))?;
+    admin_recovery.last_heartbeat = env.block.time.seconds();
+    ADMIN_RECOVERY.save(deps.storage, &admin_recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "heartbeat"))
+}
+
+pub fn claim_fee_admin(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut admin_recovery = ADMIN_RECOVERY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if info.sender != admin_recovery.recovery {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() <= admin_recovery.claimable_after()? {
+        return Err(StdError::generic_err("The fee admin sent a heartbeat within the window").into());
+    }
+
+    let mut swap_fee_config = SWAP_FEE_CONFIG
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::not_found("SwapFeeConfig"))?;
+    swap_fee_config.fee_admin = info.sender.clone();
+    SWAP_FEE_CONFIG.save(deps.storage, &swap_fee_config)?;
+    // The recovery address is now the fee admin, it heartbeats to keep the role
+    admin_recovery.last_heartbeat = env.block.time.seconds();
+    ADMIN_RECOVERY.save(deps.storage, &admin_recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "claim_fee_admin")
+        .add_attribute("fee_admin", info.sender))
+}
+
+pub fn update_fee_discount_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    discount: Decimal,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if discount > Decimal::one() {
+        return Err(StdError::generic_err("Fee discount cannot exceed 1").into());
+    }
+
+    let config = FeeDiscountConfig {
+        collection: deps.api.addr_validate(&collection)?,
+        discount,
+    };
+    FEE_DISCOUNT_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_fee_discount_config"))
+}
+
+pub fn receive_cw721(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw721_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw721_msg.msg)? {
+        Cw721HookMsg::DepositFeeDiscount {} => {
+            let config = FEE_DISCOUNT_CONFIG
+                .may_load(deps.storage)?
+                .ok_or(ContractError::Unauthorized {})?;
+            if info.sender != config.collection {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let holder = deps.api.addr_validate(&cw721_msg.sender)?;
+            if FEE_DISCOUNTS.has(deps.storage, &holder) {
+                return Err(StdError::generic_err("A fee discount badge is already deposited").into());
+            }
+
+            let deposit = FeeDiscount {
+                collection: info.sender,
+                token_id: cw721_msg.token_id,
+            };
+            FEE_DISCOUNTS.save(deps.storage, &holder, &deposit)?;
+
+            Ok(Response::new()
+                .add_attribute("method", "deposit_fee_discount")
+                .add_attribute("holder", holder)
+                .add_attribute("token_id", deposit.token_id))
+        }
+    }
+}
+
+/// Returns the deposited badge to its holder, even if its collection is no longer the
+/// configured one
+pub fn withdraw_fee_discount_nft(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let deposit = FEE_DISCOUNTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| StdError::not_found("FeeDiscount"))?;
+    FEE_DISCOUNTS.remove(deps.storage, &info.sender);
+
+    // The collection may no longer be the configured one, it cannot call back into the escrows
+    nonreentrant::enter(deps.storage).map_err(reentrancy_error)?;
+    let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: deposit.collection.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: info.sender.to_string(),
+            token_id: deposit.token_id.clone(),
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_submessage(guarded_submsg(SubMsg::new(transfer_msg), GUARD_REPLY_ID))
+        .add_attribute("method", "withdraw_fee_discount_nft")
+        .add_attribute("holder", info.sender)
+        .add_attribute("token_id", deposit.token_id))
+}
+
+pub fn update_rebate_share(deps: DepsMut, info: MessageInfo, share: Decimal) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if share > Decimal::one() {
+        return Err(StdError::generic_err("Rebate share cannot exceed 1").into());
+    }
+    REBATE_SHARE.save(deps.storage, &share)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_rebate_share")
+        .add_attribute("share", share.to_string()))
+}
+
+pub fn update_description(
+    deps: DepsMut,
+    info: MessageInfo,
+    description: String,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if description.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Description cannot exceed {} bytes", MAX_DESCRIPTION_LENGTH
+        )).into());
+    }
+    DESCRIPTION.save(deps.storage, &description)?;
+
+    Ok(Response::new().add_attribute("method", "update_description"))
+}
+
+pub fn claim_rebate(deps: DepsMut, env: Env, info: MessageInfo, epoch: u64) -> Result<Response, ContractError> {
+    if epoch >= rebate_epoch(&env) {
+        return Err(StdError::generic_err("The rebate epoch has not ended yet").into());
+    }
+
+    let amount = rebate_amount(deps.storage, epoch, &info.sender)?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    TRADER_FEES.remove(deps.storage, (U64Key::new(epoch), &info.sender));
+    transfer(deps.storage, &env.contract.address, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "claim_rebate")
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("trader", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn update_pauser(
+    deps: DepsMut,
+    info: MessageInfo,
+    pauser: Option<String>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pauser = match pauser {
+        Some(pauser) => Some(deps.api.addr_validate(&pauser)?),
+        None => None,
+    };
+    pausable::update_pauser(deps.storage, pauser)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_pauser"))
+}
+
+pub fn set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    pausable::set_paused(deps.storage, &info.sender, paused).map_err(pause_error)?;
+
+    Ok(Response::new()
+        .add_attribute("method", if paused { "pause" } else { "unpause" }))
+}
+
+pub fn update_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: String,
+    address: String,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !ROLE_NAMES.contains(&role.as_str()) {
+        return Err(StdError::generic_err(format!("Unknown role: {}", role)).into());
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    if granted {
+        grant_role(deps.storage, &role, &address)?;
+    } else {
+        revoke_role(deps.storage, &role, &address);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", if granted { "grant_role" } else { "revoke_role" })
+        .add_attribute("role", role)
+        .add_attribute("address", address))
+}
+
+pub fn update_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, MINTER_ADMIN_ROLE, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    let mut mint = token_info
+        .mint
+        .ok_or_else(|| StdError::generic_err("The token is not mintable"))?;
+    mint.minter = deps.api.addr_validate(&minter)?;
+    token_info.mint = Some(mint);
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_minter")
+        .add_attribute("minter", minter))
+}
+
+/// Indexes the next holders in address order, their index entry is the same
+/// whether they were already indexed along a balance change or not
+pub fn index_balances(deps: DepsMut, limit: Option<u32>) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+    let start = BALANCE_INDEX_CURSOR.may_load(deps.storage)?.map(|cursor| Bound::exclusive(cursor.as_str()));
+
+    let balances = BALANCES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, balance) = item?;
+            Ok((Addr::unchecked(String::from_utf8(key)?), balance))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (holder, balance) in balances.iter() {
+        index_balance(deps.storage, holder, Uint128::zero(), *balance)?;
+    }
+    if let Some((holder, _)) = balances.last() {
+        BALANCE_INDEX_CURSOR.save(deps.storage, holder)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "index_balances")
+        .add_attribute("indexed", balances.len().to_string())
+        .add_attribute("complete", (balances.len() < limit).to_string()))
+}
+
+pub fn prune_expired_allowances(
+    deps: DepsMut,
+    env: Env,
+    owner: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+
+    let expired = ALLOWANCES
+        .prefix(&owner)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, allowance)) => allowance.expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            Ok(Addr::unchecked(String::from_utf8(key)?))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for spender in expired.iter() {
+        ALLOWANCES.remove(deps.storage, (&owner, spender));
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "prune_expired_allowances")
+        .add_attribute("owner", owner)
+        .add_attribute("pruned", expired.len().to_string()))
+}
+
+/// Writes the exported entries. The imported balances are snapshotted and replace
+/// the current ones in the total supply.
+pub fn import_state(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    section: StateSection,
+    batch: Vec<StateEntry>,
+) -> Result<Response, ContractError> {
+    if !is_fee_admin(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !is_paused(deps.storage)? {
+        return Err(StdError::generic_err("The token must be paused to import state").into());
+    }
+
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    for entry in batch.iter() {
+        if entry.section() != section {
+            return Err(StdError::generic_err("The entry does not belong to the imported section").into());
+        }
+
+        match entry {
+            StateEntry::Balance { address, amount } => {
+                let address = deps.api.addr_validate(address)?;
+                // The balance held before the import stays readable at the earlier heights
+                init_balance_snapshots(deps.storage, std::slice::from_ref(&address))?;
+                let previous = BALANCES.may_load(deps.storage, &address)?.unwrap_or_default();
+                token_info.total_supply = token_info.total_supply
+                    .checked_sub(previous)
+                    .and_then(|supply| supply.checked_add(*amount))
+                    .map_err(StdError::from)?;
+                BALANCES.save(deps.storage, &address, amount)?;
+                BALANCE_SNAPSHOTS.save(deps.storage, &address, amount, env.block.height)?;
+                index_balance(deps.storage, &address, previous, *amount)?;
+            }
+            StateEntry::Allowance { owner, spender, allowance, expires } => {
+                let owner = deps.api.addr_validate(owner)?;
+                let spender = deps.api.addr_validate(spender)?;
+                ALLOWANCES.save(deps.storage, (&owner, &spender), &AllowanceResponse {
+                    allowance: *allowance,
+                    expires: *expires,
+                })?;
+            }
+            StateEntry::Role { role, address } => {
+                if !ROLE_NAMES.contains(&role.as_str()) {
+                    return Err(StdError::generic_err(format!("Unknown role: {}", role)).into());
+                }
+                grant_role(deps.storage, role, &deps.api.addr_validate(address)?)?;
+            }
+        }
+    }
+
+    if let Some(cap) = token_info.get_cap() {
+        if token_info.total_supply > cap {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "import_state")
+        .add_attribute("entries", batch.len().to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_binary(&query_allowance(deps, owner, spender)?)
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+            exclude_expired,
+        } => to_binary(&query_all_allowances(deps, env, owner, start_after, limit, exclude_expired)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::SwapFeeConfig {} => {
+            to_binary(&query_swap_fee_config(deps)?)
+        }
+        QueryMsg::PauseInfo {} => to_binary(&query_pause_info(deps)?),
+        QueryMsg::ScheduledSwap { swap_id } => to_binary(&query_scheduled_swap(deps, swap_id)?),
+        QueryMsg::Dividend { snapshot_id } => to_binary(&query_dividend(deps, snapshot_id)?),
+        QueryMsg::DividendClaim { snapshot_id, address } => {
+            to_binary(&query_dividend_claim(deps, env, snapshot_id, address)?)
+        }
+        QueryMsg::ProtocolContracts { start_after, limit } => {
+            to_binary(&query_protocol_contracts(deps, start_after, limit)?)
+        }
+        QueryMsg::SendCap { contract } => to_binary(&query_send_cap(deps, env, contract)?),
+        QueryMsg::LaunchMode {} => to_binary(&query_launch_mode(deps, env)?),
+        QueryMsg::LaunchAllowlist { start_after, limit } => {
+            to_binary(&query_launch_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::SendStrictMode {} => to_binary(&SendStrictModeResponse {
+            enabled: SEND_STRICT_MODE.may_load(deps.storage)?.unwrap_or(false),
+        }),
+        QueryMsg::SendAllowlist { start_after, limit } => {
+            to_binary(&query_send_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::AdminRecovery {} => to_binary(&query_admin_recovery(deps)?),
+        QueryMsg::FeeDiscountConfig {} => to_binary(&query_fee_discount_config(deps)?),
+        QueryMsg::FeeDiscount { address } => to_binary(&query_fee_discount(deps, address)?),
+        QueryMsg::RebateEpoch { epoch } => to_binary(&query_rebate_epoch(deps, epoch)?),
+        QueryMsg::Rebate { epoch, address } => to_binary(&query_rebate(deps, epoch, address)?),
+        QueryMsg::Paused {} => to_binary(&PausedResponse { paused: is_paused(deps.storage)? }),
+        QueryMsg::IsBlocked { address } => to_binary(&query_is_blocked(deps, env, address)?),
+        QueryMsg::TransferRestrictions {} => to_binary(&query_transfer_restrictions(deps, env)?),
+        QueryMsg::BalanceAt { address, height } => to_binary(&query_balance_at(deps, address, height)?),
+        QueryMsg::TotalSupplyAt { height } => to_binary(&TotalSupplyResponse {
+            total_supply: total_supply_at(deps.storage, height)?,
+        }),
+        QueryMsg::Roles { address } => to_binary(&query_roles(deps, address)?),
+        QueryMsg::TopHolders { limit } => to_binary(&query_holders_by_balance(deps, None, None, limit)?),
+        QueryMsg::HoldersByBalance {
+            min_balance,
+            start_after,
+            limit,
+        } => to_binary(&query_holders_by_balance(deps, min_balance, start_after, limit)?),
+        QueryMsg::ExportState {
+            section,
+            start_after,
+            limit,
+        } => to_binary(&query_export_state(deps, section, start_after, limit)?),
+        QueryMsg::DenomMetadata {} => to_binary(&query_denom_metadata(deps, env)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_swap_fee_config(deps: Deps) -> StdResult<SwapFeeConfigResponse> {
+    let swap_fee_config = SWAP_FEE_CONFIG.may_load(deps.storage)?;
+    match swap_fee_config {
+        Some(swap_fee_config) => {
+            Ok(SwapFeeConfigResponse {
+                fee_admin: swap_fee_config.fee_admin.to_string(),
+                enable_swap_fee: swap_fee_config.enable_swap_fee,
+                swap_percent_fee: swap_fee_config.swap_percent_fee,
+                fee_receiver: swap_fee_config.fee_receiver.to_string(),
+            })
+        }
+        None => Ok(Default::default())
+    }
+}
+
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(BalanceResponse { balance: balance_at(deps.storage, &address, height)? })
+}
+
+fn balance_at(storage: &dyn Storage, address: &Addr, height: u64) -> StdResult<Uint128> {
+    // Holders untouched since the snapshots were introduced still have the same balance
+    match BALANCE_SNAPSHOTS.may_load(storage, address)? {
+        Some(_) => Ok(BALANCE_SNAPSHOTS.may_load_at_height(storage, address, height)?.unwrap_or_default()),
+        None => Ok(BALANCES.may_load(storage, address)?.unwrap_or_default()),
+    }
+}
+
+fn total_supply_at(storage: &dyn Storage, height: u64) -> StdResult<Uint128> {
+    // The supply is unchanged since the snapshots were introduced until it is snapshotted
+    match TOTAL_SUPPLY_SNAPSHOTS.may_load(storage)? {
+        Some(_) => Ok(TOTAL_SUPPLY_SNAPSHOTS.may_load_at_height(storage, height)?.unwrap_or_default()),
+        None => Ok(TOKEN_INFO.load(storage)?.total_supply),
+    }
+}
+
+pub fn query_dividend(deps: Deps, snapshot_id: u64) -> StdResult<DividendResponse> {
+    let dividend = DIVIDENDS.load(deps.storage, U64Key::new(snapshot_id))?;
+    Ok(DividendResponse {
+        denom: dividend.denom,
+        amount: dividend.amount,
+        height: dividend.height,
+        total_supply: dividend.total_supply,
+        claimed: dividend.claimed,
+        claim_deadline: dividend.claim_deadline,
+        swept: dividend.swept,
+    })
+}
+
+pub fn query_dividend_claim(
+    deps: Deps,
+    env: Env,
+    snapshot_id: u64,
+    address: String,
+) -> StdResult<DividendClaimResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let dividend = DIVIDENDS.load(deps.storage, U64Key::new(snapshot_id))?;
+    Ok(DividendClaimResponse {
+        amount: dividend_share(deps.storage, &env, &dividend, &address)?,
+        claimed: DIVIDEND_CLAIMS.has(deps.storage, (U64Key::new(snapshot_id), &address)),
+    })
+}
+
+pub fn query_roles(deps: Deps, address: String) -> StdResult<RolesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let roles = ROLE_NAMES
+        .iter()
+        .filter(|role| has_role(deps.storage, role, &address))
+        .map(|role| role.to_string())
+        .collect();
+
+    Ok(RolesResponse { roles })
+}
+
+/// The base unit is the IBC denom of the token and the display unit its lowercase symbol,
+/// so the amounts render the same in the wallets once transferred
+pub fn query_denom_metadata(deps: Deps, env: Env) -> StdResult<DenomMetadataResponse> {
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    let base = format!("cw20:{}", env.contract.address);
+    let display = token_info.symbol.to_lowercase();
+
+    Ok(DenomMetadataResponse {
+        description: DESCRIPTION.may_load(deps.storage)?.unwrap_or_default(),
+        denom_units: vec![
+            DenomUnit {
+                denom: base.clone(),
+                exponent: 0,
+                aliases: vec![],
+            },
+            DenomUnit {
+                denom: display.clone(),
+                exponent: token_info.decimals as u32,
+                aliases: vec![],
+            },
+        ],
+        base,
+        display,
+        name: token_info.name,
+        symbol: token_info.symbol,
+        decimals: token_info.decimals,
+    })
+}
+
+/// Reads the balance index downwards from the holder after `start_after`
+pub fn query_holders_by_balance(
+    deps: Deps,
+    min_balance: Option<Uint128>,
+    start_after: Option<HolderResponse>,
+    limit: Option<u32>,
+) -> StdResult<HoldersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = min_balance.map(|min_balance| {
+        Bound::inclusive((U128Key::new(min_balance.u128()), &Addr::unchecked("")).joined_key())
+    });
+    let max = start_after.map(|holder| {
+        let address = Addr::unchecked(holder.address);
+        Bound::exclusive((U128Key::new(holder.balance.u128()), &address).joined_key())
+    });
+
+    let holders: StdResult<Vec<HolderResponse>> = BALANCE_INDEX
+        .keys(deps.storage, min, max, Order::Descending)
+        .take(limit)
+        .map(|key| parse_balance_index_key(&key))
+        .collect();
+
+    Ok(HoldersResponse { holders: holders? })
+}
+
+/// The raw index key is the length prefixed big endian balance followed by the address
+fn parse_balance_index_key(key: &[u8]) -> StdResult<HolderResponse> {
+    if key.len() < 18 {
+        return Err(StdError::generic_err("Invalid balance index key"));
+    }
+
+    let mut balance = [0u8; 16];
+    balance.copy_from_slice(&key[2..18]);
+    Ok(HolderResponse {
+        address: String::from_utf8(key[18..].to_vec())?,
+        balance: Uint128::new(u128::from_be_bytes(balance)),
+    })
+}
+
+pub fn query_export_state(
+    deps: Deps,
+    section: StateSection,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<ExportStateResponse> {
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries: Vec<(Vec<u8>, StateEntry)> = match section {
+        StateSection::Balances => BALANCES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, amount) = item?;
+                let address = String::from_utf8(key.clone())?;
+                Ok((key, StateEntry::Balance { address, amount }))
+            })
+            .collect::<StdResult<_>>()?,
+        StateSection::Allowances => ALLOWANCES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, allowance) = item?;
+                let (owner, spender) = split_pair_key(&key)?;
+                Ok((key, StateEntry::Allowance {
+                    owner,
+                    spender,
+                    allowance: allowance.allowance,
+                    expires: allowance.expires,
+                }))
+            })
+            .collect::<StdResult<_>>()?,
+        StateSection::Roles => ROLES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, _) = item?;
+                let (role, address) = split_pair_key(&key)?;
+                Ok((key, StateEntry::Role { role, address }))
+            })
+            .collect::<StdResult<_>>()?,
+    };
+
+    // A short page is the last one
+    let next_start_after = match entries.last() {
+        Some((key, _)) if entries.len() == limit => Some(Binary::from(key.as_slice())),
+        _ => None,
+    };
+
+    Ok(ExportStateResponse {
+        entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+        next_start_after,
+    })
+}
+
+/// Splits the raw key of a map keyed by a pair, the first part is length prefixed
+fn split_pair_key(key: &[u8]) -> StdResult<(String, String)> {
+    if key.len() < 2 {
+        return Err(StdError::generic_err("Invalid pair key"));
+    }
+    let len = u16::from_be_bytes([key[0], key[1]]) as usize;
+    if key.len() < 2 + len {
+        return Err(StdError::generic_err("Invalid pair key"));
+    }
+
+    let first = String::from_utf8(key[2..2 + len].to_vec())?;
+    let second = String::from_utf8(key[2 + len..].to_vec())?;
+    Ok((first, second))
+}
+
+pub fn query_all_allowances(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    exclude_expired: Option<bool>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let exclude_expired = exclude_expired.unwrap_or(false);
+
+    let allowances: StdResult<Vec<AllowanceInfo>> = ALLOWANCES
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, allowance)) => !exclude_expired || !allowance.expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (key, allowance) = item?;
+            Ok(AllowanceInfo {
+                spender: String::from_utf8(key)?,
+                allowance: allowance.allowance,
+                expires: allowance.expires,
+            })
+        })
+        .collect();
+
+    Ok(AllAllowancesResponse { allowances: allowances? })
+}
+
+pub fn query_pause_info(deps: Deps) -> StdResult<PauseInfoResponse> {
+    let pause_config = PAUSE_CONFIG.may_load(deps.storage)?;
+    match pause_config {
+        Some(pause_config) => {
+            Ok(PauseInfoResponse {
+                pauser: pause_config.pauser.map(|p| p.to_string()),
+                paused: pause_config.paused,
+            })
+        }
+        None => Ok(Default::default())
+    }
+}
+
+pub fn query_scheduled_swap(deps: Deps, swap_id: u64) -> StdResult<ScheduledSwapResponse> {
+    let swap = SCHEDULED_SWAPS.load(deps.storage, U64Key::new(swap_id))?;
+    Ok(ScheduledSwapResponse {
+        owner: swap.owner.to_string(),
+        pair: swap.pair.to_string(),
+        remaining: swap.remaining,
+        swaps_left: swap.swaps_left,
+        next_swap_time: swap.next_swap_time,
+        belief_price: swap.belief_price,
+        max_spread: swap.max_spread,
+    })
+}
+
+pub fn query_protocol_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ProtocolContractsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let contracts: StdResult<Vec<String>> = PROTOCOL_CONTRACTS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect();
+
+    Ok(ProtocolContractsResponse { contracts: contracts? })
+}
+
+pub fn query_send_cap(deps: Deps, env: Env, contract: String) -> StdResult<SendCapResponse> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let buckets = send_cap_buckets(deps.storage, &contract, env.block.time.seconds())?;
+    Ok(SendCapResponse {
+        cap: SEND_CAPS.may_load(deps.storage, &contract)?,
+        sent: sent_within_window(&buckets)?,
+    })
+}
+
+/// The buckets of the destination still overlapping the window ending now
+fn send_cap_buckets(storage: &dyn Storage, contract: &Addr, now: u64) -> StdResult<Vec<(u64, Uint128)>> {
+    let mut buckets = SEND_CAP_BUCKETS.may_load(storage, contract)?.unwrap_or_default();
+    buckets.retain(|(start, _)| start + SEND_CAP_BUCKET > now.saturating_sub(SEND_CAP_WINDOW));
+    Ok(buckets)
+}
+
+/// The total amount of the buckets
+fn sent_within_window(buckets: &[(u64, Uint128)]) -> StdResult<Uint128> {
+    buckets.iter().try_fold(Uint128::zero(), |sent, (_, amount)| Ok(sent.checked_add(*amount)?))
+}
+
+/// Accounts the amount sent to a capped destination, failing if the amount sent within the
+/// window would exceed the cap
+fn record_capped_send(storage: &mut dyn Storage, contract: &Addr, amount: Uint128, now: u64) -> StdResult<()> {
+    let cap = match SEND_CAPS.may_load(storage, contract)? {
+        Some(cap) => cap,
+        None => return Ok(()),
+    };
+
+    let mut buckets = send_cap_buckets(storage, contract, now)?;
+    if sent_within_window(&buckets)?.checked_add(amount)? > cap {
+        return Err(StdError::generic_err("Send cap of the destination exceeded"));
+    }
+
+    let bucket_start = now - now % SEND_CAP_BUCKET;
+    match buckets.last_mut() {
+        Some((start, bucket_amount)) if *start == bucket_start => *bucket_amount = bucket_amount.checked_add(amount)?,
+        _ => buckets.push((bucket_start, amount)),
+    }
+    SEND_CAP_BUCKETS.save(storage, contract, &buckets)
+}
+
+/// Reports the restrictions the execute wrapper enforces on the address
+pub fn query_is_blocked(deps: Deps, env: Env, address: String) -> StdResult<IsBlockedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut reasons = vec![];
+    if is_paused(deps.storage)? {
+        reasons.push(BlockReason::Paused);
+    }
+    if launch_unlock_time(deps.storage, &env)?.is_some()
+        && address != env.contract.address
+        && !LAUNCH_ALLOWLIST.has(deps.storage, &address) {
+        reasons.push(BlockReason::LaunchAllowlist);
+    }
+    let send_restricted = SEND_STRICT_MODE.may_load(deps.storage)?.unwrap_or(false)
+        && !SEND_ALLOWLIST.has(deps.storage, &address);
+    Ok(IsBlockedResponse { blocked: !reasons.is_empty(), reasons, send_restricted })
+}
+
+pub fn query_transfer_restrictions(deps: Deps, env: Env) -> StdResult<TransferRestrictionsResponse> {
+    let swap_fee_config = query_swap_fee_config(deps)?;
+    Ok(TransferRestrictionsResponse {
+        paused: is_paused(deps.storage)?,
+        enable_swap_fee: swap_fee_config.enable_swap_fee,
+        swap_percent_fee: swap_fee_config.swap_percent_fee,
+        fee_discount: query_fee_discount_config(deps)?.discount,
+        launch_unlock_time: launch_unlock_time(deps.storage, &env)?,
+        send_strict_mode: SEND_STRICT_MODE.may_load(deps.storage)?.unwrap_or(false),
+        send_caps: SEND_CAPS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (contract, cap) = item?;
+                Ok(DestinationSendCap { contract: String::from_utf8(contract)?, cap })
+            })
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+pub fn query_launch_mode(deps: Deps, env: Env) -> StdResult<LaunchModeResponse> {
+    Ok(LaunchModeResponse {
+        unlock_time: LAUNCH_UNLOCK_TIME.may_load(deps.storage)?,
+        active: launch_unlock_time(deps.storage, &env)?.is_some(),
+    })
+}
+
+pub fn query_launch_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<LaunchAllowlistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let addresses: StdResult<Vec<String>> = LAUNCH_ALLOWLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect();
+
+    Ok(LaunchAllowlistResponse { addresses: addresses? })
+}
+
+pub fn query_send_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SendAllowlistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let contracts: StdResult<Vec<String>> = SEND_ALLOWLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect();
+
+    Ok(SendAllowlistResponse { contracts: contracts? })
+}
+
+pub fn query_admin_recovery(deps: Deps) -> StdResult<AdminRecoveryResponse> {
+    match ADMIN_RECOVERY.may_load(deps.storage)? {
+        Some(admin_recovery) => Ok(AdminRecoveryResponse {
+            recovery: Some(admin_recovery.recovery.to_string()),
+            window: admin_recovery.window,
+            last_heartbeat: admin_recovery.last_heartbeat,
+            claimable_after: Some(admin_recovery.claimable_after()?),
+        }),
+        None => Ok(Default::default()),
+    }
+}
+
+pub fn query_fee_discount_config(deps: Deps) -> StdResult<FeeDiscountConfigResponse> {
+    let config = FEE_DISCOUNT_CONFIG.may_load(deps.storage)?;
+    match config {
+        Some(config) => {
+            Ok(FeeDiscountConfigResponse {
+                collection: Some(config.collection.to_string()),
+                discount: config.discount,
+            })
+        }
+        None => Ok(Default::default())
+    }
+}
+
+pub fn query_fee_discount(deps: Deps, address: String) -> StdResult<FeeDiscountResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(FeeDiscountResponse {
+        token_id: FEE_DISCOUNTS.may_load(deps.storage, &address)?.map(|d| d.token_id),
+        discount: fee_discount(deps.storage, &address)?,
+    })
+}
+
+/// The share of the swap fee waived for the payer, zero without a badge of the configured collection
+fn fee_discount(storage: &dyn Storage, payer: &Addr) -> StdResult<Decimal> {
+    let config = match FEE_DISCOUNT_CONFIG.may_load(storage)? {
+        Some(config) => config,
+        None => return Ok(Decimal::zero()),
+    };
+    match FEE_DISCOUNTS.may_load(storage, payer)? {
+        Some(deposit) if deposit.collection == config.collection => Ok(config.discount),
+        _ => Ok(Decimal::zero()),
+    }
+}
+
+fn apply_fee_discount(storage: &dyn Storage, payer: &Addr, fee_amount: Uint128) -> StdResult<Uint128> {
+    let discount = fee_discount(storage, payer)?;
+    Ok(fee_amount.checked_sub(fee_amount * discount)?)
+}
+
+pub fn query_rebate_epoch(deps: Deps, epoch: u64) -> StdResult<RebateEpochResponse> {
+    let rebate_epoch = REBATE_EPOCHS.may_load(deps.storage, U64Key::new(epoch))?.unwrap_or_default();
+    Ok(RebateEpochResponse {
+        rebate_share: REBATE_SHARE.may_load(deps.storage)?.unwrap_or_default(),
+        end_time: (epoch + 1) * REBATE_EPOCH_LENGTH,
+        fees: rebate_epoch.fees,
+        rebates: rebate_epoch.rebates,
+    })
+}
+
+pub fn query_rebate(deps: Deps, epoch: u64, address: String) -> StdResult<RebateResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(RebateResponse {
+        fees: TRADER_FEES.may_load(deps.storage, (U64Key::new(epoch), &address))?.unwrap_or_default(),
+        amount: rebate_amount(deps.storage, epoch, &address)?,
+    })
+}
+
+fn rebate_epoch(env: &Env) -> u64 {
+    env.block.time.seconds() / REBATE_EPOCH_LENGTH
+}
+
+/// The rebates of the epoch shared pro-rata to the swap fees paid by the trader
+fn rebate_amount(storage: &dyn Storage, epoch: u64, trader: &Addr) -> StdResult<Uint128> {
+    let fees = TRADER_FEES.may_load(storage, (U64Key::new(epoch), trader))?.unwrap_or_default();
+    if fees.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let rebate_epoch = REBATE_EPOCHS.load(storage, U64Key::new(epoch))?;
+    Ok(rebate_epoch.rebates.multiply_ratio(fees, rebate_epoch.fees))
+}
+
+/// Pays the swap fee of the trader to the fee receiver, except the rebated share which the token
+/// contract holds until the trader claims it once the epoch ended
+fn collect_swap_fee(
+    storage: &mut dyn Storage,
+    env: &Env,
+    payer: &Addr,
+    trader: &Addr,
+    fee_receiver: &Addr,
+    fee_amount: Uint128,
+) -> Result<(), ContractError> {
+    let rebate = fee_amount * REBATE_SHARE.may_load(storage)?.unwrap_or_default();
+    if !rebate.is_zero() {
+        if *payer != env.contract.address {
+            transfer(storage, payer, &env.contract.address, rebate)?;
+        }
+        let epoch = rebate_epoch(env);
+        REBATE_EPOCHS.update(storage, U64Key::new(epoch), |rebate_epoch| -> StdResult<_> {
+            let mut rebate_epoch: RebateEpoch = rebate_epoch.unwrap_or_default();
+            rebate_epoch.fees = rebate_epoch.fees.checked_add(fee_amount)?;
+            rebate_epoch.rebates = rebate_epoch.rebates.checked_add(rebate)?;
+            Ok(rebate_epoch)
+        })?;
+        TRADER_FEES.update(storage, (U64Key::new(epoch), trader), |fees| -> StdResult<_> {
+            Ok(fees.unwrap_or_default().checked_add(fee_amount)?)
+        })?;
+    }
+
+    let fee_amount = fee_amount.checked_sub(rebate).map_err(StdError::from)?;
+    if !fee_amount.is_zero() {
+        transfer(storage, payer, fee_receiver, fee_amount)?;
+    }
+    Ok(())
+}
+
+/// The swap fee is a percentage of the sent amount, so it can't exceed 100
+fn validate_swap_percent_fee(swap_percent_fee: Decimal) -> StdResult<()> {
+    if swap_percent_fee > Decimal::percent(10_000) {
+        return Err(StdError::generic_err("Swap percent fee cannot exceed 100"));
+    }
+    Ok(())
+}
+
+/// Computes `amount * swap_percent_fee / 100` on 256 bits, so that no fee percent can overflow
+fn calculate_fee_amount(amount: Uint128, msg: &Binary, swap_fee_config: &SwapFeeConfig) -> StdResult<Uint128> {
+    if !swap_fee_config.enable_swap_fee || !is_swap_message(msg.clone()) {
+        return Ok(Uint128::zero());
+    }
+
+    let percent_fee = swap_fee_config.swap_percent_fee;
+    let fee_amount = amount
+        .full_mul(percent_fee.numerator())
+        .checked_div(Uint256::from(percent_fee.denominator()))?
+        .checked_div(Uint256::from(100u128))?;
+    Uint128::try_from(fee_amount).map_err(|err| StdError::generic_err(err.to_string()))
 }
 
 fn is_swap_message(msg: Binary) -> bool {
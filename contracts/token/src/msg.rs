@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Binary, Decimal, StdError, StdResult, Uint128};
 use cw20::{Cw20Coin, Expiration, MinterResponse};
+use cw721::Cw721ReceiveMsg;
 
 /// This structure describes the parameters used for creating a token contract.
 /// TokenContract InstantiateMsg
@@ -20,6 +21,8 @@ pub struct InstantiateMsg {
     pub mint: Option<MinterResponse>,
     /// Swap fee configuration
     pub swap_fee_config: Option<SwapFeeConfigResponse>,
+    /// The address who can pause the token transfers
+    pub pauser: Option<String>,
 }
 
 /// This structure describes a migration message.
@@ -138,7 +141,103 @@ pub enum ExecuteMsg {
         swap_percent_fee: Option<Decimal>,
         /// The address who receives all fee amounts
         fee_receiver: Option<String>,
-    }
+    },
+    /// Only the fee admin can set the pauser
+    UpdatePauser { pauser: Option<String> },
+    /// Only the pauser can stop the transfers, sends, burns and mints
+    Pause {},
+    /// Only the pauser can resume the paused token
+    Unpause {},
+    /// Only the fee admin can grant the fee_admin, pauser and minter_admin roles
+    GrantRole { role: String, address: String },
+    /// Only the fee admin can revoke a role
+    RevokeRole { role: String, address: String },
+    /// Only a minter admin can change the minter, the cap is kept
+    UpdateMinter { minter: String },
+    /// Indexes the balances held before the balance index, from where the previous call
+    /// stopped. Anyone can execute it.
+    IndexBalances { limit: Option<u32> },
+    /// Removes up to `limit` expired allowances of the owner. Anyone can execute it.
+    PruneExpiredAllowances { owner: String, limit: Option<u32> },
+    /// Escrows the tokens of the sender to swap them on the pair in even parts, one part per hour.
+    /// The swaps pay the swap fee and the swapped assets go to the sender. Each swap passes the
+    /// belief price and max spread to the pair, failing when the pool moved past them.
+    ScheduleSwap {
+        pair: String,
+        total_amount: Uint128,
+        intervals: u32,
+        belief_price: Decimal,
+        max_spread: Option<Decimal>,
+    },
+    /// Executes the next due swap of a scheduled swap. Anyone can execute it.
+    ExecuteScheduledSwap { swap_id: u64 },
+    /// Returns the tokens not swapped yet to the owner of the scheduled swap. Only the owner
+    /// can execute it.
+    CancelScheduledSwap { swap_id: u64 },
+    /// Only the fee admin can deposit the native coin sent along as a dividend of the holders
+    /// at the start of the snapshot height
+    DepositDividend { height: u64, claim_deadline: u64 },
+    /// Sends the share of the dividend of the sender, pro-rata to its balance at the snapshot
+    /// height. The tokens escrowed by the token contract have no share.
+    ClaimDividend { snapshot_id: u64 },
+    /// Sends the unclaimed part of the dividend to the fee receiver once the claim deadline has
+    /// passed. Anyone can execute it.
+    SweepDividend { snapshot_id: u64 },
+    /// Only the fee admin can add and remove the protocol contracts, whose sends to each other
+    /// pay no swap fee and ignore the send caps
+    UpdateProtocolContracts { add: Vec<String>, remove: Vec<String> },
+    /// Only the fee admin can cap the amount sent to a destination contract within 24 hours,
+    /// or remove the cap
+    UpdateSendCap { contract: String, cap: Option<Uint128> },
+    /// Only the fee admin can clear the amount accounted against the cap of a destination
+    ResetSendCapWindow { contract: String },
+    /// Only the fee admin can restrict the receivers of tokens to the launch allowlist until the
+    /// unlock time. Once set, the unlock time can only be brought forward and the restriction
+    /// cannot be set up again after it lifted.
+    UpdateLaunchMode { unlock_time: u64 },
+    /// Only the fee admin can add or remove addresses of the launch allowlist
+    UpdateLaunchAllowlist { add: Vec<String>, remove: Vec<String> },
+    /// Restricts the destinations of the sends to the send allowlist while enabled, to stop the
+    /// sends to a malicious receiver during an incident. The fee admin and the pausers can
+    /// enable it, only the fee admin can disable it.
+    UpdateSendStrictMode { enabled: bool },
+    /// Only the fee admin can add or remove contracts of the send allowlist
+    UpdateSendAllowlist { add: Vec<String>, remove: Vec<String> },
+    /// Only the fee admin can set the address claiming the fee admin once no heartbeat was sent
+    /// for the window, or remove it. Counts as a heartbeat.
+    UpdateAdminRecovery { recovery: Option<String>, window: u64 },
+    /// Proves the fee admin key is still in use, the fee admins send it periodically
+    Heartbeat {},
+    /// Makes the recovery address the fee admin once the heartbeats stopped for the window.
+    /// The fee admin role holders keep their role until the new fee admin revokes it.
+    ClaimFeeAdmin {},
+    /// Only the fee admin can set the badge collection and the swap fee discount of its holders
+    UpdateFeeDiscountConfig { collection: String, discount: Decimal },
+    /// Receives a badge deposited for a swap fee discount, see [`Cw721HookMsg`]
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Returns the badge deposited by the sender, ending the discount
+    WithdrawFeeDiscountNft {},
+    /// Only the fee admin can set the share of the swap fees rebated to the traders who paid them
+    UpdateRebateShare { share: Decimal },
+    /// Sends the sender its rebate of an ended epoch, pro-rata to the swap fees it paid
+    /// during the epoch
+    ClaimRebate { epoch: u64 },
+    /// Only the fee admin can set the description returned with the denom metadata
+    UpdateDescription { description: String },
+    /// Writes a batch of exported entries of the section, to rehearse or complete a migration.
+    /// Only the fee admin can import, while the token is paused.
+    ImportState {
+        section: StateSection,
+        batch: Vec<StateEntry>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    /// Deposits the badge, reducing the swap fee of the sender until it is withdrawn.
+    /// A holder can deposit one badge.
+    DepositFeeDiscount {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -165,6 +264,8 @@ pub enum QueryMsg {
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        /// Leaves out the expired allowances, false by default
+        exclude_expired: Option<bool>,
     },
     /// Only with "enumerable" extension
     /// Returns all accounts that have balances. Supports pagination.
@@ -174,7 +275,104 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     /// Returns swap fee configuration
-    SwapFeeConfig {}
+    SwapFeeConfig {},
+    /// Returns the pauser and whether the token is paused
+    PauseInfo {},
+    /// Returns the scheduled swap, removed once all its swaps are executed.
+    /// Return type: ScheduledSwapResponse.
+    ScheduledSwap { swap_id: u64 },
+    /// Returns the dividend deposited against the snapshot.
+    /// Return type: DividendResponse.
+    Dividend { snapshot_id: u64 },
+    /// Returns the share of the dividend of the given address.
+    /// Return type: DividendClaimResponse.
+    DividendClaim { snapshot_id: u64, address: String },
+    /// Returns the protocol contracts. Supports pagination.
+    /// Return type: ProtocolContractsResponse.
+    ProtocolContracts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the cap of the destination contract and the amount sent to it within 24 hours.
+    /// Return type: SendCapResponse.
+    SendCap { contract: String },
+    /// Returns the launch unlock time and whether the receivers are still restricted.
+    /// Return type: LaunchModeResponse.
+    LaunchMode {},
+    /// Returns the addresses receiving tokens before the launch unlock time. Supports pagination.
+    /// Return type: LaunchAllowlistResponse.
+    LaunchAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether the sends only target the send allowlist.
+    /// Return type: SendStrictModeResponse.
+    SendStrictMode {},
+    /// Returns the contracts receiving sends in strict mode. Supports pagination.
+    /// Return type: SendAllowlistResponse.
+    SendAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the recovery address of the fee admin and the time it can claim it.
+    /// Return type: AdminRecoveryResponse.
+    AdminRecovery {},
+    /// Returns the badge collection and the swap fee discount of its holders.
+    /// Return type: FeeDiscountConfigResponse.
+    FeeDiscountConfig {},
+    /// Returns the badge deposited by the given address and the discount it grants.
+    /// Return type: FeeDiscountResponse.
+    FeeDiscount { address: String },
+    /// Returns the swap fees paid and the rebates set aside during the epoch.
+    /// Return type: RebateEpochResponse.
+    RebateEpoch { epoch: u64 },
+    /// Returns the swap fees paid by the given address during the epoch and its rebate so far.
+    /// Return type: RebateResponse.
+    Rebate { epoch: u64, address: String },
+    /// Returns whether the token is paused, see the pausable interface
+    Paused {},
+    /// Returns whether the token transfers involving the given address are rejected, with the
+    /// restrictions causing it. The token has no per-address blocklist, the address is blocked
+    /// by the pause and the launch allowlist. The send strict mode only restricts the sends.
+    /// Return type: IsBlockedResponse.
+    IsBlocked { address: String },
+    /// Returns the restrictions applying to the transfers and sends in one response.
+    /// Return type: TransferRestrictionsResponse.
+    TransferRestrictions {},
+    /// Returns the balance of the given address at the start of the given height.
+    /// Return type: BalanceResponse.
+    BalanceAt { address: String, height: u64 },
+    /// Returns the total supply at the start of the given height.
+    /// Return type: TotalSupplyResponse.
+    TotalSupplyAt { height: u64 },
+    /// Returns the roles granted to the given address.
+    /// Return type: RolesResponse.
+    Roles { address: String },
+    /// Returns the largest holders, largest first.
+    /// Return type: HoldersResponse.
+    TopHolders { limit: Option<u32> },
+    /// Returns the holders with at least the minimum balance, largest first. Supports pagination.
+    /// Return type: HoldersResponse.
+    HoldersByBalance {
+        min_balance: Option<Uint128>,
+        start_after: Option<HolderResponse>,
+        limit: Option<u32>,
+    },
+    /// Returns the entries of the section in storage order, starting after the key returned
+    /// with the previous page.
+    /// Return type: ExportStateResponse.
+    ExportState {
+        section: StateSection,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns the token metadata in the shape of the Cosmos bank denom metadata, for the
+    /// wallets and front-ends displaying the token.
+    /// Return type: DenomMetadataResponse.
+    DenomMetadata {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
 }
 
 #[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -184,3 +382,235 @@ pub struct SwapFeeConfigResponse {
     pub swap_percent_fee: Decimal,
     pub fee_receiver: String,
 }
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PauseInfoResponse {
+    pub pauser: Option<String>,
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ScheduledSwapResponse {
+    pub owner: String,
+    pub pair: String,
+    pub remaining: Uint128,
+    pub swaps_left: u32,
+    pub next_swap_time: u64,
+    pub belief_price: Decimal,
+    pub max_spread: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DividendResponse {
+    pub denom: String,
+    pub amount: Uint128,
+    pub height: u64,
+    pub total_supply: Uint128,
+    pub claimed: Uint128,
+    pub claim_deadline: u64,
+    pub swept: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DividendClaimResponse {
+    pub amount: Uint128,
+    pub claimed: bool,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProtocolContractsResponse {
+    pub contracts: Vec<String>,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SendCapResponse {
+    pub cap: Option<Uint128>,
+    /// The amount sent within the last 24 hours, accounted by the hour
+    pub sent: Uint128,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LaunchModeResponse {
+    pub unlock_time: Option<u64>,
+    /// Whether only the launch allowlist receives tokens
+    pub active: bool,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LaunchAllowlistResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SendStrictModeResponse {
+    pub enabled: bool,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SendAllowlistResponse {
+    pub contracts: Vec<String>,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AdminRecoveryResponse {
+    pub recovery: Option<String>,
+    pub window: u64,
+    pub last_heartbeat: u64,
+    /// The time after which the recovery address can claim the fee admin, none without recovery
+    pub claimable_after: Option<u64>,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeeDiscountConfigResponse {
+    pub collection: Option<String>,
+    pub discount: Decimal,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeeDiscountResponse {
+    pub token_id: Option<String>,
+    /// The share of the swap fee waived, zero if the badge is not of the configured collection
+    pub discount: Decimal,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RebateEpochResponse {
+    /// The share of the swap fees currently set aside for the rebates
+    pub rebate_share: Decimal,
+    /// The time after which the rebates of the epoch can be claimed
+    pub end_time: u64,
+    pub fees: Uint128,
+    pub rebates: Uint128,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RebateResponse {
+    pub fees: Uint128,
+    /// The rebate of the address, zero once claimed
+    pub amount: Uint128,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IsBlockedResponse {
+    pub blocked: bool,
+    pub reasons: Vec<BlockReason>,
+    /// Whether the sends to the address are rejected in strict mode, the transfers to it still
+    /// go through
+    pub send_restricted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockReason {
+    /// No transfer, send, burn nor mint goes through while paused
+    Paused,
+    /// The address cannot receive tokens until the launch unlock time
+    LaunchAllowlist,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TransferRestrictionsResponse {
+    /// No transfer, send, burn nor mint goes through while paused
+    pub paused: bool,
+    /// Whether the sends of a swap message pay the swap fee
+    pub enable_swap_fee: bool,
+    /// The percent of the sent amount paid as swap fee
+    pub swap_percent_fee: Decimal,
+    /// The share of the swap fee waived for the holders of a deposited badge
+    pub fee_discount: Decimal,
+    /// The time until which only the launch allowlist receives tokens, none once lifted
+    pub launch_unlock_time: Option<u64>,
+    /// Whether the sends only target the send allowlist
+    pub send_strict_mode: bool,
+    /// The destinations receiving a capped amount of sends within the rolling window
+    pub send_caps: Vec<DestinationSendCap>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DestinationSendCap {
+    pub contract: String,
+    pub cap: Uint128,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RolesResponse {
+    pub roles: Vec<String>,
+}
+
+/// A unit of the token, the amounts in the unit are the base amounts divided by 10^exponent
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DenomUnit {
+    pub denom: String,
+    pub exponent: u32,
+    pub aliases: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DenomMetadataResponse {
+    pub description: String,
+    /// The base unit first, then the display unit
+    pub denom_units: Vec<DenomUnit>,
+    /// The denom of the token once transferred over IBC, `cw20:<token address>`
+    pub base: String,
+    /// The lowercase symbol, the unit with `decimals` as exponent
+    pub display: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HolderResponse {
+    pub address: String,
+    pub balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HoldersResponse {
+    pub holders: Vec<HolderResponse>,
+}
+
+/// The parts of the state exported and imported along a migration
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StateSection {
+    Balances,
+    Allowances,
+    Roles,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StateEntry {
+    Balance {
+        address: String,
+        amount: Uint128,
+    },
+    Allowance {
+        owner: String,
+        spender: String,
+        allowance: Uint128,
+        expires: Expiration,
+    },
+    Role {
+        role: String,
+        address: String,
+    },
+}
+
+impl StateEntry {
+    pub fn section(&self) -> StateSection {
+        match self {
+            StateEntry::Balance { .. } => StateSection::Balances,
+            StateEntry::Allowance { .. } => StateSection::Allowances,
+            StateEntry::Role { .. } => StateSection::Roles,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportStateResponse {
+    pub entries: Vec<StateEntry>,
+    /// The start_after of the next page, unset once the section is exported
+    pub next_start_after: Option<Binary>,
+}
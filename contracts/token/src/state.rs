@@ -1,8 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Empty, OverflowError, OverflowOperation, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy, U128Key, U64Key};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct SwapFeeConfig {
@@ -16,3 +16,171 @@ pub struct SwapFeeConfig {
 }
 
 pub const SWAP_FEE_CONFIG: Item<SwapFeeConfig> = Item::new("swap_fee_config");
+
+/// The time in seconds between two swaps of a scheduled swap
+pub const SCHEDULED_SWAP_INTERVAL: u64 = 3_600;
+
+/// Tokens escrowed by the token contract itself and swapped on the pair in even parts over time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledSwap {
+    /// The owner of the escrowed tokens, receiving the swapped assets
+    pub owner: Addr,
+    pub pair: Addr,
+    /// The escrowed tokens not swapped yet
+    pub remaining: Uint128,
+    pub swaps_left: u32,
+    /// The time from which the next swap can be executed
+    pub next_swap_time: u64,
+    /// The price the swaps are protected by, anyone can execute them
+    pub belief_price: Decimal,
+    pub max_spread: Option<Decimal>,
+}
+
+pub const SCHEDULED_SWAP_COUNT: Item<u64> = Item::new("scheduled_swap_count");
+pub const SCHEDULED_SWAPS: Map<U64Key, ScheduledSwap> = Map::new("scheduled_swaps");
+
+/// The contracts of the protocol, sending between each other without swap fee nor send cap
+pub const PROTOCOL_CONTRACTS: Map<&Addr, Empty> = Map::new("protocol_contracts");
+
+/// The time until which only the launch allowlist receives tokens, unset if no launch was set up
+pub const LAUNCH_UNLOCK_TIME: Item<u64> = Item::new("launch_unlock_time");
+/// The addresses receiving tokens before the launch unlock time, such as the pairs, the
+/// launchpad and the treasury
+pub const LAUNCH_ALLOWLIST: Map<&Addr, Empty> = Map::new("launch_allowlist");
+
+/// The length in seconds of the rolling window of the send caps
+pub const SEND_CAP_WINDOW: u64 = 86_400;
+/// The sends are accounted against the caps in buckets of an hour
+pub const SEND_CAP_BUCKET: u64 = 3_600;
+
+/// The maximum amount sent to each capped destination contract within the window
+pub const SEND_CAPS: Map<&Addr, Uint128> = Map::new("send_caps");
+/// The amounts sent to each capped destination as (bucket start time, amount), oldest first
+pub const SEND_CAP_BUCKETS: Map<&Addr, Vec<(u64, Uint128)>> = Map::new("send_cap_buckets");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountConfig {
+    /// The badge collection whose NFTs can be deposited for a discount
+    pub collection: Addr,
+    /// The share of the swap fee waived for the depositors
+    pub discount: Decimal,
+}
+
+pub const FEE_DISCOUNT_CONFIG: Item<FeeDiscountConfig> = Item::new("fee_discount_config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscount {
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+/// The NFT deposited by each holder, the discount only applies while its collection is the
+/// configured one
+pub const FEE_DISCOUNTS: Map<&Addr, FeeDiscount> = Map::new("fee_discounts");
+
+/// Whether the sends only target the contracts of the send allowlist
+pub const SEND_STRICT_MODE: Item<bool> = Item::new("send_strict_mode");
+/// The contracts receiving sends in strict mode, e.g. the pairs, the marketplace and the staking
+pub const SEND_ALLOWLIST: Map<&Addr, Empty> = Map::new("send_allowlist");
+
+/// The length of the rebate epochs, which start at the unix epoch
+pub const REBATE_EPOCH_LENGTH: u64 = 7 * 86_400;
+
+/// The share of the swap fees set aside to be rebated to the traders who paid them
+pub const REBATE_SHARE: Item<Decimal> = Item::new("rebate_share");
+
+#[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RebateEpoch {
+    /// The swap fees paid during the epoch while a rebate share was set
+    pub fees: Uint128,
+    /// The part of these fees held by the token contract for the rebates
+    pub rebates: Uint128,
+}
+
+pub const REBATE_EPOCHS: Map<U64Key, RebateEpoch> = Map::new("rebate_epochs");
+/// The swap fees paid by each trader during an epoch, removed once the rebate is claimed
+pub const TRADER_FEES: Map<(U64Key, &Addr), Uint128> = Map::new("trader_fees");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminRecovery {
+    /// The address taking over the fee admin once the heartbeats stop, e.g. the governance
+    pub recovery: Addr,
+    /// The seconds without heartbeat after which the recovery address can claim the fee admin
+    pub window: u64,
+    pub last_heartbeat: u64,
+}
+
+impl AdminRecovery {
+    /// Returns the time after which the recovery address can claim the fee admin
+    pub fn claimable_after(&self) -> StdResult<u64> {
+        self.last_heartbeat.checked_add(self.window).ok_or_else(|| {
+            StdError::from(OverflowError::new(OverflowOperation::Add, self.last_heartbeat, self.window))
+        })
+    }
+}
+
+/// The longest heartbeat window, one year
+pub const MAX_RECOVERY_WINDOW: u64 = 365 * 86_400;
+pub const ADMIN_RECOVERY: Item<AdminRecovery> = Item::new("admin_recovery");
+
+/// The description of the token returned with the denom metadata
+pub const DESCRIPTION: Item<String> = Item::new("description");
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Shares the powers of the fee admin of the swap fee configuration, including managing the roles
+pub const FEE_ADMIN_ROLE: &str = "fee_admin";
+pub use luart_utils::pausable::PAUSER_ROLE;
+/// Changes the minter
+pub const MINTER_ADMIN_ROLE: &str = "minter_admin";
+pub const ROLE_NAMES: [&str; 3] = [FEE_ADMIN_ROLE, PAUSER_ROLE, MINTER_ADMIN_ROLE];
+
+/// The balances at every height, changed along the cw20 balances
+pub const BALANCE_SNAPSHOTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance_snapshots",
+    "balance_snapshots__checkpoints",
+    "balance_snapshots__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The total supply at every height, changed along the token info
+pub const TOTAL_SUPPLY_SNAPSHOTS: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply_snapshots",
+    "total_supply_snapshots__checkpoints",
+    "total_supply_snapshots__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Native coins shared by the holders pro-rata to their balance at the snapshot height
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Dividend {
+    pub denom: String,
+    pub amount: Uint128,
+    /// The balances and the total supply are read at the start of this height
+    pub height: u64,
+    /// The total supply at the height, less the balance of the token contract which never claims
+    pub total_supply: Uint128,
+    pub claimed: Uint128,
+    /// The time after which the dividend can't be claimed anymore and the rest can be swept
+    pub claim_deadline: u64,
+    pub swept: bool,
+}
+
+pub const DIVIDEND_COUNT: Item<u64> = Item::new("dividend_count");
+pub const DIVIDENDS: Map<U64Key, Dividend> = Map::new("dividends");
+pub const DIVIDEND_CLAIMS: Map<(U64Key, &Addr), Empty> = Map::new("dividend_claims");
+
+/// The holders by balance, so that the largest holders are read without iterating the balances
+pub const BALANCE_INDEX: Map<(U128Key, &Addr), Empty> = Map::new("balance_index");
+/// The last holder indexed by the backfill of the balances held before the index
+pub const BALANCE_INDEX_CURSOR: Item<Addr> = Item::new("balance_index_cursor");
+
+/// Moves the holder in the balance index, the holders without balance are not indexed
+pub fn index_balance(storage: &mut dyn Storage, holder: &Addr, previous: Uint128, current: Uint128) -> StdResult<()> {
+    if !previous.is_zero() {
+        BALANCE_INDEX.remove(storage, (U128Key::new(previous.u128()), holder));
+    }
+    if !current.is_zero() {
+        BALANCE_INDEX.save(storage, (U128Key::new(current.u128()), holder), &Empty {})?;
+    }
+    Ok(())
+}
@@ -1 +1,2 @@
+pub mod prop_tests;
 pub mod tests;
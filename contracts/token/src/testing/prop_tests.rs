@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Binary, Decimal, Deps, Fraction, from_binary, OwnedDeps, to_binary, Uint128};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cw20::{BalanceResponse, Cw20Coin, TokenInfoResponse};
+use cw20_base::ContractError;
+use proptest::prelude::*;
+use terraswap::pair::Cw20HookMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SwapFeeConfigResponse};
+
+const OWNER: &str = "mock_owner";
+const SPENDER: &str = "mock_spender";
+const FEE_RECEIVER: &str = "mock_fee_receiver";
+const DEX: &str = "dex_contract";
+/// The denominator of the decimals, the fee percents are drawn as numerators over it
+const FRACTION: u128 = 1_000_000_000_000_000_000;
+
+type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+fn instantiate_with(balance: u128, swap_percent_fee: Decimal) -> MockDeps {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        name: "name".to_string(),
+        symbol: "symbol".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: OWNER.to_string(), amount: Uint128::new(balance) }],
+        mint: None,
+        swap_fee_config: Some(SwapFeeConfigResponse {
+            fee_admin: OWNER.to_string(),
+            enable_swap_fee: true,
+            swap_percent_fee,
+            fee_receiver: FEE_RECEIVER.to_string(),
+        }),
+        pauser: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+    deps
+}
+
+fn swap_msg() -> Binary {
+    to_binary(&Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap()
+}
+
+fn balance(deps: Deps, address: &str) -> u128 {
+    let res = query(deps, mock_env(), QueryMsg::Balance { address: address.to_string() }).unwrap();
+    from_binary::<BalanceResponse>(&res).unwrap().balance.u128()
+}
+
+/// The balances of every account the tests touch add up to the total supply
+fn assert_supply_conserved(deps: Deps, total_supply: u128) -> Result<(), TestCaseError> {
+    let res = query(deps, mock_env(), QueryMsg::TokenInfo {}).unwrap();
+    let token_info: TokenInfoResponse = from_binary(&res).unwrap();
+    prop_assert_eq!(token_info.total_supply.u128(), total_supply);
+
+    let held = [OWNER, SPENDER, FEE_RECEIVER, DEX].iter()
+        .map(|address| balance(deps, address))
+        .try_fold(0u128, |sum, balance| sum.checked_add(balance));
+    prop_assert_eq!(held, Some(total_supply));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn send_fee_plus_net_is_gross(amount in 1..=u128::MAX, percent in 0..=100 * FRACTION) {
+        let percent_fee = Decimal::from_ratio(percent, FRACTION);
+        let mut deps = instantiate_with(amount, percent_fee);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), ExecuteMsg::Send {
+            contract: DEX.to_string(),
+            amount: Uint128::new(amount),
+            msg: swap_msg(),
+        });
+        match res {
+            // A fee taking the whole amount leaves nothing to send
+            Err(err) => prop_assert_eq!(err, ContractError::InvalidZeroAmount {}),
+            Ok(_) => {
+                let fee = balance(deps.as_ref(), FEE_RECEIVER);
+                prop_assert_eq!(fee + balance(deps.as_ref(), DEX), amount);
+
+                // The fee is the percent of the amount rounded down
+                let exact_fee = Uint128::new(amount).full_mul(percent_fee.numerator());
+                prop_assert!(Uint128::new(fee).full_mul(100 * FRACTION) <= exact_fee);
+                prop_assert!(Uint128::new(fee + 1).full_mul(100 * FRACTION) > exact_fee);
+            }
+        }
+        assert_supply_conserved(deps.as_ref(), amount)?;
+    }
+
+    #[test]
+    fn send_from_conserves_supply(
+        balance_amount in 1..=u128::MAX,
+        amount_seed in 0..=u128::MAX,
+        percent in 0..=100 * FRACTION,
+    ) {
+        let amount = 1 + amount_seed % balance_amount;
+        let mut deps = instantiate_with(balance_amount, Decimal::from_ratio(percent, FRACTION));
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), ExecuteMsg::IncreaseAllowance {
+            spender: SPENDER.to_string(),
+            amount: Uint128::new(amount),
+            expires: None,
+        }).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(SPENDER, &[]), ExecuteMsg::SendFrom {
+            owner: OWNER.to_string(),
+            contract: DEX.to_string(),
+            amount: Uint128::new(amount),
+            msg: swap_msg(),
+        });
+        if res.is_ok() {
+            let fee = balance(deps.as_ref(), FEE_RECEIVER);
+            prop_assert_eq!(fee + balance(deps.as_ref(), DEX), amount);
+            prop_assert_eq!(balance(deps.as_ref(), OWNER), balance_amount - amount);
+        }
+        assert_supply_conserved(deps.as_ref(), balance_amount)?;
+    }
+
+    #[test]
+    fn arbitrary_hook_msg_does_not_panic(msg in collection::vec(any::<u8>(), 0..256), amount in 1..=u128::MAX) {
+        let mut deps = instantiate_with(amount, Decimal::from_str("10").unwrap());
+
+        // Only swap messages pay the fee, anything else is forwarded whole
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), ExecuteMsg::Send {
+            contract: DEX.to_string(),
+            amount: Uint128::new(amount),
+            msg: Binary(msg),
+        });
+        prop_assert!(res.is_ok());
+        prop_assert_eq!(balance(deps.as_ref(), FEE_RECEIVER) + balance(deps.as_ref(), DEX), amount);
+        assert_supply_conserved(deps.as_ref(), amount)?;
+    }
+}
@@ -1,13 +1,32 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{Decimal, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128};
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cw20::{BalanceResponse, Cw20Coin, Cw20ReceiveMsg};
+use cosmwasm_std::{
+    Addr, attr, BankMsg, Binary, coins, ContractResult, CosmosMsg, Decimal, Deps, DepsMut, Env, from_binary, Reply,
+    Response, StdError, StdResult, SubMsg, SubMsgExecutionResponse, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, mock_dependencies, mock_env, mock_info};
+use cw20::{AllAllowancesResponse, BalanceResponse, Cw20Coin, Cw20ReceiveMsg, Expiration, MinterResponse, TokenInfoResponse};
 use cw20_base::ContractError;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use luart_protocol::pausable::{PausedResponse, QueryMsg as PausableQueryMsg};
+use luart_protocol::token::{
+    ExecuteMsg as TokenExecuteMsg, InstantiateMsg as TokenInstantiateMsg, QueryMsg as TokenQueryMsg,
+    SwapFeeConfig, TotalSupplyResponse,
+};
+use luart_utils::batch::BatchResponse;
 use terraswap::pair::Cw20HookMsg;
 
-use crate::contract::{execute, instantiate, query};
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SwapFeeConfigResponse};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{
+    AdminRecoveryResponse, BlockReason, Cw721HookMsg, DenomMetadataResponse, DenomUnit, DestinationSendCap,
+    DividendClaimResponse, ExecuteMsg, ExportStateResponse, FeeDiscountResponse, HolderResponse, HoldersResponse,
+    InstantiateMsg, IsBlockedResponse, LaunchAllowlistResponse, LaunchModeResponse, PauseInfoResponse,
+    ProtocolContractsResponse, QueryMsg, RebateEpochResponse, RebateResponse, RolesResponse, ScheduledSwapResponse,
+    SendAllowlistResponse, SendCapResponse, SendStrictModeResponse, StateEntry, StateSection, SwapFeeConfigResponse,
+    TransferRestrictionsResponse,
+};
+use crate::state::{index_balance, REBATE_EPOCH_LENGTH};
 
 const OWNER: &str = "mock_owner";
 const SENDER: &str = "mock_sender";
@@ -32,6 +51,7 @@ fn get_default_instantiate_msg() -> InstantiateMsg {
             swap_percent_fee: Decimal::from_str("10").unwrap(),
             fee_receiver: FEE_RECEIVER.to_string(),
         }),
+        pauser: None,
     }
 }
 
@@ -130,6 +150,39 @@ fn test_send() {
     assert_eq!(balance.balance, Uint128::new(1_000_000));
 }
 
+#[test]
+fn test_swap_fee_bounds() {
+    let swap_msg = to_binary(&Cw20HookMsg::Swap {
+        belief_price: None,
+        max_spread: None,
+        to: None,
+    }).unwrap();
+
+    // A fee of 100 percent leaves nothing to send
+    let mut deps = mock_dependencies(&[]);
+    let mut msg = get_default_instantiate_msg();
+    msg.initial_balances[0].amount = Uint128::MAX;
+    msg.swap_fee_config.as_mut().unwrap().swap_percent_fee = Decimal::percent(10_000);
+    instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), ExecuteMsg::Send {
+        contract: "dex_contract".to_string(),
+        amount: Uint128::new(u128::MAX),
+        msg: swap_msg,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    // The fee percent is capped at 100
+    let mut deps = mock_dependencies(&[]);
+    default_instantiate(deps.as_mut(), mock_env());
+    let err = execute(deps.as_mut(), mock_env(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::UpdateSwapFeeConfig {
+        fee_admin: None,
+        enable_swap_fee: None,
+        swap_percent_fee: Some(Decimal::MAX),
+        fee_receiver: None,
+    }).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Swap percent fee cannot exceed 100")));
+}
+
 #[test]
 fn test_send_from() {
     let mut deps = mock_dependencies(&[]);
@@ -176,3 +229,976 @@ fn test_send_from() {
     assert_eq!(balance.balance, Uint128::new(1_000_000));
 }
 
+
+#[test]
+fn test_pause() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Only the fee admin sets the pauser
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::UpdatePauser { pauser: Some(OWNER.to_string()) }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+            ExecuteMsg::UpdatePauser { pauser: Some("mock_pauser".to_string()) }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("mock_pauser", &[]), ExecuteMsg::Pause {}).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::PauseInfo {}).unwrap();
+    let pause_info: PauseInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(pause_info, PauseInfoResponse { pauser: Some("mock_pauser".to_string()), paused: true });
+    let res = query(deps.as_ref(), env.clone(), from_binary(&to_binary(&PausableQueryMsg::Paused {}).unwrap()).unwrap())
+        .unwrap();
+    assert_eq!(from_binary::<PausedResponse>(&res).unwrap(), PausedResponse { paused: true });
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::IsBlocked { address: OWNER.to_string() }).unwrap();
+    assert_eq!(from_binary::<IsBlockedResponse>(&res).unwrap(), IsBlockedResponse {
+        blocked: true,
+        reasons: vec![BlockReason::Paused],
+        send_restricted: false,
+    });
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::TransferRestrictions {}).unwrap();
+    assert_eq!(from_binary::<TransferRestrictionsResponse>(&res).unwrap(), TransferRestrictionsResponse {
+        paused: true,
+        enable_swap_fee: true,
+        swap_percent_fee: Decimal::from_str("10").unwrap(),
+        fee_discount: Decimal::zero(),
+        launch_unlock_time: None,
+        send_strict_mode: false,
+        send_caps: vec![],
+    });
+
+    // Transfers are stopped while allowances can still change
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(1) }).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Token transfers are paused")));
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::IncreaseAllowance {
+                spender: SENDER.to_string(),
+                amount: Uint128::new(1),
+                expires: None,
+            }).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info("mock_pauser", &[]), ExecuteMsg::Unpause {}).unwrap();
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+            ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(1) }).unwrap();
+}
+
+#[test]
+fn test_balance_at() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    let start_height = env.block.height;
+    default_instantiate(deps.as_mut(), env.clone());
+
+    env.block.height += 10;
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(100) }).unwrap();
+
+    let balance_at = |address: &str, height: u64| -> Uint128 {
+        let res = query(deps.as_ref(), env.clone(),
+                        QueryMsg::BalanceAt { address: address.to_string(), height }).unwrap();
+        let balance: BalanceResponse = from_binary(&res).unwrap();
+        balance.balance
+    };
+
+    // Balances are read at the start of the height
+    assert_eq!(balance_at(OWNER, start_height), Uint128::zero());
+    assert_eq!(balance_at(OWNER, start_height + 5), Uint128::new(1_000_000_000));
+    assert_eq!(balance_at(OWNER, start_height + 10), Uint128::new(1_000_000_000));
+    assert_eq!(balance_at(OWNER, start_height + 11), Uint128::new(999_999_900));
+    assert_eq!(balance_at(SENDER, start_height + 5), Uint128::zero());
+    assert_eq!(balance_at(SENDER, start_height + 11), Uint128::new(100));
+
+    // Burns change the total supply from the next height
+    env.block.height += 10;
+    execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]),
+            ExecuteMsg::Burn { amount: Uint128::new(40) }).unwrap();
+    let total_supply_at = |height: u64| -> Uint128 {
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::TotalSupplyAt { height }).unwrap();
+        let res: TotalSupplyResponse = from_binary(&res).unwrap();
+        res.total_supply
+    };
+    assert_eq!(total_supply_at(start_height + 20), Uint128::new(1_000_000_000));
+    assert_eq!(total_supply_at(start_height + 21), Uint128::new(999_999_960));
+}
+
+#[test]
+fn test_roles() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let mut msg = get_default_instantiate_msg();
+    msg.mint = Some(MinterResponse { minter: OWNER.to_string(), cap: None });
+    instantiate(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    // Only the fee admin manages the roles
+    let grant_msg = |role: &str, address: &str| ExecuteMsg::GrantRole {
+        role: role.to_string(),
+        address: address.to_string(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      grant_msg("pauser", "mock_guardian")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+                      grant_msg("owner", "mock_guardian")).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Unknown role: owner")));
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), grant_msg("pauser", "mock_guardian")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), grant_msg("fee_admin", "mock_dao")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]), grant_msg("minter_admin", "mock_dao")).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Roles { address: "mock_dao".to_string() }).unwrap();
+    let roles: RolesResponse = from_binary(&res).unwrap();
+    assert_eq!(roles.roles, vec!["fee_admin".to_string(), "minter_admin".to_string()]);
+
+    // Several addresses share a role
+    execute(deps.as_mut(), env.clone(), mock_info("mock_guardian", &[]), ExecuteMsg::Pause {}).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]), ExecuteMsg::RevokeRole {
+        role: "pauser".to_string(),
+        address: "mock_guardian".to_string(),
+    }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("mock_guardian", &[]), ExecuteMsg::Unpause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+                      ExecuteMsg::UpdateMinter { minter: "mock_dao".to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("mock_dao", &[]),
+            ExecuteMsg::UpdateMinter { minter: "mock_dao".to_string() }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Minter {}).unwrap();
+    let minter: Option<MinterResponse> = from_binary(&res).unwrap();
+    assert_eq!(minter, Some(MinterResponse { minter: "mock_dao".to_string(), cap: None }));
+}
+
+#[test]
+fn test_denom_metadata() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let mut msg = get_default_instantiate_msg();
+    msg.symbol = "LUART".to_string();
+    instantiate(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    // Only the fee admin sets the description
+    let update = ExecuteMsg::UpdateDescription { description: "The Luart protocol token".to_string() };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+                      ExecuteMsg::UpdateDescription { description: "x".repeat(1025) }).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Description cannot exceed 1024 bytes")));
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::DenomMetadata {}).unwrap();
+    let metadata: DenomMetadataResponse = from_binary(&res).unwrap();
+    let base = format!("cw20:{}", MOCK_CONTRACT_ADDR);
+    assert_eq!(metadata, DenomMetadataResponse {
+        description: "The Luart protocol token".to_string(),
+        denom_units: vec![
+            DenomUnit { denom: base.clone(), exponent: 0, aliases: vec![] },
+            DenomUnit { denom: "luart".to_string(), exponent: 6, aliases: vec![] },
+        ],
+        base,
+        display: "luart".to_string(),
+        name: "name".to_string(),
+        symbol: "LUART".to_string(),
+        decimals: 6,
+    });
+}
+
+#[test]
+fn test_export_import_state() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(1_000) }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::IncreaseAllowance {
+        spender: SENDER.to_string(),
+        amount: Uint128::new(500),
+        expires: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::GrantRole {
+        role: "pauser".to_string(),
+        address: "mock_guardian".to_string(),
+    }).unwrap();
+
+    // The sections are exported page by page
+    let export = |deps: Deps, section: StateSection| -> Vec<StateEntry> {
+        let mut entries = vec![];
+        let mut start_after = None;
+        loop {
+            let res = query(deps, mock_env(), QueryMsg::ExportState { section, start_after, limit: Some(1) })
+                .unwrap();
+            let page: ExportStateResponse = from_binary(&res).unwrap();
+            entries.extend(page.entries);
+            match page.next_start_after {
+                Some(next) => start_after = Some(next),
+                None => return entries,
+            }
+        }
+    };
+    let balances = export(deps.as_ref(), StateSection::Balances);
+    assert_eq!(balances, vec![
+        StateEntry::Balance { address: OWNER.to_string(), amount: Uint128::new(999_999_000) },
+        StateEntry::Balance { address: SENDER.to_string(), amount: Uint128::new(1_000) },
+    ]);
+    let allowances = export(deps.as_ref(), StateSection::Allowances);
+    assert_eq!(allowances, vec![StateEntry::Allowance {
+        owner: OWNER.to_string(),
+        spender: SENDER.to_string(),
+        allowance: Uint128::new(500),
+        expires: Expiration::Never {},
+    }]);
+    let roles = export(deps.as_ref(), StateSection::Roles);
+    assert_eq!(roles, vec![StateEntry::Role { role: "pauser".to_string(), address: "mock_guardian".to_string() }]);
+
+    // The new token imports the state while it is paused
+    let mut new_deps = mock_dependencies(&[]);
+    let mut msg = get_default_instantiate_msg();
+    msg.initial_balances = vec![];
+    msg.pauser = Some(FEE_ADMIN.to_string());
+    instantiate(new_deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+    // A balance held before the balance snapshots were introduced
+    BALANCES.save(new_deps.as_mut().storage, &Addr::unchecked(SENDER), &Uint128::new(5)).unwrap();
+    TOKEN_INFO.update(new_deps.as_mut().storage, |mut token_info| -> StdResult<_> {
+        token_info.total_supply += Uint128::new(5);
+        Ok(token_info)
+    }).unwrap();
+    let import = |section: StateSection, batch: &[StateEntry]| ExecuteMsg::ImportState {
+        section,
+        batch: batch.to_vec(),
+    };
+    let err = execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+                      import(StateSection::Balances, &balances)).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The token must be paused to import state")));
+    execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::Pause {}).unwrap();
+    let err = execute(new_deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      import(StateSection::Balances, &balances)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]),
+                      import(StateSection::Roles, &balances)).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The entry does not belong to the imported section")));
+
+    execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), import(StateSection::Balances, &balances))
+        .unwrap();
+    execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), import(StateSection::Allowances, &allowances))
+        .unwrap();
+    execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), import(StateSection::Roles, &roles)).unwrap();
+    assert_eq!(export(new_deps.as_ref(), StateSection::Balances), balances);
+    assert_eq!(export(new_deps.as_ref(), StateSection::Allowances), allowances);
+    assert_eq!(export(new_deps.as_ref(), StateSection::Roles), roles);
+
+    // The balances before the import height are the ones held before the import
+    let balance_at = |deps: Deps, height: u64| -> Uint128 {
+        let msg = QueryMsg::BalanceAt { address: SENDER.to_string(), height };
+        from_binary::<BalanceResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().balance
+    };
+    assert_eq!(balance_at(new_deps.as_ref(), env.block.height), Uint128::new(5));
+    assert_eq!(balance_at(new_deps.as_ref(), env.block.height + 1), Uint128::new(1_000));
+
+    // Importing a balance again replaces it in the total supply
+    execute(new_deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), import(StateSection::Balances, &balances[1..]))
+        .unwrap();
+    let res = query(new_deps.as_ref(), env, QueryMsg::TokenInfo {}).unwrap();
+    let token_info: TokenInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(token_info.total_supply, Uint128::new(1_000_000_000));
+}
+
+#[test]
+fn test_top_holders() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let transfer = |recipient: &str, amount: u128| ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount: Uint128::new(amount),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer(SENDER, 300)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer(FEE_ADMIN, 100)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer(FEE_RECEIVER, 200)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_RECEIVER, &[]), transfer(SENDER, 200)).unwrap();
+
+    let holder = |address: &str, balance: u128| HolderResponse {
+        address: address.to_string(),
+        balance: Uint128::new(balance),
+    };
+    let holders = |deps: Deps, msg: QueryMsg| -> Vec<HolderResponse> {
+        let res: HoldersResponse = from_binary(&query(deps, env.clone(), msg).unwrap()).unwrap();
+        res.holders
+    };
+
+    // The emptied balance leaves the index
+    assert_eq!(holders(deps.as_ref(), QueryMsg::TopHolders { limit: None }), vec![
+        holder(OWNER, 999_999_400),
+        holder(SENDER, 500),
+        holder(FEE_ADMIN, 100),
+    ]);
+    assert_eq!(holders(deps.as_ref(), QueryMsg::HoldersByBalance {
+        min_balance: Some(Uint128::new(100)),
+        start_after: Some(holder(SENDER, 500)),
+        limit: None,
+    }), vec![holder(FEE_ADMIN, 100)]);
+    assert_eq!(holders(deps.as_ref(), QueryMsg::HoldersByBalance {
+        min_balance: Some(Uint128::new(101)),
+        start_after: None,
+        limit: Some(1),
+    }), vec![holder(OWNER, 999_999_400)]);
+
+    // The balances held before the index are backfilled by anyone
+    index_balance(deps.as_mut().storage, &Addr::unchecked(SENDER), Uint128::new(500), Uint128::zero()).unwrap();
+    index_balance(deps.as_mut().storage, &Addr::unchecked(OWNER), Uint128::new(999_999_400), Uint128::zero())
+        .unwrap();
+    assert_eq!(holders(deps.as_ref(), QueryMsg::TopHolders { limit: None }), vec![holder(FEE_ADMIN, 100)]);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::IndexBalances {
+        limit: Some(3),
+    }).unwrap();
+    assert_eq!(res.attributes[2].value, "false");
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::IndexBalances {
+        limit: Some(3),
+    }).unwrap();
+    assert_eq!(res.attributes[1].value, "1");
+    assert_eq!(res.attributes[2].value, "true");
+    assert_eq!(holders(deps.as_ref(), QueryMsg::TopHolders { limit: None }), vec![
+        holder(OWNER, 999_999_400),
+        holder(SENDER, 500),
+        holder(FEE_ADMIN, 100),
+    ]);
+}
+
+#[test]
+fn test_protocol_messages() {
+    // The messages other contracts send to the token are parsed by it
+    let msg = TokenExecuteMsg::UpdateSwapFeeConfig {
+        fee_admin: Some("mock_dao".to_string()),
+        enable_swap_fee: Some(true),
+        swap_percent_fee: Some(Decimal::percent(2)),
+        fee_receiver: None,
+    };
+    let parsed: ExecuteMsg = from_binary(&to_binary(&msg).unwrap()).unwrap();
+    assert_eq!(parsed, ExecuteMsg::UpdateSwapFeeConfig {
+        fee_admin: Some("mock_dao".to_string()),
+        enable_swap_fee: Some(true),
+        swap_percent_fee: Some(Decimal::percent(2)),
+        fee_receiver: None,
+    });
+    let msg = TokenQueryMsg::BalanceAt { address: SENDER.to_string(), height: 12_345 };
+    let parsed: QueryMsg = from_binary(&to_binary(&msg).unwrap()).unwrap();
+    assert_eq!(parsed, QueryMsg::BalanceAt { address: SENDER.to_string(), height: 12_345 });
+    let msg = TokenQueryMsg::TotalSupplyAt { height: 12_345 };
+    let parsed: QueryMsg = from_binary(&to_binary(&msg).unwrap()).unwrap();
+    assert_eq!(parsed, QueryMsg::TotalSupplyAt { height: 12_345 });
+
+    // The tokens created by the factory are instantiated with the protocol message
+    let msg = TokenInstantiateMsg {
+        name: "Wrapped".to_string(),
+        symbol: "WRAP".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: OWNER.to_string(), amount: Uint128::new(100) }],
+        mint: None,
+        swap_fee_config: Some(SwapFeeConfig {
+            fee_admin: OWNER.to_string(),
+            enable_swap_fee: true,
+            swap_percent_fee: Decimal::percent(1),
+            fee_receiver: OWNER.to_string(),
+        }),
+        pauser: None,
+    };
+    let parsed: InstantiateMsg = from_binary(&to_binary(&msg).unwrap()).unwrap();
+    let mut deps = mock_dependencies(&[]);
+    instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), parsed).unwrap();
+    let res: SwapFeeConfigResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::SwapFeeConfig {}).unwrap())
+        .unwrap();
+    assert_eq!(res.swap_percent_fee, Decimal::percent(1));
+}
+
+#[test]
+fn test_batch_query() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Batch {
+        queries: vec![
+            QueryMsg::Balance { address: OWNER.to_string() },
+            QueryMsg::SwapFeeConfig {},
+        ],
+    }).unwrap();
+    let batch: BatchResponse = from_binary(&res).unwrap();
+    let balance: BalanceResponse = from_binary(&batch.results[0]).unwrap();
+    assert_eq!(balance.balance, Uint128::new(1_000_000_000));
+    let config: SwapFeeConfigResponse = from_binary(&batch.results[1]).unwrap();
+    assert_eq!(config.fee_receiver, FEE_RECEIVER.to_string());
+
+    // A failing query fails the whole batch
+    query(deps.as_ref(), env.clone(), QueryMsg::Batch {
+        queries: vec![QueryMsg::SwapFeeConfig {}, QueryMsg::Balance { address: "".to_string() }],
+    }).unwrap_err();
+
+    // A batch cannot hold batches
+    let err = query(deps.as_ref(), env, QueryMsg::Batch {
+        queries: vec![QueryMsg::Batch { queries: vec![QueryMsg::SwapFeeConfig {}] }],
+    }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("A batch cannot hold batches"));
+}
+
+#[test]
+fn test_prune_expired_allowances() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let expirations = [
+        ("spender_a", Expiration::AtHeight(env.block.height + 10)),
+        ("spender_b", Expiration::Never {}),
+        ("spender_c", Expiration::AtTime(env.block.time.plus_seconds(10))),
+    ];
+    for (spender, expires) in expirations.iter() {
+        execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(100),
+            expires: Some(*expires),
+        }).unwrap();
+    }
+    env.block.height += 10;
+    env.block.time = env.block.time.plus_seconds(10);
+
+    let all_allowances = |deps: Deps, exclude_expired: Option<bool>| -> Vec<String> {
+        let res = query(deps, env.clone(), QueryMsg::AllAllowances {
+            owner: OWNER.to_string(),
+            start_after: None,
+            limit: None,
+            exclude_expired,
+        }).unwrap();
+        let res: AllAllowancesResponse = from_binary(&res).unwrap();
+        res.allowances.into_iter().map(|a| a.spender).collect()
+    };
+    assert_eq!(all_allowances(deps.as_ref(), None), vec!["spender_a", "spender_b", "spender_c"]);
+    assert_eq!(all_allowances(deps.as_ref(), Some(true)), vec!["spender_b"]);
+
+    // Anyone prunes the expired allowances, a batch at a time
+    let prune_msg = ExecuteMsg::PruneExpiredAllowances { owner: OWNER.to_string(), limit: Some(1) };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), prune_msg.clone()).unwrap();
+    assert_eq!(res.attributes[2], attr("pruned", "1"));
+    assert_eq!(all_allowances(deps.as_ref(), None), vec!["spender_b", "spender_c"]);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), prune_msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), prune_msg).unwrap();
+    assert_eq!(res.attributes[2], attr("pruned", "0"));
+    assert_eq!(all_allowances(deps.as_ref(), None), vec!["spender_b"]);
+}
+
+#[test]
+fn test_fee_discount_nft() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let deposit = |sender: &str, token_id: &str| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: sender.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_binary(&Cw721HookMsg::DepositFeeDiscount {}).unwrap(),
+    });
+
+    // Only the badges of the configured collection are accepted
+    let err = execute(deps.as_mut(), env.clone(), mock_info("badges", &[]), deposit(OWNER, "1")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let update_config = ExecuteMsg::UpdateFeeDiscountConfig {
+        collection: "badges".to_string(),
+        discount: Decimal::percent(50),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_config.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_config).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("other_nfts", &[]), deposit(OWNER, "1")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("badges", &[]), deposit(OWNER, "1")).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("badges", &[]), deposit(OWNER, "2")).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("A fee discount badge is already deposited")));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::FeeDiscount { address: OWNER.to_string() }).unwrap();
+    assert_eq!(from_binary::<FeeDiscountResponse>(&res).unwrap(), FeeDiscountResponse {
+        token_id: Some("1".to_string()),
+        discount: Decimal::percent(50),
+    });
+
+    // The depositor pays half of the 10% swap fee
+    let swap_msg = to_binary(&Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap();
+    let send = ExecuteMsg::Send {
+        contract: "dex_contract".to_string(),
+        amount: Uint128::new(10_000_000),
+        msg: swap_msg.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send.clone()).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(Cw20ReceiveMsg {
+            sender: OWNER.to_string(),
+            amount: Uint128::new(9_500_000),
+            msg: swap_msg,
+        }.into_cosmos_msg("dex_contract".to_string()).unwrap()),
+    ]);
+
+    // The withdrawn badge is returned and the full fee applies again
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::WithdrawFeeDiscountNft {})
+        .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::reply_on_success(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "badges".to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: OWNER.to_string(),
+            token_id: "1".to_string(),
+        }).unwrap(),
+        funds: vec![],
+    }), 1)]);
+
+    // The collection cannot deposit the badge back until the transfer replied
+    let err = execute(deps.as_mut(), env.clone(), mock_info("badges", &[]), deposit(OWNER, "1")).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Reentrant call")));
+    let transfer_reply = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    };
+    reply(deps.as_mut(), env.clone(), transfer_reply).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: FEE_RECEIVER.to_string() }).unwrap();
+    assert_eq!(from_binary::<BalanceResponse>(&res).unwrap().balance, Uint128::new(1_500_000));
+    let res = query(deps.as_ref(), env, QueryMsg::FeeDiscount { address: OWNER.to_string() }).unwrap();
+    assert_eq!(from_binary::<FeeDiscountResponse>(&res).unwrap(), FeeDiscountResponse::default());
+}
+
+#[test]
+fn test_rebates() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let epoch = env.block.time.seconds() / REBATE_EPOCH_LENGTH;
+
+    let update_share = |share: Decimal| ExecuteMsg::UpdateRebateShare { share };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_share(Decimal::percent(20)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_share(Decimal::percent(101)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Rebate share cannot exceed 1")));
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_share(Decimal::percent(20))).unwrap();
+
+    let transfer = ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(10_000_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer).unwrap();
+    let swap = |amount: u128| ExecuteMsg::Send {
+        contract: "dex_contract".to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap(),
+    };
+    // 20% of the 10% swap fees are set aside for the rebates
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), swap(10_000_000)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), swap(5_000_000)).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: FEE_RECEIVER.to_string() }).unwrap();
+    assert_eq!(from_binary::<BalanceResponse>(&res).unwrap().balance, Uint128::new(1_200_000));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::RebateEpoch { epoch }).unwrap();
+    assert_eq!(from_binary::<RebateEpochResponse>(&res).unwrap(), RebateEpochResponse {
+        rebate_share: Decimal::percent(20),
+        end_time: (epoch + 1) * REBATE_EPOCH_LENGTH,
+        fees: Uint128::new(1_500_000),
+        rebates: Uint128::new(300_000),
+    });
+
+    // The rebates are claimed once the epoch ended, pro-rata to the fees paid
+    let claim = ExecuteMsg::ClaimRebate { epoch };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), claim.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The rebate epoch has not ended yet")));
+    env.block.time = env.block.time.plus_seconds(REBATE_EPOCH_LENGTH);
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), claim.clone()).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), claim).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: OWNER.to_string() }).unwrap();
+    assert_eq!(from_binary::<BalanceResponse>(&res).unwrap().balance, Uint128::new(980_200_000));
+
+    let res = query(deps.as_ref(), env, QueryMsg::Rebate { epoch, address: SENDER.to_string() }).unwrap();
+    assert_eq!(from_binary::<RebateResponse>(&res).unwrap(), RebateResponse {
+        fees: Uint128::new(500_000),
+        amount: Uint128::new(100_000),
+    });
+}
+
+#[test]
+fn test_send_cap() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let update_cap = ExecuteMsg::UpdateSendCap {
+        contract: "new_pair".to_string(),
+        cap: Some(Uint128::new(1_000)),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_cap.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_cap).unwrap();
+
+    let send = |contract: &str, amount: u128| ExecuteMsg::Send {
+        contract: contract.to_string(),
+        amount: Uint128::new(amount),
+        msg: Binary::default(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 600)).unwrap();
+    env.block.time = env.block.time.plus_seconds(12 * 3_600);
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 400)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 1)).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Send cap of the destination exceeded")));
+
+    // The other destinations are not capped
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("other_pair", 10_000)).unwrap();
+
+    // The first send leaves the window 24 hours later
+    env.block.time = env.block.time.plus_seconds(13 * 3_600);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::SendCap { contract: "new_pair".to_string() }).unwrap();
+    assert_eq!(from_binary::<SendCapResponse>(&res).unwrap(), SendCapResponse {
+        cap: Some(Uint128::new(1_000)),
+        sent: Uint128::new(400),
+    });
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::TransferRestrictions {}).unwrap();
+    assert_eq!(from_binary::<TransferRestrictionsResponse>(&res).unwrap().send_caps, vec![DestinationSendCap {
+        contract: "new_pair".to_string(),
+        cap: Uint128::new(1_000),
+    }]);
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 600)).unwrap();
+
+    // The fee admin can clear the accounted amount
+    let reset = ExecuteMsg::ResetSendCapWindow { contract: "new_pair".to_string() };
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), reset).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 1_000)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("new_pair", 1)).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Send cap of the destination exceeded")));
+}
+
+#[test]
+fn test_protocol_contracts() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let update = ExecuteMsg::UpdateProtocolContracts {
+        add: vec!["staking".to_string(), "distributor".to_string()],
+        remove: vec![],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update).unwrap();
+    let update_cap = ExecuteMsg::UpdateSendCap { contract: "distributor".to_string(), cap: Some(Uint128::zero()) };
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_cap).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::ProtocolContracts { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(from_binary::<ProtocolContractsResponse>(&res).unwrap().contracts,
+               vec!["distributor".to_string(), "staking".to_string()]);
+
+    let transfer = ExecuteMsg::Transfer { recipient: "staking".to_string(), amount: Uint128::new(1_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer).unwrap();
+
+    // A swap message sent between protocol contracts pays no fee and ignores the cap
+    let swap_msg = to_binary(&Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: None }).unwrap();
+    let send = ExecuteMsg::Send {
+        contract: "distributor".to_string(),
+        amount: Uint128::new(1_000),
+        msg: swap_msg,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("staking", &[]), send.clone()).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "distributor".to_string() }).unwrap();
+    assert_eq!(from_binary::<BalanceResponse>(&res).unwrap().balance, Uint128::new(1_000));
+
+    // The other senders still go through the cap
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]), send).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Send cap of the destination exceeded")));
+}
+
+#[test]
+fn test_launch_mode() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let unlock_time = env.block.time.seconds() + 1_000;
+
+    let update_mode = |unlock_time: u64| ExecuteMsg::UpdateLaunchMode { unlock_time };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_mode(unlock_time)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_mode(unlock_time + 1_000)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_mode(unlock_time + 2_000))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The launch unlock time can only be brought forward")));
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_mode(unlock_time)).unwrap();
+    let update_allowlist = ExecuteMsg::UpdateLaunchAllowlist { add: vec!["pair".to_string()], remove: vec![] };
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_allowlist).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::LaunchAllowlist { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(from_binary::<LaunchAllowlistResponse>(&res).unwrap().addresses, vec!["pair".to_string()]);
+
+    // Only the allowlist receives tokens before the unlock time
+    let transfer = |recipient: &str| ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount: Uint128::new(10),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer(SENDER)).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err(
+        format!("Only the launch allowlist receives tokens until {}", unlock_time))));
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer("pair")).unwrap();
+    let is_blocked = |deps: Deps, env: Env, address: &str| -> IsBlockedResponse {
+        from_binary(&query(deps, env, QueryMsg::IsBlocked { address: address.to_string() }).unwrap()).unwrap()
+    };
+    assert_eq!(is_blocked(deps.as_ref(), env.clone(), SENDER), IsBlockedResponse {
+        blocked: true,
+        reasons: vec![BlockReason::LaunchAllowlist],
+        send_restricted: false,
+    });
+    assert!(!is_blocked(deps.as_ref(), env.clone(), "pair").blocked);
+
+    // The restriction lifts by itself and cannot be set up again
+    env.block.time = env.block.time.plus_seconds(1_000);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::LaunchMode {}).unwrap();
+    assert_eq!(from_binary::<LaunchModeResponse>(&res).unwrap(), LaunchModeResponse {
+        unlock_time: Some(unlock_time),
+        active: false,
+    });
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer(SENDER)).unwrap();
+    assert!(!is_blocked(deps.as_ref(), env.clone(), SENDER).blocked);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_mode(unlock_time + 1_000))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The launch mode has already lifted")));
+}
+
+#[test]
+fn test_send_strict_mode() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let update_pauser = ExecuteMsg::UpdatePauser { pauser: Some("mock_pauser".to_string()) };
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_pauser).unwrap();
+    let update_allowlist = ExecuteMsg::UpdateSendAllowlist { add: vec!["pair".to_string()], remove: vec![] };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_allowlist.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_allowlist).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::SendAllowlist { start_after: None, limit: None }).unwrap();
+    assert_eq!(from_binary::<SendAllowlistResponse>(&res).unwrap().contracts, vec!["pair".to_string()]);
+
+    // The pauser enables the strict mode but cannot lift it
+    let update_mode = |enabled: bool| ExecuteMsg::UpdateSendStrictMode { enabled };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_mode(true)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("mock_pauser", &[]), update_mode(true)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("mock_pauser", &[]), update_mode(false)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::SendStrictMode {}).unwrap();
+    assert_eq!(from_binary::<SendStrictModeResponse>(&res).unwrap(), SendStrictModeResponse { enabled: true });
+
+    // Only the allowlisted contracts receive sends, the transfers are not restricted
+    let send = |contract: &str| ExecuteMsg::Send {
+        contract: contract.to_string(),
+        amount: Uint128::new(10),
+        msg: Binary::default(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("drainer")).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Sends to drainer are disabled in strict mode")));
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), send("pair")).unwrap();
+    let transfer = ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(10) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer).unwrap();
+    let is_blocked = |deps: Deps, address: &str| -> IsBlockedResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::IsBlocked { address: address.to_string() }).unwrap()).unwrap()
+    };
+    assert_eq!(is_blocked(deps.as_ref(), "drainer"), IsBlockedResponse {
+        blocked: false,
+        reasons: vec![],
+        send_restricted: true,
+    });
+    assert!(!is_blocked(deps.as_ref(), "pair").send_restricted);
+
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_mode(false)).unwrap();
+    assert!(!is_blocked(deps.as_ref(), "drainer").send_restricted);
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), send("drainer")).unwrap();
+}
+
+#[test]
+fn test_admin_recovery() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let now = env.block.time.seconds();
+
+    let update_recovery = ExecuteMsg::UpdateAdminRecovery { recovery: Some("governance".to_string()), window: 1_000 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), update_recovery.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let too_long = ExecuteMsg::UpdateAdminRecovery { recovery: Some("governance".to_string()), window: u64::MAX };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), too_long).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The heartbeat window cannot exceed 31536000 seconds")));
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), update_recovery).unwrap();
+
+    // The heartbeats postpone the recovery
+    env.block.time = env.block.time.plus_seconds(800);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::Heartbeat {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::Heartbeat {}).unwrap();
+    env.block.time = env.block.time.plus_seconds(800);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("governance", &[]), ExecuteMsg::ClaimFeeAdmin {})
+        .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The fee admin sent a heartbeat within the window")));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::AdminRecovery {}).unwrap();
+    assert_eq!(from_binary::<AdminRecoveryResponse>(&res).unwrap(), AdminRecoveryResponse {
+        recovery: Some("governance".to_string()),
+        window: 1_000,
+        last_heartbeat: now + 800,
+        claimable_after: Some(now + 1_800),
+    });
+
+    // Only the recovery address claims the fee admin once the window passed
+    env.block.time = env.block.time.plus_seconds(201);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::ClaimFeeAdmin {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("governance", &[]), ExecuteMsg::ClaimFeeAdmin {}).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::SwapFeeConfig {}).unwrap();
+    assert_eq!(from_binary::<SwapFeeConfigResponse>(&res).unwrap().fee_admin, "governance".to_string());
+    let err = execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &[]), ExecuteMsg::Heartbeat {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env, mock_info("governance", &[]), ExecuteMsg::Heartbeat {}).unwrap();
+}
+
+#[test]
+fn test_scheduled_swap() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let balance = |deps: Deps, address: &str| -> Uint128 {
+        let res = query(deps, mock_env(), QueryMsg::Balance { address: address.to_string() }).unwrap();
+        from_binary::<BalanceResponse>(&res).unwrap().balance
+    };
+
+    let schedule = ExecuteMsg::ScheduleSwap {
+        pair: "pair".to_string(),
+        total_amount: Uint128::new(1_000_001),
+        intervals: 2,
+        belief_price: Decimal::percent(50),
+        max_spread: Some(Decimal::percent(1)),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), schedule.clone()).unwrap_err();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), schedule).unwrap();
+    assert_eq!(balance(deps.as_ref(), MOCK_CONTRACT_ADDR), Uint128::new(1_000_001));
+
+    // Each swap sends an even part less the 10% fee, the proceeds go to the owner
+    let execute_swap = ExecuteMsg::ExecuteScheduledSwap { swap_id: 1 };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), execute_swap.clone()).unwrap();
+    let swap_msg = to_binary(&Cw20HookMsg::Swap {
+        belief_price: Some(Decimal::percent(50)),
+        max_spread: Some(Decimal::percent(1)),
+        to: Some(OWNER.to_string()),
+    }).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::reply_on_success(Cw20ReceiveMsg {
+            sender: MOCK_CONTRACT_ADDR.to_string(),
+            amount: Uint128::new(450_000),
+            msg: swap_msg,
+        }.into_cosmos_msg("pair".to_string()).unwrap(), 1),
+    ]);
+
+    // The pair cannot call back into the scheduled swaps until the send replied
+    let cancel = ExecuteMsg::CancelScheduledSwap { swap_id: 1 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), cancel).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Reentrant call")));
+    let send_reply = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    };
+    reply(deps.as_mut(), env.clone(), send_reply.clone()).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), execute_swap.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The next swap is not due yet")));
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::ScheduledSwap { swap_id: 1 }).unwrap();
+    assert_eq!(from_binary::<ScheduledSwapResponse>(&res).unwrap(), ScheduledSwapResponse {
+        owner: OWNER.to_string(),
+        pair: "pair".to_string(),
+        remaining: Uint128::new(500_001),
+        swaps_left: 1,
+        next_swap_time: env.block.time.seconds() + 3_600,
+        belief_price: Decimal::percent(50),
+        max_spread: Some(Decimal::percent(1)),
+    });
+
+    // The last swap takes the rounding remainder and ends the schedule
+    env.block.time = env.block.time.plus_seconds(3_600);
+    execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), execute_swap).unwrap();
+    reply(deps.as_mut(), env.clone(), send_reply).unwrap();
+    assert_eq!(balance(deps.as_ref(), "pair"), Uint128::new(900_001));
+    assert_eq!(balance(deps.as_ref(), FEE_RECEIVER), Uint128::new(100_000));
+    assert_eq!(balance(deps.as_ref(), MOCK_CONTRACT_ADDR), Uint128::zero());
+    query(deps.as_ref(), env.clone(), QueryMsg::ScheduledSwap { swap_id: 1 }).unwrap_err();
+
+    // Only the owner can cancel, getting the tokens not swapped yet back
+    let schedule = ExecuteMsg::ScheduleSwap {
+        pair: "pair".to_string(),
+        total_amount: Uint128::new(1_000),
+        intervals: 4,
+        belief_price: Decimal::percent(50),
+        max_spread: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), schedule).unwrap();
+    let cancel = ExecuteMsg::CancelScheduledSwap { swap_id: 2 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), cancel.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let owner_balance = balance(deps.as_ref(), OWNER);
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), cancel).unwrap();
+    assert_eq!(balance(deps.as_ref(), OWNER), owner_balance + Uint128::new(1_000));
+}
+
+#[test]
+fn test_dividend() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    let snapshot_height = env.block.height + 1;
+
+    let transfer = ExecuteMsg::Transfer { recipient: SENDER.to_string(), amount: Uint128::new(250_000_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), transfer).unwrap();
+
+    // The tokens escrowed by the token contract are left out of the supply sharing the dividend
+    let escrow = ExecuteMsg::Transfer { recipient: MOCK_CONTRACT_ADDR.to_string(), amount: Uint128::new(500_000_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), escrow).unwrap();
+
+    // The burn after the snapshot height changes neither the shares nor the supply read
+    env.block.height += 1;
+    let burn = ExecuteMsg::Burn { amount: Uint128::new(100_000_000) };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), burn).unwrap();
+
+    env.block.height += 1;
+    let claim_deadline = env.block.time.seconds() + 1_000;
+    let deposit = ExecuteMsg::DepositDividend { height: snapshot_height, claim_deadline };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &coins(1_000, "uusd")), deposit.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(FEE_ADMIN, &coins(1_000, "uusd")), deposit).unwrap();
+
+    let claim = ExecuteMsg::ClaimDividend { snapshot_id: 1 };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), claim.clone()).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+        to_address: SENDER.to_string(),
+        amount: coins(500, "uusd"),
+    })]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), claim.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Dividend already claimed")));
+    let err = execute(deps.as_mut(), env.clone(), mock_info(MOCK_CONTRACT_ADDR, &[]), claim.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    let query_claim = QueryMsg::DividendClaim { snapshot_id: 1, address: OWNER.to_string() };
+    let res = query(deps.as_ref(), env.clone(), query_claim).unwrap();
+    assert_eq!(from_binary::<DividendClaimResponse>(&res).unwrap(), DividendClaimResponse {
+        amount: Uint128::new(500),
+        claimed: false,
+    });
+
+    // The unclaimed part goes to the fee receiver after the deadline
+    let sweep = ExecuteMsg::SweepDividend { snapshot_id: 1 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SENDER, &[]), sweep.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The claim deadline has not passed yet")));
+    env.block.time = env.block.time.plus_seconds(1_001);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), claim).unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("The claim deadline has passed")));
+    let res = execute(deps.as_mut(), env, mock_info(SENDER, &[]), sweep).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+        to_address: FEE_RECEIVER.to_string(),
+        amount: coins(500, "uusd"),
+    })]);
+}
@@ -0,0 +1,29 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use luart_utils::batch::BatchResponse;
+
+use luart_treasury::msg::{
+    AutoLiquidityResponse, BalancesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    ProposalResponse, ProposalsResponse, QueryMsg, RoleResponse, SpenderResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(RoleResponse), &out_dir);
+    export_schema(&schema_for!(SpenderResponse), &out_dir);
+    export_schema(&schema_for!(ProposalResponse), &out_dir);
+    export_schema(&schema_for!(ProposalsResponse), &out_dir);
+    export_schema(&schema_for!(BalancesResponse), &out_dir);
+    export_schema(&schema_for!(AutoLiquidityResponse), &out_dir);
+    export_schema(&schema_for!(BatchResponse), &out_dir);
+}
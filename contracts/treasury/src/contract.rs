@@ -0,0 +1,656 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    QuerierWrapper, Reply, Response, StdError, StdResult, Storage, SubMsg, to_binary, Uint128,
+    WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_storage_plus::{Bound, U64Key};
+use luart_utils::batch::batch_query;
+use terraswap::asset::{Asset, AssetInfo, PairInfo};
+use terraswap::pair::{ExecuteMsg as PairExecuteMsg, PoolResponse, QueryMsg as PairQueryMsg};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AutoLiquidityResponse, BalancesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, ProposalResponse, ProposalsResponse, QueryMsg, RoleLimitResponse, RoleResponse,
+    SpenderResponse,
+};
+use crate::state::{
+    asset_key, AUTO_LIQUIDITY, AutoLiquidity, Config, CONFIG, DAILY_SPENT, DailySpend,
+    FEES_ACCOUNTED, LIQUIDITY_PROVIDED, PENDING_LIQUIDITY, PendingLiquidity, Proposal,
+    PROPOSAL_COUNT, PROPOSALS, ProposalStatus, Role, ROLES, SpendLimit, SPENDERS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-treasury";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const DAY: u64 = 86_400;
+
+const PROVIDE_LIQUIDITY_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    CONFIG.save(deps.storage, &Config {
+        owner,
+        assets: msg.assets,
+    })?;
+    PROPOSAL_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetRole { name, limits } => set_role(deps, info, name, limits),
+        ExecuteMsg::RemoveRole { name } => remove_role(deps, info, name),
+        ExecuteMsg::SetSpender { address, role } => set_spender(deps, info, address, role),
+        ExecuteMsg::Spend { asset, recipient } => spend(deps, env, info, asset, recipient),
+        ExecuteMsg::ProposeSpend {
+            asset,
+            recipient,
+            description,
+        } => propose_spend(deps, info, asset, recipient, description),
+        ExecuteMsg::ApproveSpend { proposal_id } => {
+            review_spend(deps, info, proposal_id, ProposalStatus::Approved)
+        }
+        ExecuteMsg::RejectSpend { proposal_id } => {
+            review_spend(deps, info, proposal_id, ProposalStatus::Rejected)
+        }
+        ExecuteMsg::ExecuteSpend { proposal_id } => execute_spend(deps, info, proposal_id),
+        ExecuteMsg::UpdateConfig { owner, assets } => update_config(deps, info, owner, assets),
+        ExecuteMsg::UpdateAutoLiquidity { pair, share } => {
+            update_auto_liquidity(deps, env, info, pair, share)
+        }
+        ExecuteMsg::ProvideFeeLiquidity {} => provide_fee_liquidity(deps, env, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        PROVIDE_LIQUIDITY_REPLY_ID => record_fee_liquidity(deps, env),
+        id => Err(StdError::generic_err(format!("Unknown reply id {}", id)).into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn set_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    limits: Vec<SpendLimit>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ROLES.save(deps.storage, &name, &Role { limits })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_role")
+        .add_attribute("role", name))
+}
+
+pub fn remove_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !ROLES.has(deps.storage, &name) {
+        return Err(ContractError::RoleNotFound { name });
+    }
+    ROLES.remove(deps.storage, &name);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_role")
+        .add_attribute("role", name))
+}
+
+pub fn set_spender(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    role: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    match &role {
+        Some(role) => {
+            if !ROLES.has(deps.storage, role) {
+                return Err(ContractError::RoleNotFound { name: role.clone() });
+            }
+            SPENDERS.save(deps.storage, &address, role)?;
+        }
+        None => SPENDERS.remove(deps.storage, &address),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_spender")
+        .add_attribute("address", address)
+        .add_attribute("role", role.unwrap_or_default()))
+}
+
+pub fn spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: Asset,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let role_name = SPENDERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+    let role = ROLES
+        .may_load(deps.storage, &role_name)?
+        .ok_or(ContractError::Unauthorized {})?;
+    let key = asset_key(&asset.info).to_string();
+    let daily_limit = role.daily_limit(&asset.info)
+        .ok_or_else(|| ContractError::NoSpendLimit { asset: key.clone() })?;
+
+    // The daily usage resets on the first spend of a new day
+    let today = env.block.time.seconds() / DAY;
+    let mut spent = DAILY_SPENT
+        .may_load(deps.storage, (&role_name, &key))?
+        .filter(|s| s.day == today)
+        .unwrap_or(DailySpend { day: today, amount: Uint128::zero() });
+    let remaining = daily_limit.saturating_sub(spent.amount);
+    if asset.amount > remaining {
+        return Err(ContractError::DailyLimitExceeded { remaining });
+    }
+    spent.amount = spent.amount.checked_add(asset.amount)?;
+    DAILY_SPENT.save(deps.storage, (&role_name, &key), &spent)?;
+    record_token_spend(deps.storage, &asset)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    Ok(Response::new()
+        .add_message(asset_transfer_msg(&asset, &recipient)?)
+        .add_attribute("action", "spend")
+        .add_attribute("spender", info.sender)
+        .add_attribute("role", role_name)
+        .add_attribute("recipient", recipient)
+        .add_attribute("asset", key)
+        .add_attribute("amount", asset.amount))
+}
+
+pub fn propose_spend(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: Asset,
+    recipient: String,
+    description: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender && !SPENDERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &Proposal {
+        proposer: info.sender.clone(),
+        asset,
+        recipient: deps.api.addr_validate(&recipient)?,
+        description,
+        status: ProposalStatus::Pending,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_spend")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposer", info.sender))
+}
+
+pub fn review_spend(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    status: ProposalStatus,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(ContractError::ProposalNotPending {});
+    }
+    proposal.status = status;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", match status {
+            ProposalStatus::Approved => "approve_spend",
+            _ => "reject_spend",
+        })
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_spend(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    if config.owner != info.sender && proposal.proposer != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if proposal.status != ProposalStatus::Approved {
+        return Err(ContractError::ProposalNotApproved {});
+    }
+
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+    record_token_spend(deps.storage, &proposal.asset)?;
+
+    Ok(Response::new()
+        .add_message(asset_transfer_msg(&proposal.asset, &proposal.recipient)?)
+        .add_attribute("action", "execute_spend")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("recipient", proposal.recipient)
+        .add_attribute("asset", asset_key(&proposal.asset.info))
+        .add_attribute("amount", proposal.asset.amount))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    assets: Option<Vec<AssetInfo>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(assets) = assets {
+        config.assets = assets;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_auto_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+    share: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if share > Decimal::one() {
+        return Err(ContractError::InvalidShare {});
+    }
+
+    let pair = deps.api.addr_validate(&pair)?;
+    let pair_info: PairInfo = deps.querier.query_wasm_smart(&pair, &PairQueryMsg::Pair {})?;
+    let (token, denom) = match &pair_info.asset_infos {
+        [AssetInfo::Token { contract_addr }, AssetInfo::NativeToken { denom }]
+        | [AssetInfo::NativeToken { denom }, AssetInfo::Token { contract_addr }] => {
+            (deps.api.addr_validate(contract_addr)?, denom.clone())
+        }
+        _ => return Err(ContractError::InvalidPair {}),
+    };
+
+    // Only the fees received from now on are added, unless the token stays the same
+    let previous = AUTO_LIQUIDITY.may_load(deps.storage)?;
+    if previous.map(|p| p.token) != Some(token.clone()) {
+        let balance = query_token_balance(&deps.querier, &token, &env.contract.address)?;
+        FEES_ACCOUNTED.save(deps.storage, &balance)?;
+    }
+
+    AUTO_LIQUIDITY.save(deps.storage, &AutoLiquidity {
+        pair: pair.clone(),
+        lp_token: deps.api.addr_validate(&pair_info.liquidity_token)?,
+        token,
+        denom,
+        share,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_auto_liquidity")
+        .add_attribute("pair", pair)
+        .add_attribute("share", share.to_string()))
+}
+
+pub fn provide_fee_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender && !SPENDERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let auto = AUTO_LIQUIDITY
+        .may_load(deps.storage)?
+        .filter(|a| !a.share.is_zero())
+        .ok_or(ContractError::AutoLiquidityDisabled {})?;
+
+    let balance = query_token_balance(&deps.querier, &auto.token, &env.contract.address)?;
+    let token_amount = pending_fees(deps.storage, &auto, balance)?;
+    if token_amount.is_zero() {
+        return Err(ContractError::NoFeesCollected {});
+    }
+
+    // The native side is matched at the pool price so that no value goes to the pool
+    let pool: PoolResponse = deps.querier.query_wasm_smart(&auto.pair, &PairQueryMsg::Pool {})?;
+    let mut token_reserve = Uint128::zero();
+    let mut native_reserve = Uint128::zero();
+    for asset in pool.assets.iter() {
+        match &asset.info {
+            AssetInfo::Token { .. } => token_reserve = asset.amount,
+            AssetInfo::NativeToken { .. } => native_reserve = asset.amount,
+        }
+    }
+    if token_reserve.is_zero() || native_reserve.is_zero() {
+        return Err(ContractError::EmptyPool {});
+    }
+    let native_amount = token_amount.multiply_ratio(native_reserve, token_reserve);
+    let native_balance = deps.querier.query_balance(&env.contract.address, &auto.denom)?.amount;
+    if native_amount.is_zero() || native_amount > native_balance {
+        return Err(ContractError::InsufficientFunds { denom: auto.denom, required: native_amount });
+    }
+
+    FEES_ACCOUNTED.save(deps.storage, &balance.checked_sub(token_amount)?)?;
+    PENDING_LIQUIDITY.save(deps.storage, &PendingLiquidity {
+        token_amount,
+        native_amount,
+        lp_balance: query_token_balance(&deps.querier, &auto.lp_token, &env.contract.address)?,
+    })?;
+
+    let token_info = AssetInfo::Token { contract_addr: auto.token.to_string() };
+    let native_info = AssetInfo::NativeToken { denom: auto.denom.clone() };
+
+    // The LP tokens are minted to the treasury, the reply records how many
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: auto.token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: auto.pair.to_string(),
+                amount: token_amount,
+                expires: None,
+            })?,
+            funds: vec![],
+        })
+        .add_submessage(SubMsg::reply_on_success(WasmMsg::Execute {
+            contract_addr: auto.pair.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset { info: token_info, amount: token_amount },
+                    Asset { info: native_info, amount: native_amount },
+                ],
+                slippage_tolerance: None,
+                receiver: None,
+            })?,
+            funds: vec![Coin { denom: auto.denom, amount: native_amount }],
+        }, PROVIDE_LIQUIDITY_REPLY_ID))
+        .add_attribute("action", "provide_fee_liquidity")
+        .add_attribute("token_amount", token_amount)
+        .add_attribute("native_amount", native_amount))
+}
+
+fn record_fee_liquidity(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let auto = AUTO_LIQUIDITY.load(deps.storage)?;
+    let pending = PENDING_LIQUIDITY.load(deps.storage)?;
+    PENDING_LIQUIDITY.remove(deps.storage);
+
+    let lp_balance = query_token_balance(&deps.querier, &auto.lp_token, &env.contract.address)?;
+    let lp_amount = lp_balance.checked_sub(pending.lp_balance)?;
+
+    let mut provided = LIQUIDITY_PROVIDED.may_load(deps.storage)?.unwrap_or_default();
+    provided.token_amount = provided.token_amount.checked_add(pending.token_amount)?;
+    provided.native_amount = provided.native_amount.checked_add(pending.native_amount)?;
+    provided.lp_amount = provided.lp_amount.checked_add(lp_amount)?;
+    LIQUIDITY_PROVIDED.save(deps.storage, &provided)?;
+
+    Ok(Response::new().add_attribute("lp_amount", lp_amount))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Role { name } => to_binary(&query_role(deps, env, name)?),
+        QueryMsg::Spender { address } => to_binary(&query_spender(deps, address)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Balances {} => to_binary(&query_balances(deps, env)?),
+        QueryMsg::AutoLiquidity {} => to_binary(&query_auto_liquidity(deps, env)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        assets: config.assets,
+    })
+}
+
+pub fn query_role(deps: Deps, env: Env, name: String) -> StdResult<RoleResponse> {
+    let role = ROLES.load(deps.storage, &name)?;
+    let today = env.block.time.seconds() / DAY;
+
+    let limits: StdResult<Vec<RoleLimitResponse>> = role.limits
+        .into_iter()
+        .map(|limit| {
+            let spent_today = DAILY_SPENT
+                .may_load(deps.storage, (&name, asset_key(&limit.info)))?
+                .filter(|s| s.day == today)
+                .map(|s| s.amount)
+                .unwrap_or_default();
+            Ok(RoleLimitResponse {
+                info: limit.info,
+                daily_limit: limit.daily_limit,
+                spent_today,
+            })
+        })
+        .collect();
+
+    Ok(RoleResponse { name, limits: limits? })
+}
+
+pub fn query_spender(deps: Deps, address: String) -> StdResult<SpenderResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(SpenderResponse {
+        role: SPENDERS.may_load(deps.storage, &address)?,
+    })
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, U64Key::new(proposal_id))?;
+    Ok(proposal_response(proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let proposals: StdResult<Vec<ProposalResponse>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, proposal) = item?;
+            Ok(proposal_response(parse_proposal_id(&k)?, proposal))
+        })
+        .collect();
+
+    Ok(ProposalsResponse { proposals: proposals? })
+}
+
+pub fn query_balances(deps: Deps, env: Env) -> StdResult<BalancesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let balances: StdResult<Vec<Asset>> = config.assets
+        .into_iter()
+        .map(|info| {
+            let amount = match &info {
+                AssetInfo::NativeToken { denom } => {
+                    deps.querier.query_balance(&env.contract.address, denom)?.amount
+                }
+                AssetInfo::Token { contract_addr } => {
+                    query_token_balance(&deps.querier, &Addr::unchecked(contract_addr), &env.contract.address)?
+                }
+            };
+            Ok(Asset { info, amount })
+        })
+        .collect();
+
+    Ok(BalancesResponse { balances: balances? })
+}
+
+pub fn query_auto_liquidity(deps: Deps, env: Env) -> StdResult<AutoLiquidityResponse> {
+    let auto = AUTO_LIQUIDITY.load(deps.storage)?;
+    let balance = query_token_balance(&deps.querier, &auto.token, &env.contract.address)?;
+    let provided = LIQUIDITY_PROVIDED.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(AutoLiquidityResponse {
+        pending_fees: pending_fees(deps.storage, &auto, balance)?,
+        pair: auto.pair.to_string(),
+        lp_token: auto.lp_token.to_string(),
+        token: auto.token.to_string(),
+        denom: auto.denom,
+        share: auto.share,
+        token_provided: provided.token_amount,
+        native_provided: provided.native_amount,
+        lp_received: provided.lp_amount,
+    })
+}
+
+/// Returns the share of the fees collected since the last provision
+fn pending_fees(storage: &dyn Storage, auto: &AutoLiquidity, balance: Uint128) -> StdResult<Uint128> {
+    let accounted = FEES_ACCOUNTED.may_load(storage)?.unwrap_or_default();
+    Ok(balance.saturating_sub(accounted) * auto.share)
+}
+
+/// Spends of the fee token lower the balance the new fees are counted from
+fn record_token_spend(storage: &mut dyn Storage, asset: &Asset) -> StdResult<()> {
+    if let Some(auto) = AUTO_LIQUIDITY.may_load(storage)? {
+        if asset.info == (AssetInfo::Token { contract_addr: auto.token.to_string() }) {
+            FEES_ACCOUNTED.update(storage, |accounted| -> StdResult<_> {
+                Ok(accounted.saturating_sub(asset.amount))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn query_token_balance(querier: &QuerierWrapper, token: &Addr, address: &Addr) -> StdResult<Uint128> {
+    let res: BalanceResponse = querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance { address: address.to_string() },
+    )?;
+    Ok(res.balance)
+}
+
+fn proposal_response(proposal_id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        proposal_id,
+        proposer: proposal.proposer.to_string(),
+        asset: proposal.asset,
+        recipient: proposal.recipient.to_string(),
+        description: proposal.description,
+        status: proposal.status,
+    }
+}
+
+fn parse_proposal_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted proposal key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn asset_transfer_msg(asset: &Asset, recipient: &Addr) -> StdResult<CosmosMsg> {
+    Ok(match &asset.info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
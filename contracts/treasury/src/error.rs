@@ -0,0 +1,50 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Role {name} does not exist")]
+    RoleNotFound { name: String },
+
+    #[error("The spender role has no spend limit for {asset}")]
+    NoSpendLimit { asset: String },
+
+    #[error("Daily spend limit exceeded, {remaining} left for today")]
+    DailyLimitExceeded { remaining: Uint128 },
+
+    #[error("The proposal is not pending")]
+    ProposalNotPending {},
+
+    #[error("The proposal is not approved")]
+    ProposalNotApproved {},
+
+    #[error("Invalid share, must be at most 1")]
+    InvalidShare {},
+
+    #[error("The pair must trade a token against a native coin")]
+    InvalidPair {},
+
+    #[error("Auto liquidity is disabled")]
+    AutoLiquidityDisabled {},
+
+    #[error("No fees to add as liquidity")]
+    NoFeesCollected {},
+
+    #[error("The pair has no liquidity to price the fees")]
+    EmptyPool {},
+
+    #[error("Insufficient {denom} balance, {required} required")]
+    InsufficientFunds { denom: String, required: Uint128 },
+}
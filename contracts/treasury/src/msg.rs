@@ -0,0 +1,149 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use terraswap::asset::{Asset, AssetInfo};
+
+use crate::state::{ProposalStatus, SpendLimit};
+
+/// This structure describes the parameters used for creating a treasury contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The assets reported by the balances query
+    pub assets: Vec<AssetInfo>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates or replaces a role and its daily spend limits. Only the owner can execute it.
+    SetRole { name: String, limits: Vec<SpendLimit> },
+    /// Removes a role, its spenders cannot spend anymore. Only the owner can execute it.
+    RemoveRole { name: String },
+    /// Assigns a role to the address, or revokes it when unset. Only the owner can execute it.
+    SetSpender { address: String, role: Option<String> },
+    /// Transfers the asset within the daily limit of the sender role
+    Spend { asset: Asset, recipient: String },
+    /// Proposes a spend above the daily limits. Only spenders and the owner can execute it.
+    ProposeSpend {
+        asset: Asset,
+        recipient: String,
+        description: String,
+    },
+    /// Approves a pending proposal. Only the owner can execute it.
+    ApproveSpend { proposal_id: u64 },
+    /// Rejects a pending proposal. Only the owner can execute it.
+    RejectSpend { proposal_id: u64 },
+    /// Transfers the asset of an approved proposal. Only the proposer and the owner can execute it.
+    ExecuteSpend { proposal_id: u64 },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        owner: Option<String>,
+        assets: Option<Vec<AssetInfo>>,
+    },
+    /// Sets the pair the share of the collected LUART swap fees is added to as liquidity, a zero
+    /// share disables it. Only the owner can execute it.
+    UpdateAutoLiquidity { pair: String, share: Decimal },
+    /// Pairs the share of the fees collected since the last provision with the native coins of
+    /// the treasury at the pool price and adds them to the pair. The LP tokens stay in the
+    /// treasury. Only spenders and the owner can execute it.
+    ProvideFeeLiquidity {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the role limits and what its spenders spent today.
+    /// Return type: RoleResponse.
+    Role { name: String },
+    /// Returns the role of the address.
+    /// Return type: SpenderResponse.
+    Spender { address: String },
+    /// Returns a spend proposal.
+    /// Return type: ProposalResponse.
+    Proposal { proposal_id: u64 },
+    /// Returns spend proposals by id.
+    /// Return type: ProposalsResponse.
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the treasury balance of every configured asset.
+    /// Return type: BalancesResponse.
+    Balances {},
+    /// Returns the auto liquidity configuration, the fees awaiting provision and the totals provided.
+    /// Return type: AutoLiquidityResponse.
+    AutoLiquidity {},
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub assets: Vec<AssetInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleLimitResponse {
+    pub info: AssetInfo,
+    pub daily_limit: Uint128,
+    /// The amount spent by the role spenders today
+    pub spent_today: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleResponse {
+    pub name: String,
+    pub limits: Vec<RoleLimitResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SpenderResponse {
+    pub role: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub asset: Asset,
+    pub recipient: String,
+    pub description: String,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalancesResponse {
+    pub balances: Vec<Asset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutoLiquidityResponse {
+    pub pair: String,
+    pub lp_token: String,
+    pub token: String,
+    pub denom: String,
+    pub share: Decimal,
+    /// The LUART the next provision would add
+    pub pending_fees: Uint128,
+    pub token_provided: Uint128,
+    pub native_provided: Uint128,
+    pub lp_received: Uint128,
+}
@@ -0,0 +1,111 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use terraswap::asset::{Asset, AssetInfo};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who manages the roles and approves spend proposals
+    pub owner: Addr,
+    /// The assets reported by the balances query
+    pub assets: Vec<AssetInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SpendLimit {
+    pub info: AssetInfo,
+    /// The amount the role spenders can spend together per day
+    pub daily_limit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Role {
+    pub limits: Vec<SpendLimit>,
+}
+
+impl Role {
+    pub fn daily_limit(&self, info: &AssetInfo) -> Option<Uint128> {
+        self.limits.iter().find(|l| &l.info == info).map(|l| l.daily_limit)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct DailySpend {
+    /// Days since epoch the amount was spent on
+    pub day: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Executed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub asset: Asset,
+    pub recipient: Addr,
+    pub description: String,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutoLiquidity {
+    /// The LUART-UST pair the fees are added to
+    pub pair: Addr,
+    /// The LP token of the pair, held by the treasury
+    pub lp_token: Addr,
+    /// The LUART token, whose swap fees the treasury receives
+    pub token: Addr,
+    /// The native denom paired with the fees
+    pub denom: String,
+    /// The share of the collected fees added as liquidity, zero when disabled
+    pub share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct LiquidityProvided {
+    pub token_amount: Uint128,
+    pub native_amount: Uint128,
+    pub lp_amount: Uint128,
+}
+
+/// A provision waiting for the reply of the pair
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingLiquidity {
+    pub token_amount: Uint128,
+    pub native_amount: Uint128,
+    /// The LP balance of the treasury before the provision
+    pub lp_balance: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ROLES: Map<&str, Role> = Map::new("roles");
+/// The role of each spender
+pub const SPENDERS: Map<&Addr, String> = Map::new("spenders");
+/// The amount spent today by a role, keyed by role and asset
+pub const DAILY_SPENT: Map<(&str, &str), DailySpend> = Map::new("daily_spent");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+pub const AUTO_LIQUIDITY: Item<AutoLiquidity> = Item::new("auto_liquidity");
+/// The LUART balance already split between the treasury and the liquidity, what the
+/// treasury holds above it are the fees collected since the last provision
+pub const FEES_ACCOUNTED: Item<Uint128> = Item::new("fees_accounted");
+pub const PENDING_LIQUIDITY: Item<PendingLiquidity> = Item::new("pending_liquidity");
+/// The totals added as liquidity since the mode was first enabled
+pub const LIQUIDITY_PROVIDED: Item<LiquidityProvided> = Item::new("liquidity_provided");
+
+/// Returns the storage key of an asset, its denom or token address
+pub fn asset_key(info: &AssetInfo) -> &str {
+    match info {
+        AssetInfo::NativeToken { denom } => denom,
+        AssetInfo::Token { contract_addr } => contract_addr,
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use terraswap::asset::{Asset, AssetInfo, PairInfo};
+use terraswap::pair::{PoolResponse, QueryMsg as PairQueryMsg};
+
+pub const MOCK_PAIR: &str = "mock_pair";
+pub const MOCK_LP_TOKEN: &str = "mock_lp_token";
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the cw20 balance and LUART-UST pair queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_balances: HashMap<String, Uint128>,
+    pool: (Uint128, Uint128),
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                let token_info = AssetInfo::Token { contract_addr: "mock_token".to_string() };
+                let native_info = AssetInfo::NativeToken { denom: "uusd".to_string() };
+                match from_binary(msg) {
+                    Ok(PairQueryMsg::Pair {}) => {
+                        return SystemResult::Ok(ContractResult::from(to_binary(&PairInfo {
+                            asset_infos: [token_info, native_info],
+                            contract_addr: MOCK_PAIR.to_string(),
+                            liquidity_token: MOCK_LP_TOKEN.to_string(),
+                        })));
+                    }
+                    Ok(PairQueryMsg::Pool {}) => {
+                        return SystemResult::Ok(ContractResult::from(to_binary(&PoolResponse {
+                            assets: [
+                                Asset { info: token_info, amount: self.pool.0 },
+                                Asset { info: native_info, amount: self.pool.1 },
+                            ],
+                            total_share: self.pool.0,
+                        })));
+                    }
+                    _ => {}
+                }
+                match from_binary(msg) {
+                    Ok(Cw20QueryMsg::Balance { address: _ }) => {
+                        let balance = self.token_balances.get(contract_addr).copied().unwrap_or_default();
+                        SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse { balance })))
+                    }
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported token query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            pool: (Uint128::zero(), Uint128::zero()),
+        }
+    }
+
+    // configure the contract balance of a cw20 token
+    pub fn with_token_balance(&mut self, token: &str, balance: u128) {
+        self.token_balances.insert(token.to_string(), Uint128::new(balance));
+    }
+
+    // configure the LUART and UST reserves of the pair
+    pub fn with_pool(&mut self, token_reserve: u128, native_reserve: u128) {
+        self.pool = (Uint128::new(token_reserve), Uint128::new(native_reserve));
+    }
+}
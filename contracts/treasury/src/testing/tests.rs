@@ -0,0 +1,306 @@
+use cosmwasm_std::{
+    BankMsg, Coin, ContractResult, CosmosMsg, Decimal, DepsMut, Env, from_binary, Reply, Response,
+    SubMsg, SubMsgExecutionResponse, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::Cw20ExecuteMsg;
+use terraswap::asset::{Asset, AssetInfo};
+use terraswap::pair::ExecuteMsg as PairExecuteMsg;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{
+    AutoLiquidityResponse, BalancesResponse, ExecuteMsg, InstantiateMsg, ProposalResponse,
+    QueryMsg, RoleLimitResponse, RoleResponse,
+};
+use crate::state::{ProposalStatus, SpendLimit};
+use crate::testing::mock_querier::{mock_dependencies, MOCK_LP_TOKEN, MOCK_PAIR};
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const SPENDER: &str = "mock_spender";
+const RECIPIENT: &str = "mock_recipient";
+const DENOM: &str = "uusd";
+
+const DAY: u64 = 86_400;
+
+fn native(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        assets: vec![
+            AssetInfo::NativeToken { denom: DENOM.to_string() },
+            AssetInfo::Token { contract_addr: TOKEN.to_string() },
+        ],
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+/// Creates a marketing role allowed to spend 1000 uusd a day and assigns it to the spender
+fn setup_spender(deps: &mut DepsMut, env: Env) {
+    execute(deps.branch(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::SetRole {
+                name: "marketing".to_string(),
+                limits: vec![SpendLimit {
+                    info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+                    daily_limit: Uint128::new(1_000),
+                }],
+            }).unwrap();
+    execute(deps.branch(), env, mock_info(OWNER, &[]),
+            ExecuteMsg::SetSpender {
+                address: SPENDER.to_string(),
+                role: Some("marketing".to_string()),
+            }).unwrap();
+}
+
+fn bank_send_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![Coin { denom: DENOM.to_string(), amount: Uint128::new(amount) }],
+    }))
+}
+
+#[test]
+fn test_spend_within_daily_limit() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    setup_spender(&mut deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Spend { asset: native(100), recipient: RECIPIENT.to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::Spend {
+                          asset: Asset {
+                              info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+                              amount: Uint128::new(100),
+                          },
+                          recipient: RECIPIENT.to_string(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::NoSpendLimit { asset: TOKEN.to_string() });
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::Spend { asset: native(600), recipient: RECIPIENT.to_string() }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(RECIPIENT, 600)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::Spend { asset: native(500), recipient: RECIPIENT.to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::DailyLimitExceeded { remaining: Uint128::new(400) });
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Role { name: "marketing".to_string() }).unwrap();
+    let role: RoleResponse = from_binary(&res).unwrap();
+    assert_eq!(role.limits, vec![RoleLimitResponse {
+        info: AssetInfo::NativeToken { denom: DENOM.to_string() },
+        daily_limit: Uint128::new(1_000),
+        spent_today: Uint128::new(600),
+    }]);
+
+    // The limit resets the next day
+    env.block.time = env.block.time.plus_seconds(DAY);
+    execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+            ExecuteMsg::Spend { asset: native(1_000), recipient: RECIPIENT.to_string() }).unwrap();
+
+    // Revoked spenders cannot spend anymore
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::SetSpender { address: SPENDER.to_string(), role: None }).unwrap();
+    env.block.time = env.block.time.plus_seconds(DAY);
+    let err = execute(deps.as_mut(), env, mock_info(SPENDER, &[]),
+                      ExecuteMsg::Spend { asset: native(100), recipient: RECIPIENT.to_string() }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_spend_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    setup_spender(&mut deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ProposeSpend {
+                          asset: native(5_000),
+                          recipient: RECIPIENT.to_string(),
+                          description: "Exchange listing".to_string(),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+            ExecuteMsg::ProposeSpend {
+                asset: native(5_000),
+                recipient: RECIPIENT.to_string(),
+                description: "Exchange listing".to_string(),
+            }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::ExecuteSpend { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotApproved {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::ApproveSpend { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::ApproveSpend { proposal_id: 1 }).unwrap();
+
+    // Approved spends are not bound by the daily limits
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::ExecuteSpend { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![bank_send_msg(RECIPIENT, 5_000)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::ExecuteSpend { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotApproved {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::Proposal { proposal_id: 1 }).unwrap();
+    let proposal: ProposalResponse = from_binary(&res).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+}
+
+#[test]
+fn test_token_spend_and_balances() {
+    let mut deps = mock_dependencies(&[Coin::new(10_000, DENOM)]);
+    deps.querier.with_token_balance(TOKEN, 50_000);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::SetRole {
+                name: "grants".to_string(),
+                limits: vec![SpendLimit {
+                    info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+                    daily_limit: Uint128::new(2_000),
+                }],
+            }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::SetSpender { address: SPENDER.to_string(), role: Some("grants".to_string()) }).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(SPENDER, &[]),
+                      ExecuteMsg::Spend {
+                          asset: Asset {
+                              info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+                              amount: Uint128::new(2_000),
+                          },
+                          recipient: RECIPIENT.to_string(),
+                      }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: RECIPIENT.to_string(),
+            amount: Uint128::new(2_000),
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Balances {}).unwrap();
+    let balances: BalancesResponse = from_binary(&res).unwrap();
+    assert_eq!(balances.balances, vec![
+        native(10_000),
+        Asset {
+            info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+            amount: Uint128::new(50_000),
+        },
+    ]);
+}
+
+#[test]
+fn test_fee_liquidity() {
+    let mut deps = mock_dependencies(&[Coin::new(1_000, DENOM)]);
+    deps.querier.with_token_balance(TOKEN, 1_000);
+    deps.querier.with_pool(100_000, 20_000);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideFeeLiquidity {}).unwrap_err();
+    assert_eq!(err, ContractError::AutoLiquidityDisabled {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::UpdateAutoLiquidity {
+                          pair: MOCK_PAIR.to_string(),
+                          share: Decimal::percent(150),
+                      }).unwrap_err();
+    assert_eq!(err, ContractError::InvalidShare {});
+
+    // The LUART held before enabling is not counted as fees
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+            ExecuteMsg::UpdateAutoLiquidity { pair: MOCK_PAIR.to_string(), share: Decimal::percent(50) }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideFeeLiquidity {}).unwrap_err();
+    assert_eq!(err, ContractError::NoFeesCollected {});
+
+    // 4000 LUART of fees come in, half of it is paired with UST at 0.2 UST per LUART
+    deps.querier.with_token_balance(TOKEN, 5_000);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::ProvideFeeLiquidity {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideFeeLiquidity {}).unwrap();
+    assert_eq!(res.messages, vec![
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: MOCK_PAIR.to_string(),
+                amount: Uint128::new(2_000),
+                expires: None,
+            }).unwrap(),
+            funds: vec![],
+        }),
+        SubMsg::reply_on_success(WasmMsg::Execute {
+            contract_addr: MOCK_PAIR.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::Token { contract_addr: TOKEN.to_string() },
+                        amount: Uint128::new(2_000),
+                    },
+                    native(400),
+                ],
+                slippage_tolerance: None,
+                receiver: None,
+            }).unwrap(),
+            funds: vec![Coin::new(400, DENOM)],
+        }, 1),
+    ]);
+
+    // The pair mints the LP tokens to the treasury
+    deps.querier.with_token_balance(TOKEN, 3_000);
+    deps.querier.with_token_balance(MOCK_LP_TOKEN, 300);
+    reply(deps.as_mut(), env.clone(), Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+    }).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::AutoLiquidity {}).unwrap();
+    let auto: AutoLiquidityResponse = from_binary(&res).unwrap();
+    assert_eq!(auto.pending_fees, Uint128::zero());
+    assert_eq!(auto.token_provided, Uint128::new(2_000));
+    assert_eq!(auto.native_provided, Uint128::new(400));
+    assert_eq!(auto.lp_received, Uint128::new(300));
+
+    // The other 2000 LUART of fees stay with the treasury, only new fees are shared
+    deps.querier.with_token_balance(TOKEN, 4_000);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::AutoLiquidity {}).unwrap();
+    let auto: AutoLiquidityResponse = from_binary(&res).unwrap();
+    assert_eq!(auto.pending_fees, Uint128::new(500));
+
+    // Unless the UST runs out
+    deps.querier.with_token_balance(TOKEN, 100_000);
+    let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::ProvideFeeLiquidity {}).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {
+        denom: DENOM.to_string(),
+        required: Uint128::new(9_700),
+    });
+}
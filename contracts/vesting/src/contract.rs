@@ -0,0 +1,567 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Env, from_binary, MessageInfo, Order, Response,
+    StdError, StdResult, to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_storage_plus::{Bound, U64Key};
+use luart_protocol::staking::{Cw20HookMsg as StakingHookMsg, ExecuteMsg as StakingExecuteMsg};
+use luart_utils::batch::batch_query;
+use luart_utils::ownable::{Ownable, query_pending_owner};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionExecuteMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg,
+    MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    BENEFICIARY_GRANTS, Config, CONFIG, ConfigOwner, Grant, GRANT_COUNT, GRANTS, Unbonding, UNBONDING_COUNT,
+    UNBONDINGS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "luart-vesting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+
+    let staking_contract = match msg.staking_contract {
+        Some(staking_contract) => Some(deps.api.addr_validate(&staking_contract)?),
+        None => None,
+    };
+
+    CONFIG.save(deps.storage, &Config {
+        owner: Some(owner),
+        cw20_token_address: deps.api.addr_validate(&msg.cw20_token_address)?,
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+        staking_contract,
+        position_collection: None,
+    })?;
+    GRANT_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Claim { grant_id } => claim(deps, env, info, grant_id),
+        ExecuteMsg::MintPosition { grant_id } => mint_position(deps, info, grant_id),
+        ExecuteMsg::Stake { grant_id, amount } => stake(deps, info, grant_id, amount),
+        ExecuteMsg::Unstake { grant_id, amount } => unstake(deps, info, grant_id, amount),
+        ExecuteMsg::Revoke { grant_id } => revoke(deps, env, info, grant_id),
+        ExecuteMsg::UpdateConfig {
+            treasury,
+            staking_contract,
+            position_collection,
+        } => update_config(deps, info, treasury, staking_contract, position_collection),
+        ExecuteMsg::ProposeOwner { owner } => Ok(ConfigOwner.propose_owner(deps, info, owner)?),
+        ExecuteMsg::AcceptOwnership {} => Ok(ConfigOwner.accept_ownership(deps, info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(ConfigOwner.renounce_ownership(deps, info)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // only the LUART token can fund the grants or return their unbonded tokens
+    if config.cw20_token_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateGrant {
+            beneficiary,
+            start,
+            cliff,
+            duration,
+            revocable,
+        } => {
+            if !config.is_owner(&cw20_msg.sender) {
+                return Err(ContractError::Unauthorized {});
+            }
+            let beneficiary = deps.api.addr_validate(&beneficiary)?;
+            create_grant(deps, env, beneficiary, start, cliff, duration, cw20_msg.amount, revocable)
+        }
+        Cw20HookMsg::ReturnUnbonded {} => {
+            if config.staking_contract.as_ref().map(Addr::as_str) != Some(cw20_msg.sender.as_str()) {
+                return Err(ContractError::Unauthorized {});
+            }
+            return_unbonded(deps, cw20_msg.amount)
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_grant(
+    deps: DepsMut,
+    _env: Env,
+    beneficiary: Addr,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    total_amount: Uint128,
+    revocable: bool,
+) -> Result<Response, ContractError> {
+    if total_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if duration == 0 {
+        return Err(ContractError::InvalidSchedule {
+            reason: "duration must be greater than zero".to_string(),
+        });
+    }
+    if cliff > duration {
+        return Err(ContractError::InvalidSchedule {
+            reason: "cliff cannot exceed duration".to_string(),
+        });
+    }
+
+    let grant_id = GRANT_COUNT.load(deps.storage)? + 1;
+    GRANT_COUNT.save(deps.storage, &grant_id)?;
+
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &Grant {
+        beneficiary: beneficiary.clone(),
+        start,
+        cliff,
+        duration,
+        total_amount,
+        claimed_amount: Uint128::zero(),
+        staked_amount: Uint128::zero(),
+        unbonding_amount: Uint128::zero(),
+        revocable,
+        revoked: false,
+        position: false,
+    })?;
+    BENEFICIARY_GRANTS.save(deps.storage, (&beneficiary, U64Key::new(grant_id)), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_grant")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("total_amount", total_amount))
+}
+
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    let holder = grant_holder(deps.as_ref(), &config, grant_id, &grant)?;
+    if holder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = grant.claimable_amount(env.block.time.seconds());
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    grant.claimed_amount = grant.claimed_amount.checked_add(amount)?;
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &grant)?;
+
+    Ok(Response::new()
+        .add_message(token_transfer_msg(&config, holder.to_string(), amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("beneficiary", holder)
+        .add_attribute("amount", amount))
+}
+
+/// Returns the holder of the grant position, or the beneficiary if the grant is not a position
+fn grant_holder(deps: Deps, config: &Config, grant_id: u64, grant: &Grant) -> Result<Addr, ContractError> {
+    if !grant.position {
+        return Ok(grant.beneficiary.clone());
+    }
+
+    let collection = config.position_collection.as_ref().ok_or(ContractError::PositionNotConfigured {})?;
+    let res: OwnerOfResponse = deps.querier.query_wasm_smart(collection, &Cw721QueryMsg::OwnerOf {
+        token_id: grant_id.to_string(),
+        include_expired: None,
+    })?;
+    Ok(deps.api.addr_validate(&res.owner)?)
+}
+
+pub fn mint_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let collection = config.position_collection.ok_or(ContractError::PositionNotConfigured {})?;
+
+    let mut grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    if grant.beneficiary != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if grant.position {
+        return Err(ContractError::GrantIsPosition {});
+    }
+    // The stake is managed by the beneficiary, the holder of the position could not unstake it
+    if !grant.staked_amount.is_zero() {
+        return Err(ContractError::GrantStaked {});
+    }
+
+    grant.position = true;
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &grant)?;
+
+    let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&CollectionExecuteMsg::Mint {
+            token_id: grant_id.to_string(),
+            owner: grant.beneficiary.to_string(),
+            token_uri: None,
+            extension: None,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(mint_msg)
+        .add_attribute("action", "mint_position")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("beneficiary", grant.beneficiary))
+}
+
+pub fn stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking_contract = config.staking_contract
+        .clone()
+        .ok_or(ContractError::StakingNotConfigured {})?;
+
+    let mut grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    if grant.beneficiary != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if grant.revoked {
+        return Err(ContractError::AlreadyRevoked {});
+    }
+    if grant.position {
+        return Err(ContractError::GrantIsPosition {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let available = grant.liquid_amount();
+    if amount > available {
+        return Err(ContractError::InsufficientGrantBalance { available });
+    }
+
+    grant.staked_amount = grant.staked_amount.checked_add(amount)?;
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &grant)?;
+
+    let bond_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.cw20_token_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: staking_contract.to_string(),
+            amount,
+            msg: to_binary(&StakingHookMsg::Bond { on_behalf_of: None })?,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(bond_msg)
+        .add_attribute("action", "stake")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("beneficiary", grant.beneficiary)
+        .add_attribute("amount", amount))
+}
+
+pub fn unstake(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking_contract = config.staking_contract
+        .clone()
+        .ok_or(ContractError::StakingNotConfigured {})?;
+
+    let mut grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    if grant.beneficiary != info.sender && !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if amount > grant.staked_amount {
+        return Err(ContractError::InsufficientGrantBalance {
+            available: grant.staked_amount,
+        });
+    }
+
+    // The unbonded tokens stay locked until the staking contract returns them to this contract
+    grant.staked_amount = grant.staked_amount.checked_sub(amount)?;
+    grant.unbonding_amount = grant.unbonding_amount.checked_add(amount)?;
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &grant)?;
+
+    let unbonding_id = UNBONDING_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    UNBONDING_COUNT.save(deps.storage, &unbonding_id)?;
+    UNBONDINGS.save(deps.storage, U64Key::new(unbonding_id), &Unbonding { grant_id, amount })?;
+
+    let unbond_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: staking_contract.to_string(),
+        msg: to_binary(&StakingExecuteMsg::Unbond { amount, on_behalf_of: None })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(unbond_msg)
+        .add_attribute("action", "unstake")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("beneficiary", grant.beneficiary)
+        .add_attribute("amount", amount))
+}
+
+/// Credits the returned tokens to the oldest unstakes first, the staking contract paying the
+/// unbonds in the order they were requested
+pub fn return_unbonded(deps: DepsMut, amount: Uint128) -> Result<Response, ContractError> {
+    let mut remaining = amount;
+    let mut returns = vec![];
+    for item in UNBONDINGS.range(deps.storage, None, None, Order::Ascending) {
+        if remaining.is_zero() {
+            break;
+        }
+        let (key, unbonding) = item?;
+        let returned = remaining.min(unbonding.amount);
+        remaining = remaining.checked_sub(returned)?;
+        returns.push((key, unbonding, returned));
+    }
+    if !remaining.is_zero() {
+        return Err(ContractError::ExceedsUnbonding { unbonding: amount.checked_sub(remaining)? });
+    }
+
+    for (key, mut unbonding, returned) in returns {
+        GRANTS.update(deps.storage, U64Key::new(unbonding.grant_id), |grant| -> Result<_, ContractError> {
+            let mut grant = grant.ok_or_else(|| StdError::not_found("Grant"))?;
+            grant.unbonding_amount = grant.unbonding_amount.checked_sub(returned)?;
+            Ok(grant)
+        })?;
+        unbonding.amount = unbonding.amount.checked_sub(returned)?;
+        if unbonding.amount.is_zero() {
+            UNBONDINGS.remove(deps.storage, U64Key::from(key));
+        } else {
+            UNBONDINGS.save(deps.storage, U64Key::from(key), &unbonding)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "return_unbonded")
+        .add_attribute("amount", amount))
+}
+
+pub fn revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    if !grant.revocable {
+        return Err(ContractError::NotRevocable {});
+    }
+    if grant.revoked {
+        return Err(ContractError::AlreadyRevoked {});
+    }
+
+    // Freeze the grant at the currently vested amount, the beneficiary keeps it claimable
+    let vested_amount = grant.vested_amount(env.block.time.seconds());
+    let unvested_amount = grant.total_amount.checked_sub(vested_amount)?;
+    if unvested_amount > grant.liquid_amount() {
+        return Err(ContractError::GrantStaked {});
+    }
+    grant.total_amount = vested_amount;
+    grant.revoked = true;
+    GRANTS.save(deps.storage, U64Key::new(grant_id), &grant)?;
+
+    let mut response = Response::new();
+    if !unvested_amount.is_zero() {
+        response = response.add_message(
+            token_transfer_msg(&config, config.treasury.to_string(), unvested_amount)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "revoke")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("vested_amount", vested_amount)
+        .add_attribute("returned_amount", unvested_amount))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    treasury: Option<String>,
+    staking_contract: Option<String>,
+    position_collection: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = deps.api.addr_validate(&treasury)?;
+    }
+
+    if let Some(staking_contract) = staking_contract {
+        config.staking_contract = Some(deps.api.addr_validate(&staking_contract)?);
+    }
+
+    if let Some(position_collection) = position_collection {
+        config.position_collection = Some(deps.api.addr_validate(&position_collection)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Grant { grant_id } => to_binary(&query_grant(deps, env, grant_id)?),
+        QueryMsg::GrantsOf {
+            beneficiary,
+            start_after,
+            limit,
+        } => to_binary(&query_grants_of(deps, env, beneficiary, start_after, limit)?),
+        QueryMsg::Batch { queries } => {
+            let is_batch = |q: &QueryMsg| matches!(q, QueryMsg::Batch { .. });
+            to_binary(&batch_query(queries, is_batch, |q| query(deps, env.clone(), q))?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|o| o.to_string()),
+        pending_owner: query_pending_owner(deps.storage)?.map(|o| o.to_string()),
+        cw20_token_address: config.cw20_token_address.to_string(),
+        treasury: config.treasury.to_string(),
+        staking_contract: config.staking_contract.map(|addr| addr.to_string()),
+        position_collection: config.position_collection.map(|addr| addr.to_string()),
+    })
+}
+
+pub fn query_grant(deps: Deps, env: Env, grant_id: u64) -> StdResult<GrantResponse> {
+    let grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+    Ok(grant_response(grant_id, grant, env.block.time.seconds()))
+}
+
+pub fn query_grants_of(
+    deps: Deps,
+    env: Env,
+    beneficiary: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GrantsResponse> {
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let time = env.block.time.seconds();
+
+    let grants: StdResult<Vec<GrantResponse>> = BENEFICIARY_GRANTS
+        .prefix(&beneficiary)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|k| {
+            let grant_id = parse_grant_id(&k)?;
+            let grant = GRANTS.load(deps.storage, U64Key::new(grant_id))?;
+            Ok(grant_response(grant_id, grant, time))
+        })
+        .collect();
+
+    Ok(GrantsResponse { grants: grants? })
+}
+
+fn grant_response(grant_id: u64, grant: Grant, time: u64) -> GrantResponse {
+    GrantResponse {
+        grant_id,
+        beneficiary: grant.beneficiary.to_string(),
+        start: grant.start,
+        cliff: grant.cliff,
+        duration: grant.duration,
+        total_amount: grant.total_amount,
+        vested_amount: grant.vested_amount(time),
+        claimed_amount: grant.claimed_amount,
+        staked_amount: grant.staked_amount,
+        unbonding_amount: grant.unbonding_amount,
+        claimable_amount: grant.claimable_amount(time),
+        revocable: grant.revocable,
+        revoked: grant.revoked,
+        position: grant.position,
+    }
+}
+
+fn parse_grant_id(key: &[u8]) -> StdResult<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupted grant key"))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn token_transfer_msg(config: &Config, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.cw20_token_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use luart_utils::ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Invalid vesting schedule: {reason}")]
+    InvalidSchedule { reason: String },
+
+    #[error("Grant is not revocable")]
+    NotRevocable {},
+
+    #[error("Grant is already revoked")]
+    AlreadyRevoked {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Staking contract is not configured")]
+    StakingNotConfigured {},
+
+    #[error("Amount exceeds the grant {available} available tokens")]
+    InsufficientGrantBalance { available: Uint128 },
+
+    #[error("Amount exceeds the {unbonding} unbonding tokens")]
+    ExceedsUnbonding { unbonding: Uint128 },
+
+    #[error("Unvested grant tokens are staked, unstake them first")]
+    GrantStaked {},
+
+    #[error("Position collection is not configured")]
+    PositionNotConfigured {},
+
+    #[error("The grant is held as a position")]
+    GrantIsPosition {},
+}
+
+impl From<OwnershipError> for ContractError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => ContractError::Std(err),
+            OwnershipError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Empty, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// This structure describes the parameters used for creating a vesting contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The owner address, defaults to the sender
+    pub owner: Option<String>,
+    /// The LUART token contract address
+    pub cw20_token_address: String,
+    /// The address receiving the unvested remainder of revoked grants
+    pub treasury: String,
+    /// The staking contract grant tokens can be bonded into
+    pub staking_contract: Option<String>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives tokens funding a new grant, see [`Cw20HookMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Transfers the vested and not yet claimed tokens to the grant beneficiary, or to the
+    /// holder of the grant position
+    Claim { grant_id: u64 },
+    /// Mints the grant as a transferable position NFT to the beneficiary, the holder of the
+    /// position claiming the grant from then on. Staked grants cannot become positions.
+    /// Only the beneficiary can execute it.
+    MintPosition { grant_id: u64 },
+    /// Bonds grant tokens into the staking contract, this contract being the staker of record.
+    /// Staked tokens keep vesting but cannot be claimed until they are unstaked.
+    /// Grants held as positions cannot be staked.
+    Stake { grant_id: u64, amount: Uint128 },
+    /// Unbonds grant tokens from the staking contract. They stay locked until the staking
+    /// contract returns the unbonded tokens through [`Cw20HookMsg::ReturnUnbonded`].
+    /// Both the beneficiary and the owner can execute it.
+    Unstake { grant_id: u64, amount: Uint128 },
+    /// Stops a revocable grant and returns the unvested remainder to the treasury.
+    /// Only the owner can execute it.
+    Revoke { grant_id: u64 },
+    /// Updates the contract configuration. Only the owner can execute it.
+    UpdateConfig {
+        treasury: Option<String>,
+        staking_contract: Option<String>,
+        position_collection: Option<String>,
+    },
+    /// Proposes a new owner who has to accept the ownership. Only the owner can execute it.
+    ProposeOwner { owner: String },
+    /// Takes the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Removes the owner for good, disabling the owner actions. Only the owner can execute it.
+    RenounceOwnership {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates a grant for the sent amount. Only the owner can send it.
+    CreateGrant {
+        beneficiary: String,
+        /// Vesting start timestamp in seconds
+        start: u64,
+        /// Seconds after start before anything is vested
+        cliff: u64,
+        /// Seconds after start when the grant is fully vested
+        duration: u64,
+        revocable: bool,
+    },
+    /// Returns unbonded tokens to the grants in the order they were unstaked, making them
+    /// claimable once vested. Only the staking contract can send it.
+    ReturnUnbonded {},
+}
+
+/// The messages executed on the position collection, a subset of the cw721-base execute messages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Option<Empty>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration.
+    /// Return type: ConfigResponse.
+    Config {},
+    /// Returns the grant with its vested and claimable amounts at the current block time.
+    /// Return type: GrantResponse.
+    Grant { grant_id: u64 },
+    /// Returns all grants of the beneficiary. Supports pagination.
+    /// Return type: GrantsResponse.
+    GrantsOf {
+        beneficiary: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Runs up to 30 queries in one call, returning their results in order.
+    /// Return type: BatchResponse.
+    Batch { queries: Vec<QueryMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub cw20_token_address: String,
+    pub treasury: String,
+    pub staking_contract: Option<String>,
+    pub position_collection: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GrantResponse {
+    pub grant_id: u64,
+    pub beneficiary: String,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub total_amount: Uint128,
+    pub vested_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub staked_amount: Uint128,
+    pub unbonding_amount: Uint128,
+    pub claimable_amount: Uint128,
+    pub revocable: bool,
+    pub revoked: bool,
+    pub position: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GrantsResponse {
+    pub grants: Vec<GrantResponse>,
+}
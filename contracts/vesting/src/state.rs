@@ -0,0 +1,125 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use luart_utils::ownable::Ownable;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address who can create and revoke grants
+    /// Unset once the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The LUART token contract address
+    pub cw20_token_address: Addr,
+    /// The address receiving the unvested remainder of revoked grants
+    pub treasury: Addr,
+    /// The staking contract grant tokens can be bonded into
+    pub staking_contract: Option<Addr>,
+    /// The cw721 collection minting the grant positions, this contract being its minter
+    #[serde(default)]
+    pub position_collection: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Grant {
+    pub beneficiary: Addr,
+    /// Vesting start timestamp in seconds
+    pub start: u64,
+    /// Seconds after start before anything is vested
+    pub cliff: u64,
+    /// Seconds after start when the grant is fully vested
+    pub duration: u64,
+    /// The total amount of tokens of the grant
+    pub total_amount: Uint128,
+    /// The amount of tokens already claimed by the beneficiary
+    pub claimed_amount: Uint128,
+    /// The amount of tokens currently bonded into the staking contract
+    pub staked_amount: Uint128,
+    /// The amount of tokens unbonded from the staking contract and not returned by it yet
+    #[serde(default)]
+    pub unbonding_amount: Uint128,
+    /// Whether the owner can revoke the unvested remainder
+    pub revocable: bool,
+    /// Set once the grant was revoked, total_amount is then the amount vested at revocation
+    pub revoked: bool,
+    /// Whether the grant is held as a position NFT of the position collection, its token id
+    /// being the grant id. The holder of the position claims the grant instead of the beneficiary.
+    #[serde(default)]
+    pub position: bool,
+}
+
+impl Grant {
+    /// Returns the amount of tokens vested at the given timestamp
+    pub fn vested_amount(&self, time: u64) -> Uint128 {
+        if self.revoked {
+            return self.total_amount;
+        }
+        if time < self.start + self.cliff {
+            return Uint128::zero();
+        }
+        let elapsed = time - self.start;
+        if elapsed >= self.duration {
+            return self.total_amount;
+        }
+        self.total_amount.multiply_ratio(elapsed, self.duration)
+    }
+
+    /// Returns the amount of grant tokens held by this contract, i.e. not claimed, staked nor
+    /// unbonding
+    pub fn liquid_amount(&self) -> Uint128 {
+        self.total_amount
+            .saturating_sub(self.claimed_amount)
+            .saturating_sub(self.staked_amount)
+            .saturating_sub(self.unbonding_amount)
+    }
+
+    /// Returns the amount of tokens the beneficiary can claim at the given timestamp.
+    /// Vested tokens which are still staked have to be unstaked and returned first.
+    pub fn claimable_amount(&self, time: u64) -> Uint128 {
+        self.vested_amount(time)
+            .saturating_sub(self.claimed_amount)
+            .min(self.liquid_amount())
+    }
+}
+
+impl Config {
+    pub fn is_owner(&self, address: &str) -> bool {
+        match &self.owner {
+            Some(owner) => owner.as_str() == address,
+            None => false,
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The owner of the configuration, transferred in two phases
+pub struct ConfigOwner;
+
+impl Ownable for ConfigOwner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(CONFIG.load(storage)?.owner)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        CONFIG.update(storage, |mut config| -> StdResult<_> {
+            config.owner = owner;
+            Ok(config)
+        })?;
+        Ok(())
+    }
+}
+/// An unstake waiting for the staking contract to return the unbonded tokens
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unbonding {
+    pub grant_id: u64,
+    pub amount: Uint128,
+}
+
+pub const GRANT_COUNT: Item<u64> = Item::new("grant_count");
+pub const GRANTS: Map<U64Key, Grant> = Map::new("grants");
+pub const BENEFICIARY_GRANTS: Map<(&Addr, U64Key), bool> = Map::new("beneficiary_grants");
+pub const UNBONDING_COUNT: Item<u64> = Item::new("unbonding_count");
+/// The unstakes not returned yet, in the order they were requested
+pub const UNBONDINGS: Map<U64Key, Unbonding> = Map::new("unbondings");
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, ContractResult, Empty, from_binary, from_slice, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, to_binary, WasmQuery,
+};
+use cosmwasm_std::testing::{MOCK_CONTRACT_ADDR, MockApi, MockQuerier, MockStorage};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier which answers the cw721 owner queries.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    /// The owner of each (collection, token id)
+    owners: HashMap<(String, String), String>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg) {
+                    Ok(Cw721QueryMsg::OwnerOf { token_id, .. }) => {
+                        match self.owners.get(&(contract_addr.clone(), token_id)) {
+                            Some(owner) => SystemResult::Ok(ContractResult::from(to_binary(&OwnerOfResponse {
+                                owner: owner.clone(),
+                                approvals: vec![],
+                            }))),
+                            None => SystemResult::Ok(ContractResult::Err("Token not found".to_string())),
+                        }
+                    }
+                    _ => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Unsupported collection query".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            owners: HashMap::new(),
+        }
+    }
+
+    // configure the owner of a token
+    pub fn with_owner(&mut self, collection: &str, token_id: &str, owner: &str) {
+        self.owners.insert((collection.to_string(), token_id.to_string()), owner.to_string());
+    }
+}
@@ -0,0 +1,342 @@
+use cosmwasm_std::{CosmosMsg, Deps, DepsMut, Env, from_binary, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use luart_protocol::staking::{Cw20HookMsg as StakingHookMsg, ExecuteMsg as StakingExecuteMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionExecuteMsg, Cw20HookMsg, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg,
+};
+use crate::testing::mock_querier::mock_dependencies;
+
+const OWNER: &str = "mock_owner";
+const TOKEN: &str = "mock_token";
+const TREASURY: &str = "mock_treasury";
+const BENEFICIARY: &str = "mock_beneficiary";
+const STAKING: &str = "mock_staking";
+const POSITIONS: &str = "mock_positions";
+
+const DAY: u64 = 86_400;
+
+fn default_instantiate(
+    deps: DepsMut,
+    env: Env,
+) -> Response {
+    let msg = InstantiateMsg {
+        owner: None,
+        cw20_token_address: TOKEN.to_string(),
+        treasury: TREASURY.to_string(),
+        staking_contract: Some(STAKING.to_string()),
+    };
+    instantiate(deps, env, mock_info(OWNER, &[]), msg).unwrap()
+}
+
+fn create_grant(deps: DepsMut, env: Env, sender: &str, revocable: bool) -> Result<Response, ContractError> {
+    let start = env.block.time.seconds();
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: to_binary(&Cw20HookMsg::CreateGrant {
+                    beneficiary: BENEFICIARY.to_string(),
+                    start,
+                    cliff: 30 * DAY,
+                    duration: 100 * DAY,
+                    revocable,
+                }).unwrap(),
+            }))
+}
+
+fn return_unbonded(deps: DepsMut, env: Env, sender: &str, amount: u128) -> Result<Response, ContractError> {
+    execute(deps, env, mock_info(TOKEN, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::ReturnUnbonded {}).unwrap(),
+            }))
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        }).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn test_create_grant() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+
+    // Only the owner can fund grants
+    let err = create_grant(deps.as_mut(), env.clone(), "anyone", true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    create_grant(deps.as_mut(), env.clone(), OWNER, true).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::GrantsOf {
+        beneficiary: BENEFICIARY.to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap();
+    let grants: GrantsResponse = from_binary(&res).unwrap();
+    assert_eq!(grants.grants, vec![GrantResponse {
+        grant_id: 1,
+        beneficiary: BENEFICIARY.to_string(),
+        start: env.block.time.seconds(),
+        cliff: 30 * DAY,
+        duration: 100 * DAY,
+        total_amount: Uint128::new(1_000_000),
+        vested_amount: Uint128::zero(),
+        claimed_amount: Uint128::zero(),
+        staked_amount: Uint128::zero(),
+        unbonding_amount: Uint128::zero(),
+        claimable_amount: Uint128::zero(),
+        revocable: true,
+        revoked: false,
+        position: false,
+    }]);
+}
+
+#[test]
+fn test_claim() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+
+    // Nothing is vested before the cliff
+    env.block.time = env.block.time.plus_seconds(29 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Vesting is linear from the start once the cliff has passed
+    env.block.time = env.block.time.plus_seconds(21 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 500_000)]);
+
+    // Only the remainder is claimable after the end
+    env.block.time = env.block.time.plus_seconds(60 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 500_000)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Grant { grant_id: 1 }).unwrap();
+    let grant: GrantResponse = from_binary(&res).unwrap();
+    assert_eq!(grant.claimed_amount, Uint128::new(1_000_000));
+    assert_eq!(grant.claimable_amount, Uint128::zero());
+}
+
+#[test]
+fn test_revoke() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+    create_grant(deps.as_mut(), env.clone(), OWNER, true).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NotRevocable {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Revoke { grant_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Revoke with 40% vested, the remainder goes back to the treasury
+    env.block.time = env.block.time.plus_seconds(40 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(TREASURY, 600_000)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRevoked {});
+
+    // The vested part stays claimable and does not grow anymore
+    env.block.time = env.block.time.plus_seconds(100 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 400_000)]);
+}
+
+#[test]
+fn test_stake_locked_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_grant(deps.as_mut(), env.clone(), OWNER, true).unwrap();
+
+    // Only the beneficiary can stake the grant
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]),
+                      ExecuteMsg::Stake { grant_id: 1, amount: Uint128::new(800_000) }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Stake { grant_id: 1, amount: Uint128::new(1_000_001) }).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientGrantBalance { available: Uint128::new(1_000_000) });
+
+    // Locked tokens are bonded by this contract, the unbonded tokens coming back to it
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Stake { grant_id: 1, amount: Uint128::new(800_000) }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: STAKING.to_string(),
+            amount: Uint128::new(800_000),
+            msg: to_binary(&StakingHookMsg::Bond { on_behalf_of: None }).unwrap(),
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+
+    // Staked tokens are not withdrawable even once vested
+    env.block.time = env.block.time.plus_seconds(50 * DAY);
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 200_000)]);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // The unvested part cannot be revoked while staked
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::GrantStaked {});
+
+    // The owner can unstake to revoke
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Unstake { grant_id: 1, amount: Uint128::new(800_000) }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: STAKING.to_string(),
+        msg: to_binary(&StakingExecuteMsg::Unbond { amount: Uint128::new(800_000), on_behalf_of: None }).unwrap(),
+        funds: vec![],
+    }))]);
+
+    // The unbonding tokens are not back yet
+    let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::GrantStaked {});
+
+    return_unbonded(deps.as_mut(), env.clone(), STAKING, 800_000).unwrap();
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]),
+                      ExecuteMsg::Revoke { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(TREASURY, 500_000)]);
+}
+
+#[test]
+fn test_claim_while_unbonding() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::Stake { grant_id: 1, amount: Uint128::new(1_000_000) }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::Stake { grant_id: 2, amount: Uint128::new(200_000) }).unwrap();
+    env.block.time = env.block.time.plus_seconds(50 * DAY);
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::Unstake { grant_id: 1, amount: Uint128::new(1_000_000) }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::Unstake { grant_id: 2, amount: Uint128::new(200_000) }).unwrap();
+
+    // The unbonding grant cannot claim the tokens of the other grant
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 2 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 500_000)]);
+
+    // Only the staking contract returns the unbonded tokens, up to the unbonding amount
+    let err = return_unbonded(deps.as_mut(), env.clone(), BENEFICIARY, 1_000_000).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = return_unbonded(deps.as_mut(), env.clone(), STAKING, 1_200_001).unwrap_err();
+    assert_eq!(err, ContractError::ExceedsUnbonding { unbonding: Uint128::new(1_200_000) });
+
+    // The oldest unstakes are returned first
+    return_unbonded(deps.as_mut(), env.clone(), STAKING, 1_100_000).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg(BENEFICIARY, 500_000)]);
+
+    let unbonding_amount = |deps: Deps, grant_id: u64| {
+        let res = query(deps, env.clone(), QueryMsg::Grant { grant_id }).unwrap();
+        from_binary::<GrantResponse>(&res).unwrap().unbonding_amount
+    };
+    assert_eq!(unbonding_amount(deps.as_ref(), 1), Uint128::zero());
+    assert_eq!(unbonding_amount(deps.as_ref(), 2), Uint128::new(100_000));
+    return_unbonded(deps.as_mut(), env.clone(), STAKING, 100_000).unwrap();
+    assert_eq!(unbonding_amount(deps.as_ref(), 2), Uint128::zero());
+}
+
+#[test]
+fn test_position() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    default_instantiate(deps.as_mut(), env.clone());
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+    create_grant(deps.as_mut(), env.clone(), OWNER, false).unwrap();
+
+    let mint_position = |grant_id: u64| ExecuteMsg::MintPosition { grant_id };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]), mint_position(1)).unwrap_err();
+    assert_eq!(err, ContractError::PositionNotConfigured {});
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::UpdateConfig {
+        treasury: None,
+        staking_contract: None,
+        position_collection: Some(POSITIONS.to_string()),
+    }).unwrap();
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), mint_position(1)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+            ExecuteMsg::Stake { grant_id: 2, amount: Uint128::new(1) }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]), mint_position(2)).unwrap_err();
+    assert_eq!(err, ContractError::GrantStaked {});
+
+    // The position is minted to the beneficiary with the grant id as token id
+    let res = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]), mint_position(1)).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: POSITIONS.to_string(),
+        msg: to_binary(&CollectionExecuteMsg::Mint {
+            token_id: "1".to_string(),
+            owner: BENEFICIARY.to_string(),
+            token_uri: None,
+            extension: None,
+        }).unwrap(),
+        funds: vec![],
+    }))]);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]), mint_position(1)).unwrap_err();
+    assert_eq!(err, ContractError::GrantIsPosition {});
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Stake { grant_id: 1, amount: Uint128::new(1) }).unwrap_err();
+    assert_eq!(err, ContractError::GrantIsPosition {});
+
+    // Once traded, the position holder claims the grant
+    deps.querier.with_owner(POSITIONS, "1", "mock_buyer");
+    env.block.time = env.block.time.plus_seconds(50 * DAY);
+    let err = execute(deps.as_mut(), env.clone(), mock_info(BENEFICIARY, &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info("mock_buyer", &[]),
+                      ExecuteMsg::Claim { grant_id: 1 }).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("mock_buyer", 500_000)]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::Grant { grant_id: 1 }).unwrap();
+    let grant: GrantResponse = from_binary(&res).unwrap();
+    assert!(grant.position);
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use bitcoin::secp256k1::{All, Secp256k1};
+use serde::Serialize;
+use serde_json::Value;
+use terra_rust_api::messages::wasm::{MsgExecuteContract, MsgInstantiateContract, MsgStoreCode};
+use terra_rust_api::messages::Message;
+use terra_rust_api::{GasOptions, PrivateKey, Terra};
+
+use crate::config::NetworkConfig;
+
+/// The number of times a broadcast transaction is looked up before giving up
+const TX_POLL_ATTEMPTS: u32 = 30;
+const TX_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Signs and broadcasts the deployment transactions of a single account
+pub struct Chain {
+    terra: Terra,
+    lcd: String,
+    secp: Secp256k1<All>,
+    key: PrivateKey,
+    pub sender: String,
+}
+
+impl Chain {
+    pub fn connect(config: &NetworkConfig, mnemonic: &str) -> Result<Self> {
+        let gas_options = GasOptions::create_with_gas_estimate(&config.gas_price, config.gas_adjustment)?;
+        let terra = Terra::lcd_client(&config.lcd, &config.chain_id, &gas_options, None);
+        let secp = Secp256k1::new();
+        let key = PrivateKey::from_words(&secp, mnemonic, 0, 0)?;
+        let sender = key.public_key(&secp).account()?;
+
+        Ok(Chain {
+            terra,
+            lcd: config.lcd.trim_end_matches('/').to_string(),
+            secp,
+            key,
+            sender,
+        })
+    }
+
+    /// Uploads the wasm file and returns its code id
+    pub async fn store_code(&self, wasm: &Path) -> Result<u64> {
+        let msg = MsgStoreCode::create_from_file(&self.sender, wasm)?;
+        let tx = self.broadcast(msg).await?;
+        find_attribute(&tx, "store_code", "code_id")?
+            .parse()
+            .map_err(|_| anyhow!("invalid code id"))
+    }
+
+    /// Instantiates the code with the deployer as admin and returns the contract address
+    pub async fn instantiate<M: Serialize>(&self, code_id: u64, msg: &M) -> Result<String> {
+        let msg = MsgInstantiateContract::create_from_json(
+            &self.sender,
+            Some(self.sender.clone()),
+            code_id,
+            &serde_json::to_string(msg)?,
+            vec![],
+        )?;
+        let tx = self.broadcast(msg).await?;
+        find_attribute(&tx, "instantiate_contract", "contract_address")
+    }
+
+    pub async fn execute<M: Serialize>(&self, contract: &str, msg: &M) -> Result<()> {
+        let msg = MsgExecuteContract::create_from_json(&self.sender, contract, &serde_json::to_string(msg)?, vec![])?;
+        self.broadcast(msg).await?;
+        Ok(())
+    }
+
+    /// Broadcasts the message and waits for its transaction to be included in a block
+    async fn broadcast(&self, msg: Message) -> Result<Value> {
+        let res = self.terra.submit_transaction_sync(&self.secp, &self.key, vec![msg], None).await?;
+        if res.code.unwrap_or_default() != 0 {
+            bail!("transaction {} rejected: {}", res.txhash, res.raw_log);
+        }
+
+        let url = format!("{}/cosmos/tx/v1beta1/txs/{}", self.lcd, res.txhash);
+        for _ in 0..TX_POLL_ATTEMPTS {
+            tokio::time::sleep(TX_POLL_INTERVAL).await;
+            let tx: Value = reqwest::get(&url).await?.json().await?;
+            let tx_response = &tx["tx_response"];
+            if tx_response.is_null() {
+                continue;
+            }
+            if tx_response["code"].as_u64().unwrap_or_default() != 0 {
+                bail!("transaction {} failed: {}", res.txhash, tx_response["raw_log"]);
+            }
+            return Ok(tx_response.clone());
+        }
+        bail!("transaction {} was not included in time", res.txhash)
+    }
+}
+
+/// Reads an attribute of the first event of the given type in the transaction logs
+fn find_attribute(tx_response: &Value, event_type: &str, key: &str) -> Result<String> {
+    tx_response["logs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|log| log["events"].as_array().into_iter().flatten())
+        .filter(|event| event["type"] == event_type)
+        .flat_map(|event| event["attributes"].as_array().into_iter().flatten())
+        .find(|attribute| attribute["key"] == key)
+        .and_then(|attribute| attribute["value"].as_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("no {} attribute in the {} events", key, event_type))
+}
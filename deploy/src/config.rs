@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use cosmwasm_std::Uint128;
+use serde::{Deserialize, Serialize};
+
+/// The chain and the protocol parameters of a network, read from `networks/<network>.json`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NetworkConfig {
+    pub chain_id: String,
+    pub lcd: String,
+    pub gas_price: String,
+    pub gas_adjustment: f64,
+    /// The native denom the launchpad contributions are paid in
+    pub payment_denom: String,
+    /// The staking contract, deployed from its own repository
+    pub staking: Option<String>,
+    /// The address receiving the launchpad funds and the clawed back airdrops,
+    /// defaults to the deployer
+    pub treasury: Option<String>,
+    /// The LUART supply minted to the deployer
+    pub initial_supply: Uint128,
+}
+
+impl NetworkConfig {
+    pub fn load(network: &str) -> Result<Self> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("networks").join(format!("{}.json", network));
+        let config = fs::read(&path).with_context(|| format!("unknown network {}", network))?;
+        Ok(serde_json::from_slice(&config)?)
+    }
+}
+
+/// The code ids and addresses deployed so far, saved after each step so that a deployment
+/// stopped halfway resumes where it stopped instead of deploying the contracts twice
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Deployment {
+    pub code_ids: BTreeMap<String, u64>,
+    pub contracts: BTreeMap<String, String>,
+    /// The wiring steps already executed
+    pub wired: Vec<String>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Deployment {
+    pub fn load(network: &str) -> Result<Self> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("deployments").join(format!("{}.json", network));
+        let mut deployment: Deployment = match fs::read(&path) {
+            Ok(deployment) => serde_json::from_slice(&deployment)?,
+            Err(_) => Deployment::default(),
+        };
+        deployment.path = path;
+        Ok(deployment)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn contract(&self, name: &str) -> Result<&str> {
+        self.contracts
+            .get(name)
+            .map(String::as_str)
+            .with_context(|| format!("{} is not deployed", name))
+    }
+}
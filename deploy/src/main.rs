@@ -0,0 +1,160 @@
+//! Deploys the Luart contracts in order and registers their addresses in each other's
+//! configs. Every step is recorded in `deployments/<network>.json`, so running it again
+//! resumes the deployment and leaves the deployed contracts untouched.
+//!
+//! Build the artifacts with `build-all-terra-contracts.sh` first, then run
+//! `MNEMONIC="..." cargo run -p luart-deploy -- <testnet|localterra> [artifacts dir]`.
+//!
+//! The staking contract lives outside this repository, its address is read from the
+//! network config. The marketplace is deployed along with it, none of the contracts here
+//! reference it.
+
+mod chain;
+mod config;
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use cw20::Cw20Coin;
+use luart_protocol::registry;
+use luart_registry::msg::AddressEntry;
+use serde::Serialize;
+use terraswap::asset::AssetInfo;
+
+use crate::chain::Chain;
+use crate::config::{Deployment, NetworkConfig};
+
+/// The contracts stored from the artifacts, in deployment order
+const CODES: [&str; 5] = [
+    "luart_token",
+    "luart_registry",
+    "luart_fee_distributor",
+    "luart_airdrop",
+    "luart_launchpad",
+];
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let network = args.next().context("usage: luart-deploy <network> [artifacts dir]")?;
+    let artifacts = args.next().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("artifacts"));
+    let mnemonic = env::var("MNEMONIC").context("MNEMONIC is not set")?;
+
+    let config = NetworkConfig::load(&network)?;
+    let chain = Chain::connect(&config, &mnemonic)?;
+    let mut deployment = Deployment::load(&network)?;
+    println!("Deploying to {} from {}", config.chain_id, chain.sender);
+
+    store_codes(&chain, &mut deployment, &artifacts).await?;
+    instantiate_contracts(&chain, &config, &mut deployment).await?;
+    wire_contracts(&chain, &mut deployment).await?;
+
+    println!("{}", serde_json::to_string_pretty(&deployment)?);
+    Ok(())
+}
+
+async fn store_codes(chain: &Chain, deployment: &mut Deployment, artifacts: &Path) -> Result<()> {
+    for name in CODES {
+        if deployment.code_ids.contains_key(name) {
+            continue;
+        }
+        let code_id = chain.store_code(&artifacts.join(format!("{}.wasm", name))).await?;
+        println!("Stored {} as code {}", name, code_id);
+        deployment.code_ids.insert(name.to_string(), code_id);
+        deployment.save()?;
+    }
+    Ok(())
+}
+
+async fn instantiate_contracts(chain: &Chain, config: &NetworkConfig, deployment: &mut Deployment) -> Result<()> {
+    let staking = config.staking.clone().context("the network config has no staking contract")?;
+    let treasury = config.treasury.clone().unwrap_or_else(|| chain.sender.clone());
+
+    // The deployer collects the swap fees until the fee distributor is registered
+    let token_msg = luart_token::msg::InstantiateMsg {
+        name: "Luart".to_string(),
+        symbol: "LUART".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: chain.sender.clone(), amount: config.initial_supply }],
+        mint: None,
+        swap_fee_config: Some(luart_token::msg::SwapFeeConfigResponse {
+            fee_admin: chain.sender.clone(),
+            enable_swap_fee: true,
+            swap_percent_fee: "2".parse()?,
+            fee_receiver: chain.sender.clone(),
+        }),
+        pauser: None,
+    };
+    instantiate(chain, deployment, "luart_token", &token_msg).await?;
+    let token = deployment.contract("luart_token")?.to_string();
+
+    // The contracts resolve their peers through the registry, the deployer hands it over
+    // to the governance once deployed
+    let registry_msg = luart_registry::msg::InstantiateMsg {
+        owner: None,
+        addresses: vec![
+            AddressEntry { name: registry::TOKEN.to_string(), address: token.clone() },
+            AddressEntry { name: registry::STAKING.to_string(), address: staking.clone() },
+            AddressEntry { name: registry::TREASURY.to_string(), address: treasury.clone() },
+        ],
+    };
+    instantiate(chain, deployment, "luart_registry", &registry_msg).await?;
+    let registry = deployment.contract("luart_registry")?.to_string();
+
+    let fee_distributor_msg = luart_fee_distributor::msg::InstantiateMsg {
+        owner: None,
+        staking: staking.clone(),
+        assets: vec![AssetInfo::Token { contract_addr: token.clone() }],
+        start_time: None,
+        registry: Some(registry.clone()),
+        token: None,
+    };
+    instantiate(chain, deployment, "luart_fee_distributor", &fee_distributor_msg).await?;
+
+    let airdrop_msg = luart_airdrop::msg::InstantiateMsg {
+        owner: None,
+        cw20_token_address: token,
+        treasury: Some(treasury.clone()),
+    };
+    instantiate(chain, deployment, "luart_airdrop", &airdrop_msg).await?;
+
+    let launchpad_msg = luart_launchpad::msg::InstantiateMsg {
+        owner: None,
+        staking_contract: staking,
+        payment_denom: config.payment_denom.clone(),
+        treasury,
+        registry: Some(registry),
+    };
+    instantiate(chain, deployment, "luart_launchpad", &launchpad_msg).await
+}
+
+async fn instantiate<M: Serialize>(chain: &Chain, deployment: &mut Deployment, name: &str, msg: &M) -> Result<()> {
+    if deployment.contracts.contains_key(name) {
+        return Ok(());
+    }
+    let code_id = *deployment.code_ids.get(name).with_context(|| format!("{} is not stored", name))?;
+    let address = chain.instantiate(code_id, msg).await?;
+    println!("Instantiated {} at {}", name, address);
+    deployment.contracts.insert(name.to_string(), address);
+    deployment.save()
+}
+
+async fn wire_contracts(chain: &Chain, deployment: &mut Deployment) -> Result<()> {
+    let step = "token_fee_receiver";
+    if deployment.wired.iter().any(|wired| wired == step) {
+        return Ok(());
+    }
+
+    // The swap fees are distributed to the stakers
+    let msg = luart_token::msg::ExecuteMsg::UpdateSwapFeeConfig {
+        fee_admin: None,
+        enable_swap_fee: None,
+        swap_percent_fee: None,
+        fee_receiver: Some(deployment.contract("luart_fee_distributor")?.to_string()),
+    };
+    chain.execute(deployment.contract("luart_token")?, &msg).await?;
+    println!("Registered the fee distributor as the token fee receiver");
+    deployment.wired.push(step.to_string());
+    deployment.save()
+}
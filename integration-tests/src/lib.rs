@@ -0,0 +1,7 @@
+//! Runs the Luart contracts together in cw-multi-test to cover the flows chaining several
+//! contracts through messages, submessages and cross-contract queries. The staking and
+//! marketplace contracts live outside this repository, they are stood in by mocks
+//! implementing their `luart-protocol` interface.
+
+#[cfg(test)]
+mod testing;
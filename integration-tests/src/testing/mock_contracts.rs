@@ -0,0 +1,192 @@
+//! Stand-ins for the contracts deployed outside this repository, implementing only the part
+//! of their interface the Luart contracts rely on.
+
+pub mod staking {
+    use cosmwasm_std::{
+        Addr, Binary, Deps, DepsMut, Env, from_binary, MessageInfo, Response, StdError, StdResult,
+        Storage, to_binary, Uint128,
+    };
+    use cw20::Cw20ReceiveMsg;
+    use cw_storage_plus::{Item, Map};
+    use luart_protocol::staking::{
+        Cw20HookMsg, QueryMsg, StakerInfoResponse, TierResponse, TotalStakedResponse,
+    };
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    pub struct InstantiateMsg {
+        pub token: String,
+        /// The minimum stake of each tier starting at tier 1
+        pub tier_thresholds: Vec<Uint128>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ExecuteMsg {
+        Receive(Cw20ReceiveMsg),
+    }
+
+    const TOKEN: Item<Addr> = Item::new("token");
+    const TIER_THRESHOLDS: Item<Vec<Uint128>> = Item::new("tier_thresholds");
+    /// The stake history of each staker as (time, amount) checkpoints
+    const STAKES: Map<&Addr, Vec<(u64, Uint128)>> = Map::new("stakes");
+    const TOTAL_STAKED: Item<Vec<(u64, Uint128)>> = Item::new("total_staked");
+
+    pub fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+        TOKEN.save(deps.storage, &deps.api.addr_validate(&msg.token)?)?;
+        TIER_THRESHOLDS.save(deps.storage, &msg.tier_thresholds)?;
+        TOTAL_STAKED.save(deps.storage, &vec![])?;
+        Ok(Response::default())
+    }
+
+    pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::Receive(cw20_msg) => {
+                if info.sender != TOKEN.load(deps.storage)? {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                let Cw20HookMsg::Bond { on_behalf_of } = from_binary(&cw20_msg.msg)?;
+                let staker = deps.api.addr_validate(&on_behalf_of.unwrap_or(cw20_msg.sender))?;
+                bond(deps.storage, env.block.time.seconds(), &staker, cw20_msg.amount)?;
+                Ok(Response::new().add_attribute("action", "bond"))
+            }
+        }
+    }
+
+    fn bond(storage: &mut dyn Storage, time: u64, staker: &Addr, amount: Uint128) -> StdResult<()> {
+        let mut stakes = STAKES.may_load(storage, staker)?.unwrap_or_default();
+        let stake = stake_at(&stakes, time) + amount;
+        stakes.push((time, stake));
+        STAKES.save(storage, staker, &stakes)?;
+
+        let mut total = TOTAL_STAKED.load(storage)?;
+        let total_staked = stake_at(&total, time) + amount;
+        total.push((time, total_staked));
+        TOTAL_STAKED.save(storage, &total)
+    }
+
+    fn stake_at(history: &[(u64, Uint128)], time: u64) -> Uint128 {
+        history.iter()
+            .rev()
+            .find(|(t, _)| *t <= time)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()
+    }
+
+    fn query_stake(deps: Deps, staker: &str, time: u64) -> StdResult<Uint128> {
+        let stakes = STAKES.may_load(deps.storage, &deps.api.addr_validate(staker)?)?.unwrap_or_default();
+        Ok(stake_at(&stakes, time))
+    }
+
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        let now = env.block.time.seconds();
+        match msg {
+            QueryMsg::StakerInfo { staker } => to_binary(&StakerInfoResponse {
+                bond_amount: query_stake(deps, &staker, now)?,
+                staker,
+            }),
+            QueryMsg::StakerInfoAt { staker, time } => to_binary(&StakerInfoResponse {
+                bond_amount: query_stake(deps, &staker, time)?,
+                staker,
+            }),
+            QueryMsg::TotalStakedAt { time } => to_binary(&TotalStakedResponse {
+                total_staked: stake_at(&TOTAL_STAKED.load(deps.storage)?, time),
+            }),
+            QueryMsg::Tier { address } => {
+                let stake = query_stake(deps, &address, now)?;
+                let tier = TIER_THRESHOLDS.load(deps.storage)?
+                    .iter()
+                    .filter(|threshold| stake >= **threshold)
+                    .count();
+                to_binary(&TierResponse { tier: tier as u8 })
+            }
+        }
+    }
+}
+
+pub mod marketplace {
+    use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, to_binary};
+    use cw_storage_plus::Map;
+    use luart_protocol::marketplace::{ListingCountResponse, QueryMsg};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ExecuteMsg {
+        /// Lists an NFT of the sender, the NFT itself is left out of the mock
+        List {},
+    }
+
+    const LISTING_COUNTS: Map<&str, u64> = Map::new("listing_counts");
+
+    pub fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    pub fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::List {} => {
+                LISTING_COUNTS.update(deps.storage, info.sender.as_str(), |count| -> StdResult<_> {
+                    Ok(count.unwrap_or_default() + 1)
+                })?;
+                Ok(Response::new().add_attribute("action", "list"))
+            }
+        }
+    }
+
+    pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::ListingCount { seller } => to_binary(&ListingCountResponse {
+                count: LISTING_COUNTS.may_load(deps.storage, &seller)?.unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+pub mod malicious_pair {
+    use cosmwasm_std::{
+        Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, WasmMsg,
+    };
+    use cw20::Cw20ReceiveMsg;
+    use cw_storage_plus::Item;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    pub struct InstantiateMsg {
+        /// The message executed on the token notifying the pair
+        pub reentry: Binary,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ExecuteMsg {
+        Receive(Cw20ReceiveMsg),
+    }
+
+    const REENTRY: Item<Binary> = Item::new("reentry");
+
+    pub fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+        REENTRY.save(deps.storage, &msg.reentry)?;
+        Ok(Response::default())
+    }
+
+    /// Calls back into the token instead of swapping the tokens it is notified of
+    pub fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::Receive(_) => Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: info.sender.to_string(),
+                    msg: REENTRY.load(deps.storage)?,
+                    funds: vec![],
+                })
+                .add_attribute("action", "reenter")),
+        }
+    }
+
+    pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("The malicious pair has no queries"))
+    }
+}
@@ -0,0 +1,3 @@
+mod mock_contracts;
+mod suite;
+mod tests;
@@ -0,0 +1,208 @@
+use cosmwasm_std::{Addr, Binary, coins, Decimal, Empty, to_binary, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use luart_protocol::staking::Cw20HookMsg as StakingHookMsg;
+use terraswap::asset::AssetInfo;
+
+use crate::testing::mock_contracts::{malicious_pair, marketplace, staking};
+
+pub const OWNER: &str = "owner";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+pub const TREASURY: &str = "treasury";
+pub const DENOM: &str = "uusd";
+
+/// The initial native balance of the users
+pub const NATIVE_BALANCE: u128 = 1_000;
+/// The initial native balance of the owner, funding the pairs
+pub const OWNER_NATIVE_BALANCE: u128 = 1_000_000;
+
+pub fn mock_app() -> App {
+    App::new(|router, _, storage| {
+        router.bank.init_balance(storage, &Addr::unchecked(OWNER), coins(OWNER_NATIVE_BALANCE, DENOM)).unwrap();
+        for user in [ALICE, BOB, CAROL] {
+            router.bank.init_balance(storage, &Addr::unchecked(user), coins(NATIVE_BALANCE, DENOM)).unwrap();
+        }
+    })
+}
+
+fn contract_token() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        luart_token::contract::execute,
+        luart_token::contract::instantiate,
+        luart_token::contract::query,
+    ).with_reply(luart_token::contract::reply))
+}
+
+fn contract_airdrop() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        luart_airdrop::contract::execute,
+        luart_airdrop::contract::instantiate,
+        luart_airdrop::contract::query,
+    ))
+}
+
+fn contract_launchpad() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        luart_launchpad::contract::execute,
+        luart_launchpad::contract::instantiate,
+        luart_launchpad::contract::query,
+    ))
+}
+
+fn contract_fee_distributor() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        luart_fee_distributor::contract::execute,
+        luart_fee_distributor::contract::instantiate,
+        luart_fee_distributor::contract::query,
+    ))
+}
+
+fn contract_staking() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(staking::execute, staking::instantiate, staking::query))
+}
+
+fn contract_marketplace() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(marketplace::execute, marketplace::instantiate, marketplace::query))
+}
+
+fn contract_pair() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        luart_mock_pair::contract::execute,
+        luart_mock_pair::contract::instantiate,
+        luart_mock_pair::contract::query,
+    ))
+}
+
+fn contract_malicious_pair() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(malicious_pair::execute, malicious_pair::instantiate, malicious_pair::query))
+}
+
+/// The LUART token, the owner holds the initial supply
+pub fn instantiate_token(app: &mut App, initial_balances: &[(&str, u128)]) -> Addr {
+    let code_id = app.store_code(contract_token());
+    let msg = luart_token::msg::InstantiateMsg {
+        name: "Luart".to_string(),
+        symbol: "LUART".to_string(),
+        decimals: 6,
+        initial_balances: initial_balances.iter()
+            .map(|(address, amount)| Cw20Coin { address: address.to_string(), amount: Uint128::new(*amount) })
+            .collect(),
+        mint: None,
+        swap_fee_config: Some(luart_token::msg::SwapFeeConfigResponse {
+            fee_admin: OWNER.to_string(),
+            enable_swap_fee: true,
+            swap_percent_fee: "2".parse().unwrap(),
+            fee_receiver: OWNER.to_string(),
+        }),
+        pauser: None,
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "luart", None).unwrap()
+}
+
+/// The staking stand-in with the given tier thresholds
+pub fn instantiate_staking(app: &mut App, token: &Addr, tier_thresholds: &[u128]) -> Addr {
+    let code_id = app.store_code(contract_staking());
+    let msg = staking::InstantiateMsg {
+        token: token.to_string(),
+        tier_thresholds: tier_thresholds.iter().map(|t| Uint128::new(*t)).collect(),
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "staking", None).unwrap()
+}
+
+pub fn instantiate_marketplace(app: &mut App) -> Addr {
+    let code_id = app.store_code(contract_marketplace());
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &Empty {}, &[], "marketplace", None).unwrap()
+}
+
+/// A token/native pair funded by the owner with the native side of the swaps
+pub fn instantiate_pair(app: &mut App, token: &Addr, price: Decimal, native_liquidity: u128) -> Addr {
+    let code_id = app.store_code(contract_pair());
+    let msg = luart_mock_pair::msg::InstantiateMsg {
+        owner: None,
+        asset_infos: [
+            AssetInfo::Token { contract_addr: token.to_string() },
+            AssetInfo::NativeToken { denom: DENOM.to_string() },
+        ],
+        price,
+        commission_rate: None,
+    };
+    let funds = coins(native_liquidity, DENOM);
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &funds, "pair", None).unwrap()
+}
+
+/// A pair executing the reentry message on the token notifying it of a swap
+pub fn instantiate_malicious_pair(app: &mut App, reentry: Binary) -> Addr {
+    let code_id = app.store_code(contract_malicious_pair());
+    let msg = malicious_pair::InstantiateMsg { reentry };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "malicious_pair", None).unwrap()
+}
+
+pub fn instantiate_airdrop(app: &mut App, token: &Addr) -> Addr {
+    let code_id = app.store_code(contract_airdrop());
+    let msg = luart_airdrop::msg::InstantiateMsg {
+        owner: None,
+        cw20_token_address: token.to_string(),
+        treasury: Some(TREASURY.to_string()),
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "airdrop", None).unwrap()
+}
+
+pub fn instantiate_launchpad(app: &mut App, staking: &Addr) -> Addr {
+    let code_id = app.store_code(contract_launchpad());
+    let msg = luart_launchpad::msg::InstantiateMsg {
+        owner: None,
+        staking_contract: staking.to_string(),
+        payment_denom: DENOM.to_string(),
+        treasury: TREASURY.to_string(),
+        registry: None,
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "launchpad", None).unwrap()
+}
+
+pub fn instantiate_fee_distributor(app: &mut App, staking: &Addr, token: &Addr) -> Addr {
+    let code_id = app.store_code(contract_fee_distributor());
+    let msg = luart_fee_distributor::msg::InstantiateMsg {
+        owner: None,
+        staking: staking.to_string(),
+        assets: vec![AssetInfo::Token { contract_addr: token.to_string() }],
+        start_time: None,
+        registry: None,
+        token: None,
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "fee_distributor", None).unwrap()
+}
+
+/// Bonds the tokens of the staker through the token send hook
+pub fn stake(app: &mut App, token: &Addr, staking: &Addr, staker: &str, amount: u128) -> AppResponse {
+    let msg = Cw20ExecuteMsg::Send {
+        contract: staking.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&StakingHookMsg::Bond { on_behalf_of: None }).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(staker), token.clone(), &msg, &[]).unwrap()
+}
+
+pub fn transfer(app: &mut App, token: &Addr, sender: &str, recipient: &Addr, amount: u128) -> AppResponse {
+    let msg = Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) };
+    app.execute_contract(Addr::unchecked(sender), token.clone(), &msg, &[]).unwrap()
+}
+
+pub fn token_balance(app: &App, token: &Addr, address: &str) -> u128 {
+    let res: BalanceResponse = app.wrap()
+        .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: address.to_string() })
+        .unwrap();
+    res.balance.u128()
+}
+
+pub fn native_balance(app: &App, address: &str) -> u128 {
+    app.wrap().query_balance(address, DENOM).unwrap().amount.u128()
+}
+
+pub fn advance_time(app: &mut App, seconds: u64) {
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(seconds);
+        block.height += seconds / 5;
+    });
+}
@@ -0,0 +1,228 @@
+use cosmwasm_std::{Addr, coins, Decimal, StdError, to_binary, Uint128};
+use cw20::Cw20ExecuteMsg;
+use cw_multi_test::Executor;
+use luart_airdrop::state::{Boost, BoostCondition};
+use luart_launchpad::msg::PhaseConfig;
+use luart_launchpad::state::PhaseKind;
+use luart_protocol::staking::{QueryMsg as StakingQueryMsg, TierResponse};
+use luart_protocol::token::ExecuteMsg as TokenExecuteMsg;
+use luart_token::msg::ExecuteMsg as TokenContractMsg;
+use sha2::Digest;
+
+use crate::testing::mock_contracts::marketplace;
+use crate::testing::suite::{
+    ALICE, advance_time, BOB, CAROL, DENOM, instantiate_airdrop, instantiate_fee_distributor,
+    instantiate_launchpad, instantiate_malicious_pair, instantiate_marketplace, instantiate_pair,
+    instantiate_staking, instantiate_token, mock_app, native_balance, NATIVE_BALANCE, OWNER, stake,
+    token_balance, transfer,
+};
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(sha2::Sha256::digest(data).as_slice());
+    hash
+}
+
+#[test]
+fn test_stake_tier_launchpad() {
+    let mut app = mock_app();
+    let token = instantiate_token(&mut app, &[(OWNER, 100_000), (ALICE, 1_000), (BOB, 100)]);
+    let staking = instantiate_staking(&mut app, &token, &[100, 1_000]);
+    let launchpad = instantiate_launchpad(&mut app, &staking);
+
+    // The staked amounts set the tiers read by the launchpad
+    stake(&mut app, &token, &staking, ALICE, 1_000);
+    stake(&mut app, &token, &staking, BOB, 100);
+    let tier = |app: &cw_multi_test::App, address: &str| -> u8 {
+        let res: TierResponse = app.wrap()
+            .query_wasm_smart(&staking, &StakingQueryMsg::Tier { address: address.to_string() })
+            .unwrap();
+        res.tier
+    };
+    assert_eq!((tier(&app, ALICE), tier(&app, BOB), tier(&app, CAROL)), (2, 1, 0));
+
+    let now = app.block_info().time.seconds();
+    let create_sale = Cw20ExecuteMsg::Send {
+        contract: launchpad.to_string(),
+        amount: Uint128::new(10_000),
+        msg: to_binary(&luart_launchpad::msg::Cw20HookMsg::CreateSale {
+            rate: Decimal::from_ratio(10u128, 1u128),
+            phases: vec![PhaseConfig {
+                kind: PhaseKind::Guaranteed,
+                start: now + 100,
+                end: now + 1_100,
+                cap: Uint128::new(1_000),
+            }],
+            whitelist_allocation: Uint128::zero(),
+            fcfs_allocation: Uint128::zero(),
+            tier_allocations: vec![Uint128::new(100), Uint128::new(500)],
+            kyc_tier: None,
+            deposit_weights: vec![],
+        }).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(OWNER), token.clone(), &create_sale, &[]).unwrap();
+    advance_time(&mut app, 100);
+
+    // The contribution above the tier allocation is refunded in the same transaction
+    let contribute = luart_launchpad::msg::ExecuteMsg::Contribute { sale_id: 1, attestation: None };
+    app.execute_contract(Addr::unchecked(ALICE), launchpad.clone(), &contribute, &coins(600, DENOM)).unwrap();
+    assert_eq!(native_balance(&app, ALICE), NATIVE_BALANCE - 500);
+    app.execute_contract(Addr::unchecked(BOB), launchpad.clone(), &contribute, &coins(100, DENOM)).unwrap();
+    let err = app.execute_contract(Addr::unchecked(CAROL), launchpad.clone(), &contribute, &coins(100, DENOM))
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), luart_launchpad::error::ContractError::NoAllocation {}.to_string());
+    assert_eq!(native_balance(&app, CAROL), NATIVE_BALANCE);
+
+    advance_time(&mut app, 1_000);
+    let claim = luart_launchpad::msg::ExecuteMsg::Claim { sale_id: 1 };
+    app.execute_contract(Addr::unchecked(ALICE), launchpad.clone(), &claim, &[]).unwrap();
+    app.execute_contract(Addr::unchecked(BOB), launchpad, &claim, &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, ALICE), 5_000);
+    assert_eq!(token_balance(&app, &token, BOB), 1_000);
+}
+
+#[test]
+fn test_swap_fee_distribution() {
+    let mut app = mock_app();
+    let token = instantiate_token(&mut app, &[(ALICE, 300), (BOB, 100), (CAROL, 10_000)]);
+    let staking = instantiate_staking(&mut app, &token, &[]);
+    let fee_distributor = instantiate_fee_distributor(&mut app, &staking, &token);
+    let pair = instantiate_pair(&mut app, &token, Decimal::permille(100), 10_000);
+
+    // The swap fees are routed to the distributor
+    let update_fee_receiver = TokenExecuteMsg::UpdateSwapFeeConfig {
+        fee_admin: None,
+        enable_swap_fee: None,
+        swap_percent_fee: None,
+        fee_receiver: Some(fee_distributor.to_string()),
+    };
+    app.execute_contract(Addr::unchecked(OWNER), token.clone(), &update_fee_receiver, &[]).unwrap();
+    stake(&mut app, &token, &staking, ALICE, 300);
+    stake(&mut app, &token, &staking, BOB, 100);
+
+    // Carol pays the 2% swap fee when swapping through the pair, at 0.1 uusd per token
+    let swap = Cw20ExecuteMsg::Send {
+        contract: pair.to_string(),
+        amount: Uint128::new(10_000),
+        msg: to_binary(&terraswap::pair::Cw20HookMsg::Swap {
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        }).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(CAROL), token.clone(), &swap, &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, pair.as_str()), 9_800);
+    assert_eq!(token_balance(&app, &token, fee_distributor.as_str()), 200);
+    assert_eq!(native_balance(&app, CAROL), NATIVE_BALANCE + 980);
+
+    // Bonding is not a swap and pays no fee
+    assert_eq!(token_balance(&app, &token, staking.as_str()), 400);
+
+    let checkpoint = luart_fee_distributor::msg::ExecuteMsg::Checkpoint {};
+    app.execute_contract(Addr::unchecked(CAROL), fee_distributor.clone(), &checkpoint, &[]).unwrap();
+    let claim = luart_fee_distributor::msg::ExecuteMsg::Claim {};
+    let err = app.execute_contract(Addr::unchecked(ALICE), fee_distributor.clone(), &claim, &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(),
+               luart_fee_distributor::error::ContractError::NothingToClaim {}.to_string());
+
+    // The epoch fees are shared by stake once the epoch has ended
+    advance_time(&mut app, luart_fee_distributor::state::EPOCH_LENGTH);
+    app.execute_contract(Addr::unchecked(ALICE), fee_distributor.clone(), &claim, &[]).unwrap();
+    app.execute_contract(Addr::unchecked(BOB), fee_distributor.clone(), &claim, &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, ALICE), 150);
+    assert_eq!(token_balance(&app, &token, BOB), 50);
+    assert_eq!(token_balance(&app, &token, fee_distributor.as_str()), 0);
+}
+
+#[test]
+fn test_boosted_airdrop() {
+    let mut app = mock_app();
+    let token = instantiate_token(&mut app, &[(OWNER, 10_000), (ALICE, 100)]);
+    let staking = instantiate_staking(&mut app, &token, &[]);
+    let marketplace_addr = instantiate_marketplace(&mut app);
+    let airdrop = instantiate_airdrop(&mut app, &token);
+    transfer(&mut app, &token, OWNER, &airdrop, 3_000);
+
+    // Alice and Bob are both allocated 1000 tokens
+    let alice_leaf = sha256(format!("{}{}", ALICE, 1_000).as_bytes());
+    let bob_leaf = sha256(format!("{}{}", BOB, 1_000).as_bytes());
+    let mut leaves = [alice_leaf, bob_leaf];
+    leaves.sort_unstable();
+    let register = luart_airdrop::msg::ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: hex::encode(sha256(&leaves.concat())),
+        expiration: None,
+        total_amount: Uint128::new(2_000),
+    };
+    app.execute_contract(Addr::unchecked(OWNER), airdrop.clone(), &register, &[]).unwrap();
+    let boosts = luart_airdrop::msg::ExecuteMsg::UpdateStageBoosts {
+        stage: 1,
+        boosts: vec![
+            Boost {
+                condition: BoostCondition::Staked { contract: staking.clone(), min_amount: Uint128::new(100) },
+                multiplier: Decimal::percent(150),
+            },
+            Boost {
+                condition: BoostCondition::ListedNft { contract: marketplace_addr.clone() },
+                multiplier: Decimal::percent(120),
+            },
+        ],
+        boost_amount: Uint128::new(1_000),
+    };
+    app.execute_contract(Addr::unchecked(OWNER), airdrop.clone(), &boosts, &[]).unwrap();
+
+    // The boosts are read from the staking and marketplace contracts at claim time
+    stake(&mut app, &token, &staking, ALICE, 100);
+    app.execute_contract(Addr::unchecked(BOB), marketplace_addr, &marketplace::ExecuteMsg::List {}, &[]).unwrap();
+
+    let claim = |amount: u128, sibling: [u8; 32]| luart_airdrop::msg::ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::new(amount),
+        proof: vec![hex::encode(sibling)],
+    };
+    app.execute_contract(Addr::unchecked(ALICE), airdrop.clone(), &claim(1_000, bob_leaf), &[]).unwrap();
+    app.execute_contract(Addr::unchecked(BOB), airdrop.clone(), &claim(1_000, alice_leaf), &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, ALICE), 1_500);
+    assert_eq!(token_balance(&app, &token, BOB), 1_200);
+    assert_eq!(token_balance(&app, &token, airdrop.as_str()), 300);
+}
+
+#[test]
+fn test_scheduled_swap_reentrancy() {
+    let mut app = mock_app();
+    let token = instantiate_token(&mut app, &[(ALICE, 10_000)]);
+    let pair = instantiate_pair(&mut app, &token, Decimal::permille(100), 10_000);
+    let reentry = to_binary(&TokenContractMsg::ExecuteScheduledSwap { swap_id: 3 }).unwrap();
+    let malicious_pair = instantiate_malicious_pair(&mut app, reentry);
+
+    let schedule_swap = |pair: &Addr, total_amount: u128, intervals: u32| TokenContractMsg::ScheduleSwap {
+        pair: pair.to_string(),
+        total_amount: Uint128::new(total_amount),
+        intervals,
+        belief_price: Decimal::percent(1_000),
+        max_spread: Some(Decimal::percent(1)),
+    };
+    let execute = |swap_id: u64| TokenContractMsg::ExecuteScheduledSwap { swap_id };
+
+    // The reply of each swap exits the guard, the next swap runs in a later block
+    app.execute_contract(Addr::unchecked(ALICE), token.clone(), &schedule_swap(&pair, 1_000, 2), &[]).unwrap();
+    app.execute_contract(Addr::unchecked(BOB), token.clone(), &execute(1), &[]).unwrap();
+    advance_time(&mut app, luart_token::state::SCHEDULED_SWAP_INTERVAL);
+    app.execute_contract(Addr::unchecked(BOB), token.clone(), &execute(1), &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, pair.as_str()), 980);
+    assert_eq!(native_balance(&app, ALICE), NATIVE_BALANCE + 98);
+
+    // The pair notified of a swap cannot execute another scheduled swap, the whole swap fails
+    app.execute_contract(Addr::unchecked(ALICE), token.clone(), &schedule_swap(&malicious_pair, 500, 1), &[])
+        .unwrap();
+    app.execute_contract(Addr::unchecked(ALICE), token.clone(), &schedule_swap(&malicious_pair, 500, 1), &[])
+        .unwrap();
+    let err = app.execute_contract(Addr::unchecked(BOB), token.clone(), &execute(2), &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), StdError::generic_err("Reentrant call").to_string());
+    assert_eq!(token_balance(&app, &token, malicious_pair.as_str()), 0);
+
+    // The failed swap left the guard exited
+    let cancel = |swap_id: u64| TokenContractMsg::CancelScheduledSwap { swap_id };
+    app.execute_contract(Addr::unchecked(ALICE), token.clone(), &cancel(2), &[]).unwrap();
+    app.execute_contract(Addr::unchecked(ALICE), token.clone(), &cancel(3), &[]).unwrap();
+    assert_eq!(token_balance(&app, &token, ALICE), 9_000);
+}
@@ -0,0 +1,12 @@
+use luart_types::asset::Asset;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The parameters of a new merkle distributor contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The address registering the rounds, defaults to the sender
+    pub owner: Option<String>,
+    /// The native coin or cw20 token distributed
+    pub asset: Asset,
+}
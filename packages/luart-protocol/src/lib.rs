@@ -0,0 +1,12 @@
+//! The messages sent to and the responses returned by the Luart contracts, as seen by the
+//! contracts calling them. Each module covers the part of a contract interface used by the
+//! other contracts, so that callers share one definition instead of their own copies.
+
+pub mod distributor;
+pub mod governance;
+pub mod marketplace;
+pub mod pausable;
+pub mod registry;
+pub mod staking;
+pub mod stats;
+pub mod token;
@@ -0,0 +1,23 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The names the protocol contracts are registered under
+pub const TOKEN: &str = "token";
+pub const STAKING: &str = "staking";
+pub const MARKETPLACE: &str = "marketplace";
+pub const TREASURY: &str = "treasury";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the address registered under the name, fails if there is none.
+    /// Return type: AddressResponse.
+    Address { name: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AddressResponse {
+    pub name: String,
+    pub address: Addr,
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Sent along the LUART tokens to stake
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bonds the tokens for the sender, or for the given address
+    Bond { on_behalf_of: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Unbonds the tokens of the sender, or of the address the sender bonded for
+    Unbond {
+        amount: Uint128,
+        on_behalf_of: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the current stake of the staker.
+    /// Return type: StakerInfoResponse.
+    StakerInfo { staker: String },
+    /// Returns the stake of the staker at the given time.
+    /// Return type: StakerInfoResponse.
+    StakerInfoAt { staker: String, time: u64 },
+    /// Returns the total stake at the given time.
+    /// Return type: TotalStakedResponse.
+    TotalStakedAt { time: u64 },
+    /// Returns the staking tier of the address.
+    /// Return type: TierResponse.
+    Tier { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfoResponse {
+    pub staker: String,
+    pub bond_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalStakedResponse {
+    pub total_staked: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TierResponse {
+    /// Staking tier of the address, 0 if it has no tier
+    pub tier: u8,
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// An activity reported to the stats contract. The amounts are counted in uusd.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatEvent {
+    /// A sale was settled for the amount, adding to the volume
+    Sale { amount: Uint128 },
+    /// The protocol collected fees
+    Fee { amount: Uint128 },
+    /// Value was locked in the protocol, adding to the TVL
+    Lock { amount: Uint128 },
+    /// Value left the protocol, subtracting from the TVL
+    Unlock { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Records the events in the current day. Only the reporters can execute it.
+    Record { events: Vec<StatEvent> },
+}
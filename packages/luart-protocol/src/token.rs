@@ -0,0 +1,59 @@
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::{Cw20Coin, MinterResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use cw20::BalanceResponse;
+
+/// The parameters of a new token contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+    /// The token is created without swap fee when unset
+    pub swap_fee_config: Option<SwapFeeConfig>,
+    /// The address who can pause the token transfers
+    pub pauser: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapFeeConfig {
+    pub fee_admin: String,
+    pub enable_swap_fee: bool,
+    pub swap_percent_fee: Decimal,
+    pub fee_receiver: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Only the fee admin can update the swap fee configuration
+    UpdateSwapFeeConfig {
+        fee_admin: Option<String>,
+        enable_swap_fee: Option<bool>,
+        swap_percent_fee: Option<Decimal>,
+        fee_receiver: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the balance of the given address at the start of the given height.
+    /// Return type: BalanceResponse.
+    BalanceAt { address: String, height: u64 },
+    /// Returns the total supply at the start of the given height.
+    /// Return type: TotalSupplyResponse.
+    TotalSupplyAt { height: u64 },
+    /// Returns the swap fee taken out of the sends to a pair.
+    /// Return type: SwapFeeConfig.
+    SwapFeeConfig {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use cosmwasm_std::{
+    Addr, BankMsg, BankQuery, BalanceResponse, Binary, Coin, CosmosMsg, Decimal, QuerierWrapper,
+    QueryRequest, StdResult, to_binary, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use terra_cosmwasm::TerraQuerier;
+
+/// The native denom exempt from the Terra tax
+const TAX_EXEMPT_DENOM: &str = "uluna";
+const DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+/// A native coin or a cw20 token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Asset {
+    Native(String),
+    Cw20(Addr),
+}
+
+impl Asset {
+    /// The denom or the token address, to key the storage by asset
+    pub fn key(&self) -> &str {
+        match self {
+            Asset::Native(denom) => denom,
+            Asset::Cw20(contract_addr) => contract_addr.as_str(),
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, Asset::Native(_))
+    }
+
+    /// Transfers the amount, the sender pays the tax of native coins on top of it
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            Asset::Native(denom) => CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }),
+            Asset::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }),
+        })
+    }
+
+    /// Sends the amount to a contract and triggers the message on it, the message is
+    /// executed directly with native coins and through the receive hook with cw20 tokens
+    pub fn send_msg(&self, contract: &Addr, amount: Uint128, msg: Binary) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            Asset::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg,
+                funds: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }),
+            Asset::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: contract.to_string(),
+                    amount,
+                    msg,
+                })?,
+                funds: vec![],
+            }),
+        })
+    }
+
+    pub fn query_balance(&self, querier: &QuerierWrapper, address: &Addr) -> StdResult<Uint128> {
+        Ok(match self {
+            Asset::Native(denom) => {
+                let res: BalanceResponse = querier.query(&QueryRequest::Bank(BankQuery::Balance {
+                    address: address.to_string(),
+                    denom: denom.clone(),
+                }))?;
+                res.amount.amount
+            }
+            Asset::Cw20(contract_addr) => {
+                let res: Cw20BalanceResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20QueryMsg::Balance { address: address.to_string() })?,
+                }))?;
+                res.balance
+            }
+        })
+    }
+
+    /// The tax taken out of a native amount sent, capped by the tax cap of the denom.
+    /// Cw20 tokens and LUNA are not taxed.
+    pub fn compute_tax(&self, querier: &QuerierWrapper, amount: Uint128) -> StdResult<Uint128> {
+        let denom = match self {
+            Asset::Native(denom) if denom != TAX_EXEMPT_DENOM => denom,
+            _ => return Ok(Uint128::zero()),
+        };
+
+        let terra_querier = TerraQuerier::new(querier);
+        let tax_rate: Decimal = terra_querier.query_tax_rate()?.rate;
+        let tax_cap: Uint128 = terra_querier.query_tax_cap(denom)?.cap;
+        // The sent amount and its tax add up to the amount
+        let sent = amount.multiply_ratio(DECIMAL_FRACTION, DECIMAL_FRACTION * tax_rate + DECIMAL_FRACTION);
+        Ok(amount.checked_sub(sent)?.min(tax_cap))
+    }
+
+    /// The amount left to send once the tax is paid out of the amount
+    pub fn deduct_tax(&self, querier: &QuerierWrapper, amount: Uint128) -> StdResult<Uint128> {
+        Ok(amount.checked_sub(self.compute_tax(querier, amount)?)?)
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod asset;
+
+#[cfg(test)]
+mod testing;
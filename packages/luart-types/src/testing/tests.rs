@@ -0,0 +1,80 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, coins, ContractResult, CosmosMsg, Decimal, QuerierWrapper, SystemResult,
+    to_binary, Uint128, WasmMsg,
+};
+use cosmwasm_std::testing::MockQuerier;
+use cw20::Cw20ExecuteMsg;
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper};
+
+use crate::asset::Asset;
+
+fn terra_querier(tax_cap: u128) -> MockQuerier<TerraQueryWrapper> {
+    let querier = MockQuerier::new(&[("holder", &coins(1_000, "uusd"))]);
+    querier.with_custom_handler(move |query: &TerraQueryWrapper| {
+        let res = match query.query_data {
+            TerraQuery::TaxRate {} => to_binary(&TaxRateResponse { rate: Decimal::permille(5) }),
+            TerraQuery::TaxCap { .. } => to_binary(&TaxCapResponse { cap: Uint128::new(tax_cap) }),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    })
+}
+
+#[test]
+fn test_transfer_msgs() {
+    let recipient = Addr::unchecked("recipient");
+    let native = Asset::Native("uusd".to_string());
+    let token = Asset::Cw20(Addr::unchecked("mock_token"));
+    assert_eq!(native.key(), "uusd");
+    assert_eq!(token.to_string(), "mock_token");
+
+    assert_eq!(native.transfer_msg(&recipient, Uint128::new(100)).unwrap(), CosmosMsg::Bank(BankMsg::Send {
+        to_address: "recipient".to_string(),
+        amount: coins(100, "uusd"),
+    }));
+    assert_eq!(token.transfer_msg(&recipient, Uint128::new(100)).unwrap(), CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "mock_token".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "recipient".to_string(), amount: Uint128::new(100) })
+            .unwrap(),
+        funds: vec![],
+    }));
+
+    let hook = Binary::from(b"{}".to_vec());
+    assert_eq!(native.send_msg(&recipient, Uint128::new(100), hook.clone()).unwrap(),
+               CosmosMsg::Wasm(WasmMsg::Execute {
+                   contract_addr: "recipient".to_string(),
+                   msg: hook.clone(),
+                   funds: coins(100, "uusd"),
+               }));
+    assert_eq!(token.send_msg(&recipient, Uint128::new(100), hook.clone()).unwrap(),
+               CosmosMsg::Wasm(WasmMsg::Execute {
+                   contract_addr: "mock_token".to_string(),
+                   msg: to_binary(&Cw20ExecuteMsg::Send {
+                       contract: "recipient".to_string(),
+                       amount: Uint128::new(100),
+                       msg: hook,
+                   }).unwrap(),
+                   funds: vec![],
+               }));
+}
+
+#[test]
+fn test_tax() {
+    let querier = terra_querier(1_000_000);
+    let querier = QuerierWrapper::new(&querier);
+    let native = Asset::Native("uusd".to_string());
+    assert_eq!(native.query_balance(&querier, &Addr::unchecked("holder")).unwrap(), Uint128::new(1_000));
+
+    // Sending the rest pays the 0.5% tax out of the amount
+    assert_eq!(native.compute_tax(&querier, Uint128::new(1_005_000)).unwrap(), Uint128::new(5_000));
+    assert_eq!(native.deduct_tax(&querier, Uint128::new(1_005_000)).unwrap(), Uint128::new(1_000_000));
+    let luna = Asset::Native("uluna".to_string());
+    assert_eq!(luna.deduct_tax(&querier, Uint128::new(1_005_000)).unwrap(), Uint128::new(1_005_000));
+    let token = Asset::Cw20(Addr::unchecked("mock_token"));
+    assert_eq!(token.compute_tax(&querier, Uint128::new(1_005_000)).unwrap(), Uint128::zero());
+
+    // The tax is capped
+    let capped_querier = terra_querier(1_000);
+    let capped_querier = QuerierWrapper::new(&capped_querier);
+    assert_eq!(native.deduct_tax(&capped_querier, Uint128::new(1_005_000)).unwrap(), Uint128::new(1_004_000));
+}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, StdError, StdResult};
+
+/// The maximum number of queries of a batch
+pub const MAX_BATCH_QUERIES: usize = 30;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchResponse {
+    /// The result of each query, in the order of the queries
+    pub results: Vec<Binary>,
+}
+
+/// Runs the queries of a batch with the query entry point of the contract, failing with the
+/// first failing query. A batch holds at most `MAX_BATCH_QUERIES` queries and no batch, so
+/// that a query runs a bounded number of sub-queries.
+pub fn batch_query<Q>(
+    queries: Vec<Q>,
+    is_batch: impl Fn(&Q) -> bool,
+    query: impl Fn(Q) -> StdResult<Binary>,
+) -> StdResult<BatchResponse> {
+    if queries.len() > MAX_BATCH_QUERIES {
+        return Err(StdError::generic_err(format!(
+            "A batch holds at most {} queries",
+            MAX_BATCH_QUERIES
+        )));
+    }
+    if queries.iter().any(is_batch) {
+        return Err(StdError::generic_err("A batch cannot hold batches"));
+    }
+
+    let results: StdResult<Vec<Binary>> = queries.into_iter().map(query).collect();
+    Ok(BatchResponse { results: results? })
+}
@@ -0,0 +1,121 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, CosmosMsg, Order, StdResult, Storage, to_binary, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::{Item, Map};
+
+/// A permissionless maintenance job paying a LUART bounty to the address executing it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KeeperJob {
+    pub bounty: Uint128,
+    /// The minimum time in seconds between two paid runs of the job
+    pub cooldown: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KeeperJobResponse {
+    pub name: String,
+    pub job: KeeperJob,
+    /// The time from which the next run is paid
+    pub next_paid_run: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KeeperJobsResponse {
+    /// The LUART left to pay the bounties
+    pub budget: Uint128,
+    pub jobs: Vec<KeeperJobResponse>,
+}
+
+pub const KEEPER_JOBS: Map<&str, KeeperJob> = Map::new("keeper_jobs");
+/// The time of the last paid run of each job
+pub const KEEPER_LAST_RUNS: Map<&str, u64> = Map::new("keeper_last_runs");
+/// The LUART set aside for the bounties, kept apart from the other funds of the contract
+pub const KEEPER_BUDGET: Item<Uint128> = Item::new("keeper_budget");
+
+/// Registers or replaces the job, or removes it if unset
+pub fn set_job(storage: &mut dyn Storage, name: &str, job: Option<KeeperJob>) -> StdResult<()> {
+    match job {
+        Some(job) => KEEPER_JOBS.save(storage, name, &job),
+        None => {
+            KEEPER_JOBS.remove(storage, name);
+            KEEPER_LAST_RUNS.remove(storage, name);
+            Ok(())
+        }
+    }
+}
+
+pub fn fund_budget(storage: &mut dyn Storage, amount: Uint128) -> StdResult<Uint128> {
+    let budget = query_budget(storage)?.checked_add(amount)?;
+    KEEPER_BUDGET.save(storage, &budget)?;
+    Ok(budget)
+}
+
+pub fn withdraw_budget(storage: &mut dyn Storage, amount: Uint128) -> StdResult<Uint128> {
+    let budget = query_budget(storage)?.checked_sub(amount)?;
+    KEEPER_BUDGET.save(storage, &budget)?;
+    Ok(budget)
+}
+
+pub fn query_budget(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(KEEPER_BUDGET.may_load(storage)?.unwrap_or_default())
+}
+
+/// Records a run of the job and returns the bounty owed to the keeper. The bounty is zero
+/// for unregistered jobs, during the cooldown of the job and once the budget is spent,
+/// the maintenance itself is never blocked.
+pub fn claim_bounty(storage: &mut dyn Storage, name: &str, now: u64) -> StdResult<Uint128> {
+    let job = match KEEPER_JOBS.may_load(storage, name)? {
+        Some(job) => job,
+        None => return Ok(Uint128::zero()),
+    };
+    let budget = query_budget(storage)?;
+    if budget < job.bounty || now < next_paid_run(storage, name, &job)? {
+        return Ok(Uint128::zero());
+    }
+
+    KEEPER_BUDGET.save(storage, &(budget - job.bounty))?;
+    KEEPER_LAST_RUNS.save(storage, name, &now)?;
+    Ok(job.bounty)
+}
+
+/// Returns the LUART transfer paying the bounty, none if the bounty is zero
+pub fn bounty_msg(token: &Addr, keeper: &Addr, bounty: Uint128) -> StdResult<Option<CosmosMsg>> {
+    if bounty.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: keeper.to_string(),
+            amount: bounty,
+        })?,
+        funds: vec![],
+    })))
+}
+
+fn next_paid_run(storage: &dyn Storage, name: &str, job: &KeeperJob) -> StdResult<u64> {
+    Ok(KEEPER_LAST_RUNS
+        .may_load(storage, name)?
+        .map(|last_run| last_run.saturating_add(job.cooldown))
+        .unwrap_or_default())
+}
+
+pub fn query_jobs(storage: &dyn Storage) -> StdResult<KeeperJobsResponse> {
+    let jobs: StdResult<Vec<KeeperJobResponse>> = KEEPER_JOBS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (name, job) = item?;
+            let name = String::from_utf8_lossy(&name).to_string();
+            Ok(KeeperJobResponse {
+                next_paid_run: next_paid_run(storage, &name, &job)?,
+                name,
+                job,
+            })
+        })
+        .collect();
+
+    Ok(KeeperJobsResponse { budget: query_budget(storage)?, jobs: jobs? })
+}
@@ -0,0 +1,9 @@
+pub mod batch;
+pub mod keeper;
+pub mod nonreentrant;
+pub mod ownable;
+pub mod pausable;
+pub mod roles;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,46 @@
+use cosmwasm_std::{ReplyOn, StdError, StdResult, Storage, SubMsg};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+/// Set while a guarded handler waits for the reply of its submessage
+pub const REENTRANCY_GUARD: Item<bool> = Item::new("reentrancy_guard");
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReentrancyError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Reentrant call")]
+    Reentrant {},
+}
+
+pub fn is_entered(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(REENTRANCY_GUARD.may_load(storage)?.unwrap_or(false))
+}
+
+/// Fails within the callbacks of a guarded handler
+pub fn assert_not_entered(storage: &dyn Storage) -> Result<(), ReentrancyError> {
+    if is_entered(storage)? {
+        return Err(ReentrancyError::Reentrant {});
+    }
+
+    Ok(())
+}
+
+/// Enters the guard, the guarded handler dispatches its messages through [`guarded_submsg`]
+pub fn enter(storage: &mut dyn Storage) -> Result<(), ReentrancyError> {
+    assert_not_entered(storage)?;
+    REENTRANCY_GUARD.save(storage, &true)?;
+    Ok(())
+}
+
+/// Exits the guard, called by the reply of the guarded submessage
+pub fn exit(storage: &mut dyn Storage) {
+    REENTRANCY_GUARD.remove(storage);
+}
+
+/// Replies with the given id once the submessage and all its callbacks succeeded. A failed
+/// submessage reverts the whole transaction, the guard entered included.
+pub fn guarded_submsg(msg: SubMsg, reply_id: u64) -> SubMsg {
+    SubMsg { id: reply_id, reply_on: ReplyOn::Success, ..msg }
+}
@@ -0,0 +1,76 @@
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+/// The owner proposed by the current owner, waiting for its acceptance
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
+
+/// Two-phase ownership transfer over the owner stored by a contract: the owner proposes
+/// a new owner who has to accept it, or renounces the ownership for good.
+pub trait Ownable {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>>;
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()>;
+
+    fn assert_owner(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), OwnershipError> {
+        if self.load_owner(storage)?.as_ref() != Some(sender) {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        Ok(())
+    }
+
+    /// Proposes a new owner, replacing the pending one if any
+    fn propose_owner(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        owner: String,
+    ) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.storage, &info.sender)?;
+
+        let owner = deps.api.addr_validate(&owner)?;
+        PENDING_OWNER.save(deps.storage, &owner)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_owner")
+            .add_attribute("pending_owner", owner))
+    }
+
+    /// The pending owner takes the ownership
+    fn accept_ownership(&self, deps: DepsMut, info: MessageInfo) -> Result<Response, OwnershipError> {
+        if PENDING_OWNER.may_load(deps.storage)? != Some(info.sender.clone()) {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        PENDING_OWNER.remove(deps.storage);
+        self.save_owner(deps.storage, Some(info.sender.clone()))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("owner", info.sender))
+    }
+
+    /// Removes the owner and the pending owner, the owner actions are disabled for good
+    fn renounce_ownership(&self, deps: DepsMut, info: MessageInfo) -> Result<Response, OwnershipError> {
+        self.assert_owner(deps.storage, &info.sender)?;
+
+        PENDING_OWNER.remove(deps.storage);
+        self.save_owner(deps.storage, None)?;
+
+        Ok(Response::new().add_attribute("action", "renounce_ownership"))
+    }
+}
+
+pub fn query_pending_owner(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    PENDING_OWNER.may_load(storage)
+}
@@ -0,0 +1,72 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+use crate::roles::has_role;
+
+/// Shares the powers of the pauser of the pause configuration
+pub const PAUSER_ROLE: &str = "pauser";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PauseConfig {
+    /// The address (if any) who can pause and unpause the contract
+    pub pauser: Option<Addr>,
+    pub paused: bool,
+}
+
+pub const PAUSE_CONFIG: Item<PauseConfig> = Item::new("pause_config");
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PauseError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The contract is paused")]
+    Paused {},
+}
+
+/// The pauser of the pause configuration and the pauser role holders
+pub fn is_pauser(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    let is_config_pauser = PAUSE_CONFIG
+        .may_load(storage)?
+        .map(|c| c.pauser.as_ref() == Some(address))
+        .unwrap_or(false);
+    Ok(is_config_pauser || has_role(storage, PAUSER_ROLE, address))
+}
+
+pub fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(PAUSE_CONFIG.may_load(storage)?.map(|c| c.paused).unwrap_or(false))
+}
+
+pub fn assert_not_paused(storage: &dyn Storage) -> Result<(), PauseError> {
+    if is_paused(storage)? {
+        return Err(PauseError::Paused {});
+    }
+
+    Ok(())
+}
+
+/// Sets or removes the pauser, keeping the pause state
+pub fn update_pauser(storage: &mut dyn Storage, pauser: Option<Addr>) -> StdResult<()> {
+    let mut pause_config = PAUSE_CONFIG.may_load(storage)?.unwrap_or_default();
+    pause_config.pauser = pauser;
+    PAUSE_CONFIG.save(storage, &pause_config)
+}
+
+/// Pauses or resumes the contract. Only a pauser can execute it.
+pub fn set_paused(storage: &mut dyn Storage, sender: &Addr, paused: bool) -> Result<(), PauseError> {
+    if !is_pauser(storage, sender)? {
+        return Err(PauseError::Unauthorized {});
+    }
+
+    let mut pause_config = PAUSE_CONFIG.may_load(storage)?.unwrap_or_default();
+    pause_config.paused = paused;
+    PAUSE_CONFIG.save(storage, &pause_config)?;
+    Ok(())
+}
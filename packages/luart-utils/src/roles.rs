@@ -0,0 +1,26 @@
+use cosmwasm_std::{Addr, Empty, StdError, StdResult, Storage};
+use cw_storage_plus::Map;
+
+/// The addresses granted each role, any number of addresses can share a role
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+
+pub fn grant_role(storage: &mut dyn Storage, role: &str, address: &Addr) -> StdResult<()> {
+    ROLES.save(storage, (role, address), &Empty {})
+}
+
+pub fn revoke_role(storage: &mut dyn Storage, role: &str, address: &Addr) {
+    ROLES.remove(storage, (role, address))
+}
+
+pub fn has_role(storage: &dyn Storage, role: &str, address: &Addr) -> bool {
+    ROLES.has(storage, (role, address))
+}
+
+/// Returns an error if the address was not granted the role
+pub fn assert_role(storage: &dyn Storage, role: &str, address: &Addr) -> StdResult<()> {
+    if !has_role(storage, role, address) {
+        return Err(StdError::generic_err(format!("Missing the {} role", role)));
+    }
+
+    Ok(())
+}
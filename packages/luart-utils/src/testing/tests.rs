@@ -0,0 +1,131 @@
+use cosmwasm_std::{Addr, BankMsg, ReplyOn, StdError, StdResult, Storage, SubMsg, to_binary, Uint128};
+use cosmwasm_std::testing::{mock_dependencies, mock_info, MockStorage};
+use cw_storage_plus::Item;
+
+use crate::batch::{batch_query, BatchResponse, MAX_BATCH_QUERIES};
+use crate::keeper::{claim_bounty, fund_budget, KeeperJob, query_jobs, set_job, withdraw_budget};
+use crate::nonreentrant::{assert_not_entered, enter, exit, guarded_submsg, ReentrancyError};
+use crate::ownable::{Ownable, OwnershipError, query_pending_owner};
+use crate::roles::{assert_role, grant_role, has_role, revoke_role};
+
+struct Owner(Item<'static, Option<Addr>>);
+
+impl Ownable for Owner {
+    fn load_owner(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        self.0.load(storage)
+    }
+
+    fn save_owner(&self, storage: &mut dyn Storage, owner: Option<Addr>) -> StdResult<()> {
+        self.0.save(storage, &owner)
+    }
+}
+
+#[test]
+fn test_roles() {
+    let mut storage = MockStorage::new();
+    let admin = Addr::unchecked("mock_admin");
+    let pauser = Addr::unchecked("mock_pauser");
+
+    grant_role(&mut storage, "admin", &admin).unwrap();
+    grant_role(&mut storage, "pauser", &admin).unwrap();
+    grant_role(&mut storage, "pauser", &pauser).unwrap();
+    assert!(has_role(&storage, "admin", &admin));
+    assert!(!has_role(&storage, "admin", &pauser));
+    assert_role(&storage, "pauser", &pauser).unwrap();
+
+    // Revoking a role keeps the other holders and roles
+    revoke_role(&mut storage, "pauser", &admin);
+    assert!(!has_role(&storage, "pauser", &admin));
+    assert!(has_role(&storage, "pauser", &pauser));
+    assert!(has_role(&storage, "admin", &admin));
+    assert_eq!(assert_role(&storage, "pauser", &admin).unwrap_err(),
+               StdError::generic_err("Missing the pauser role"));
+}
+
+#[test]
+fn test_ownable() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = Owner(Item::new("owner"));
+    owner.save_owner(deps.as_mut().storage, Some(Addr::unchecked("mock_owner"))).unwrap();
+
+    let err = owner.propose_owner(deps.as_mut(), mock_info("anyone", &[]), "anyone".to_string()).unwrap_err();
+    assert_eq!(err, OwnershipError::Unauthorized {});
+    owner.propose_owner(deps.as_mut(), mock_info("mock_owner", &[]), "mock_dao".to_string()).unwrap();
+    assert_eq!(query_pending_owner(deps.as_ref().storage).unwrap(), Some(Addr::unchecked("mock_dao")));
+
+    // The owner is kept until the proposed owner accepts
+    let err = owner.accept_ownership(deps.as_mut(), mock_info("anyone", &[])).unwrap_err();
+    assert_eq!(err, OwnershipError::Unauthorized {});
+    assert_eq!(owner.load_owner(deps.as_ref().storage).unwrap(), Some(Addr::unchecked("mock_owner")));
+    owner.accept_ownership(deps.as_mut(), mock_info("mock_dao", &[])).unwrap();
+    assert_eq!(owner.load_owner(deps.as_ref().storage).unwrap(), Some(Addr::unchecked("mock_dao")));
+    assert_eq!(query_pending_owner(deps.as_ref().storage).unwrap(), None);
+
+    owner.renounce_ownership(deps.as_mut(), mock_info("mock_dao", &[])).unwrap();
+    assert_eq!(owner.load_owner(deps.as_ref().storage).unwrap(), None);
+    let err = owner.propose_owner(deps.as_mut(), mock_info("mock_dao", &[]), "mock_dao".to_string()).unwrap_err();
+    assert_eq!(err, OwnershipError::Unauthorized {});
+}
+
+#[test]
+fn test_keeper_bounties() {
+    let mut storage = MockStorage::new();
+    let job = KeeperJob { bounty: Uint128::new(10), cooldown: 100 };
+    set_job(&mut storage, "draw", Some(job.clone())).unwrap();
+
+    // No bounty is paid without a budget or for an unregistered job
+    assert_eq!(claim_bounty(&mut storage, "draw", 1_000).unwrap(), Uint128::zero());
+    fund_budget(&mut storage, Uint128::new(25)).unwrap();
+    assert_eq!(claim_bounty(&mut storage, "settle", 1_000).unwrap(), Uint128::zero());
+
+    // A single run is paid per cooldown
+    assert_eq!(claim_bounty(&mut storage, "draw", 1_000).unwrap(), Uint128::new(10));
+    assert_eq!(claim_bounty(&mut storage, "draw", 1_099).unwrap(), Uint128::zero());
+    assert_eq!(claim_bounty(&mut storage, "draw", 1_100).unwrap(), Uint128::new(10));
+    let res = query_jobs(&storage).unwrap();
+    assert_eq!(res.budget, Uint128::new(5));
+    assert_eq!((res.jobs[0].name.as_str(), res.jobs[0].next_paid_run), ("draw", 1_200));
+
+    // The remaining budget does not cover the bounty
+    assert_eq!(claim_bounty(&mut storage, "draw", 1_200).unwrap(), Uint128::zero());
+    withdraw_budget(&mut storage, Uint128::new(5)).unwrap();
+    withdraw_budget(&mut storage, Uint128::new(1)).unwrap_err();
+
+    set_job(&mut storage, "draw", None).unwrap();
+    assert_eq!(query_jobs(&storage).unwrap().jobs, vec![]);
+}
+
+#[test]
+fn test_batch_query() {
+    let double = |n: u64| if n > 0 { to_binary(&(2 * n)) } else { Err(StdError::generic_err("Zero")) };
+    // Stands for the batch variant of a query message
+    let is_batch = |n: &u64| *n == u64::MAX;
+
+    let res = batch_query(vec![1, 2, 3], is_batch, double).unwrap();
+    assert_eq!(res, BatchResponse {
+        results: vec![to_binary(&2).unwrap(), to_binary(&4).unwrap(), to_binary(&6).unwrap()],
+    });
+
+    // The first failing query fails the batch
+    assert_eq!(batch_query(vec![1, 0], is_batch, double).unwrap_err(), StdError::generic_err("Zero"));
+    assert_eq!(batch_query(vec![1; MAX_BATCH_QUERIES + 1], is_batch, double).unwrap_err(),
+               StdError::generic_err("A batch holds at most 30 queries"));
+    assert_eq!(batch_query(vec![1, u64::MAX], is_batch, double).unwrap_err(),
+               StdError::generic_err("A batch cannot hold batches"));
+}
+
+#[test]
+fn test_nonreentrant() {
+    let mut storage = MockStorage::new();
+    assert_not_entered(&storage).unwrap();
+
+    // The guard stays entered until the reply of the guarded submessage exits it
+    enter(&mut storage).unwrap();
+    assert_eq!(assert_not_entered(&storage).unwrap_err(), ReentrancyError::Reentrant {});
+    assert_eq!(enter(&mut storage).unwrap_err(), ReentrancyError::Reentrant {});
+    exit(&mut storage);
+    enter(&mut storage).unwrap();
+
+    let msg = guarded_submsg(SubMsg::new(BankMsg::Burn { amount: vec![] }), 7);
+    assert_eq!((msg.id, msg.reply_on), (7, ReplyOn::Success));
+}